@@ -0,0 +1,159 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    base_58_encode_with_checksum, hd_wallets::Bip32KeyNetwork, taproot, transactions::ScriptType,
+    Hash160,
+};
+use crate::encoding::bech32::{self, Bech32Variant};
+use alloc::vec::Vec;
+
+// Base58Check version bytes, and segwit human readable parts, for each network.
+pub const MAIN_NET_P2PKH_VERSION: u8 = 0x00;
+pub const TEST_NET_P2PKH_VERSION: u8 = 0x6F;
+pub const MAIN_NET_P2SH_VERSION: u8 = 0x05;
+pub const TEST_NET_P2SH_VERSION: u8 = 0xC4;
+const MAIN_NET_HUMAN_READABLE_PART: &[u8] = b"bc";
+const TEST_NET_HUMAN_READABLE_PART: &[u8] = b"tb";
+
+pub fn p2pkh(public_key: &[u8], network: Bip32KeyNetwork) -> Vec<u16> {
+    // Pay to public key hash; the key may be compressed or uncompressed, and the two produce different addresses.
+    let version = match network {
+        Bip32KeyNetwork::MainNet => MAIN_NET_P2PKH_VERSION,
+        Bip32KeyNetwork::TestNet => TEST_NET_P2PKH_VERSION,
+    };
+
+    base_58_check(version, &Hash160::new().hash_160(public_key))
+}
+
+pub fn p2sh_p2wpkh(compressed_public_key: &[u8; 33], network: Bip32KeyNetwork) -> Vec<u16> {
+    // Nested segwit; pay to the hash of a P2WPKH redeem script, OP_0 PUSH20 <key hash>.
+    let version = match network {
+        Bip32KeyNetwork::MainNet => MAIN_NET_P2SH_VERSION,
+        Bip32KeyNetwork::TestNet => TEST_NET_P2SH_VERSION,
+    };
+
+    let mut hash160 = Hash160::new();
+    let mut redeem_script = [0u8; 22];
+    redeem_script[0] = 0x00;
+    redeem_script[1] = 0x14;
+    redeem_script[2..].copy_from_slice(&hash160.hash_160(compressed_public_key));
+    base_58_check(version, &hash160.hash_160(&redeem_script))
+}
+
+pub fn p2wpkh(compressed_public_key: &[u8; 33], network: Bip32KeyNetwork) -> Vec<u16> {
+    // Native segwit version 0; only compressed keys are standard.
+    segwit(0, &Hash160::new().hash_160(compressed_public_key), network)
+}
+
+pub fn p2tr(internal_key: &[u8; 32], network: Bip32KeyNetwork) -> Option<Vec<u16>> {
+    // Taproot (segwit version 1) with no script tree (BIP 86); the witness program is the tweaked output key.
+    match taproot::try_tweak_public_key(internal_key) {
+        Some(k) => Some(segwit(1, &k, network)),
+        None => None,
+    }
+}
+
+pub fn from_script_pubkey(script_pubkey: &[u8], network: Bip32KeyNetwork) -> Option<Vec<u16>> {
+    // Returns the address an output script pays to; P2PK, null data and non-standard scripts have no address.
+    let (p2pkh_version, p2sh_version) = match network {
+        Bip32KeyNetwork::MainNet => (MAIN_NET_P2PKH_VERSION, MAIN_NET_P2SH_VERSION),
+        Bip32KeyNetwork::TestNet => (TEST_NET_P2PKH_VERSION, TEST_NET_P2SH_VERSION),
+    };
+
+    let mut hash = [0u8; 20];
+    match ScriptType::from_script_pubkey(script_pubkey) {
+        ScriptType::P2pkh => {
+            hash.copy_from_slice(&script_pubkey[3..23]);
+            Some(base_58_check(p2pkh_version, &hash))
+        }
+        ScriptType::P2sh => {
+            hash.copy_from_slice(&script_pubkey[2..22]);
+            Some(base_58_check(p2sh_version, &hash))
+        }
+        ScriptType::P2wpkh | ScriptType::P2wsh => Some(segwit(0, &script_pubkey[2..], network)),
+        ScriptType::P2tr => Some(segwit(1, &script_pubkey[2..], network)),
+        ScriptType::WitnessUnknown(v) => Some(segwit(v, &script_pubkey[2..], network)),
+        ScriptType::P2pk | ScriptType::NullData | ScriptType::NonStandard => None,
+    }
+}
+
+pub fn try_decode_segwit(address: &[u16]) -> Option<(Bip32KeyNetwork, u8, Vec<u8>)> {
+    // Returns the network, witness version and witness program of a valid segwit address (BIP 173 & BIP 350).
+    let (human_readable_part, data, variant) = match bech32::decode(address) {
+        Some(d) => d,
+        None => return None,
+    };
+
+    let network = match &human_readable_part[..] {
+        MAIN_NET_HUMAN_READABLE_PART => Bip32KeyNetwork::MainNet,
+        TEST_NET_HUMAN_READABLE_PART => Bip32KeyNetwork::TestNet,
+        _ => return None,
+    };
+
+    let (witness_version, program) = match data.split_first() {
+        Some((v, p)) if *v <= 16 => (*v, p),
+        _ => return None,
+    };
+
+    let expected_variant = if witness_version == 0 {
+        Bech32Variant::Bech32
+    } else {
+        Bech32Variant::Bech32m
+    };
+
+    let program = match bech32::regroup_bits(program, 5, 8, false) {
+        Some(p) => p,
+        None => return None,
+    };
+
+    // Witness programs are 2-40 bytes, and version 0 programs are either a key hash or a script hash.
+    if variant != expected_variant
+        || program.len() < 2
+        || program.len() > 40
+        || (witness_version == 0 && program.len() != 20 && program.len() != 32)
+    {
+        return None;
+    }
+
+    Some((network, witness_version, program))
+}
+
+fn segwit(witness_version: u8, witness_program: &[u8], network: Bip32KeyNetwork) -> Vec<u16> {
+    // Witness version 0 uses bech32, and later versions use bech32m (BIP 350).
+    let human_readable_part = match network {
+        Bip32KeyNetwork::MainNet => MAIN_NET_HUMAN_READABLE_PART,
+        Bip32KeyNetwork::TestNet => TEST_NET_HUMAN_READABLE_PART,
+    };
+
+    let variant = if witness_version == 0 {
+        Bech32Variant::Bech32
+    } else {
+        Bech32Variant::Bech32m
+    };
+
+    let mut data = Vec::with_capacity(1 + (witness_program.len() * 8 + 4) / 5);
+    data.push(witness_version);
+    data.extend(bech32::regroup_bits(witness_program, 8, 5, true).unwrap());
+    bech32::encode(human_readable_part, &data, variant)
+}
+
+fn base_58_check(version: u8, hash: &[u8; 20]) -> Vec<u16> {
+    let mut payload = [0u8; 21];
+    payload[0] = version;
+    payload[1..].copy_from_slice(hash);
+    base_58_encode_with_checksum(&payload)
+}
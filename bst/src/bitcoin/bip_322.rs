@@ -0,0 +1,416 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    addresses, double_sha256, taproot, try_read_compact_size, write_compact_size, Hash160,
+};
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::{
+        ecdsa, schnorr, try_derive_serialized_public_key, FieldElement, ProjectivePoint,
+    },
+    hashing::{Hasher, Sha256},
+    String16,
+};
+use alloc::{vec, vec::Vec};
+use macros::s16;
+
+// Generic signed messages (BIP 322). A message is signed by spending a virtual 'to_spend' transaction, whose output
+// is locked by the address' script and commits to the message, with a virtual 'to_sign' transaction. The signature
+// is either the to_sign transaction's witness ('simple'), or the whole to_sign transaction ('full').
+const MESSAGE_TAG: &[u8] = b"BIP0322-signed-message";
+
+const SIGHASH_DEFAULT: u8 = 0x00;
+const SIGHASH_ALL: u8 = 0x01;
+
+// to_sign's single output is an unspendable, empty OP_RETURN.
+const TO_SIGN_OUTPUT_SCRIPT: [u8; 1] = [0x6A];
+
+// The full to_sign transaction's locktime, following the witness.
+const TO_SIGN_LOCKTIME: [u8; 4] = [0u8; 4];
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Bip322AddressType {
+    P2wpkh,
+    P2tr,
+}
+
+impl Into<String16<'static>> for Bip322AddressType {
+    fn into(self) -> String16<'static> {
+        match self {
+            Bip322AddressType::P2wpkh => s16!("Native Segwit (P2WPKH)"),
+            Bip322AddressType::P2tr => s16!("Taproot (P2TR, BIP 86)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Bip322SignatureFormat {
+    Simple,
+    Full,
+}
+
+impl Into<String16<'static>> for Bip322SignatureFormat {
+    fn into(self) -> String16<'static> {
+        match self {
+            Bip322SignatureFormat::Simple => s16!("Simple"),
+            Bip322SignatureFormat::Full => s16!("Full"),
+        }
+    }
+}
+
+pub fn message_hash(message: &[u8]) -> [u8; 32] {
+    schnorr::tagged_hash(MESSAGE_TAG, &[message])
+}
+
+pub fn to_spend_txid(script_pubkey: &[u8], message: &[u8]) -> [u8; 32] {
+    // Version 0, a single input spending the null outpoint with a scriptSig of OP_0 PUSH32 <message hash>, and a single
+    // output of 0 satoshis to the address' script, with a locktime of 0.
+    let mut transaction = Vec::with_capacity(100 + script_pubkey.len());
+    transaction.extend([0u8; 4]);
+    transaction.push(1);
+    transaction.extend([0u8; 32]);
+    transaction.extend([0xFFu8; 4]);
+    transaction.extend([34, 0x00, 32]);
+    transaction.extend(message_hash(message));
+    transaction.extend([0u8; 4]);
+    transaction.push(1);
+    transaction.extend([0u8; 8]);
+    write_compact_size(script_pubkey.len() as u64, &mut transaction);
+    transaction.extend(script_pubkey);
+    transaction.extend([0u8; 4]);
+    double_sha256(&transaction)
+}
+
+pub fn try_sign(
+    private_key: &[u8; 32],
+    address_type: Bip322AddressType,
+    message: &[u8],
+    format: Bip322SignatureFormat,
+) -> Result<Vec<u8>, String16<'static>> {
+    let witness = match address_type {
+        Bip322AddressType::P2wpkh => {
+            let public_key = match try_derive_serialized_public_key(private_key) {
+                Some(k) => k,
+                None => {
+                    return Err(s16!(
+                        "The private key is not a valid secp256k1 private key."
+                    ))
+                }
+            };
+
+            let key_hash = Hash160::new().hash_160(&public_key);
+            let to_spend_txid = to_spend_txid(&script_pubkey(0, &key_hash), message);
+            let signature = match ecdsa::try_sign_recoverable(
+                private_key,
+                &segwit_v0_signature_hash(&to_spend_txid, &key_hash),
+            ) {
+                Some((s, _)) => s,
+                None => return Err(s16!("Failed to sign the message.")),
+            };
+
+            // <DER signature || sighash type> <compressed public key>
+            let mut signature = ecdsa::to_der(&signature);
+            signature.push(SIGHASH_ALL);
+            (to_spend_txid, vec![signature, public_key[..].into()])
+        }
+        Bip322AddressType::P2tr => {
+            let output_key = match schnorr::try_derive_x_only_public_key(private_key) {
+                Some(k) => taproot::try_tweak_public_key(&k).unwrap(),
+                None => {
+                    return Err(s16!(
+                        "The private key is not a valid secp256k1 private key."
+                    ))
+                }
+            };
+
+            let script_pubkey = script_pubkey(1, &output_key);
+            let to_spend_txid = to_spend_txid(&script_pubkey, message);
+            let mut tweaked_private_key = match taproot::try_tweak_private_key(private_key) {
+                Some(k) => k,
+                None => return Err(s16!("Failed to tweak the private key.")),
+            };
+
+            // BIP 340's auxiliary randomness only hardens signing against side channels; the signature is deterministic
+            // without it, which is fine for a program run on an offline machine.
+            let signature = schnorr::try_sign(
+                &tweaked_private_key,
+                &taproot_signature_hash(&to_spend_txid, &script_pubkey, SIGHASH_DEFAULT),
+                &[0u8; 32],
+            );
+
+            tweaked_private_key.fill(0);
+            match signature {
+                // A 64 byte signature implies SIGHASH_DEFAULT.
+                Some(s) => (to_spend_txid, vec![s[..].into()]),
+                None => return Err(s16!("Failed to sign the message.")),
+            }
+        }
+    };
+
+    let (to_spend_txid, witness) = witness;
+    let mut signature = match format {
+        Bip322SignatureFormat::Simple => Vec::new(),
+        Bip322SignatureFormat::Full => to_sign_prefix(&to_spend_txid),
+    };
+
+    write_compact_size(witness.len() as u64, &mut signature);
+    for item in witness {
+        write_compact_size(item.len() as u64, &mut signature);
+        signature.extend(item);
+    }
+
+    if format == Bip322SignatureFormat::Full {
+        signature.extend(TO_SIGN_LOCKTIME);
+    }
+
+    Ok(signature)
+}
+
+pub fn verify(
+    address: &[u16],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool, String16<'static>> {
+    let (witness_version, witness_program) = match addresses::try_decode_segwit(address) {
+        Some((_, v, p)) => (v, p),
+        None => {
+            return Err(s16!(
+                "The address is not a valid native segwit or taproot address."
+            ))
+        }
+    };
+
+    let script_pubkey = script_pubkey(witness_version, &witness_program);
+    let to_spend_txid = to_spend_txid(&script_pubkey, message);
+
+    // A simple signature is just the witness; a full signature must be the expected to_sign transaction.
+    let witness = match try_read_witness(signature) {
+        Some(w) => w,
+        None => {
+            let prefix = to_sign_prefix(&to_spend_txid);
+            if signature.len() < prefix.len() + TO_SIGN_LOCKTIME.len()
+                || !signature.starts_with(&prefix)
+                || !signature.ends_with(&TO_SIGN_LOCKTIME)
+            {
+                return Err(s16!(
+                    "The signature is neither a simple BIP 322 signature, nor a full signature for this address and message."
+                ));
+            }
+
+            match try_read_witness(
+                &signature[prefix.len()..signature.len() - TO_SIGN_LOCKTIME.len()],
+            ) {
+                Some(w) => w,
+                None => return Err(s16!("The full signature's witness is malformed.")),
+            }
+        }
+    };
+
+    match (witness_version, witness_program.len()) {
+        (0, 20) => {
+            // P2WPKH; the witness is a DER signature with a sighash type, and the public key matching the key hash.
+            if witness.len() != 2 || witness[1].len() != 33 {
+                return Ok(false);
+            }
+
+            let (sighash_type, signature) = match witness[0].split_last() {
+                Some((t, s)) => (*t, s),
+                None => return Ok(false),
+            };
+
+            if sighash_type != SIGHASH_ALL {
+                return Err(s16!("Only SIGHASH_ALL signatures are supported."));
+            }
+
+            if Hash160::new().hash_160(witness[1])[..] != witness_program[..] {
+                return Ok(false);
+            }
+
+            let public_key = match ProjectivePoint::try_from_serialized(witness[1]) {
+                Some(k) => k,
+                None => return Ok(false),
+            };
+
+            let signature = match ecdsa::try_from_der(signature) {
+                Some(s) => s,
+                None => return Ok(false),
+            };
+
+            let mut key_hash = [0u8; 20];
+            key_hash.copy_from_slice(&witness_program);
+            Ok(ecdsa::verify(
+                &public_key,
+                &segwit_v0_signature_hash(&to_spend_txid, &key_hash),
+                &signature,
+            ))
+        }
+        (1, 32) => {
+            // P2TR key path spends; the witness is a Schnorr signature, with an optional, non-default sighash type.
+            if witness.len() != 1 {
+                return Err(s16!("Only taproot key path signatures are supported."));
+            }
+
+            let sighash_type = match witness[0].len() {
+                64 => SIGHASH_DEFAULT,
+                65 if witness[0][64] == SIGHASH_ALL => SIGHASH_ALL,
+                65 => {
+                    return Err(s16!(
+                        "Only SIGHASH_DEFAULT and SIGHASH_ALL signatures are supported."
+                    ))
+                }
+                _ => return Ok(false),
+            };
+
+            let mut output_key = [0u8; 32];
+            output_key.copy_from_slice(&witness_program);
+            if FieldElement::from_be_bytes(&output_key).is_none() {
+                return Ok(false);
+            }
+
+            let mut signature = [0u8; schnorr::SIGNATURE_LENGTH];
+            signature.copy_from_slice(&witness[0][..64]);
+            Ok(schnorr::verify(
+                &output_key,
+                &taproot_signature_hash(&to_spend_txid, &script_pubkey, sighash_type),
+                &signature,
+            ))
+        }
+        _ => Err(s16!(
+            "Only native segwit (P2WPKH) and taproot (P2TR) addresses are supported."
+        )),
+    }
+}
+
+fn script_pubkey(witness_version: u8, witness_program: &[u8]) -> Vec<u8> {
+    // <OP_0 or OP_1 to OP_16> <PUSH witness program>
+    let mut script = Vec::with_capacity(2 + witness_program.len());
+    script.push(if witness_version == 0 {
+        0x00
+    } else {
+        0x50 + witness_version
+    });
+    script.push(witness_program.len() as u8);
+    script.extend(witness_program);
+    script
+}
+
+fn to_sign_prefix(to_spend_txid: &[u8; 32]) -> Vec<u8> {
+    // The full to_sign transaction up to its witness; version 0, the segwit marker and flag, a single input spending
+    // to_spend's output with an empty scriptSig and a sequence of 0, and a single OP_RETURN output of 0 satoshis.
+    let mut transaction = Vec::with_capacity(59);
+    transaction.extend([0u8; 4]);
+    transaction.extend([0x00, 0x01]);
+    transaction.push(1);
+    transaction.extend(to_spend_txid);
+    transaction.extend([0u8; 4]);
+    transaction.push(0);
+    transaction.extend([0u8; 4]);
+    transaction.push(1);
+    transaction.extend([0u8; 8]);
+    transaction.push(TO_SIGN_OUTPUT_SCRIPT.len() as u8);
+    transaction.extend(TO_SIGN_OUTPUT_SCRIPT);
+    transaction
+}
+
+fn try_read_witness(bytes: &[u8]) -> Option<Vec<&[u8]>> {
+    // A compact size item count, followed by each compact size length-prefixed item; nothing may follow the witness.
+    let (count, mut remainder) = match try_read_compact_size(bytes) {
+        Some(c) => c,
+        None => return None,
+    };
+
+    if count as usize > remainder.len() {
+        return None;
+    }
+
+    let mut items = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let (length, rest) = match try_read_compact_size(remainder) {
+            Some((l, r)) if l as usize <= r.len() => (l as usize, r),
+            _ => return None,
+        };
+
+        items.push(&rest[..length]);
+        remainder = &rest[length..];
+    }
+
+    if remainder.is_empty() {
+        Some(items)
+    } else {
+        None
+    }
+}
+
+fn segwit_v0_signature_hash(to_spend_txid: &[u8; 32], key_hash: &[u8; 20]) -> [u8; 32] {
+    // BIP 143, with SIGHASH_ALL, for to_sign's only input; it spends 0 satoshis, with a sequence of 0.
+    let mut outpoint = [0u8; 36];
+    outpoint[..32].copy_from_slice(to_spend_txid);
+
+    let mut output = [0u8; 10];
+    output[8] = TO_SIGN_OUTPUT_SCRIPT.len() as u8;
+    output[9..].copy_from_slice(&TO_SIGN_OUTPUT_SCRIPT);
+
+    let mut preimage = Vec::with_capacity(182);
+    preimage.extend([0u8; 4]);
+    preimage.extend(double_sha256(&outpoint));
+    preimage.extend(double_sha256(&[0u8; 4]));
+    preimage.extend(outpoint);
+
+    // The script code of a P2WPKH input is the equivalent P2PKH script.
+    preimage.extend([0x19, 0x76, 0xA9, 0x14]);
+    preimage.extend(key_hash);
+    preimage.extend([0x88, 0xAC]);
+
+    preimage.extend([0u8; 8]);
+    preimage.extend([0u8; 4]);
+    preimage.extend(double_sha256(&output));
+    preimage.extend([0u8; 4]);
+    preimage.extend((SIGHASH_ALL as u32).to_le_bytes());
+    double_sha256(&preimage)
+}
+
+fn taproot_signature_hash(
+    to_spend_txid: &[u8; 32],
+    script_pubkey: &[u8],
+    sighash_type: u8,
+) -> [u8; 32] {
+    // BIP 341, for a key path spend of to_sign's only input; SIGHASH_ALL and SIGHASH_DEFAULT commit to the same data.
+    let mut sha256 = Sha256::new();
+    let mut outpoint = [0u8; 36];
+    outpoint[..32].copy_from_slice(to_spend_txid);
+
+    let mut script_pubkeys = Vec::with_capacity(1 + script_pubkey.len());
+    write_compact_size(script_pubkey.len() as u64, &mut script_pubkeys);
+    script_pubkeys.extend(script_pubkey);
+
+    let mut output = [0u8; 10];
+    output[8] = TO_SIGN_OUTPUT_SCRIPT.len() as u8;
+    output[9..].copy_from_slice(&TO_SIGN_OUTPUT_SCRIPT);
+
+    // The epoch, the sighash type, the version and locktime, then the single SHA256 hashes of the prevouts, amounts,
+    // script pubkeys, sequences and outputs, and finally the spend type (key path, no annex) and input index.
+    let mut message = Vec::with_capacity(175);
+    message.extend([0x00, sighash_type]);
+    message.extend([0u8; 8]);
+    message.extend(sha256.get_hash_of(&outpoint));
+    message.extend(sha256.reset().get_hash_of(&[0u8; 8]));
+    message.extend(sha256.reset().get_hash_of(&script_pubkeys));
+    message.extend(sha256.reset().get_hash_of(&[0u8; 4]));
+    message.extend(sha256.reset().get_hash_of(&output));
+    message.push(0x00);
+    message.extend([0u8; 4]);
+    schnorr::tagged_hash(b"TapSighash", &[&message])
+}
@@ -43,7 +43,6 @@ impl Hash160 {
         fingerprint
     }
 
-    #[allow(dead_code)]
     pub fn hash_160(&mut self, bytes: &[u8]) -> [u8; 20] {
         self.sha256.write_hash_of(bytes, &mut self.sha256_buffer);
         let hash = self.ripemd160.get_hash_of(&self.sha256_buffer);
@@ -17,14 +17,13 @@
 use super::{Bip32KeyType, Bip32SerializedExtendedKey};
 use crate::{
     bitcoin::Hash160,
-    cryptography::asymmetric::ecc::{
-        self, secp256k1, EllipticCurvePoint, EllipticCurvePointMultiplicationContext,
+    cryptography::asymmetric::ecc::secp256k1::{
+        serialized_private_key_bytes, ProjectivePoint, Scalar,
     },
     hashing::{Hasher, Sha512},
-    integers::{BigUnsigned, Digit},
     String16,
 };
-use core::cmp::Ordering;
+use alloc::vec::Vec;
 use macros::s16;
 
 // If a point in a derivation path is >= 2^31, we should derive a hardened key. If it's < 2^31, we should derive a normal key.
@@ -33,6 +32,146 @@ use macros::s16;
 pub const HARDENED_CHILD_DERIVATION_THRESHOLD: u32 = 0b10000000000000000000000000000000;
 pub const MAX_DERIVATION_POINT: u32 = 0b01111111111111111111111111111111;
 
+pub fn try_parse_derivation_path(text: &[u16]) -> Option<Vec<Bip32DerivationPathPoint>> {
+    // Parses paths such as "m/84'/0'/0'/0/5"; the 'm' or 'p' prefix is optional, spaces and a trailing separator
+    // are ignored, and hardened points can be suffixed with ', h or H.
+    let mut characters = text.iter().copied().filter(|c| *c != b' ' as u16);
+    let mut path = Vec::new();
+    let mut current: Option<u32> = None;
+    let mut is_first_character = true;
+    let mut segment_is_complete = false;
+    while let Some(character) = characters.next() {
+        match character {
+            0x30..=0x39 if !segment_is_complete => {
+                let value = (current.unwrap_or(0) as u64) * 10 + (character - 0x30) as u64;
+                if value > MAX_DERIVATION_POINT as u64 {
+                    return None;
+                }
+
+                current = Some(value as u32);
+            }
+            0x27 | 0x68 | 0x48 if current.is_some() && !segment_is_complete => {
+                current = Some(current.unwrap() | HARDENED_CHILD_DERIVATION_THRESHOLD);
+                segment_is_complete = true;
+            }
+            0x6D | 0x70 if is_first_character => {
+                // The 'm' or 'p' prefix must be followed by a separator, or nothing at all.
+                match characters.next() {
+                    Some(0x2F) | None => {}
+                    _ => return None,
+                }
+            }
+            0x2F => match current.take() {
+                Some(p) => {
+                    path.push(Bip32DerivationPathPoint(p));
+                    segment_is_complete = false;
+                }
+                None => return None,
+            },
+            _ => return None,
+        }
+
+        is_first_character = false;
+    }
+
+    if let Some(p) = current {
+        path.push(Bip32DerivationPathPoint(p));
+    }
+
+    Some(path)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Bip32DerivationPathTemplateSegment {
+    // One or more alternative points, such as the receive and change chains.
+    Points(Vec<Bip32DerivationPathPoint>),
+    // Every index in a range; the flag indicates whether the indexes are hardened.
+    Wildcard(bool),
+}
+
+pub fn try_parse_derivation_path_template(
+    text: &[u16],
+) -> Option<Vec<Bip32DerivationPathTemplateSegment>> {
+    // Parses templates such as "m/84'/0'/0'/{0,1}/*"; points are written as they are in derivation paths, braces contain
+    // comma separated alternative points, and the path must end with a single '*' wildcard, which may be hardened.
+    let characters: Vec<u16> = text.iter().copied().filter(|c| *c != b' ' as u16).collect();
+    let mut characters = &characters[..];
+    match characters {
+        [0x6D | 0x70] => return None,
+        [0x6D | 0x70, 0x2F, ..] => characters = &characters[2..],
+        _ => {}
+    }
+
+    if let [remaining @ .., 0x2F] = characters {
+        characters = remaining;
+    }
+
+    let mut template = Vec::new();
+    for segment in characters.split(|c| *c == 0x2F) {
+        if matches!(
+            template.last(),
+            Some(Bip32DerivationPathTemplateSegment::Wildcard(_))
+        ) {
+            // The wildcard must be the final segment.
+            return None;
+        }
+
+        template.push(match segment {
+            [0x2A] => Bip32DerivationPathTemplateSegment::Wildcard(false),
+            [0x2A, 0x27 | 0x68 | 0x48] => Bip32DerivationPathTemplateSegment::Wildcard(true),
+            [0x7B, alternatives @ .., 0x7D] => {
+                let mut points = Vec::new();
+                for alternative in alternatives.split(|c| *c == 0x2C) {
+                    match try_parse_derivation_path_point(alternative) {
+                        Some(p) => points.push(p),
+                        None => return None,
+                    }
+                }
+
+                Bip32DerivationPathTemplateSegment::Points(points)
+            }
+            _ => match try_parse_derivation_path_point(segment) {
+                Some(p) => Bip32DerivationPathTemplateSegment::Points(Vec::from([p])),
+                None => return None,
+            },
+        });
+    }
+
+    match template.last() {
+        Some(Bip32DerivationPathTemplateSegment::Wildcard(_)) => Some(template),
+        _ => None,
+    }
+}
+
+fn try_parse_derivation_path_point(text: &[u16]) -> Option<Bip32DerivationPathPoint> {
+    let (digits, is_hardened) = match text {
+        [digits @ .., 0x27 | 0x68 | 0x48] => (digits, true),
+        digits => (digits, false),
+    };
+
+    if digits.len() == 0 {
+        return None;
+    }
+
+    let mut value: u64 = 0;
+    for digit in digits {
+        match digit {
+            0x30..=0x39 => value = value * 10 + (digit - 0x30) as u64,
+            _ => return None,
+        }
+
+        if value > MAX_DERIVATION_POINT as u64 {
+            return None;
+        }
+    }
+
+    Some(Bip32DerivationPathPoint(if is_hardened {
+        value as u32 | HARDENED_CHILD_DERIVATION_THRESHOLD
+    } else {
+        value as u32
+    }))
+}
+
 #[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
 enum IlValidationResult {
     Ok,
@@ -57,9 +196,6 @@ impl Bip32DerivationPathPoint {
         sha512: &mut Sha512,
         hash160: &mut Hash160,
         parent_key: &Bip32SerializedExtendedKey,
-        private_key_buffer: &mut BigUnsigned,
-        point_buffer: &mut EllipticCurvePoint,
-        multiplication_context: &mut EllipticCurvePointMultiplicationContext,
     ) -> Result<Bip32SerializedExtendedKey, String16<'static>> {
         if parent_key.depth() == u8::MAX {
             return Err(s16!("Maximum key depth reached."));
@@ -86,24 +222,21 @@ impl Bip32DerivationPathPoint {
 
         let (parent_public_key, key_material): ([u8; 33], [u8; 33]) = match key_version.key_type() {
             Bip32KeyType::Private => {
-                // Write the private key to the private key buffer.
-                private_key_buffer.copy_be_bytes_from(parent_key.key_material());
-
-                // Derive the EC point for the public key.
-                let point = match multiplication_context.multiply_point(
-                    secp256k1::g_x(),
-                    secp256k1::g_y(),
-                    &private_key_buffer,
-                ) {
-                    Some(p) => p,
-                    None => return Err(s16!("Failed to derive parent public key.")),
-                };
+                // Read the parent private key, minus its identifying byte.
+                let mut parent_private_key =
+                    match Self::try_read_scalar(&parent_key.key_material()[1..]) {
+                        Some(k) => k,
+                        None => return Err(s16!("Invalid parent private key.")),
+                    };
 
-                // Serialize the derived public key.
-                let parent_public_key = match secp256k1::serialized_public_key_bytes(point) {
-                    Some(k) => k,
-                    None => return Err(s16!("Failed to serialize parent public key.")),
-                };
+                // Derive and serialize the public key.
+                let parent_public_key =
+                    match ProjectivePoint::multiply_generator(&parent_private_key)
+                        .try_serialize_compressed()
+                    {
+                        Some(k) => k,
+                        None => return Err(s16!("Failed to derive parent public key.")),
+                    };
 
                 if self.is_for_hardened_key() {
                     // Deriving a hardened child uses the serialized private key for the hash data.
@@ -118,28 +251,29 @@ impl Bip32DerivationPathPoint {
                     data[33..].copy_from_slice(&index.to_be_bytes());
                     hmac.write_hmac_to(&data, &mut hmac_buffer);
 
-                    let (km, _) = point_buffer.borrow_coordinates_mut();
-                    let km = km.borrow_unsigned_mut();
-                    km.copy_be_bytes_from(&hmac_buffer[..32]);
+                    // Add parent private key and child private key material; ppk + cpk (mod n).
+                    let mut child_private_key = match Self::try_read_scalar(&hmac_buffer[..32]) {
+                        Some(mut k) => {
+                            let child_private_key = k.add(&parent_private_key);
+                            k.zero();
+                            child_private_key
+                        }
+                        None => Scalar::ZERO,
+                    };
 
-                    match Self::validate_key_material(km.borrow_digits(), &mut index) {
+                    match Self::validate_key_material(child_private_key.is_zero(), &mut index) {
                         IlValidationResult::Ok => {}
                         IlValidationResult::NextIteration => continue,
                         IlValidationResult::ReturnError(e) => return Err(e),
                     }
 
-                    // Add parent private key and child private key material; ppk + cpk (mod n).
-                    private_key_buffer.add_big_unsigned(&km);
-                    private_key_buffer.modulo_big_unsigned(secp256k1::n());
-
                     // Copy the resulting key into a new key material buffer.
-                    let mut key_material = [0u8; 33];
-                    assert!(private_key_buffer.try_copy_be_bytes_to(
-                        &mut key_material[33 - private_key_buffer.byte_count()..],
-                    ));
+                    let key_material =
+                        serialized_private_key_bytes(&child_private_key.to_be_bytes());
 
-                    // Zero our private key buffer; we're done with it.
-                    private_key_buffer.zero();
+                    // Zero our private keys; we're done with them.
+                    child_private_key.zero();
+                    parent_private_key.zero();
 
                     // Return our key material from the loop.
                     break key_material;
@@ -160,62 +294,36 @@ impl Bip32DerivationPathPoint {
                 let mut parent_public_key = [0u8; 33];
                 parent_public_key.copy_from_slice(parent_key.key_material());
 
-                // We're going to manually write coordinates to the point buffer; first we need to manually ensure it's not set to infinity.
-                unsafe {
-                    point_buffer.set_not_infinity();
-                }
-
-                let (x, y) = point_buffer.borrow_coordinates_mut();
-                // Write the X coordinate of the parent public key into the working point.
-                x.copy_be_bytes_from(&parent_public_key[1..], false);
-
-                // Ensure the working point's Y value is positive.
-                y.set_sign(false);
-
-                // Calculate the Y coordinate of the parent public key's point and write into the working point Y coordinate.
-                multiplication_context.calculate_y_from_x(
-                    parent_public_key[0] == ecc::COMPRESSED_Y_IS_EVEN_IDENTIFIER,
-                    x.borrow_unsigned(),
-                    y.borrow_unsigned_mut(),
-                );
+                // Decompress the parent public key's point.
+                let parent_point = match ProjectivePoint::try_from_serialized(&parent_public_key) {
+                    Some(p) => p,
+                    None => return Err(s16!("Invalid parent public key.")),
+                };
 
                 let key_material = loop {
                     // The hash data's last 4 bytes are the index.
                     data[33..].copy_from_slice(&index.to_be_bytes());
                     hmac.write_hmac_to(&data, &mut hmac_buffer);
 
-                    // Write the child key material to the private key buffer.
-                    private_key_buffer.copy_be_bytes_from(&hmac_buffer[..32]);
-                    match Self::validate_key_material(
-                        private_key_buffer.borrow_digits(),
-                        &mut index,
-                    ) {
+                    // Derive the EC point for the child key material, and add the parent point to it.
+                    let child_point = match Self::try_read_scalar(&hmac_buffer[..32]) {
+                        Some(mut k) => {
+                            let child_point =
+                                ProjectivePoint::multiply_generator(&k).add(&parent_point);
+                            k.zero();
+                            child_point
+                        }
+                        None => ProjectivePoint::INFINITY,
+                    };
+
+                    match Self::validate_key_material(child_point.is_infinity(), &mut index) {
                         IlValidationResult::Ok => {}
                         IlValidationResult::NextIteration => continue,
                         IlValidationResult::ReturnError(e) => return Err(e),
                     }
 
-                    // Derive the EC point for the child.
-                    let mut point = match multiplication_context.multiply_point(
-                        secp256k1::g_x(),
-                        secp256k1::g_y(),
-                        &private_key_buffer,
-                    ) {
-                        Some(p) => p,
-                        None => return Err(s16!("Failed to derive parent public key.")),
-                    };
-
-                    // Add the parent point to the child point.
-                    point.add(
-                        point_buffer,
-                        multiplication_context.borrow_addition_context(),
-                    );
-
-                    // Zero the working buffer; we're done with it.
-                    private_key_buffer.zero();
-
                     // Serialize the point and return it from the loop as our key material.
-                    match secp256k1::serialized_public_key_bytes(point) {
+                    match child_point.try_serialize_compressed() {
                         Some(k) => break k,
                         None => return Err(s16!("Failed to serialize child public key.")),
                     }
@@ -242,11 +350,23 @@ impl Bip32DerivationPathPoint {
         ))
     }
 
-    fn validate_key_material(key_material: &[Digit], index: &mut u32) -> IlValidationResult {
-        if key_material.iter().all(|c| *c == 0)
-            || secp256k1::n().partial_cmp(key_material).unwrap() != Ordering::Greater
-        {
-            // If the key material == 0 or >= N, we need to move to the next index.
+    fn try_read_scalar(bytes: &[u8]) -> Option<Scalar> {
+        // Keys and key material must be in the range 0 < k < n.
+        match Scalar::try_from_be_slice(bytes) {
+            Some(s) => {
+                if s.is_zero() {
+                    None
+                } else {
+                    Some(s)
+                }
+            }
+            None => None,
+        }
+    }
+
+    fn validate_key_material(key_is_invalid: bool, index: &mut u32) -> IlValidationResult {
+        if key_is_invalid {
+            // If the key material was >= N, or the resulting key was 0 (or infinity), we need to move to the next index.
             if (*index & MAX_DERIVATION_POINT) == MAX_DERIVATION_POINT {
                 // We've run out of indexes; we can't derive a child key.
                 return IlValidationResult::ReturnError(s16!("Ran out of key derivation space; this is extremely unlikely to happen, and should be investigated."));
@@ -265,17 +385,11 @@ impl Bip32DerivationPathPoint {
 pub struct Bip32CkdContext {
     sha512: Sha512,
     hash160: Hash160,
-    private_key_buffer: BigUnsigned,
-    point_buffer: EllipticCurvePoint,
-    multiplication_context: EllipticCurvePointMultiplicationContext,
 }
 
 impl Bip32CkdContext {
     pub fn new() -> Self {
         Self {
-            point_buffer: EllipticCurvePoint::infinity(32),
-            multiplication_context: secp256k1::point_multiplication_context(),
-            private_key_buffer: BigUnsigned::with_byte_capacity(32),
             hash160: Hash160::new(),
             sha512: Sha512::new(),
         }
@@ -295,9 +409,6 @@ impl Bip32CkdContext {
                 &mut self.sha512,
                 &mut self.hash160,
                 &current_key,
-                &mut self.private_key_buffer,
-                &mut self.point_buffer,
-                &mut self.multiplication_context,
             ) {
                 Ok(k) => k,
                 Err(e) => {
@@ -318,8 +429,6 @@ impl Bip32CkdContext {
     }
 
     pub fn reset(&mut self) {
-        self.point_buffer.set_infinity();
-        self.private_key_buffer.zero();
         self.hash160.reset();
         self.sha512.reset();
     }
@@ -19,16 +19,16 @@ mod key_types;
 mod serialized_extended_key;
 
 pub use derivation_paths::{
-    Bip32CkdContext, Bip32DerivationPathPoint, HARDENED_CHILD_DERIVATION_THRESHOLD,
-    MAX_DERIVATION_POINT,
+    try_parse_derivation_path, try_parse_derivation_path_template, Bip32CkdContext,
+    Bip32DerivationPathPoint, Bip32DerivationPathTemplateSegment,
+    HARDENED_CHILD_DERIVATION_THRESHOLD, MAX_DERIVATION_POINT,
 };
 pub use key_types::{Bip32KeyNetwork, Bip32KeyType, Bip32KeyVersion};
 pub use serialized_extended_key::Bip32SerializedExtendedKey;
 
 use crate::{
-    cryptography::asymmetric::ecc::secp256k1,
+    cryptography::asymmetric::ecc::secp256k1::{self, Scalar},
     hashing::{Hasher, Sha512},
-    integers::BigUnsigned,
 };
 
 // The key used for HMAC-based BIP 32 master key derivation.
@@ -48,14 +48,23 @@ pub fn try_derive_master_key(
     let mut hmac = hasher.build_hmac(KEY_DERIVATION_KEY_BYTES);
     let mut hmac_result = hmac.get_hmac(bytes);
 
-    let mut key_integer = BigUnsigned::from_be_bytes(&hmac_result[..32]);
-    if !key_integer.is_non_zero() || &key_integer >= secp256k1::n() {
-        // The key must be in the range: 0 < K < N. This shouldn't ever be hit in reality.
+    let mut key_scalar = match Scalar::try_from_be_slice(&hmac_result[..32]) {
+        Some(k) => k,
+        None => {
+            // The key must be in the range: 0 < K < N. This shouldn't ever be hit in reality.
+            hmac_result.fill(0);
+            return None;
+        }
+    };
+
+    if key_scalar.is_zero() {
+        // As above; the key must be in the range: 0 < K < N.
+        hmac_result.fill(0);
         return None;
     }
 
-    // Zero out key integer copy of key.
-    key_integer.zero();
+    // Zero out scalar copy of key.
+    key_scalar.zero();
 
     // Key is the first 32 bytes, Chain Code is the last 32 bytes.
     let mut chain_code = [0u8; 32];
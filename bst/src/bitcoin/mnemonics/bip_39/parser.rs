@@ -38,8 +38,8 @@ impl crate::bitcoin::mnemonics::MnemonicParser for MnemonicParser {
         MNEMONIC_FORMAT
     }
 
-    fn bip_32_derivation_settings(&self) -> Bip32DevivationSettings {
-        BIP_32_DERIVATION_SETTINGS
+    fn bip_32_derivation_settings(&self) -> Option<Bip32DevivationSettings> {
+        Some(BIP_32_DERIVATION_SETTINGS)
     }
 }
 
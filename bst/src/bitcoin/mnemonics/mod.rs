@@ -16,6 +16,7 @@
 
 pub mod bip_39;
 pub mod electrum;
+pub mod slip_39;
 
 mod bip_32_derivation;
 mod mnemonic_formats;
@@ -40,7 +41,7 @@ pub trait MnemonicParser {
 
     fn mnemonic_format(&self) -> MnemonicFormat<Self::TMnemonicLength>;
 
-    fn bip_32_derivation_settings(&self) -> Bip32DevivationSettings;
+    fn bip_32_derivation_settings(&self) -> Option<Bip32DevivationSettings>;
 }
 
 pub trait MnemonicParseResult {
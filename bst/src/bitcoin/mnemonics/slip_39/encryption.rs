@@ -0,0 +1,112 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::hashing::{Hasher, Sha256};
+use alloc::vec::Vec;
+
+const ROUND_COUNT: u8 = 4;
+const BASE_ITERATION_COUNT: u32 = 10000;
+
+pub fn encrypt(
+    master_secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Vec<u8> {
+    feistel(
+        master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        0..ROUND_COUNT,
+    )
+}
+
+pub fn decrypt(
+    encrypted_master_secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Vec<u8> {
+    // Decryption is encryption with the rounds reversed.
+    feistel(
+        encrypted_master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        (0..ROUND_COUNT).rev(),
+    )
+}
+
+fn feistel<TRounds: Iterator<Item = u8>>(
+    input: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    rounds: TRounds,
+) -> Vec<u8> {
+    let half_length = input.len() / 2;
+    let mut left = Vec::from(&input[..half_length]);
+    let mut right = Vec::from(&input[half_length..]);
+
+    // Extendable backups aren't salted with the identifier, so new shares can be created with a different identifier.
+    let mut salt = Vec::with_capacity(8 + half_length);
+    if !extendable {
+        salt.extend(b"shamir");
+        salt.extend(identifier.to_be_bytes());
+    }
+
+    let salt_prefix_length = salt.len();
+    let iterations = (BASE_ITERATION_COUNT << iteration_exponent) / ROUND_COUNT as u32;
+    let mut key = Vec::with_capacity(passphrase.len() + 1);
+    let mut round_output = Vec::from(&right[..]);
+
+    for round in rounds {
+        // The round function is PBKDF2-HMAC-SHA256, keyed with the round number and passphrase, and salted with the right half.
+        key.clear();
+        key.push(round);
+        key.extend(passphrase);
+        salt.truncate(salt_prefix_length);
+        salt.extend(&right[..]);
+
+        Sha256::new()
+            .build_hmac(&key)
+            .pbkdf2(&salt, iterations, &mut round_output);
+
+        for i in 0..half_length {
+            left[i] ^= round_output[i];
+        }
+
+        core::mem::swap(&mut left, &mut right);
+    }
+
+    // The halves are swapped on output.
+    let mut output = Vec::with_capacity(input.len());
+    output.extend(&right[..]);
+    output.extend(&left[..]);
+
+    left.fill(0);
+    right.fill(0);
+    key.fill(0);
+    salt.fill(0);
+    round_output.fill(0);
+    output
+}
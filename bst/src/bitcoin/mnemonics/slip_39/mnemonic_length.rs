@@ -0,0 +1,44 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::String16;
+use macros::s16;
+
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+pub enum MnemonicLength {
+    Twenty,
+    ThirtyThree,
+}
+
+impl Into<usize> for MnemonicLength {
+    fn into(self) -> usize {
+        match self {
+            MnemonicLength::Twenty => 20,
+            MnemonicLength::ThirtyThree => 33,
+        }
+    }
+}
+
+impl Into<String16<'static>> for MnemonicLength {
+    fn into(self) -> String16<'static> {
+        match self {
+            MnemonicLength::Twenty => s16!("Twenty Word"),
+            MnemonicLength::ThirtyThree => s16!("Thirty Three Word"),
+        }
+    }
+}
+
+impl crate::bitcoin::mnemonics::MnemonicLength for MnemonicLength {}
@@ -0,0 +1,310 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod encryption;
+mod mnemonic_length;
+mod parser;
+mod shares;
+mod word_list;
+
+pub use mnemonic_length::MnemonicLength;
+pub use parser::MnemonicParser;
+pub use shares::{
+    try_generate_shares, try_recover_master_secret, try_validate_share_set,
+    MINIMUM_RANDOM_SEED_LENGTH,
+};
+
+use super::{MnemonicFormat, MnemonicWordList};
+use crate::String16;
+use alloc::{boxed::Box, vec::Vec};
+use macros::s16;
+
+pub const WORD_LIST: MnemonicWordList = MnemonicWordList::from(
+    &word_list::WORDS,
+    word_list::LONGEST_WORD_LENGTH,
+    s16!("SLIP 39"),
+    word_list::BITS_PER_WORD,
+);
+
+pub const MNEMONIC_FORMAT: MnemonicFormat<MnemonicLength> = MnemonicFormat::from(
+    get_available_lengths_for_byte_count,
+    s16!("16"),
+    WORD_LIST,
+    s16!("SLIP 39"),
+    33,
+);
+
+// The checksum is customized with a different string for extendable backups.
+const CUSTOMIZATION_STRING: &[u8] = b"shamir";
+const EXTENDABLE_CUSTOMIZATION_STRING: &[u8] = b"shamir_extendable";
+
+// The identifier, extendable flag and iteration exponent occupy two words, and the group and member parameters another two.
+const HEADER_WORD_COUNT: usize = 4;
+const CHECKSUM_WORD_COUNT: usize = 3;
+const MINIMUM_MNEMONIC_LENGTH: usize = 20;
+
+pub const MINIMUM_SECRET_LENGTH: usize = 16;
+pub const MAXIMUM_SHARE_COUNT: u8 = 16;
+pub const MAXIMUM_ITERATION_EXPONENT: u8 = 15;
+
+fn get_available_lengths_for_byte_count(byte_count: usize) -> &'static [MnemonicLength] {
+    match byte_count {
+        16 => &[MnemonicLength::Twenty],
+        32 => &[MnemonicLength::ThirtyThree],
+        _ => &[],
+    }
+}
+
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Share {
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: Box<[u8]>,
+}
+
+impl Share {
+    pub const fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    pub const fn extendable(&self) -> bool {
+        self.extendable
+    }
+
+    pub const fn group_index(&self) -> u8 {
+        self.group_index
+    }
+
+    pub const fn group_threshold(&self) -> u8 {
+        self.group_threshold
+    }
+
+    pub const fn group_count(&self) -> u8 {
+        self.group_count
+    }
+
+    pub const fn member_index(&self) -> u8 {
+        self.member_index
+    }
+
+    pub const fn member_threshold(&self) -> u8 {
+        self.member_threshold
+    }
+
+    fn has_same_backup_parameters(&self, other: &Share) -> bool {
+        self.identifier == other.identifier
+            && self.extendable == other.extendable
+            && self.iteration_exponent == other.iteration_exponent
+            && self.group_threshold == other.group_threshold
+            && self.group_count == other.group_count
+            && self.value.len() == other.value.len()
+    }
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.value.fill(0);
+    }
+}
+
+#[derive(Clone, Eq, Ord, PartialEq, PartialOrd)]
+pub enum MnemonicParsingResult<'a> {
+    InvalidLength,
+    InvalidWordEncountered(String16<'a>, usize),
+    InvalidChecksum,
+    InvalidPadding,
+    InvalidGroupParameters,
+    Valid(Share),
+}
+
+pub fn try_parse_slip39_mnemonic<'a>(words: &Vec<String16<'a>>) -> MnemonicParsingResult<'a> {
+    if words.len() < MINIMUM_MNEMONIC_LENGTH {
+        return MnemonicParsingResult::InvalidLength;
+    }
+
+    // The share value is left-padded to a whole number of words; the padding can never exceed a byte.
+    let value_word_count = words.len() - HEADER_WORD_COUNT - CHECKSUM_WORD_COUNT;
+    let padding = (value_word_count * word_list::BITS_PER_WORD) % 16;
+    if padding > 8 {
+        return MnemonicParsingResult::InvalidLength;
+    }
+
+    // Look up the index of each word in the word list.
+    let mut indices = Vec::with_capacity(words.len());
+    for i in 0..words.len() {
+        match WORD_LIST.try_get_word_index(words[i]) {
+            Some(index) => indices.push(index as u16),
+            None => return MnemonicParsingResult::InvalidWordEncountered(words[i], i),
+        }
+    }
+
+    let extendable = (indices[1] >> 4) & 1 == 1;
+    if rs1024_polymod(customization_string(extendable), &indices) != 1 {
+        indices.fill(0);
+        return MnemonicParsingResult::InvalidChecksum;
+    }
+
+    let value_indices = &indices[HEADER_WORD_COUNT..indices.len() - CHECKSUM_WORD_COUNT];
+    let value = match try_read_share_value(value_indices, padding) {
+        Some(v) => v,
+        None => {
+            indices.fill(0);
+            return MnemonicParsingResult::InvalidPadding;
+        }
+    };
+
+    // The group and member parameters are five 4 bit values spread across the third and fourth words.
+    let parameters = ((indices[2] as u32) << 10) | indices[3] as u32;
+    let share = Share {
+        identifier: (indices[0] << 5) | (indices[1] >> 5),
+        iteration_exponent: (indices[1] & 0xF) as u8,
+        group_index: (parameters >> 16) as u8,
+        group_threshold: ((parameters >> 12) & 0xF) as u8 + 1,
+        group_count: ((parameters >> 8) & 0xF) as u8 + 1,
+        member_index: ((parameters >> 4) & 0xF) as u8,
+        member_threshold: (parameters & 0xF) as u8 + 1,
+        value: value.into(),
+        extendable,
+    };
+
+    indices.fill(0);
+    if share.group_threshold > share.group_count || share.group_index >= share.group_count {
+        MnemonicParsingResult::InvalidGroupParameters
+    } else {
+        MnemonicParsingResult::Valid(share)
+    }
+}
+
+pub fn get_share_mnemonic(share: &Share) -> Vec<String16<'static>> {
+    let value_word_count =
+        (share.value.len() * 8 + word_list::BITS_PER_WORD - 1) / word_list::BITS_PER_WORD;
+    let mut indices =
+        Vec::with_capacity(HEADER_WORD_COUNT + value_word_count + CHECKSUM_WORD_COUNT);
+
+    // Identifier, extendable flag and iteration exponent.
+    indices.push(share.identifier >> 5);
+    indices.push(
+        ((share.identifier & 0x1F) << 5)
+            | ((share.extendable as u16) << 4)
+            | share.iteration_exponent as u16,
+    );
+
+    // Group and member parameters.
+    let parameters = ((share.group_index as u32) << 16)
+        | ((share.group_threshold as u32 - 1) << 12)
+        | ((share.group_count as u32 - 1) << 8)
+        | ((share.member_index as u32) << 4)
+        | (share.member_threshold as u32 - 1);
+    indices.push((parameters >> 10) as u16);
+    indices.push((parameters & 0x3FF) as u16);
+
+    // The share value, left-padded with zero bits to a whole number of words.
+    let mut accumulator = 0u32;
+    let mut bits = value_word_count * word_list::BITS_PER_WORD - share.value.len() * 8;
+    for byte in share.value.iter() {
+        accumulator = (accumulator << 8) | *byte as u32;
+        bits += 8;
+        while bits >= word_list::BITS_PER_WORD {
+            bits -= word_list::BITS_PER_WORD;
+            indices.push((accumulator >> bits) as u16 & 0x3FF);
+            accumulator &= (1 << bits) - 1;
+        }
+    }
+
+    // The checksum words are chosen so the polymod of the whole mnemonic is 1.
+    let customization_string = customization_string(share.extendable);
+    indices.extend([0, 0, 0]);
+    let checksum = rs1024_polymod(customization_string, &indices) ^ 1;
+    let length = indices.len();
+    for i in 0..CHECKSUM_WORD_COUNT {
+        indices[length - CHECKSUM_WORD_COUNT + i] = ((checksum >> (10 * (2 - i))) & 0x3FF) as u16;
+    }
+
+    let words = indices
+        .iter()
+        .map(|i| WORD_LIST.words()[*i as usize])
+        .collect();
+    indices.fill(0);
+    words
+}
+
+fn try_read_share_value(indices: &[u16], padding: usize) -> Option<Vec<u8>> {
+    let mut value = Vec::with_capacity((indices.len() * word_list::BITS_PER_WORD - padding) / 8);
+    let mut accumulator = 0u32;
+    let mut bits = 0usize;
+    let mut padding_remaining = padding;
+    for index in indices {
+        accumulator = (accumulator << word_list::BITS_PER_WORD) | *index as u32;
+        bits += word_list::BITS_PER_WORD;
+
+        if padding_remaining > 0 {
+            // The padding bits lead the first word, and must all be zero.
+            bits -= padding_remaining;
+            if accumulator >> bits != 0 {
+                return None;
+            }
+
+            padding_remaining = 0;
+        }
+
+        while bits >= 8 {
+            bits -= 8;
+            value.push((accumulator >> bits) as u8);
+            accumulator &= (1 << bits) - 1;
+        }
+    }
+
+    Some(value)
+}
+
+fn customization_string(extendable: bool) -> &'static [u8] {
+    if extendable {
+        EXTENDABLE_CUSTOMIZATION_STRING
+    } else {
+        CUSTOMIZATION_STRING
+    }
+}
+
+fn rs1024_polymod(customization_string: &[u8], indices: &[u16]) -> u32 {
+    // The Reed-Solomon code over GF(1024) from SLIP 39; it detects any error affecting at most 3 words.
+    const GENERATOR: [u32; 10] = [
+        0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48,
+        0x21B1F890, 0x3F3F120,
+    ];
+
+    let mut checksum = 1u32;
+    for value in customization_string
+        .iter()
+        .map(|b| *b as u32)
+        .chain(indices.iter().map(|i| *i as u32))
+    {
+        let top = checksum >> 20;
+        checksum = ((checksum & 0xFFFFF) << 10) ^ value;
+        for i in 0..10 {
+            if (top >> i) & 1 == 1 {
+                checksum ^= GENERATOR[i];
+            }
+        }
+    }
+
+    checksum
+}
@@ -0,0 +1,355 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    encryption, Share, MAXIMUM_ITERATION_EXPONENT, MAXIMUM_SHARE_COUNT, MINIMUM_SECRET_LENGTH,
+};
+use crate::{
+    cryptography::shamir,
+    hashing::{derive_bytes, Hasher, Sha256},
+    String16,
+};
+use alloc::{vec, vec::Vec};
+use macros::s16;
+
+const DIGEST_LENGTH: usize = 4;
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+
+pub const MINIMUM_RANDOM_SEED_LENGTH: usize = 32;
+
+pub fn try_generate_shares(
+    master_secret: &[u8],
+    passphrase: &[u8],
+    extendable: bool,
+    iteration_exponent: u8,
+    group_threshold: u8,
+    groups: &[(u8, u8)],
+    random_seed: &[u8],
+) -> Result<Vec<Vec<Share>>, String16<'static>> {
+    if master_secret.len() < MINIMUM_SECRET_LENGTH || master_secret.len() % 2 != 0 {
+        return Err(s16!(
+            "The master secret must be an even number of bytes, and at least 16 bytes long."
+        ));
+    }
+
+    if passphrase.iter().any(|c| *c < 32 || *c > 126) {
+        return Err(s16!(
+            "The passphrase must only contain printable ASCII characters."
+        ));
+    }
+
+    if iteration_exponent > MAXIMUM_ITERATION_EXPONENT {
+        return Err(s16!("The iteration exponent cannot exceed 15."));
+    }
+
+    if groups.len() == 0 || groups.len() > MAXIMUM_SHARE_COUNT as usize {
+        return Err(s16!("There must be between 1 and 16 groups."));
+    }
+
+    if group_threshold == 0 || group_threshold as usize > groups.len() {
+        return Err(s16!(
+            "The group threshold must be between 1 and the number of groups."
+        ));
+    }
+
+    for (member_threshold, member_count) in groups {
+        if *member_threshold == 0
+            || member_threshold > member_count
+            || *member_count > MAXIMUM_SHARE_COUNT
+        {
+            return Err(s16!("Each group's member threshold must be between 1 and its member count, which cannot exceed 16."));
+        }
+
+        if *member_threshold == 1 && *member_count > 1 {
+            return Err(s16!(
+                "Groups with a member threshold of 1 must have exactly 1 member."
+            ));
+        }
+    }
+
+    if random_seed.len() < MINIMUM_RANDOM_SEED_LENGTH {
+        return Err(s16!("The random seed must be at least 32 bytes."));
+    }
+
+    // We don't have a source of randomness; derive the identifier and the random share data from the seed.
+    let secret_length = master_secret.len();
+    let random_length = 2
+        + random_length_for_split(group_threshold, secret_length)
+        + groups
+            .iter()
+            .map(|(t, _)| random_length_for_split(*t, secret_length))
+            .sum::<usize>();
+    let mut random = derive_bytes(random_seed, master_secret, random_length);
+    let mut random_offset = 2;
+
+    let identifier = u16::from_be_bytes([random[0], random[1]]) >> 1;
+    let mut encrypted_master_secret = encryption::encrypt(
+        master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+    );
+
+    let mut group_secrets = split_secret(
+        group_threshold,
+        groups.len() as u8,
+        &encrypted_master_secret,
+        &random,
+        &mut random_offset,
+    );
+    encrypted_master_secret.fill(0);
+
+    let mut shares = Vec::with_capacity(groups.len());
+    for ((group_index, group_secret), (member_threshold, member_count)) in
+        group_secrets.iter().zip(groups)
+    {
+        let mut member_secrets = split_secret(
+            *member_threshold,
+            *member_count,
+            group_secret,
+            &random,
+            &mut random_offset,
+        );
+
+        shares.push(
+            member_secrets
+                .iter()
+                .map(|(member_index, value)| Share {
+                    identifier,
+                    extendable,
+                    iteration_exponent,
+                    group_index: *group_index,
+                    group_threshold,
+                    group_count: groups.len() as u8,
+                    member_index: *member_index,
+                    member_threshold: *member_threshold,
+                    value: value[..].into(),
+                })
+                .collect(),
+        );
+
+        member_secrets.iter_mut().for_each(|(_, s)| s.fill(0));
+    }
+
+    group_secrets.iter_mut().for_each(|(_, s)| s.fill(0));
+    random.fill(0);
+    Ok(shares)
+}
+
+pub fn try_validate_share_set(shares: &[Share]) -> Result<bool, String16<'static>> {
+    if shares.len() == 0 {
+        return Ok(false);
+    }
+
+    let first = &shares[0];
+    let mut complete_groups = 0;
+    for group_index in 0..first.group_count {
+        let mut member_threshold = None;
+        let mut member_count = 0;
+        for (i, share) in shares.iter().enumerate() {
+            if !share.has_same_backup_parameters(first) {
+                return Err(s16!("The shares do not belong to the same backup."));
+            }
+
+            if share.group_index != group_index {
+                continue;
+            }
+
+            match member_threshold {
+                Some(t) if t != share.member_threshold => {
+                    return Err(s16!(
+                        "The shares in a group have inconsistent member thresholds."
+                    ))
+                }
+                _ => member_threshold = Some(share.member_threshold),
+            }
+
+            if shares[..i]
+                .iter()
+                .any(|s| s.group_index == group_index && s.member_index == share.member_index)
+            {
+                return Err(s16!("The shares contain a duplicate member."));
+            }
+
+            member_count += 1;
+        }
+
+        if let Some(t) = member_threshold {
+            if member_count >= t {
+                complete_groups += 1;
+            }
+        }
+    }
+
+    Ok(complete_groups >= first.group_threshold)
+}
+
+pub fn try_recover_master_secret(
+    shares: &[Share],
+    passphrase: &[u8],
+) -> Result<Vec<u8>, String16<'static>> {
+    if !try_validate_share_set(shares)? {
+        return Err(s16!(
+            "Not enough shares were provided to recover the secret."
+        ));
+    }
+
+    // Recover the secret of each group which meets its member threshold, until we meet the group threshold.
+    let first = &shares[0];
+    let mut group_secrets: Vec<(u8, Vec<u8>)> = Vec::with_capacity(first.group_threshold as usize);
+    for group_index in 0..first.group_count {
+        if group_secrets.len() == first.group_threshold as usize {
+            break;
+        }
+
+        let members: Vec<(u8, &[u8])> = shares
+            .iter()
+            .filter(|s| s.group_index == group_index)
+            .map(|s| (s.member_index, &s.value[..]))
+            .collect();
+
+        if members.len() == 0 {
+            continue;
+        }
+
+        let member_threshold = shares
+            .iter()
+            .find(|s| s.group_index == group_index)
+            .unwrap()
+            .member_threshold;
+
+        if members.len() >= member_threshold as usize {
+            match recover_secret(member_threshold, &members[..member_threshold as usize]) {
+                Ok(s) => group_secrets.push((group_index, s)),
+                Err(e) => {
+                    group_secrets.iter_mut().for_each(|(_, s)| s.fill(0));
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    let groups: Vec<(u8, &[u8])> = group_secrets.iter().map(|(i, s)| (*i, &s[..])).collect();
+    let encrypted_master_secret = recover_secret(first.group_threshold, &groups);
+    group_secrets.iter_mut().for_each(|(_, s)| s.fill(0));
+    let mut encrypted_master_secret = encrypted_master_secret?;
+
+    let master_secret = encryption::decrypt(
+        &encrypted_master_secret,
+        passphrase,
+        first.iteration_exponent,
+        first.identifier,
+        first.extendable,
+    );
+
+    encrypted_master_secret.fill(0);
+    Ok(master_secret)
+}
+
+fn random_length_for_split(threshold: u8, secret_length: usize) -> usize {
+    if threshold == 1 {
+        0
+    } else {
+        (threshold as usize - 2) * secret_length + secret_length - DIGEST_LENGTH
+    }
+}
+
+fn take_random<'a>(random: &'a [u8], offset: &mut usize, length: usize) -> &'a [u8] {
+    let bytes = &random[*offset..*offset + length];
+    *offset += length;
+    bytes
+}
+
+fn split_secret(
+    threshold: u8,
+    share_count: u8,
+    secret: &[u8],
+    random: &[u8],
+    random_offset: &mut usize,
+) -> Vec<(u8, Vec<u8>)> {
+    if threshold == 1 {
+        // Every share is the secret itself.
+        return (0..share_count).map(|i| (i, Vec::from(secret))).collect();
+    }
+
+    // The first threshold - 2 shares are random; the remaining two points defining the polynomial are the digest and secret.
+    let random_share_count = threshold - 2;
+    let mut shares: Vec<(u8, Vec<u8>)> = (0..random_share_count)
+        .map(|i| {
+            (
+                i,
+                Vec::from(take_random(random, random_offset, secret.len())),
+            )
+        })
+        .collect();
+
+    let random_part = take_random(random, random_offset, secret.len() - DIGEST_LENGTH);
+    let mut digest_share = vec![0u8; secret.len()];
+    digest_share[..DIGEST_LENGTH].copy_from_slice(&create_digest(random_part, secret));
+    digest_share[DIGEST_LENGTH..].copy_from_slice(random_part);
+
+    let mut base_shares: Vec<(u8, &[u8])> = shares.iter().map(|(i, s)| (*i, &s[..])).collect();
+    base_shares.push((DIGEST_INDEX, &digest_share));
+    base_shares.push((SECRET_INDEX, secret));
+
+    let mut derived_shares: Vec<(u8, Vec<u8>)> = (random_share_count..share_count)
+        .map(|i| (i, shamir::interpolate(&base_shares, i).unwrap()))
+        .collect();
+
+    digest_share.fill(0);
+    shares.append(&mut derived_shares);
+    shares
+}
+
+fn recover_secret(threshold: u8, shares: &[(u8, &[u8])]) -> Result<Vec<u8>, String16<'static>> {
+    if threshold == 1 {
+        return Ok(Vec::from(shares[0].1));
+    }
+
+    let (mut secret, mut digest_share) = match (
+        shamir::interpolate(shares, SECRET_INDEX),
+        shamir::interpolate(shares, DIGEST_INDEX),
+    ) {
+        (Some(s), Some(d)) => (s, d),
+        _ => return Err(s16!("The shares contain a duplicate member.")),
+    };
+
+    let digest = create_digest(&digest_share[DIGEST_LENGTH..], &secret);
+    let valid = digest[..] == digest_share[..DIGEST_LENGTH];
+    digest_share.fill(0);
+    if valid {
+        Ok(secret)
+    } else {
+        secret.fill(0);
+        Err(s16!(
+            "The recovered secret failed its digest check; the shares are inconsistent."
+        ))
+    }
+}
+
+fn create_digest(random_part: &[u8], secret: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut hmac = [0u8; Sha256::HASH_SIZE];
+    Sha256::new()
+        .build_hmac(random_part)
+        .write_hmac_to(secret, &mut hmac);
+
+    let mut digest = [0u8; DIGEST_LENGTH];
+    digest.copy_from_slice(&hmac[..DIGEST_LENGTH]);
+    hmac.fill(0);
+    digest
+}
@@ -0,0 +1,1047 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::String16;
+use macros::s16;
+
+pub const LONGEST_WORD_LENGTH: usize = 8;
+pub const BITS_PER_WORD: usize = 10;
+pub const WORDS: [String16; 1024] = [
+    s16!("academic"),
+    s16!("acid"),
+    s16!("acne"),
+    s16!("acquire"),
+    s16!("acrobat"),
+    s16!("activity"),
+    s16!("actress"),
+    s16!("adapt"),
+    s16!("adequate"),
+    s16!("adjust"),
+    s16!("admit"),
+    s16!("adorn"),
+    s16!("adult"),
+    s16!("advance"),
+    s16!("advocate"),
+    s16!("afraid"),
+    s16!("again"),
+    s16!("agency"),
+    s16!("agree"),
+    s16!("aide"),
+    s16!("aircraft"),
+    s16!("airline"),
+    s16!("airport"),
+    s16!("ajar"),
+    s16!("alarm"),
+    s16!("album"),
+    s16!("alcohol"),
+    s16!("alien"),
+    s16!("alive"),
+    s16!("alpha"),
+    s16!("already"),
+    s16!("alto"),
+    s16!("aluminum"),
+    s16!("always"),
+    s16!("amazing"),
+    s16!("ambition"),
+    s16!("amount"),
+    s16!("amuse"),
+    s16!("analysis"),
+    s16!("anatomy"),
+    s16!("ancestor"),
+    s16!("ancient"),
+    s16!("angel"),
+    s16!("angry"),
+    s16!("animal"),
+    s16!("answer"),
+    s16!("antenna"),
+    s16!("anxiety"),
+    s16!("apart"),
+    s16!("aquatic"),
+    s16!("arcade"),
+    s16!("arena"),
+    s16!("argue"),
+    s16!("armed"),
+    s16!("artist"),
+    s16!("artwork"),
+    s16!("aspect"),
+    s16!("auction"),
+    s16!("august"),
+    s16!("aunt"),
+    s16!("average"),
+    s16!("aviation"),
+    s16!("avoid"),
+    s16!("award"),
+    s16!("away"),
+    s16!("axis"),
+    s16!("axle"),
+    s16!("beam"),
+    s16!("beard"),
+    s16!("beaver"),
+    s16!("become"),
+    s16!("bedroom"),
+    s16!("behavior"),
+    s16!("being"),
+    s16!("believe"),
+    s16!("belong"),
+    s16!("benefit"),
+    s16!("best"),
+    s16!("beyond"),
+    s16!("bike"),
+    s16!("biology"),
+    s16!("birthday"),
+    s16!("bishop"),
+    s16!("black"),
+    s16!("blanket"),
+    s16!("blessing"),
+    s16!("blimp"),
+    s16!("blind"),
+    s16!("blue"),
+    s16!("body"),
+    s16!("bolt"),
+    s16!("boring"),
+    s16!("born"),
+    s16!("both"),
+    s16!("boundary"),
+    s16!("bracelet"),
+    s16!("branch"),
+    s16!("brave"),
+    s16!("breathe"),
+    s16!("briefing"),
+    s16!("broken"),
+    s16!("brother"),
+    s16!("browser"),
+    s16!("bucket"),
+    s16!("budget"),
+    s16!("building"),
+    s16!("bulb"),
+    s16!("bulge"),
+    s16!("bumpy"),
+    s16!("bundle"),
+    s16!("burden"),
+    s16!("burning"),
+    s16!("busy"),
+    s16!("buyer"),
+    s16!("cage"),
+    s16!("calcium"),
+    s16!("camera"),
+    s16!("campus"),
+    s16!("canyon"),
+    s16!("capacity"),
+    s16!("capital"),
+    s16!("capture"),
+    s16!("carbon"),
+    s16!("cards"),
+    s16!("careful"),
+    s16!("cargo"),
+    s16!("carpet"),
+    s16!("carve"),
+    s16!("category"),
+    s16!("cause"),
+    s16!("ceiling"),
+    s16!("center"),
+    s16!("ceramic"),
+    s16!("champion"),
+    s16!("change"),
+    s16!("charity"),
+    s16!("check"),
+    s16!("chemical"),
+    s16!("chest"),
+    s16!("chew"),
+    s16!("chubby"),
+    s16!("cinema"),
+    s16!("civil"),
+    s16!("class"),
+    s16!("clay"),
+    s16!("cleanup"),
+    s16!("client"),
+    s16!("climate"),
+    s16!("clinic"),
+    s16!("clock"),
+    s16!("clogs"),
+    s16!("closet"),
+    s16!("clothes"),
+    s16!("club"),
+    s16!("cluster"),
+    s16!("coal"),
+    s16!("coastal"),
+    s16!("coding"),
+    s16!("column"),
+    s16!("company"),
+    s16!("corner"),
+    s16!("costume"),
+    s16!("counter"),
+    s16!("course"),
+    s16!("cover"),
+    s16!("cowboy"),
+    s16!("cradle"),
+    s16!("craft"),
+    s16!("crazy"),
+    s16!("credit"),
+    s16!("cricket"),
+    s16!("criminal"),
+    s16!("crisis"),
+    s16!("critical"),
+    s16!("crowd"),
+    s16!("crucial"),
+    s16!("crunch"),
+    s16!("crush"),
+    s16!("crystal"),
+    s16!("cubic"),
+    s16!("cultural"),
+    s16!("curious"),
+    s16!("curly"),
+    s16!("custody"),
+    s16!("cylinder"),
+    s16!("daisy"),
+    s16!("damage"),
+    s16!("dance"),
+    s16!("darkness"),
+    s16!("database"),
+    s16!("daughter"),
+    s16!("deadline"),
+    s16!("deal"),
+    s16!("debris"),
+    s16!("debut"),
+    s16!("decent"),
+    s16!("decision"),
+    s16!("declare"),
+    s16!("decorate"),
+    s16!("decrease"),
+    s16!("deliver"),
+    s16!("demand"),
+    s16!("density"),
+    s16!("deny"),
+    s16!("depart"),
+    s16!("depend"),
+    s16!("depict"),
+    s16!("deploy"),
+    s16!("describe"),
+    s16!("desert"),
+    s16!("desire"),
+    s16!("desktop"),
+    s16!("destroy"),
+    s16!("detailed"),
+    s16!("detect"),
+    s16!("device"),
+    s16!("devote"),
+    s16!("diagnose"),
+    s16!("dictate"),
+    s16!("diet"),
+    s16!("dilemma"),
+    s16!("diminish"),
+    s16!("dining"),
+    s16!("diploma"),
+    s16!("disaster"),
+    s16!("discuss"),
+    s16!("disease"),
+    s16!("dish"),
+    s16!("dismiss"),
+    s16!("display"),
+    s16!("distance"),
+    s16!("dive"),
+    s16!("divorce"),
+    s16!("document"),
+    s16!("domain"),
+    s16!("domestic"),
+    s16!("dominant"),
+    s16!("dough"),
+    s16!("downtown"),
+    s16!("dragon"),
+    s16!("dramatic"),
+    s16!("dream"),
+    s16!("dress"),
+    s16!("drift"),
+    s16!("drink"),
+    s16!("drove"),
+    s16!("drug"),
+    s16!("dryer"),
+    s16!("duckling"),
+    s16!("duke"),
+    s16!("duration"),
+    s16!("dwarf"),
+    s16!("dynamic"),
+    s16!("early"),
+    s16!("earth"),
+    s16!("easel"),
+    s16!("easy"),
+    s16!("echo"),
+    s16!("eclipse"),
+    s16!("ecology"),
+    s16!("edge"),
+    s16!("editor"),
+    s16!("educate"),
+    s16!("either"),
+    s16!("elbow"),
+    s16!("elder"),
+    s16!("election"),
+    s16!("elegant"),
+    s16!("element"),
+    s16!("elephant"),
+    s16!("elevator"),
+    s16!("elite"),
+    s16!("else"),
+    s16!("email"),
+    s16!("emerald"),
+    s16!("emission"),
+    s16!("emperor"),
+    s16!("emphasis"),
+    s16!("employer"),
+    s16!("empty"),
+    s16!("ending"),
+    s16!("endless"),
+    s16!("endorse"),
+    s16!("enemy"),
+    s16!("energy"),
+    s16!("enforce"),
+    s16!("engage"),
+    s16!("enjoy"),
+    s16!("enlarge"),
+    s16!("entrance"),
+    s16!("envelope"),
+    s16!("envy"),
+    s16!("epidemic"),
+    s16!("episode"),
+    s16!("equation"),
+    s16!("equip"),
+    s16!("eraser"),
+    s16!("erode"),
+    s16!("escape"),
+    s16!("estate"),
+    s16!("estimate"),
+    s16!("evaluate"),
+    s16!("evening"),
+    s16!("evidence"),
+    s16!("evil"),
+    s16!("evoke"),
+    s16!("exact"),
+    s16!("example"),
+    s16!("exceed"),
+    s16!("exchange"),
+    s16!("exclude"),
+    s16!("excuse"),
+    s16!("execute"),
+    s16!("exercise"),
+    s16!("exhaust"),
+    s16!("exotic"),
+    s16!("expand"),
+    s16!("expect"),
+    s16!("explain"),
+    s16!("express"),
+    s16!("extend"),
+    s16!("extra"),
+    s16!("eyebrow"),
+    s16!("facility"),
+    s16!("fact"),
+    s16!("failure"),
+    s16!("faint"),
+    s16!("fake"),
+    s16!("false"),
+    s16!("family"),
+    s16!("famous"),
+    s16!("fancy"),
+    s16!("fangs"),
+    s16!("fantasy"),
+    s16!("fatal"),
+    s16!("fatigue"),
+    s16!("favorite"),
+    s16!("fawn"),
+    s16!("fiber"),
+    s16!("fiction"),
+    s16!("filter"),
+    s16!("finance"),
+    s16!("findings"),
+    s16!("finger"),
+    s16!("firefly"),
+    s16!("firm"),
+    s16!("fiscal"),
+    s16!("fishing"),
+    s16!("fitness"),
+    s16!("flame"),
+    s16!("flash"),
+    s16!("flavor"),
+    s16!("flea"),
+    s16!("flexible"),
+    s16!("flip"),
+    s16!("float"),
+    s16!("floral"),
+    s16!("fluff"),
+    s16!("focus"),
+    s16!("forbid"),
+    s16!("force"),
+    s16!("forecast"),
+    s16!("forget"),
+    s16!("formal"),
+    s16!("fortune"),
+    s16!("forward"),
+    s16!("founder"),
+    s16!("fraction"),
+    s16!("fragment"),
+    s16!("frequent"),
+    s16!("freshman"),
+    s16!("friar"),
+    s16!("fridge"),
+    s16!("friendly"),
+    s16!("frost"),
+    s16!("froth"),
+    s16!("frozen"),
+    s16!("fumes"),
+    s16!("funding"),
+    s16!("furl"),
+    s16!("fused"),
+    s16!("galaxy"),
+    s16!("game"),
+    s16!("garbage"),
+    s16!("garden"),
+    s16!("garlic"),
+    s16!("gasoline"),
+    s16!("gather"),
+    s16!("general"),
+    s16!("genius"),
+    s16!("genre"),
+    s16!("genuine"),
+    s16!("geology"),
+    s16!("gesture"),
+    s16!("glad"),
+    s16!("glance"),
+    s16!("glasses"),
+    s16!("glen"),
+    s16!("glimpse"),
+    s16!("goat"),
+    s16!("golden"),
+    s16!("graduate"),
+    s16!("grant"),
+    s16!("grasp"),
+    s16!("gravity"),
+    s16!("gray"),
+    s16!("greatest"),
+    s16!("grief"),
+    s16!("grill"),
+    s16!("grin"),
+    s16!("grocery"),
+    s16!("gross"),
+    s16!("group"),
+    s16!("grownup"),
+    s16!("grumpy"),
+    s16!("guard"),
+    s16!("guest"),
+    s16!("guilt"),
+    s16!("guitar"),
+    s16!("gums"),
+    s16!("hairy"),
+    s16!("hamster"),
+    s16!("hand"),
+    s16!("hanger"),
+    s16!("harvest"),
+    s16!("have"),
+    s16!("havoc"),
+    s16!("hawk"),
+    s16!("hazard"),
+    s16!("headset"),
+    s16!("health"),
+    s16!("hearing"),
+    s16!("heat"),
+    s16!("helpful"),
+    s16!("herald"),
+    s16!("herd"),
+    s16!("hesitate"),
+    s16!("hobo"),
+    s16!("holiday"),
+    s16!("holy"),
+    s16!("home"),
+    s16!("hormone"),
+    s16!("hospital"),
+    s16!("hour"),
+    s16!("huge"),
+    s16!("human"),
+    s16!("humidity"),
+    s16!("hunting"),
+    s16!("husband"),
+    s16!("hush"),
+    s16!("husky"),
+    s16!("hybrid"),
+    s16!("idea"),
+    s16!("identify"),
+    s16!("idle"),
+    s16!("image"),
+    s16!("impact"),
+    s16!("imply"),
+    s16!("improve"),
+    s16!("impulse"),
+    s16!("include"),
+    s16!("income"),
+    s16!("increase"),
+    s16!("index"),
+    s16!("indicate"),
+    s16!("industry"),
+    s16!("infant"),
+    s16!("inform"),
+    s16!("inherit"),
+    s16!("injury"),
+    s16!("inmate"),
+    s16!("insect"),
+    s16!("inside"),
+    s16!("install"),
+    s16!("intend"),
+    s16!("intimate"),
+    s16!("invasion"),
+    s16!("involve"),
+    s16!("iris"),
+    s16!("island"),
+    s16!("isolate"),
+    s16!("item"),
+    s16!("ivory"),
+    s16!("jacket"),
+    s16!("jerky"),
+    s16!("jewelry"),
+    s16!("join"),
+    s16!("judicial"),
+    s16!("juice"),
+    s16!("jump"),
+    s16!("junction"),
+    s16!("junior"),
+    s16!("junk"),
+    s16!("jury"),
+    s16!("justice"),
+    s16!("kernel"),
+    s16!("keyboard"),
+    s16!("kidney"),
+    s16!("kind"),
+    s16!("kitchen"),
+    s16!("knife"),
+    s16!("knit"),
+    s16!("laden"),
+    s16!("ladle"),
+    s16!("ladybug"),
+    s16!("lair"),
+    s16!("lamp"),
+    s16!("language"),
+    s16!("large"),
+    s16!("laser"),
+    s16!("laundry"),
+    s16!("lawsuit"),
+    s16!("leader"),
+    s16!("leaf"),
+    s16!("learn"),
+    s16!("leaves"),
+    s16!("lecture"),
+    s16!("legal"),
+    s16!("legend"),
+    s16!("legs"),
+    s16!("lend"),
+    s16!("length"),
+    s16!("level"),
+    s16!("liberty"),
+    s16!("library"),
+    s16!("license"),
+    s16!("lift"),
+    s16!("likely"),
+    s16!("lilac"),
+    s16!("lily"),
+    s16!("lips"),
+    s16!("liquid"),
+    s16!("listen"),
+    s16!("literary"),
+    s16!("living"),
+    s16!("lizard"),
+    s16!("loan"),
+    s16!("lobe"),
+    s16!("location"),
+    s16!("losing"),
+    s16!("loud"),
+    s16!("loyalty"),
+    s16!("luck"),
+    s16!("lunar"),
+    s16!("lunch"),
+    s16!("lungs"),
+    s16!("luxury"),
+    s16!("lying"),
+    s16!("lyrics"),
+    s16!("machine"),
+    s16!("magazine"),
+    s16!("maiden"),
+    s16!("mailman"),
+    s16!("main"),
+    s16!("makeup"),
+    s16!("making"),
+    s16!("mama"),
+    s16!("manager"),
+    s16!("mandate"),
+    s16!("mansion"),
+    s16!("manual"),
+    s16!("marathon"),
+    s16!("march"),
+    s16!("market"),
+    s16!("marvel"),
+    s16!("mason"),
+    s16!("material"),
+    s16!("math"),
+    s16!("maximum"),
+    s16!("mayor"),
+    s16!("meaning"),
+    s16!("medal"),
+    s16!("medical"),
+    s16!("member"),
+    s16!("memory"),
+    s16!("mental"),
+    s16!("merchant"),
+    s16!("merit"),
+    s16!("method"),
+    s16!("metric"),
+    s16!("midst"),
+    s16!("mild"),
+    s16!("military"),
+    s16!("mineral"),
+    s16!("minister"),
+    s16!("miracle"),
+    s16!("mixed"),
+    s16!("mixture"),
+    s16!("mobile"),
+    s16!("modern"),
+    s16!("modify"),
+    s16!("moisture"),
+    s16!("moment"),
+    s16!("morning"),
+    s16!("mortgage"),
+    s16!("mother"),
+    s16!("mountain"),
+    s16!("mouse"),
+    s16!("move"),
+    s16!("much"),
+    s16!("mule"),
+    s16!("multiple"),
+    s16!("muscle"),
+    s16!("museum"),
+    s16!("music"),
+    s16!("mustang"),
+    s16!("nail"),
+    s16!("national"),
+    s16!("necklace"),
+    s16!("negative"),
+    s16!("nervous"),
+    s16!("network"),
+    s16!("news"),
+    s16!("nuclear"),
+    s16!("numb"),
+    s16!("numerous"),
+    s16!("nylon"),
+    s16!("oasis"),
+    s16!("obesity"),
+    s16!("object"),
+    s16!("observe"),
+    s16!("obtain"),
+    s16!("ocean"),
+    s16!("often"),
+    s16!("olympic"),
+    s16!("omit"),
+    s16!("oral"),
+    s16!("orange"),
+    s16!("orbit"),
+    s16!("order"),
+    s16!("ordinary"),
+    s16!("organize"),
+    s16!("ounce"),
+    s16!("oven"),
+    s16!("overall"),
+    s16!("owner"),
+    s16!("paces"),
+    s16!("pacific"),
+    s16!("package"),
+    s16!("paid"),
+    s16!("painting"),
+    s16!("pajamas"),
+    s16!("pancake"),
+    s16!("pants"),
+    s16!("papa"),
+    s16!("paper"),
+    s16!("parcel"),
+    s16!("parking"),
+    s16!("party"),
+    s16!("patent"),
+    s16!("patrol"),
+    s16!("payment"),
+    s16!("payroll"),
+    s16!("peaceful"),
+    s16!("peanut"),
+    s16!("peasant"),
+    s16!("pecan"),
+    s16!("penalty"),
+    s16!("pencil"),
+    s16!("percent"),
+    s16!("perfect"),
+    s16!("permit"),
+    s16!("petition"),
+    s16!("phantom"),
+    s16!("pharmacy"),
+    s16!("photo"),
+    s16!("phrase"),
+    s16!("physics"),
+    s16!("pickup"),
+    s16!("picture"),
+    s16!("piece"),
+    s16!("pile"),
+    s16!("pink"),
+    s16!("pipeline"),
+    s16!("pistol"),
+    s16!("pitch"),
+    s16!("plains"),
+    s16!("plan"),
+    s16!("plastic"),
+    s16!("platform"),
+    s16!("playoff"),
+    s16!("pleasure"),
+    s16!("plot"),
+    s16!("plunge"),
+    s16!("practice"),
+    s16!("prayer"),
+    s16!("preach"),
+    s16!("predator"),
+    s16!("pregnant"),
+    s16!("premium"),
+    s16!("prepare"),
+    s16!("presence"),
+    s16!("prevent"),
+    s16!("priest"),
+    s16!("primary"),
+    s16!("priority"),
+    s16!("prisoner"),
+    s16!("privacy"),
+    s16!("prize"),
+    s16!("problem"),
+    s16!("process"),
+    s16!("profile"),
+    s16!("program"),
+    s16!("promise"),
+    s16!("prospect"),
+    s16!("provide"),
+    s16!("prune"),
+    s16!("public"),
+    s16!("pulse"),
+    s16!("pumps"),
+    s16!("punish"),
+    s16!("puny"),
+    s16!("pupal"),
+    s16!("purchase"),
+    s16!("purple"),
+    s16!("python"),
+    s16!("quantity"),
+    s16!("quarter"),
+    s16!("quick"),
+    s16!("quiet"),
+    s16!("race"),
+    s16!("racism"),
+    s16!("radar"),
+    s16!("railroad"),
+    s16!("rainbow"),
+    s16!("raisin"),
+    s16!("random"),
+    s16!("ranked"),
+    s16!("rapids"),
+    s16!("raspy"),
+    s16!("reaction"),
+    s16!("realize"),
+    s16!("rebound"),
+    s16!("rebuild"),
+    s16!("recall"),
+    s16!("receiver"),
+    s16!("recover"),
+    s16!("regret"),
+    s16!("regular"),
+    s16!("reject"),
+    s16!("relate"),
+    s16!("remember"),
+    s16!("remind"),
+    s16!("remove"),
+    s16!("render"),
+    s16!("repair"),
+    s16!("repeat"),
+    s16!("replace"),
+    s16!("require"),
+    s16!("rescue"),
+    s16!("research"),
+    s16!("resident"),
+    s16!("response"),
+    s16!("result"),
+    s16!("retailer"),
+    s16!("retreat"),
+    s16!("reunion"),
+    s16!("revenue"),
+    s16!("review"),
+    s16!("reward"),
+    s16!("rhyme"),
+    s16!("rhythm"),
+    s16!("rich"),
+    s16!("rival"),
+    s16!("river"),
+    s16!("robin"),
+    s16!("rocky"),
+    s16!("romantic"),
+    s16!("romp"),
+    s16!("roster"),
+    s16!("round"),
+    s16!("royal"),
+    s16!("ruin"),
+    s16!("ruler"),
+    s16!("rumor"),
+    s16!("sack"),
+    s16!("safari"),
+    s16!("salary"),
+    s16!("salon"),
+    s16!("salt"),
+    s16!("satisfy"),
+    s16!("satoshi"),
+    s16!("saver"),
+    s16!("says"),
+    s16!("scandal"),
+    s16!("scared"),
+    s16!("scatter"),
+    s16!("scene"),
+    s16!("scholar"),
+    s16!("science"),
+    s16!("scout"),
+    s16!("scramble"),
+    s16!("screw"),
+    s16!("script"),
+    s16!("scroll"),
+    s16!("seafood"),
+    s16!("season"),
+    s16!("secret"),
+    s16!("security"),
+    s16!("segment"),
+    s16!("senior"),
+    s16!("shadow"),
+    s16!("shaft"),
+    s16!("shame"),
+    s16!("shaped"),
+    s16!("sharp"),
+    s16!("shelter"),
+    s16!("sheriff"),
+    s16!("short"),
+    s16!("should"),
+    s16!("shrimp"),
+    s16!("sidewalk"),
+    s16!("silent"),
+    s16!("silver"),
+    s16!("similar"),
+    s16!("simple"),
+    s16!("single"),
+    s16!("sister"),
+    s16!("skin"),
+    s16!("skunk"),
+    s16!("slap"),
+    s16!("slavery"),
+    s16!("sled"),
+    s16!("slice"),
+    s16!("slim"),
+    s16!("slow"),
+    s16!("slush"),
+    s16!("smart"),
+    s16!("smear"),
+    s16!("smell"),
+    s16!("smirk"),
+    s16!("smith"),
+    s16!("smoking"),
+    s16!("smug"),
+    s16!("snake"),
+    s16!("snapshot"),
+    s16!("sniff"),
+    s16!("society"),
+    s16!("software"),
+    s16!("soldier"),
+    s16!("solution"),
+    s16!("soul"),
+    s16!("source"),
+    s16!("space"),
+    s16!("spark"),
+    s16!("speak"),
+    s16!("species"),
+    s16!("spelling"),
+    s16!("spend"),
+    s16!("spew"),
+    s16!("spider"),
+    s16!("spill"),
+    s16!("spine"),
+    s16!("spirit"),
+    s16!("spit"),
+    s16!("spray"),
+    s16!("sprinkle"),
+    s16!("square"),
+    s16!("squeeze"),
+    s16!("stadium"),
+    s16!("staff"),
+    s16!("standard"),
+    s16!("starting"),
+    s16!("station"),
+    s16!("stay"),
+    s16!("steady"),
+    s16!("step"),
+    s16!("stick"),
+    s16!("stilt"),
+    s16!("story"),
+    s16!("strategy"),
+    s16!("strike"),
+    s16!("style"),
+    s16!("subject"),
+    s16!("submit"),
+    s16!("sugar"),
+    s16!("suitable"),
+    s16!("sunlight"),
+    s16!("superior"),
+    s16!("surface"),
+    s16!("surprise"),
+    s16!("survive"),
+    s16!("sweater"),
+    s16!("swimming"),
+    s16!("swing"),
+    s16!("switch"),
+    s16!("symbolic"),
+    s16!("sympathy"),
+    s16!("syndrome"),
+    s16!("system"),
+    s16!("tackle"),
+    s16!("tactics"),
+    s16!("tadpole"),
+    s16!("talent"),
+    s16!("task"),
+    s16!("taste"),
+    s16!("taught"),
+    s16!("taxi"),
+    s16!("teacher"),
+    s16!("teammate"),
+    s16!("teaspoon"),
+    s16!("temple"),
+    s16!("tenant"),
+    s16!("tendency"),
+    s16!("tension"),
+    s16!("terminal"),
+    s16!("testify"),
+    s16!("texture"),
+    s16!("thank"),
+    s16!("that"),
+    s16!("theater"),
+    s16!("theory"),
+    s16!("therapy"),
+    s16!("thorn"),
+    s16!("threaten"),
+    s16!("thumb"),
+    s16!("thunder"),
+    s16!("ticket"),
+    s16!("tidy"),
+    s16!("timber"),
+    s16!("timely"),
+    s16!("ting"),
+    s16!("tofu"),
+    s16!("together"),
+    s16!("tolerate"),
+    s16!("total"),
+    s16!("toxic"),
+    s16!("tracks"),
+    s16!("traffic"),
+    s16!("training"),
+    s16!("transfer"),
+    s16!("trash"),
+    s16!("traveler"),
+    s16!("treat"),
+    s16!("trend"),
+    s16!("trial"),
+    s16!("tricycle"),
+    s16!("trip"),
+    s16!("triumph"),
+    s16!("trouble"),
+    s16!("true"),
+    s16!("trust"),
+    s16!("twice"),
+    s16!("twin"),
+    s16!("type"),
+    s16!("typical"),
+    s16!("ugly"),
+    s16!("ultimate"),
+    s16!("umbrella"),
+    s16!("uncover"),
+    s16!("undergo"),
+    s16!("unfair"),
+    s16!("unfold"),
+    s16!("unhappy"),
+    s16!("union"),
+    s16!("universe"),
+    s16!("unkind"),
+    s16!("unknown"),
+    s16!("unusual"),
+    s16!("unwrap"),
+    s16!("upgrade"),
+    s16!("upstairs"),
+    s16!("username"),
+    s16!("usher"),
+    s16!("usual"),
+    s16!("valid"),
+    s16!("valuable"),
+    s16!("vampire"),
+    s16!("vanish"),
+    s16!("various"),
+    s16!("vegan"),
+    s16!("velvet"),
+    s16!("venture"),
+    s16!("verdict"),
+    s16!("verify"),
+    s16!("very"),
+    s16!("veteran"),
+    s16!("vexed"),
+    s16!("victim"),
+    s16!("video"),
+    s16!("view"),
+    s16!("vintage"),
+    s16!("violence"),
+    s16!("viral"),
+    s16!("visitor"),
+    s16!("visual"),
+    s16!("vitamins"),
+    s16!("vocal"),
+    s16!("voice"),
+    s16!("volume"),
+    s16!("voter"),
+    s16!("voting"),
+    s16!("walnut"),
+    s16!("warmth"),
+    s16!("warn"),
+    s16!("watch"),
+    s16!("wavy"),
+    s16!("wealthy"),
+    s16!("weapon"),
+    s16!("webcam"),
+    s16!("welcome"),
+    s16!("welfare"),
+    s16!("western"),
+    s16!("width"),
+    s16!("wildlife"),
+    s16!("window"),
+    s16!("wine"),
+    s16!("wireless"),
+    s16!("wisdom"),
+    s16!("withdraw"),
+    s16!("wits"),
+    s16!("wolf"),
+    s16!("woman"),
+    s16!("work"),
+    s16!("worthy"),
+    s16!("wrap"),
+    s16!("wrist"),
+    s16!("writing"),
+    s16!("wrote"),
+    s16!("year"),
+    s16!("yelp"),
+    s16!("yield"),
+    s16!("yoga"),
+    s16!("zero"),
+];
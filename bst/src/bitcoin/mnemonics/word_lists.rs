@@ -87,6 +87,12 @@ impl MnemonicWordList {
         Some(self.words[index])
     }
 
+    pub fn try_get_word_index(&self, word: String16) -> Option<usize> {
+        self.words
+            .binary_search_by(|w| w.content_iterator().cmp(word.content_iterator()))
+            .ok()
+    }
+
     pub fn try_read_mnemonic_bytes<'a>(
         &self,
         mnemonic_byte_count: usize,
@@ -105,12 +111,9 @@ impl MnemonicWordList {
             let word = words[i];
 
             // Look for the word in the word list.
-            let word = match self
-                .words
-                .binary_search_by(|w| w.content_iterator().cmp(word.content_iterator()))
-            {
-                Ok(i) => i,
-                Err(_) => {
+            let word = match self.try_get_word_index(word) {
+                Some(i) => i,
+                None => {
                     // We couldn't find the word in the word list; return an error.
                     return Err((word, i));
                 }
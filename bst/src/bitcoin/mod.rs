@@ -14,8 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod addresses;
+pub mod bip_322;
 pub mod hd_wallets;
 pub mod mnemonics;
+pub mod psbt;
+pub mod signature_hashes;
+pub mod signed_messages;
+pub mod taproot;
+pub mod transactions;
 
 mod hash_160;
 
@@ -25,7 +32,113 @@ use crate::{
     hashing::{Hasher, Sha256},
     integers::{NumericBase, NumericCollector, NumericCollectorRoundBase},
 };
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
+use hd_wallets::{Bip32KeyNetwork, Bip32KeyVersion};
+
+// Wallet Import Format private keys are Base58Check encoded with these version bytes, optionally suffixed with a
+// 0x01 byte indicating the corresponding public key should be compressed.
+pub const MAIN_NET_WIF_VERSION: u8 = 0x80;
+pub const TEST_NET_WIF_VERSION: u8 = 0xEF;
+pub const WIF_COMPRESSION_FLAG: u8 = 0x01;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Base58CheckPayloadType {
+    P2pkhAddress(Bip32KeyNetwork),
+    P2shAddress(Bip32KeyNetwork),
+    // The flag indicates whether the corresponding public key is compressed.
+    WifPrivateKey(Bip32KeyNetwork, bool),
+    ExtendedKey(Bip32KeyVersion),
+    Unknown,
+}
+
+impl Base58CheckPayloadType {
+    pub fn from_payload(payload: &[u8]) -> Self {
+        // Payloads are identified by their version bytes and length; the payload excludes the checksum.
+        match (payload.len(), payload.first()) {
+            (21, Some(&addresses::MAIN_NET_P2PKH_VERSION)) => {
+                Self::P2pkhAddress(Bip32KeyNetwork::MainNet)
+            }
+            (21, Some(&addresses::TEST_NET_P2PKH_VERSION)) => {
+                Self::P2pkhAddress(Bip32KeyNetwork::TestNet)
+            }
+            (21, Some(&addresses::MAIN_NET_P2SH_VERSION)) => {
+                Self::P2shAddress(Bip32KeyNetwork::MainNet)
+            }
+            (21, Some(&addresses::TEST_NET_P2SH_VERSION)) => {
+                Self::P2shAddress(Bip32KeyNetwork::TestNet)
+            }
+            (33, Some(&MAIN_NET_WIF_VERSION)) => {
+                Self::WifPrivateKey(Bip32KeyNetwork::MainNet, false)
+            }
+            (33, Some(&TEST_NET_WIF_VERSION)) => {
+                Self::WifPrivateKey(Bip32KeyNetwork::TestNet, false)
+            }
+            (34, Some(&MAIN_NET_WIF_VERSION)) if payload[33] == WIF_COMPRESSION_FLAG => {
+                Self::WifPrivateKey(Bip32KeyNetwork::MainNet, true)
+            }
+            (34, Some(&TEST_NET_WIF_VERSION)) if payload[33] == WIF_COMPRESSION_FLAG => {
+                Self::WifPrivateKey(Bip32KeyNetwork::TestNet, true)
+            }
+            (78, _) => {
+                let mut version_bytes = [0u8; 4];
+                version_bytes.copy_from_slice(&payload[..4]);
+                match Bip32KeyVersion::try_from(u32::from_be_bytes(version_bytes)) {
+                    Ok(v) => Self::ExtendedKey(v),
+                    Err(_) => Self::Unknown,
+                }
+            }
+            _ => Self::Unknown,
+        }
+    }
+}
+
+pub fn double_sha256(bytes: &[u8]) -> [u8; 32] {
+    // Bitcoin's transaction IDs, signature hashes and checksums are SHA256(SHA256(data)).
+    let mut sha256 = Sha256::new();
+    let hash = sha256.get_hash_of(bytes);
+    sha256.reset().get_hash_of(&hash)
+}
+
+pub fn write_compact_size(value: u64, buffer: &mut Vec<u8>) {
+    // Values below 0xFD are a single byte; larger values are a 0xFD, 0xFE or 0xFF marker followed by a little-endian
+    // u16, u32 or u64 respectively.
+    if value < 0xFD {
+        buffer.push(value as u8);
+    } else if value <= 0xFFFF {
+        buffer.push(0xFD);
+        buffer.extend((value as u16).to_le_bytes());
+    } else if value <= 0xFFFFFFFF {
+        buffer.push(0xFE);
+        buffer.extend((value as u32).to_le_bytes());
+    } else {
+        buffer.push(0xFF);
+        buffer.extend(value.to_le_bytes());
+    }
+}
+
+pub fn try_read_compact_size(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    // Returns the value, and the remaining bytes. Values must use the shortest possible encoding.
+    let (length, minimum) = match bytes.first() {
+        Some(0xFD) => (2, 0xFD),
+        Some(0xFE) => (4, 0x10000),
+        Some(0xFF) => (8, 0x100000000),
+        Some(b) => return Some((*b as u64, &bytes[1..])),
+        None => return None,
+    };
+
+    if bytes.len() < 1 + length {
+        return None;
+    }
+
+    let mut value_bytes = [0u8; 8];
+    value_bytes[..length].copy_from_slice(&bytes[1..1 + length]);
+    let value = u64::from_le_bytes(value_bytes);
+    if value < minimum {
+        None
+    } else {
+        Some((value, &bytes[1 + length..]))
+    }
+}
 
 pub fn calculate_checksum_for(bytes: &[u8]) -> [u8; 4] {
     let mut hasher = Sha256::new();
@@ -70,11 +183,101 @@ pub fn base_58_encode_with_checksum(bytes: &[u8]) -> Vec<u16> {
     let mut integer = numeric_collector.extract_big_unsigned();
 
     // Build a base-58 string from the big integer.
-    let base_58_string =
+    let mut base_58_string =
         NumericBase::BASE_58.build_string_from_big_unsigned(&mut integer, false, 0);
 
+    // The integer drops leading zero bytes; Base58Check encodes each as a leading '1' (base-58's zero digit), which is
+    // what gives P2PKH addresses, with their zero version byte, their leading '1'.
+    let leading_zeroes = bytes.iter().take_while(|b| **b == 0).count();
+    if leading_zeroes > 0 {
+        base_58_string.splice(0..0, (0..leading_zeroes).map(|_| b'1' as u16));
+    }
+
     // Zero the integer; we're done with it.
     integer.zero();
 
     base_58_string
 }
+
+pub fn try_base_58_decode_with_checksum(string: &[u16]) -> Option<Vec<u8>> {
+    // Returns the payload with its checksum removed, if the string is valid base-58 and its checksum passes.
+    // Each leading '1' (base-58's zero digit) is a leading zero byte, which the integer would otherwise drop.
+    let leading_zeroes = string.iter().take_while(|c| **c == b'1' as u16).count();
+
+    // Build a numeric collector for combining the digits.
+    let mut numeric_collector = NumericCollector::with_byte_capacity(string.len());
+    for character in &string[leading_zeroes..] {
+        match NumericBase::BASE_58.value_from(*character) {
+            Some(v) => {
+                _ = numeric_collector.try_add_round(v, NumericCollectorRoundBase::SubByte(58))
+            }
+            None => return None,
+        }
+    }
+
+    // Extract the underlying big unsigned integer, and copy its bytes after the leading zeroes.
+    let mut integer = numeric_collector.extract_big_unsigned();
+    let mut bytes = vec![0u8; leading_zeroes];
+    let copied = if integer.is_non_zero() {
+        bytes.resize(leading_zeroes + integer.byte_count(), 0);
+        integer.try_copy_be_bytes_to(&mut bytes[leading_zeroes..])
+    } else {
+        true
+    };
+
+    // Zero the integer; we're done with it.
+    integer.zero();
+
+    if !copied || !validate_checksum_in(&bytes).0 {
+        bytes.fill(0);
+        return None;
+    }
+
+    bytes.truncate(bytes.len() - 4);
+    Some(bytes)
+}
+
+pub fn wif_encode(
+    private_key: &[u8; 32],
+    network: Bip32KeyNetwork,
+    is_compressed: bool,
+) -> Vec<u16> {
+    let mut payload = [0u8; 34];
+    payload[0] = match network {
+        Bip32KeyNetwork::MainNet => MAIN_NET_WIF_VERSION,
+        Bip32KeyNetwork::TestNet => TEST_NET_WIF_VERSION,
+    };
+
+    payload[1..33].copy_from_slice(private_key);
+    payload[33] = WIF_COMPRESSION_FLAG;
+
+    let wif = base_58_encode_with_checksum(if is_compressed {
+        &payload
+    } else {
+        &payload[..33]
+    });
+
+    // Zero the payload; it contains the private key.
+    payload.fill(0);
+    wif
+}
+
+pub fn try_wif_decode(string: &[u16]) -> Option<([u8; 32], Bip32KeyNetwork, bool)> {
+    // Returns the private key, its network, and whether its public key should be compressed.
+    let mut payload = match try_base_58_decode_with_checksum(string) {
+        Some(p) => p,
+        None => return None,
+    };
+
+    let result = match Base58CheckPayloadType::from_payload(&payload) {
+        Base58CheckPayloadType::WifPrivateKey(network, is_compressed) => {
+            let mut private_key = [0u8; 32];
+            private_key.copy_from_slice(&payload[1..33]);
+            Some((private_key, network, is_compressed))
+        }
+        _ => None,
+    };
+
+    payload.fill(0);
+    result
+}
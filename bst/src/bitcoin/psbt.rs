@@ -0,0 +1,1699 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    hd_wallets::{
+        Bip32CkdContext, Bip32DerivationPathPoint, Bip32KeyType, Bip32SerializedExtendedKey,
+    },
+    signature_hashes::{
+        self, SIGHASH_ALL, SIGHASH_ANYONECANPAY, SIGHASH_DEFAULT, SIGHASH_NONE,
+        SIGHASH_OUTPUT_MASK, SIGHASH_SINGLE,
+    },
+    taproot,
+    transactions::{ScriptType, Transaction, TransactionInput, TransactionOutput, MAX_MONEY},
+    try_read_compact_size, write_compact_size, Hash160,
+};
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::{self, ecdsa, schnorr},
+    hashing::{Hasher, Sha256},
+    String16,
+};
+use alloc::vec::Vec;
+use macros::s16;
+
+// Partially Signed Bitcoin Transactions (BIP 174). A PSBT is a magic prefix, followed by a global key-value map, a map
+// for each of the unsigned transaction's inputs, and a map for each of its outputs. Each map is a sequence of
+// length-prefixed keys and values, terminated by a zero byte; each key is a compact size type, followed by key data.
+// Version 0 PSBTs contain the unsigned transaction; version 2 PSBTs (BIP 370) store each of its fields separately.
+pub const MAGIC: [u8; 5] = [0x70, 0x73, 0x62, 0x74, 0xFF];
+
+pub const GLOBAL_UNSIGNED_TX: u64 = 0x00;
+pub const GLOBAL_XPUB: u64 = 0x01;
+pub const GLOBAL_TX_VERSION: u64 = 0x02;
+pub const GLOBAL_FALLBACK_LOCKTIME: u64 = 0x03;
+pub const GLOBAL_INPUT_COUNT: u64 = 0x04;
+pub const GLOBAL_OUTPUT_COUNT: u64 = 0x05;
+pub const GLOBAL_TX_MODIFIABLE: u64 = 0x06;
+pub const GLOBAL_VERSION: u64 = 0xFB;
+
+pub const IN_NON_WITNESS_UTXO: u64 = 0x00;
+pub const IN_WITNESS_UTXO: u64 = 0x01;
+pub const IN_PARTIAL_SIG: u64 = 0x02;
+pub const IN_SIGHASH_TYPE: u64 = 0x03;
+pub const IN_REDEEM_SCRIPT: u64 = 0x04;
+pub const IN_WITNESS_SCRIPT: u64 = 0x05;
+pub const IN_BIP32_DERIVATION: u64 = 0x06;
+pub const IN_FINAL_SCRIPTSIG: u64 = 0x07;
+pub const IN_FINAL_SCRIPTWITNESS: u64 = 0x08;
+pub const IN_PREVIOUS_TXID: u64 = 0x0E;
+pub const IN_OUTPUT_INDEX: u64 = 0x0F;
+pub const IN_SEQUENCE: u64 = 0x10;
+pub const IN_REQUIRED_TIME_LOCKTIME: u64 = 0x11;
+pub const IN_REQUIRED_HEIGHT_LOCKTIME: u64 = 0x12;
+pub const IN_TAP_KEY_SIG: u64 = 0x13;
+pub const IN_TAP_BIP32_DERIVATION: u64 = 0x16;
+pub const IN_TAP_INTERNAL_KEY: u64 = 0x17;
+pub const IN_TAP_MERKLE_ROOT: u64 = 0x18;
+
+pub const OUT_REDEEM_SCRIPT: u64 = 0x00;
+pub const OUT_WITNESS_SCRIPT: u64 = 0x01;
+pub const OUT_BIP32_DERIVATION: u64 = 0x02;
+pub const OUT_AMOUNT: u64 = 0x03;
+pub const OUT_SCRIPT: u64 = 0x04;
+pub const OUT_TAP_INTERNAL_KEY: u64 = 0x05;
+pub const OUT_TAP_TREE: u64 = 0x06;
+pub const OUT_TAP_BIP32_DERIVATION: u64 = 0x07;
+
+const V2_GLOBAL_TYPES: [u64; 5] = [
+    GLOBAL_TX_VERSION,
+    GLOBAL_FALLBACK_LOCKTIME,
+    GLOBAL_INPUT_COUNT,
+    GLOBAL_OUTPUT_COUNT,
+    GLOBAL_TX_MODIFIABLE,
+];
+
+const V2_INPUT_TYPES: [u64; 5] = [
+    IN_PREVIOUS_TXID,
+    IN_OUTPUT_INDEX,
+    IN_SEQUENCE,
+    IN_REQUIRED_TIME_LOCKTIME,
+    IN_REQUIRED_HEIGHT_LOCKTIME,
+];
+
+const V2_OUTPUT_TYPES: [u64; 2] = [OUT_AMOUNT, OUT_SCRIPT];
+
+const KNOWN_GLOBAL_TYPES: [u64; 8] = [
+    GLOBAL_UNSIGNED_TX,
+    GLOBAL_XPUB,
+    GLOBAL_TX_VERSION,
+    GLOBAL_FALLBACK_LOCKTIME,
+    GLOBAL_INPUT_COUNT,
+    GLOBAL_OUTPUT_COUNT,
+    GLOBAL_TX_MODIFIABLE,
+    GLOBAL_VERSION,
+];
+
+const KNOWN_INPUT_TYPES: [u64; 18] = [
+    IN_NON_WITNESS_UTXO,
+    IN_WITNESS_UTXO,
+    IN_PARTIAL_SIG,
+    IN_SIGHASH_TYPE,
+    IN_REDEEM_SCRIPT,
+    IN_WITNESS_SCRIPT,
+    IN_BIP32_DERIVATION,
+    IN_FINAL_SCRIPTSIG,
+    IN_FINAL_SCRIPTWITNESS,
+    IN_TAP_KEY_SIG,
+    IN_TAP_BIP32_DERIVATION,
+    IN_TAP_INTERNAL_KEY,
+    IN_TAP_MERKLE_ROOT,
+    IN_PREVIOUS_TXID,
+    IN_OUTPUT_INDEX,
+    IN_SEQUENCE,
+    IN_REQUIRED_TIME_LOCKTIME,
+    IN_REQUIRED_HEIGHT_LOCKTIME,
+];
+
+const KNOWN_OUTPUT_TYPES: [u64; 8] = [
+    OUT_REDEEM_SCRIPT,
+    OUT_WITNESS_SCRIPT,
+    OUT_BIP32_DERIVATION,
+    OUT_AMOUNT,
+    OUT_SCRIPT,
+    OUT_TAP_INTERNAL_KEY,
+    OUT_TAP_TREE,
+    OUT_TAP_BIP32_DERIVATION,
+];
+
+// Finalized inputs only retain their UTXOs, their final scripts, their version 2 transaction fields, and unknown pairs.
+const FINALIZED_INPUT_TYPES: [u64; 9] = [
+    IN_NON_WITNESS_UTXO,
+    IN_WITNESS_UTXO,
+    IN_FINAL_SCRIPTSIG,
+    IN_FINAL_SCRIPTWITNESS,
+    IN_PREVIOUS_TXID,
+    IN_OUTPUT_INDEX,
+    IN_SEQUENCE,
+    IN_REQUIRED_TIME_LOCKTIME,
+    IN_REQUIRED_HEIGHT_LOCKTIME,
+];
+
+// Locktimes below the threshold are block heights; those above it are UNIX timestamps.
+const LOCKTIME_THRESHOLD: u32 = 500_000_000;
+
+// A version 2 PSBT's modifiable flags indicate whether inputs and outputs can still be added (BIP 370).
+const INPUTS_MODIFIABLE: u8 = 0x01;
+const OUTPUTS_MODIFIABLE: u8 = 0x02;
+const HAS_SIGHASH_SINGLE: u8 = 0x04;
+
+const OP_0: u8 = 0x00;
+const OP_1: u8 = 0x51;
+const OP_16: u8 = 0x60;
+const OP_CHECKMULTISIG: u8 = 0xAE;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PsbtKeyValuePair {
+    key: Vec<u8>,
+    value: Vec<u8>,
+}
+
+impl PsbtKeyValuePair {
+    pub fn from(key: Vec<u8>, value: Vec<u8>) -> Self {
+        Self { value, key }
+    }
+
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    pub fn key_type(&self) -> u64 {
+        // Keys are validated to start with a compact size type when they're read.
+        try_read_compact_size(&self.key).unwrap().0
+    }
+
+    pub fn key_data(&self) -> &[u8] {
+        try_read_compact_size(&self.key).unwrap().1
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PsbtMap {
+    pairs: Vec<PsbtKeyValuePair>,
+}
+
+impl PsbtMap {
+    pub fn new() -> Self {
+        Self { pairs: Vec::new() }
+    }
+
+    pub fn get(&self, key_type: u64) -> Option<&[u8]> {
+        // Returns the value for a key type without key data.
+        self.pairs
+            .iter()
+            .find(|p| p.key_type() == key_type && p.key_data().is_empty())
+            .map(|p| p.value())
+    }
+
+    pub fn get_all(&self, key_type: u64) -> impl Iterator<Item = &PsbtKeyValuePair> {
+        self.pairs.iter().filter(move |p| p.key_type() == key_type)
+    }
+
+    pub fn insert(&mut self, key_type: u64, key_data: &[u8], value: Vec<u8>) {
+        // Replaces the value of an existing pair with the same key.
+        let mut key = Vec::with_capacity(9 + key_data.len());
+        write_compact_size(key_type, &mut key);
+        key.extend(key_data);
+        match self.pairs.iter_mut().find(|p| p.key == key) {
+            Some(p) => p.value = value,
+            None => self.pairs.push(PsbtKeyValuePair::from(key, value)),
+        }
+    }
+
+    pub fn merge(&mut self, other: &Self) {
+        // Adds the other map's pairs, where this map doesn't already have a value for their key.
+        for pair in &other.pairs {
+            if !self.pairs.iter().any(|p| p.key == pair.key) {
+                self.pairs.push(pair.clone());
+            }
+        }
+    }
+
+    pub fn unknown_pair_count(&self, known_types: &[u64]) -> usize {
+        self.pairs
+            .iter()
+            .filter(|p| !known_types.contains(&p.key_type()))
+            .count()
+    }
+
+    fn contains_any(&self, key_types: &[u64]) -> bool {
+        self.pairs.iter().any(|p| key_types.contains(&p.key_type()))
+    }
+
+    fn try_deserialize(mut bytes: &[u8]) -> Result<(Self, &[u8]), String16<'static>> {
+        // Returns the map, and the bytes following its terminator.
+        let mut map = Self::new();
+        loop {
+            let (key, remainder) = try_read_length_prefixed(bytes)?;
+            if key.is_empty() {
+                // A zero length key is the map's terminator.
+                return Ok((map, remainder));
+            }
+
+            if try_read_compact_size(key).is_none() {
+                return Err(s16!("The PSBT contains a key with an invalid type."));
+            }
+
+            if map.pairs.iter().any(|p| p.key == key) {
+                return Err(s16!("The PSBT contains a duplicate key."));
+            }
+
+            let (value, remainder) = try_read_length_prefixed(remainder)?;
+            map.pairs
+                .push(PsbtKeyValuePair::from(key.into(), value.into()));
+            bytes = remainder;
+        }
+    }
+
+    fn serialize_to(&self, bytes: &mut Vec<u8>) {
+        for pair in &self.pairs {
+            write_compact_size(pair.key.len() as u64, bytes);
+            bytes.extend(&pair.key);
+            write_compact_size(pair.value.len() as u64, bytes);
+            bytes.extend(&pair.value);
+        }
+
+        bytes.push(0x00);
+    }
+
+    fn try_validate_key_data_lengths(
+        &self,
+        key_data_lengths: &[(u64, &[usize])],
+    ) -> Result<(), String16<'static>> {
+        // Each known type has a fixed set of valid key data lengths; most have no key data at all.
+        for pair in &self.pairs {
+            if let Some((_, l)) = key_data_lengths.iter().find(|(t, _)| *t == pair.key_type()) {
+                if !l.contains(&pair.key_data().len()) {
+                    return Err(s16!("The PSBT contains a known key with invalid key data."));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PsbtBip32Derivation {
+    public_key: Vec<u8>,
+    fingerprint: [u8; 4],
+    path: Vec<Bip32DerivationPathPoint>,
+}
+
+impl PsbtBip32Derivation {
+    pub fn fingerprint(&self) -> &[u8; 4] {
+        &self.fingerprint
+    }
+
+    pub fn path(&self) -> &[Bip32DerivationPathPoint] {
+        &self.path
+    }
+
+    pub fn try_derive_public_key_from(
+        &self,
+        key: &Bip32SerializedExtendedKey,
+        key_fingerprint: &[u8; 4],
+    ) -> Option<[u8; 33]> {
+        // Returns the compressed public key, if the key derives this record's public key.
+        match self.try_derive_key_from(key, key_fingerprint) {
+            Some((k, p)) => {
+                k.zero();
+                Some(p)
+            }
+            None => None,
+        }
+    }
+
+    fn try_derive_key_from(
+        &self,
+        key: &Bip32SerializedExtendedKey,
+        key_fingerprint: &[u8; 4],
+    ) -> Option<(Bip32SerializedExtendedKey, [u8; 33])> {
+        // Returns the child key and its compressed public key, if the key derives this record's public key. Records are
+        // relative to a master key fingerprint; the key is either that master key, or an ancestor along the path.
+        let relative_path = if &self.fingerprint == key_fingerprint {
+            &self.path[..]
+        } else {
+            let depth = key.depth() as usize;
+            if depth == 0
+                || self.path.len() <= depth
+                || self.path[depth - 1].numeric_value().to_be_bytes() != key.child_number()
+            {
+                return None;
+            }
+
+            &self.path[depth..]
+        };
+
+        let mut context = Bip32CkdContext::new();
+        let child_key = context.derive(|_| {}, *key, relative_path);
+        context.reset();
+
+        let child_key = match child_key {
+            Ok(k) => k,
+            Err(_) => return None,
+        };
+
+        let public_key = match try_get_public_key(&child_key) {
+            Some(k) => k,
+            None => {
+                child_key.zero();
+                return None;
+            }
+        };
+
+        let matches = match self.public_key.len() {
+            32 => self.public_key[..] == public_key[1..],
+            33 => self.public_key[..] == public_key[..],
+            _ => match secp256k1::try_decode_public_key(&self.public_key) {
+                Some(p) => p.try_serialize_compressed() == Some(public_key),
+                None => false,
+            },
+        };
+
+        if matches {
+            Some((child_key, public_key))
+        } else {
+            child_key.zero();
+            None
+        }
+    }
+
+    fn try_read(
+        public_key: &[u8],
+        value: &[u8],
+        has_leaf_hashes: bool,
+    ) -> Result<Self, String16<'static>> {
+        // Taproot derivations prefix the fingerprint and path with the hashes of the leaves the key is used in.
+        let value = if has_leaf_hashes {
+            match try_read_compact_size(value) {
+                Some((c, r)) if c <= (r.len() / 32) as u64 => &r[c as usize * 32..],
+                _ => return Err(s16!("The PSBT contains an invalid taproot key derivation.")),
+            }
+        } else {
+            value
+        };
+
+        // The master key fingerprint, followed by each little-endian 32 bit index.
+        if value.len() < 4 || value.len() % 4 != 0 {
+            return Err(s16!("The PSBT contains an invalid key derivation."));
+        }
+
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&value[..4]);
+        let path = value[4..]
+            .chunks_exact(4)
+            .map(|c| Bip32DerivationPathPoint::from(u32::from_le_bytes([c[0], c[1], c[2], c[3]])))
+            .collect();
+
+        Ok(Self {
+            public_key: public_key.into(),
+            fingerprint,
+            path,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Psbt {
+    version: u32,
+    global: PsbtMap,
+    inputs: Vec<PsbtMap>,
+    outputs: Vec<PsbtMap>,
+    unsigned_transaction: Transaction,
+}
+
+impl Psbt {
+    pub fn try_deserialize(bytes: &[u8]) -> Result<Self, String16<'static>> {
+        if !bytes.starts_with(&MAGIC) {
+            return Err(s16!("The data does not start with the PSBT magic bytes."));
+        }
+
+        let (global, mut remainder) = PsbtMap::try_deserialize(&bytes[MAGIC.len()..])?;
+        global.try_validate_key_data_lengths(&[
+            (GLOBAL_UNSIGNED_TX, &[0]),
+            (GLOBAL_XPUB, &[78]),
+            (GLOBAL_TX_VERSION, &[0]),
+            (GLOBAL_FALLBACK_LOCKTIME, &[0]),
+            (GLOBAL_INPUT_COUNT, &[0]),
+            (GLOBAL_OUTPUT_COUNT, &[0]),
+            (GLOBAL_TX_MODIFIABLE, &[0]),
+            (GLOBAL_VERSION, &[0]),
+        ])?;
+
+        for pair in global.get_all(GLOBAL_XPUB) {
+            PsbtBip32Derivation::try_read(&pair.key_data()[45..], pair.value(), false)?;
+        }
+
+        let version = match global.get(GLOBAL_VERSION) {
+            None => 0,
+            Some(v) => match try_read_u32(v) {
+                Some(v @ (0 | 2)) => v,
+                _ => return Err(s16!("Only version 0 and version 2 PSBTs are supported.")),
+            },
+        };
+
+        let v0_transaction = if version == 0 {
+            if global.contains_any(&V2_GLOBAL_TYPES) {
+                return Err(s16!("The version 0 PSBT contains version 2 fields."));
+            }
+
+            let transaction = match global.get(GLOBAL_UNSIGNED_TX) {
+                Some(t) => Transaction::try_deserialize(t)?,
+                None => return Err(s16!("The PSBT does not contain an unsigned transaction.")),
+            };
+
+            if transaction
+                .inputs()
+                .iter()
+                .any(|i| !i.script_sig().is_empty() || !i.witness().is_empty())
+            {
+                return Err(s16!("The PSBT's unsigned transaction has signature data."));
+            }
+
+            Some(transaction)
+        } else {
+            if global.contains_any(&[GLOBAL_UNSIGNED_TX]) {
+                return Err(s16!("The version 2 PSBT contains an unsigned transaction."));
+            }
+
+            None
+        };
+
+        let (input_count, output_count) = match &v0_transaction {
+            Some(t) => (t.inputs().len(), t.outputs().len()),
+            None => (
+                try_read_count(&global, GLOBAL_INPUT_COUNT)?,
+                try_read_count(&global, GLOBAL_OUTPUT_COUNT)?,
+            ),
+        };
+
+        // Counts aren't used to pre-allocate; a version 2 PSBT's counts are only bounded by the maps which follow.
+        let mut inputs = Vec::new();
+        for _ in 0..input_count {
+            let (input, rest) = PsbtMap::try_deserialize(remainder)?;
+            input.try_validate_key_data_lengths(&[
+                (IN_NON_WITNESS_UTXO, &[0]),
+                (IN_WITNESS_UTXO, &[0]),
+                (IN_PARTIAL_SIG, &[33, 65]),
+                (IN_SIGHASH_TYPE, &[0]),
+                (IN_REDEEM_SCRIPT, &[0]),
+                (IN_WITNESS_SCRIPT, &[0]),
+                (IN_BIP32_DERIVATION, &[33, 65]),
+                (IN_FINAL_SCRIPTSIG, &[0]),
+                (IN_FINAL_SCRIPTWITNESS, &[0]),
+                (IN_PREVIOUS_TXID, &[0]),
+                (IN_OUTPUT_INDEX, &[0]),
+                (IN_SEQUENCE, &[0]),
+                (IN_REQUIRED_TIME_LOCKTIME, &[0]),
+                (IN_REQUIRED_HEIGHT_LOCKTIME, &[0]),
+                (IN_TAP_KEY_SIG, &[0]),
+                (IN_TAP_BIP32_DERIVATION, &[32]),
+                (IN_TAP_INTERNAL_KEY, &[0]),
+                (IN_TAP_MERKLE_ROOT, &[0]),
+            ])?;
+
+            if version == 0 && input.contains_any(&V2_INPUT_TYPES) {
+                return Err(s16!("The version 0 PSBT contains version 2 fields."));
+            }
+
+            inputs.push(input);
+            remainder = rest;
+        }
+
+        let mut outputs = Vec::new();
+        for _ in 0..output_count {
+            let (output, rest) = PsbtMap::try_deserialize(remainder)?;
+            output.try_validate_key_data_lengths(&[
+                (OUT_REDEEM_SCRIPT, &[0]),
+                (OUT_WITNESS_SCRIPT, &[0]),
+                (OUT_BIP32_DERIVATION, &[33, 65]),
+                (OUT_AMOUNT, &[0]),
+                (OUT_SCRIPT, &[0]),
+                (OUT_TAP_INTERNAL_KEY, &[0]),
+                (OUT_TAP_TREE, &[0]),
+                (OUT_TAP_BIP32_DERIVATION, &[32]),
+            ])?;
+
+            if version == 0 && output.contains_any(&V2_OUTPUT_TYPES) {
+                return Err(s16!("The version 0 PSBT contains version 2 fields."));
+            }
+
+            outputs.push(output);
+            remainder = rest;
+        }
+
+        if !remainder.is_empty() {
+            return Err(s16!("Unexpected bytes follow the PSBT."));
+        }
+
+        let unsigned_transaction = match v0_transaction {
+            Some(t) => t,
+            None => try_build_transaction(&global, &inputs, &outputs)?,
+        };
+
+        let psbt = Self {
+            unsigned_transaction,
+            outputs,
+            inputs,
+            global,
+            version,
+        };
+
+        for i in 0..psbt.inputs.len() {
+            psbt.try_validate_input(i)?;
+        }
+
+        for i in 0..psbt.outputs.len() {
+            psbt.output_bip32_derivations(i)?;
+        }
+
+        Ok(psbt)
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = Vec::from(MAGIC);
+        self.global.serialize_to(&mut bytes);
+        for input in &self.inputs {
+            input.serialize_to(&mut bytes);
+        }
+
+        for output in &self.outputs {
+            output.serialize_to(&mut bytes);
+        }
+
+        bytes
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn unsigned_transaction(&self) -> &Transaction {
+        &self.unsigned_transaction
+    }
+
+    pub fn unknown_pair_count(&self) -> usize {
+        // Unknown and proprietary (0xFC) pairs are retained, and re-serialized as they were read.
+        self.global.unknown_pair_count(&KNOWN_GLOBAL_TYPES)
+            + self
+                .inputs
+                .iter()
+                .map(|i| i.unknown_pair_count(&KNOWN_INPUT_TYPES))
+                .sum::<usize>()
+            + self
+                .outputs
+                .iter()
+                .map(|o| o.unknown_pair_count(&KNOWN_OUTPUT_TYPES))
+                .sum::<usize>()
+    }
+
+    pub fn spent_output(&self, input_index: usize) -> Option<TransactionOutput> {
+        // The output an input spends, if the PSBT includes it. The full previous transaction is preferred; its
+        // transaction ID commits to the amount, whereas a segwit v0 witness UTXO could misstate it.
+        let input = &self.inputs[input_index];
+        let output_index = self.unsigned_transaction.inputs()[input_index].previous_output_index();
+        match input.get(IN_NON_WITNESS_UTXO) {
+            Some(t) => Some(
+                Transaction::try_deserialize(t).unwrap().outputs()[output_index as usize].clone(),
+            ),
+            None => match input.get(IN_WITNESS_UTXO) {
+                Some(o) => Some(try_read_output(o).unwrap()),
+                None => None,
+            },
+        }
+    }
+
+    pub fn total_input_value(&self) -> Option<u64> {
+        // The sum of the spent outputs' values, if they're all known.
+        let mut total = 0u64;
+        for i in 0..self.inputs.len() {
+            match self.spent_output(i) {
+                Some(o) => total += o.value(),
+                None => return None,
+            }
+        }
+
+        Some(total)
+    }
+
+    pub fn fee(&self) -> Option<u64> {
+        // Returns none if an input's value is unknown, or the outputs are worth more than the inputs.
+        match self.total_input_value() {
+            Some(v) => v.checked_sub(self.unsigned_transaction.total_output_value()),
+            None => None,
+        }
+    }
+
+    pub fn input_sighash_type(&self, input_index: usize) -> Option<u32> {
+        self.inputs[input_index]
+            .get(IN_SIGHASH_TYPE)
+            .and_then(try_read_u32)
+    }
+
+    pub fn is_input_finalized(&self, input_index: usize) -> bool {
+        let input = &self.inputs[input_index];
+        input.get(IN_FINAL_SCRIPTSIG).is_some() || input.get(IN_FINAL_SCRIPTWITNESS).is_some()
+    }
+
+    pub fn input_partial_signatures(
+        &self,
+        input_index: usize,
+    ) -> impl Iterator<Item = &PsbtKeyValuePair> {
+        // Each pair's key data is the signing public key, and its value the DER signature and sighash type byte.
+        self.inputs[input_index].get_all(IN_PARTIAL_SIG)
+    }
+
+    pub fn input_taproot_key_signature(&self, input_index: usize) -> Option<&[u8]> {
+        self.inputs[input_index].get(IN_TAP_KEY_SIG)
+    }
+
+    pub fn input_bip32_derivations(&self, input_index: usize) -> Vec<PsbtBip32Derivation> {
+        // Derivations are validated when the PSBT is read.
+        read_bip32_derivations(
+            &self.inputs[input_index],
+            IN_BIP32_DERIVATION,
+            IN_TAP_BIP32_DERIVATION,
+        )
+        .unwrap()
+    }
+
+    pub fn output_bip32_derivations(
+        &self,
+        output_index: usize,
+    ) -> Result<Vec<PsbtBip32Derivation>, String16<'static>> {
+        read_bip32_derivations(
+            &self.outputs[output_index],
+            OUT_BIP32_DERIVATION,
+            OUT_TAP_BIP32_DERIVATION,
+        )
+    }
+
+    pub fn try_identify_change_output(
+        &self,
+        output_index: usize,
+        key: &Bip32SerializedExtendedKey,
+        key_fingerprint: &[u8; 4],
+    ) -> Option<PsbtBip32Derivation> {
+        // An output is change if one of its derivations is derived from the key, and the output pays to that
+        // derivation's public key; the derivations are supplied by the PSBT's creator, and can't be taken on trust.
+        let script_pubkey = self.unsigned_transaction.outputs()[output_index].script_pubkey();
+        let has_script_tree = self.outputs[output_index].get(OUT_TAP_TREE).is_some();
+        for derivation in self.output_bip32_derivations(output_index).unwrap() {
+            if let Some(k) = derivation.try_derive_public_key_from(key, key_fingerprint) {
+                if key_pays_to(&k, script_pubkey, has_script_tree) {
+                    return Some(derivation);
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn has_unverified_segwit_v0_amounts(&self) -> bool {
+        // Whether an unfinalized segwit v0 input only has its witness UTXO; BIP 143 signatures commit to the amount the
+        // witness UTXO states, which a malicious PSBT can understate across separately signed inputs (CVE-2020-14199).
+        (0..self.inputs.len()).any(|i| {
+            !self.is_input_finalized(i)
+                && self.inputs[i].get(IN_NON_WITNESS_UTXO).is_none()
+                && self.spent_output(i).is_some_and(|o| {
+                    matches!(
+                        ScriptType::from_script_pubkey(o.script_pubkey()),
+                        ScriptType::P2wpkh | ScriptType::P2sh
+                    )
+                })
+        })
+    }
+
+    pub fn try_sign(
+        &mut self,
+        key: &Bip32SerializedExtendedKey,
+        key_fingerprint: &[u8; 4],
+        allow_unverified_segwit_v0_amounts: bool,
+    ) -> Result<usize, String16<'static>> {
+        // Signs each unfinalized input which spends a single-key output for a key derived from the extended private
+        // key, returning the number of signatures added. Inputs the key can't sign for are left as they are. Segwit v0
+        // inputs without their previous transaction are only signed if unverified amounts are allowed.
+        if key.try_get_key_version().map(|v| v.key_type()) != Ok(Bip32KeyType::Private) {
+            return Err(s16!("Signing requires an extended private key."));
+        }
+
+        let spent_outputs: Vec<Option<TransactionOutput>> = (0..self.inputs.len())
+            .map(|i| self.spent_output(i))
+            .collect();
+
+        let mut signature_count = 0;
+        for i in 0..self.inputs.len() {
+            if self.is_input_finalized(i) {
+                continue;
+            }
+
+            let spent_output = match &spent_outputs[i] {
+                Some(o) => o,
+                None => continue,
+            };
+
+            let has_script_tree = self.inputs[i].get(IN_TAP_MERKLE_ROOT).is_some();
+            for derivation in self.input_bip32_derivations(i) {
+                let (child_key, public_key) =
+                    match derivation.try_derive_key_from(key, key_fingerprint) {
+                        Some(k) => k,
+                        None => continue,
+                    };
+
+                if !key_pays_to(&public_key, spent_output.script_pubkey(), has_script_tree) {
+                    child_key.zero();
+                    continue;
+                }
+
+                // Private key material is prefixed with a zero byte.
+                let mut private_key = [0u8; 32];
+                private_key.copy_from_slice(&child_key.key_material()[1..]);
+                child_key.zero();
+
+                let signed = self.try_sign_input(
+                    i,
+                    &private_key,
+                    &public_key,
+                    &spent_outputs,
+                    allow_unverified_segwit_v0_amounts,
+                );
+                private_key.fill(0);
+                if signed? {
+                    signature_count += 1;
+                }
+
+                // Single-key outputs only have one key to sign with.
+                break;
+            }
+        }
+
+        Ok(signature_count)
+    }
+
+    fn try_sign_input(
+        &mut self,
+        input_index: usize,
+        private_key: &[u8; 32],
+        public_key: &[u8; 33],
+        spent_outputs: &[Option<TransactionOutput>],
+        allow_unverified_segwit_v0_amounts: bool,
+    ) -> Result<bool, String16<'static>> {
+        // Returns false if the input already has a signature for the key.
+        let spent_output = spent_outputs[input_index].as_ref().unwrap();
+        let sighash_type = self.input_sighash_type(input_index);
+        let input = &mut self.inputs[input_index];
+        if ScriptType::from_script_pubkey(spent_output.script_pubkey()) == ScriptType::P2tr {
+            if input.get(IN_TAP_KEY_SIG).is_some() {
+                return Ok(false);
+            }
+
+            let sighash_type = match sighash_type {
+                None => SIGHASH_DEFAULT,
+                Some(t) if t <= 0xFF => t as u8,
+                Some(_) => return Err(s16!("An input has an invalid sighash type.")),
+            };
+
+            let spent_outputs = match spent_outputs.iter().cloned().collect::<Option<Vec<_>>>() {
+                Some(o) => o,
+                None => {
+                    return Err(s16!(
+                        "Taproot signatures require the outputs spent by every input."
+                    ))
+                }
+            };
+
+            let sighash = signature_hashes::taproot_key_path(
+                &self.unsigned_transaction,
+                input_index,
+                &spent_outputs,
+                sighash_type,
+            )?;
+
+            let mut tweaked_private_key = match taproot::try_tweak_private_key(private_key) {
+                Some(k) => k,
+                None => return Err(s16!("Failed to tweak the private key.")),
+            };
+
+            let signature = schnorr::try_sign(&tweaked_private_key, &sighash, &[0u8; 32]);
+            tweaked_private_key.fill(0);
+            let mut signature = match signature {
+                Some(s) => Vec::from(s),
+                None => return Err(s16!("Failed to sign an input.")),
+            };
+
+            // A 64 byte signature implies SIGHASH_DEFAULT.
+            if sighash_type != SIGHASH_DEFAULT {
+                signature.push(sighash_type);
+            }
+
+            input.insert(IN_TAP_KEY_SIG, &[], signature);
+            if self.version == 2 {
+                update_modifiable_flags(&mut self.global, sighash_type);
+            }
+
+            return Ok(true);
+        }
+
+        if input
+            .get_all(IN_PARTIAL_SIG)
+            .any(|p| p.key_data() == public_key)
+        {
+            return Ok(false);
+        }
+
+        let sighash_type = match sighash_type {
+            None => SIGHASH_ALL as u32,
+            Some(t @ (0x01..=0x03 | 0x81..=0x83)) => t,
+            Some(_) => return Err(s16!("An input has an invalid sighash type.")),
+        };
+
+        // The script code of every single-key ECDSA output type is the P2PKH script for the key.
+        let mut script_code = Vec::with_capacity(25);
+        script_code.extend([0x76, 0xA9, 0x14]);
+        script_code.extend(Hash160::new().hash_160(public_key));
+        script_code.extend([0x88, 0xAC]);
+
+        let sighash = match ScriptType::from_script_pubkey(spent_output.script_pubkey()) {
+            ScriptType::P2pkh => {
+                // Legacy signatures don't commit to the amount; the previous transaction is required to verify it.
+                if input.get(IN_NON_WITNESS_UTXO).is_none() {
+                    return Err(s16!(
+                        "A legacy input does not include its previous transaction."
+                    ));
+                }
+
+                signature_hashes::legacy(
+                    &self.unsigned_transaction,
+                    input_index,
+                    &script_code,
+                    sighash_type,
+                )
+            }
+            script_type => {
+                // BIP 143 signatures commit to the amount, but only the previous transaction proves it's correct.
+                if !allow_unverified_segwit_v0_amounts && input.get(IN_NON_WITNESS_UTXO).is_none() {
+                    return Err(s16!(
+                        "A segwit input does not include its previous transaction; its amount cannot be verified."
+                    ));
+                }
+
+                if script_type == ScriptType::P2sh && input.get(IN_REDEEM_SCRIPT).is_none() {
+                    // The P2WPKH redeem script is needed to finalize the input.
+                    let mut redeem_script = Vec::with_capacity(22);
+                    redeem_script.extend([0x00, 0x14]);
+                    redeem_script.extend(&script_code[3..23]);
+                    input.insert(IN_REDEEM_SCRIPT, &[], redeem_script);
+                }
+
+                signature_hashes::segwit_v0(
+                    &self.unsigned_transaction,
+                    input_index,
+                    &script_code,
+                    spent_output.value(),
+                    sighash_type,
+                )
+            }
+        };
+
+        let mut signature = match ecdsa::try_sign_recoverable(private_key, &sighash) {
+            Some((s, _)) => ecdsa::to_der(&s),
+            None => return Err(s16!("Failed to sign an input.")),
+        };
+
+        signature.push(sighash_type as u8);
+        input.insert(IN_PARTIAL_SIG, public_key, signature);
+        if self.version == 2 {
+            update_modifiable_flags(&mut self.global, sighash_type as u8);
+        }
+
+        Ok(true)
+    }
+
+    pub fn try_combine(&self, other: &Self) -> Result<Self, String16<'static>> {
+        // Combines two PSBTs for the same transaction; pairs are copied from the other PSBT where this one doesn't have
+        // a value for their key. Where both have a value for the same key, this PSBT's value is kept.
+        if self.version != other.version || self.unsigned_transaction != other.unsigned_transaction
+        {
+            return Err(s16!("The PSBTs are for different transactions."));
+        }
+
+        let mut combined = self.clone();
+        combined.global.merge(&other.global);
+        for (input, other_input) in combined.inputs.iter_mut().zip(&other.inputs) {
+            input.merge(other_input);
+        }
+
+        for (output, other_output) in combined.outputs.iter_mut().zip(&other.outputs) {
+            output.merge(other_output);
+        }
+
+        // The other PSBT's UTXOs might not match this PSBT's.
+        for i in 0..combined.inputs.len() {
+            combined.try_validate_input(i)?;
+        }
+
+        Ok(combined)
+    }
+
+    pub fn try_finalize(&mut self) -> Result<usize, String16<'static>> {
+        // Finalizes each input which spends a standard output, and has enough valid signatures to spend it, returning
+        // the number of inputs finalized. Signatures are verified; a PSBT with an invalid signature is rejected.
+        let spent_outputs: Vec<Option<TransactionOutput>> = (0..self.inputs.len())
+            .map(|i| self.spent_output(i))
+            .collect();
+
+        let mut finalized_count = 0;
+        for i in 0..self.inputs.len() {
+            if self.is_input_finalized(i) {
+                continue;
+            }
+
+            let (script_sig, witness) = match &spent_outputs[i] {
+                Some(o) => match self.try_build_final_scripts(i, o, &spent_outputs)? {
+                    Some(s) => s,
+                    None => continue,
+                },
+                None => continue,
+            };
+
+            let input = &mut self.inputs[i];
+            input.pairs.retain(|p| {
+                FINALIZED_INPUT_TYPES.contains(&p.key_type())
+                    || !KNOWN_INPUT_TYPES.contains(&p.key_type())
+            });
+
+            if !script_sig.is_empty() {
+                input.insert(IN_FINAL_SCRIPTSIG, &[], script_sig);
+            }
+
+            if !witness.is_empty() {
+                let mut serialized_witness = Vec::new();
+                write_compact_size(witness.len() as u64, &mut serialized_witness);
+                for item in witness {
+                    write_compact_size(item.len() as u64, &mut serialized_witness);
+                    serialized_witness.extend(item);
+                }
+
+                input.insert(IN_FINAL_SCRIPTWITNESS, &[], serialized_witness);
+            }
+
+            finalized_count += 1;
+        }
+
+        Ok(finalized_count)
+    }
+
+    pub fn try_extract_transaction(&self) -> Result<Transaction, String16<'static>> {
+        // The unsigned transaction, with each input's final script sig and witness.
+        let mut inputs = Vec::with_capacity(self.inputs.len());
+        for (i, input) in self.unsigned_transaction.inputs().iter().enumerate() {
+            if !self.is_input_finalized(i) {
+                return Err(s16!(
+                    "Every input must be finalized before the transaction can be extracted."
+                ));
+            }
+
+            let input_map = &self.inputs[i];
+            inputs.push(TransactionInput::from(
+                *input.previous_txid(),
+                input.previous_output_index(),
+                input_map.get(IN_FINAL_SCRIPTSIG).unwrap_or(&[]).into(),
+                input.sequence(),
+                match input_map.get(IN_FINAL_SCRIPTWITNESS) {
+                    // Final witnesses are validated when the PSBT is read.
+                    Some(w) => try_read_witness(w).unwrap(),
+                    None => Vec::new(),
+                },
+            ));
+        }
+
+        Ok(Transaction::from(
+            self.unsigned_transaction.version(),
+            inputs,
+            self.unsigned_transaction.outputs().into(),
+            self.unsigned_transaction.lock_time(),
+        ))
+    }
+
+    fn try_build_final_scripts(
+        &self,
+        input_index: usize,
+        spent_output: &TransactionOutput,
+        spent_outputs: &[Option<TransactionOutput>],
+    ) -> Result<Option<(Vec<u8>, Vec<Vec<u8>>)>, String16<'static>> {
+        // Returns the script sig and witness for the input, or none if it can't be finalized yet.
+        let input = &self.inputs[input_index];
+        let script_pubkey = spent_output.script_pubkey();
+        let transaction = &self.unsigned_transaction;
+        match ScriptType::from_script_pubkey(script_pubkey) {
+            ScriptType::P2tr => {
+                let signature = match input.get(IN_TAP_KEY_SIG) {
+                    Some(s) => s,
+                    None => return Ok(None),
+                };
+
+                let spent_outputs = match spent_outputs.iter().cloned().collect::<Option<Vec<_>>>()
+                {
+                    Some(o) => o,
+                    None => {
+                        return Err(s16!(
+                            "Taproot signatures require the outputs spent by every input."
+                        ))
+                    }
+                };
+
+                // A 64 byte signature implies SIGHASH_DEFAULT; otherwise, the type follows the signature.
+                let (sighash_type, schnorr_signature) = match signature.len() {
+                    64 => (SIGHASH_DEFAULT, signature),
+                    65 if signature[64] != SIGHASH_DEFAULT => (signature[64], &signature[..64]),
+                    _ => return Err(s16!("The PSBT contains an invalid signature.")),
+                };
+
+                let sighash = signature_hashes::taproot_key_path(
+                    transaction,
+                    input_index,
+                    &spent_outputs,
+                    sighash_type,
+                )?;
+
+                let mut output_key = [0u8; 32];
+                output_key.copy_from_slice(&script_pubkey[2..]);
+                let mut signature_bytes = [0u8; 64];
+                signature_bytes.copy_from_slice(schnorr_signature);
+                if !schnorr::verify(&output_key, &sighash, &signature_bytes) {
+                    return Err(s16!("The PSBT contains an invalid signature."));
+                }
+
+                Ok(Some((Vec::new(), Vec::from([signature.into()]))))
+            }
+            ScriptType::P2pkh => {
+                let signature_hash =
+                    |t| signature_hashes::legacy(transaction, input_index, script_pubkey, t);
+                Ok(self
+                    .try_get_key_hash_signature(
+                        input_index,
+                        &script_pubkey[3..23],
+                        &signature_hash,
+                    )?
+                    .map(|(s, k)| {
+                        let mut script_sig = Vec::new();
+                        push_data(&s, &mut script_sig);
+                        push_data(&k, &mut script_sig);
+                        (script_sig, Vec::new())
+                    }))
+            }
+            ScriptType::P2wpkh => Ok(self
+                .try_get_p2wpkh_witness(input_index, &script_pubkey[2..], spent_output.value())?
+                .map(|w| (Vec::new(), w))),
+            ScriptType::P2wsh => Ok(self
+                .try_get_p2wsh_witness(input_index, &script_pubkey[2..], spent_output.value())?
+                .map(|w| (Vec::new(), w))),
+            ScriptType::P2sh => {
+                // Nested segwit inputs' script sigs only push their redeem script.
+                let redeem_script = match input.get(IN_REDEEM_SCRIPT) {
+                    Some(s) if Hash160::new().hash_160(s) == script_pubkey[2..22] => s,
+                    Some(_) => {
+                        return Err(s16!(
+                            "An input's redeem script does not match the output it spends."
+                        ))
+                    }
+                    None => return Ok(None),
+                };
+
+                let mut script_sig = Vec::new();
+                let witness = match ScriptType::from_script_pubkey(redeem_script) {
+                    ScriptType::P2wpkh => self.try_get_p2wpkh_witness(
+                        input_index,
+                        &redeem_script[2..],
+                        spent_output.value(),
+                    )?,
+                    ScriptType::P2wsh => self.try_get_p2wsh_witness(
+                        input_index,
+                        &redeem_script[2..],
+                        spent_output.value(),
+                    )?,
+                    _ => {
+                        let signature_hash = |t| {
+                            signature_hashes::legacy(transaction, input_index, redeem_script, t)
+                        };
+
+                        // OP_CHECKMULTISIG pops an extra, unused, stack item.
+                        match self.try_get_multisig_signatures(
+                            input_index,
+                            redeem_script,
+                            &signature_hash,
+                        )? {
+                            Some(s) => {
+                                script_sig.push(OP_0);
+                                for signature in s {
+                                    push_data(&signature, &mut script_sig);
+                                }
+                            }
+                            None => return Ok(None),
+                        }
+
+                        push_data(redeem_script, &mut script_sig);
+                        return Ok(Some((script_sig, Vec::new())));
+                    }
+                };
+
+                Ok(witness.map(|w| {
+                    push_data(redeem_script, &mut script_sig);
+                    (script_sig, w)
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn try_get_p2wpkh_witness(
+        &self,
+        input_index: usize,
+        key_hash: &[u8],
+        amount: u64,
+    ) -> Result<Option<Vec<Vec<u8>>>, String16<'static>> {
+        // The script code of a P2WPKH input is the equivalent P2PKH script.
+        let mut script_code = Vec::with_capacity(25);
+        script_code.extend([0x76, 0xA9, 0x14]);
+        script_code.extend(key_hash);
+        script_code.extend([0x88, 0xAC]);
+
+        let signature_hash = |t| {
+            signature_hashes::segwit_v0(
+                &self.unsigned_transaction,
+                input_index,
+                &script_code,
+                amount,
+                t,
+            )
+        };
+
+        Ok(self
+            .try_get_key_hash_signature(input_index, key_hash, &signature_hash)?
+            .map(|(s, k)| Vec::from([s, k])))
+    }
+
+    fn try_get_p2wsh_witness(
+        &self,
+        input_index: usize,
+        script_hash: &[u8],
+        amount: u64,
+    ) -> Result<Option<Vec<Vec<u8>>>, String16<'static>> {
+        // Only multisig witness scripts are supported; the witness script is the last witness item.
+        let witness_script = match self.inputs[input_index].get(IN_WITNESS_SCRIPT) {
+            Some(s) if Sha256::new().get_hash_of(s) == script_hash => s,
+            Some(_) => {
+                return Err(s16!(
+                    "An input's witness script does not match the output it spends."
+                ))
+            }
+            None => return Ok(None),
+        };
+
+        let signature_hash = |t| {
+            signature_hashes::segwit_v0(
+                &self.unsigned_transaction,
+                input_index,
+                witness_script,
+                amount,
+                t,
+            )
+        };
+
+        Ok(self
+            .try_get_multisig_signatures(input_index, witness_script, &signature_hash)?
+            .map(|s| {
+                let mut witness = Vec::from([Vec::new()]);
+                witness.extend(s);
+                witness.push(witness_script.into());
+                witness
+            }))
+    }
+
+    fn try_get_key_hash_signature(
+        &self,
+        input_index: usize,
+        key_hash: &[u8],
+        signature_hash: &dyn Fn(u32) -> [u8; 32],
+    ) -> Result<Option<(Vec<u8>, Vec<u8>)>, String16<'static>> {
+        // Returns the signature, and the public key, for the key with the hash.
+        let mut hash160 = Hash160::new();
+        let public_key = match self
+            .input_partial_signatures(input_index)
+            .find(|p| hash160.hash_160(p.key_data()) == key_hash)
+        {
+            Some(p) => p.key_data(),
+            None => return Ok(None),
+        };
+
+        Ok(self
+            .try_get_verified_signature(input_index, public_key, signature_hash)?
+            .map(|s| (s, public_key.into())))
+    }
+
+    fn try_get_multisig_signatures(
+        &self,
+        input_index: usize,
+        script: &[u8],
+        signature_hash: &dyn Fn(u32) -> [u8; 32],
+    ) -> Result<Option<Vec<Vec<u8>>>, String16<'static>> {
+        // Returns the threshold number of signatures, in the order of their keys in the script.
+        let (threshold, public_keys) = match try_read_multisig_script(script) {
+            Some(m) => m,
+            None => return Ok(None),
+        };
+
+        let mut signatures = Vec::with_capacity(threshold);
+        for public_key in public_keys {
+            if signatures.len() == threshold {
+                break;
+            }
+
+            if let Some(s) =
+                self.try_get_verified_signature(input_index, public_key, signature_hash)?
+            {
+                signatures.push(s);
+            }
+        }
+
+        if signatures.len() == threshold {
+            Ok(Some(signatures))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn try_get_verified_signature(
+        &self,
+        input_index: usize,
+        public_key: &[u8],
+        signature_hash: &dyn Fn(u32) -> [u8; 32],
+    ) -> Result<Option<Vec<u8>>, String16<'static>> {
+        // Returns the key's partial signature, if there is one; its DER signature is followed by its sighash type.
+        let signature = match self
+            .input_partial_signatures(input_index)
+            .find(|p| p.key_data() == public_key)
+        {
+            Some(p) => p.value(),
+            None => return Ok(None),
+        };
+
+        let is_valid = match (
+            signature.split_last(),
+            secp256k1::try_decode_public_key(public_key),
+        ) {
+            (Some((t, s)), Some(k)) => match ecdsa::try_from_der(s) {
+                Some(s) => ecdsa::verify(&k, &signature_hash(*t as u32), &s),
+                None => false,
+            },
+            _ => false,
+        };
+
+        if is_valid {
+            Ok(Some(signature.into()))
+        } else {
+            Err(s16!("The PSBT contains an invalid signature."))
+        }
+    }
+
+    fn try_validate_input(&self, input_index: usize) -> Result<(), String16<'static>> {
+        let input = &self.inputs[input_index];
+        let outpoint = &self.unsigned_transaction.inputs()[input_index];
+        let non_witness_utxo = match input.get(IN_NON_WITNESS_UTXO) {
+            Some(t) => {
+                // The previous transaction must be the one the input spends.
+                let transaction = Transaction::try_deserialize(t)?;
+                if &transaction.txid() != outpoint.previous_txid()
+                    || outpoint.previous_output_index() as usize >= transaction.outputs().len()
+                {
+                    return Err(s16!(
+                        "An input's previous transaction does not match the output it spends."
+                    ));
+                }
+
+                Some(transaction.outputs()[outpoint.previous_output_index() as usize].clone())
+            }
+            None => None,
+        };
+
+        if let Some(o) = input.get(IN_WITNESS_UTXO) {
+            let witness_utxo = match try_read_output(o) {
+                Some(o) => o,
+                None => return Err(s16!("The PSBT contains an invalid witness UTXO.")),
+            };
+
+            if non_witness_utxo.is_some_and(|o| o != witness_utxo) {
+                return Err(s16!(
+                    "An input's witness UTXO does not match its previous transaction."
+                ));
+            }
+        }
+
+        if input.get(IN_SIGHASH_TYPE).is_some_and(|v| v.len() != 4) {
+            return Err(s16!("The PSBT contains an invalid sighash type."));
+        }
+
+        if input
+            .get(IN_FINAL_SCRIPTWITNESS)
+            .is_some_and(|w| try_read_witness(w).is_none())
+        {
+            return Err(s16!("The PSBT contains an invalid final witness."));
+        }
+
+        read_bip32_derivations(input, IN_BIP32_DERIVATION, IN_TAP_BIP32_DERIVATION)?;
+        Ok(())
+    }
+}
+
+pub fn try_get_public_key(key: &Bip32SerializedExtendedKey) -> Option<[u8; 33]> {
+    // Returns the compressed public key for an extended key, or none if its key material is invalid; private key
+    // material is prefixed with a zero byte, and must be in the range 0 < k < n.
+    let key_material = key.key_material();
+    match key.try_get_key_version() {
+        Ok(v) => match v.key_type() {
+            Bip32KeyType::Private if key_material[0] == 0x00 => {
+                secp256k1::try_derive_serialized_public_key(&key_material[1..])
+            }
+            Bip32KeyType::Private => None,
+            Bip32KeyType::Public => {
+                // Public key material must be a compressed point on the curve.
+                match secp256k1::try_decode_public_key(key_material) {
+                    Some(_) => {
+                        let mut public_key = [0u8; 33];
+                        public_key.copy_from_slice(key_material);
+                        Some(public_key)
+                    }
+                    None => None,
+                }
+            }
+        },
+        Err(_) => None,
+    }
+}
+
+pub fn key_pays_to(public_key: &[u8; 33], script_pubkey: &[u8], has_script_tree: bool) -> bool {
+    // Whether the script is a single-key P2PKH, P2SH-P2WPKH, P2WPKH or BIP 86 P2TR output for the key.
+    let mut hash160 = Hash160::new();
+    let key_hash = hash160.hash_160(public_key);
+    match ScriptType::from_script_pubkey(script_pubkey) {
+        ScriptType::P2pkh => script_pubkey[3..23] == key_hash,
+        ScriptType::P2wpkh => script_pubkey[2..] == key_hash,
+        ScriptType::P2sh => {
+            let mut redeem_script = [0u8; 22];
+            redeem_script[1] = 0x14;
+            redeem_script[2..].copy_from_slice(&key_hash);
+            script_pubkey[2..22] == hash160.hash_160(&redeem_script)
+        }
+        ScriptType::P2tr if !has_script_tree => {
+            let mut internal_key = [0u8; 32];
+            internal_key.copy_from_slice(&public_key[1..]);
+            match taproot::try_tweak_public_key(&internal_key) {
+                Some(k) => script_pubkey[2..] == k,
+                None => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn update_modifiable_flags(global: &mut PsbtMap, sighash_type: u8) {
+    // Signatures which commit to all inputs or outputs prevent any more from being added.
+    let flags = global.get(GLOBAL_TX_MODIFIABLE).map_or(0, |f| f[0]);
+    let output_type = sighash_type & SIGHASH_OUTPUT_MASK;
+    let mut updated_flags = flags;
+    if sighash_type & SIGHASH_ANYONECANPAY == 0 {
+        updated_flags &= !INPUTS_MODIFIABLE;
+    }
+
+    if output_type != SIGHASH_NONE {
+        updated_flags &= !OUTPUTS_MODIFIABLE;
+    }
+
+    if output_type == SIGHASH_SINGLE {
+        updated_flags |= HAS_SIGHASH_SINGLE;
+    }
+
+    if updated_flags != flags {
+        global.insert(GLOBAL_TX_MODIFIABLE, &[], Vec::from([updated_flags]));
+    }
+}
+
+fn try_build_transaction(
+    global: &PsbtMap,
+    inputs: &[PsbtMap],
+    outputs: &[PsbtMap],
+) -> Result<Transaction, String16<'static>> {
+    // Builds a version 2 PSBT's unsigned transaction from its fields (BIP 370).
+    const INVALID_FIELD: String16<'static> =
+        s16!("The version 2 PSBT has a missing or invalid transaction field.");
+
+    // The transaction version is signed; negative versions are below the minimum of 2.
+    let version = match global.get(GLOBAL_TX_VERSION).and_then(try_read_u32) {
+        Some(v) if v as i32 >= 2 => v as i32,
+        _ => return Err(INVALID_FIELD),
+    };
+
+    let fallback_lock_time = match global.get(GLOBAL_FALLBACK_LOCKTIME) {
+        Some(l) => match try_read_u32(l) {
+            Some(l) => l,
+            None => return Err(INVALID_FIELD),
+        },
+        None => 0,
+    };
+
+    if global
+        .get(GLOBAL_TX_MODIFIABLE)
+        .is_some_and(|f| f.len() != 1)
+    {
+        return Err(INVALID_FIELD);
+    }
+
+    // The greatest required height and time; either is none if an input with a requirement doesn't allow it.
+    let mut required_height = Some(0);
+    let mut required_time = Some(0);
+    let mut has_requirement = false;
+    let mut transaction_inputs = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let mut previous_txid = [0u8; 32];
+        match input.get(IN_PREVIOUS_TXID) {
+            Some(t) if t.len() == 32 => previous_txid.copy_from_slice(t),
+            _ => return Err(INVALID_FIELD),
+        }
+
+        let previous_output_index = match input.get(IN_OUTPUT_INDEX).and_then(try_read_u32) {
+            Some(i) => i,
+            None => return Err(INVALID_FIELD),
+        };
+
+        let sequence = match input.get(IN_SEQUENCE) {
+            Some(s) => match try_read_u32(s) {
+                Some(s) => s,
+                None => return Err(INVALID_FIELD),
+            },
+            None => 0xFFFFFFFF,
+        };
+
+        let height = match input.get(IN_REQUIRED_HEIGHT_LOCKTIME) {
+            Some(h) => match try_read_u32(h) {
+                Some(h) if h > 0 && h < LOCKTIME_THRESHOLD => Some(h),
+                _ => return Err(INVALID_FIELD),
+            },
+            None => None,
+        };
+
+        let time = match input.get(IN_REQUIRED_TIME_LOCKTIME) {
+            Some(t) => match try_read_u32(t) {
+                Some(t) if t >= LOCKTIME_THRESHOLD => Some(t),
+                _ => return Err(INVALID_FIELD),
+            },
+            None => None,
+        };
+
+        if height.is_some() || time.is_some() {
+            has_requirement = true;
+            required_height = required_height.zip(height).map(|(a, b)| a.max(b));
+            required_time = required_time.zip(time).map(|(a, b)| a.max(b));
+        }
+
+        transaction_inputs.push(TransactionInput::from(
+            previous_txid,
+            previous_output_index,
+            Vec::new(),
+            sequence,
+            Vec::new(),
+        ));
+    }
+
+    // Heights are preferred where every input with a requirement allows one.
+    let lock_time = match (has_requirement, required_height, required_time) {
+        (false, _, _) => fallback_lock_time,
+        (true, Some(h), _) => h,
+        (true, None, Some(t)) => t,
+        (true, None, None) => {
+            return Err(s16!(
+                "The PSBT's inputs have incompatible locktime requirements."
+            ))
+        }
+    };
+
+    let mut transaction_outputs = Vec::with_capacity(outputs.len());
+    for output in outputs {
+        // Amounts are signed 64 bit integers; negative amounts exceed MAX_MONEY as unsigned integers.
+        let mut amount = [0u8; 8];
+        match output.get(OUT_AMOUNT) {
+            Some(a) if a.len() == 8 => amount.copy_from_slice(a),
+            _ => return Err(INVALID_FIELD),
+        }
+
+        let amount = u64::from_le_bytes(amount);
+        match output.get(OUT_SCRIPT) {
+            Some(s) if amount <= MAX_MONEY => {
+                transaction_outputs.push(TransactionOutput::from(amount, s.into()))
+            }
+            _ => return Err(INVALID_FIELD),
+        }
+    }
+
+    Ok(Transaction::from(
+        version,
+        transaction_inputs,
+        transaction_outputs,
+        lock_time,
+    ))
+}
+
+fn try_read_multisig_script(script: &[u8]) -> Option<(usize, Vec<&[u8]>)> {
+    // OP_m <public key>... OP_n OP_CHECKMULTISIG, with 1 <= m <= n <= 16.
+    let (threshold, mut remainder) = match script.split_first() {
+        Some((&m, r)) if (OP_1..=OP_16).contains(&m) => ((m - OP_1 + 1) as usize, r),
+        _ => return None,
+    };
+
+    let mut public_keys = Vec::new();
+    loop {
+        match remainder {
+            [n, OP_CHECKMULTISIG] if (OP_1..=OP_16).contains(n) => {
+                let key_count = (n - OP_1 + 1) as usize;
+                return if key_count == public_keys.len() && threshold <= key_count {
+                    Some((threshold, public_keys))
+                } else {
+                    None
+                };
+            }
+            [l @ (33 | 65), r @ ..] if r.len() >= *l as usize => {
+                public_keys.push(&r[..*l as usize]);
+                remainder = &r[*l as usize..];
+            }
+            _ => return None,
+        }
+    }
+}
+
+fn push_data(data: &[u8], script: &mut Vec<u8>) {
+    // Pushes of up to 75 bytes are prefixed with their length; longer pushes use OP_PUSHDATA1 or OP_PUSHDATA2.
+    if data.len() <= 75 {
+        script.push(data.len() as u8);
+    } else if data.len() <= 0xFF {
+        script.extend([0x4C, data.len() as u8]);
+    } else {
+        script.push(0x4D);
+        script.extend((data.len() as u16).to_le_bytes());
+    }
+
+    script.extend(data);
+}
+
+fn try_read_witness(mut bytes: &[u8]) -> Option<Vec<Vec<u8>>> {
+    // A compact size item count, followed by each length-prefixed item.
+    let (count, remainder) = match try_read_compact_size(bytes) {
+        Some((c, r)) if c <= r.len() as u64 => (c as usize, r),
+        _ => return None,
+    };
+
+    bytes = remainder;
+    let mut items = Vec::with_capacity(count);
+    for _ in 0..count {
+        match try_read_length_prefixed(bytes) {
+            Ok((i, r)) => {
+                items.push(i.into());
+                bytes = r;
+            }
+            Err(_) => return None,
+        }
+    }
+
+    if bytes.is_empty() {
+        Some(items)
+    } else {
+        None
+    }
+}
+
+fn try_read_count(map: &PsbtMap, key_type: u64) -> Result<usize, String16<'static>> {
+    // A version 2 PSBT's input and output counts are compact size integers.
+    match map.get(key_type).and_then(try_read_compact_size) {
+        Some((c, [])) => Ok(c as usize),
+        _ => Err(s16!(
+            "The version 2 PSBT has a missing or invalid input or output count."
+        )),
+    }
+}
+
+fn try_read_u32(bytes: &[u8]) -> Option<u32> {
+    match bytes {
+        [a, b, c, d] => Some(u32::from_le_bytes([*a, *b, *c, *d])),
+        _ => None,
+    }
+}
+
+fn read_bip32_derivations(
+    map: &PsbtMap,
+    key_type: u64,
+    taproot_key_type: u64,
+) -> Result<Vec<PsbtBip32Derivation>, String16<'static>> {
+    let mut derivations = Vec::new();
+    for pair in map.get_all(key_type) {
+        derivations.push(PsbtBip32Derivation::try_read(
+            pair.key_data(),
+            pair.value(),
+            false,
+        )?);
+    }
+
+    for pair in map.get_all(taproot_key_type) {
+        derivations.push(PsbtBip32Derivation::try_read(
+            pair.key_data(),
+            pair.value(),
+            true,
+        )?);
+    }
+
+    Ok(derivations)
+}
+
+fn try_read_output(bytes: &[u8]) -> Option<TransactionOutput> {
+    // A serialized transaction output; an 8 byte little-endian value, and a length-prefixed script.
+    if bytes.len() < 8 {
+        return None;
+    }
+
+    let mut value = [0u8; 8];
+    value.copy_from_slice(&bytes[..8]);
+    let value = u64::from_le_bytes(value);
+    match try_read_compact_size(&bytes[8..]) {
+        Some((l, r)) if l as usize == r.len() && value <= MAX_MONEY => {
+            Some(TransactionOutput::from(value, r.into()))
+        }
+        _ => None,
+    }
+}
+
+fn try_read_length_prefixed(bytes: &[u8]) -> Result<(&[u8], &[u8]), String16<'static>> {
+    match try_read_compact_size(bytes) {
+        Some((l, r)) if l <= r.len() as u64 => Ok((&r[..l as usize], &r[l as usize..])),
+        _ => Err(s16!("The PSBT is truncated.")),
+    }
+}
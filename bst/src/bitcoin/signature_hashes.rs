@@ -0,0 +1,427 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    double_sha256,
+    transactions::{Transaction, TransactionOutput},
+    write_compact_size,
+};
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::schnorr,
+    hashing::{Hasher, Sha256},
+    String16,
+};
+use alloc::vec::Vec;
+use macros::s16;
+
+// A signature's sighash type determines which parts of the transaction it commits to. The low bits select the
+// outputs (all, none, or the one at the input's index), and ANYONECANPAY restricts the inputs to the one being signed.
+// SIGHASH_DEFAULT is only valid for taproot; it commits to the same data as SIGHASH_ALL, without a type byte.
+pub const SIGHASH_DEFAULT: u8 = 0x00;
+pub const SIGHASH_ALL: u8 = 0x01;
+pub const SIGHASH_NONE: u8 = 0x02;
+pub const SIGHASH_SINGLE: u8 = 0x03;
+pub const SIGHASH_ANYONECANPAY: u8 = 0x80;
+
+pub const SIGHASH_OUTPUT_MASK: u8 = 0x1F;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SighashType {
+    Default,
+    All,
+    None,
+    Single,
+    AllAnyoneCanPay,
+    NoneAnyoneCanPay,
+    SingleAnyoneCanPay,
+}
+
+impl SighashType {
+    pub fn value(&self) -> u8 {
+        match self {
+            SighashType::Default => SIGHASH_DEFAULT,
+            SighashType::All => SIGHASH_ALL,
+            SighashType::None => SIGHASH_NONE,
+            SighashType::Single => SIGHASH_SINGLE,
+            SighashType::AllAnyoneCanPay => SIGHASH_ALL | SIGHASH_ANYONECANPAY,
+            SighashType::NoneAnyoneCanPay => SIGHASH_NONE | SIGHASH_ANYONECANPAY,
+            SighashType::SingleAnyoneCanPay => SIGHASH_SINGLE | SIGHASH_ANYONECANPAY,
+        }
+    }
+}
+
+impl Into<String16<'static>> for SighashType {
+    fn into(self) -> String16<'static> {
+        match self {
+            SighashType::Default => s16!("SIGHASH_DEFAULT (Taproot Only)"),
+            SighashType::All => s16!("SIGHASH_ALL"),
+            SighashType::None => s16!("SIGHASH_NONE"),
+            SighashType::Single => s16!("SIGHASH_SINGLE"),
+            SighashType::AllAnyoneCanPay => s16!("SIGHASH_ALL | SIGHASH_ANYONECANPAY"),
+            SighashType::NoneAnyoneCanPay => s16!("SIGHASH_NONE | SIGHASH_ANYONECANPAY"),
+            SighashType::SingleAnyoneCanPay => s16!("SIGHASH_SINGLE | SIGHASH_ANYONECANPAY"),
+        }
+    }
+}
+
+pub fn legacy(
+    transaction: &Transaction,
+    input_index: usize,
+    script_code: &[u8],
+    sighash_type: u32,
+) -> [u8; 32] {
+    // The original signature hash; the transaction is serialized with every script sig emptied, except the signed
+    // input's, which is replaced with the script code. The script code must not contain OP_CODESEPARATOR.
+    let output_type = sighash_type as u8 & SIGHASH_OUTPUT_MASK;
+    let anyone_can_pay = sighash_type as u8 & SIGHASH_ANYONECANPAY != 0;
+    if output_type == SIGHASH_SINGLE && input_index >= transaction.outputs().len() {
+        // Consensus signs the number 1 when there's no corresponding output, rather than failing.
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        return one;
+    }
+
+    let mut preimage = Vec::new();
+    preimage.extend(transaction.version().to_le_bytes());
+    write_compact_size(
+        if anyone_can_pay {
+            1
+        } else {
+            transaction.inputs().len() as u64
+        },
+        &mut preimage,
+    );
+
+    for (i, input) in transaction.inputs().iter().enumerate() {
+        if anyone_can_pay && i != input_index {
+            continue;
+        }
+
+        preimage.extend(input.previous_txid());
+        preimage.extend(input.previous_output_index().to_le_bytes());
+        if i == input_index {
+            write_compact_size(script_code.len() as u64, &mut preimage);
+            preimage.extend(script_code);
+            preimage.extend(input.sequence().to_le_bytes());
+        } else if output_type == SIGHASH_NONE || output_type == SIGHASH_SINGLE {
+            // Other inputs' sequences are zeroed, so they can be updated without invalidating the signature.
+            preimage.extend([0u8; 5]);
+        } else {
+            preimage.push(0x00);
+            preimage.extend(input.sequence().to_le_bytes());
+        }
+    }
+
+    match output_type {
+        SIGHASH_NONE => preimage.push(0x00),
+        SIGHASH_SINGLE => {
+            // The outputs up to the input's index; those before it are blanked, with a value of -1.
+            write_compact_size(input_index as u64 + 1, &mut preimage);
+            for _ in 0..input_index {
+                preimage.extend([0xFFu8; 8]);
+                preimage.push(0x00);
+            }
+
+            serialize_output(&transaction.outputs()[input_index], &mut preimage);
+        }
+        _ => {
+            write_compact_size(transaction.outputs().len() as u64, &mut preimage);
+            for output in transaction.outputs() {
+                serialize_output(output, &mut preimage);
+            }
+        }
+    }
+
+    preimage.extend(transaction.lock_time().to_le_bytes());
+    preimage.extend(sighash_type.to_le_bytes());
+    double_sha256(&preimage)
+}
+
+pub fn segwit_v0(
+    transaction: &Transaction,
+    input_index: usize,
+    script_code: &[u8],
+    amount: u64,
+    sighash_type: u32,
+) -> [u8; 32] {
+    // BIP 143; the preimage commits to the spent amount, and the hashes of the prevouts, sequences and outputs.
+    let hashes = SegwitV0Hashes::from(transaction, input_index, sighash_type);
+    let input = &transaction.inputs()[input_index];
+
+    let mut preimage = Vec::with_capacity(156 + script_code.len());
+    preimage.extend(transaction.version().to_le_bytes());
+    preimage.extend(hashes.hash_prevouts);
+    preimage.extend(hashes.hash_sequence);
+    preimage.extend(input.previous_txid());
+    preimage.extend(input.previous_output_index().to_le_bytes());
+    write_compact_size(script_code.len() as u64, &mut preimage);
+    preimage.extend(script_code);
+    preimage.extend(amount.to_le_bytes());
+    preimage.extend(input.sequence().to_le_bytes());
+    preimage.extend(hashes.hash_outputs);
+    preimage.extend(transaction.lock_time().to_le_bytes());
+    preimage.extend(sighash_type.to_le_bytes());
+    double_sha256(&preimage)
+}
+
+pub fn taproot_key_path(
+    transaction: &Transaction,
+    input_index: usize,
+    spent_outputs: &[TransactionOutput],
+    sighash_type: u8,
+) -> Result<[u8; 32], String16<'static>> {
+    // BIP 341, for a key path spend without an annex; the signature message is hashed after the zero epoch byte.
+    let message = taproot_key_path_message(transaction, input_index, spent_outputs, sighash_type)?;
+    Ok(schnorr::tagged_hash(b"TapSighash", &[&[0x00], &message]))
+}
+
+pub fn taproot_key_path_message(
+    transaction: &Transaction,
+    input_index: usize,
+    spent_outputs: &[TransactionOutput],
+    sighash_type: u8,
+) -> Result<Vec<u8>, String16<'static>> {
+    // BIP 341's SigMsg for a key path spend. Unlike earlier signature hashes, every input's spent output is committed
+    // to, and the single SHA256 hashes of each field are used.
+    let hashes = TaprootHashes::try_from(transaction, input_index, spent_outputs, sighash_type)?;
+
+    // The sighash type, and the transaction's version and locktime.
+    let mut message = Vec::with_capacity(205);
+    message.push(sighash_type);
+    message.extend(transaction.version().to_le_bytes());
+    message.extend(transaction.lock_time().to_le_bytes());
+    for hash in [
+        hashes.sha_prevouts,
+        hashes.sha_amounts,
+        hashes.sha_script_pubkeys,
+        hashes.sha_sequences,
+        hashes.sha_outputs,
+    ]
+    .iter()
+    .flatten()
+    {
+        message.extend(hash);
+    }
+
+    // The spend type; a key path spend, without an annex.
+    message.push(0x00);
+    if sighash_type & SIGHASH_ANYONECANPAY != 0 {
+        let input = &transaction.inputs()[input_index];
+        message.extend(input.previous_txid());
+        message.extend(input.previous_output_index().to_le_bytes());
+        serialize_output(&spent_outputs[input_index], &mut message);
+        message.extend(input.sequence().to_le_bytes());
+    } else {
+        message.extend((input_index as u32).to_le_bytes());
+    }
+
+    if let Some(h) = hashes.sha_single_output {
+        message.extend(h);
+    }
+
+    Ok(message)
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SegwitV0Hashes {
+    hash_prevouts: [u8; 32],
+    hash_sequence: [u8; 32],
+    hash_outputs: [u8; 32],
+}
+
+impl SegwitV0Hashes {
+    pub fn from(transaction: &Transaction, input_index: usize, sighash_type: u32) -> Self {
+        // BIP 143's double SHA256 hashes; each is zeroed when the sighash type excludes the data it commits to.
+        let output_type = sighash_type as u8 & SIGHASH_OUTPUT_MASK;
+        let anyone_can_pay = sighash_type as u8 & SIGHASH_ANYONECANPAY != 0;
+
+        let mut hash_prevouts = [0u8; 32];
+        if !anyone_can_pay {
+            hash_prevouts = double_sha256(&serialize_prevouts(transaction));
+        }
+
+        let mut hash_sequence = [0u8; 32];
+        if !anyone_can_pay && output_type != SIGHASH_SINGLE && output_type != SIGHASH_NONE {
+            hash_sequence = double_sha256(&serialize_sequences(transaction));
+        }
+
+        let mut hash_outputs = [0u8; 32];
+        if output_type != SIGHASH_SINGLE && output_type != SIGHASH_NONE {
+            hash_outputs = double_sha256(&serialize_outputs(transaction.outputs()));
+        } else if output_type == SIGHASH_SINGLE && input_index < transaction.outputs().len() {
+            hash_outputs = double_sha256(&serialize_outputs(
+                &transaction.outputs()[input_index..input_index + 1],
+            ));
+        }
+
+        Self {
+            hash_prevouts,
+            hash_sequence,
+            hash_outputs,
+        }
+    }
+
+    pub fn hash_prevouts(&self) -> &[u8; 32] {
+        &self.hash_prevouts
+    }
+
+    pub fn hash_sequence(&self) -> &[u8; 32] {
+        &self.hash_sequence
+    }
+
+    pub fn hash_outputs(&self) -> &[u8; 32] {
+        &self.hash_outputs
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct TaprootHashes {
+    sha_prevouts: Option<[u8; 32]>,
+    sha_amounts: Option<[u8; 32]>,
+    sha_script_pubkeys: Option<[u8; 32]>,
+    sha_sequences: Option<[u8; 32]>,
+    sha_outputs: Option<[u8; 32]>,
+    sha_single_output: Option<[u8; 32]>,
+}
+
+impl TaprootHashes {
+    pub fn try_from(
+        transaction: &Transaction,
+        input_index: usize,
+        spent_outputs: &[TransactionOutput],
+        sighash_type: u8,
+    ) -> Result<Self, String16<'static>> {
+        // BIP 341's single SHA256 hashes; unlike BIP 143, those the sighash type excludes are omitted from the message.
+        let output_type = sighash_type & SIGHASH_OUTPUT_MASK;
+        let anyone_can_pay = sighash_type & SIGHASH_ANYONECANPAY != 0;
+        if !matches!(sighash_type, 0x00..=0x03 | 0x81..=0x83) {
+            return Err(s16!("The sighash type is not valid for taproot."));
+        }
+
+        if output_type == SIGHASH_SINGLE && input_index >= transaction.outputs().len() {
+            return Err(s16!(
+                "SIGHASH_SINGLE requires an output at the input's index."
+            ));
+        }
+
+        if spent_outputs.len() != transaction.inputs().len() {
+            return Err(s16!(
+                "Taproot signatures require the outputs spent by every input."
+            ));
+        }
+
+        let mut sha256 = Sha256::new();
+        let mut hashes = Self {
+            sha_prevouts: None,
+            sha_amounts: None,
+            sha_script_pubkeys: None,
+            sha_sequences: None,
+            sha_outputs: None,
+            sha_single_output: None,
+        };
+
+        if !anyone_can_pay {
+            let mut amounts = Vec::with_capacity(spent_outputs.len() * 8);
+            let mut script_pubkeys = Vec::new();
+            for output in spent_outputs {
+                amounts.extend(output.value().to_le_bytes());
+                write_compact_size(output.script_pubkey().len() as u64, &mut script_pubkeys);
+                script_pubkeys.extend(output.script_pubkey());
+            }
+
+            hashes.sha_prevouts = Some(sha256.get_hash_of(&serialize_prevouts(transaction)));
+            hashes.sha_amounts = Some(sha256.reset().get_hash_of(&amounts));
+            hashes.sha_script_pubkeys = Some(sha256.reset().get_hash_of(&script_pubkeys));
+            hashes.sha_sequences = Some(
+                sha256
+                    .reset()
+                    .get_hash_of(&serialize_sequences(transaction)),
+            );
+        }
+
+        if output_type != SIGHASH_NONE && output_type != SIGHASH_SINGLE {
+            hashes.sha_outputs = Some(
+                sha256
+                    .reset()
+                    .get_hash_of(&serialize_outputs(transaction.outputs())),
+            );
+        } else if output_type == SIGHASH_SINGLE {
+            hashes.sha_single_output = Some(sha256.reset().get_hash_of(&serialize_outputs(
+                &transaction.outputs()[input_index..input_index + 1],
+            )));
+        }
+
+        Ok(hashes)
+    }
+
+    pub fn sha_prevouts(&self) -> Option<&[u8; 32]> {
+        self.sha_prevouts.as_ref()
+    }
+
+    pub fn sha_amounts(&self) -> Option<&[u8; 32]> {
+        self.sha_amounts.as_ref()
+    }
+
+    pub fn sha_script_pubkeys(&self) -> Option<&[u8; 32]> {
+        self.sha_script_pubkeys.as_ref()
+    }
+
+    pub fn sha_sequences(&self) -> Option<&[u8; 32]> {
+        self.sha_sequences.as_ref()
+    }
+
+    pub fn sha_outputs(&self) -> Option<&[u8; 32]> {
+        self.sha_outputs.as_ref()
+    }
+
+    pub fn sha_single_output(&self) -> Option<&[u8; 32]> {
+        self.sha_single_output.as_ref()
+    }
+}
+
+fn serialize_prevouts(transaction: &Transaction) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(transaction.inputs().len() * 36);
+    for input in transaction.inputs() {
+        bytes.extend(input.previous_txid());
+        bytes.extend(input.previous_output_index().to_le_bytes());
+    }
+
+    bytes
+}
+
+fn serialize_sequences(transaction: &Transaction) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(transaction.inputs().len() * 4);
+    for input in transaction.inputs() {
+        bytes.extend(input.sequence().to_le_bytes());
+    }
+
+    bytes
+}
+
+fn serialize_outputs(outputs: &[TransactionOutput]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for output in outputs {
+        serialize_output(output, &mut bytes);
+    }
+
+    bytes
+}
+
+fn serialize_output(output: &TransactionOutput, bytes: &mut Vec<u8>) {
+    bytes.extend(output.value().to_le_bytes());
+    write_compact_size(output.script_pubkey().len() as u64, bytes);
+    bytes.extend(output.script_pubkey());
+}
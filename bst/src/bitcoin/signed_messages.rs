@@ -0,0 +1,199 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{addresses, double_sha256, hd_wallets::Bip32KeyNetwork, write_compact_size};
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::{ecdsa, ProjectivePoint},
+    String16,
+};
+use alloc::vec::Vec;
+use macros::s16;
+
+// Signed messages, as produced by Bitcoin Core, Electrum, and hardware wallets (BIP 137). A signature is a header byte
+// encoding the ECDSA recovery ID and address type, followed by the 64 byte compact signature, usually shared as base-64.
+pub const SIGNATURE_LENGTH: usize = 1 + ecdsa::SIGNATURE_LENGTH;
+
+const MESSAGE_PREFIX: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SignedMessageAddressType {
+    P2pkhUncompressed,
+    P2pkh,
+    P2shP2wpkh,
+    P2wpkh,
+}
+
+impl SignedMessageAddressType {
+    const fn header_base(&self) -> u8 {
+        match self {
+            SignedMessageAddressType::P2pkhUncompressed => 27,
+            SignedMessageAddressType::P2pkh => 31,
+            SignedMessageAddressType::P2shP2wpkh => 35,
+            SignedMessageAddressType::P2wpkh => 39,
+        }
+    }
+
+    const fn try_from_header(header: u8) -> Option<(Self, u8)> {
+        // The address type, and the recovery ID.
+        match header {
+            27..=30 => Some((SignedMessageAddressType::P2pkhUncompressed, header - 27)),
+            31..=34 => Some((SignedMessageAddressType::P2pkh, header - 31)),
+            35..=38 => Some((SignedMessageAddressType::P2shP2wpkh, header - 35)),
+            39..=42 => Some((SignedMessageAddressType::P2wpkh, header - 39)),
+            _ => None,
+        }
+    }
+
+    pub fn address(&self, public_key: &[u8], network: Bip32KeyNetwork) -> Option<Vec<u16>> {
+        // Uncompressed keys can only be used with legacy addresses.
+        match (self, public_key.len()) {
+            (SignedMessageAddressType::P2pkhUncompressed, 65)
+            | (SignedMessageAddressType::P2pkh, 33) => Some(addresses::p2pkh(public_key, network)),
+            (SignedMessageAddressType::P2shP2wpkh, 33) => Some(addresses::p2sh_p2wpkh(
+                public_key.try_into().unwrap(),
+                network,
+            )),
+            (SignedMessageAddressType::P2wpkh, 33) => {
+                Some(addresses::p2wpkh(public_key.try_into().unwrap(), network))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Into<String16<'static>> for SignedMessageAddressType {
+    fn into(self) -> String16<'static> {
+        match self {
+            SignedMessageAddressType::P2pkhUncompressed => {
+                s16!("Legacy (P2PKH, Uncompressed Public Key)")
+            }
+            SignedMessageAddressType::P2pkh => s16!("Legacy (P2PKH)"),
+            SignedMessageAddressType::P2shP2wpkh => s16!("Nested Segwit (P2SH-P2WPKH)"),
+            SignedMessageAddressType::P2wpkh => s16!("Native Segwit (P2WPKH)"),
+        }
+    }
+}
+
+pub fn message_hash(message: &[u8]) -> [u8; 32] {
+    // SHA256d(prefix || compact size(message length) || message)
+    let mut bytes = Vec::with_capacity(MESSAGE_PREFIX.len() + 9 + message.len());
+    bytes.extend(MESSAGE_PREFIX);
+    write_compact_size(message.len() as u64, &mut bytes);
+    bytes.extend(message);
+    double_sha256(&bytes)
+}
+
+pub fn try_sign(
+    private_key: &[u8; 32],
+    message: &[u8],
+    address_type: SignedMessageAddressType,
+) -> Option<[u8; SIGNATURE_LENGTH]> {
+    let (compact_signature, recovery_id) =
+        match ecdsa::try_sign_recoverable(private_key, &message_hash(message)) {
+            Some(s) => s,
+            None => return None,
+        };
+
+    let mut signature = [0u8; SIGNATURE_LENGTH];
+    signature[0] = address_type.header_base() + recovery_id;
+    signature[1..].copy_from_slice(&compact_signature);
+    Some(signature)
+}
+
+pub fn try_recover_public_key(
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(Vec<u8>, SignedMessageAddressType), String16<'static>> {
+    // Returns the signer's serialized public key, compressed or otherwise as the header indicates, and the address type.
+    if signature.len() != SIGNATURE_LENGTH {
+        return Err(s16!(
+            "Signed message signatures must be 65 bytes in length."
+        ));
+    }
+
+    let (address_type, recovery_id) = match SignedMessageAddressType::try_from_header(signature[0])
+    {
+        Some(h) => h,
+        None => return Err(s16!("The signature's header byte is not recognized.")),
+    };
+
+    let public_point = match ecdsa::try_recover_public_key(
+        &message_hash(message),
+        signature[1..].try_into().unwrap(),
+        recovery_id,
+    ) {
+        Some(p) => p,
+        None => return Err(s16!("No public key could be recovered from the signature.")),
+    };
+
+    let public_key = match address_type {
+        SignedMessageAddressType::P2pkhUncompressed => serialize(&public_point, false),
+        _ => serialize(&public_point, true),
+    };
+
+    Ok((public_key, address_type))
+}
+
+pub fn address_matches_signer(
+    address: &[u16],
+    public_key: &[u8],
+    address_type: SignedMessageAddressType,
+) -> bool {
+    // Electrum signs for segwit addresses with the compressed P2PKH header, so with a compressed key, we accept any
+    // single-key address the key controls, on either network; the public key was recovered from the signature either way.
+    let address_types: &[SignedMessageAddressType] = match address_type {
+        SignedMessageAddressType::P2pkhUncompressed => &[address_type],
+        _ => &[
+            SignedMessageAddressType::P2pkh,
+            SignedMessageAddressType::P2shP2wpkh,
+            SignedMessageAddressType::P2wpkh,
+        ],
+    };
+
+    for network in [Bip32KeyNetwork::MainNet, Bip32KeyNetwork::TestNet] {
+        for candidate_type in address_types {
+            let candidate = match candidate_type.address(public_key, network) {
+                Some(a) => a,
+                None => continue,
+            };
+
+            // Bech32 addresses are compared by their decoded witness programs, as they can be written in either case (but not
+            // mixed case); Base58Check addresses are compared exactly.
+            let matches = match candidate_type {
+                SignedMessageAddressType::P2wpkh => match addresses::try_decode_segwit(address) {
+                    Some(d) => addresses::try_decode_segwit(&candidate) == Some(d),
+                    None => false,
+                },
+                _ => candidate == address,
+            };
+
+            if matches {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+fn serialize(point: &ProjectivePoint, compressed: bool) -> Vec<u8> {
+    // Recovered points are never infinity.
+    if compressed {
+        point.try_serialize_compressed().unwrap()[..].into()
+    } else {
+        point.try_serialize_uncompressed().unwrap()[..].into()
+    }
+}
@@ -0,0 +1,88 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::cryptography::asymmetric::ecc::secp256k1::{
+    schnorr::{self, tagged_hash},
+    FieldElement, ProjectivePoint, Scalar,
+};
+
+// Taproot key path tweaking (BIP 341), for outputs with no script tree, as in BIP 86. The output key is the internal
+// key, plus a tweak committing to the internal key alone: Q = P + int(hash_TapTweak(bytes(P)))G.
+
+pub fn try_tweak_public_key(internal_key: &[u8; 32]) -> Option<[u8; 32]> {
+    // P = lift_x(internal key); the internal key's Y coordinate is implicitly even.
+    let internal_point = match FieldElement::from_be_bytes(internal_key) {
+        Some(x) => match ProjectivePoint::try_from_x(&x, true) {
+            Some(p) => p,
+            None => return None,
+        },
+        None => return None,
+    };
+
+    let tweak = match tweak(internal_key) {
+        Some(t) => t,
+        None => return None,
+    };
+
+    match ProjectivePoint::multiply_generator(&tweak)
+        .add(&internal_point)
+        .try_to_affine()
+    {
+        Some((x, _)) => Some(x.to_be_bytes()),
+        None => None,
+    }
+}
+
+pub fn try_tweak_private_key(private_key: &[u8; 32]) -> Option<[u8; 32]> {
+    // The internal key's private key is negated if its point has an odd Y coordinate, so it matches lift_x(P).
+    let internal_key = match schnorr::try_derive_x_only_public_key(private_key) {
+        Some(k) => k,
+        None => return None,
+    };
+
+    let mut d = match Scalar::from_be_bytes(private_key) {
+        Some(d) => d,
+        None => return None,
+    };
+
+    let mut point = ProjectivePoint::multiply_generator(&d);
+    let y_is_even = point.try_to_affine().unwrap().1.is_even();
+    point.zero();
+    d = Scalar::select(&d.negate(), &d, y_is_even);
+
+    let tweak = match tweak(&internal_key) {
+        Some(t) => t,
+        None => {
+            d.zero();
+            return None;
+        }
+    };
+
+    let mut tweaked = d.add(&tweak);
+    d.zero();
+    if tweaked.is_zero() {
+        return None;
+    }
+
+    let tweaked_bytes = tweaked.to_be_bytes();
+    tweaked.zero();
+    Some(tweaked_bytes)
+}
+
+fn tweak(internal_key: &[u8; 32]) -> Option<Scalar> {
+    // t = int(hash_TapTweak(bytes(P))); it must be less than n.
+    Scalar::from_be_bytes(&tagged_hash(b"TapTweak", &[internal_key]))
+}
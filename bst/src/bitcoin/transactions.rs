@@ -0,0 +1,415 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{double_sha256, try_read_compact_size, write_compact_size};
+use crate::String16;
+use alloc::vec::Vec;
+use macros::s16;
+
+pub const SATOSHIS_PER_BITCOIN: u64 = 100_000_000;
+
+// No more than 21 million bitcoin can ever exist; larger output values are invalid.
+pub const MAX_MONEY: u64 = 21_000_000 * SATOSHIS_PER_BITCOIN;
+
+// Segwit transactions follow their version with a zero marker byte, which would otherwise be an input count of 0,
+// and a non-zero flag byte (BIP 144).
+const SEGWIT_MARKER: u8 = 0x00;
+const SEGWIT_FLAG: u8 = 0x01;
+
+const OP_0: u8 = 0x00;
+const OP_1: u8 = 0x51;
+const OP_16: u8 = 0x60;
+const OP_RETURN: u8 = 0x6A;
+const OP_DUP: u8 = 0x76;
+const OP_EQUAL: u8 = 0x87;
+const OP_EQUALVERIFY: u8 = 0x88;
+const OP_HASH160: u8 = 0xA9;
+const OP_CHECKSIG: u8 = 0xAC;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ScriptType {
+    P2pk,
+    P2pkh,
+    P2sh,
+    P2wpkh,
+    P2wsh,
+    P2tr,
+    // A witness program of a version, or length, without defined semantics.
+    WitnessUnknown(u8),
+    NullData,
+    NonStandard,
+}
+
+impl ScriptType {
+    pub fn from_script_pubkey(script_pubkey: &[u8]) -> Self {
+        match script_pubkey {
+            // <PUSH33 compressed key> OP_CHECKSIG, or <PUSH65 uncompressed key> OP_CHECKSIG
+            [0x21, k @ .., OP_CHECKSIG] if k.len() == 33 => Self::P2pk,
+            [0x41, k @ .., OP_CHECKSIG] if k.len() == 65 => Self::P2pk,
+            // OP_DUP OP_HASH160 <PUSH20 key hash> OP_EQUALVERIFY OP_CHECKSIG
+            [OP_DUP, OP_HASH160, 0x14, h @ .., OP_EQUALVERIFY, OP_CHECKSIG] if h.len() == 20 => {
+                Self::P2pkh
+            }
+            // OP_HASH160 <PUSH20 script hash> OP_EQUAL
+            [OP_HASH160, 0x14, h @ .., OP_EQUAL] if h.len() == 20 => Self::P2sh,
+            // OP_0 <PUSH20 key hash>, or OP_0 <PUSH32 script hash>
+            [OP_0, 0x14, h @ ..] if h.len() == 20 => Self::P2wpkh,
+            [OP_0, 0x20, h @ ..] if h.len() == 32 => Self::P2wsh,
+            // OP_1 <PUSH32 output key>
+            [OP_1, 0x20, k @ ..] if k.len() == 32 => Self::P2tr,
+            // <OP_1 to OP_16> <PUSH2 to PUSH40 witness program>
+            [v @ OP_1..=OP_16, l, p @ ..]
+                if *l as usize == p.len() && p.len() >= 2 && p.len() <= 40 =>
+            {
+                Self::WitnessUnknown(v - OP_1 + 1)
+            }
+            [OP_RETURN, ..] => Self::NullData,
+            _ => Self::NonStandard,
+        }
+    }
+}
+
+impl Into<String16<'static>> for ScriptType {
+    fn into(self) -> String16<'static> {
+        match self {
+            ScriptType::P2pk => s16!("Pay to Public Key (P2PK)"),
+            ScriptType::P2pkh => s16!("Legacy (P2PKH)"),
+            ScriptType::P2sh => s16!("Script Hash (P2SH)"),
+            ScriptType::P2wpkh => s16!("Native Segwit (P2WPKH)"),
+            ScriptType::P2wsh => s16!("Native Segwit Script Hash (P2WSH)"),
+            ScriptType::P2tr => s16!("Taproot (P2TR)"),
+            ScriptType::WitnessUnknown(_) => s16!("Unknown Witness Program"),
+            ScriptType::NullData => s16!("Null Data (OP_RETURN)"),
+            ScriptType::NonStandard => s16!("Non-Standard"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TransactionInput {
+    previous_txid: [u8; 32],
+    previous_output_index: u32,
+    script_sig: Vec<u8>,
+    sequence: u32,
+    witness: Vec<Vec<u8>>,
+}
+
+impl TransactionInput {
+    pub fn from(
+        previous_txid: [u8; 32],
+        previous_output_index: u32,
+        script_sig: Vec<u8>,
+        sequence: u32,
+        witness: Vec<Vec<u8>>,
+    ) -> Self {
+        Self {
+            previous_output_index,
+            previous_txid,
+            script_sig,
+            sequence,
+            witness,
+        }
+    }
+
+    // The txid is in its serialized, internal byte order; it's conventionally displayed reversed.
+    pub fn previous_txid(&self) -> &[u8; 32] {
+        &self.previous_txid
+    }
+
+    pub fn previous_output_index(&self) -> u32 {
+        self.previous_output_index
+    }
+
+    pub fn script_sig(&self) -> &[u8] {
+        &self.script_sig
+    }
+
+    pub fn sequence(&self) -> u32 {
+        self.sequence
+    }
+
+    pub fn witness(&self) -> &[Vec<u8>] {
+        &self.witness
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TransactionOutput {
+    value: u64,
+    script_pubkey: Vec<u8>,
+}
+
+impl TransactionOutput {
+    pub fn from(value: u64, script_pubkey: Vec<u8>) -> Self {
+        Self {
+            script_pubkey,
+            value,
+        }
+    }
+
+    // The value, in satoshis.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn script_pubkey(&self) -> &[u8] {
+        &self.script_pubkey
+    }
+
+    pub fn script_type(&self) -> ScriptType {
+        ScriptType::from_script_pubkey(&self.script_pubkey)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Transaction {
+    // Versions are signed in consensus serialisation.
+    version: i32,
+    inputs: Vec<TransactionInput>,
+    outputs: Vec<TransactionOutput>,
+    lock_time: u32,
+}
+
+impl Transaction {
+    pub fn from(
+        version: i32,
+        inputs: Vec<TransactionInput>,
+        outputs: Vec<TransactionOutput>,
+        lock_time: u32,
+    ) -> Self {
+        Self {
+            lock_time,
+            version,
+            outputs,
+            inputs,
+        }
+    }
+
+    pub fn try_deserialize(bytes: &[u8]) -> Result<Self, String16<'static>> {
+        let (transaction, remainder) = Self::try_deserialize_prefix(bytes)?;
+        if remainder.is_empty() {
+            Ok(transaction)
+        } else {
+            Err(s16!("Unexpected bytes follow the transaction."))
+        }
+    }
+
+    pub fn try_deserialize_prefix(bytes: &[u8]) -> Result<(Self, &[u8]), String16<'static>> {
+        // Returns the transaction, and the bytes following it.
+        const TRUNCATED: String16 = s16!("The transaction is truncated.");
+        let (version, mut remainder) = match try_read_u32(bytes) {
+            Some((v, r)) => (v as i32, r),
+            None => return Err(TRUNCATED),
+        };
+
+        let has_witness = match remainder {
+            [SEGWIT_MARKER, SEGWIT_FLAG, ..] => {
+                remainder = &remainder[2..];
+                true
+            }
+            [SEGWIT_MARKER, f, ..] if *f != 0 => {
+                return Err(s16!("The transaction has an unknown segwit flag."))
+            }
+            _ => false,
+        };
+
+        let (input_count, rest) = try_read_count(remainder, 41)?;
+        remainder = rest;
+
+        let mut inputs = Vec::with_capacity(input_count);
+        for _ in 0..input_count {
+            if remainder.len() < 36 {
+                return Err(TRUNCATED);
+            }
+
+            let mut previous_txid = [0u8; 32];
+            previous_txid.copy_from_slice(&remainder[..32]);
+            let (previous_output_index, rest) = try_read_u32(&remainder[32..]).unwrap();
+            let (script_sig, rest) = try_read_bytes(rest)?;
+            let (sequence, rest) = match try_read_u32(rest) {
+                Some(s) => s,
+                None => return Err(TRUNCATED),
+            };
+
+            inputs.push(TransactionInput::from(
+                previous_txid,
+                previous_output_index,
+                script_sig.into(),
+                sequence,
+                Vec::new(),
+            ));
+            remainder = rest;
+        }
+
+        let (output_count, rest) = try_read_count(remainder, 9)?;
+        remainder = rest;
+
+        let mut outputs = Vec::with_capacity(output_count);
+        for _ in 0..output_count {
+            if remainder.len() < 8 {
+                return Err(TRUNCATED);
+            }
+
+            let mut value = [0u8; 8];
+            value.copy_from_slice(&remainder[..8]);
+            let value = u64::from_le_bytes(value);
+            if value > MAX_MONEY {
+                return Err(s16!("An output's value exceeds 21 million bitcoin."));
+            }
+
+            let (script_pubkey, rest) = try_read_bytes(&remainder[8..])?;
+            outputs.push(TransactionOutput::from(value, script_pubkey.into()));
+            remainder = rest;
+        }
+
+        if has_witness {
+            // Each input has a witness; a compact size item count, followed by each length-prefixed item.
+            for input in inputs.iter_mut() {
+                let (item_count, rest) = try_read_count(remainder, 1)?;
+                remainder = rest;
+                for _ in 0..item_count {
+                    let (item, rest) = try_read_bytes(remainder)?;
+                    input.witness.push(item.into());
+                    remainder = rest;
+                }
+            }
+
+            // A serialization with the segwit marker must actually need it.
+            if inputs.iter().all(|i| i.witness.is_empty()) {
+                return Err(s16!(
+                    "The transaction has the segwit marker, but no witness data."
+                ));
+            }
+        }
+
+        let (lock_time, remainder) = match try_read_u32(remainder) {
+            Some(l) => l,
+            None => return Err(TRUNCATED),
+        };
+
+        Ok((Self::from(version, inputs, outputs, lock_time), remainder))
+    }
+
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
+    pub fn inputs(&self) -> &[TransactionInput] {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &[TransactionOutput] {
+        &self.outputs
+    }
+
+    pub fn lock_time(&self) -> u32 {
+        self.lock_time
+    }
+
+    pub fn has_witness(&self) -> bool {
+        self.inputs.iter().any(|i| !i.witness.is_empty())
+    }
+
+    pub fn total_output_value(&self) -> u64 {
+        // Each value is at most MAX_MONEY, so the sum can't overflow for any transaction which fits in memory.
+        self.outputs.iter().map(|o| o.value).sum()
+    }
+
+    pub fn serialize(&self, include_witness: bool) -> Vec<u8> {
+        // The witness is only serialized if requested, and there's at least one non-empty witness.
+        let include_witness = include_witness && self.has_witness();
+        let mut bytes = Vec::new();
+        bytes.extend(self.version.to_le_bytes());
+        if include_witness {
+            bytes.extend([SEGWIT_MARKER, SEGWIT_FLAG]);
+        }
+
+        write_compact_size(self.inputs.len() as u64, &mut bytes);
+        for input in &self.inputs {
+            bytes.extend(input.previous_txid);
+            bytes.extend(input.previous_output_index.to_le_bytes());
+            write_compact_size(input.script_sig.len() as u64, &mut bytes);
+            bytes.extend(&input.script_sig);
+            bytes.extend(input.sequence.to_le_bytes());
+        }
+
+        write_compact_size(self.outputs.len() as u64, &mut bytes);
+        for output in &self.outputs {
+            bytes.extend(output.value.to_le_bytes());
+            write_compact_size(output.script_pubkey.len() as u64, &mut bytes);
+            bytes.extend(&output.script_pubkey);
+        }
+
+        if include_witness {
+            for input in &self.inputs {
+                write_compact_size(input.witness.len() as u64, &mut bytes);
+                for item in &input.witness {
+                    write_compact_size(item.len() as u64, &mut bytes);
+                    bytes.extend(item);
+                }
+            }
+        }
+
+        bytes.extend(self.lock_time.to_le_bytes());
+        bytes
+    }
+
+    pub fn txid(&self) -> [u8; 32] {
+        // The transaction ID excludes the witness, so signatures can't malleate it.
+        double_sha256(&self.serialize(false))
+    }
+
+    pub fn wtxid(&self) -> [u8; 32] {
+        // The witness transaction ID is equal to the transaction ID for transactions without witness data.
+        double_sha256(&self.serialize(true))
+    }
+
+    pub fn virtual_size(&self) -> usize {
+        // Witness bytes are discounted; the weight is 3 times the stripped size, plus the total size (BIP 141).
+        let weight = self.serialize(false).len() * 3 + self.serialize(true).len();
+        (weight + 3) / 4
+    }
+}
+
+fn try_read_u32(bytes: &[u8]) -> Option<(u32, &[u8])> {
+    if bytes.len() < 4 {
+        return None;
+    }
+
+    let mut value = [0u8; 4];
+    value.copy_from_slice(&bytes[..4]);
+    Some((u32::from_le_bytes(value), &bytes[4..]))
+}
+
+fn try_read_count(
+    bytes: &[u8],
+    minimum_item_length: usize,
+) -> Result<(usize, &[u8]), String16<'static>> {
+    // Counts which couldn't possibly fit in the remaining bytes are rejected before anything is allocated for them.
+    match try_read_compact_size(bytes) {
+        Some((c, r)) if c <= (r.len() / minimum_item_length) as u64 => Ok((c as usize, r)),
+        Some(_) => Err(s16!("The transaction is truncated.")),
+        None => Err(s16!(
+            "The transaction contains an invalid compact size integer."
+        )),
+    }
+}
+
+fn try_read_bytes(bytes: &[u8]) -> Result<(&[u8], &[u8]), String16<'static>> {
+    // A compact size length, followed by that many bytes.
+    let (length, remainder) = try_read_count(bytes, 1)?;
+    Ok((&remainder[..length], &remainder[length..]))
+}
@@ -15,10 +15,11 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 pub mod secp256k1;
+pub mod secp256r1;
 
 mod point;
 
-pub use point::{EllipticCurvePoint, COMPRESSED_Y_IS_EVEN_IDENTIFIER};
+pub use point::{EllipticCurvePoint, COMPRESSED_Y_IS_EVEN_IDENTIFIER, UNCOMPRESSED_IDENTIFIER};
 
 use crate::{
     bits::{try_get_bit_at_index, try_set_bit_at_index},
@@ -30,7 +31,7 @@ use core::{cmp::Ordering, mem::size_of};
 
 const PRIVATE_KEY_PREFIX: u8 = 0x00;
 
-static mut CUBE: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+static CUBE: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     GlobalRuntimeImmutable::from(|| BigUnsigned::from_digits(&[3]));
 
 pub struct EllipticCurvePointAdditionContext {
@@ -304,12 +305,141 @@ impl EllipticCurvePointMultiplicationContext {
         Some(product)
     }
 
+    pub fn try_tweak_add_scalar(&self, scalar: &mut BigUnsigned, tweak: &BigUnsigned) -> bool {
+        if tweak.cmp(self.n) != Ordering::Less {
+            // Tweaks must be in the range 0 <= t < n.
+            return false;
+        }
+
+        // k = k + t (mod n)
+        scalar.add_big_unsigned(tweak);
+        scalar.modulo_big_unsigned(self.n);
+
+        // If the tweak was n - k, the result is zero, which is not a valid private key.
+        scalar.is_non_zero()
+    }
+
+    pub fn try_tweak_multiply_scalar(&self, scalar: &mut BigUnsigned, tweak: &BigUnsigned) -> bool {
+        if tweak.is_zero() || tweak.cmp(self.n) != Ordering::Less {
+            // Multiplicative tweaks must be in the range 0 < t < n.
+            return false;
+        }
+
+        // k = k * t (mod n); n is prime, so the product of two non-zero values is never zero.
+        scalar.multiply_big_unsigned(tweak);
+        scalar.modulo_big_unsigned(self.n);
+        scalar.is_non_zero()
+    }
+
+    pub fn try_tweak_add_point(
+        &mut self,
+        point: &mut EllipticCurvePoint,
+        g_x: &BigUnsigned,
+        g_y: &BigUnsigned,
+        tweak: &BigUnsigned,
+    ) -> bool {
+        if tweak.is_zero() {
+            // P + 0G = P.
+            return !point.is_infinity();
+        }
+
+        // Calculate tG; this fails if the tweak is >= n.
+        let tweak_point = match self.multiply_point(g_x, g_y, tweak) {
+            Some(p) => p,
+            None => return false,
+        };
+
+        // P = P + tG
+        point.add(&tweak_point, &mut self.addition_context);
+        self.addition_context.zero();
+
+        // If P was -tG, the result is infinity, which is not a valid public key.
+        !point.is_infinity()
+    }
+
+    pub fn try_decode_point(&mut self, bytes: &[u8]) -> Option<EllipticCurvePoint> {
+        // Public keys are encoded as a compressed point (a prefix indicating Y's evenness, followed by X),
+        // an uncompressed point (a 0x04 prefix, followed by X and Y), or an 'x-only' point (BIP 340; X alone, with an implied even Y).
+        let coordinate_length = self.addition_context.p.byte_count();
+        let integer_byte_capacity = coordinate_length * 2;
+        let mut y = BigUnsigned::with_byte_capacity(integer_byte_capacity);
+        let x = if bytes.len() == coordinate_length + 1
+            && (bytes[0] == COMPRESSED_Y_IS_EVEN_IDENTIFIER || bytes[0] == 0x03)
+        {
+            let x = BigUnsigned::from_be_bytes(&bytes[1..]);
+            self.calculate_y_from_x(bytes[0] == COMPRESSED_Y_IS_EVEN_IDENTIFIER, &x, &mut y);
+            x
+        } else if bytes.len() == coordinate_length * 2 + 1 && bytes[0] == UNCOMPRESSED_IDENTIFIER {
+            y.copy_be_bytes_from(&bytes[coordinate_length + 1..]);
+            BigUnsigned::from_be_bytes(&bytes[1..coordinate_length + 1])
+        } else if bytes.len() == coordinate_length {
+            let x = BigUnsigned::from_be_bytes(bytes);
+            self.calculate_y_from_x(true, &x, &mut y);
+            x
+        } else {
+            // The input isn't a recognised encoding for this curve.
+            return None;
+        };
+
+        // Calculating Y from X always produces a value, even when there is no point with the X coordinate; we need to check
+        // the result, as well as any explicit Y coordinate, actually satisfies the curve equation.
+        if !self.is_on_curve(&x, &y) {
+            return None;
+        }
+
+        let mut point = EllipticCurvePoint::infinity(integer_byte_capacity);
+        point.set_equal_to_unsigned(&x, &y);
+        if point.is_infinity() {
+            return None;
+        }
+
+        Some(point)
+    }
+
+    pub fn is_on_curve(&mut self, x: &BigUnsigned, y: &BigUnsigned) -> bool {
+        let p = self.addition_context.p;
+        if x.cmp(p) != Ordering::Less || y.cmp(p) != Ordering::Less {
+            // Coordinates must be reduced (mod p).
+            return false;
+        }
+
+        // l = y^2 (mod p)
+        let mut left = y.clone();
+        left.multiply_big_unsigned(y);
+        left.modulo_big_unsigned(p);
+
+        // r = x^3 + ax + b (mod p)
+        let mut right = BigUnsigned::with_byte_capacity(p.byte_count() * 2);
+        self.calculate_curve_right_hand_side(x, &mut right);
+
+        left.cmp(&right) == Ordering::Equal
+    }
+
     pub fn calculate_y_from_x(
         &mut self,
         y_is_even: bool,
         x: &BigUnsigned,
         y_out: &mut BigUnsigned,
     ) {
+        // y_out = x^3 + ax + b (mod p)
+        self.calculate_curve_right_hand_side(x, y_out);
+
+        // sqrt shortcut for curves where p = 3 (mod 4); y = (x^3 + ax + b)^((p + 1) / 4) (mod p)
+        self.addition_context
+            .unsigned_calculator
+            .modpow(y_out, self.i, self.addition_context.p);
+
+        if y_is_even != y_out.is_even() {
+            // Y has two possible values given an X coordinate; if the calculated Y's evenness does not match
+            // the desired evenness from the compressed coordinate flag, we just subtract it from P.
+            // This is equivalent to calculating the difference, as P will always be larger.
+            y_out.difference_big_unsigned(self.addition_context.p);
+        }
+
+        self.working_point.set_infinity();
+    }
+
+    fn calculate_curve_right_hand_side(&mut self, x: &BigUnsigned, y_out: &mut BigUnsigned) {
         // y_out = x
         y_out.set_equal_to(x);
 
@@ -348,24 +478,6 @@ impl EllipticCurvePointMultiplicationContext {
             // y_out = x^3 + ax + b (mod p)
             y_out.modulo_big_unsigned(self.addition_context.p);
         }
-
-        // sqrt shortcut for curve where p mod 3 = 1
-        self.addition_context
-            .unsigned_calculator
-            .modpow(y_out, self.i, self.addition_context.p);
-
-        if y_is_even != y_out.is_even() {
-            // Y has two possible values given an X coordinate; if the calculated Y's evenness does not match
-            // the desired evenness from the compressed coordinate flag, we just subtract it from P.
-            // This is equivalent to calculating the difference, as P will always be larger.
-            y_out.difference_big_unsigned(self.addition_context.p);
-        }
-
-        self.working_point.set_infinity();
-    }
-
-    pub fn borrow_addition_context(&mut self) -> &mut EllipticCurvePointAdditionContext {
-        &mut self.addition_context
     }
 
     fn zero(&mut self) {
@@ -378,5 +490,5 @@ impl EllipticCurvePointMultiplicationContext {
 }
 
 fn cube() -> &'static BigUnsigned {
-    unsafe { CUBE.value() }
+    CUBE.value()
 }
@@ -282,6 +282,7 @@ use super::EllipticCurvePointAdditionContext;
 use crate::integers::{BigSigned, BigUnsigned};
 
 pub const COMPRESSED_Y_IS_EVEN_IDENTIFIER: u8 = 0x02;
+pub const UNCOMPRESSED_IDENTIFIER: u8 = 0x04;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct EllipticCurvePoint {
@@ -324,6 +325,33 @@ impl EllipticCurvePoint {
         Some(buffer)
     }
 
+    pub fn try_serialize_uncompressed<const N: usize>(&self) -> Option<[u8; N]> {
+        // An uncompressed point is represented with both coordinates in full, each padded to half of the buffer after the prefix.
+        let coordinate_length = (N - 1) / 2;
+        if self.is_infinity
+            || N % 2 == 0
+            || self.x.byte_count() > coordinate_length
+            || self.y.byte_count() > coordinate_length
+        {
+            return None;
+        }
+
+        // The coordinates are right-aligned within their halves of the buffer.
+        let mut buffer = [0u8; N];
+        buffer[0] = UNCOMPRESSED_IDENTIFIER;
+        assert!(self
+            .x
+            .try_copy_be_bytes_to(&mut buffer[1..coordinate_length + 1]));
+        assert!(self
+            .y
+            .try_copy_be_bytes_to(&mut buffer[coordinate_length + 1..]));
+        Some(buffer)
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.is_infinity
+    }
+
     pub fn add(
         &mut self,
         addend: &EllipticCurvePoint,
@@ -536,6 +564,7 @@ impl EllipticCurvePoint {
             .divide_big_unsigned_with_signed_modulus(addition_context.p, &mut self.y);
     }
 
+    #[cfg(test)]
     pub unsafe fn set_not_infinity(&mut self) {
         // Only to be used when the coordinates have been manually written to.
         self.is_infinity = false;
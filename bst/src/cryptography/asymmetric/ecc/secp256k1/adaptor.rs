@@ -0,0 +1,236 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    schnorr::{self, challenge, tagged_hash, try_get_x_only_coordinates},
+    ProjectivePoint, Scalar,
+};
+
+// Schnorr adaptor signatures over secp256k1, completing to BIP 340 signatures. A pre-signature is 'locked' to an adaptor point T = tG;
+// anyone who knows the adaptor secret t can complete it to a valid signature, and anyone holding both the pre-signature and the
+// completed signature can extract t. This is the building block for atomic swaps; publishing the signature reveals the secret.
+// There is no finalized standard for Schnorr adaptor signatures, so the pre-signature format (33 byte R || 32 byte s') and the
+// 'SchnorrAdaptor/aux' and 'SchnorrAdaptor/nonce' nonce derivation tags are specific to bst; pre-signatures are not interchangeable
+// with other implementations, although the completed signatures are standard BIP 340 signatures.
+pub const ADAPTOR_POINT_LENGTH: usize = 33;
+pub const ADAPTOR_SECRET_LENGTH: usize = 32;
+pub const PRE_SIGNATURE_LENGTH: usize = 65;
+
+pub fn try_pre_sign(
+    private_key: &[u8; 32],
+    message: &[u8],
+    adaptor_point: &[u8; ADAPTOR_POINT_LENGTH],
+    auxiliary_random: &[u8; 32],
+) -> Option<[u8; PRE_SIGNATURE_LENGTH]> {
+    let adaptor_point_value = match ProjectivePoint::try_from_serialized(adaptor_point) {
+        Some(p) => p,
+        None => return None,
+    };
+
+    let mut d = match Scalar::from_be_bytes(private_key) {
+        Some(d) => d,
+        None => return None,
+    };
+
+    if d.is_zero() {
+        return None;
+    }
+
+    // As in BIP 340; d is negated if P has an odd Y coordinate.
+    let mut public_point = ProjectivePoint::multiply_generator(&d);
+    let (public_key, public_key_y_is_even) = try_get_x_only_coordinates(&public_point).unwrap();
+    public_point.zero();
+    d = Scalar::select(&d.negate(), &d, public_key_y_is_even);
+
+    // The nonce is derived as in BIP 340, but under our own tags, and committing to the adaptor point.
+    let mut t = tagged_hash(b"SchnorrAdaptor/aux", &[auxiliary_random]);
+    let mut d_bytes = d.to_be_bytes();
+    for i in 0..32 {
+        t[i] ^= d_bytes[i];
+    }
+
+    d_bytes.fill(0);
+    let mut nonce_hash = tagged_hash(
+        b"SchnorrAdaptor/nonce",
+        &[&t, &public_key, adaptor_point, message],
+    );
+    let mut k = Scalar::from_be_bytes_reduced(&nonce_hash);
+    nonce_hash.fill(0);
+    t.fill(0);
+
+    // R = k'G + T is the nonce point of the completed signature; k = k' if R has an even Y coordinate, otherwise n - k'.
+    let mut nonce_point = ProjectivePoint::multiply_generator(&k).add(&adaptor_point_value);
+    let (r, r_y_is_even) = match try_get_x_only_coordinates(&nonce_point) {
+        Some(c) if !k.is_zero() => c,
+        _ => {
+            k.zero();
+            d.zero();
+            return None;
+        }
+    };
+
+    // The pre-signature carries R's parity, as completion and extraction negate the adaptor secret when R's Y coordinate is odd.
+    let serialized_nonce_point = nonce_point.try_serialize_compressed().unwrap();
+    nonce_point.zero();
+    k = Scalar::select(&k.negate(), &k, r_y_is_even);
+
+    // s' = k + ed (mod n)
+    let e = challenge(&r, &public_key, message);
+    let s = k.add(&e.multiply(&d));
+    k.zero();
+    d.zero();
+
+    let mut pre_signature = [0u8; PRE_SIGNATURE_LENGTH];
+    pre_signature[..33].copy_from_slice(&serialized_nonce_point);
+    pre_signature[33..].copy_from_slice(&s.to_be_bytes());
+
+    // Verify the pre-signature before returning it, to guard against faults.
+    if verify_pre_signature(&public_key, message, adaptor_point, &pre_signature) {
+        Some(pre_signature)
+    } else {
+        None
+    }
+}
+
+pub fn verify_pre_signature(
+    public_key: &[u8; schnorr::PUBLIC_KEY_LENGTH],
+    message: &[u8],
+    adaptor_point: &[u8; ADAPTOR_POINT_LENGTH],
+    pre_signature: &[u8; PRE_SIGNATURE_LENGTH],
+) -> bool {
+    let public_point = match super::try_decode_public_key(public_key) {
+        Some(p) => p,
+        None => return false,
+    };
+
+    let (nonce_point, adaptor_point, s) = match decode(pre_signature, adaptor_point) {
+        Some(d) => d,
+        None => return false,
+    };
+
+    // s'G - eP must equal R - T when R has an even Y coordinate, and T - R otherwise.
+    let (r, r_y_is_even) = try_get_x_only_coordinates(&nonce_point).unwrap();
+    let e = challenge(&r, public_key, message);
+    let expected = nonce_point.add(&adaptor_point.negate());
+    ProjectivePoint::multiply_generator(&s)
+        .add(&public_point.multiply_variable_time(&e).negate())
+        .equals(&if r_y_is_even {
+            expected
+        } else {
+            expected.negate()
+        })
+}
+
+pub fn try_adapt(
+    pre_signature: &[u8; PRE_SIGNATURE_LENGTH],
+    adaptor_secret: &[u8; ADAPTOR_SECRET_LENGTH],
+) -> Option<[u8; schnorr::SIGNATURE_LENGTH]> {
+    let mut t = match Scalar::from_be_bytes(adaptor_secret) {
+        Some(t) if !t.is_zero() => t,
+        _ => return None,
+    };
+
+    let mut adaptor_point = ProjectivePoint::multiply_generator(&t);
+    let decoded = decode(
+        pre_signature,
+        &adaptor_point.try_serialize_compressed().unwrap(),
+    );
+
+    adaptor_point.zero();
+    let (nonce_point, _, pre_s) = match decoded {
+        Some(d) => d,
+        None => {
+            t.zero();
+            return None;
+        }
+    };
+
+    // s = s' + t when R has an even Y coordinate, and s' - t otherwise.
+    let (r, r_y_is_even) = try_get_x_only_coordinates(&nonce_point).unwrap();
+    t = Scalar::select(&t.negate(), &t, r_y_is_even);
+    let s = pre_s.add(&t);
+    t.zero();
+
+    let mut signature = [0u8; schnorr::SIGNATURE_LENGTH];
+    signature[..32].copy_from_slice(&r);
+    signature[32..].copy_from_slice(&s.to_be_bytes());
+    Some(signature)
+}
+
+pub fn try_extract_secret(
+    pre_signature: &[u8; PRE_SIGNATURE_LENGTH],
+    signature: &[u8; schnorr::SIGNATURE_LENGTH],
+    adaptor_point: &[u8; ADAPTOR_POINT_LENGTH],
+) -> Option<[u8; ADAPTOR_SECRET_LENGTH]> {
+    let (nonce_point, adaptor_point, pre_s) = match decode(pre_signature, adaptor_point) {
+        Some(d) => d,
+        None => return None,
+    };
+
+    // The signature must share the pre-signature's nonce.
+    let (r, r_y_is_even) = try_get_x_only_coordinates(&nonce_point).unwrap();
+    if signature[..32] != r {
+        return None;
+    }
+
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&signature[32..]);
+    let s = match Scalar::from_be_bytes(&s_bytes) {
+        Some(s) => s,
+        None => return None,
+    };
+
+    // t = s - s' when R has an even Y coordinate, and s' - s otherwise; it's only the secret if it matches the adaptor point.
+    let mut t = s.subtract(&pre_s);
+    t = Scalar::select(&t.negate(), &t, r_y_is_even);
+    let mut derived_adaptor_point = ProjectivePoint::multiply_generator(&t);
+    let is_secret = derived_adaptor_point.equals(&adaptor_point);
+    derived_adaptor_point.zero();
+
+    let secret = if is_secret {
+        Some(t.to_be_bytes())
+    } else {
+        None
+    };
+
+    t.zero();
+    secret
+}
+
+fn decode(
+    pre_signature: &[u8; PRE_SIGNATURE_LENGTH],
+    adaptor_point: &[u8; ADAPTOR_POINT_LENGTH],
+) -> Option<(ProjectivePoint, ProjectivePoint, Scalar)> {
+    // R, T and s'; R - T must not be infinity, as k' must not be zero.
+    let nonce_point = match ProjectivePoint::try_from_serialized(&pre_signature[..33]) {
+        Some(p) => p,
+        None => return None,
+    };
+
+    let adaptor_point = match ProjectivePoint::try_from_serialized(adaptor_point) {
+        Some(p) => p,
+        None => return None,
+    };
+
+    if nonce_point.equals(&adaptor_point) {
+        return None;
+    }
+
+    match Scalar::try_from_be_slice(&pre_signature[33..]) {
+        Some(s) => Some((nonce_point, adaptor_point, s)),
+        None => None,
+    }
+}
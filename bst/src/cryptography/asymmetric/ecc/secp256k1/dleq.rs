@@ -0,0 +1,182 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{schnorr::tagged_hash, ProjectivePoint, Scalar};
+
+// Discrete logarithm equality proofs over secp256k1, as specified in BIP 374. A proof shows that A = aG and C = aB share the same
+// secret a, without revealing it; for example, that an adaptor point and an ECDH shared point were derived from the same secret.
+pub const POINT_LENGTH: usize = 33;
+pub const PROOF_LENGTH: usize = 64;
+
+pub fn try_generate_proof(
+    secret: &[u8; 32],
+    base: &[u8; POINT_LENGTH],
+    auxiliary_random: &[u8; 32],
+    message: Option<&[u8; 32]>,
+) -> Option<([u8; POINT_LENGTH], [u8; PROOF_LENGTH])> {
+    // a must be in the range 0 < a < n, and B must be a valid point.
+    let base_point = match ProjectivePoint::try_from_serialized(base) {
+        Some(p) => p,
+        None => return None,
+    };
+
+    let mut a = match Scalar::from_be_bytes(secret) {
+        Some(a) if !a.is_zero() => a,
+        _ => return None,
+    };
+
+    // A = aG, C = aB
+    let public_point = ProjectivePoint::multiply_generator(&a);
+    let shared_point = base_point.multiply(&a);
+    let (public_key, shared_key) = match (
+        public_point.try_serialize_compressed(),
+        shared_point.try_serialize_compressed(),
+    ) {
+        (Some(p), Some(s)) => (p, s),
+        _ => {
+            a.zero();
+            return None;
+        }
+    };
+
+    // t = bytes(a) XOR hash_BIP0374/aux(r); k = int(hash_BIP0374/nonce(t || cbytes(A) || cbytes(C) || m)) (mod n)
+    let message_bytes = message_bytes(message);
+
+    let mut t = tagged_hash(b"BIP0374/aux", &[auxiliary_random]);
+    let mut a_bytes = a.to_be_bytes();
+    for i in 0..32 {
+        t[i] ^= a_bytes[i];
+    }
+
+    a_bytes.fill(0);
+    let mut nonce_hash = tagged_hash(
+        b"BIP0374/nonce",
+        &[&t, &public_key, &shared_key, message_bytes],
+    );
+    let mut k = Scalar::from_be_bytes_reduced(&nonce_hash);
+    nonce_hash.fill(0);
+    t.fill(0);
+
+    if k.is_zero() {
+        a.zero();
+        return None;
+    }
+
+    // R1 = kG, R2 = kB; e = DLEQ_challenge(A, B, C, R1, R2, m), s = k + ea (mod n)
+    let mut r1 = ProjectivePoint::multiply_generator(&k);
+    let mut r2 = base_point.multiply(&k);
+    let e = match challenge(&public_key, base, &shared_key, &r1, &r2, message_bytes) {
+        Some(e) => e,
+        None => {
+            k.zero();
+            a.zero();
+            return None;
+        }
+    };
+
+    r1.zero();
+    r2.zero();
+    let s = k.add(&e.multiply(&a));
+    k.zero();
+    a.zero();
+
+    let mut proof = [0u8; PROOF_LENGTH];
+    proof[..32].copy_from_slice(&e.to_be_bytes());
+    proof[32..].copy_from_slice(&s.to_be_bytes());
+
+    // Verify the proof before returning it, to guard against faults.
+    if verify_proof(&public_key, base, &shared_key, &proof, message) {
+        Some((shared_key, proof))
+    } else {
+        None
+    }
+}
+
+pub fn verify_proof(
+    public_key: &[u8; POINT_LENGTH],
+    base: &[u8; POINT_LENGTH],
+    shared_key: &[u8; POINT_LENGTH],
+    proof: &[u8; PROOF_LENGTH],
+    message: Option<&[u8; 32]>,
+) -> bool {
+    let (public_point, base_point, shared_point) = match (
+        ProjectivePoint::try_from_serialized(public_key),
+        ProjectivePoint::try_from_serialized(base),
+        ProjectivePoint::try_from_serialized(shared_key),
+    ) {
+        (Some(a), Some(b), Some(c)) => (a, b, c),
+        _ => return false,
+    };
+
+    // e is compared against the reduced challenge, so a value of n or more can never be valid; s must be less than n.
+    let (e, s) = match (
+        Scalar::try_from_be_slice(&proof[..32]),
+        Scalar::try_from_be_slice(&proof[32..]),
+    ) {
+        (Some(e), Some(s)) => (e, s),
+        _ => return false,
+    };
+
+    // R1 = sG - eA, R2 = sB - eC
+    let r1 = ProjectivePoint::multiply_generator(&s)
+        .add(&public_point.multiply_variable_time(&e).negate());
+    let r2 = base_point
+        .multiply_variable_time(&s)
+        .add(&shared_point.multiply_variable_time(&e).negate());
+
+    match challenge(
+        public_key,
+        base,
+        shared_key,
+        &r1,
+        &r2,
+        message_bytes(message),
+    ) {
+        Some(challenge) => challenge.equals(&e),
+        None => false,
+    }
+}
+
+fn challenge(
+    public_key: &[u8; POINT_LENGTH],
+    base: &[u8; POINT_LENGTH],
+    shared_key: &[u8; POINT_LENGTH],
+    r1: &ProjectivePoint,
+    r2: &ProjectivePoint,
+    message: &[u8],
+) -> Option<Scalar> {
+    // e = int(hash_BIP0374/challenge(cbytes(A) || cbytes(B) || cbytes(C) || cbytes(G) || cbytes(R1) || cbytes(R2) || m)) (mod n)
+    let (r1, r2) = match (r1.try_serialize_compressed(), r2.try_serialize_compressed()) {
+        (Some(r1), Some(r2)) => (r1, r2),
+        _ => return None,
+    };
+
+    let generator = ProjectivePoint::GENERATOR
+        .try_serialize_compressed()
+        .unwrap();
+    Some(Scalar::from_be_bytes_reduced(&tagged_hash(
+        b"BIP0374/challenge",
+        &[public_key, base, shared_key, &generator, &r1, &r2, message],
+    )))
+}
+
+fn message_bytes(message: Option<&[u8; 32]>) -> &[u8] {
+    // The optional message is simply omitted from the nonce and challenge hashes when it isn't provided.
+    match message {
+        Some(m) => m,
+        None => &[],
+    }
+}
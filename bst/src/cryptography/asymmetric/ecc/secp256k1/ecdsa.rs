@@ -0,0 +1,236 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{FieldElement, ProjectivePoint, Scalar};
+use crate::{
+    encoding::der,
+    hashing::{Hasher, Sha256},
+};
+use alloc::vec::Vec;
+
+// ECDSA over secp256k1, with deterministic nonces (RFC 6979, using HMAC-SHA256), low-S normalized signatures, and a
+// recovery ID which allows the signer's public key to be recovered from the signature and message hash.
+pub const SIGNATURE_LENGTH: usize = 64;
+
+pub fn try_sign_recoverable(
+    private_key: &[u8; 32],
+    message_hash: &[u8; 32],
+) -> Option<([u8; SIGNATURE_LENGTH], u8)> {
+    // d must be in the range 0 < d < n.
+    let mut d = match Scalar::from_be_bytes(private_key) {
+        Some(d) => d,
+        None => return None,
+    };
+
+    if d.is_zero() {
+        return None;
+    }
+
+    // z = int(hash) (mod n); with a 256 bit hash and a 256 bit order, no truncation is required.
+    let z = Scalar::from_be_bytes_reduced(message_hash);
+    let mut k = deterministic_nonce(&d, &z);
+
+    // R = kG; r = x(R) (mod n).
+    let mut nonce_point = ProjectivePoint::multiply_generator(&k);
+    let (x, y) = nonce_point.try_to_affine().unwrap();
+    nonce_point.zero();
+
+    let x = x.to_be_bytes();
+    let r = Scalar::from_be_bytes_reduced(&x);
+
+    // The recovery ID's low bit is the parity of R's Y coordinate; its second bit indicates x(R) was >= n.
+    let mut recovery_id = (!y.is_even() as u8) | ((Scalar::from_be_bytes(&x).is_none() as u8) << 1);
+
+    // s = (z + rd) / k (mod n)
+    let mut s = k.invert().multiply(&z.add(&r.multiply(&d)));
+    k.zero();
+    d.zero();
+
+    if r.is_zero() || s.is_zero() {
+        return None;
+    }
+
+    if s.is_high() {
+        // Both s and n - s are valid; we always produce the low one, as required by Bitcoin's standardness rules.
+        // Negating s is equivalent to negating R, which flips its Y coordinate's parity.
+        s = s.negate();
+        recovery_id ^= 1;
+    }
+
+    let mut signature = [0u8; SIGNATURE_LENGTH];
+    signature[..32].copy_from_slice(&r.to_be_bytes());
+    signature[32..].copy_from_slice(&s.to_be_bytes());
+    Some((signature, recovery_id))
+}
+
+pub fn try_recover_public_key(
+    message_hash: &[u8; 32],
+    signature: &[u8; SIGNATURE_LENGTH],
+    recovery_id: u8,
+) -> Option<ProjectivePoint> {
+    if recovery_id > 3 {
+        return None;
+    }
+
+    // r and s must be in the range 0 < r, s < n.
+    let (r, s) = match try_decode_signature(signature) {
+        Some(s) => s,
+        None => return None,
+    };
+
+    // x(R) = r, or r + n if the recovery ID's second bit is set; it must be less than p.
+    let x = if recovery_id & 2 == 0 {
+        r.to_be_bytes()
+    } else {
+        match r.try_add_order() {
+            Some(x) => x,
+            None => return None,
+        }
+    };
+
+    let nonce_point = match FieldElement::from_be_bytes(&x) {
+        Some(x) => match ProjectivePoint::try_from_x(&x, recovery_id & 1 == 0) {
+            Some(p) => p,
+            None => return None,
+        },
+        None => return None,
+    };
+
+    // Q = (sR - zG) / r
+    let r_inverse = r.invert();
+    let z = Scalar::from_be_bytes_reduced(message_hash);
+    let public_point = nonce_point
+        .multiply_variable_time(&s.multiply(&r_inverse))
+        .add(&ProjectivePoint::multiply_generator(&z.multiply(&r_inverse)).negate());
+
+    if public_point.is_infinity() {
+        None
+    } else {
+        Some(public_point)
+    }
+}
+
+pub fn verify(
+    public_key: &ProjectivePoint,
+    message_hash: &[u8; 32],
+    signature: &[u8; SIGNATURE_LENGTH],
+) -> bool {
+    // r and s must be in the range 0 < r, s < n; high S values are valid, though Bitcoin won't relay them.
+    let (r, s) = match try_decode_signature(signature) {
+        Some(s) => s,
+        None => return false,
+    };
+
+    // R = (z / s)G + (r / s)Q must not be infinity, and must have an X coordinate of r (mod n).
+    let s_inverse = s.invert();
+    let z = Scalar::from_be_bytes_reduced(message_hash);
+    let nonce_point = ProjectivePoint::multiply_generator(&z.multiply(&s_inverse))
+        .add(&public_key.multiply_variable_time(&r.multiply(&s_inverse)));
+
+    match nonce_point.try_to_affine() {
+        Some((x, _)) => Scalar::from_be_bytes_reduced(&x.to_be_bytes()).equals(&r),
+        None => false,
+    }
+}
+
+pub fn to_der(signature: &[u8; SIGNATURE_LENGTH]) -> Vec<u8> {
+    // SEQUENCE { INTEGER r, INTEGER s }, as used in Bitcoin transactions.
+    let mut integers = Vec::with_capacity(70);
+    der::write_unsigned_integer(&signature[..32], &mut integers);
+    der::write_unsigned_integer(&signature[32..], &mut integers);
+
+    let mut encoded = Vec::with_capacity(72);
+    der::write_element(der::SEQUENCE_TAG, &integers, &mut encoded);
+    encoded
+}
+
+pub fn try_from_der(bytes: &[u8]) -> Option<[u8; SIGNATURE_LENGTH]> {
+    // The encoding must be strict DER (BIP 66), with nothing following it.
+    let integers = match der::try_read_element(bytes) {
+        Some((der::SEQUENCE_TAG, content, [])) => content,
+        _ => return None,
+    };
+
+    let mut signature = [0u8; SIGNATURE_LENGTH];
+    let mut remainder = integers;
+    for offset in [0, 32] {
+        let (magnitude, rest) = match der::try_read_unsigned_integer(remainder) {
+            Some((m, r)) if m.len() <= 32 => (m, r),
+            _ => return None,
+        };
+
+        signature[offset + 32 - magnitude.len()..offset + 32].copy_from_slice(magnitude);
+        remainder = rest;
+    }
+
+    if remainder.is_empty() {
+        Some(signature)
+    } else {
+        None
+    }
+}
+
+fn try_decode_signature(signature: &[u8; SIGNATURE_LENGTH]) -> Option<(Scalar, Scalar)> {
+    let mut r = [0u8; 32];
+    let mut s = [0u8; 32];
+    r.copy_from_slice(&signature[..32]);
+    s.copy_from_slice(&signature[32..]);
+    match (Scalar::from_be_bytes(&r), Scalar::from_be_bytes(&s)) {
+        (Some(r), Some(s)) if !r.is_zero() && !s.is_zero() => Some((r, s)),
+        _ => None,
+    }
+}
+
+fn deterministic_nonce(private_key: &Scalar, message_hash: &Scalar) -> Scalar {
+    // RFC 6979 section 3.2, with HMAC-SHA256; the private key and reduced hash are each encoded as 32 bytes.
+    let mut key_and_hash = [0u8; 64];
+    key_and_hash[..32].copy_from_slice(&private_key.to_be_bytes());
+    key_and_hash[32..].copy_from_slice(&message_hash.to_be_bytes());
+
+    let mut sha256 = Sha256::new();
+    let mut v = [0x01u8; 32];
+    let mut k = [0x00u8; 32];
+    let mut buffer = [0u8; 97];
+    for separator in [0x00, 0x01] {
+        // K = HMAC_K(V || separator || int2octets(x) || bits2octets(h1)), V = HMAC_K(V)
+        buffer[..32].copy_from_slice(&v);
+        buffer[32] = separator;
+        buffer[33..].copy_from_slice(&key_and_hash);
+        k = sha256.build_hmac(&k).get_hmac(&buffer);
+        v = sha256.build_hmac(&k).get_hmac(&v);
+    }
+
+    buffer.fill(0);
+    key_and_hash.fill(0);
+    let nonce = loop {
+        // V = HMAC_K(V); the candidate nonce is V, which must be in the range 0 < k < n.
+        v = sha256.build_hmac(&k).get_hmac(&v);
+        match Scalar::from_be_bytes(&v) {
+            Some(nonce) if !nonce.is_zero() => break nonce,
+            _ => {
+                // K = HMAC_K(V || 0x00), V = HMAC_K(V)
+                let mut retry = [0u8; 33];
+                retry[..32].copy_from_slice(&v);
+                k = sha256.build_hmac(&k).get_hmac(&retry);
+                v = sha256.build_hmac(&k).get_hmac(&v);
+            }
+        }
+    };
+
+    v.fill(0);
+    k.fill(0);
+    nonce
+}
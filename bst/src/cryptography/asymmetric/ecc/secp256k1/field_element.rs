@@ -0,0 +1,174 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::limbs::{self, Limbs};
+
+// The secp256k1 field prime is of the special form p = 2^256 - c, where c = 2^32 + 977 = 0x1000003D1.
+// Because 2^256 ≡ c (mod p), the high half of a 512 bit product can be folded back into the low half by multiplying it by c,
+// which is much, much cheaper than generic long division.
+const P: Limbs = [
+    0xFFFFFFFEFFFFFC2F,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+];
+
+const C: Limbs = [0x00000001000003D1, 0, 0, 0];
+
+// p - 2; used for inversion via Fermat's little theorem: x^(p - 2) ≡ x^-1 (mod p).
+const P_MINUS_TWO: Limbs = [
+    0xFFFFFFFEFFFFFC2D,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+];
+
+// (p + 1) / 4; p ≡ 3 (mod 4), so x^((p + 1) / 4) is a square root of x, if x has one.
+const P_PLUS_ONE_OVER_FOUR: Limbs = [
+    0xFFFFFFFFBFFFFF0C,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0x3FFFFFFFFFFFFFFF,
+];
+
+// A secp256k1 field element; an integer in the range [0, p). Values are always kept fully reduced.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldElement(Limbs);
+
+impl FieldElement {
+    pub const ZERO: Self = Self([0, 0, 0, 0]);
+    pub const ONE: Self = Self([1, 0, 0, 0]);
+
+    pub const fn from_u64(value: u64) -> Self {
+        Self([value, 0, 0, 0])
+    }
+
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let limbs = limbs::from_be_bytes(bytes);
+        if limbs::is_less_than(&limbs, &P) {
+            Some(Self(limbs))
+        } else {
+            // The value is >= p; it's not a valid field element.
+            None
+        }
+    }
+
+    pub(super) const fn from_limbs(limbs: Limbs) -> Self {
+        Self(limbs)
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        limbs::to_be_bytes(&self.0)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        limbs::is_zero(&self.0)
+    }
+
+    pub fn is_even(&self) -> bool {
+        self.0[0] & 1 == 0
+    }
+
+    pub fn equals(&self, other: &Self) -> bool {
+        limbs::equals(&self.0, &other.0)
+    }
+
+    pub fn add(&self, addend: &Self) -> Self {
+        let (sum, carry) = limbs::add(&self.0, &addend.0);
+        Self(limbs::reduce_once(&sum, carry, &P))
+    }
+
+    pub fn subtract(&self, subtrahend: &Self) -> Self {
+        let (difference, borrow) = limbs::subtract(&self.0, &subtrahend.0);
+
+        // If the subtraction borrowed, the result wrapped around 2^256; adding p brings it back into range,
+        // and adding p (mod 2^256) is the same as subtracting c.
+        let correction = limbs::select(&[0, 0, 0, 0], &C, borrow == 1);
+        let (corrected, _) = limbs::subtract(&difference, &correction);
+        Self(corrected)
+    }
+
+    pub fn negate(&self) -> Self {
+        Self::ZERO.subtract(self)
+    }
+
+    pub fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    pub fn multiply(&self, multiplier: &Self) -> Self {
+        Self::reduce_wide(&limbs::multiply_wide(&self.0, &multiplier.0))
+    }
+
+    pub fn multiply_u64(&self, multiplier: u64) -> Self {
+        self.multiply(&Self::from_u64(multiplier))
+    }
+
+    pub fn square(&self) -> Self {
+        self.multiply(self)
+    }
+
+    pub fn invert(&self) -> Self {
+        // Fermat's little theorem gives us a constant-time inversion; the exponent is public, so the sequence of operations
+        // is the same regardless of the value being inverted. Zero 'inverts' to zero.
+        self.pow(&P_MINUS_TWO)
+    }
+
+    pub fn try_sqrt(&self) -> Option<Self> {
+        // Calculate the candidate root, then check that it is actually a root; not every field element has a square root.
+        let root = self.pow(&P_PLUS_ONE_OVER_FOUR);
+        if root.square().equals(self) {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    pub fn select(a: &Self, b: &Self, choose_b: bool) -> Self {
+        Self(limbs::select(&a.0, &b.0, choose_b))
+    }
+
+    pub fn zero(&mut self) {
+        limbs::zero(&mut self.0);
+    }
+
+    fn pow(&self, exponent: &Limbs) -> Self {
+        // Left-to-right square-and-multiply; see integers::BigUnsignedCalculator::modpow for the general idea.
+        let mut result = Self::ONE;
+        for i in (0..256).rev() {
+            result = result.square();
+            let multiplied = result.multiply(self);
+            result = Self::select(
+                &result,
+                &multiplied,
+                (exponent[i / 64] >> (i % 64)) & 1 == 1,
+            );
+        }
+
+        result
+    }
+
+    fn reduce_wide(wide: &[u64; 8]) -> Self {
+        // A 512 bit product folds to < 2^290, then to < 2^256 + 2^67, then to < 2^256.
+        let mut folded = limbs::fold_wide(wide, &C);
+        folded = limbs::fold_wide(&folded, &C);
+        folded = limbs::fold_wide(&folded, &C);
+
+        let mut low = [0u64; 4];
+        low.copy_from_slice(&folded[..4]);
+        Self(limbs::reduce_once(&low, 0, &P))
+    }
+}
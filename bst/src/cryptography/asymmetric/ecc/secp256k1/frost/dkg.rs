@@ -0,0 +1,263 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    hash_to_scalar, identifier_scalar,
+    keys::{commit_to_polynomial, derive_scalars, evaluate_polynomial, validate_parameters},
+    serialize_element, KeyPackage, PublicKeyPackage, ELEMENT_LENGTH, MINIMUM_SEED_LENGTH,
+    SCALAR_LENGTH,
+};
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::{ProjectivePoint, Scalar},
+    String16,
+};
+use alloc::vec::Vec;
+use macros::s16;
+
+// RFC 9591 leaves key generation by DKG out of scope; this is the Pedersen DKG with proofs of knowledge from the original FROST
+// paper, with the challenge computed as HDKG(SerializeScalar(identifier) || SerializeElement(C_0) || SerializeElement(R)).
+//
+// identifier || proof of knowledge (R || mu); followed by the participant's commitment, which is threshold elements long.
+const ROUND_1_PACKAGE_BASE_LENGTH: usize = 1 + ELEMENT_LENGTH + SCALAR_LENGTH;
+// sender identifier || recipient identifier || secret share
+pub const ROUND_2_PACKAGE_LENGTH: usize = 2 + SCALAR_LENGTH;
+
+pub struct DkgParticipant {
+    identifier: u8,
+    threshold: u8,
+    participant_count: u8,
+    coefficients: Vec<Scalar>,
+    round_1_package: Vec<u8>,
+}
+
+impl DkgParticipant {
+    pub fn try_from(
+        identifier: u8,
+        threshold: u8,
+        participant_count: u8,
+        seed: &[u8],
+    ) -> Result<Self, String16<'static>> {
+        validate_parameters(threshold, participant_count)?;
+        if identifier == 0 || identifier > participant_count {
+            return Err(s16!(
+                "The identifier must be between 1 and the participant count."
+            ));
+        }
+
+        if seed.len() < MINIMUM_SEED_LENGTH {
+            return Err(s16!("The coefficient seed must be at least 32 bytes."));
+        }
+
+        // The polynomial's coefficients, followed by the proof of knowledge's nonce.
+        let mut coefficients = derive_scalars(seed, &[identifier], threshold as usize + 1);
+        let mut k = coefficients.pop().unwrap();
+        let commitment = commit_to_polynomial(&coefficients);
+
+        // Prove knowledge of the constant term; R = kG, mu = k + a_0 * c.
+        let r = ProjectivePoint::multiply_generator(&k);
+        let mut round_1_package =
+            Vec::with_capacity(ROUND_1_PACKAGE_BASE_LENGTH + threshold as usize * ELEMENT_LENGTH);
+
+        round_1_package.push(identifier);
+        let result = Self::try_get_proof_challenge(identifier, &commitment[0], &r).and_then(|c| {
+            round_1_package.extend(serialize_element(&r)?);
+            round_1_package.extend(k.add(&coefficients[0].multiply(&c)).to_be_bytes());
+            for element in commitment.iter() {
+                round_1_package.extend(serialize_element(element)?);
+            }
+
+            Ok(())
+        });
+
+        k.zero();
+        if let Err(e) = result {
+            coefficients.iter_mut().for_each(|c| c.zero());
+            return Err(e);
+        }
+
+        Ok(Self {
+            identifier,
+            threshold,
+            participant_count,
+            coefficients,
+            round_1_package,
+        })
+    }
+
+    pub fn round_1_package(&self) -> &[u8] {
+        &self.round_1_package
+    }
+
+    pub fn round_2_package(&self, recipient: u8) -> [u8; ROUND_2_PACKAGE_LENGTH] {
+        // The recipient's secret share, f(recipient); this must only be sent to the recipient, over a confidential channel.
+        let mut share = evaluate_polynomial(&self.coefficients, recipient);
+        let mut package = [0u8; ROUND_2_PACKAGE_LENGTH];
+        package[0] = self.identifier;
+        package[1] = recipient;
+        package[2..].copy_from_slice(&share.to_be_bytes());
+        share.zero();
+        package
+    }
+
+    pub fn try_verify_round_1_package(
+        &self,
+        bytes: &[u8],
+    ) -> Result<(u8, Vec<ProjectivePoint>), String16<'static>> {
+        if bytes.len() != ROUND_1_PACKAGE_BASE_LENGTH + self.threshold as usize * ELEMENT_LENGTH {
+            return Err(s16!(
+                "The round 1 package's length does not match the threshold."
+            ));
+        }
+
+        let identifier = bytes[0];
+        if identifier == 0 || identifier > self.participant_count || identifier == self.identifier {
+            return Err(s16!("The round 1 package's identifier is invalid."));
+        }
+
+        let r = ProjectivePoint::try_from_serialized(&bytes[1..1 + ELEMENT_LENGTH]);
+        let mu = Scalar::try_from_be_slice(&bytes[1 + ELEMENT_LENGTH..ROUND_1_PACKAGE_BASE_LENGTH]);
+        let mut commitment = Vec::with_capacity(self.threshold as usize);
+        for element in bytes[ROUND_1_PACKAGE_BASE_LENGTH..].chunks_exact(ELEMENT_LENGTH) {
+            match ProjectivePoint::try_from_serialized(element) {
+                Some(p) => commitment.push(p),
+                None => return Err(s16!("The round 1 package contains an invalid commitment.")),
+            }
+        }
+
+        // R = mu * G - c * C_0
+        let valid = match (r, mu) {
+            (Some(r), Some(mu)) => {
+                match Self::try_get_proof_challenge(identifier, &commitment[0], &r) {
+                    Ok(c) => r.equals(
+                        &ProjectivePoint::multiply_generator(&mu)
+                            .add(&commitment[0].multiply_variable_time(&c).negate()),
+                    ),
+                    Err(_) => false,
+                }
+            }
+            _ => false,
+        };
+
+        if valid {
+            Ok((identifier, commitment))
+        } else {
+            Err(s16!("The round 1 package's proof of knowledge is invalid."))
+        }
+    }
+
+    pub fn try_finish(
+        &self,
+        round_1_packages: &[Vec<u8>],
+        round_2_packages: &[[u8; ROUND_2_PACKAGE_LENGTH]],
+    ) -> Result<(PublicKeyPackage, KeyPackage), String16<'static>> {
+        // We need a round 1 package, and a round 2 package, from every other participant.
+        let other_count = self.participant_count as usize - 1;
+        if round_1_packages.len() != other_count || round_2_packages.len() != other_count {
+            return Err(s16!("A package is required from every other participant."));
+        }
+
+        let mut commitments = Vec::with_capacity(other_count);
+        for package in round_1_packages {
+            let (identifier, commitment) = self.try_verify_round_1_package(package)?;
+            if commitments.iter().any(|(i, _)| *i == identifier) {
+                return Err(s16!(
+                    "Each participant can only provide one round 1 package."
+                ));
+            }
+
+            commitments.push((identifier, commitment));
+        }
+
+        // Our own share of our own polynomial, plus the shares we received, each validated against its sender's commitment.
+        let x = identifier_scalar(self.identifier);
+        let mut signing_share = evaluate_polynomial(&self.coefficients, self.identifier);
+        let mut used = Vec::with_capacity(other_count);
+        for package in round_2_packages {
+            let share = Scalar::try_from_be_slice(&package[2..]);
+            let commitment = commitments.iter().find(|(i, _)| *i == package[0]);
+            let valid = match (share, commitment) {
+                (Some(s), Some((_, c))) => {
+                    let mut expected = ProjectivePoint::INFINITY;
+                    for element in c.iter().rev() {
+                        expected = expected.multiply_variable_time(&x).add(element);
+                    }
+
+                    package[1] == self.identifier
+                        && !used.contains(&package[0])
+                        && ProjectivePoint::multiply_generator(&s).equals(&expected)
+                }
+                _ => false,
+            };
+
+            if !valid {
+                signing_share.zero();
+                return Err(s16!("A round 2 package is invalid, or was not sent to us."));
+            }
+
+            used.push(package[0]);
+            signing_share = signing_share.add(&share.unwrap());
+        }
+
+        // The group commitment is the sum of every participant's commitment; its constant term is the group public key.
+        let mut group_commitment = commit_to_polynomial(&self.coefficients);
+        for (_, commitment) in commitments.iter() {
+            for (sum, element) in group_commitment.iter_mut().zip(commitment.iter()) {
+                *sum = sum.add(element);
+            }
+        }
+
+        let public_key_package = match PublicKeyPackage::try_from(
+            self.threshold,
+            self.participant_count,
+            group_commitment,
+        ) {
+            Ok(p) => p,
+            Err(e) => {
+                signing_share.zero();
+                return Err(e);
+            }
+        };
+
+        let key_package = KeyPackage::from(self.identifier, signing_share);
+        signing_share.zero();
+        Ok((public_key_package, key_package))
+    }
+
+    pub fn zero(&mut self) {
+        self.coefficients.iter_mut().for_each(|c| c.zero());
+    }
+
+    fn try_get_proof_challenge(
+        identifier: u8,
+        constant_commitment: &ProjectivePoint,
+        r: &ProjectivePoint,
+    ) -> Result<Scalar, String16<'static>> {
+        Ok(hash_to_scalar(
+            b"dkg",
+            &[
+                &identifier_scalar(identifier).to_be_bytes(),
+                &serialize_element(constant_commitment)?,
+                &serialize_element(r)?,
+            ],
+        ))
+    }
+}
+
+impl Drop for DkgParticipant {
+    fn drop(&mut self) {
+        self.zero();
+    }
+}
@@ -0,0 +1,259 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    identifier_scalar, serialize_element, ELEMENT_LENGTH, MINIMUM_SEED_LENGTH, SCALAR_LENGTH,
+};
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::{ProjectivePoint, Scalar},
+    hashing::derive_bytes,
+    String16,
+};
+use alloc::vec::Vec;
+use macros::s16;
+
+// identifier || signing share
+pub const KEY_PACKAGE_LENGTH: usize = 1 + SCALAR_LENGTH;
+
+pub struct PublicKeyPackage {
+    threshold: u8,
+    participant_count: u8,
+    // The commitment to the key polynomial's coefficients, from which the group public key and every participant's verification
+    // share can be derived; the sum of the participants' commitments when the key was generated with a DKG.
+    commitment: Vec<ProjectivePoint>,
+}
+
+impl PublicKeyPackage {
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, String16<'static>> {
+        // threshold || participant count || commitment
+        if bytes.len() < 2 || bytes.len() != 2 + bytes[0] as usize * ELEMENT_LENGTH {
+            return Err(s16!("The public key package's length is invalid."));
+        }
+
+        validate_parameters(bytes[0], bytes[1])?;
+        let mut commitment = Vec::with_capacity(bytes[0] as usize);
+        for element in bytes[2..].chunks_exact(ELEMENT_LENGTH) {
+            match ProjectivePoint::try_from_serialized(element) {
+                Some(p) => commitment.push(p),
+                None => {
+                    return Err(s16!(
+                        "The public key package contains an invalid commitment."
+                    ))
+                }
+            }
+        }
+
+        Ok(Self {
+            threshold: bytes[0],
+            participant_count: bytes[1],
+            commitment,
+        })
+    }
+
+    pub(super) fn try_from(
+        threshold: u8,
+        participant_count: u8,
+        commitment: Vec<ProjectivePoint>,
+    ) -> Result<Self, String16<'static>> {
+        for element in commitment.iter() {
+            serialize_element(element)?;
+        }
+
+        Ok(Self {
+            threshold,
+            participant_count,
+            commitment,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // The commitment's elements are validated when the package is built, so none of them are the identity element.
+        let mut bytes = Vec::with_capacity(2 + self.commitment.len() * ELEMENT_LENGTH);
+        bytes.push(self.threshold);
+        bytes.push(self.participant_count);
+        for element in self.commitment.iter() {
+            bytes.extend(element.try_serialize_compressed().unwrap());
+        }
+
+        bytes
+    }
+
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    pub fn participant_count(&self) -> u8 {
+        self.participant_count
+    }
+
+    pub fn group_public_key(&self) -> [u8; ELEMENT_LENGTH] {
+        // The group public key is the commitment to the constant term; this is validated when the package is built.
+        self.commitment[0].try_serialize_compressed().unwrap()
+    }
+
+    pub fn verification_share(&self, identifier: u8) -> ProjectivePoint {
+        // PK_i = sum(C_j * i^j), evaluated with Horner's method.
+        let x = identifier_scalar(identifier);
+        let mut share = ProjectivePoint::INFINITY;
+        for element in self.commitment.iter().rev() {
+            share = share.multiply_variable_time(&x).add(element);
+        }
+
+        share
+    }
+}
+
+pub struct KeyPackage {
+    identifier: u8,
+    signing_share: Scalar,
+}
+
+impl KeyPackage {
+    pub fn try_from_bytes(bytes: &[u8; KEY_PACKAGE_LENGTH]) -> Result<Self, String16<'static>> {
+        match Scalar::try_from_be_slice(&bytes[1..]) {
+            Some(s) if bytes[0] != 0 && !s.is_zero() => Ok(Self {
+                identifier: bytes[0],
+                signing_share: s,
+            }),
+            _ => Err(s16!("The key package is invalid.")),
+        }
+    }
+
+    pub(super) fn from(identifier: u8, signing_share: Scalar) -> Self {
+        Self {
+            identifier,
+            signing_share,
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; KEY_PACKAGE_LENGTH] {
+        let mut bytes = [0u8; KEY_PACKAGE_LENGTH];
+        bytes[0] = self.identifier;
+        bytes[1..].copy_from_slice(&self.signing_share.to_be_bytes());
+        bytes
+    }
+
+    pub fn identifier(&self) -> u8 {
+        self.identifier
+    }
+
+    pub(super) fn signing_share(&self) -> Scalar {
+        self.signing_share
+    }
+
+    pub fn is_valid_for(&self, public_key_package: &PublicKeyPackage) -> bool {
+        // vss_verify; the signing share must match the participant's verification share.
+        self.identifier <= public_key_package.participant_count
+            && ProjectivePoint::multiply_generator(&self.signing_share)
+                .equals(&public_key_package.verification_share(self.identifier))
+    }
+
+    pub fn zero(&mut self) {
+        self.signing_share.zero();
+    }
+}
+
+impl Drop for KeyPackage {
+    fn drop(&mut self) {
+        self.zero();
+    }
+}
+
+pub fn try_generate_with_trusted_dealer(
+    secret_key: &[u8; SCALAR_LENGTH],
+    threshold: u8,
+    participant_count: u8,
+    coefficient_seed: &[u8],
+) -> Result<(PublicKeyPackage, Vec<KeyPackage>), String16<'static>> {
+    validate_parameters(threshold, participant_count)?;
+    if coefficient_seed.len() < MINIMUM_SEED_LENGTH {
+        return Err(s16!("The coefficient seed must be at least 32 bytes."));
+    }
+
+    let mut coefficients = Vec::with_capacity(threshold as usize);
+    match Scalar::from_be_bytes(secret_key) {
+        Some(s) if !s.is_zero() => coefficients.push(s),
+        _ => return Err(s16!("The secret key is invalid.")),
+    }
+
+    // trusted_dealer_keygen; the polynomial's remaining coefficients are derived from the seed, with the secret as context.
+    coefficients.extend(derive_scalars(
+        coefficient_seed,
+        secret_key,
+        threshold as usize - 1,
+    ));
+
+    let mut key_packages = Vec::with_capacity(participant_count as usize);
+    for identifier in 1..=participant_count {
+        key_packages.push(KeyPackage {
+            identifier,
+            signing_share: evaluate_polynomial(&coefficients, identifier),
+        });
+    }
+
+    let commitment = commit_to_polynomial(&coefficients);
+    coefficients.iter_mut().for_each(|c| c.zero());
+    Ok((
+        PublicKeyPackage::try_from(threshold, participant_count, commitment)?,
+        key_packages,
+    ))
+}
+
+pub(super) fn validate_parameters(
+    threshold: u8,
+    participant_count: u8,
+) -> Result<(), String16<'static>> {
+    if threshold < 2 {
+        return Err(s16!("The threshold must be at least 2."));
+    }
+
+    if participant_count < threshold {
+        return Err(s16!(
+            "The number of participants cannot be lower than the threshold."
+        ));
+    }
+
+    Ok(())
+}
+
+pub(super) fn derive_scalars(seed: &[u8], context: &[u8], count: usize) -> Vec<Scalar> {
+    let mut bytes = derive_bytes(seed, context, count * SCALAR_LENGTH);
+    let scalars = bytes
+        .chunks_exact(SCALAR_LENGTH)
+        .map(|c| Scalar::from_be_bytes_reduced(c.try_into().unwrap()))
+        .collect();
+
+    bytes.fill(0);
+    scalars
+}
+
+pub(super) fn evaluate_polynomial(coefficients: &[Scalar], identifier: u8) -> Scalar {
+    // Horner's method, from the highest degree coefficient to the constant term.
+    let x = identifier_scalar(identifier);
+    let mut value = Scalar::ZERO;
+    for coefficient in coefficients.iter().rev() {
+        value = value.multiply(&x).add(coefficient);
+    }
+
+    value
+}
+
+pub(super) fn commit_to_polynomial(coefficients: &[Scalar]) -> Vec<ProjectivePoint> {
+    coefficients
+        .iter()
+        .map(|c| ProjectivePoint::multiply_generator(c))
+        .collect()
+}
@@ -0,0 +1,554 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod dkg;
+mod keys;
+
+pub use dkg::{DkgParticipant, ROUND_2_PACKAGE_LENGTH};
+pub use keys::{
+    try_generate_with_trusted_dealer, KeyPackage, PublicKeyPackage, KEY_PACKAGE_LENGTH,
+};
+
+use super::{ProjectivePoint, Scalar};
+use crate::{
+    hashing::{derive_bytes, Hasher, Sha256},
+    String16,
+};
+use alloc::vec::Vec;
+use macros::s16;
+
+// FROST threshold Schnorr signatures, as specified in RFC 9591, with the FROST(secp256k1, SHA-256) ciphersuite. The messages passed
+// between participants are our own compact encodings; participant identifiers are limited to 1-255, so they fit in a single byte.
+const CONTEXT_STRING: &[u8] = b"FROST-secp256k1-SHA256-v1";
+
+pub const ELEMENT_LENGTH: usize = 33;
+pub const SCALAR_LENGTH: usize = 32;
+// identifier || hiding nonce commitment || binding nonce commitment
+pub const COMMITMENT_LENGTH: usize = 1 + ELEMENT_LENGTH * 2;
+// identifier || signature share
+pub const SIGNATURE_SHARE_LENGTH: usize = 1 + SCALAR_LENGTH;
+// R || z
+pub const SIGNATURE_LENGTH: usize = ELEMENT_LENGTH + SCALAR_LENGTH;
+pub const MINIMUM_SEED_LENGTH: usize = 32;
+
+// 2^256 (mod n); used to reduce the 384 bit outputs of hash_to_field.
+const TWO_POW_256: [u8; 32] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0x45, 0x51, 0x23, 0x19, 0x50, 0xB7, 0x5F, 0xC4,
+    0x40, 0x2D, 0xA1, 0x73, 0x2F, 0xC9, 0xBE, 0xBF,
+];
+
+pub struct SigningNonces {
+    identifier: u8,
+    hiding: Scalar,
+    binding: Scalar,
+    commitment: [u8; COMMITMENT_LENGTH],
+}
+
+impl SigningNonces {
+    pub fn try_generate(key_package: &KeyPackage, seed: &[u8]) -> Result<Self, String16<'static>> {
+        if seed.len() < MINIMUM_SEED_LENGTH {
+            return Err(s16!("The nonce seed must be at least 32 bytes."));
+        }
+
+        // Round one, commit; nonce_generate(secret) = H3(random_bytes(32) || SerializeScalar(secret)). We don't have a source
+        // of randomness, so the random bytes are derived from the caller's seed.
+        let mut signing_share = key_package.signing_share().to_be_bytes();
+        let mut random = derive_bytes(seed, b"FROST nonces", 64);
+        let hiding = hash_to_scalar(b"nonce", &[&random[..32], &signing_share]);
+        let binding = hash_to_scalar(b"nonce", &[&random[32..], &signing_share]);
+        signing_share.fill(0);
+        random.fill(0);
+
+        let mut commitment = [0u8; COMMITMENT_LENGTH];
+        commitment[0] = key_package.identifier();
+        commitment[1..1 + ELEMENT_LENGTH].copy_from_slice(&serialize_element(
+            &ProjectivePoint::multiply_generator(&hiding),
+        )?);
+        commitment[1 + ELEMENT_LENGTH..].copy_from_slice(&serialize_element(
+            &ProjectivePoint::multiply_generator(&binding),
+        )?);
+
+        Ok(Self {
+            identifier: key_package.identifier(),
+            hiding,
+            binding,
+            commitment,
+        })
+    }
+
+    pub fn commitment(&self) -> &[u8; COMMITMENT_LENGTH] {
+        &self.commitment
+    }
+
+    pub fn zero(&mut self) {
+        self.hiding.zero();
+        self.binding.zero();
+    }
+}
+
+impl Drop for SigningNonces {
+    fn drop(&mut self) {
+        self.zero();
+    }
+}
+
+struct Commitment {
+    identifier: u8,
+    hiding: ProjectivePoint,
+    binding: ProjectivePoint,
+}
+
+pub struct SigningPackage {
+    commitments: Vec<Commitment>,
+    encoded_commitments: Vec<u8>,
+    message: Vec<u8>,
+}
+
+impl SigningPackage {
+    pub fn try_from(
+        commitments: &[[u8; COMMITMENT_LENGTH]],
+        message: &[u8],
+    ) -> Result<Self, String16<'static>> {
+        // The commitment list is sorted by identifier, and each identifier can only appear once.
+        let mut sorted: Vec<[u8; COMMITMENT_LENGTH]> = commitments.into();
+        sorted.sort_by_key(|c| c[0]);
+        if sorted.windows(2).any(|w| w[0][0] == w[1][0]) {
+            return Err(s16!("Each participant can only provide one commitment."));
+        }
+
+        let mut decoded = Vec::with_capacity(sorted.len());
+        let mut encoded_commitments =
+            Vec::with_capacity(sorted.len() * (SCALAR_LENGTH + ELEMENT_LENGTH * 2));
+        for commitment in sorted.iter() {
+            let (hiding, binding) = match (
+                ProjectivePoint::try_from_serialized(&commitment[1..1 + ELEMENT_LENGTH]),
+                ProjectivePoint::try_from_serialized(&commitment[1 + ELEMENT_LENGTH..]),
+            ) {
+                (Some(h), Some(b)) if commitment[0] != 0 => (h, b),
+                _ => return Err(s16!("A commitment is invalid.")),
+            };
+
+            // encode_group_commitment_list; SerializeScalar(identifier) || SerializeElement(hiding) || SerializeElement(binding)
+            encoded_commitments.extend(identifier_scalar(commitment[0]).to_be_bytes());
+            encoded_commitments.extend(&commitment[1..]);
+            decoded.push(Commitment {
+                identifier: commitment[0],
+                hiding,
+                binding,
+            });
+        }
+
+        Ok(Self {
+            commitments: decoded,
+            encoded_commitments,
+            message: message.into(),
+        })
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, String16<'static>> {
+        // commitment count || commitments || message
+        if bytes.len() == 0 || bytes.len() < 1 + bytes[0] as usize * COMMITMENT_LENGTH {
+            return Err(s16!("The signing package is too short."));
+        }
+
+        let message_offset = 1 + bytes[0] as usize * COMMITMENT_LENGTH;
+        let commitments: Vec<[u8; COMMITMENT_LENGTH]> = bytes[1..message_offset]
+            .chunks_exact(COMMITMENT_LENGTH)
+            .map(|c| c.try_into().unwrap())
+            .collect();
+
+        Self::try_from(&commitments, &bytes[message_offset..])
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes =
+            Vec::with_capacity(1 + self.commitments.len() * COMMITMENT_LENGTH + self.message.len());
+        bytes.push(self.commitments.len() as u8);
+        for (i, commitment) in self.commitments.iter().enumerate() {
+            let offset = i * (SCALAR_LENGTH + ELEMENT_LENGTH * 2) + SCALAR_LENGTH;
+            bytes.push(commitment.identifier);
+            bytes.extend(&self.encoded_commitments[offset..offset + ELEMENT_LENGTH * 2]);
+        }
+
+        bytes.extend(&self.message);
+        bytes
+    }
+
+    pub fn identifiers(&self) -> Vec<u8> {
+        self.commitments.iter().map(|c| c.identifier).collect()
+    }
+
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    fn try_get_session_values(
+        &self,
+        public_key_package: &PublicKeyPackage,
+    ) -> Result<SessionValues, String16<'static>> {
+        if self.commitments.len() < public_key_package.threshold() as usize {
+            return Err(s16!(
+                "There are fewer commitments than the signing threshold."
+            ));
+        }
+
+        if self
+            .commitments
+            .iter()
+            .any(|c| c.identifier > public_key_package.participant_count())
+        {
+            return Err(s16!(
+                "A commitment's identifier does not belong to a participant."
+            ));
+        }
+
+        // compute_binding_factors; rho_input = SerializeElement(PK) || H4(msg) || H5(encoded commitments) || SerializeScalar(identifier)
+        let group_public_key = public_key_package.group_public_key();
+        let message_hash = hash(b"msg", &[&self.message]);
+        let commitment_hash = hash(b"com", &[&self.encoded_commitments]);
+        let mut binding_factors = Vec::with_capacity(self.commitments.len());
+        let mut group_commitment = ProjectivePoint::INFINITY;
+        for commitment in self.commitments.iter() {
+            let binding_factor = hash_to_scalar(
+                b"rho",
+                &[
+                    &group_public_key,
+                    &message_hash,
+                    &commitment_hash,
+                    &identifier_scalar(commitment.identifier).to_be_bytes(),
+                ],
+            );
+
+            // compute_group_commitment; R = sum(hiding commitment + binding commitment * binding factor)
+            group_commitment = group_commitment
+                .add(&commitment.hiding)
+                .add(&commitment.binding.multiply_variable_time(&binding_factor));
+            binding_factors.push(binding_factor);
+        }
+
+        // compute_challenge; c = H2(SerializeElement(R) || SerializeElement(PK) || msg)
+        let encoded_group_commitment = serialize_element(&group_commitment)?;
+        let challenge = hash_to_scalar(
+            b"chal",
+            &[&encoded_group_commitment, &group_public_key, &self.message],
+        );
+
+        Ok(SessionValues {
+            binding_factors,
+            encoded_group_commitment,
+            challenge,
+        })
+    }
+
+    fn index_of(&self, identifier: u8) -> Option<usize> {
+        self.commitments
+            .iter()
+            .position(|c| c.identifier == identifier)
+    }
+
+    fn interpolating_value(&self, identifier: u8) -> Scalar {
+        // derive_interpolating_value; the Lagrange coefficient for the identifier at x = 0, over the signing participants.
+        let x_i = identifier_scalar(identifier);
+        let mut numerator = Scalar::ONE;
+        let mut denominator = Scalar::ONE;
+        for commitment in self.commitments.iter() {
+            if commitment.identifier == identifier {
+                continue;
+            }
+
+            let x_j = identifier_scalar(commitment.identifier);
+            numerator = numerator.multiply(&x_j);
+            denominator = denominator.multiply(&x_j.subtract(&x_i));
+        }
+
+        numerator.multiply(&denominator.invert())
+    }
+}
+
+struct SessionValues {
+    binding_factors: Vec<Scalar>,
+    encoded_group_commitment: [u8; ELEMENT_LENGTH],
+    challenge: Scalar,
+}
+
+pub fn try_sign(
+    key_package: &KeyPackage,
+    public_key_package: &PublicKeyPackage,
+    nonces: &mut SigningNonces,
+    signing_package: &SigningPackage,
+) -> Result<[u8; SIGNATURE_SHARE_LENGTH], String16<'static>> {
+    // Round two, sign; the nonces are zeroed whatever the outcome, so they can never be reused.
+    let result = try_sign_with_nonces(key_package, public_key_package, nonces, signing_package);
+    nonces.zero();
+    result
+}
+
+fn try_sign_with_nonces(
+    key_package: &KeyPackage,
+    public_key_package: &PublicKeyPackage,
+    nonces: &SigningNonces,
+    signing_package: &SigningPackage,
+) -> Result<[u8; SIGNATURE_SHARE_LENGTH], String16<'static>> {
+    if nonces.hiding.is_zero() || nonces.binding.is_zero() {
+        return Err(s16!("The signing nonces have already been used."));
+    }
+
+    let identifier = key_package.identifier();
+    let index = match signing_package.index_of(identifier) {
+        Some(i) => i,
+        None => return Err(s16!("The signing package does not include our commitment.")),
+    };
+
+    if nonces.identifier != identifier
+        || signing_package.encoded_commitments
+            [index * (SCALAR_LENGTH + ELEMENT_LENGTH * 2) + SCALAR_LENGTH..][..ELEMENT_LENGTH * 2]
+            != nonces.commitment[1..]
+    {
+        return Err(s16!(
+            "The signing package's commitment does not match our nonces."
+        ));
+    }
+
+    let session_values = signing_package.try_get_session_values(public_key_package)?;
+
+    // z_i = hiding nonce + binding nonce * binding factor + lambda_i * sk_i * c
+    let lambda = signing_package.interpolating_value(identifier);
+    let mut signing_share = key_package.signing_share();
+    let signature_share = nonces
+        .hiding
+        .add(
+            &nonces
+                .binding
+                .multiply(&session_values.binding_factors[index]),
+        )
+        .add(
+            &lambda
+                .multiply(&signing_share)
+                .multiply(&session_values.challenge),
+        );
+    signing_share.zero();
+
+    let mut share = [0u8; SIGNATURE_SHARE_LENGTH];
+    share[0] = identifier;
+    share[1..].copy_from_slice(&signature_share.to_be_bytes());
+    Ok(share)
+}
+
+pub fn verify_signature_share(
+    public_key_package: &PublicKeyPackage,
+    signing_package: &SigningPackage,
+    signature_share: &[u8; SIGNATURE_SHARE_LENGTH],
+) -> bool {
+    let session_values = match signing_package.try_get_session_values(public_key_package) {
+        Ok(v) => v,
+        Err(_) => return false,
+    };
+
+    verify_signature_share_with(
+        public_key_package,
+        signing_package,
+        &session_values,
+        signature_share,
+    )
+}
+
+fn verify_signature_share_with(
+    public_key_package: &PublicKeyPackage,
+    signing_package: &SigningPackage,
+    session_values: &SessionValues,
+    signature_share: &[u8; SIGNATURE_SHARE_LENGTH],
+) -> bool {
+    let identifier = signature_share[0];
+    let index = match signing_package.index_of(identifier) {
+        Some(i) => i,
+        None => return false,
+    };
+
+    let z = match Scalar::try_from_be_slice(&signature_share[1..]) {
+        Some(z) => z,
+        None => return false,
+    };
+
+    // z_i * G = hiding commitment + binding commitment * binding factor + PK_i * (c * lambda_i)
+    let commitment = &signing_package.commitments[index];
+    let commitment_share = commitment.hiding.add(
+        &commitment
+            .binding
+            .multiply_variable_time(&session_values.binding_factors[index]),
+    );
+
+    let lambda = signing_package.interpolating_value(identifier);
+    ProjectivePoint::multiply_generator(&z).equals(
+        &commitment_share.add(
+            &public_key_package
+                .verification_share(identifier)
+                .multiply_variable_time(&session_values.challenge.multiply(&lambda)),
+        ),
+    )
+}
+
+pub fn try_aggregate(
+    public_key_package: &PublicKeyPackage,
+    signing_package: &SigningPackage,
+    signature_shares: &[[u8; SIGNATURE_SHARE_LENGTH]],
+) -> Result<[u8; SIGNATURE_LENGTH], String16<'static>> {
+    let session_values = signing_package.try_get_session_values(public_key_package)?;
+    let mut identifiers: Vec<u8> = signature_shares.iter().map(|s| s[0]).collect();
+    identifiers.sort();
+    if identifiers != signing_package.identifiers() {
+        return Err(s16!(
+            "There must be exactly one signature share for each commitment in the signing package."
+        ));
+    }
+
+    // z = sum(z_i); the shares are verified first, so a misbehaving participant can be identified.
+    let mut z = Scalar::ZERO;
+    for signature_share in signature_shares {
+        if !verify_signature_share_with(
+            public_key_package,
+            signing_package,
+            &session_values,
+            signature_share,
+        ) {
+            return Err(s16!("A signature share is invalid."));
+        }
+
+        z = z.add(&Scalar::try_from_be_slice(&signature_share[1..]).unwrap());
+    }
+
+    let mut signature = [0u8; SIGNATURE_LENGTH];
+    signature[..ELEMENT_LENGTH].copy_from_slice(&session_values.encoded_group_commitment);
+    signature[ELEMENT_LENGTH..].copy_from_slice(&z.to_be_bytes());
+    Ok(signature)
+}
+
+pub fn verify(
+    group_public_key: &[u8; ELEMENT_LENGTH],
+    message: &[u8],
+    signature: &[u8; SIGNATURE_LENGTH],
+) -> bool {
+    let (public_key, r) = match (
+        ProjectivePoint::try_from_serialized(group_public_key),
+        ProjectivePoint::try_from_serialized(&signature[..ELEMENT_LENGTH]),
+    ) {
+        (Some(p), Some(r)) => (p, r),
+        _ => return false,
+    };
+
+    let z = match Scalar::try_from_be_slice(&signature[ELEMENT_LENGTH..]) {
+        Some(z) => z,
+        None => return false,
+    };
+
+    // zG = R + cPK, where c = H2(SerializeElement(R) || SerializeElement(PK) || msg)
+    let challenge = hash_to_scalar(
+        b"chal",
+        &[&signature[..ELEMENT_LENGTH], group_public_key, message],
+    );
+
+    ProjectivePoint::multiply_generator(&z)
+        .equals(&r.add(&public_key.multiply_variable_time(&challenge)))
+}
+
+fn identifier_scalar(identifier: u8) -> Scalar {
+    let mut bytes = [0u8; SCALAR_LENGTH];
+    bytes[SCALAR_LENGTH - 1] = identifier;
+    Scalar::from_be_bytes(&bytes).unwrap()
+}
+
+fn serialize_element(point: &ProjectivePoint) -> Result<[u8; ELEMENT_LENGTH], String16<'static>> {
+    // The identity element can't be serialized.
+    match point.try_serialize_compressed() {
+        Some(b) => Ok(b),
+        None => Err(s16!("Encountered the identity element.")),
+    }
+}
+
+fn hash(domain: &[u8], inputs: &[&[u8]]) -> [u8; 32] {
+    // H4 and H5; SHA256(contextString || domain || m)
+    let mut sha256 = Sha256::new();
+    sha256.feed_bytes(CONTEXT_STRING).feed_bytes(domain);
+    for input in inputs {
+        sha256.feed_bytes(input);
+    }
+
+    sha256.get_hash()
+}
+
+fn hash_to_scalar(domain: &[u8], inputs: &[&[u8]]) -> Scalar {
+    // H1, H2, H3 and HDKG; hash_to_field from RFC 9380, with expand_message_xmd, SHA256, DST = contextString || domain, and L = 48.
+    let mut uniform_bytes = expand_message_xmd(domain, inputs);
+    let mut high = [0u8; 32];
+    let mut low = [0u8; 32];
+    high[16..].copy_from_slice(&uniform_bytes[..16]);
+    low.copy_from_slice(&uniform_bytes[16..]);
+
+    // high * 2^256 + low (mod n)
+    let scalar = Scalar::from_be_bytes(&high)
+        .unwrap()
+        .multiply(&Scalar::from_be_bytes(&TWO_POW_256).unwrap())
+        .add(&Scalar::from_be_bytes_reduced(&low));
+
+    uniform_bytes.fill(0);
+    high.fill(0);
+    low.fill(0);
+    scalar
+}
+
+fn expand_message_xmd(domain: &[u8], inputs: &[&[u8]]) -> [u8; 48] {
+    const LENGTH: usize = 48;
+    let dst_length = [(CONTEXT_STRING.len() + domain.len()) as u8];
+
+    // b_0 = H(Z_pad || msg || I2OSP(len_in_bytes, 2) || I2OSP(0, 1) || DST_prime)
+    let mut sha256 = Sha256::new();
+    sha256.feed_bytes(&[0u8; 64]);
+    for input in inputs {
+        sha256.feed_bytes(input);
+    }
+
+    let mut b_0 = sha256
+        .feed_bytes(&(LENGTH as u16).to_be_bytes())
+        .feed_byte(0)
+        .feed_bytes(CONTEXT_STRING)
+        .feed_bytes(domain)
+        .feed_bytes(&dst_length)
+        .get_hash();
+
+    // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime); b_1 is just H(b_0 || I2OSP(1, 1) || DST_prime).
+    let mut output = [0u8; LENGTH];
+    let mut b_i = [0u8; 32];
+    for i in 0..2 {
+        for j in 0..32 {
+            b_i[j] ^= b_0[j];
+        }
+
+        b_i = sha256
+            .reset()
+            .feed_bytes(&b_i)
+            .feed_byte(i as u8 + 1)
+            .feed_bytes(CONTEXT_STRING)
+            .feed_bytes(domain)
+            .feed_bytes(&dst_length)
+            .get_hash();
+
+        let length = (LENGTH - i * 32).min(32);
+        output[i * 32..i * 32 + length].copy_from_slice(&b_i[..length]);
+    }
+
+    b_0.fill(0);
+    b_i.fill(0);
+    output
+}
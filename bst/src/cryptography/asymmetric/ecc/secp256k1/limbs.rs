@@ -0,0 +1,167 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Fixed-size 256-bit integers are stored as four 64-bit limbs in little-endian limb order; limbs[0] is the least significant.
+// All of the helpers below are branch-free with respect to the values they operate on; any selection is performed with masks.
+pub type Limbs = [u64; 4];
+
+pub const fn from_be_bytes(bytes: &[u8; 32]) -> Limbs {
+    let mut limbs = [0u64; 4];
+    let mut i = 0;
+    while i < 4 {
+        let offset = 24 - (i * 8);
+        limbs[i] = u64::from_be_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+            bytes[offset + 4],
+            bytes[offset + 5],
+            bytes[offset + 6],
+            bytes[offset + 7],
+        ]);
+        i += 1;
+    }
+
+    limbs
+}
+
+pub fn to_be_bytes(limbs: &Limbs) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for i in 0..4 {
+        let offset = 24 - (i * 8);
+        bytes[offset..offset + 8].copy_from_slice(&limbs[i].to_be_bytes());
+    }
+
+    bytes
+}
+
+pub const fn add_with_carry(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let sum = a as u128 + b as u128 + carry as u128;
+    (sum as u64, (sum >> 64) as u64)
+}
+
+pub const fn subtract_with_borrow(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let difference = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (difference as u64, ((difference >> 64) as u64) & 1)
+}
+
+pub const fn multiply_add_with_carry(a: u64, b: u64, addend: u64, carry: u64) -> (u64, u64) {
+    // a * b + addend + carry can never overflow 128 bits: (2^64 - 1)^2 + 2(2^64 - 1) = 2^128 - 1.
+    let product = (a as u128) * (b as u128) + addend as u128 + carry as u128;
+    (product as u64, (product >> 64) as u64)
+}
+
+pub fn add(a: &Limbs, b: &Limbs) -> (Limbs, u64) {
+    let mut result = [0u64; 4];
+    let mut carry = 0;
+    for i in 0..4 {
+        (result[i], carry) = add_with_carry(a[i], b[i], carry);
+    }
+
+    (result, carry)
+}
+
+pub fn subtract(a: &Limbs, b: &Limbs) -> (Limbs, u64) {
+    let mut result = [0u64; 4];
+    let mut borrow = 0;
+    for i in 0..4 {
+        (result[i], borrow) = subtract_with_borrow(a[i], b[i], borrow);
+    }
+
+    (result, borrow)
+}
+
+pub fn multiply_wide(a: &Limbs, b: &Limbs) -> [u64; 8] {
+    // Schoolbook multiplication; a 256 bit by 256 bit product is at most 512 bits long.
+    let mut result = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0;
+        for j in 0..4 {
+            (result[i + j], carry) = multiply_add_with_carry(a[i], b[j], result[i + j], carry);
+        }
+
+        result[i + 4] = carry;
+    }
+
+    result
+}
+
+pub const fn mask_from(choice: bool) -> u64 {
+    // 0 -> 0x0000000000000000, 1 -> 0xFFFFFFFFFFFFFFFF
+    0u64.wrapping_sub(choice as u64)
+}
+
+pub fn select(a: &Limbs, b: &Limbs, choose_b: bool) -> Limbs {
+    let mask = mask_from(choose_b);
+    let mut result = [0u64; 4];
+    for i in 0..4 {
+        result[i] = (a[i] & !mask) | (b[i] & mask);
+    }
+
+    result
+}
+
+pub fn is_zero(limbs: &Limbs) -> bool {
+    (limbs[0] | limbs[1] | limbs[2] | limbs[3]) == 0
+}
+
+pub fn equals(a: &Limbs, b: &Limbs) -> bool {
+    ((a[0] ^ b[0]) | (a[1] ^ b[1]) | (a[2] ^ b[2]) | (a[3] ^ b[3])) == 0
+}
+
+pub fn is_less_than(a: &Limbs, b: &Limbs) -> bool {
+    // a < b if and only if a - b borrows.
+    let (_, borrow) = subtract(a, b);
+    borrow == 1
+}
+
+pub fn reduce_once(value: &Limbs, carry: u64, modulus: &Limbs) -> Limbs {
+    // Reduces a value in the range [0, 2m) with an optional carry bit into the range [0, m).
+    let (reduced, borrow) = subtract(value, modulus);
+
+    // If there was a carry, the value was definitely >= m. Otherwise, the value was >= m if there was no borrow.
+    select(value, &reduced, (carry | (borrow ^ 1)) == 1)
+}
+
+pub fn fold_wide(wide: &[u64; 8], c: &Limbs) -> [u64; 8] {
+    // For a modulus m = 2^256 - c, 2^256 ≡ c (mod m); a 512 bit value hi * 2^256 + lo ≡ hi * c + lo (mod m).
+    // The result is congruent to the input, but not necessarily fully reduced; callers repeat the fold until the high half is zero.
+    let mut result = [0u64; 8];
+    result[..4].copy_from_slice(&wide[..4]);
+    for i in 0..4 {
+        let mut carry = 0;
+        for j in 0..4 {
+            (result[i + j], carry) =
+                multiply_add_with_carry(wide[4 + i], c[j], result[i + j], carry);
+        }
+
+        let mut k = i + 4;
+        while k < 8 {
+            (result[k], carry) = add_with_carry(result[k], 0, carry);
+            k += 1;
+        }
+    }
+
+    result
+}
+
+pub fn zero(limbs: &mut Limbs) {
+    for limb in limbs.iter_mut() {
+        // Volatile writes can't be optimized away, even if the limbs are never read again.
+        unsafe { core::ptr::write_volatile(limb, 0) };
+    }
+}
@@ -14,6 +14,22 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod field_element;
+mod limbs;
+mod projective_point;
+mod scalar;
+
+pub mod adaptor;
+pub mod dleq;
+pub mod ecdsa;
+pub mod frost;
+pub mod musig2;
+pub mod schnorr;
+
+pub use field_element::FieldElement;
+pub use projective_point::ProjectivePoint;
+pub use scalar::Scalar;
+
 use super::{EllipticCurvePoint, EllipticCurvePointMultiplicationContext, PRIVATE_KEY_PREFIX};
 use crate::{global_runtime_immutable::GlobalRuntimeImmutable, integers::BigUnsigned};
 
@@ -22,7 +38,7 @@ use crate::{global_runtime_immutable::GlobalRuntimeImmutable, integers::BigUnsig
 // Prime finite field:
 // p = FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFE FFFFFC2F
 //   = 2^256 − 2^32 − 2^9 − 2^8 − 2^7 − 2^6 − 2^4 − 1
-static mut P: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+static P: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     GlobalRuntimeImmutable::from(|| {
         BigUnsigned::from_be_bytes(&[
             0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
@@ -32,7 +48,7 @@ static mut P: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     });
 
 // Prime identity, used for point decompression.
-static mut P_I: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+static P_I: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     GlobalRuntimeImmutable::from(|| {
         let mut i = p().clone();
         i.add(&[1]);
@@ -44,18 +60,18 @@ static mut P_I: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
 // Point doubling coefficient:
 // a = 00000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
 //   = 0
-static mut A: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+static A: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     GlobalRuntimeImmutable::from(|| BigUnsigned::from_be_bytes(&[0x00]));
 
 // Curve coefficient:
 // b = 00000000 00000000 00000000 00000000 00000000 00000000 00000000 00000007
 //   = 7
-static mut B: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+static B: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     GlobalRuntimeImmutable::from(|| BigUnsigned::from_be_bytes(&[0x07]));
 
 // Generator Point:
 // Xg = 79BE667E F9DCBBAC 55A06295 CE870B07 029BFCDB 2DCE28D9 59F2815B 16F81798
-static mut G_X: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+static G_X: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     GlobalRuntimeImmutable::from(|| {
         BigUnsigned::from_be_bytes(&[
             0x79, 0xBE, 0x66, 0x7E, 0xF9, 0xDC, 0xBB, 0xAC, 0x55, 0xA0, 0x62, 0x95, 0xCE, 0x87,
@@ -65,7 +81,7 @@ static mut G_X: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     });
 
 // Yg = 483ADA77 26A3C465 5DA4FBFC 0E1108A8 FD17B448 A6855419 9C47D08F FB10D4B8
-static mut G_Y: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+static G_Y: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     GlobalRuntimeImmutable::from(|| {
         BigUnsigned::from_be_bytes(&[
             0x48, 0x3A, 0xDA, 0x77, 0x26, 0xA3, 0xC4, 0x65, 0x5D, 0xA4, 0xFB, 0xFC, 0x0E, 0x11,
@@ -76,7 +92,7 @@ static mut G_Y: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
 
 // Order (number of points reachable from the generator point):
 // n = FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFE BAAEDCE6 AF48A03B BFD25E8C D0364141
-static mut N: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+static N: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     GlobalRuntimeImmutable::from(|| {
         BigUnsigned::from_be_bytes(&[
             0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
@@ -85,6 +101,8 @@ static mut N: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
         ])
     });
 
+// The dedicated FieldElement, Scalar and ProjectivePoint types are what we use for performance-sensitive secp256k1 operations;
+// the generic, BigUnsigned-based multiplication context is used where we treat every curve the same way, such as key tweaking.
 pub fn point_multiplication_context() -> EllipticCurvePointMultiplicationContext {
     EllipticCurvePointMultiplicationContext::new(64, n(), p(), p_i(), a(), b())
 }
@@ -96,6 +114,41 @@ pub fn serialized_private_key_bytes(key: &[u8]) -> [u8; 33] {
     bytes
 }
 
+pub fn try_derive_serialized_public_key(private_key: &[u8]) -> Option<[u8; 33]> {
+    // Private keys must be in the range 0 < k < n.
+    let mut scalar = match Scalar::try_from_be_slice(private_key) {
+        Some(s) => s,
+        None => return None,
+    };
+
+    if scalar.is_zero() {
+        return None;
+    }
+
+    let mut point = ProjectivePoint::multiply_generator(&scalar);
+    let serialized = point.try_serialize_compressed();
+
+    // Zero our working values; they could otherwise leak the private key.
+    scalar.zero();
+    point.zero();
+    serialized
+}
+
+pub fn try_decode_public_key(bytes: &[u8]) -> Option<ProjectivePoint> {
+    if bytes.len() == 32 {
+        // An x-only public key (BIP 340); the Y coordinate is implicitly even.
+        let mut x = [0u8; 32];
+        x.copy_from_slice(bytes);
+        match FieldElement::from_be_bytes(&x) {
+            Some(x) => ProjectivePoint::try_from_x(&x, true),
+            None => None,
+        }
+    } else {
+        // A compressed or uncompressed public key.
+        ProjectivePoint::try_from_serialized(bytes)
+    }
+}
+
 pub fn serialized_public_key_bytes(mut key: EllipticCurvePoint) -> Option<[u8; 33]> {
     let serialized = key.try_serialize_compressed();
     key.set_infinity();
@@ -103,29 +156,29 @@ pub fn serialized_public_key_bytes(mut key: EllipticCurvePoint) -> Option<[u8; 3
 }
 
 pub fn g_x() -> &'static BigUnsigned {
-    unsafe { G_X.value() }
+    G_X.value()
 }
 
 pub fn g_y() -> &'static BigUnsigned {
-    unsafe { G_Y.value() }
+    G_Y.value()
 }
 
 pub fn n() -> &'static BigUnsigned {
-    unsafe { N.value() }
+    N.value()
 }
 
 fn p_i() -> &'static BigUnsigned {
-    unsafe { P_I.value() }
+    P_I.value()
 }
 
 fn p() -> &'static BigUnsigned {
-    unsafe { P.value() }
+    P.value()
 }
 
 fn a() -> &'static BigUnsigned {
-    unsafe { A.value() }
+    A.value()
 }
 
 fn b() -> &'static BigUnsigned {
-    unsafe { B.value() }
+    B.value()
 }
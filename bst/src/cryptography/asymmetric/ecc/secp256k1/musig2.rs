@@ -0,0 +1,494 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    schnorr::{self, tagged_hash, try_get_x_only_coordinates},
+    ProjectivePoint, Scalar,
+};
+use crate::String16;
+use alloc::vec::Vec;
+use macros::s16;
+
+// MuSig2 multi-signatures, as specified in BIP 327. The signers' keys are aggregated into a single key, and their partial signatures
+// into a single BIP 340 Schnorr signature, which is indistinguishable from a signature produced by a single signer.
+pub const PUBLIC_KEY_LENGTH: usize = 33;
+pub const PUBLIC_NONCE_LENGTH: usize = 66;
+pub const SECRET_NONCE_LENGTH: usize = 97;
+pub const PARTIAL_SIGNATURE_LENGTH: usize = 32;
+
+pub fn sort_public_keys(public_keys: &mut [[u8; PUBLIC_KEY_LENGTH]]) {
+    // KeySort; the lexicographical order of the serialized keys.
+    public_keys.sort();
+}
+
+pub struct KeyAggregationContext {
+    public_keys: Vec<[u8; PUBLIC_KEY_LENGTH]>,
+    list_hash: [u8; 32],
+    second_key: [u8; PUBLIC_KEY_LENGTH],
+    aggregate_point: ProjectivePoint,
+    gacc: Scalar,
+    tacc: Scalar,
+}
+
+impl KeyAggregationContext {
+    pub fn try_from(public_keys: &[[u8; PUBLIC_KEY_LENGTH]]) -> Result<Self, String16<'static>> {
+        if public_keys.len() == 0 {
+            return Err(s16!("At least one public key is required."));
+        }
+
+        // L = hash_KeyAgg list(pk1 || pk2 || ... || pku)
+        let keys: Vec<&[u8]> = public_keys.iter().map(|k| &k[..]).collect();
+        let list_hash = tagged_hash(b"KeyAgg list", &keys);
+
+        // The first key which differs from the first key in the list gets a coefficient of 1; if there is no such key,
+        // we use 33 zero bytes, which can't match any valid key.
+        let second_key = match public_keys.iter().find(|k| **k != public_keys[0]) {
+            Some(k) => *k,
+            None => [0u8; PUBLIC_KEY_LENGTH],
+        };
+
+        let mut context = Self {
+            public_keys: public_keys.into(),
+            list_hash,
+            second_key,
+            aggregate_point: ProjectivePoint::INFINITY,
+            gacc: Scalar::ONE,
+            tacc: Scalar::ZERO,
+        };
+
+        // Q = a1 * P1 + a2 * P2 + ... + au * Pu
+        let mut aggregate_point = ProjectivePoint::INFINITY;
+        for public_key in public_keys {
+            let point = match ProjectivePoint::try_from_serialized(public_key) {
+                Some(p) => p,
+                None => return Err(s16!("A public key is invalid.")),
+            };
+
+            aggregate_point = aggregate_point
+                .add(&point.multiply_variable_time(&context.coefficient(public_key)));
+        }
+
+        if aggregate_point.is_infinity() {
+            return Err(s16!("The aggregate public key is the point at infinity."));
+        }
+
+        context.aggregate_point = aggregate_point;
+        Ok(context)
+    }
+
+    pub fn try_apply_tweak(
+        &mut self,
+        tweak: &[u8; 32],
+        is_x_only: bool,
+    ) -> Result<(), String16<'static>> {
+        // x-only tweaks are applied to the key with an even Y coordinate, so we negate Q if its Y coordinate is odd.
+        let g = if is_x_only && !self.has_even_y() {
+            Scalar::ONE.negate()
+        } else {
+            Scalar::ONE
+        };
+
+        let t = match Scalar::from_be_bytes(tweak) {
+            Some(t) => t,
+            None => return Err(s16!("The tweak is not less than the curve order.")),
+        };
+
+        // Q' = gQ + tG
+        let aggregate_point = self
+            .aggregate_point
+            .multiply_variable_time(&g)
+            .add(&ProjectivePoint::multiply_generator(&t));
+
+        if aggregate_point.is_infinity() {
+            return Err(s16!("The tweaked public key is the point at infinity."));
+        }
+
+        // gacc' = g * gacc, tacc' = t + g * tacc (mod n)
+        self.aggregate_point = aggregate_point;
+        self.gacc = g.multiply(&self.gacc);
+        self.tacc = t.add(&g.multiply(&self.tacc));
+        Ok(())
+    }
+
+    pub fn public_keys(&self) -> &[[u8; PUBLIC_KEY_LENGTH]] {
+        &self.public_keys
+    }
+
+    pub fn x_only_aggregate_public_key(&self) -> [u8; schnorr::PUBLIC_KEY_LENGTH] {
+        try_get_x_only_coordinates(&self.aggregate_point).unwrap().0
+    }
+
+    fn has_even_y(&self) -> bool {
+        try_get_x_only_coordinates(&self.aggregate_point).unwrap().1
+    }
+
+    fn coefficient(&self, public_key: &[u8; PUBLIC_KEY_LENGTH]) -> Scalar {
+        // KeyAggCoeff; 1 for the second distinct key, otherwise int(hash_KeyAgg coefficient(L || pk)) (mod n).
+        if *public_key == self.second_key {
+            Scalar::ONE
+        } else {
+            Scalar::from_be_bytes_reduced(&tagged_hash(
+                b"KeyAgg coefficient",
+                &[&self.list_hash, public_key],
+            ))
+        }
+    }
+
+    fn signing_coefficient(&self) -> Scalar {
+        // g * gacc (mod n), where g is -1 if Q has an odd Y coordinate; this accounts for the implicit negation of the
+        // aggregate public key when it's used as an x-only key, and the negations of any x-only tweaks.
+        if self.has_even_y() {
+            self.gacc
+        } else {
+            self.gacc.negate()
+        }
+    }
+}
+
+pub struct SecretNonce([u8; SECRET_NONCE_LENGTH]);
+
+impl SecretNonce {
+    #[cfg(test)]
+    pub const fn from(bytes: [u8; SECRET_NONCE_LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn zero(&mut self) {
+        self.0.fill(0);
+    }
+}
+
+impl Drop for SecretNonce {
+    fn drop(&mut self) {
+        self.zero();
+    }
+}
+
+pub fn try_generate_nonce(
+    random: &[u8; 32],
+    private_key: Option<&[u8; 32]>,
+    public_key: &[u8; PUBLIC_KEY_LENGTH],
+    aggregate_public_key: Option<&[u8; schnorr::PUBLIC_KEY_LENGTH]>,
+    message: Option<&[u8]>,
+    extra_input: Option<&[u8]>,
+) -> Result<(SecretNonce, [u8; PUBLIC_NONCE_LENGTH]), String16<'static>> {
+    // NonceGen; if a private key is provided, rand = sk XOR hash_MuSig/aux(rand'), otherwise rand = rand'.
+    let mut rand = *random;
+    if let Some(private_key) = private_key {
+        let mut auxiliary_hash = tagged_hash(b"MuSig/aux", &[random]);
+        for i in 0..32 {
+            rand[i] = private_key[i] ^ auxiliary_hash[i];
+        }
+
+        auxiliary_hash.fill(0);
+    }
+
+    let aggregate_public_key: &[u8] = match aggregate_public_key {
+        Some(k) => k,
+        None => &[],
+    };
+
+    let extra_input = extra_input.unwrap_or(&[]);
+    let mut message_prefix = [0u8; 9];
+    let message_prefix: &[u8] = match message {
+        Some(m) => {
+            message_prefix[0] = 1;
+            message_prefix[1..].copy_from_slice(&(m.len() as u64).to_be_bytes());
+            &message_prefix
+        }
+        None => &message_prefix[..1],
+    };
+
+    // k_i = int(hash_MuSig/nonce(rand || len(pk) || pk || len(aggpk) || aggpk || m_prefixed || len(extra_in) || extra_in || i - 1)) (mod n)
+    let mut k = [Scalar::ZERO; 2];
+    for i in 0..2 {
+        let mut nonce_hash = tagged_hash(
+            b"MuSig/nonce",
+            &[
+                &rand,
+                &[public_key.len() as u8],
+                public_key,
+                &[aggregate_public_key.len() as u8],
+                aggregate_public_key,
+                message_prefix,
+                message.unwrap_or(&[]),
+                &(extra_input.len() as u32).to_be_bytes(),
+                extra_input,
+                &[i as u8],
+            ],
+        );
+
+        k[i] = Scalar::from_be_bytes_reduced(&nonce_hash);
+        nonce_hash.fill(0);
+    }
+
+    rand.fill(0);
+    if k[0].is_zero() || k[1].is_zero() {
+        return Err(s16!("A generated nonce is zero."));
+    }
+
+    // secnonce = bytes(k1) || bytes(k2) || pk, pubnonce = cbytes(k1G) || cbytes(k2G)
+    let mut secret_nonce = SecretNonce([0u8; SECRET_NONCE_LENGTH]);
+    let mut public_nonce = [0u8; PUBLIC_NONCE_LENGTH];
+    for i in 0..2 {
+        let mut point = ProjectivePoint::multiply_generator(&k[i]);
+        public_nonce[i * 33..(i + 1) * 33]
+            .copy_from_slice(&point.try_serialize_compressed().unwrap());
+        secret_nonce.0[i * 32..(i + 1) * 32].copy_from_slice(&k[i].to_be_bytes());
+        point.zero();
+        k[i].zero();
+    }
+
+    secret_nonce.0[64..].copy_from_slice(public_key);
+    Ok((secret_nonce, public_nonce))
+}
+
+pub fn try_aggregate_nonces(
+    public_nonces: &[[u8; PUBLIC_NONCE_LENGTH]],
+) -> Result<[u8; PUBLIC_NONCE_LENGTH], String16<'static>> {
+    // NonceAgg; R_j = R_1,j + R_2,j + ... + R_u,j for j in 1, 2.
+    let mut aggregate_nonce = [0u8; PUBLIC_NONCE_LENGTH];
+    for j in 0..2 {
+        let mut sum = ProjectivePoint::INFINITY;
+        for public_nonce in public_nonces {
+            sum = match ProjectivePoint::try_from_serialized(&public_nonce[j * 33..(j + 1) * 33]) {
+                Some(p) => sum.add(&p),
+                None => return Err(s16!("A public nonce is invalid.")),
+            };
+        }
+
+        // The sum can be infinity (although not without a dishonest signer), in which case it's encoded as 33 zero bytes.
+        if let Some(bytes) = sum.try_serialize_compressed() {
+            aggregate_nonce[j * 33..(j + 1) * 33].copy_from_slice(&bytes);
+        }
+    }
+
+    Ok(aggregate_nonce)
+}
+
+pub struct SigningSession {
+    key_context: KeyAggregationContext,
+    b: Scalar,
+    r: [u8; 32],
+    r_has_even_y: bool,
+    e: Scalar,
+}
+
+impl SigningSession {
+    pub fn try_from(
+        key_context: KeyAggregationContext,
+        aggregate_nonce: &[u8; PUBLIC_NONCE_LENGTH],
+        message: &[u8],
+    ) -> Result<Self, String16<'static>> {
+        // GetSessionValues; b = int(hash_MuSig/noncecoef(aggnonce || xbytes(Q) || m)) (mod n)
+        let aggregate_public_key = key_context.x_only_aggregate_public_key();
+        let b = Scalar::from_be_bytes_reduced(&tagged_hash(
+            b"MuSig/noncecoef",
+            &[aggregate_nonce, &aggregate_public_key, message],
+        ));
+
+        // R = R1 + b * R2, or G if that sum is infinity.
+        let mut r_points = [ProjectivePoint::INFINITY; 2];
+        for j in 0..2 {
+            let bytes = &aggregate_nonce[j * 33..(j + 1) * 33];
+            if bytes.iter().any(|b| *b != 0) {
+                r_points[j] = match ProjectivePoint::try_from_serialized(bytes) {
+                    Some(p) => p,
+                    None => return Err(s16!("The aggregate nonce is invalid.")),
+                }
+            }
+        }
+
+        let mut r_point = r_points[0].add(&r_points[1].multiply_variable_time(&b));
+        if r_point.is_infinity() {
+            r_point = ProjectivePoint::GENERATOR;
+        }
+
+        // e = int(hash_BIP0340/challenge(xbytes(R) || xbytes(Q) || m)) (mod n)
+        let (r, r_has_even_y) = try_get_x_only_coordinates(&r_point).unwrap();
+        let e = schnorr::challenge(&r, &aggregate_public_key, message);
+        Ok(Self {
+            key_context,
+            b,
+            r,
+            r_has_even_y,
+            e,
+        })
+    }
+
+    pub fn key_context(&self) -> &KeyAggregationContext {
+        &self.key_context
+    }
+
+    pub fn try_sign(
+        &self,
+        secret_nonce: &mut SecretNonce,
+        private_key: &[u8; 32],
+    ) -> Result<[u8; PARTIAL_SIGNATURE_LENGTH], String16<'static>> {
+        // Sign; read the nonces, and immediately zero the secret nonce, so it can never be reused.
+        let mut k_bytes = [0u8; 32];
+        let mut k = [Scalar::ZERO; 2];
+        let mut k_valid = true;
+        for i in 0..2 {
+            k_bytes.copy_from_slice(&secret_nonce.0[i * 32..(i + 1) * 32]);
+            match Scalar::from_be_bytes(&k_bytes) {
+                Some(s) => {
+                    k_valid &= !s.is_zero();
+                    k[i] = s;
+                }
+                None => k_valid = false,
+            }
+        }
+
+        let mut public_key = [0u8; PUBLIC_KEY_LENGTH];
+        public_key.copy_from_slice(&secret_nonce.0[64..]);
+        secret_nonce.zero();
+        k_bytes.fill(0);
+
+        if !k_valid {
+            k[0].zero();
+            k[1].zero();
+            return Err(s16!(
+                "The secret nonce is invalid, or has already been used."
+            ));
+        }
+
+        // The public nonce is needed to verify the partial signature before we return it.
+        let mut public_nonce = [0u8; PUBLIC_NONCE_LENGTH];
+        for i in 0..2 {
+            public_nonce[i * 33..(i + 1) * 33].copy_from_slice(
+                &ProjectivePoint::multiply_generator(&k[i])
+                    .try_serialize_compressed()
+                    .unwrap(),
+            );
+
+            // k_i = k'_i if R has an even Y coordinate, otherwise n - k'_i.
+            k[i] = Scalar::select(&k[i].negate(), &k[i], self.r_has_even_y);
+        }
+
+        let mut d = match Scalar::from_be_bytes(private_key) {
+            Some(d) => d,
+            None => Scalar::ZERO,
+        };
+
+        let result = if d.is_zero() {
+            Err(s16!("The private key is invalid."))
+        } else if ProjectivePoint::multiply_generator(&d).try_serialize_compressed()
+            != Some(public_key)
+        {
+            Err(s16!("The private key does not belong to the secret nonce."))
+        } else if !self.key_context.public_keys.contains(&public_key) {
+            Err(s16!(
+                "The private key does not belong to any of the signers."
+            ))
+        } else {
+            // d = g * gacc * d' (mod n), s = k1 + b * k2 + e * a * d (mod n)
+            d = self.key_context.signing_coefficient().multiply(&d);
+            let a = self.key_context.coefficient(&public_key);
+            let s = k[0]
+                .add(&self.b.multiply(&k[1]))
+                .add(&self.e.multiply(&a).multiply(&d));
+
+            let partial_signature = s.to_be_bytes();
+            if self.verify_partial_signature(&partial_signature, &public_nonce, &public_key) {
+                Ok(partial_signature)
+            } else {
+                Err(s16!("The partial signature failed verification."))
+            }
+        };
+
+        k[0].zero();
+        k[1].zero();
+        d.zero();
+        result
+    }
+
+    pub fn verify_partial_signature(
+        &self,
+        partial_signature: &[u8; PARTIAL_SIGNATURE_LENGTH],
+        public_nonce: &[u8; PUBLIC_NONCE_LENGTH],
+        public_key: &[u8; PUBLIC_KEY_LENGTH],
+    ) -> bool {
+        // PartialSigVerifyInternal
+        let s = match Scalar::from_be_bytes(partial_signature) {
+            Some(s) => s,
+            None => return false,
+        };
+
+        if !self.key_context.public_keys.contains(public_key) {
+            return false;
+        }
+
+        let public_point = match ProjectivePoint::try_from_serialized(public_key) {
+            Some(p) => p,
+            None => return false,
+        };
+
+        let (r1, r2) = match (
+            ProjectivePoint::try_from_serialized(&public_nonce[..33]),
+            ProjectivePoint::try_from_serialized(&public_nonce[33..]),
+        ) {
+            (Some(r1), Some(r2)) => (r1, r2),
+            _ => return false,
+        };
+
+        // Re = R1 + b * R2, negated if R has an odd Y coordinate.
+        let mut effective_nonce = r1.add(&r2.multiply_variable_time(&self.b));
+        if !self.r_has_even_y {
+            effective_nonce = effective_nonce.negate();
+        }
+
+        // sG = Re + e * a * g * gacc * P
+        let multiplier = self
+            .e
+            .multiply(&self.key_context.coefficient(public_key))
+            .multiply(&self.key_context.signing_coefficient());
+
+        ProjectivePoint::multiply_generator(&s)
+            .equals(&effective_nonce.add(&public_point.multiply_variable_time(&multiplier)))
+    }
+
+    pub fn try_aggregate_partial_signatures(
+        &self,
+        partial_signatures: &[[u8; PARTIAL_SIGNATURE_LENGTH]],
+    ) -> Result<[u8; schnorr::SIGNATURE_LENGTH], String16<'static>> {
+        // PartialSigAgg; s = s1 + s2 + ... + su + e * g * tacc (mod n)
+        let mut s = Scalar::ZERO;
+        for partial_signature in partial_signatures {
+            s = match Scalar::from_be_bytes(partial_signature) {
+                Some(p) => s.add(&p),
+                None => {
+                    return Err(s16!(
+                        "A partial signature is not less than the curve order."
+                    ))
+                }
+            };
+        }
+
+        let g = if self.key_context.has_even_y() {
+            Scalar::ONE
+        } else {
+            Scalar::ONE.negate()
+        };
+
+        s = s.add(&self.e.multiply(&g).multiply(&self.key_context.tacc));
+
+        let mut signature = [0u8; schnorr::SIGNATURE_LENGTH];
+        signature[..32].copy_from_slice(&self.r);
+        signature[32..].copy_from_slice(&s.to_be_bytes());
+        Ok(signature)
+    }
+}
@@ -0,0 +1,494 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{FieldElement, Scalar};
+use crate::global_runtime_immutable::GlobalRuntimeImmutable;
+use alloc::vec::Vec;
+
+// 3 * b, where b = 7 is the secp256k1 curve coefficient; used by the complete addition formulas below.
+const B: u64 = 7;
+const B3: u64 = 3 * B;
+
+const G_X: FieldElement = FieldElement::from_limbs([
+    0x59F2815B16F81798,
+    0x029BFCDB2DCE28D9,
+    0x55A06295CE870B07,
+    0x79BE667EF9DCBBAC,
+]);
+
+const G_Y: FieldElement = FieldElement::from_limbs([
+    0x9C47D08FFB10D4B8,
+    0xFD17B448A6855419,
+    0x5DA4FBFC0E1108A8,
+    0x483ADA7726A3C465,
+]);
+
+// β, a cube root of unity mod p; for every point on the curve, λ * (x, y) = (β * x, y). See Scalar::split_lambda.
+const BETA: FieldElement = FieldElement::from_limbs([
+    0xC1396C28719501EE,
+    0x9CF0497512F58995,
+    0x6E64479EAC3434E9,
+    0x7AE96A2B657C0710,
+]);
+
+// The window width for variable-time multiplication; we precompute the odd multiples P, 3P, ..., (2^(w-1) - 1)P.
+const WNAF_WIDTH: usize = 5;
+const WNAF_TABLE_SIZE: usize = 1 << (WNAF_WIDTH - 2);
+
+// A scalar has 256 bits, but the wNAF representation can carry into a few more.
+const WNAF_LENGTH: usize = 256 + WNAF_WIDTH;
+
+// Precomputed multiples of the generator point; GENERATOR_TABLE[w][i] = i * 16^w * G, for each of the 64 4-bit windows in a scalar.
+// This takes about 96KiB, and lets us multiply the generator point with 64 additions and no doublings.
+static GENERATOR_TABLE: GlobalRuntimeImmutable<
+    Vec<[ProjectivePoint; 16]>,
+    fn() -> Vec<[ProjectivePoint; 16]>,
+> = GlobalRuntimeImmutable::from(|| {
+    let mut table = Vec::with_capacity(64);
+    let mut window_base = ProjectivePoint::GENERATOR;
+    for _ in 0..64 {
+        let mut window = [ProjectivePoint::INFINITY; 16];
+        for i in 1..16 {
+            window[i] = window[i - 1].add(&window_base);
+        }
+
+        // The next window's base is 16 times this window's base.
+        window_base = window[15].add(&window_base);
+        table.push(window);
+    }
+
+    table
+});
+
+// A secp256k1 point in homogeneous projective coordinates; the affine point is (X / Z, Y / Z), and infinity is any point with Z = 0.
+//
+// ecc::EllipticCurvePoint works with affine coordinates, which means every addition and doubling requires a modular inversion.
+// Projective coordinates defer the inversion until we actually need the affine coordinates, at the cost of a few extra
+// multiplications per operation; multiplications are much cheaper than inversions.
+//
+// The addition and doubling methods use the complete formulas from 'Complete addition formulas for prime order elliptic curves'
+// (Renes, Costello, Batina; 2015), algorithms 7 and 9, specialized for a = 0. 'Complete' means they have no special cases; the
+// same sequence of field operations gives the correct result for P + Q, P + P, P + -P, P + Infinity, and Infinity + Infinity.
+// Having no special cases means having no branches, which is what we want for constant-time scalar multiplication.
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectivePoint {
+    x: FieldElement,
+    y: FieldElement,
+    z: FieldElement,
+}
+
+impl ProjectivePoint {
+    pub const INFINITY: Self = Self {
+        x: FieldElement::ZERO,
+        y: FieldElement::ONE,
+        z: FieldElement::ZERO,
+    };
+
+    pub const GENERATOR: Self = Self {
+        x: G_X,
+        y: G_Y,
+        z: FieldElement::ONE,
+    };
+
+    pub fn try_from_affine(x: &FieldElement, y: &FieldElement) -> Option<Self> {
+        // y^2 = x^3 + 7
+        if y.square().equals(&Self::curve_right_hand_side(x)) {
+            Some(Self {
+                x: *x,
+                y: *y,
+                z: FieldElement::ONE,
+            })
+        } else {
+            // The point isn't on the curve.
+            None
+        }
+    }
+
+    pub fn try_from_x(x: &FieldElement, y_is_even: bool) -> Option<Self> {
+        // Calculate y from y^2 = x^3 + 7; if x^3 + 7 has no square root, there is no point with the x coordinate.
+        let y = match Self::curve_right_hand_side(x).try_sqrt() {
+            Some(y) => y,
+            None => return None,
+        };
+
+        // There are two possible y values for every x coordinate on the curve; y and p - y. One is odd, and the other is even.
+        let negated_y = y.negate();
+        Some(Self {
+            y: FieldElement::select(&y, &negated_y, y.is_even() != y_is_even),
+            z: FieldElement::ONE,
+            x: *x,
+        })
+    }
+
+    pub fn try_from_serialized(bytes: &[u8]) -> Option<Self> {
+        match bytes.len() {
+            33 => {
+                // Compressed; a 0x02 or 0x03 prefix indicating the evenness of y, followed by the x coordinate.
+                let y_is_even = match bytes[0] {
+                    0x02 => true,
+                    0x03 => false,
+                    _ => return None,
+                };
+
+                match Self::coordinate_from(&bytes[1..]) {
+                    Some(x) => Self::try_from_x(&x, y_is_even),
+                    None => None,
+                }
+            }
+            65 => {
+                // Uncompressed; a 0x04 prefix, followed by the x and y coordinates.
+                if bytes[0] != 0x04 {
+                    return None;
+                }
+
+                match (
+                    Self::coordinate_from(&bytes[1..33]),
+                    Self::coordinate_from(&bytes[33..]),
+                ) {
+                    (Some(x), Some(y)) => Self::try_from_affine(&x, &y),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub fn equals(&self, other: &Self) -> bool {
+        // (X1 / Z1, Y1 / Z1) = (X2 / Z2, Y2 / Z2) if and only if X1 * Z2 = X2 * Z1 and Y1 * Z2 = Y2 * Z1.
+        self.x.multiply(&other.z).equals(&other.x.multiply(&self.z))
+            & self.y.multiply(&other.z).equals(&other.y.multiply(&self.z))
+    }
+
+    pub fn negate(&self) -> Self {
+        Self {
+            y: self.y.negate(),
+            x: self.x,
+            z: self.z,
+        }
+    }
+
+    pub fn add(&self, addend: &Self) -> Self {
+        let (x1, y1, z1) = (&self.x, &self.y, &self.z);
+        let (x2, y2, z2) = (&addend.x, &addend.y, &addend.z);
+
+        let mut t0 = x1.multiply(x2);
+        let mut t1 = y1.multiply(y2);
+        let mut t2 = z1.multiply(z2);
+        let mut t3 = x1.add(y1);
+        let mut t4 = x2.add(y2);
+        t3 = t3.multiply(&t4);
+        t4 = t0.add(&t1);
+        t3 = t3.subtract(&t4);
+        t4 = y1.add(z1);
+        let mut x3 = y2.add(z2);
+        t4 = t4.multiply(&x3);
+        x3 = t1.add(&t2);
+        t4 = t4.subtract(&x3);
+        x3 = x1.add(z1);
+        let mut y3 = x2.add(z2);
+        x3 = x3.multiply(&y3);
+        y3 = t0.add(&t2);
+        y3 = x3.subtract(&y3);
+        x3 = t0.double();
+        t0 = x3.add(&t0);
+        t2 = t2.multiply_u64(B3);
+        let mut z3 = t1.add(&t2);
+        t1 = t1.subtract(&t2);
+        y3 = y3.multiply_u64(B3);
+        x3 = t4.multiply(&y3);
+        t2 = t3.multiply(&t1);
+        x3 = t2.subtract(&x3);
+        y3 = y3.multiply(&t0);
+        t1 = t1.multiply(&z3);
+        y3 = t1.add(&y3);
+        t0 = t0.multiply(&t3);
+        z3 = z3.multiply(&t4);
+        z3 = z3.add(&t0);
+
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    pub fn double(&self) -> Self {
+        let (x, y, z) = (&self.x, &self.y, &self.z);
+
+        let mut t0 = y.square();
+        let mut z3 = t0.double();
+        z3 = z3.double();
+        z3 = z3.double();
+        let mut t1 = y.multiply(z);
+        let mut t2 = z.square();
+        t2 = t2.multiply_u64(B3);
+        let mut x3 = t2.multiply(&z3);
+        let mut y3 = t0.add(&t2);
+        z3 = t1.multiply(&z3);
+        t1 = t2.double();
+        t2 = t1.add(&t2);
+        t0 = t0.subtract(&t2);
+        y3 = t0.multiply(&y3);
+        y3 = x3.add(&y3);
+        t1 = x.multiply(y);
+        x3 = t0.multiply(&t1);
+        x3 = x3.double();
+
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    pub fn multiply(&self, multiplier: &Scalar) -> Self {
+        // Constant-time fixed-window multiplication. This is double-and-add (see ecc::EllipticCurvePointMultiplicationContext::multiply_point),
+        // but processing 4 bits at a time; we precompute 0P through 15P, then for each 4 bit window of the multiplier, from most to least
+        // significant, we double the product 4 times and add the table entry for the window's value.
+        //
+        // Every table entry is read for every window, and every window performs an addition (adding infinity for a zero window),
+        // so the sequence of operations and memory accesses doesn't depend on the multiplier.
+        let mut table = [Self::INFINITY; 16];
+        for i in 1..16 {
+            table[i] = table[i - 1].add(self);
+        }
+
+        let mut product = Self::INFINITY;
+        for window in (0..64).rev() {
+            product = product.double().double().double().double();
+
+            let mut addend =
+                Self::select_from_table(&table, Self::window_value(multiplier, window));
+            product = product.add(&addend);
+            addend.zero();
+        }
+
+        for entry in table.iter_mut() {
+            entry.zero();
+        }
+
+        product
+    }
+
+    pub fn multiply_generator(multiplier: &Scalar) -> Self {
+        // The same as multiply, but the generator point's table for each window is precomputed, with the doublings baked in;
+        // window w's table contains the multiples of 16^w * G. That leaves just one constant-time lookup and one addition per window.
+        let table = generator_table();
+        let mut product = Self::INFINITY;
+        for window in 0..64 {
+            let mut addend =
+                Self::select_from_table(&table[window], Self::window_value(multiplier, window));
+            product = product.add(&addend);
+            addend.zero();
+        }
+
+        product
+    }
+
+    pub fn multiply_variable_time(&self, multiplier: &Scalar) -> Self {
+        // NOT constant-time; only use this where the multiplier is public, like when verifying signatures or tweaking public keys.
+        //
+        // We split the multiplier with the GLV endomorphism, so that kP = k1 * P + k2 * λP, where k1 and k2 are ~128 bits long, then
+        // write k1 and k2 in width-w non-adjacent form (wNAF). In wNAF, each non-zero digit is odd and in the range (-2^(w-1), 2^(w-1)),
+        // and any w consecutive digits contain at most one non-zero digit. That means we only need to precompute the odd multiples
+        // of P and λP, and we perform roughly one addition per w bits of each half of the multiplier, with only 128 doublings in total.
+        let (mut k1, mut k2) = multiplier.split_lambda();
+
+        // Work with the low value of each half, and negate the point instead; -k * P = k * -P.
+        let negate_k1 = k1.is_high();
+        let negate_k2 = k2.is_high();
+        if negate_k1 {
+            k1 = k1.negate();
+        }
+
+        if negate_k2 {
+            k2 = k2.negate();
+        }
+
+        let (k1_digits, k1_length) = Self::wnaf_digits(&k1);
+        let (k2_digits, k2_length) = Self::wnaf_digits(&k2);
+
+        // table[i] = (2i + 1)P, lambda_table[i] = (2i + 1)λP
+        let mut table = [*self; WNAF_TABLE_SIZE];
+        let doubled = self.double();
+        for i in 1..WNAF_TABLE_SIZE {
+            table[i] = table[i - 1].add(&doubled);
+        }
+
+        let mut lambda_table = [Self::INFINITY; WNAF_TABLE_SIZE];
+        for i in 0..WNAF_TABLE_SIZE {
+            lambda_table[i] = table[i].endomorphism();
+            if negate_k1 {
+                table[i] = table[i].negate();
+            }
+
+            if negate_k2 {
+                lambda_table[i] = lambda_table[i].negate();
+            }
+        }
+
+        let mut product = Self::INFINITY;
+        for i in (0..k1_length.max(k2_length)).rev() {
+            product = product.double();
+            product = Self::add_wnaf_digit(&product, &table, k1_digits[i]);
+            product = Self::add_wnaf_digit(&product, &lambda_table, k2_digits[i]);
+        }
+
+        product
+    }
+
+    pub fn try_to_affine(&self) -> Option<(FieldElement, FieldElement)> {
+        if self.is_infinity() {
+            // Infinity has no affine coordinates.
+            return None;
+        }
+
+        let z_inverse = self.z.invert();
+        Some((self.x.multiply(&z_inverse), self.y.multiply(&z_inverse)))
+    }
+
+    pub fn try_serialize_compressed(&self) -> Option<[u8; 33]> {
+        let (x, y) = match self.try_to_affine() {
+            Some(c) => c,
+            None => return None,
+        };
+
+        let mut bytes = [0u8; 33];
+        bytes[0] = if y.is_even() { 0x02 } else { 0x03 };
+        bytes[1..].copy_from_slice(&x.to_be_bytes());
+        Some(bytes)
+    }
+
+    pub fn try_serialize_uncompressed(&self) -> Option<[u8; 65]> {
+        let (x, y) = match self.try_to_affine() {
+            Some(c) => c,
+            None => return None,
+        };
+
+        let mut bytes = [0u8; 65];
+        bytes[0] = 0x04;
+        bytes[1..33].copy_from_slice(&x.to_be_bytes());
+        bytes[33..].copy_from_slice(&y.to_be_bytes());
+        Some(bytes)
+    }
+
+    pub fn select(a: &Self, b: &Self, choose_b: bool) -> Self {
+        Self {
+            x: FieldElement::select(&a.x, &b.x, choose_b),
+            y: FieldElement::select(&a.y, &b.y, choose_b),
+            z: FieldElement::select(&a.z, &b.z, choose_b),
+        }
+    }
+
+    pub fn zero(&mut self) {
+        self.x.zero();
+        self.y.zero();
+        self.z.zero();
+    }
+
+    fn endomorphism(&self) -> Self {
+        // λ(X / Z, Y / Z) = (β * X / Z, Y / Z)
+        Self {
+            x: self.x.multiply(&BETA),
+            y: self.y,
+            z: self.z,
+        }
+    }
+
+    fn window_value(multiplier: &Scalar, window: usize) -> u64 {
+        (multiplier.limbs()[window / 16] >> ((window % 16) * 4)) & 0xF
+    }
+
+    fn select_from_table(table: &[Self; 16], index: u64) -> Self {
+        let mut selected = Self::INFINITY;
+        for i in 0..16 {
+            // (i ^ index) - 1 only underflows, setting the top bit, when i == index.
+            selected = Self::select(
+                &selected,
+                &table[i],
+                (i as u64 ^ index).wrapping_sub(1) >> 63 == 1,
+            );
+        }
+
+        selected
+    }
+
+    fn wnaf_digits(scalar: &Scalar) -> ([i8; WNAF_LENGTH], usize) {
+        // Scan the scalar from least to most significant bit. When we find a set bit (accounting for any carry), we take the w bit
+        // window starting at that bit as a digit; if the digit is >= 2^(w-1), we subtract 2^w to make it negative, and carry
+        // 2^w into the next window to compensate. The digit is odd, so the next w - 1 digits are zero.
+        let limbs = scalar.limbs();
+        let bit_at = |bit: usize| -> u64 {
+            if bit < 256 {
+                (limbs[bit / 64] >> (bit % 64)) & 1
+            } else {
+                0
+            }
+        };
+
+        let mut digits = [0i8; WNAF_LENGTH];
+        let mut length = 0;
+        let mut carry = 0;
+        let mut bit = 0;
+        while bit < WNAF_LENGTH {
+            if bit_at(bit) == carry {
+                bit += 1;
+                continue;
+            }
+
+            let mut window = carry;
+            for offset in 0..WNAF_WIDTH {
+                window += bit_at(bit + offset) << offset;
+            }
+
+            carry = (window >> (WNAF_WIDTH - 1)) & 1;
+            digits[bit] = (window as i64 - ((carry as i64) << WNAF_WIDTH)) as i8;
+            length = bit + 1;
+            bit += WNAF_WIDTH;
+        }
+
+        (digits, length)
+    }
+
+    fn add_wnaf_digit(point: &Self, table: &[Self; WNAF_TABLE_SIZE], digit: i8) -> Self {
+        if digit > 0 {
+            point.add(&table[(digit as usize) / 2])
+        } else if digit < 0 {
+            point.add(&table[(-digit as usize) / 2].negate())
+        } else {
+            *point
+        }
+    }
+
+    fn curve_right_hand_side(x: &FieldElement) -> FieldElement {
+        // x^3 + 7
+        x.square().multiply(x).add(&FieldElement::from_u64(B))
+    }
+
+    fn coordinate_from(bytes: &[u8]) -> Option<FieldElement> {
+        let mut buffer = [0u8; 32];
+        buffer.copy_from_slice(bytes);
+        FieldElement::from_be_bytes(&buffer)
+    }
+}
+
+fn generator_table() -> &'static Vec<[ProjectivePoint; 16]> {
+    GENERATOR_TABLE.value()
+}
@@ -0,0 +1,239 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::limbs::{self, Limbs};
+
+// The order of the secp256k1 generator point; scalars are integers in the range [0, n).
+// Like the field prime, n is just below 2^256: n = 2^256 - c, where c is a 129 bit value.
+const N: Limbs = [
+    0xBFD25E8CD0364141,
+    0xBAAEDCE6AF48A03B,
+    0xFFFFFFFFFFFFFFFE,
+    0xFFFFFFFFFFFFFFFF,
+];
+
+const C: Limbs = [
+    0x402DA1732FC9BEBF,
+    0x4551231950B75FC4,
+    0x0000000000000001,
+    0,
+];
+
+// n - 2; used for inversion via Fermat's little theorem.
+const N_MINUS_TWO: Limbs = [
+    0xBFD25E8CD036413F,
+    0xBAAEDCE6AF48A03B,
+    0xFFFFFFFFFFFFFFFE,
+    0xFFFFFFFFFFFFFFFF,
+];
+
+// (n - 1) / 2; scalars greater than this are 'high', and their negation is 'low'.
+const N_HALF: Limbs = [
+    0xDFE92F46681B20A0,
+    0x5D576E7357A4501D,
+    0xFFFFFFFFFFFFFFFF,
+    0x7FFFFFFFFFFFFFFF,
+];
+
+// Constants for splitting a scalar with the GLV endomorphism; see split_lambda. These are the values used by libsecp256k1,
+// derived in 'Faster Point Multiplication on Elliptic Curves with Efficient Endomorphisms' (Gallant, Lambert, Vanstone; 2001).
+// -λ, where λ is a cube root of unity mod n.
+const MINUS_LAMBDA: Limbs = [
+    0xE0CFC810B51283CF,
+    0xA880B9FC8EC739C2,
+    0x5AD9E3FD77ED9BA4,
+    0xAC9C52B33FA3CF1F,
+];
+
+// -b1 and -b2, where (a1, b1) and (a2, b2) are short basis vectors of the lattice {(x, y) | x + yλ ≡ 0 (mod n)}.
+const MINUS_B1: Limbs = [0x6F547FA90ABFE4C3, 0xE4437ED6010E8828, 0, 0];
+const MINUS_B2: Limbs = [
+    0xD765CDA83DB1562C,
+    0x8A280AC50774346D,
+    0xFFFFFFFFFFFFFFFE,
+    0xFFFFFFFFFFFFFFFF,
+];
+
+// round(2^384 * b2 / n) and round(2^384 * -b1 / n).
+const G1: Limbs = [
+    0xE893209A45DBB031,
+    0x3DAA8A1471E8CA7F,
+    0xE86C90E49284EB15,
+    0x3086D221A7D46BCD,
+];
+
+const G2: Limbs = [
+    0x1571B4AE8AC47F71,
+    0x221208AC9DF506C6,
+    0x6F547FA90ABFE4C4,
+    0xE4437ED6010E8828,
+];
+
+// A secp256k1 scalar; private keys, tweaks, nonces and signature components. Values are always kept fully reduced.
+#[derive(Debug, Clone, Copy)]
+pub struct Scalar(Limbs);
+
+impl Scalar {
+    pub const ZERO: Self = Self([0, 0, 0, 0]);
+    pub const ONE: Self = Self([1, 0, 0, 0]);
+
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let limbs = limbs::from_be_bytes(bytes);
+        if limbs::is_less_than(&limbs, &N) {
+            Some(Self(limbs))
+        } else {
+            // The value is >= n; it's not a valid scalar.
+            None
+        }
+    }
+
+    pub fn from_be_bytes_reduced(bytes: &[u8; 32]) -> Self {
+        // Any 256 bit value is < 2n, so a single conditional subtraction fully reduces it.
+        Self(limbs::reduce_once(&limbs::from_be_bytes(bytes), 0, &N))
+    }
+
+    pub fn try_from_be_slice(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() > 32 {
+            // Strip any leading zeroes; anything else means the value is too large.
+            let (leading, trailing) = bytes.split_at(bytes.len() - 32);
+            if leading.iter().any(|b| *b != 0) {
+                return None;
+            }
+
+            return Self::try_from_be_slice(trailing);
+        }
+
+        let mut buffer = [0u8; 32];
+        buffer[32 - bytes.len()..].copy_from_slice(bytes);
+        let scalar = Self::from_be_bytes(&buffer);
+        buffer.fill(0);
+        scalar
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        limbs::to_be_bytes(&self.0)
+    }
+
+    pub(super) const fn limbs(&self) -> &Limbs {
+        &self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        limbs::is_zero(&self.0)
+    }
+
+    pub fn is_high(&self) -> bool {
+        limbs::is_less_than(&N_HALF, &self.0)
+    }
+
+    pub fn equals(&self, other: &Self) -> bool {
+        limbs::equals(&self.0, &other.0)
+    }
+
+    pub fn add(&self, addend: &Self) -> Self {
+        let (sum, carry) = limbs::add(&self.0, &addend.0);
+        Self(limbs::reduce_once(&sum, carry, &N))
+    }
+
+    pub fn subtract(&self, subtrahend: &Self) -> Self {
+        self.add(&subtrahend.negate())
+    }
+
+    pub fn negate(&self) -> Self {
+        // n - x, or 0 if x is 0.
+        let (negated, _) = limbs::subtract(&N, &self.0);
+        Self(limbs::select(&negated, &[0, 0, 0, 0], self.is_zero()))
+    }
+
+    pub fn multiply(&self, multiplier: &Self) -> Self {
+        Self::reduce_wide(&limbs::multiply_wide(&self.0, &multiplier.0))
+    }
+
+    pub fn square(&self) -> Self {
+        self.multiply(self)
+    }
+
+    pub fn invert(&self) -> Self {
+        // Fermat's little theorem; constant-time, as the exponent is public. Zero 'inverts' to zero.
+        let mut result = Self::ONE;
+        for i in (0..256).rev() {
+            result = result.square();
+            let multiplied = result.multiply(self);
+            result = Self::select(
+                &result,
+                &multiplied,
+                (N_MINUS_TWO[i / 64] >> (i % 64)) & 1 == 1,
+            );
+        }
+
+        result
+    }
+
+    pub fn select(a: &Self, b: &Self, choose_b: bool) -> Self {
+        Self(limbs::select(&a.0, &b.0, choose_b))
+    }
+
+    pub(super) fn split_lambda(&self) -> (Self, Self) {
+        // Split k into k1 and k2 such that k = k1 + k2 * λ (mod n), where k1 and k2 are either less than 2^128, or are
+        // the negation of a value less than 2^128. This lets us calculate kP as k1 * P + k2 * λP, where λP is (nearly) free
+        // to calculate, with half as many doublings as kP would require.
+        //
+        // c1 = round(k * b2 / n), c2 = round(k * -b1 / n)
+        // k2 = -(c1 * b1 + c2 * b2)
+        // k1 = k - k2 * λ
+        let c1 = Self::multiply_shift_384(&self.0, &G1).multiply(&Self(MINUS_B1));
+        let c2 = Self::multiply_shift_384(&self.0, &G2).multiply(&Self(MINUS_B2));
+        let k2 = c1.add(&c2);
+        let k1 = k2.multiply(&Self(MINUS_LAMBDA)).add(self);
+        (k1, k2)
+    }
+
+    pub(super) fn try_add_order(&self) -> Option<[u8; 32]> {
+        // The scalar's value plus n, as big-endian bytes, or None if the sum doesn't fit in 256 bits. ECDSA public key
+        // recovery needs this, as a nonce point's X coordinate may have been reduced mod n.
+        let (sum, carry) = limbs::add(&self.0, &N);
+        if carry == 0 {
+            Some(limbs::to_be_bytes(&sum))
+        } else {
+            None
+        }
+    }
+
+    fn multiply_shift_384(a: &Limbs, b: &Limbs) -> Self {
+        // round(a * b / 2^384); the rounding bit is the most significant bit we discard.
+        let wide = limbs::multiply_wide(a, b);
+        let (low, carry) = limbs::add_with_carry(wide[6], wide[5] >> 63, 0);
+        let (high, _) = limbs::add_with_carry(wide[7], 0, carry);
+        Self([low, high, 0, 0])
+    }
+
+    pub fn zero(&mut self) {
+        limbs::zero(&mut self.0);
+    }
+
+    fn reduce_wide(wide: &[u64; 8]) -> Self {
+        // c is ~129 bits, so each fold only removes ~127 bits: a 512 bit product folds to < 2^386, then < 2^260,
+        // then < 2^256 + 2^133, and finally to < 2^256.
+        let mut folded = limbs::fold_wide(wide, &C);
+        folded = limbs::fold_wide(&folded, &C);
+        folded = limbs::fold_wide(&folded, &C);
+        folded = limbs::fold_wide(&folded, &C);
+
+        let mut low = [0u64; 4];
+        low.copy_from_slice(&folded[..4]);
+        Self(limbs::reduce_once(&low, 0, &N))
+    }
+}
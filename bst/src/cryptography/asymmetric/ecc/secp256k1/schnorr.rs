@@ -0,0 +1,167 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{FieldElement, ProjectivePoint, Scalar};
+use crate::hashing::{Hasher, Sha256};
+
+// Schnorr signatures over secp256k1, as specified in BIP 340.
+pub const PUBLIC_KEY_LENGTH: usize = 32;
+pub const SIGNATURE_LENGTH: usize = 64;
+
+pub fn tagged_hash(tag: &[u8], data: &[&[u8]]) -> [u8; 32] {
+    // SHA256(SHA256(tag) || SHA256(tag) || data); the tag prefix separates hashes used for different purposes.
+    let mut sha256 = Sha256::new();
+    let tag_hash = sha256.get_hash_of(tag);
+    sha256.reset().feed_bytes(&tag_hash).feed_bytes(&tag_hash);
+    for bytes in data {
+        sha256.feed_bytes(bytes);
+    }
+
+    sha256.get_hash()
+}
+
+pub fn try_derive_x_only_public_key(private_key: &[u8; 32]) -> Option<[u8; PUBLIC_KEY_LENGTH]> {
+    let mut scalar = match Scalar::from_be_bytes(private_key) {
+        Some(s) => s,
+        None => return None,
+    };
+
+    let mut point = ProjectivePoint::multiply_generator(&scalar);
+    let public_key = try_get_x_only_coordinates(&point).map(|(x, _)| x);
+    scalar.zero();
+    point.zero();
+    public_key
+}
+
+pub fn try_sign(
+    private_key: &[u8; 32],
+    message: &[u8],
+    auxiliary_random: &[u8; 32],
+) -> Option<[u8; SIGNATURE_LENGTH]> {
+    // d' = int(sk); it must be in the range 0 < d' < n.
+    let mut d = match Scalar::from_be_bytes(private_key) {
+        Some(d) => d,
+        None => return None,
+    };
+
+    if d.is_zero() {
+        return None;
+    }
+
+    // P = d'G; d = d' if P has an even Y coordinate, otherwise n - d'.
+    let mut public_point = ProjectivePoint::multiply_generator(&d);
+    let (public_key, public_key_y_is_even) = try_get_x_only_coordinates(&public_point).unwrap();
+    public_point.zero();
+    d = Scalar::select(&d.negate(), &d, public_key_y_is_even);
+
+    // t = bytes(d) XOR hash_BIP0340/aux(a)
+    let mut t = tagged_hash(b"BIP0340/aux", &[auxiliary_random]);
+    let mut d_bytes = d.to_be_bytes();
+    for i in 0..32 {
+        t[i] ^= d_bytes[i];
+    }
+
+    d_bytes.fill(0);
+
+    // k' = int(hash_BIP0340/nonce(t || bytes(P) || m)) (mod n)
+    let mut nonce_hash = tagged_hash(b"BIP0340/nonce", &[&t, &public_key, message]);
+    let mut k = Scalar::from_be_bytes_reduced(&nonce_hash);
+    nonce_hash.fill(0);
+    t.fill(0);
+
+    if k.is_zero() {
+        d.zero();
+        return None;
+    }
+
+    // R = k'G; k = k' if R has an even Y coordinate, otherwise n - k'.
+    let mut nonce_point = ProjectivePoint::multiply_generator(&k);
+    let (r, r_y_is_even) = try_get_x_only_coordinates(&nonce_point).unwrap();
+    nonce_point.zero();
+    k = Scalar::select(&k.negate(), &k, r_y_is_even);
+
+    // s = k + ed (mod n)
+    let e = challenge(&r, &public_key, message);
+    let s = k.add(&e.multiply(&d));
+    k.zero();
+    d.zero();
+
+    let mut signature = [0u8; SIGNATURE_LENGTH];
+    signature[..32].copy_from_slice(&r);
+    signature[32..].copy_from_slice(&s.to_be_bytes());
+
+    // Verify the signature before returning it, to guard against faults.
+    if verify(&public_key, message, &signature) {
+        Some(signature)
+    } else {
+        None
+    }
+}
+
+pub fn verify(
+    public_key: &[u8; PUBLIC_KEY_LENGTH],
+    message: &[u8],
+    signature: &[u8; SIGNATURE_LENGTH],
+) -> bool {
+    // P = lift_x(int(pk)); the public key's Y coordinate is implicitly even.
+    let public_point = match FieldElement::from_be_bytes(public_key) {
+        Some(x) => match ProjectivePoint::try_from_x(&x, true) {
+            Some(p) => p,
+            None => return false,
+        },
+        None => return false,
+    };
+
+    // r = int(sig[0:32]) must be less than p, and s = int(sig[32:64]) must be less than n.
+    let mut r = [0u8; 32];
+    r.copy_from_slice(&signature[..32]);
+    if FieldElement::from_be_bytes(&r).is_none() {
+        return false;
+    }
+
+    let mut s_bytes = [0u8; 32];
+    s_bytes.copy_from_slice(&signature[32..]);
+    let s = match Scalar::from_be_bytes(&s_bytes) {
+        Some(s) => s,
+        None => return false,
+    };
+
+    // R = sG - eP must not be infinity, must have an even Y coordinate, and must have an X coordinate of r.
+    let e = challenge(&r, public_key, message);
+    let nonce_point = ProjectivePoint::multiply_generator(&s)
+        .add(&public_point.multiply_variable_time(&e).negate());
+
+    match try_get_x_only_coordinates(&nonce_point) {
+        Some((x, y_is_even)) => y_is_even && x == r,
+        None => false,
+    }
+}
+
+pub(super) fn challenge(r: &[u8; 32], public_key: &[u8; 32], message: &[u8]) -> Scalar {
+    // e = int(hash_BIP0340/challenge(bytes(R) || bytes(P) || m)) (mod n)
+    Scalar::from_be_bytes_reduced(&tagged_hash(
+        b"BIP0340/challenge",
+        &[r, public_key, message],
+    ))
+}
+
+pub(super) fn try_get_x_only_coordinates(point: &ProjectivePoint) -> Option<([u8; 32], bool)> {
+    // The X coordinate's bytes, and whether the Y coordinate is even.
+    match point.try_to_affine() {
+        Some((x, y)) => Some((x.to_be_bytes(), y.is_even())),
+        None => None,
+    }
+}
@@ -0,0 +1,135 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{EllipticCurvePoint, EllipticCurvePointMultiplicationContext};
+use crate::{global_runtime_immutable::GlobalRuntimeImmutable, integers::BigUnsigned};
+
+// The secp256r1 (NIST P-256) parameters are:
+//
+// Prime finite field:
+// p = FFFFFFFF 00000001 00000000 00000000 00000000 FFFFFFFF FFFFFFFF FFFFFFFF
+//   = 2^256 − 2^224 + 2^192 + 2^96 − 1
+static P: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+    GlobalRuntimeImmutable::from(|| {
+        BigUnsigned::from_be_bytes(&[
+            0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF, 0xFF,
+        ])
+    });
+
+// Prime identity, used for point decompression.
+static P_I: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+    GlobalRuntimeImmutable::from(|| {
+        let mut i = p().clone();
+        i.add(&[1]);
+        let mut r = 0;
+        i.divide_by_single_digit_with_remainder(4, &mut r);
+        i
+    });
+
+// Point doubling coefficient:
+// a = FFFFFFFF 00000001 00000000 00000000 00000000 FFFFFFFF FFFFFFFF FFFFFFFC
+//   = -3 (mod p)
+static A: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+    GlobalRuntimeImmutable::from(|| {
+        BigUnsigned::from_be_bytes(&[
+            0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF, 0xFC,
+        ])
+    });
+
+// Curve coefficient:
+// b = 5AC635D8 AA3A93E7 B3EBBD55 769886BC 651D06B0 CC53B0F6 3BCE3C3E 27D2604B
+static B: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+    GlobalRuntimeImmutable::from(|| {
+        BigUnsigned::from_be_bytes(&[
+            0x5A, 0xC6, 0x35, 0xD8, 0xAA, 0x3A, 0x93, 0xE7, 0xB3, 0xEB, 0xBD, 0x55, 0x76, 0x98,
+            0x86, 0xBC, 0x65, 0x1D, 0x06, 0xB0, 0xCC, 0x53, 0xB0, 0xF6, 0x3B, 0xCE, 0x3C, 0x3E,
+            0x27, 0xD2, 0x60, 0x4B,
+        ])
+    });
+
+// Generator Point:
+// Xg = 6B17D1F2 E12C4247 F8BCE6E5 63A440F2 77037D81 2DEB33A0 F4A13945 D898C296
+static G_X: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+    GlobalRuntimeImmutable::from(|| {
+        BigUnsigned::from_be_bytes(&[
+            0x6B, 0x17, 0xD1, 0xF2, 0xE1, 0x2C, 0x42, 0x47, 0xF8, 0xBC, 0xE6, 0xE5, 0x63, 0xA4,
+            0x40, 0xF2, 0x77, 0x03, 0x7D, 0x81, 0x2D, 0xEB, 0x33, 0xA0, 0xF4, 0xA1, 0x39, 0x45,
+            0xD8, 0x98, 0xC2, 0x96,
+        ])
+    });
+
+// Yg = 4FE342E2 FE1A7F9B 8EE7EB4A 7C0F9E16 2BCE3357 6B315ECE CBB64068 37BF51F5
+static G_Y: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+    GlobalRuntimeImmutable::from(|| {
+        BigUnsigned::from_be_bytes(&[
+            0x4F, 0xE3, 0x42, 0xE2, 0xFE, 0x1A, 0x7F, 0x9B, 0x8E, 0xE7, 0xEB, 0x4A, 0x7C, 0x0F,
+            0x9E, 0x16, 0x2B, 0xCE, 0x33, 0x57, 0x6B, 0x31, 0x5E, 0xCE, 0xCB, 0xB6, 0x40, 0x68,
+            0x37, 0xBF, 0x51, 0xF5,
+        ])
+    });
+
+// Order (number of points reachable from the generator point):
+// n = FFFFFFFF 00000000 FFFFFFFF FFFFFFFF BCE6FAAD A7179E84 F3B9CAC2 FC632551
+static N: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+    GlobalRuntimeImmutable::from(|| {
+        BigUnsigned::from_be_bytes(&[
+            0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xBC, 0xE6, 0xFA, 0xAD, 0xA7, 0x17, 0x9E, 0x84, 0xF3, 0xB9, 0xCA, 0xC2,
+            0xFC, 0x63, 0x25, 0x51,
+        ])
+    });
+
+pub fn point_multiplication_context() -> EllipticCurvePointMultiplicationContext {
+    EllipticCurvePointMultiplicationContext::new(64, n(), p(), p_i(), a(), b())
+}
+
+pub fn serialized_public_key_bytes(mut key: EllipticCurvePoint) -> Option<[u8; 33]> {
+    let serialized = key.try_serialize_compressed();
+    key.set_infinity();
+    serialized
+}
+
+pub fn g_x() -> &'static BigUnsigned {
+    G_X.value()
+}
+
+pub fn g_y() -> &'static BigUnsigned {
+    G_Y.value()
+}
+
+pub fn n() -> &'static BigUnsigned {
+    N.value()
+}
+
+fn p_i() -> &'static BigUnsigned {
+    P_I.value()
+}
+
+fn p() -> &'static BigUnsigned {
+    P.value()
+}
+
+fn a() -> &'static BigUnsigned {
+    A.value()
+}
+
+fn b() -> &'static BigUnsigned {
+    B.value()
+}
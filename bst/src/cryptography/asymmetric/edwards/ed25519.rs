@@ -0,0 +1,156 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{EdwardsPoint, Scalar};
+use crate::hashing::{Hasher, Sha512};
+
+// Ed25519, as specified in RFC 8032.
+pub const SEED_LENGTH: usize = 32;
+pub const PUBLIC_KEY_LENGTH: usize = 32;
+pub const SIGNATURE_LENGTH: usize = 64;
+
+pub struct Ed25519KeyPair {
+    secret_scalar: [u8; 32],
+    prefix: [u8; 32],
+    public_key: [u8; PUBLIC_KEY_LENGTH],
+}
+
+impl Ed25519KeyPair {
+    pub fn from_seed(seed: &[u8; SEED_LENGTH]) -> Self {
+        // RFC 8032, section 5.1.5. The private key is a 32 byte seed; hash it with SHA512. The first half of the hash is 'clamped'
+        // to produce the secret scalar, and the second half is the prefix, used for deterministic nonce generation when signing.
+        let mut hash = Sha512::new().get_hash_of(seed);
+
+        let mut secret_scalar = [0u8; 32];
+        secret_scalar.copy_from_slice(&hash[..32]);
+
+        // Clear the lowest three bits, so the scalar is a multiple of the cofactor (8), clear the highest bit, and set the second highest.
+        secret_scalar[0] &= 0xF8;
+        secret_scalar[31] &= 0x7F;
+        secret_scalar[31] |= 0x40;
+
+        let mut prefix = [0u8; 32];
+        prefix.copy_from_slice(&hash[32..]);
+        hash.fill(0);
+
+        // The public key is the compressed point [s]B.
+        let mut public_point = EdwardsPoint::BASE.multiply(&secret_scalar);
+        let public_key = public_point.compress();
+        public_point.zero();
+
+        Self {
+            secret_scalar,
+            public_key,
+            prefix,
+        }
+    }
+
+    pub fn public_key(&self) -> &[u8; PUBLIC_KEY_LENGTH] {
+        &self.public_key
+    }
+
+    pub fn sign(&self, message: &[u8]) -> [u8; SIGNATURE_LENGTH] {
+        // RFC 8032, section 5.1.6.
+        let mut sha512 = Sha512::new();
+
+        // r = SHA512(prefix || M) (mod L); the nonce is deterministic, and never reused across different messages.
+        let mut hash = sha512
+            .feed_bytes(&self.prefix)
+            .feed_bytes(message)
+            .get_hash();
+        let mut r = Scalar::from_le_bytes_reduced(&hash);
+
+        // R = [r]B
+        let mut r_bytes = r.to_le_bytes();
+        let mut r_point = EdwardsPoint::BASE.multiply(&r_bytes);
+        let encoded_r = r_point.compress();
+        r_bytes.fill(0);
+        r_point.zero();
+
+        // k = SHA512(R || A || M) (mod L)
+        let k = Self::challenge(sha512.reset(), &encoded_r, &self.public_key, message);
+
+        // S = (r + k * s) (mod L)
+        let mut s = Scalar::from_le_bytes_reduced(&self.secret_scalar);
+        let signature_s = k.multiply_add(&s, &r);
+        s.zero();
+        r.zero();
+        hash.fill(0);
+        sha512.reset();
+
+        let mut signature = [0u8; SIGNATURE_LENGTH];
+        signature[..32].copy_from_slice(&encoded_r);
+        signature[32..].copy_from_slice(&signature_s.to_le_bytes());
+        signature
+    }
+
+    pub fn zero(&mut self) {
+        self.secret_scalar.fill(0);
+        self.prefix.fill(0);
+    }
+
+    fn challenge(
+        sha512: &mut Sha512,
+        encoded_r: &[u8],
+        public_key: &[u8],
+        message: &[u8],
+    ) -> Scalar {
+        Scalar::from_le_bytes_reduced(
+            &sha512
+                .feed_bytes(encoded_r)
+                .feed_bytes(public_key)
+                .feed_bytes(message)
+                .get_hash(),
+        )
+    }
+}
+
+pub fn verify(
+    public_key: &[u8; PUBLIC_KEY_LENGTH],
+    message: &[u8],
+    signature: &[u8; SIGNATURE_LENGTH],
+) -> bool {
+    // RFC 8032, section 5.1.7. Decode the public key A, the signature's R point, and the signature's S scalar.
+    let a = match EdwardsPoint::try_decompress(public_key) {
+        Some(a) => a,
+        None => return false,
+    };
+
+    let mut encoded_r = [0u8; 32];
+    encoded_r.copy_from_slice(&signature[..32]);
+    let r = match EdwardsPoint::try_decompress(&encoded_r) {
+        Some(r) => r,
+        None => return false,
+    };
+
+    let mut encoded_s = [0u8; 32];
+    encoded_s.copy_from_slice(&signature[32..]);
+    let s = match Scalar::from_canonical_le_bytes(&encoded_s) {
+        Some(s) => s,
+        None => return false,
+    };
+
+    // k = SHA512(R || A || M) (mod L)
+    let k = Ed25519KeyPair::challenge(&mut Sha512::new(), &encoded_r, public_key, message);
+
+    // Check the group equation [8][S]B = [8]R + [8][k]A. Multiplying by the cofactor discards any small-order components.
+    let left = EdwardsPoint::BASE
+        .multiply(&s.to_le_bytes())
+        .multiply_by_cofactor();
+    let right = r.add(&a.multiply(&k.to_le_bytes())).multiply_by_cofactor();
+
+    left.equals(&right)
+}
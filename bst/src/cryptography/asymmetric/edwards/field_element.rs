@@ -0,0 +1,302 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// The Curve25519 field prime is p = 2^255 - 19.
+//
+// Field elements are stored as five 51-bit limbs in little-endian limb order; the value is l0 + l1 * 2^51 + ... + l4 * 2^204.
+// 51-bit limbs leave plenty of headroom in a u64 for additions without carrying, and for multiplication, the high part of a
+// product is folded back down into the low part by multiplying it by 19, as 2^255 = 19 (mod p).
+//
+// Values are kept 'weakly' reduced (each limb is at most slightly over 51 bits) between operations; they are only fully reduced
+// into the range [0, p) when serialized or compared.
+const LIMB_MASK: u64 = (1 << 51) - 1;
+
+// 4p, split into limbs; added before subtraction so that no limb can underflow.
+const FOUR_P: [u64; 5] = [
+    0x1FFFFFFFFFFFB4,
+    0x1FFFFFFFFFFFFC,
+    0x1FFFFFFFFFFFFC,
+    0x1FFFFFFFFFFFFC,
+    0x1FFFFFFFFFFFFC,
+];
+
+// p - 2, little-endian; used for inversion via Fermat's little theorem.
+const P_MINUS_TWO: [u8; 32] = [
+    0xEB, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F,
+];
+
+// (p - 5) / 8, little-endian; used for calculating square roots.
+const P_MINUS_FIVE_OVER_EIGHT: [u8; 32] = [
+    0xFD, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F,
+];
+
+// sqrt(-1) = 2^((p - 1) / 4) (mod p)
+const SQRT_MINUS_ONE: FieldElement = FieldElement([
+    0x61B274A0EA0B0,
+    0x0D5A5FC8F189D,
+    0x7EF5E9CBD0C60,
+    0x78595A6804C9E,
+    0x2B8324804FC1D,
+]);
+
+#[derive(Debug, Clone, Copy)]
+pub struct FieldElement([u64; 5]);
+
+impl FieldElement {
+    pub const ZERO: Self = Self([0, 0, 0, 0, 0]);
+    pub const ONE: Self = Self([1, 0, 0, 0, 0]);
+
+    pub(super) const fn from_limbs(limbs: [u64; 5]) -> Self {
+        Self(limbs)
+    }
+
+    pub fn from_le_bytes(bytes: &[u8; 32]) -> Self {
+        // The most significant bit is ignored; values in the range [p, 2^255) are accepted, and will be reduced.
+        let load = |offset: usize| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[offset..offset + 8]);
+            u64::from_le_bytes(word)
+        };
+
+        Self([
+            load(0) & LIMB_MASK,
+            (load(6) >> 3) & LIMB_MASK,
+            (load(12) >> 6) & LIMB_MASK,
+            (load(19) >> 1) & LIMB_MASK,
+            (load(24) >> 12) & LIMB_MASK,
+        ])
+    }
+
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let limbs = self.fully_reduced();
+        let mut bytes = [0u8; 32];
+        let mut accumulator: u128 = 0;
+        let mut accumulated_bits = 0;
+        let mut offset = 0;
+        for limb in limbs {
+            accumulator |= (limb as u128) << accumulated_bits;
+            accumulated_bits += 51;
+            while accumulated_bits >= 8 && offset < 32 {
+                bytes[offset] = accumulator as u8;
+                accumulator >>= 8;
+                accumulated_bits -= 8;
+                offset += 1;
+            }
+        }
+
+        if offset < 32 {
+            // The final 7 bits.
+            bytes[offset] = accumulator as u8;
+        }
+
+        bytes
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.to_le_bytes().iter().fold(0, |a, b| a | b) == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        // A field element is 'negative' if its fully reduced value is odd.
+        self.to_le_bytes()[0] & 1 == 1
+    }
+
+    pub fn equals(&self, other: &Self) -> bool {
+        let (a, b) = (self.to_le_bytes(), other.to_le_bytes());
+        a.iter().zip(b.iter()).fold(0, |d, (a, b)| d | (a ^ b)) == 0
+    }
+
+    pub fn add(&self, addend: &Self) -> Self {
+        let mut result = [0u64; 5];
+        for i in 0..5 {
+            result[i] = self.0[i] + addend.0[i];
+        }
+
+        Self::weak_reduce(result)
+    }
+
+    pub fn subtract(&self, subtrahend: &Self) -> Self {
+        let mut result = [0u64; 5];
+        for i in 0..5 {
+            result[i] = (self.0[i] + FOUR_P[i]) - subtrahend.0[i];
+        }
+
+        Self::weak_reduce(result)
+    }
+
+    pub fn negate(&self) -> Self {
+        Self::ZERO.subtract(self)
+    }
+
+    pub fn multiply(&self, multiplier: &Self) -> Self {
+        let (a, b) = (&self.0, &multiplier.0);
+        let m = |x: u64, y: u64| (x as u128) * (y as u128);
+
+        // Limbs which would land at or above 2^255 wrap around to the bottom, multiplied by 19.
+        let b1_19 = b[1] * 19;
+        let b2_19 = b[2] * 19;
+        let b3_19 = b[3] * 19;
+        let b4_19 = b[4] * 19;
+
+        let r0 = m(a[0], b[0]) + m(a[1], b4_19) + m(a[2], b3_19) + m(a[3], b2_19) + m(a[4], b1_19);
+        let r1 = m(a[0], b[1]) + m(a[1], b[0]) + m(a[2], b4_19) + m(a[3], b3_19) + m(a[4], b2_19);
+        let r2 = m(a[0], b[2]) + m(a[1], b[1]) + m(a[2], b[0]) + m(a[3], b4_19) + m(a[4], b3_19);
+        let r3 = m(a[0], b[3]) + m(a[1], b[2]) + m(a[2], b[1]) + m(a[3], b[0]) + m(a[4], b4_19);
+        let r4 = m(a[0], b[4]) + m(a[1], b[3]) + m(a[2], b[2]) + m(a[3], b[1]) + m(a[4], b[0]);
+
+        Self::reduce_wide([r0, r1, r2, r3, r4])
+    }
+
+    pub fn multiply_u64(&self, multiplier: u64) -> Self {
+        // Only valid for multipliers < 2^32.
+        let mut result = [0u128; 5];
+        for i in 0..5 {
+            result[i] = (self.0[i] as u128) * (multiplier as u128);
+        }
+
+        Self::reduce_wide(result)
+    }
+
+    pub fn square(&self) -> Self {
+        self.multiply(self)
+    }
+
+    pub fn invert(&self) -> Self {
+        // Fermat's little theorem; x^(p - 2) = x^-1 (mod p). Zero 'inverts' to zero.
+        self.pow(&P_MINUS_TWO)
+    }
+
+    pub fn try_sqrt_ratio(u: &Self, v: &Self) -> Option<Self> {
+        // Calculates x such that x^2 = u / v, if such an x exists (RFC 8032, section 5.1.3):
+        // x = (u / v)^((p + 3) / 8) = u * v^3 * (u * v^7)^((p - 5) / 8)
+        let v3 = v.square().multiply(v);
+        let v7 = v3.square().multiply(v);
+        let x = u
+            .multiply(&v3)
+            .multiply(&u.multiply(&v7).pow(&P_MINUS_FIVE_OVER_EIGHT));
+
+        let check = v.multiply(&x.square());
+        if check.equals(u) {
+            // v * x^2 = u; x is a square root of u / v.
+            Some(x)
+        } else if check.equals(&u.negate()) {
+            // v * x^2 = -u; x * sqrt(-1) is a square root of u / v.
+            Some(x.multiply(&SQRT_MINUS_ONE))
+        } else {
+            // u / v has no square root.
+            None
+        }
+    }
+
+    pub fn select(a: &Self, b: &Self, choose_b: bool) -> Self {
+        let mask = 0u64.wrapping_sub(choose_b as u64);
+        let mut result = [0u64; 5];
+        for i in 0..5 {
+            result[i] = (a.0[i] & !mask) | (b.0[i] & mask);
+        }
+
+        Self(result)
+    }
+
+    pub fn swap(a: &mut Self, b: &mut Self, swap: bool) {
+        let mask = 0u64.wrapping_sub(swap as u64);
+        for i in 0..5 {
+            let difference = (a.0[i] ^ b.0[i]) & mask;
+            a.0[i] ^= difference;
+            b.0[i] ^= difference;
+        }
+    }
+
+    pub fn zero(&mut self) {
+        for limb in self.0.iter_mut() {
+            // Volatile writes can't be optimized away, even if the limbs are never read again.
+            unsafe { core::ptr::write_volatile(limb, 0) };
+        }
+    }
+
+    fn pow(&self, exponent: &[u8; 32]) -> Self {
+        // Square and multiply, from the most significant bit; the exponents we use are public constants, so branching is fine.
+        let mut result = Self::ONE;
+        for i in (0..256).rev() {
+            result = result.square();
+            if (exponent[i / 8] >> (i % 8)) & 1 == 1 {
+                result = result.multiply(self);
+            }
+        }
+
+        result
+    }
+
+    fn weak_reduce(mut limbs: [u64; 5]) -> Self {
+        // Carry the excess from each limb into the next; the excess from the top limb wraps around to the bottom, multiplied by 19.
+        let mut carry;
+        for i in 0..4 {
+            carry = limbs[i] >> 51;
+            limbs[i] &= LIMB_MASK;
+            limbs[i + 1] += carry;
+        }
+
+        carry = limbs[4] >> 51;
+        limbs[4] &= LIMB_MASK;
+        limbs[0] += carry * 19;
+        Self(limbs)
+    }
+
+    fn reduce_wide(mut wide: [u128; 5]) -> Self {
+        let mut carry;
+        for i in 0..4 {
+            carry = wide[i] >> 51;
+            wide[i] &= LIMB_MASK as u128;
+            wide[i + 1] += carry;
+        }
+
+        carry = wide[4] >> 51;
+        wide[4] &= LIMB_MASK as u128;
+
+        let mut limbs = [0u64; 5];
+        for i in 0..5 {
+            limbs[i] = wide[i] as u64;
+        }
+
+        // The top carry is at most ~2^64 / 2^51 * 19; carry once more through the bottom limbs to keep them bounded.
+        let bottom = limbs[0] as u128 + carry * 19;
+        limbs[0] = (bottom as u64) & LIMB_MASK;
+        limbs[1] += (bottom >> 51) as u64;
+        Self(limbs)
+    }
+
+    fn fully_reduced(&self) -> [u64; 5] {
+        let mut limbs = Self::weak_reduce(Self::weak_reduce(self.0).0).0;
+
+        // The value is now < 2^255 + a little; it's >= p if and only if value + 19 >= 2^255.
+        let mut q = (limbs[0] + 19) >> 51;
+        for i in 1..5 {
+            q = (limbs[i] + q) >> 51;
+        }
+
+        // Subtract p if the value was >= p; that's adding 19, then discarding bit 255.
+        limbs[0] += 19 * q;
+        for i in 0..4 {
+            limbs[i + 1] += limbs[i] >> 51;
+            limbs[i] &= LIMB_MASK;
+        }
+
+        limbs[4] &= LIMB_MASK;
+        limbs
+    }
+}
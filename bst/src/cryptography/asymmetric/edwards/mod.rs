@@ -0,0 +1,34 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// A twisted Edwards curve is made up of points with the following equation:
+// ax^2 + y^2 = 1 + dx^2y^2
+//
+// Unlike the short Weierstrass curves in the ecc module, twisted Edwards curves have a complete addition law when a is a square
+// and d is not; one formula works for every pair of points, including doubling, and the neutral element is the ordinary point (0, 1).
+//
+// Edwards25519 (a = -1, d = -121665 / 121666) is birationally equivalent to Curve25519, over the prime field 2^255 - 19.
+// X25519 works with the u coordinate of the Montgomery form of Curve25519 directly, but shares the field arithmetic.
+pub mod ed25519;
+pub mod x25519;
+
+mod field_element;
+mod point;
+mod scalar;
+
+pub use field_element::FieldElement;
+pub use point::EdwardsPoint;
+pub use scalar::Scalar;
@@ -0,0 +1,245 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::FieldElement;
+
+// d = -121665 / 121666 (mod p); the Edwards25519 curve is -x^2 + y^2 = 1 + d * x^2 * y^2.
+const D: FieldElement = FieldElement::from_limbs([
+    0x34DCA135978A3,
+    0x1A8283B156EBD,
+    0x5E7A26001C029,
+    0x739C663A03CBB,
+    0x52036CEE2B6FF,
+]);
+
+// 2d, used by the addition formula.
+const D2: FieldElement = FieldElement::from_limbs([
+    0x69B9426B2F159,
+    0x35050762ADD7A,
+    0x3CF44C0038052,
+    0x6738CC7407977,
+    0x2406D9DC56DFF,
+]);
+
+// A point on the Edwards25519 curve in extended homogeneous coordinates (X : Y : Z : T), where x = X / Z, y = Y / Z, and x * y = T / Z.
+//
+// The addition and doubling formulas are those given in RFC 8032, section 5.1.4, from 'Twisted Edwards Curves Revisited'
+// (Hisil, Wong, Carter, Dawson; 2008). Unlike short Weierstrass curves, Edwards curves have a complete addition law; there is no
+// point at infinity, and the neutral element (0, 1) is an ordinary point which the same formulas handle without special cases.
+#[derive(Debug, Clone, Copy)]
+pub struct EdwardsPoint {
+    x: FieldElement,
+    y: FieldElement,
+    z: FieldElement,
+    t: FieldElement,
+}
+
+impl EdwardsPoint {
+    pub const IDENTITY: Self = Self {
+        x: FieldElement::ZERO,
+        y: FieldElement::ONE,
+        z: FieldElement::ONE,
+        t: FieldElement::ZERO,
+    };
+
+    // The base point B; y = 4/5, and x is positive (even).
+    pub const BASE: Self = Self {
+        x: FieldElement::from_limbs([
+            0x62D608F25D51A,
+            0x412A4B4F6592A,
+            0x75B7171A4B31D,
+            0x1FF60527118FE,
+            0x216936D3CD6E5,
+        ]),
+        y: FieldElement::from_limbs([
+            0x6666666666658,
+            0x4CCCCCCCCCCCC,
+            0x1999999999999,
+            0x3333333333333,
+            0x6666666666666,
+        ]),
+        z: FieldElement::ONE,
+        t: FieldElement::from_limbs([
+            0x68AB3A5B7DDA3,
+            0x00EEA2A5EADBB,
+            0x2AF8DF483C27E,
+            0x332B375274732,
+            0x67875F0FD78B7,
+        ]),
+    };
+
+    pub fn try_decompress(bytes: &[u8; 32]) -> Option<Self> {
+        // RFC 8032, section 5.1.3. The encoding is the y coordinate, little-endian, with the most significant bit of the final byte
+        // set to the least significant bit of the x coordinate.
+        let x_is_negative = bytes[31] >> 7 == 1;
+        let y = FieldElement::from_le_bytes(bytes);
+
+        // The y coordinate must be canonical (< p); re-encoding it must give back the input, ignoring the sign bit.
+        let mut y_bytes = y.to_le_bytes();
+        y_bytes[31] |= bytes[31] & 0x80;
+        if y_bytes != *bytes {
+            return None;
+        }
+
+        // x^2 = (y^2 - 1) / (d * y^2 + 1)
+        let y_squared = y.square();
+        let u = y_squared.subtract(&FieldElement::ONE);
+        let v = D.multiply(&y_squared).add(&FieldElement::ONE);
+        let mut x = match FieldElement::try_sqrt_ratio(&u, &v) {
+            Some(x) => x,
+            None => return None,
+        };
+
+        if x.is_zero() && x_is_negative {
+            // There is no negative zero.
+            return None;
+        }
+
+        if x.is_negative() != x_is_negative {
+            x = x.negate();
+        }
+
+        Some(Self {
+            t: x.multiply(&y),
+            z: FieldElement::ONE,
+            x,
+            y,
+        })
+    }
+
+    pub fn compress(&self) -> [u8; 32] {
+        let z_inverse = self.z.invert();
+        let x = self.x.multiply(&z_inverse);
+        let mut bytes = self.y.multiply(&z_inverse).to_le_bytes();
+        bytes[31] |= (x.is_negative() as u8) << 7;
+        bytes
+    }
+
+    pub fn equals(&self, other: &Self) -> bool {
+        // (X1 / Z1, Y1 / Z1) = (X2 / Z2, Y2 / Z2) if and only if X1 * Z2 = X2 * Z1 and Y1 * Z2 = Y2 * Z1.
+        self.x.multiply(&other.z).equals(&other.x.multiply(&self.z))
+            & self.y.multiply(&other.z).equals(&other.y.multiply(&self.z))
+    }
+
+    #[cfg(test)]
+    pub fn negate(&self) -> Self {
+        Self {
+            x: self.x.negate(),
+            t: self.t.negate(),
+            y: self.y,
+            z: self.z,
+        }
+    }
+
+    pub fn add(&self, addend: &Self) -> Self {
+        let a = self
+            .y
+            .subtract(&self.x)
+            .multiply(&addend.y.subtract(&addend.x));
+        let b = self.y.add(&self.x).multiply(&addend.y.add(&addend.x));
+        let c = self.t.multiply(&D2).multiply(&addend.t);
+        let d = self.z.add(&self.z).multiply(&addend.z);
+        let e = b.subtract(&a);
+        let f = d.subtract(&c);
+        let g = d.add(&c);
+        let h = b.add(&a);
+
+        Self {
+            x: e.multiply(&f),
+            y: g.multiply(&h),
+            t: e.multiply(&h),
+            z: f.multiply(&g),
+        }
+    }
+
+    pub fn double(&self) -> Self {
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = self.z.square().multiply_u64(2);
+        let h = a.add(&b);
+        let e = h.subtract(&self.x.add(&self.y).square());
+        let g = a.subtract(&b);
+        let f = c.add(&g);
+
+        Self {
+            x: e.multiply(&f),
+            y: g.multiply(&h),
+            t: e.multiply(&h),
+            z: f.multiply(&g),
+        }
+    }
+
+    pub fn multiply(&self, multiplier: &[u8; 32]) -> Self {
+        // Constant-time fixed-window multiplication by a little-endian 256 bit integer; see secp256k1::ProjectivePoint::multiply.
+        // We precompute 0P through 15P, then for each 4 bit window of the multiplier, from most to least significant, we double
+        // the product 4 times and add the table entry for the window's value, reading every table entry for every window.
+        let mut table = [Self::IDENTITY; 16];
+        for i in 1..16 {
+            table[i] = table[i - 1].add(self);
+        }
+
+        let mut product = Self::IDENTITY;
+        for window in (0..64).rev() {
+            product = product.double().double().double().double();
+
+            let window_value = (multiplier[window / 2] >> ((window % 2) * 4)) & 0xF;
+            let mut addend = Self::IDENTITY;
+            for i in 0..16 {
+                // (i ^ window_value) - 1 only underflows, setting the top bit, when i == window_value.
+                addend = Self::select(
+                    &addend,
+                    &table[i],
+                    (i as u8 ^ window_value).wrapping_sub(1) >> 7 == 1,
+                );
+            }
+
+            product = product.add(&addend);
+            addend.zero();
+        }
+
+        for entry in table.iter_mut() {
+            entry.zero();
+        }
+
+        product
+    }
+
+    pub fn multiply_by_cofactor(&self) -> Self {
+        // The curve's cofactor is 8.
+        self.double().double().double()
+    }
+
+    #[cfg(test)]
+    pub fn is_identity(&self) -> bool {
+        self.equals(&Self::IDENTITY)
+    }
+
+    pub fn select(a: &Self, b: &Self, choose_b: bool) -> Self {
+        Self {
+            x: FieldElement::select(&a.x, &b.x, choose_b),
+            y: FieldElement::select(&a.y, &b.y, choose_b),
+            z: FieldElement::select(&a.z, &b.z, choose_b),
+            t: FieldElement::select(&a.t, &b.t, choose_b),
+        }
+    }
+
+    pub fn zero(&mut self) {
+        self.x.zero();
+        self.y.zero();
+        self.z.zero();
+        self.t.zero();
+    }
+}
@@ -0,0 +1,148 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// The order of the Ed25519 base point; L = 2^252 + 27742317777372353535851937790883648493.
+// Limbs are 64 bits, in little-endian limb order.
+const L: [u64; 4] = [
+    0x5812631A5CF5D3ED,
+    0x14DEF9DEA2F79CD6,
+    0x0000000000000000,
+    0x1000000000000000,
+];
+
+// An integer modulo L; signature scalars, nonces, and hash-derived challenges. Values are always kept fully reduced.
+#[derive(Debug, Clone, Copy)]
+pub struct Scalar([u64; 4]);
+
+impl Scalar {
+    pub const ZERO: Self = Self([0, 0, 0, 0]);
+
+    pub fn from_canonical_le_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        // Signatures must contain fully reduced scalars; anything >= L is rejected to prevent malleability.
+        let limbs = Self::limbs_from_le_bytes(bytes);
+        let (_, borrow) = Self::subtract_l(&limbs);
+        if borrow == 1 {
+            Some(Self(limbs))
+        } else {
+            None
+        }
+    }
+
+    pub fn from_le_bytes_reduced(bytes: &[u8]) -> Self {
+        // Reduces an arbitrary length little-endian integer (in practice, a 32 byte key or a 64 byte SHA512 hash) modulo L.
+        let mut result = Self::ZERO;
+        for byte in bytes.iter().rev() {
+            for bit in (0..8).rev() {
+                result = result.shift_in_bit((byte >> bit) & 1);
+            }
+        }
+
+        result
+    }
+
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for i in 0..4 {
+            bytes[i * 8..(i + 1) * 8].copy_from_slice(&self.0[i].to_le_bytes());
+        }
+
+        bytes
+    }
+
+    pub fn multiply_add(&self, multiplier: &Self, addend: &Self) -> Self {
+        // (self * multiplier) + addend (mod L); calculate the 512 bit result, then reduce it.
+        let mut wide = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let product =
+                    (self.0[i] as u128) * (multiplier.0[j] as u128) + wide[i + j] as u128 + carry;
+                wide[i + j] = product as u64;
+                carry = product >> 64;
+            }
+
+            wide[i + 4] = carry as u64;
+        }
+
+        let mut carry = 0u128;
+        for i in 0..8 {
+            let sum = wide[i] as u128 + if i < 4 { addend.0[i] as u128 } else { 0 } + carry;
+            wide[i] = sum as u64;
+            carry = sum >> 64;
+        }
+
+        let mut result = Self::ZERO;
+        for i in (0..512).rev() {
+            result = result.shift_in_bit(((wide[i / 64] >> (i % 64)) & 1) as u8);
+        }
+
+        for limb in wide.iter_mut() {
+            unsafe { core::ptr::write_volatile(limb, 0) };
+        }
+
+        result
+    }
+
+    pub fn zero(&mut self) {
+        for limb in self.0.iter_mut() {
+            // Volatile writes can't be optimized away, even if the limbs are never read again.
+            unsafe { core::ptr::write_volatile(limb, 0) };
+        }
+    }
+
+    fn shift_in_bit(&self, bit: u8) -> Self {
+        // (2 * self + bit) (mod L); self < L < 2^253, so the shifted value always fits in 256 bits, and is < 2L.
+        let mut shifted = [0u64; 4];
+        let mut carry = bit as u64;
+        for i in 0..4 {
+            shifted[i] = (self.0[i] << 1) | carry;
+            carry = self.0[i] >> 63;
+        }
+
+        // Constant-time conditional subtraction of L.
+        let (reduced, borrow) = Self::subtract_l(&shifted);
+        let mask = 0u64.wrapping_sub(borrow);
+        let mut result = [0u64; 4];
+        for i in 0..4 {
+            result[i] = (shifted[i] & mask) | (reduced[i] & !mask);
+        }
+
+        Self(result)
+    }
+
+    fn subtract_l(limbs: &[u64; 4]) -> ([u64; 4], u64) {
+        let mut result = [0u64; 4];
+        let mut borrow = 0u64;
+        for i in 0..4 {
+            let difference = (limbs[i] as u128).wrapping_sub(L[i] as u128 + borrow as u128);
+            result[i] = difference as u64;
+            borrow = ((difference >> 64) as u64) & 1;
+        }
+
+        (result, borrow)
+    }
+
+    fn limbs_from_le_bytes(bytes: &[u8; 32]) -> [u64; 4] {
+        let mut limbs = [0u64; 4];
+        for i in 0..4 {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[i * 8..(i + 1) * 8]);
+            limbs[i] = u64::from_le_bytes(word);
+        }
+
+        limbs
+    }
+}
@@ -0,0 +1,139 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::FieldElement;
+use crate::encoding::bech32::{self, Bech32Variant};
+use alloc::vec::Vec;
+
+pub const KEY_LENGTH: usize = 32;
+
+// The bech32 human readable parts of age recipients (public keys) and identities (private keys).
+pub const AGE_RECIPIENT_HUMAN_READABLE_PART: &[u8] = b"age";
+pub const AGE_IDENTITY_HUMAN_READABLE_PART: &[u8] = b"age-secret-key-";
+
+// The u coordinate of the base point of Curve25519.
+pub const BASE_POINT: [u8; KEY_LENGTH] = [
+    9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+// (A - 2) / 4, for the Montgomery curve v^2 = u^3 + Au^2 + u, where A = 486662.
+const A24: u64 = 121665;
+
+pub fn clamp(scalar: &mut [u8; KEY_LENGTH]) {
+    // Clear the bottom 3 bits so the scalar is a multiple of the cofactor (8), clear bit 255, and set bit 254 (RFC 7748, section 5).
+    scalar[0] &= 0b11111000;
+    scalar[31] &= 0b01111111;
+    scalar[31] |= 0b01000000;
+}
+
+pub fn x25519(scalar: &[u8; KEY_LENGTH], u: &[u8; KEY_LENGTH]) -> [u8; KEY_LENGTH] {
+    // Scalars are always clamped before use; this means any 32 bytes are a valid private key.
+    let mut k = *scalar;
+    clamp(&mut k);
+
+    // The most significant bit of u is ignored when decoding.
+    let x1 = FieldElement::from_le_bytes(u);
+    let (mut x2, mut z2) = (FieldElement::ONE, FieldElement::ZERO);
+    let (mut x3, mut z3) = (x1, FieldElement::ONE);
+
+    // The Montgomery ladder (RFC 7748, section 5); every bit performs the same operations, with constant-time swaps in place of branches.
+    let mut swap = false;
+    for t in (0..255).rev() {
+        let bit = (k[t / 8] >> (t % 8)) & 1 == 1;
+        swap ^= bit;
+        FieldElement::swap(&mut x2, &mut x3, swap);
+        FieldElement::swap(&mut z2, &mut z3, swap);
+        swap = bit;
+
+        let a = x2.add(&z2);
+        let aa = a.square();
+        let b = x2.subtract(&z2);
+        let bb = b.square();
+        let e = aa.subtract(&bb);
+        let c = x3.add(&z3);
+        let d = x3.subtract(&z3);
+        let da = d.multiply(&a);
+        let cb = c.multiply(&b);
+
+        x3 = da.add(&cb).square();
+        z3 = x1.multiply(&da.subtract(&cb).square());
+        x2 = aa.multiply(&bb);
+        z2 = e.multiply(&aa.add(&e.multiply_u64(A24)));
+    }
+
+    FieldElement::swap(&mut x2, &mut x3, swap);
+    FieldElement::swap(&mut z2, &mut z3, swap);
+
+    let result = x2.multiply(&z2.invert()).to_le_bytes();
+
+    // Zero everything derived from the scalar.
+    k.fill(0);
+    x2.zero();
+    z2.zero();
+    x3.zero();
+    z3.zero();
+
+    result
+}
+
+pub fn derive_public_key(private_key: &[u8; KEY_LENGTH]) -> [u8; KEY_LENGTH] {
+    x25519(private_key, &BASE_POINT)
+}
+
+pub fn try_agree(
+    private_key: &[u8; KEY_LENGTH],
+    peer_public_key: &[u8; KEY_LENGTH],
+) -> Option<[u8; KEY_LENGTH]> {
+    let shared_secret = x25519(private_key, peer_public_key);
+    if shared_secret.iter().fold(0, |a, b| a | b) == 0 {
+        // The peer's public key was a low order point; the 'shared secret' is zero regardless of our private key (RFC 7748, section 6.1).
+        None
+    } else {
+        Some(shared_secret)
+    }
+}
+
+pub fn encode_age_key(human_readable_part: &[u8], key: &[u8; KEY_LENGTH]) -> Vec<u16> {
+    // Encodes an age recipient or identity; identities are conventionally written in upper case, which is left to the caller.
+    let mut data = bech32::regroup_bits(key, 8, 5, true).unwrap();
+    let string = bech32::encode(human_readable_part, &data, Bech32Variant::Bech32);
+    data.fill(0);
+    string
+}
+
+pub fn try_decode_age_key(human_readable_part: &[u8], string: &[u16]) -> Option<[u8; KEY_LENGTH]> {
+    let (decoded_human_readable_part, mut data, variant) = match bech32::decode(string) {
+        Some(d) => d,
+        None => return None,
+    };
+
+    let key =
+        if decoded_human_readable_part == human_readable_part && variant == Bech32Variant::Bech32 {
+            bech32::regroup_bits(&data, 5, 8, false)
+        } else {
+            None
+        };
+    data.fill(0);
+
+    match key {
+        Some(mut k) => {
+            let result = <[u8; KEY_LENGTH]>::try_from(&k[..]).ok();
+            k.fill(0);
+            result
+        }
+        None => None,
+    }
+}
@@ -15,3 +15,5 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 pub mod ecc;
+pub mod edwards;
+pub mod rsa;
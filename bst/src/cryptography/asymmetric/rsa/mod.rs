@@ -0,0 +1,549 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod padding;
+pub mod primes;
+
+use crate::{
+    encoding::der,
+    hashing::{derive_bytes, Hasher, Sha256},
+    integers::{BigUnsigned, BigUnsignedCalculator, MontgomeryContext},
+    String16,
+};
+use alloc::{vec, vec::Vec};
+use macros::s16;
+use padding::HASH_LENGTH;
+use primes::vec_of_be_bytes;
+
+// RSA, as specified in RFC 8017 (PKCS #1 v2.2), on our generic BigUnsigned arithmetic. Keys are serialized as PKCS #1 DER,
+// signatures are RSASSA-PSS or RSASSA-PKCS1-v1_5, and encryption is RSAES-OAEP; all with SHA-256. None of the arithmetic is
+// constant-time, which is acceptable for an offline machine, but means these keys shouldn't be used on a shared one.
+pub const PUBLIC_EXPONENT: u32 = 65537;
+pub const MINIMUM_MODULUS_BITS: usize = 1024;
+pub const MAXIMUM_MODULUS_BITS: usize = 8192;
+pub const MINIMUM_SEED_LENGTH: usize = 32;
+pub const OAEP_SEED_LENGTH: usize = HASH_LENGTH;
+
+// The DER encoded AlgorithmIdentifier for rsaEncryption; used to recognise SubjectPublicKeyInfo-wrapped public keys.
+const RSA_ENCRYPTION_ALGORITHM_IDENTIFIER: [u8; 15] = [
+    0x30, 0x0D, 0x06, 0x09, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x01, 0x05, 0x00,
+];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SignatureScheme {
+    Pss,
+    Pkcs1V1_5,
+}
+
+#[derive(Clone)]
+pub struct RsaPublicKey {
+    modulus: BigUnsigned,
+    exponent: BigUnsigned,
+}
+
+impl RsaPublicKey {
+    pub fn try_from_der(bytes: &[u8]) -> Result<Self, String16<'static>> {
+        // RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }, optionally wrapped in a SubjectPublicKeyInfo.
+        let content = match der::try_read_element(bytes) {
+            Some((der::SEQUENCE_TAG, content, [])) => content,
+            _ => return Err(s16!("The public key is not a DER encoded RSA public key.")),
+        };
+
+        let content = match Self::try_unwrap_subject_public_key_info(content) {
+            Some(c) => c,
+            None => content,
+        };
+
+        match der::try_read_unsigned_integer(content).and_then(|(modulus, remainder)| {
+            der::try_read_unsigned_integer(remainder)
+                .map(|(exponent, remainder)| (modulus, exponent, remainder))
+        }) {
+            Some((modulus, exponent, [])) => Self::try_from(
+                BigUnsigned::from_be_bytes(modulus),
+                BigUnsigned::from_be_bytes(exponent),
+            ),
+            _ => Err(s16!("The public key is not a DER encoded RSA public key.")),
+        }
+    }
+
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut content = Vec::new();
+        der::write_unsigned_integer(&vec_of_be_bytes(&self.modulus), &mut content);
+        der::write_unsigned_integer(&vec_of_be_bytes(&self.exponent), &mut content);
+
+        let mut der = Vec::with_capacity(content.len() + 4);
+        der::write_element(der::SEQUENCE_TAG, &content, &mut der);
+        der
+    }
+
+    pub fn modulus_bits(&self) -> usize {
+        let modulus_bytes = vec_of_be_bytes(&self.modulus);
+        modulus_bytes.len() * 8 - modulus_bytes[0].leading_zeros() as usize
+    }
+
+    pub fn modulus_length(&self) -> usize {
+        // k; the length of the modulus in bytes, and therefore of signatures and ciphertexts.
+        self.modulus.byte_count()
+    }
+
+    fn try_from(modulus: BigUnsigned, exponent: BigUnsigned) -> Result<Self, String16<'static>> {
+        // The exponent must be odd and greater than 1, and the modulus must be odd and of a supported size.
+        let key = Self { modulus, exponent };
+        let modulus_bits = key.modulus_bits();
+        if key.exponent.is_even()
+            || key.exponent.is_one()
+            || key.modulus.is_even()
+            || key.exponent >= key.modulus
+            || modulus_bits < MINIMUM_MODULUS_BITS
+            || modulus_bits > MAXIMUM_MODULUS_BITS
+        {
+            return Err(s16!("The RSA public key is invalid or unsupported."));
+        }
+
+        Ok(key)
+    }
+
+    fn try_unwrap_subject_public_key_info(content: &[u8]) -> Option<&[u8]> {
+        // SubjectPublicKeyInfo ::= SEQUENCE { algorithm AlgorithmIdentifier, subjectPublicKey BIT STRING }
+        if !content.starts_with(&RSA_ENCRYPTION_ALGORITHM_IDENTIFIER) {
+            return None;
+        }
+
+        match der::try_read_element(&content[RSA_ENCRYPTION_ALGORITHM_IDENTIFIER.len()..]) {
+            // The BIT STRING's first byte is the number of unused bits, which must be zero.
+            Some((0x03, [0x00, public_key @ ..], [])) => match der::try_read_element(public_key) {
+                Some((der::SEQUENCE_TAG, content, [])) => Some(content),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn try_apply(&self, input: &[u8]) -> Option<Vec<u8>> {
+        // RSAEP / RSAVP1; input^e mod n, where the input must be less than n.
+        let value = BigUnsigned::from_be_bytes(input);
+        if input.len() != self.modulus_length() || value >= self.modulus {
+            return None;
+        }
+
+        let value = MontgomeryContext::try_from(&self.modulus)
+            .unwrap()
+            .modpow(&value, &self.exponent);
+        let mut output = vec![0u8; input.len()];
+        value.try_copy_be_bytes_to(&mut output);
+        Some(output)
+    }
+}
+
+pub struct RsaPrivateKey {
+    public_key: RsaPublicKey,
+    private_exponent: BigUnsigned,
+    prime_1: BigUnsigned,
+    prime_2: BigUnsigned,
+    exponent_1: BigUnsigned,
+    exponent_2: BigUnsigned,
+    coefficient: BigUnsigned,
+}
+
+impl RsaPrivateKey {
+    pub fn try_generate(modulus_bits: usize, seed: &[u8]) -> Result<Self, String16<'static>> {
+        if modulus_bits < MINIMUM_MODULUS_BITS
+            || modulus_bits > MAXIMUM_MODULUS_BITS
+            || modulus_bits % 16 != 0
+        {
+            return Err(s16!(
+                "The modulus size must be a multiple of 16 bits between 1024 and 8192 bits."
+            ));
+        }
+
+        if seed.len() < MINIMUM_SEED_LENGTH {
+            return Err(s16!("The seed must be at least 32 bytes."));
+        }
+
+        // Excluding primes congruent to 1 modulo e ensures e is coprime with p - 1 and q - 1, and therefore invertible.
+        let prime_bits = modulus_bits / 2;
+        let mut p = primes::generate_prime(prime_bits, PUBLIC_EXPONENT as _, seed, b"RSA prime p");
+
+        // |p - q| must be greater than 2^(nlen / 2 - 100); otherwise, n is easily factored by Fermat's method.
+        let mut minimum_distance_bytes = vec![0u8; prime_bits / 8];
+        minimum_distance_bytes[12] = 0x10;
+        let minimum_distance = BigUnsigned::from_be_bytes(&minimum_distance_bytes);
+        let mut q_context = Vec::from(&b"RSA prime q"[..]);
+        let mut q = loop {
+            let q = primes::generate_prime(prime_bits, PUBLIC_EXPONENT as _, seed, &q_context);
+            let mut distance = p.clone();
+            distance.difference_big_unsigned(&q);
+            if distance > minimum_distance {
+                break q;
+            }
+
+            q_context.push(0);
+        };
+
+        // By convention, p > q.
+        if p < q {
+            core::mem::swap(&mut p, &mut q);
+        }
+
+        let key = Self::try_from_primes(p, q);
+        match key {
+            Some(k) => Ok(k),
+            None => Err(s16!(
+                "Failed to derive an RSA key from the generated primes."
+            )),
+        }
+    }
+
+    pub fn try_from_der(bytes: &[u8]) -> Result<Self, String16<'static>> {
+        // RSAPrivateKey ::= SEQUENCE { version, modulus, publicExponent, privateExponent, prime1, prime2, exponent1, exponent2,
+        // coefficient }, where version is 0; multi-prime keys aren't supported.
+        let mut content = match der::try_read_element(bytes) {
+            Some((der::SEQUENCE_TAG, content, [])) => content,
+            _ => {
+                return Err(s16!(
+                    "The private key is not a DER encoded RSA private key."
+                ))
+            }
+        };
+
+        let mut values = Vec::with_capacity(9);
+        while values.len() < 9 {
+            match der::try_read_unsigned_integer(content) {
+                Some((value, remainder)) => {
+                    values.push(BigUnsigned::from_be_bytes(value));
+                    content = remainder;
+                }
+                None => {
+                    return Err(s16!(
+                        "The private key is not a DER encoded RSA private key."
+                    ))
+                }
+            }
+        }
+
+        if content.len() != 0 || values[0].is_non_zero() {
+            return Err(s16!("Only two-prime RSA private keys are supported."));
+        }
+
+        let mut values = values.drain(1..);
+        let public_key = RsaPublicKey::try_from(values.next().unwrap(), values.next().unwrap())?;
+        let key = Self {
+            public_key,
+            private_exponent: values.next().unwrap(),
+            prime_1: values.next().unwrap(),
+            prime_2: values.next().unwrap(),
+            exponent_1: values.next().unwrap(),
+            exponent_2: values.next().unwrap(),
+            coefficient: values.next().unwrap(),
+        };
+
+        if key.is_consistent() {
+            Ok(key)
+        } else {
+            Err(s16!("The private key's values are inconsistent."))
+        }
+    }
+
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut content = Vec::new();
+        der::write_unsigned_integer(&[0], &mut content);
+        for value in [
+            &self.public_key.modulus,
+            &self.public_key.exponent,
+            &self.private_exponent,
+            &self.prime_1,
+            &self.prime_2,
+            &self.exponent_1,
+            &self.exponent_2,
+            &self.coefficient,
+        ] {
+            let mut bytes = vec_of_be_bytes(value);
+            der::write_unsigned_integer(&bytes, &mut content);
+            bytes.fill(0);
+        }
+
+        let mut der = Vec::with_capacity(content.len() + 4);
+        der::write_element(der::SEQUENCE_TAG, &content, &mut der);
+        content.fill(0);
+        der
+    }
+
+    pub fn public_key(&self) -> &RsaPublicKey {
+        &self.public_key
+    }
+
+    pub fn zero(&mut self) {
+        self.private_exponent.zero();
+        self.prime_1.zero();
+        self.prime_2.zero();
+        self.exponent_1.zero();
+        self.exponent_2.zero();
+        self.coefficient.zero();
+    }
+
+    fn try_from_primes(p: BigUnsigned, q: BigUnsigned) -> Option<Self> {
+        let mut calculator = BigUnsignedCalculator::new(p.byte_count() * 2);
+        let exponent = BigUnsigned::from_be_bytes(&PUBLIC_EXPONENT.to_be_bytes());
+        let mut modulus = p.clone();
+        modulus.multiply_big_unsigned(&q);
+
+        let mut p_minus_one = p.clone();
+        p_minus_one.subtract(&[1]);
+        let mut q_minus_one = q.clone();
+        q_minus_one.subtract(&[1]);
+
+        // d = e^-1 mod lcm(p - 1, q - 1), as in FIPS 186-5; lcm(a, b) = a * b / gcd(a, b).
+        let mut lambda = p_minus_one.clone();
+        lambda.multiply_big_unsigned(&q_minus_one);
+        let mut divisor = gcd(&p_minus_one, &q_minus_one);
+        let mut remainder = BigUnsigned::with_byte_capacity(divisor.byte_count());
+        lambda.divide_big_unsigned_with_remainder(&divisor, &mut remainder);
+        divisor.zero();
+
+        let mut private_exponent = exponent.clone();
+        if !calculator.calculate_mod_inverse(&mut private_exponent, false, &lambda) {
+            lambda.zero();
+            return None;
+        }
+
+        lambda.zero();
+
+        // dP = d mod (p - 1), dQ = d mod (q - 1), qInv = q^-1 mod p
+        let mut exponent_1 = private_exponent.clone();
+        exponent_1.modulo_big_unsigned(&p_minus_one);
+        let mut exponent_2 = private_exponent.clone();
+        exponent_2.modulo_big_unsigned(&q_minus_one);
+        p_minus_one.zero();
+        q_minus_one.zero();
+
+        let mut coefficient = q.clone();
+        if !calculator.calculate_mod_inverse(&mut coefficient, false, &p) {
+            return None;
+        }
+
+        let key = Self {
+            public_key: match RsaPublicKey::try_from(modulus, exponent) {
+                Ok(k) => k,
+                Err(_) => return None,
+            },
+            private_exponent,
+            prime_1: p,
+            prime_2: q,
+            exponent_1,
+            exponent_2,
+            coefficient,
+        };
+
+        if key.is_consistent() {
+            Some(key)
+        } else {
+            None
+        }
+    }
+
+    fn is_consistent(&self) -> bool {
+        // n = pq, and the CRT values must agree with the public exponent and primes; e * dP = 1 mod (p - 1), e * dQ = 1 mod (q - 1),
+        // and q * qInv = 1 mod p.
+        let mut modulus = self.prime_1.clone();
+        modulus.multiply_big_unsigned(&self.prime_2);
+        if modulus != self.public_key.modulus {
+            return false;
+        }
+
+        let mut p_minus_one = self.prime_1.clone();
+        p_minus_one.subtract(&[1]);
+        let mut q_minus_one = self.prime_2.clone();
+        q_minus_one.subtract(&[1]);
+        let is_consistent =
+            is_product_one(&self.public_key.exponent, &self.exponent_1, &p_minus_one)
+                && is_product_one(&self.public_key.exponent, &self.exponent_2, &q_minus_one)
+                && is_product_one(&self.prime_2, &self.coefficient, &self.prime_1);
+
+        p_minus_one.zero();
+        q_minus_one.zero();
+        is_consistent
+    }
+
+    fn try_apply(&self, input: &[u8]) -> Option<Vec<u8>> {
+        // RSADP / RSASP1 with the Chinese remainder theorem; m1 = c^dP mod p, m2 = c^dQ mod q, h = qInv(m1 - m2) mod p, m = m2 + qh.
+        let value = BigUnsigned::from_be_bytes(input);
+        if input.len() != self.public_key.modulus_length() || value >= self.public_key.modulus {
+            return None;
+        }
+
+        let (mut m1, mut m2) = match (
+            MontgomeryContext::try_from(&self.prime_1),
+            MontgomeryContext::try_from(&self.prime_2),
+        ) {
+            (Some(p), Some(q)) => (
+                p.modpow(&value, &self.exponent_1),
+                q.modpow(&value, &self.exponent_2),
+            ),
+            _ => return None,
+        };
+
+        // m1 - m2 mod p; both are reduced mod p first, so adding p once is enough to keep the difference positive.
+        let mut h = m2.clone();
+        h.modulo_big_unsigned(&self.prime_1);
+        m1.add_big_unsigned(&self.prime_1);
+        m1.subtract_big_unsigned(&h);
+        m1.multiply_big_unsigned(&self.coefficient);
+        m1.modulo_big_unsigned(&self.prime_1);
+
+        h.set_equal_to(&m1);
+        h.multiply_big_unsigned(&self.prime_2);
+        h.add_big_unsigned(&m2);
+        m1.zero();
+        m2.zero();
+
+        let mut output = vec![0u8; input.len()];
+        h.try_copy_be_bytes_to(&mut output);
+        h.zero();
+
+        // Check the result with the public key before returning it; a fault in the CRT computation can leak a prime factor.
+        if self.public_key.try_apply(&output).as_deref() == Some(input) {
+            Some(output)
+        } else {
+            output.fill(0);
+            None
+        }
+    }
+}
+
+impl Drop for RsaPrivateKey {
+    fn drop(&mut self) {
+        self.zero();
+    }
+}
+
+pub fn try_sign(
+    private_key: &RsaPrivateKey,
+    scheme: SignatureScheme,
+    message: &[u8],
+) -> Result<Vec<u8>, String16<'static>> {
+    let message_hash = Sha256::new().get_hash_of(message);
+    let modulus_length = private_key.public_key.modulus_length();
+    let encoded = match scheme {
+        SignatureScheme::Pkcs1V1_5 => {
+            padding::emsa_pkcs1_v1_5_encode(&message_hash, modulus_length)
+        }
+        SignatureScheme::Pss => {
+            // The salt is derived from the private key and message, rather than randomly; verifiers can't tell the difference.
+            let mut private_exponent = vec_of_be_bytes(&private_key.private_exponent);
+            let mut salt = derive_bytes(&private_exponent, &message_hash, HASH_LENGTH);
+            private_exponent.fill(0);
+
+            let encoded_bits = private_key.public_key.modulus_bits() - 1;
+            let encoded = padding::emsa_pss_encode(&message_hash, encoded_bits, &salt)
+                .map(|e| left_pad(e, modulus_length));
+            salt.fill(0);
+            encoded
+        }
+    };
+
+    match encoded.and_then(|e| private_key.try_apply(&e)) {
+        Some(s) => Ok(s),
+        None => Err(s16!("Failed to produce a signature.")),
+    }
+}
+
+pub fn verify(
+    public_key: &RsaPublicKey,
+    scheme: SignatureScheme,
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    let encoded = match public_key.try_apply(signature) {
+        Some(e) => e,
+        None => return false,
+    };
+
+    let message_hash = Sha256::new().get_hash_of(message);
+    match scheme {
+        SignatureScheme::Pkcs1V1_5 => {
+            // Compare the re-encoded message, rather than parsing the signature's encoding; parsing invites forgery.
+            padding::emsa_pkcs1_v1_5_encode(&message_hash, encoded.len()) == Some(encoded)
+        }
+        SignatureScheme::Pss => {
+            // The encoded message is one byte shorter than the modulus when its bit length is a multiple of 8.
+            let encoded_bits = public_key.modulus_bits() - 1;
+            let encoded_length = (encoded_bits + 7) / 8;
+            let (leading, encoded) = encoded.split_at(encoded.len() - encoded_length);
+            leading.iter().all(|b| *b == 0)
+                && padding::emsa_pss_verify(&message_hash, encoded, encoded_bits, HASH_LENGTH)
+        }
+    }
+}
+
+pub fn try_encrypt(
+    public_key: &RsaPublicKey,
+    message: &[u8],
+    seed: &[u8; OAEP_SEED_LENGTH],
+) -> Result<Vec<u8>, String16<'static>> {
+    match padding::eme_oaep_encode(message, public_key.modulus_length(), seed)
+        .and_then(|e| public_key.try_apply(&e))
+    {
+        Some(c) => Ok(c),
+        None => Err(s16!("The message is too long for the RSA key.")),
+    }
+}
+
+pub fn try_decrypt(
+    private_key: &RsaPrivateKey,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, String16<'static>> {
+    let mut encoded = match private_key.try_apply(ciphertext) {
+        Some(e) => e,
+        None => return Err(s16!("Decryption failed.")),
+    };
+
+    let message = padding::eme_oaep_decode(&encoded);
+    encoded.fill(0);
+    match message {
+        Some(m) => Ok(m),
+        None => Err(s16!("Decryption failed.")),
+    }
+}
+
+fn left_pad(mut bytes: Vec<u8>, length: usize) -> Vec<u8> {
+    while bytes.len() < length {
+        bytes.insert(0, 0);
+    }
+
+    bytes
+}
+
+fn gcd(a: &BigUnsigned, b: &BigUnsigned) -> BigUnsigned {
+    // The Euclidean algorithm; gcd(a, b) = gcd(b, a mod b).
+    let mut a = a.clone();
+    let mut b = b.clone();
+    while b.is_non_zero() {
+        a.modulo_big_unsigned(&b);
+        core::mem::swap(&mut a, &mut b);
+    }
+
+    b.zero();
+    a
+}
+
+fn is_product_one(a: &BigUnsigned, b: &BigUnsigned, modulus: &BigUnsigned) -> bool {
+    let mut product = a.clone();
+    product.multiply_big_unsigned(b);
+    product.modulo_big_unsigned(modulus);
+    let is_one = product.is_one();
+    product.zero();
+    is_one
+}
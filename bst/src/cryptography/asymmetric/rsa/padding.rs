@@ -0,0 +1,213 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::hashing::{Hasher, Sha256};
+use alloc::{vec, vec::Vec};
+
+// The encoding methods from RFC 8017 (PKCS #1 v2.2), all with SHA-256 as the hash function, and MGF1 with SHA-256 as the mask
+// generation function.
+pub const HASH_LENGTH: usize = 32;
+
+// The DER encoded DigestInfo prefix for a SHA-256 digest; AlgorithmIdentifier { id-sha256, NULL }, and the OCTET STRING header.
+const SHA_256_DIGEST_INFO_PREFIX: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0D, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+    0x00, 0x04, 0x20,
+];
+
+pub fn emsa_pkcs1_v1_5_encode(
+    message_hash: &[u8; HASH_LENGTH],
+    encoded_length: usize,
+) -> Option<Vec<u8>> {
+    // EM = 0x00 || 0x01 || PS || 0x00 || T, where T is the DigestInfo, and PS is at least 8 0xFF bytes.
+    let t_length = SHA_256_DIGEST_INFO_PREFIX.len() + HASH_LENGTH;
+    if encoded_length < t_length + 11 {
+        return None;
+    }
+
+    let mut encoded = vec![0xFFu8; encoded_length];
+    encoded[0] = 0x00;
+    encoded[1] = 0x01;
+    encoded[encoded_length - t_length - 1] = 0x00;
+    encoded[encoded_length - t_length..encoded_length - HASH_LENGTH]
+        .copy_from_slice(&SHA_256_DIGEST_INFO_PREFIX);
+    encoded[encoded_length - HASH_LENGTH..].copy_from_slice(message_hash);
+    Some(encoded)
+}
+
+pub fn emsa_pss_encode(
+    message_hash: &[u8; HASH_LENGTH],
+    encoded_bits: usize,
+    salt: &[u8],
+) -> Option<Vec<u8>> {
+    let encoded_length = (encoded_bits + 7) / 8;
+    if encoded_length < HASH_LENGTH + salt.len() + 2 {
+        return None;
+    }
+
+    // H = Hash(0x00 * 8 || mHash || salt)
+    let h = pss_hash(message_hash, salt);
+
+    // DB = PS || 0x01 || salt, masked with MGF(H).
+    let db_length = encoded_length - HASH_LENGTH - 1;
+    let mut db = vec![0u8; db_length];
+    db[db_length - salt.len() - 1] = 0x01;
+    db[db_length - salt.len()..].copy_from_slice(salt);
+    apply_mask(&mut db, &h);
+
+    // Clear the leftmost bits, so the encoded message's integer value is less than the modulus.
+    db[0] &= 0xFF >> (8 * encoded_length - encoded_bits);
+
+    // EM = maskedDB || H || 0xBC
+    let mut encoded = db;
+    encoded.extend(h);
+    encoded.push(0xBC);
+    Some(encoded)
+}
+
+pub fn emsa_pss_verify(
+    message_hash: &[u8; HASH_LENGTH],
+    encoded: &[u8],
+    encoded_bits: usize,
+    salt_length: usize,
+) -> bool {
+    let encoded_length = (encoded_bits + 7) / 8;
+    if encoded.len() != encoded_length
+        || encoded_length < HASH_LENGTH + salt_length + 2
+        || encoded[encoded_length - 1] != 0xBC
+    {
+        return false;
+    }
+
+    let db_length = encoded_length - HASH_LENGTH - 1;
+    let mut h = [0u8; HASH_LENGTH];
+    h.copy_from_slice(&encoded[db_length..encoded_length - 1]);
+
+    // The leftmost bits must be clear.
+    let leftmost_bits_mask = 0xFFu8 >> (8 * encoded_length - encoded_bits);
+    if encoded[0] & !leftmost_bits_mask != 0 {
+        return false;
+    }
+
+    let mut db = Vec::from(&encoded[..db_length]);
+    apply_mask(&mut db, &h);
+    db[0] &= leftmost_bits_mask;
+
+    // DB must be zero padding, followed by 0x01, followed by the salt.
+    let padding_length = db_length - salt_length - 1;
+    if db[..padding_length].iter().any(|b| *b != 0) || db[padding_length] != 0x01 {
+        return false;
+    }
+
+    pss_hash(message_hash, &db[padding_length + 1..]) == h
+}
+
+pub fn eme_oaep_encode(
+    message: &[u8],
+    encoded_length: usize,
+    seed: &[u8; HASH_LENGTH],
+) -> Option<Vec<u8>> {
+    // The message can be at most k - 2hLen - 2 bytes.
+    if encoded_length < 2 * HASH_LENGTH + 2 || message.len() > encoded_length - 2 * HASH_LENGTH - 2
+    {
+        return None;
+    }
+
+    // DB = lHash || PS || 0x01 || M, where lHash is the hash of the empty label.
+    let db_length = encoded_length - HASH_LENGTH - 1;
+    let mut db = vec![0u8; db_length];
+    db[..HASH_LENGTH].copy_from_slice(&Sha256::new().get_hash_of(&[]));
+    db[db_length - message.len() - 1] = 0x01;
+    db[db_length - message.len()..].copy_from_slice(message);
+
+    // maskedDB = DB XOR MGF(seed); maskedSeed = seed XOR MGF(maskedDB)
+    apply_mask(&mut db, seed);
+    let mut masked_seed = *seed;
+    apply_mask(&mut masked_seed, &db);
+
+    // EM = 0x00 || maskedSeed || maskedDB
+    let mut encoded = Vec::with_capacity(encoded_length);
+    encoded.push(0x00);
+    encoded.extend(masked_seed);
+    encoded.extend(&db);
+    masked_seed.fill(0);
+    db.fill(0);
+    Some(encoded)
+}
+
+pub fn eme_oaep_decode(encoded: &[u8]) -> Option<Vec<u8>> {
+    let encoded_length = encoded.len();
+    if encoded_length < 2 * HASH_LENGTH + 2 {
+        return None;
+    }
+
+    let mut seed = [0u8; HASH_LENGTH];
+    seed.copy_from_slice(&encoded[1..1 + HASH_LENGTH]);
+    let mut db = Vec::from(&encoded[1 + HASH_LENGTH..]);
+    apply_mask(&mut seed, &db);
+    apply_mask(&mut db, &seed);
+    seed.fill(0);
+
+    // Every check is performed before deciding the result, so a failure doesn't reveal which check failed.
+    let label_hash = Sha256::new().get_hash_of(&[]);
+    let mut is_invalid = encoded[0] != 0x00;
+    is_invalid |= db[..HASH_LENGTH] != label_hash;
+
+    let mut separator_index = 0;
+    for (i, byte) in db.iter().enumerate().skip(HASH_LENGTH) {
+        if separator_index == 0 {
+            if *byte == 0x01 {
+                separator_index = i;
+            } else if *byte != 0x00 {
+                is_invalid = true;
+            }
+        }
+    }
+
+    is_invalid |= separator_index == 0;
+    let message = if is_invalid {
+        None
+    } else {
+        Some(Vec::from(&db[separator_index + 1..]))
+    };
+
+    db.fill(0);
+    message
+}
+
+fn pss_hash(message_hash: &[u8; HASH_LENGTH], salt: &[u8]) -> [u8; HASH_LENGTH] {
+    Sha256::new()
+        .feed_bytes(&[0u8; 8])
+        .feed_bytes(message_hash)
+        .feed_bytes(salt)
+        .get_hash()
+}
+
+fn apply_mask(bytes: &mut [u8], seed: &[u8]) {
+    // XOR the bytes with MGF1(seed, len); the concatenation of Hash(seed || counter) for a 32 bit big-endian counter.
+    let mut sha256 = Sha256::new();
+    for (counter, chunk) in bytes.chunks_mut(HASH_LENGTH).enumerate() {
+        let mut mask = sha256
+            .reset()
+            .feed_bytes(seed)
+            .feed_bytes(&(counter as u32).to_be_bytes())
+            .get_hash();
+        for (byte, mask_byte) in chunk.iter_mut().zip(mask.iter()) {
+            *byte ^= *mask_byte;
+        }
+
+        mask.fill(0);
+    }
+}
@@ -0,0 +1,164 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    hashing::derive_bytes,
+    integers::{BigUnsigned, Digit, MontgomeryContext},
+};
+use alloc::vec::Vec;
+
+// Each Miller-Rabin round passes a composite with probability at most 1/4, and far less for random candidates.
+pub const MILLER_RABIN_ROUNDS: usize = 64;
+
+// The odd primes below 1000; trial division by these cheaply discards most candidates before the expensive Miller-Rabin rounds.
+const SMALL_PRIMES: [Digit; 167] = [
+    3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199, 211, 223, 227, 229, 233, 239, 241, 251, 257, 263, 269, 271, 277, 281, 283, 293, 307,
+    311, 313, 317, 331, 337, 347, 349, 353, 359, 367, 373, 379, 383, 389, 397, 401, 409, 419, 421,
+    431, 433, 439, 443, 449, 457, 461, 463, 467, 479, 487, 491, 499, 503, 509, 521, 523, 541, 547,
+    557, 563, 569, 571, 577, 587, 593, 599, 601, 607, 613, 617, 619, 631, 641, 643, 647, 653, 659,
+    661, 673, 677, 683, 691, 701, 709, 719, 727, 733, 739, 743, 751, 757, 761, 769, 773, 787, 797,
+    809, 811, 821, 823, 827, 829, 839, 853, 857, 859, 863, 877, 881, 883, 887, 907, 911, 919, 929,
+    937, 941, 947, 953, 967, 971, 977, 983, 991, 997,
+];
+
+pub fn generate_prime(
+    bits: usize,
+    excluded_residue_modulus: Digit,
+    seed: &[u8],
+    context: &[u8],
+) -> BigUnsigned {
+    // Candidates are derived from the seed until one is prime; each attempt has its own context, so the search is deterministic
+    // for a given seed, and the result is uniformly distributed over the primes of the requested form. The caller can exclude
+    // primes congruent to 1 modulo a value; for RSA, p - 1 must be coprime with the public exponent.
+    let mut attempt_context = Vec::with_capacity(context.len() + 4);
+    let mut attempt = 0u32;
+    loop {
+        attempt_context.clear();
+        attempt_context.extend(context);
+        attempt_context.extend(attempt.to_be_bytes());
+        attempt += 1;
+
+        let mut bytes = derive_bytes(seed, &attempt_context, bits / 8);
+
+        // Set the top two bits, so the product of two such primes has exactly twice as many bits, and the bottom bit, so it's odd.
+        bytes[0] |= 0xC0;
+        bytes[bits / 8 - 1] |= 1;
+        let mut candidate = BigUnsigned::from_be_bytes(&bytes);
+        bytes.fill(0);
+
+        if residue(&candidate, excluded_residue_modulus) != 1
+            && is_probable_prime(&candidate, seed, MILLER_RABIN_ROUNDS)
+        {
+            return candidate;
+        }
+
+        candidate.zero();
+    }
+}
+
+pub fn is_probable_prime(candidate: &BigUnsigned, witness_seed: &[u8], rounds: usize) -> bool {
+    if candidate.is_zero() || candidate.is_one() {
+        return false;
+    }
+
+    if candidate.is_even() {
+        return *candidate == [2][..];
+    }
+
+    // Trial division; a candidate divisible by a small prime is only prime if it is that small prime.
+    for small_prime in SMALL_PRIMES {
+        if residue(candidate, small_prime) == 0 {
+            return *candidate == [small_prime][..];
+        }
+    }
+
+    if *candidate < [SMALL_PRIMES[SMALL_PRIMES.len() - 1]][..] {
+        // Anything under the largest small prime which survived trial division is prime.
+        return true;
+    }
+
+    // Write n - 1 as 2^s * r, with r odd.
+    let mut n_minus_one = candidate.clone();
+    n_minus_one.subtract(&[1]);
+    let mut r = n_minus_one.clone();
+    let mut s = 0usize;
+    let mut remainder = 0;
+    while r.is_even() {
+        r.divide_by_single_digit_with_remainder(2, &mut remainder);
+        s += 1;
+    }
+
+    // Witnesses are uniformly random in the range [2, n - 2]; 8 extra bytes per witness make the modulo bias negligible.
+    let mut n_minus_three = n_minus_one.clone();
+    n_minus_three.subtract(&[2]);
+    let witness_length = candidate.byte_count() + 8;
+    let mut candidate_bytes = vec_of_be_bytes(candidate);
+    let mut witness_bytes = derive_bytes(witness_seed, &candidate_bytes, witness_length * rounds);
+    candidate_bytes.fill(0);
+
+    let context = MontgomeryContext::try_from(candidate).unwrap();
+    let mut x = BigUnsigned::with_byte_capacity(witness_length * 2);
+    let mut square = BigUnsigned::with_byte_capacity(witness_length);
+    let mut is_probable_prime = true;
+    'witnesses: for witness in witness_bytes.chunks_exact(witness_length) {
+        // x = a^r mod n
+        x.copy_be_bytes_from(witness);
+        x.modulo_big_unsigned(&n_minus_three);
+        x.add(&[2]);
+        x = context.modpow(&x, &r);
+        if x.is_one() || x == n_minus_one {
+            continue;
+        }
+
+        // Square up to s - 1 times; reaching n - 1 means this witness doesn't prove n composite. Never reaching it means it does.
+        for _ in 1..s {
+            square.set_equal_to(&x);
+            x.multiply_big_unsigned(&square);
+            x.modulo_big_unsigned(candidate);
+            if x == n_minus_one {
+                continue 'witnesses;
+            }
+
+            if x.is_one() {
+                break;
+            }
+        }
+
+        is_probable_prime = false;
+        break;
+    }
+
+    witness_bytes.fill(0);
+    x.zero();
+    square.zero();
+    is_probable_prime
+}
+
+pub(super) fn vec_of_be_bytes(value: &BigUnsigned) -> Vec<u8> {
+    let mut bytes = alloc::vec![0u8; value.byte_count()];
+    value.try_copy_be_bytes_to(&mut bytes);
+    bytes
+}
+
+fn residue(value: &BigUnsigned, modulus: Digit) -> Digit {
+    let mut quotient = value.clone();
+    let mut remainder = 0;
+    quotient.divide_by_single_digit_with_remainder(modulus, &mut remainder);
+    quotient.zero();
+    remainder
+}
@@ -0,0 +1,55 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Arithmetic in GF(2^8), using the AES (Rijndael) reduction polynomial x^8 + x^4 + x^3 + x + 1.
+const REDUCTION_POLYNOMIAL: u8 = 0x1B;
+
+pub fn add(a: u8, b: u8) -> u8 {
+    // Addition and subtraction are both XOR in characteristic 2.
+    a ^ b
+}
+
+pub fn multiply(mut a: u8, mut b: u8) -> u8 {
+    // Shift-and-add multiplication; every iteration performs the same operations regardless of the inputs' values.
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = a >> 7;
+        a = (a << 1) ^ (REDUCTION_POLYNOMIAL & 0u8.wrapping_sub(carry));
+        b >>= 1;
+    }
+
+    product
+}
+
+pub fn invert(a: u8) -> u8 {
+    // a^254 = a^-1, as the multiplicative group has order 255; zero maps to zero.
+    let a2 = multiply(a, a);
+    let a4 = multiply(a2, a2);
+    let a8 = multiply(a4, a4);
+    let a16 = multiply(a8, a8);
+    let a32 = multiply(a16, a16);
+    let a64 = multiply(a32, a32);
+    let a128 = multiply(a64, a64);
+    multiply(
+        multiply(multiply(a128, a64), multiply(a32, a16)),
+        multiply(multiply(a8, a4), a2),
+    )
+}
+
+pub fn divide(a: u8, b: u8) -> u8 {
+    multiply(a, invert(b))
+}
@@ -0,0 +1,272 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod galois_field;
+
+use crate::{
+    bitcoin::mnemonics::MnemonicWordList,
+    hashing::{derive_bytes, Hasher, Sha256},
+    String16,
+};
+use alloc::{vec, vec::Vec};
+use macros::s16;
+
+// Shares are laid out as [threshold][index][value][checksum].
+const THRESHOLD_OFFSET: usize = 0;
+const INDEX_OFFSET: usize = 1;
+const VALUE_OFFSET: usize = 2;
+
+pub const CHECKSUM_LENGTH: usize = 4;
+
+// The secret is suffixed with a digest before it is split, so a recombined secret can be verified.
+pub const DIGEST_LENGTH: usize = 4;
+
+pub const SHARE_OVERHEAD: usize = VALUE_OFFSET + DIGEST_LENGTH + CHECKSUM_LENGTH;
+
+pub const MINIMUM_COEFFICIENT_SEED_LENGTH: usize = 32;
+
+#[derive(Clone, Copy)]
+pub struct Share<'a> {
+    threshold: u8,
+    index: u8,
+    value: &'a [u8],
+}
+
+impl<'a> Share<'a> {
+    pub const fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    pub const fn index(&self) -> u8 {
+        self.index
+    }
+}
+
+pub fn try_parse_share<'a>(bytes: &'a [u8]) -> Result<Share<'a>, String16<'static>> {
+    if bytes.len() <= SHARE_OVERHEAD {
+        return Err(s16!("The share is too short."));
+    }
+
+    // The checksum covers the threshold, index and value.
+    let checksum_offset = bytes.len() - CHECKSUM_LENGTH;
+    if Sha256::new().calculate_double_hash_checksum_for(&bytes[..checksum_offset])
+        != bytes[checksum_offset..]
+    {
+        return Err(s16!("The share failed its checksum."));
+    }
+
+    let share = Share {
+        threshold: bytes[THRESHOLD_OFFSET],
+        index: bytes[INDEX_OFFSET],
+        value: &bytes[VALUE_OFFSET..checksum_offset],
+    };
+
+    if share.threshold == 0 {
+        Err(s16!("The share has a threshold of zero."))
+    } else if share.index == 0 {
+        // The secret lives at x = 0; a share can never be located there.
+        Err(s16!("The share has an index of zero."))
+    } else {
+        Ok(share)
+    }
+}
+
+pub fn try_split_secret(
+    secret: &[u8],
+    threshold: u8,
+    share_count: u8,
+    coefficient_seed: &[u8],
+) -> Result<Vec<Vec<u8>>, String16<'static>> {
+    if secret.len() == 0 {
+        return Err(s16!("The secret cannot be empty."));
+    }
+
+    if threshold == 0 {
+        return Err(s16!("The threshold must be at least 1."));
+    }
+
+    if share_count < threshold {
+        return Err(s16!(
+            "The number of shares cannot be lower than the threshold."
+        ));
+    }
+
+    if coefficient_seed.len() < MINIMUM_COEFFICIENT_SEED_LENGTH {
+        return Err(s16!("The coefficient seed must be at least 32 bytes."));
+    }
+
+    // The constant term of each byte's polynomial is the secret, followed by its digest.
+    let value_length = secret.len() + DIGEST_LENGTH;
+    let mut constant_term = Vec::with_capacity(value_length);
+    constant_term.extend(secret);
+    constant_term.extend(Sha256::new().calculate_double_hash_checksum_for(secret));
+
+    // The remaining coefficients are derived from the seed; we don't have a source of randomness, so the caller provides the entropy.
+    let mut coefficients = derive_bytes(
+        coefficient_seed,
+        &constant_term,
+        (threshold as usize - 1) * value_length,
+    );
+
+    let mut shares = Vec::with_capacity(share_count as usize);
+    for x in 1..=share_count {
+        let mut share = Vec::with_capacity(VALUE_OFFSET + value_length + CHECKSUM_LENGTH);
+        share.push(threshold);
+        share.push(x);
+
+        // Evaluate each byte's polynomial at x with Horner's method.
+        for i in 0..value_length {
+            let mut y = 0u8;
+            for j in (0..threshold as usize - 1).rev() {
+                y = galois_field::add(
+                    galois_field::multiply(y, x),
+                    coefficients[j * value_length + i],
+                );
+            }
+
+            share.push(galois_field::add(
+                galois_field::multiply(y, x),
+                constant_term[i],
+            ));
+        }
+
+        let checksum = Sha256::new().calculate_double_hash_checksum_for(&share);
+        share.extend(checksum);
+        shares.push(share);
+    }
+
+    constant_term.fill(0);
+    coefficients.fill(0);
+    Ok(shares)
+}
+
+pub fn try_combine_shares(shares: &[&[u8]]) -> Result<Vec<u8>, String16<'static>> {
+    if shares.len() == 0 {
+        return Err(s16!("No shares were provided."));
+    }
+
+    let mut parsed_shares = Vec::with_capacity(shares.len());
+    for share in shares {
+        parsed_shares.push(try_parse_share(share)?);
+    }
+
+    let first = parsed_shares[0];
+    for share in &parsed_shares[1..] {
+        if share.threshold != first.threshold || share.value.len() != first.value.len() {
+            return Err(s16!("The shares do not belong to the same secret."));
+        }
+    }
+
+    if parsed_shares.len() < first.threshold as usize {
+        return Err(s16!(
+            "Not enough shares were provided to meet the threshold."
+        ));
+    }
+
+    // Any threshold-sized subset of the shares is enough to recover the secret.
+    let points: Vec<(u8, &[u8])> = parsed_shares[..first.threshold as usize]
+        .iter()
+        .map(|s| (s.index, s.value))
+        .collect();
+
+    let mut recovered = match interpolate(&points, 0) {
+        Some(r) => r,
+        None => return Err(s16!("The shares contain duplicate indices.")),
+    };
+
+    // Verify the secret against the digest which was split alongside it.
+    let secret_length = recovered.len() - DIGEST_LENGTH;
+    if Sha256::new().calculate_double_hash_checksum_for(&recovered[..secret_length])
+        != recovered[secret_length..]
+    {
+        recovered.fill(0);
+        return Err(s16!(
+            "The recovered secret failed its checksum; the shares are inconsistent."
+        ));
+    }
+
+    recovered.truncate(secret_length);
+    Ok(recovered)
+}
+
+pub fn interpolate(points: &[(u8, &[u8])], x: u8) -> Option<Vec<u8>> {
+    if points.len() == 0 {
+        return None;
+    }
+
+    let length = points[0].1.len();
+    for i in 0..points.len() {
+        if points[i].1.len() != length || points[..i].iter().any(|p| p.0 == points[i].0) {
+            // Lagrange interpolation requires distinct x coordinates and equal length values.
+            return None;
+        }
+    }
+
+    let mut result = vec![0u8; length];
+    for (i, (x_i, y_i)) in points.iter().enumerate() {
+        // Calculate the Lagrange basis polynomial for the point, evaluated at x.
+        let mut numerator = 1u8;
+        let mut denominator = 1u8;
+        for (j, (x_j, _)) in points.iter().enumerate() {
+            if i != j {
+                numerator = galois_field::multiply(numerator, galois_field::add(x, *x_j));
+                denominator = galois_field::multiply(denominator, galois_field::add(*x_i, *x_j));
+            }
+        }
+
+        let basis = galois_field::divide(numerator, denominator);
+        for k in 0..length {
+            result[k] = galois_field::add(result[k], galois_field::multiply(basis, y_i[k]));
+        }
+    }
+
+    Some(result)
+}
+
+pub fn get_share_words(word_list: &MnemonicWordList, share: &[u8]) -> Vec<String16<'static>> {
+    // Right-align the share in a buffer which holds a whole number of words; the leading bits are zero padding.
+    let bits_per_word = word_list.bits_per_word();
+    let word_count = (share.len() * 8 + bits_per_word - 1) / bits_per_word;
+    let mut buffer = vec![0u8; (word_count * bits_per_word + 7) / 8];
+    let buffer_length = buffer.len();
+    buffer[buffer_length - share.len()..].copy_from_slice(share);
+
+    let start_bit_offset = buffer_length * 8 - word_count * bits_per_word;
+    let words = (0..word_count)
+        .map(|i| {
+            word_list
+                .try_get_word(start_bit_offset, i, &buffer)
+                .unwrap()
+        })
+        .collect();
+
+    buffer.fill(0);
+    words
+}
+
+pub fn try_read_share_words<'a>(
+    word_list: &MnemonicWordList,
+    words: &Vec<String16<'a>>,
+) -> Result<Vec<u8>, (String16<'a>, usize)> {
+    let bit_count = words.len() * word_list.bits_per_word();
+    let (_, mut bytes) =
+        word_list.try_read_mnemonic_bytes((bit_count + 7) / 8, bit_count, words)?;
+
+    // A share's threshold is never zero, so the leading zero bytes are padding.
+    let padding = bytes.iter().take_while(|b| **b == 0).count();
+    bytes.drain(..padding);
+    Ok(bytes)
+}
@@ -0,0 +1,96 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use alloc::vec::Vec;
+use macros::u16_array;
+
+// The standard RFC 4648 alphabet. Note this differs from the base-64 numeric base, which treats the input as one big integer;
+// RFC 4648 base-64 encodes each group of 3 bytes as 4 characters, and pads the final group with '='.
+const ALPHABET: [u16; 64] =
+    u16_array!("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+const PADDING: u16 = b'=' as u16;
+
+pub fn encode(bytes: &[u8]) -> Vec<u16> {
+    let mut string = Vec::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        // Pack the chunk into the top 24 bits of a group, then take 6 bits at a time.
+        let group = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                string.push(ALPHABET[((group >> (18 - 6 * i)) & 0b111111) as usize]);
+            } else {
+                // The chunk was short; pad the group out to 4 characters.
+                string.push(PADDING);
+            }
+        }
+    }
+
+    string
+}
+
+pub fn decode(string: &[u16]) -> Option<Vec<u8>> {
+    // Whitespace is ignored, as encoded strings are often wrapped; otherwise the input must be canonical, padded base-64.
+    let mut values = Vec::with_capacity(string.len());
+    let mut padding = 0;
+    for character in string {
+        match *character {
+            0x09 | 0x0A | 0x0D | 0x20 => continue,
+            PADDING => padding += 1,
+            c => {
+                if padding > 0 {
+                    // Padding can only appear at the end of the string.
+                    return None;
+                }
+
+                match ALPHABET.iter().position(|a| *a == c) {
+                    Some(v) => values.push(v as u32),
+                    None => return None,
+                }
+            }
+        }
+    }
+
+    if (values.len() + padding) % 4 != 0
+        || padding > 2
+        || (padding > 0 && values.len() % 4 + padding != 4)
+    {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(values.len() * 3 / 4);
+    for chunk in values.chunks(4) {
+        // Pack the chunk's 6 bit values into the top bits of a 24 bit group, then take 8 bits at a time.
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |g, (i, v)| g | v << (18 - 6 * i));
+
+        let byte_count = chunk.len() * 6 / 8;
+        if group & ((1 << (24 - byte_count * 8)) - 1) != 0 {
+            // The unused bits of a padded group must be zero.
+            return None;
+        }
+
+        for i in 0..byte_count {
+            bytes.push((group >> (16 - 8 * i)) as u8);
+        }
+    }
+
+    Some(bytes)
+}
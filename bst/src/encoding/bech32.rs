@@ -0,0 +1,381 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use macros::u16_array;
+
+const CHARSET: [u16; 32] = u16_array!("qpzry9x8gf2tvdw0s3jn54khce6mua7l");
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+const SEPARATOR: u16 = b'1' as u16;
+const CHECKSUM_LENGTH: usize = 6;
+const MAX_LENGTH: usize = 90;
+
+// Bech32's checksum has a minimum distance of 5 for strings within the maximum length; up to 2 substitution errors can be corrected.
+pub const MAX_CORRECTABLE_ERRORS: usize = 2;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Bech32Variant {
+    // BIP 173.
+    Bech32,
+    // BIP 350.
+    Bech32m,
+}
+
+impl Bech32Variant {
+    const fn checksum_constant(&self) -> u32 {
+        match self {
+            Bech32Variant::Bech32 => 1,
+            Bech32Variant::Bech32m => 0x2bc830a3,
+        }
+    }
+}
+
+pub fn encode(human_readable_part: &[u8], data: &[u8], variant: Bech32Variant) -> Vec<u16> {
+    // The data is expected to have already been regrouped into 5 bit values; the human readable part should be lower case ASCII.
+    let checksum = create_checksum(human_readable_part, data, variant);
+    let mut string =
+        Vec::with_capacity(human_readable_part.len() + 1 + data.len() + CHECKSUM_LENGTH);
+
+    string.extend(human_readable_part.iter().map(|c| *c as u16));
+    string.push(SEPARATOR);
+    string.extend(
+        data.iter()
+            .chain(checksum.iter())
+            .map(|d| CHARSET[*d as usize]),
+    );
+    string
+}
+
+pub fn decode(string: &[u16]) -> Option<(Vec<u8>, Vec<u8>, Bech32Variant)> {
+    // Returns the lower case human readable part, the 5 bit data values without the checksum, and the checksum's variant.
+    let (human_readable_part, separator) = match try_split(string) {
+        Some(s) => s,
+        None => return None,
+    };
+
+    let mut data = Vec::with_capacity(string.len() - separator - 1);
+    for character in &string[separator + 1..] {
+        match try_get_value(*character) {
+            Some(v) => data.push(v),
+            None => return None,
+        }
+    }
+
+    let checksum =
+        polymod(expand_human_readable_part(&human_readable_part).chain(data.iter().copied()));
+    let variant = if checksum == Bech32Variant::Bech32.checksum_constant() {
+        Bech32Variant::Bech32
+    } else if checksum == Bech32Variant::Bech32m.checksum_constant() {
+        Bech32Variant::Bech32m
+    } else {
+        return None;
+    };
+
+    data.truncate(data.len() - CHECKSUM_LENGTH);
+    Some((human_readable_part, data, variant))
+}
+
+pub fn try_locate_errors(string: &[u16]) -> Option<(Bech32Variant, Vec<(usize, u16)>)> {
+    // Locates up to MAX_CORRECTABLE_ERRORS substituted characters in the data part of a string which fails decoding, returning the
+    // variant the corrected string is valid under, and each erroneous character's index alongside its correction. Characters outside
+    // the character set are always treated as errors. Errors in the human readable part cannot be located; the human readable part
+    // is an input to the checksum, so the string is assumed to have been intended for the human readable part it has.
+    let (human_readable_part, separator) = match try_split(string) {
+        Some(s) => s,
+        None => return None,
+    };
+
+    let mut data = Vec::with_capacity(string.len() - separator - 1);
+    let mut invalid_indices = Vec::new();
+    for (i, character) in string[separator + 1..].iter().enumerate() {
+        data.push(match try_get_value(*character) {
+            Some(v) => v,
+            None => {
+                invalid_indices.push(i);
+                0
+            }
+        });
+    }
+
+    let bech32_errors = try_locate_data_errors(
+        &human_readable_part,
+        &data,
+        &invalid_indices,
+        Bech32Variant::Bech32,
+    );
+    let bech32m_errors = try_locate_data_errors(
+        &human_readable_part,
+        &data,
+        &invalid_indices,
+        Bech32Variant::Bech32m,
+    );
+
+    // Prefer the variant requiring the fewest corrections; if both require the same number, the intended variant is ambiguous.
+    let (variant, errors) = match (bech32_errors, bech32m_errors) {
+        (Some(b), Some(m)) if b.len() < m.len() => (Bech32Variant::Bech32, b),
+        (Some(b), Some(m)) if m.len() < b.len() => (Bech32Variant::Bech32m, m),
+        (Some(b), None) => (Bech32Variant::Bech32, b),
+        (None, Some(m)) => (Bech32Variant::Bech32m, m),
+        _ => return None,
+    };
+
+    let is_upper_case = string.iter().any(|c| (0x41..=0x5A).contains(c));
+    Some((
+        variant,
+        errors
+            .into_iter()
+            .map(|(i, v)| {
+                let correction = CHARSET[v as usize];
+                (
+                    separator + 1 + i,
+                    if is_upper_case && correction > 0x60 {
+                        correction - 0x20
+                    } else {
+                        correction
+                    },
+                )
+            })
+            .collect(),
+    ))
+}
+
+pub fn regroup_bits(data: &[u8], from_bits: u32, to_bits: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut accumulator: u32 = 0;
+    let mut accumulated_bits = 0;
+    let max_value = (1u32 << to_bits) - 1;
+    let mut result = Vec::with_capacity(
+        (data.len() * from_bits as usize + to_bits as usize - 1) / to_bits as usize,
+    );
+    for value in data {
+        if (*value as u32) >> from_bits != 0 {
+            // The value doesn't fit in the input group size.
+            return None;
+        }
+
+        accumulator = (accumulator << from_bits) | *value as u32;
+        accumulated_bits += from_bits;
+        while accumulated_bits >= to_bits {
+            accumulated_bits -= to_bits;
+            result.push(((accumulator >> accumulated_bits) & max_value) as u8);
+        }
+    }
+
+    if pad {
+        if accumulated_bits > 0 {
+            // Pad the final group with zero bits.
+            result.push(((accumulator << (to_bits - accumulated_bits)) & max_value) as u8);
+        }
+    } else if accumulated_bits >= from_bits
+        || ((accumulator << (to_bits - accumulated_bits)) & max_value) != 0
+    {
+        // Without padding, any leftover bits must be fewer than a whole input group, and zero.
+        return None;
+    }
+
+    Some(result)
+}
+
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    values.fold(1, |checksum, value| polymod_step(checksum, value))
+}
+
+fn polymod_step(checksum: u32, value: u8) -> u32 {
+    let top = checksum >> 25;
+    let mut checksum = (checksum & 0x1ffffff) << 5 ^ value as u32;
+    for (i, generator) in GENERATOR.iter().enumerate() {
+        if (top >> i) & 1 == 1 {
+            checksum ^= generator;
+        }
+    }
+
+    checksum
+}
+
+fn try_split(string: &[u16]) -> Option<(Vec<u8>, usize)> {
+    // Returns the lower case human readable part, and the separator's index.
+    if string.len() > MAX_LENGTH
+        || (string.iter().any(|c| (0x61..=0x7A).contains(c))
+            && string.iter().any(|c| (0x41..=0x5A).contains(c)))
+    {
+        // Strings can be at most 90 characters, and can't be mixed case.
+        return None;
+    }
+
+    let separator = match string.iter().rposition(|c| *c == SEPARATOR) {
+        Some(s) if s > 0 && string.len() - s - 1 >= CHECKSUM_LENGTH => s,
+        _ => return None,
+    };
+
+    let mut human_readable_part = Vec::with_capacity(separator);
+    for character in &string[..separator] {
+        match *character {
+            // Human readable part characters are printable ASCII.
+            0x21..=0x7E => human_readable_part.push((*character as u8).to_ascii_lowercase()),
+            _ => return None,
+        }
+    }
+
+    Some((human_readable_part, separator))
+}
+
+fn try_get_value(character: u16) -> Option<u8> {
+    let lower_case = match character {
+        0x41..=0x5A => character + 0x20,
+        c => c,
+    };
+
+    match CHARSET.iter().position(|c| *c == lower_case) {
+        Some(v) => Some(v as u8),
+        None => None,
+    }
+}
+
+fn try_locate_data_errors(
+    human_readable_part: &[u8],
+    data: &[u8],
+    invalid_indices: &[usize],
+    variant: Bech32Variant,
+) -> Option<Vec<(usize, u8)>> {
+    // Returns the data indices of the fewest substitutions which would make the data valid, alongside their correct values.
+    if invalid_indices.len() > MAX_CORRECTABLE_ERRORS {
+        return None;
+    }
+
+    let residue =
+        polymod(expand_human_readable_part(human_readable_part).chain(data.iter().copied()))
+            ^ variant.checksum_constant();
+
+    // The checksum is linear over GF(2); substituting a value with another XORs the residue with the checksum of their difference
+    // followed by as many zeroes as there are values after it. Calculate that for each bit of a difference at each index.
+    let mut bit_effects = Vec::with_capacity(data.len());
+    let mut effects = [1, 2, 4, 8, 16];
+    for _ in 0..data.len() {
+        bit_effects.push(effects);
+        for effect in &mut effects {
+            *effect = polymod_step(*effect, 0);
+        }
+    }
+
+    bit_effects.reverse();
+    let effect = |index: usize, difference: u8| {
+        (0..5)
+            .filter(|b| (difference >> b) & 1 == 1)
+            .fold(0, |e, b| e ^ bit_effects[index][b])
+    };
+
+    let correction = |index: usize, difference: u8| (index, data[index] ^ difference);
+
+    // Invalid characters must be corrected, but their placeholder values may already be correct.
+    match invalid_indices {
+        [] => {}
+        [i] => {
+            for difference in 0..32 {
+                if effect(*i, difference) == residue {
+                    return Some(Vec::from([correction(*i, difference)]));
+                }
+            }
+
+            for first_difference in 0..32 {
+                let remaining_residue = residue ^ effect(*i, first_difference);
+                for j in (0..data.len()).filter(|j| j != i) {
+                    for second_difference in 1..32 {
+                        if effect(j, second_difference) == remaining_residue {
+                            return Some(Vec::from([
+                                correction(*i, first_difference),
+                                correction(j, second_difference),
+                            ]));
+                        }
+                    }
+                }
+            }
+
+            return None;
+        }
+        [i, j] => {
+            for first_difference in 0..32 {
+                for second_difference in 0..32 {
+                    if effect(*i, first_difference) ^ effect(*j, second_difference) == residue {
+                        return Some(Vec::from([
+                            correction(*i, first_difference),
+                            correction(*j, second_difference),
+                        ]));
+                    }
+                }
+            }
+
+            return None;
+        }
+        _ => return None,
+    }
+
+    if residue == 0 {
+        return Some(Vec::new());
+    }
+
+    // Map each single substitution's effect on the residue to the substitution; with a minimum distance of 5, at most one
+    // combination of up to 2 substitutions can cancel out the residue.
+    let mut substitutions = BTreeMap::new();
+    for i in 0..data.len() {
+        for difference in 1..32 {
+            substitutions.insert(effect(i, difference), (i, difference));
+        }
+    }
+
+    if let Some((i, difference)) = substitutions.get(&residue) {
+        return Some(Vec::from([correction(*i, *difference)]));
+    }
+
+    for (e, (i, first_difference)) in &substitutions {
+        if let Some((j, second_difference)) = substitutions.get(&(residue ^ e)) {
+            if i < j {
+                return Some(Vec::from([
+                    correction(*i, *first_difference),
+                    correction(*j, *second_difference),
+                ]));
+            }
+        }
+    }
+
+    None
+}
+
+fn expand_human_readable_part(human_readable_part: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    // The high bits of each character, a zero separator, then the low bits of each character.
+    human_readable_part
+        .iter()
+        .map(|c| c >> 5)
+        .chain([0])
+        .chain(human_readable_part.iter().map(|c| c & 0b11111))
+}
+
+fn create_checksum(
+    human_readable_part: &[u8],
+    data: &[u8],
+    variant: Bech32Variant,
+) -> [u8; CHECKSUM_LENGTH] {
+    let checksum = polymod(
+        expand_human_readable_part(human_readable_part)
+            .chain(data.iter().copied())
+            .chain([0u8; CHECKSUM_LENGTH]),
+    ) ^ variant.checksum_constant();
+
+    let mut result = [0u8; CHECKSUM_LENGTH];
+    for i in 0..CHECKSUM_LENGTH {
+        result[i] = ((checksum >> (5 * (CHECKSUM_LENGTH - 1 - i))) & 0b11111) as u8;
+    }
+
+    result
+}
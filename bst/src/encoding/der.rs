@@ -0,0 +1,115 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+// A minimal subset of ASN.1 DER; enough to read and write the SEQUENCEs of INTEGERs which make up PKCS #1 RSA keys.
+pub const INTEGER_TAG: u8 = 0x02;
+pub const SEQUENCE_TAG: u8 = 0x30;
+
+pub fn write_element(tag: u8, content: &[u8], buffer: &mut Vec<u8>) {
+    buffer.push(tag);
+    if content.len() < 0x80 {
+        // Short form; the length fits in the low 7 bits of a single byte.
+        buffer.push(content.len() as u8);
+    } else {
+        // Long form; the number of length bytes, with the high bit set, followed by the length in as few bytes as possible.
+        let length = content.len().to_be_bytes();
+        let leading_zeroes = length.iter().take_while(|b| **b == 0).count();
+        buffer.push(0x80 | (length.len() - leading_zeroes) as u8);
+        buffer.extend(&length[leading_zeroes..]);
+    }
+
+    buffer.extend(content);
+}
+
+pub fn write_unsigned_integer(be_bytes: &[u8], buffer: &mut Vec<u8>) {
+    // INTEGERs are two's complement with no redundant leading bytes; a zero byte is prepended if the high bit is set.
+    let leading_zeroes = be_bytes.iter().take_while(|b| **b == 0).count();
+    let be_bytes = &be_bytes[leading_zeroes..];
+    let mut content = Vec::with_capacity(be_bytes.len() + 1);
+    if be_bytes.len() == 0 || be_bytes[0] & 0x80 != 0 {
+        content.push(0);
+    }
+
+    content.extend(be_bytes);
+    write_element(INTEGER_TAG, &content, buffer);
+    content.fill(0);
+}
+
+pub fn try_read_element(bytes: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    // Returns the element's tag, its content, and any bytes following it.
+    if bytes.len() < 2 {
+        return None;
+    }
+
+    let (length, header_length) = if bytes[1] & 0x80 == 0 {
+        (bytes[1] as usize, 2)
+    } else {
+        // Long form; indefinite lengths, and lengths which aren't minimally encoded, aren't valid DER.
+        let length_bytes = (bytes[1] & 0x7F) as usize;
+        if length_bytes == 0
+            || length_bytes > size_of::<usize>()
+            || bytes.len() < 2 + length_bytes
+            || bytes[2] == 0
+        {
+            return None;
+        }
+
+        let mut length = 0usize;
+        for byte in &bytes[2..2 + length_bytes] {
+            length = (length << 8) | *byte as usize;
+        }
+
+        if length < 0x80 {
+            return None;
+        }
+
+        (length, 2 + length_bytes)
+    };
+
+    if bytes.len() - header_length < length {
+        return None;
+    }
+
+    let (content, remainder) = bytes[header_length..].split_at(length);
+    Some((bytes[0], content, remainder))
+}
+
+pub fn try_read_unsigned_integer(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    // Returns the INTEGER's big-endian magnitude, without its sign byte, and any bytes following it.
+    let (content, remainder) = match try_read_element(bytes) {
+        Some((INTEGER_TAG, content, remainder)) if content.len() > 0 => (content, remainder),
+        _ => return None,
+    };
+
+    if content[0] & 0x80 != 0 {
+        // Negative.
+        return None;
+    }
+
+    if content[0] == 0 && content.len() > 1 {
+        // A leading zero byte is only valid when it's required to keep the high bit clear.
+        if content[1] & 0x80 == 0 {
+            return None;
+        }
+
+        return Some((&content[1..], remainder));
+    }
+
+    Some((content, remainder))
+}
@@ -14,24 +14,62 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+const UNINITIALIZED: u8 = 0;
+const INITIALIZING: u8 = 1;
+const INITIALIZED: u8 = 2;
+
 pub struct GlobalRuntimeImmutable<T, FBuilder: Fn() -> T> {
     builder: FBuilder,
-    value: Option<T>,
+    state: AtomicU8,
+    value: UnsafeCell<Option<T>>,
+}
+
+// The value is only written once, by the caller which wins the state transition, before any reference to it is handed
+// out; after that it's only ever read, so it can be shared between threads like any other immutable value.
+unsafe impl<T: Send + Sync, FBuilder: Fn() -> T + Sync> Sync
+    for GlobalRuntimeImmutable<T, FBuilder>
+{
 }
 
 impl<T, FBuilder: Fn() -> T> GlobalRuntimeImmutable<T, FBuilder> {
     pub const fn from(builder: FBuilder) -> Self {
         Self {
             builder,
-            value: None,
+            state: AtomicU8::new(UNINITIALIZED),
+            value: UnsafeCell::new(None),
         }
     }
 
-    pub fn value(&mut self) -> &T {
-        if self.value.is_none() {
-            self.value = Some((self.builder)());
+    pub fn value(&self) -> &T {
+        // UEFI boot services are single threaded, but our tests are not; make sure only the first caller builds the value,
+        // and that any other callers wait for it to be built, rather than building and overwriting it themselves.
+        if self.state.load(Ordering::Acquire) != INITIALIZED {
+            if self
+                .state
+                .compare_exchange(
+                    UNINITIALIZED,
+                    INITIALIZING,
+                    Ordering::Acquire,
+                    Ordering::Acquire,
+                )
+                .is_ok()
+            {
+                // No references to the value exist until the state is INITIALIZED, so this is the only access to it.
+                unsafe { *self.value.get() = Some((self.builder)()) };
+                self.state.store(INITIALIZED, Ordering::Release);
+            } else {
+                while self.state.load(Ordering::Acquire) != INITIALIZED {
+                    core::hint::spin_loop();
+                }
+            }
         }
 
-        self.value.as_ref().unwrap()
+        // The value is never written again once it's INITIALIZED.
+        unsafe { (*self.value.get()).as_ref().unwrap() }
     }
 }
@@ -0,0 +1,46 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{Hasher, Sha512};
+use alloc::vec::Vec;
+
+// Deterministically expands a secret seed into any number of bytes, using HMAC-SHA512 in counter mode (as in NIST SP 800-108), keyed with the seed.
+// Each block is HMAC(seed, context || counter), where the counter is a 32 bit big endian integer starting at 0.
+// The context binds the output to its use (eg: the secret being split, or a protocol label), so a reused seed doesn't produce the same output for different purposes.
+// The output is only as unpredictable as the seed; it must come from a strong entropy source.
+pub fn derive_bytes(seed: &[u8], context: &[u8], length: usize) -> Vec<u8> {
+    let mut output = Vec::with_capacity(length + Sha512::HASH_SIZE);
+    let mut message = Vec::with_capacity(context.len() + 4);
+    let mut block = [0u8; Sha512::HASH_SIZE];
+    let mut hasher = Sha512::new();
+    let mut hmac = hasher.build_hmac(seed);
+
+    let mut counter = 0u32;
+    while output.len() < length {
+        message.clear();
+        message.extend(context);
+        message.extend(counter.to_be_bytes());
+        hmac.write_hmac_to(&message, &mut block);
+        output.extend(block);
+        counter += 1;
+    }
+
+    message.fill(0);
+    block.fill(0);
+    output[length..].fill(0);
+    output.truncate(length);
+    output
+}
@@ -15,11 +15,13 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 mod hmac;
+mod key_derivation;
 mod ripemd_160;
 mod sha_256;
 mod sha_512;
 
 pub use hmac::Hmac;
+pub use key_derivation::derive_bytes;
 pub use ripemd_160::RIPEMD160;
 pub use sha_256::Sha256;
 pub use sha_512::Sha512;
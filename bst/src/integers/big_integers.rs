@@ -21,22 +21,22 @@ use core::{cmp::Ordering, mem::size_of};
 #[cfg(target_pointer_width = "8")]
 pub type Digit = u8;
 #[cfg(target_pointer_width = "8")]
-type Carry = u16;
+pub(super) type Carry = u16;
 
 #[cfg(target_pointer_width = "16")]
 pub type Digit = u16;
 #[cfg(target_pointer_width = "16")]
-type Carry = u32;
+pub(super) type Carry = u32;
 
 #[cfg(target_pointer_width = "32")]
 pub type Digit = u32;
 #[cfg(target_pointer_width = "32")]
-type Carry = u64;
+pub(super) type Carry = u64;
 
 #[cfg(target_pointer_width = "64")]
 pub type Digit = u64;
 #[cfg(target_pointer_width = "64")]
-type Carry = u128;
+pub(super) type Carry = u128;
 
 pub const BITS_PER_DIGIT: usize = size_of::<Digit>() * 8;
 
@@ -333,6 +333,7 @@ impl BigUnsigned {
 }
 
 impl BigSigned {
+    #[cfg(test)]
     pub fn copy_be_bytes_from(&mut self, be_bytes: &[u8], is_negative: bool) {
         self.big_unsigned.copy_be_bytes_from(be_bytes);
         self.is_negative = is_negative;
@@ -343,10 +344,6 @@ impl BigSigned {
         self.is_negative = is_negative;
     }
 
-    pub fn set_sign(&mut self, is_negative: bool) {
-        self.is_negative = is_negative;
-    }
-
     pub fn set_equal_to(&mut self, value: &Self) {
         self.big_unsigned.set_equal_to(&value.big_unsigned);
         self.is_negative = value.is_negative;
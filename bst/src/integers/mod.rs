@@ -16,11 +16,13 @@
 
 mod big_integers;
 mod big_unsigned_calculator;
+mod montgomery;
 mod numeric_base;
 mod numeric_collector;
 
 pub use big_integers::{BigSigned, BigUnsigned, Digit, BITS_PER_DIGIT};
 pub use big_unsigned_calculator::BigUnsignedCalculator;
+pub use montgomery::MontgomeryContext;
 pub use numeric_base::{NumericBase, NumericBaseWithCharacterPredicate, NumericBases};
 pub use numeric_collector::{
     NumericCollector, NumericCollectorRoundBase, NumericCollectorRoundError,
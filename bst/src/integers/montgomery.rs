@@ -0,0 +1,195 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{big_integers::Carry, BigUnsigned, Digit, BITS_PER_DIGIT};
+use alloc::{vec, vec::Vec};
+
+// Window size for exponentiation; 2^4 precomputed powers of the base.
+const WINDOW_BITS: usize = 4;
+
+// Modular arithmetic in Montgomery form, where each value a is represented as aR mod m for R = 2^(BITS_PER_DIGIT * digits). The
+// reduction after each multiplication becomes multiplications and shifts, rather than a long division, which makes this far faster
+// than BigUnsignedCalculator::modpow for the large exponents RSA needs. The modulus must be odd. Digits are stored little-endian here,
+// unlike BigUnsigned.
+pub struct MontgomeryContext {
+    modulus: Vec<Digit>,
+    big_modulus: BigUnsigned,
+    // -m^-1 mod 2^BITS_PER_DIGIT
+    inverse: Digit,
+}
+
+impl MontgomeryContext {
+    pub fn try_from(modulus: &BigUnsigned) -> Option<Self> {
+        if modulus.is_even() || modulus.is_one() {
+            return None;
+        }
+
+        // Newton's method; each iteration doubles the number of correct low bits of m^-1, starting from 1 (as m is odd, m * 1 = 1 mod 2).
+        let low_digit = *modulus.borrow_digits().last().unwrap();
+        let mut inverse: Digit = 1;
+        let mut correct_bits = 1;
+        while correct_bits < BITS_PER_DIGIT {
+            inverse =
+                inverse.wrapping_mul((2 as Digit).wrapping_sub(low_digit.wrapping_mul(inverse)));
+            correct_bits *= 2;
+        }
+
+        Some(Self {
+            modulus: modulus.borrow_digits().iter().rev().copied().collect(),
+            big_modulus: modulus.clone(),
+            inverse: inverse.wrapping_neg(),
+        })
+    }
+
+    pub fn modpow(&self, base: &BigUnsigned, exponent: &BigUnsigned) -> BigUnsigned {
+        // Left-to-right fixed window exponentiation; for each window of the exponent, square the result once per bit, then multiply
+        // by the precomputed power of the base matching the window's value.
+        let length = self.modulus.len();
+        let mut scratch = vec![0 as Digit; length + 2];
+        let mut table = Vec::with_capacity(1 << WINDOW_BITS);
+        table.push(self.to_montgomery(&BigUnsigned::from_digits(&[1])));
+        table.push(self.to_montgomery(base));
+        for i in 2..1 << WINDOW_BITS {
+            let mut power = vec![0 as Digit; length];
+            self.multiply(&table[i - 1], &table[1], &mut power, &mut scratch);
+            table.push(power);
+        }
+
+        let mut result = table[0].clone();
+        let mut buffer = vec![0 as Digit; length];
+        let mut is_leading = true;
+        for digit in exponent.borrow_digits() {
+            for window in (0..BITS_PER_DIGIT / WINDOW_BITS).rev() {
+                let index = ((digit >> (window * WINDOW_BITS)) & ((1 << WINDOW_BITS) - 1)) as usize;
+                if is_leading {
+                    // Skip leading zero windows; squaring one is a waste of time.
+                    if index == 0 {
+                        continue;
+                    }
+
+                    result.copy_from_slice(&table[index]);
+                    is_leading = false;
+                    continue;
+                }
+
+                for _ in 0..WINDOW_BITS {
+                    self.multiply(&result, &result, &mut buffer, &mut scratch);
+                    result.copy_from_slice(&buffer);
+                }
+
+                self.multiply(&result, &table[index], &mut buffer, &mut scratch);
+                result.copy_from_slice(&buffer);
+            }
+        }
+
+        let output = self.from_montgomery(&result, &mut buffer, &mut scratch);
+        for power in table.iter_mut() {
+            power.fill(0);
+        }
+
+        result.fill(0);
+        buffer.fill(0);
+        scratch.fill(0);
+        output
+    }
+
+    fn to_montgomery(&self, value: &BigUnsigned) -> Vec<Digit> {
+        // aR mod m; shifting left by whole digits is multiplication by R, and a single long division reduces it.
+        let mut shifted = Vec::with_capacity(value.digit_count() + self.modulus.len());
+        shifted.extend(value.borrow_digits());
+        shifted.extend((0..self.modulus.len()).map(|_| 0));
+        let mut reduced = BigUnsigned::from_digits(&shifted);
+        reduced.modulo_big_unsigned(&self.big_modulus);
+        shifted.fill(0);
+
+        let mut digits = vec![0 as Digit; self.modulus.len()];
+        for (digit, reduced_digit) in digits.iter_mut().zip(reduced.borrow_digits().iter().rev()) {
+            *digit = *reduced_digit;
+        }
+
+        reduced.zero();
+        digits
+    }
+
+    fn from_montgomery(
+        &self,
+        value: &[Digit],
+        buffer: &mut [Digit],
+        scratch: &mut [Digit],
+    ) -> BigUnsigned {
+        // aR * 1 * R^-1 = a mod m.
+        let mut one = vec![0 as Digit; self.modulus.len()];
+        one[0] = 1;
+        self.multiply(value, &one, buffer, scratch);
+
+        let big_endian: Vec<Digit> = buffer.iter().rev().copied().collect();
+        BigUnsigned::from_digits(&big_endian)
+    }
+
+    fn multiply(&self, a: &[Digit], b: &[Digit], output: &mut [Digit], t: &mut [Digit]) {
+        // Coarsely integrated operand scanning; abR^-1 mod m, interleaving each row of the multiplication with a reduction step which
+        // adds a multiple of m, chosen so the lowest digit becomes zero and can be shifted away.
+        let length = self.modulus.len();
+        t.fill(0);
+        for i in 0..length {
+            let mut carry: Carry = 0;
+            for j in 0..length {
+                let sum = t[j] as Carry + (a[j] as Carry) * (b[i] as Carry) + carry;
+                t[j] = sum as Digit;
+                carry = sum >> BITS_PER_DIGIT;
+            }
+
+            let sum = t[length] as Carry + carry;
+            t[length] = sum as Digit;
+            t[length + 1] = (sum >> BITS_PER_DIGIT) as Digit;
+
+            let m = t[0].wrapping_mul(self.inverse);
+            let sum = t[0] as Carry + (m as Carry) * (self.modulus[0] as Carry);
+            let mut carry = sum >> BITS_PER_DIGIT;
+            for j in 1..length {
+                let sum = t[j] as Carry + (m as Carry) * (self.modulus[j] as Carry) + carry;
+                t[j - 1] = sum as Digit;
+                carry = sum >> BITS_PER_DIGIT;
+            }
+
+            let sum = t[length] as Carry + carry;
+            t[length - 1] = sum as Digit;
+            t[length] = t[length + 1] + (sum >> BITS_PER_DIGIT) as Digit;
+        }
+
+        // The result is less than 2m; subtract m once if it's not already less than m.
+        output.copy_from_slice(&t[..length]);
+        if t[length] != 0 || !Self::is_less_than(output, &self.modulus) {
+            let mut borrow = false;
+            for (digit, modulus_digit) in output.iter_mut().zip(self.modulus.iter()) {
+                let (difference, borrow_1) = digit.overflowing_sub(*modulus_digit);
+                let (difference, borrow_2) = difference.overflowing_sub(borrow as Digit);
+                *digit = difference;
+                borrow = borrow_1 || borrow_2;
+            }
+        }
+    }
+
+    fn is_less_than(a: &[Digit], b: &[Digit]) -> bool {
+        for (a, b) in a.iter().rev().zip(b.iter().rev()) {
+            if a != b {
+                return a < b;
+            }
+        }
+
+        false
+    }
+}
@@ -332,4 +332,7 @@ impl KeyPress {
 
 pub trait KeyboardIn {
     fn read_key(&self) -> KeyPress;
+
+    // Returns the next key press if there is one waiting, without blocking.
+    fn try_read_key(&self) -> Option<KeyPress>;
 }
@@ -26,6 +26,7 @@ mod clipboard;
 mod console_out;
 mod constants;
 mod cryptography;
+mod encoding;
 mod global_runtime_immutable;
 mod hashing;
 mod integers;
@@ -0,0 +1,221 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    prompt_for_derivation_path, prompt_for_extended_key, prompt_for_network, prompt_for_selection,
+    try_derive_child_key,
+};
+use crate::{
+    bitcoin::{addresses, psbt},
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1,
+    integers::NumericBases,
+    programs::{Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_data_input, ConsoleUiContinuePrompt, ConsoleUiKeyValue, ConsoleUiTitle,
+            ConsoleWriteable,
+        },
+        ContinuePrompt, DataInput, DataInputType,
+    },
+    String16,
+};
+use macros::s16;
+
+#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+enum AddressKeySource {
+    PublicKey,
+    ExtendedKey,
+}
+
+impl ConsoleWriteable for AddressKeySource {
+    fn write_to<T: ConsoleOut>(&self, console: &T) {
+        console.output_utf16(match self {
+            AddressKeySource::PublicKey => s16!("secp256k1 Public Key"),
+            AddressKeySource::ExtendedKey => s16!("BIP 32 Extended Key"),
+        });
+    }
+}
+
+pub struct ConsoleBitcoinAddressDerivationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleBitcoinAddressDerivationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleBitcoinAddressDerivationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Bitcoin Address Derivation")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program derives the legacy (P2PKH), nested segwit (P2SH-P2WPKH), native segwit (P2WPKH) and taproot (P2TR, BIP 86) addresses for a secp256k1 public key, or for a key derived from a BIP 32 extended key and a derivation path, relative to that key."
+            ))
+            .output_utf16_line(s16!(
+                "Extended keys are typically at the account level (e.g. m/84'/0'/0'); the first receiving address is then at the relative path 0/0."
+            ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel address derivation?");
+
+        let source = match prompt_for_selection(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!(" Key Source "),
+            &[AddressKeySource::ExtendedKey, AddressKeySource::PublicKey],
+        ) {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let (public_key, uncompressed_public_key, network) = match source {
+            AddressKeySource::PublicKey => {
+                let (public_key, uncompressed_public_key) = loop {
+                    let bytes = match prompt_for_data_input(
+                        Some(NumericBases::Hexadecimal.into()),
+                        &[DataInputType::Bytes],
+                        &self.system_services,
+                        CANCEL_PROMPT,
+                        s16!("Public Key"),
+                    ) {
+                        DataInput::Bytes(b) => b,
+                        _ => return ProgramExitResult::UserCancelled,
+                    };
+
+                    // x-only keys can't be used for the legacy and segwit v0 addresses.
+                    if bytes.len() == 33 || bytes.len() == 65 {
+                        if let Some(p) = secp256k1::try_decode_public_key(&bytes) {
+                            break (
+                                p.try_serialize_compressed().unwrap(),
+                                if bytes.len() == 65 { Some(bytes) } else { None },
+                            );
+                        }
+                    }
+
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.line_start().new_line().output_utf16_line(s16!(
+                            "Expected a valid 33 byte compressed, or 65 byte uncompressed, secp256k1 public key."
+                        ))
+                    });
+                };
+
+                let network = match prompt_for_network(&self.system_services, CANCEL_PROMPT) {
+                    Some(n) => n,
+                    None => return ProgramExitResult::UserCancelled,
+                };
+
+                (public_key, uncompressed_public_key, network)
+            }
+            AddressKeySource::ExtendedKey => {
+                let parent_key = match prompt_for_extended_key(
+                    &self.system_services,
+                    CANCEL_PROMPT,
+                    s16!("Extended Key"),
+                ) {
+                    Some(k) => k,
+                    None => return ProgramExitResult::UserCancelled,
+                };
+
+                // An empty derivation path uses the extended key itself, so its key material must be valid.
+                if psbt::try_get_public_key(&parent_key).is_none() {
+                    parent_key.zero();
+                    return s16!("The extended key's key material is invalid.").to_program_error();
+                }
+
+                let derivation_path =
+                    match prompt_for_derivation_path(&self.system_services, CANCEL_PROMPT) {
+                        Some(p) => p,
+                        None => {
+                            parent_key.zero();
+                            return ProgramExitResult::UserCancelled;
+                        }
+                    };
+
+                let key_version = parent_key.try_get_key_version().unwrap();
+                let child_key =
+                    match try_derive_child_key(&self.system_services, parent_key, &derivation_path)
+                    {
+                        Ok(k) => k,
+                        Err(e) => return e.to_program_error(),
+                    };
+
+                let public_key = psbt::try_get_public_key(&child_key);
+                child_key.zero();
+                match public_key {
+                    Some(k) => (k, None, key_version.key_network()),
+                    None => {
+                        return s16!("The extended key's key material is invalid.")
+                            .to_program_error()
+                    }
+                }
+            }
+        };
+
+        let mut x_only_public_key = [0u8; 32];
+        x_only_public_key.copy_from_slice(&public_key[1..]);
+
+        console.line_start().new_line();
+        ConsoleUiKeyValue::from(s16!("Network"), network.into()).write_to(&console);
+        if let Some(k) = &uncompressed_public_key {
+            ConsoleUiKeyValue::from(
+                s16!("P2PKH (Uncompressed)"),
+                String16::from(&addresses::p2pkh(k, network)),
+            )
+            .write_to(&console);
+        }
+
+        ConsoleUiKeyValue::from(
+            s16!("P2PKH"),
+            String16::from(&addresses::p2pkh(&public_key, network)),
+        )
+        .write_to(&console);
+        ConsoleUiKeyValue::from(
+            s16!("P2SH-P2WPKH"),
+            String16::from(&addresses::p2sh_p2wpkh(&public_key, network)),
+        )
+        .write_to(&console);
+        ConsoleUiKeyValue::from(
+            s16!("P2WPKH"),
+            String16::from(&addresses::p2wpkh(&public_key, network)),
+        )
+        .write_to(&console);
+        match addresses::p2tr(&x_only_public_key, network) {
+            Some(a) => {
+                ConsoleUiKeyValue::from(s16!("P2TR"), String16::from(&a)).write_to(&console);
+            }
+            None => {
+                console.in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16_line(s16!("The key's BIP 86 taproot tweak is invalid."))
+                });
+            }
+        }
+
+        ConsoleUiContinuePrompt::from(&self.system_services).prompt_for_continue();
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,447 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_extended_key, try_derive_child_key};
+use crate::{
+    bitcoin::{
+        addresses,
+        hd_wallets::{
+            try_parse_derivation_path_template, Bip32CkdContext, Bip32DerivationPathPoint,
+            Bip32DerivationPathTemplateSegment, Bip32KeyNetwork, Bip32KeyType,
+            Bip32SerializedExtendedKey, HARDENED_CHILD_DERIVATION_THRESHOLD, MAX_DERIVATION_POINT,
+        },
+        try_base_58_decode_with_checksum, Base58CheckPayloadType,
+    },
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1,
+    integers::{BigUnsigned, NumericBase, NumericBases},
+    keyboard_in::{BehaviourKey, Key, KeyboardIn},
+    programs::{Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_data_input, prompt_for_u32, ConsoleUiConfirmationPrompt,
+            ConsoleUiContinuePrompt, ConsoleUiKeyValue, ConsoleUiLabel, ConsoleUiTitle,
+            ConsoleWriteable,
+        },
+        ConfirmationPrompt, ContinuePrompt, DataInput, DataInputType, Point,
+    },
+    String16,
+};
+use alloc::vec::Vec;
+use macros::s16;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum SearchAddressType {
+    P2pkh,
+    // P2SH addresses are assumed to be nested segwit; other scripts can't be derived from a single key.
+    P2shP2wpkh,
+    P2wpkh,
+    P2tr,
+}
+
+impl SearchAddressType {
+    fn try_identify(address: &[u16]) -> Option<(Self, Bip32KeyNetwork, Vec<u16>)> {
+        // Returns the address' type, its network, and its canonical form for comparison with derived addresses.
+        if let Some((network, witness_version, witness_program)) =
+            addresses::try_decode_segwit(address)
+        {
+            let address_type = match (witness_version, witness_program.len()) {
+                (0, 20) => Self::P2wpkh,
+                (1, 32) => Self::P2tr,
+                _ => return None,
+            };
+
+            // Segwit addresses are encoded in lower case.
+            let address = address
+                .iter()
+                .map(|c| match c {
+                    0x41..=0x5A => c + 0x20,
+                    c => *c,
+                })
+                .collect();
+            return Some((address_type, network, address));
+        }
+
+        match try_base_58_decode_with_checksum(address) {
+            Some(p) => match Base58CheckPayloadType::from_payload(&p) {
+                Base58CheckPayloadType::P2pkhAddress(n) => Some((Self::P2pkh, n, address.into())),
+                Base58CheckPayloadType::P2shAddress(n) => {
+                    Some((Self::P2shP2wpkh, n, address.into()))
+                }
+                _ => None,
+            },
+            None => None,
+        }
+    }
+
+    fn address_for(&self, public_key: &[u8; 33], network: Bip32KeyNetwork) -> Option<Vec<u16>> {
+        match self {
+            Self::P2pkh => Some(addresses::p2pkh(public_key, network)),
+            Self::P2shP2wpkh => Some(addresses::p2sh_p2wpkh(public_key, network)),
+            Self::P2wpkh => Some(addresses::p2wpkh(public_key, network)),
+            Self::P2tr => {
+                let mut x_only_public_key = [0u8; 32];
+                x_only_public_key.copy_from_slice(&public_key[1..]);
+                addresses::p2tr(&x_only_public_key, network)
+            }
+        }
+    }
+}
+
+pub struct ConsoleBitcoinAddressSearchProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleBitcoinAddressSearchProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleBitcoinAddressSearchProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Address Ownership Search")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program searches the keys derived from a BIP 32 extended key for one whose address matches a target address, proving the address belongs to the wallet. Legacy (P2PKH), nested segwit (P2SH-P2WPKH), native segwit (P2WPKH) and taproot (P2TR, BIP 86) addresses are supported."
+            ))
+            .output_utf16_line(s16!(
+                "The derivation path template is relative to the extended key; braces list alternative points, and the final '*' is replaced with each index in the search range. For example, from a master key, m/84'/0'/0'/{0,1}/* searches the first native segwit account's receive and change addresses."
+            ))
+            .output_utf16_line(s16!("Press ESC during the search to cancel it."));
+        const CANCEL_PROMPT: String16 = s16!("Cancel address ownership search?");
+
+        let parent_key = match prompt_for_extended_key(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Extended Key"),
+        ) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        // The matching path is relative to the extended key, so we keep its position for the result.
+        let key_version = parent_key.try_get_key_version().unwrap();
+        let key_depth = parent_key.depth();
+        let mut key_parent_fingerprint = [0u8; 4];
+        key_parent_fingerprint.copy_from_slice(parent_key.parent_fingerprint());
+        let template = loop {
+            match prompt_for_data_input(
+                None,
+                &[DataInputType::Text],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Derivation Path Template (e.g. m/84'/0'/0'/{0,1}/*)"),
+            ) {
+                DataInput::Text(t) => {
+                    let error = match try_parse_derivation_path_template(&t) {
+                        Some(t) => {
+                            let has_hardened_points = t.iter().any(|s| match s {
+                                Bip32DerivationPathTemplateSegment::Points(p) => {
+                                    p.iter().any(|p| p.is_for_hardened_key())
+                                }
+                                Bip32DerivationPathTemplateSegment::Wildcard(h) => *h,
+                            });
+
+                            if has_hardened_points && key_version.key_type() == Bip32KeyType::Public
+                            {
+                                s16!("Hardened keys cannot be derived from an extended public key.")
+                            } else {
+                                break t;
+                            }
+                        }
+                        None => s16!(
+                            "Templates are '/' separated points, or {} enclosed comma separated points, ending with a single '*'. Hardened points are suffixed by '."
+                        ),
+                    };
+
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.line_start().new_line().output_utf16_line(error)
+                    });
+                }
+                _ => {
+                    parent_key.zero();
+                    return ProgramExitResult::UserCancelled;
+                }
+            }
+        };
+
+        let start_index = match prompt_for_u32(
+            |i| match i {
+                i if i > MAX_DERIVATION_POINT => Some(s16!("Indexes must be below 2^31.")),
+                _ => None,
+            },
+            s16!("First Index"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(i) => i,
+            None => {
+                parent_key.zero();
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let end_index = match prompt_for_u32(
+            |i| match i {
+                i if i > MAX_DERIVATION_POINT => Some(s16!("Indexes must be below 2^31.")),
+                i if i < start_index => {
+                    Some(s16!("The last index cannot be less than the first index."))
+                }
+                _ => None,
+            },
+            s16!("Last Index"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(i) => i,
+            None => {
+                parent_key.zero();
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let (address_type, target_address) = loop {
+            match prompt_for_data_input(
+                None,
+                &[DataInputType::Text],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Target Address"),
+            ) {
+                DataInput::Text(t) => {
+                    let error = match SearchAddressType::try_identify(&t) {
+                        Some((t, n, a)) if n == key_version.key_network() => break (t, a),
+                        Some(_) => {
+                            s16!("The address is for a different network to the extended key.")
+                        }
+                        None => {
+                            s16!("The address is not a valid P2PKH, P2SH, P2WPKH or P2TR address.")
+                        }
+                    };
+
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.line_start().new_line().output_utf16_line(error)
+                    });
+                }
+                _ => {
+                    parent_key.zero();
+                    return ProgramExitResult::UserCancelled;
+                }
+            }
+        };
+
+        // Derive the fixed prefix of the template once.
+        let prefix_length = template
+            .iter()
+            .take_while(|s| match s {
+                Bip32DerivationPathTemplateSegment::Points(p) => p.len() == 1,
+                Bip32DerivationPathTemplateSegment::Wildcard(_) => false,
+            })
+            .count();
+        let prefix: Vec<Bip32DerivationPathPoint> = template[..prefix_length]
+            .iter()
+            .map(|s| match s {
+                Bip32DerivationPathTemplateSegment::Points(p) => p[0],
+                Bip32DerivationPathTemplateSegment::Wildcard(_) => unreachable!(),
+            })
+            .collect();
+
+        let prefix_key = match try_derive_child_key(&self.system_services, parent_key, &prefix) {
+            Ok(k) => k,
+            Err(e) => return e.to_program_error(),
+        };
+
+        // Build every combination of the alternative points between the prefix and the wildcard.
+        let mut branches: Vec<Vec<Bip32DerivationPathPoint>> = Vec::from([Vec::new()]);
+        let mut is_hardened_wildcard = false;
+        for segment in &template[prefix_length..] {
+            match segment {
+                Bip32DerivationPathTemplateSegment::Points(points) => {
+                    branches = branches
+                        .iter()
+                        .flat_map(|b| {
+                            points.iter().map(|p| {
+                                let mut branch = b.clone();
+                                branch.push(*p);
+                                branch
+                            })
+                        })
+                        .collect();
+                }
+                Bip32DerivationPathTemplateSegment::Wildcard(h) => is_hardened_wildcard = *h,
+            }
+        }
+
+        let mut context = Bip32CkdContext::new();
+        let mut branch_keys: Vec<Bip32SerializedExtendedKey> = Vec::with_capacity(branches.len());
+        for branch in &branches {
+            match context.derive(|_| {}, prefix_key, branch) {
+                Ok(k) => branch_keys.push(k),
+                Err(e) => {
+                    context.reset();
+                    prefix_key.zero();
+                    branch_keys.into_iter().for_each(|k| k.zero());
+                    return e.to_program_error();
+                }
+            }
+        }
+
+        prefix_key.zero();
+        console.line_start().new_line();
+        let progress_row = console.cursor().position().y();
+        let end_index_string = NumericBase::BASE_10.build_string_from_big_unsigned(
+            &mut BigUnsigned::from_be_bytes(&end_index.to_be_bytes()),
+            true,
+            1,
+        );
+        let keyboard_in = self.system_services.get_keyboard_in();
+        let mut result = None;
+        'search: for index in start_index..=end_index {
+            console
+                .set_cursor_position(Point::from(0, progress_row))
+                .in_colours(constants::SUCCESS_COLOURS, |c| {
+                    c.output_utf16(s16!("Searching index "))
+                        .output_utf16(String16::from(
+                            &NumericBase::BASE_10.build_string_from_big_unsigned(
+                                &mut BigUnsigned::from_be_bytes(&index.to_be_bytes()),
+                                true,
+                                1,
+                            ),
+                        ))
+                        .output_utf16(s16!(" of "))
+                        .output_utf16(String16::from(&end_index_string))
+                        .output_utf16(s16!("..."))
+                });
+
+            for (branch, branch_key) in branches.iter().zip(branch_keys.iter()) {
+                let point = Bip32DerivationPathPoint::from(if is_hardened_wildcard {
+                    index | HARDENED_CHILD_DERIVATION_THRESHOLD
+                } else {
+                    index
+                });
+
+                let child_key = match context.derive(|_| {}, *branch_key, &[point]) {
+                    Ok(k) => k,
+                    // Roughly 1 in 2^127 indexes are invalid; they're skipped by wallets, so we can skip them too.
+                    Err(_) => continue,
+                };
+
+                let public_key = match key_version.key_type() {
+                    // Private key material is prefixed with a zero byte.
+                    Bip32KeyType::Private => {
+                        secp256k1::try_derive_serialized_public_key(&child_key.key_material()[1..])
+                            .unwrap()
+                    }
+                    Bip32KeyType::Public => {
+                        let mut public_key = [0u8; 33];
+                        public_key.copy_from_slice(child_key.key_material());
+                        public_key
+                    }
+                };
+
+                child_key.zero();
+                match address_type.address_for(&public_key, key_version.key_network()) {
+                    Some(a) if a == target_address => {
+                        let mut path = prefix.clone();
+                        path.extend(branch);
+                        path.push(point);
+                        result = Some(path);
+                        break 'search;
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(k) = keyboard_in.try_read_key() {
+                if k.key() == Key::Behaviour(BehaviourKey::Escape)
+                    && ConsoleUiConfirmationPrompt::from(&self.system_services)
+                        .prompt_for_confirmation(CANCEL_PROMPT)
+                {
+                    context.reset();
+                    branch_keys.into_iter().for_each(|k| k.zero());
+                    return ProgramExitResult::UserCancelled;
+                }
+            }
+        }
+
+        context.reset();
+        branch_keys.into_iter().for_each(|k| k.zero());
+
+        console.line_start().new_line();
+        match result {
+            Some(path) => {
+                console.in_colours(constants::SUCCESS_COLOURS, |c| {
+                    c.output_utf16_line(s16!("The address belongs to the extended key."))
+                });
+                ConsoleUiKeyValue::from(s16!("Address"), String16::from(&target_address))
+                    .write_to(&console);
+                ConsoleUiKeyValue::from(
+                    s16!("Extended Key Depth"),
+                    String16::from(
+                        &NumericBase::BASE_10.build_string_from_bytes(&[key_depth], true),
+                    ),
+                )
+                .write_to(&console);
+                ConsoleUiKeyValue::from(
+                    s16!("Extended Key Parent Fingerprint"),
+                    String16::from(
+                        &NumericBase::BASE_16
+                            .build_string_from_bytes(&key_parent_fingerprint, true),
+                    ),
+                )
+                .write_to(&console);
+
+                // Paths from a key other than a master key are relative to that key, written as 'p/'.
+                ConsoleUiLabel::from(s16!("Derivation Path")).write_to(&console);
+                console.output_utf16(if key_depth == 0 {
+                    s16!("m/")
+                } else {
+                    s16!("p/")
+                });
+
+                for point in &path {
+                    point.write_to(&console);
+                }
+
+                console.line_start().new_line();
+            }
+            None => {
+                console.in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16_line(s16!(
+                        "The address was not found in the searched range; it may not belong to the extended key, or may be outside the range or template."
+                    ))
+                });
+            }
+        }
+
+        ConsoleUiContinuePrompt::from(&self.system_services).prompt_for_continue();
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,182 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    bitcoin::{
+        hd_wallets::{Bip32KeyType, Bip32SerializedExtendedKey},
+        try_base_58_decode_with_checksum, Base58CheckPayloadType,
+    },
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    integers::NumericBase,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_clipboard_write, prompt_for_data_input, ConsoleUiKeyValue, ConsoleUiTitle,
+            ConsoleWriteable,
+        },
+        DataInput, DataInputType,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleBase58CheckDecoderProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleBase58CheckDecoderProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleBase58CheckDecoderProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Base58Check Decoder")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program decodes a Base58Check string, validates its double SHA256 checksum, and identifies its payload from its version bytes; legacy (P2PKH) and script hash (P2SH) addresses, WIF private keys, and BIP 32 extended keys are recognised."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "WIF private keys and extended private keys are secrets; their decoded bytes are equally sensitive."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel Base58Check decoding?");
+
+        let payload = loop {
+            let input = match prompt_for_data_input(
+                None,
+                &[DataInputType::Text],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Base58Check String"),
+            ) {
+                DataInput::Text(t) => t,
+                _ => return ProgramExitResult::UserCancelled,
+            };
+
+            match try_base_58_decode_with_checksum(&input) {
+                Some(p) => break p,
+                None => {
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.line_start().new_line().output_utf16_line(
+                            if input
+                                .iter()
+                                .all(|c| NumericBase::BASE_58.value_from(*c).is_some())
+                            {
+                                s16!("The string failed checksumming.")
+                            } else {
+                                s16!("The string contains characters outside of the base-58 alphabet.")
+                            },
+                        )
+                    });
+                }
+            }
+        };
+
+        console.line_start().new_line();
+        match Base58CheckPayloadType::from_payload(&payload) {
+            Base58CheckPayloadType::P2pkhAddress(n) => {
+                ConsoleUiKeyValue::from(s16!("Type"), s16!("P2PKH Address")).write_to(&console);
+                ConsoleUiKeyValue::from(s16!("Network"), n.into()).write_to(&console);
+                self.write_hex(s16!("Public Key Hash"), &payload[1..]);
+            }
+            Base58CheckPayloadType::P2shAddress(n) => {
+                ConsoleUiKeyValue::from(s16!("Type"), s16!("P2SH Address")).write_to(&console);
+                ConsoleUiKeyValue::from(s16!("Network"), n.into()).write_to(&console);
+                self.write_hex(s16!("Script Hash"), &payload[1..]);
+            }
+            Base58CheckPayloadType::WifPrivateKey(n, is_compressed) => {
+                ConsoleUiKeyValue::from(s16!("Type"), s16!("WIF Private Key")).write_to(&console);
+                ConsoleUiKeyValue::from(s16!("Network"), n.into()).write_to(&console);
+                ConsoleUiKeyValue::from(
+                    s16!("Public Key"),
+                    if is_compressed {
+                        s16!("Compressed")
+                    } else {
+                        s16!("Uncompressed")
+                    },
+                )
+                .write_to(&console);
+                self.write_hex(s16!("Private Key"), &payload[1..33]);
+            }
+            Base58CheckPayloadType::ExtendedKey(v) => {
+                let key = Bip32SerializedExtendedKey::from_bytes(&payload).unwrap();
+                ConsoleUiKeyValue::from(
+                    s16!("Type"),
+                    match v.key_type() {
+                        Bip32KeyType::Private => s16!("BIP 32 Extended Private Key"),
+                        Bip32KeyType::Public => s16!("BIP 32 Extended Public Key"),
+                    },
+                )
+                .write_to(&console);
+                ConsoleUiKeyValue::from(s16!("Network"), v.key_network().into()).write_to(&console);
+                ConsoleUiKeyValue::from(
+                    s16!("Depth"),
+                    String16::from(
+                        &NumericBase::BASE_10.build_string_from_bytes(&[key.depth()], true),
+                    ),
+                )
+                .write_to(&console);
+                self.write_hex(s16!("Parent Fingerprint"), key.parent_fingerprint());
+                ConsoleUiKeyValue::from(
+                    s16!("Child Number"),
+                    String16::from(
+                        &NumericBase::BASE_10.build_string_from_bytes(key.child_number(), true),
+                    ),
+                )
+                .write_to(&console);
+                self.write_hex(s16!("Chain Code"), key.chain_code());
+                self.write_hex(s16!("Key Material"), key.key_material());
+                key.zero();
+            }
+            Base58CheckPayloadType::Unknown => {
+                ConsoleUiKeyValue::from(s16!("Type"), s16!("Unknown")).write_to(&console);
+            }
+        }
+
+        const LABEL: String16 = s16!("Base58Check Payload");
+        write_bytes(&self.system_services, LABEL, &payload);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(LABEL, payload.into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
+
+impl<TSystemServices: SystemServices> ConsoleBase58CheckDecoderProgram<TSystemServices> {
+    fn write_hex(&self, key: String16, bytes: &[u8]) {
+        let mut string = NumericBase::BASE_16.build_string_from_bytes(bytes, true);
+        ConsoleUiKeyValue::from(key, String16::from(&string))
+            .write_to(&self.system_services.get_console_out());
+        string.fill(0);
+    }
+}
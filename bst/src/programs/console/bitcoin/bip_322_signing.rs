@@ -0,0 +1,200 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    prompt_for_derivation_path, prompt_for_extended_key, prompt_for_selection, try_derive_child_key,
+};
+use crate::{
+    bitcoin::{
+        addresses,
+        bip_322::{self, Bip322AddressType, Bip322SignatureFormat},
+        hd_wallets::Bip32KeyType,
+        psbt,
+    },
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::{schnorr, try_derive_serialized_public_key},
+    encoding::base64,
+    programs::{console::write_string_program_output, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_bytes_from_any_data_type, prompt_for_clipboard_write, ConsoleUiTitle,
+        ConsoleWriteable,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleBip322SigningProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleBip322SigningProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for ConsoleBip322SigningProgram<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("BIP 322 Message Signing")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program signs a message for a native segwit (P2WPKH) or taproot (P2TR, BIP 86) address with BIP 322 generic signed messages. The address' key is derived from a BIP 32 extended private key and a derivation path, relative to that key; an empty path signs with the extended key itself."
+            ))
+            .output_utf16_line(s16!(
+                "Simple signatures are just the witness, and are what most wallets expect; full signatures are the whole virtual transaction."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "Text is signed as UTF8. Pressing ENTER inserts CR LF, but other wallets typically use LF alone; use CTRL + ENTER for a LF newline."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel BIP 322 message signing?");
+
+        let parent_key = match prompt_for_extended_key(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Extended Private Key"),
+        ) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let key_version = parent_key.try_get_key_version().unwrap();
+        if key_version.key_type() != Bip32KeyType::Private {
+            parent_key.zero();
+            return s16!("Signing requires an extended private key.").to_program_error();
+        }
+
+        // An empty derivation path signs with the extended key itself, so its key material must be valid.
+        if psbt::try_get_public_key(&parent_key).is_none() {
+            parent_key.zero();
+            return s16!("The extended key's key material is invalid.").to_program_error();
+        }
+
+        let derivation_path = match prompt_for_derivation_path(&self.system_services, CANCEL_PROMPT)
+        {
+            Some(p) => p,
+            None => {
+                parent_key.zero();
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let child_key =
+            match try_derive_child_key(&self.system_services, parent_key, &derivation_path) {
+                Ok(k) => k,
+                Err(e) => return e.to_program_error(),
+            };
+
+        // Private key material is prefixed with a zero byte.
+        let mut private_key = [0u8; 32];
+        private_key.copy_from_slice(&child_key.key_material()[1..]);
+        child_key.zero();
+
+        let address_type = match prompt_for_selection(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!(" Address Type "),
+            &[Bip322AddressType::P2wpkh, Bip322AddressType::P2tr],
+        ) {
+            Some(t) => t,
+            None => {
+                private_key.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => {
+                private_key.fill(0);
+                return e;
+            }
+        };
+
+        let format = match prompt_for_selection(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!(" Signature Format "),
+            &[Bip322SignatureFormat::Simple, Bip322SignatureFormat::Full],
+        ) {
+            Some(f) => f,
+            None => {
+                private_key.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let network = key_version.key_network();
+        let address = match address_type {
+            Bip322AddressType::P2wpkh => match try_derive_serialized_public_key(&private_key) {
+                Some(k) => Some(addresses::p2wpkh(&k, network)),
+                None => None,
+            },
+            Bip322AddressType::P2tr => match schnorr::try_derive_x_only_public_key(&private_key) {
+                Some(k) => addresses::p2tr(&k, network),
+                None => None,
+            },
+        };
+
+        let address = match address {
+            Some(a) => a,
+            None => {
+                private_key.fill(0);
+                return s16!("The extended key's key material is invalid.").to_program_error();
+            }
+        };
+
+        let signature = bip_322::try_sign(&private_key, address_type, &message, format);
+        private_key.fill(0);
+
+        let signature = match signature {
+            Ok(s) => s,
+            Err(e) => return e.to_program_error(),
+        };
+
+        console.line_start().new_line();
+        write_string_program_output(
+            &self.system_services,
+            s16!("Address"),
+            String16::from(&address),
+        );
+
+        const LABEL: String16 = s16!("Signature (Base-64)");
+        let signature = base64::encode(&signature);
+        write_string_program_output(&self.system_services, LABEL, String16::from(&signature));
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::String16(LABEL, signature.into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,129 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    bitcoin::bip_322,
+    console_out::ConsoleOut,
+    constants,
+    encoding::base64,
+    programs::{Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_bytes_from_any_data_type, prompt_for_data_input, ConsoleUiContinuePrompt,
+            ConsoleUiTitle, ConsoleWriteable,
+        },
+        ContinuePrompt, DataInput, DataInputType,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleBip322VerificationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleBip322VerificationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleBip322VerificationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("BIP 322 Message Verification")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program verifies a base-64, simple or full BIP 322 signature for a native segwit (P2WPKH) address, or a taproot (P2TR) address' key path."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "Pressing ENTER inserts CR LF, but other wallets typically use LF alone; use CTRL + ENTER for a LF newline."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel BIP 322 message verification?");
+
+        let address = match prompt_for_data_input(
+            None,
+            &[DataInputType::Text],
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Address"),
+        ) {
+            DataInput::Text(a) => a,
+            _ => return ProgramExitResult::UserCancelled,
+        };
+
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => return e,
+        };
+
+        let signature = loop {
+            match prompt_for_data_input(
+                None,
+                &[DataInputType::Text],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Signature (Base-64)"),
+            ) {
+                DataInput::Text(t) => match base64::decode(&t) {
+                    Some(s) => break s,
+                    None => {
+                        console.in_colours(constants::ERROR_COLOURS, |c| {
+                            c.line_start()
+                                .new_line()
+                                .output_utf16_line(s16!("The signature is not valid base-64."))
+                        });
+                    }
+                },
+                _ => return ProgramExitResult::UserCancelled,
+            }
+        };
+
+        let signature_is_valid = match bip_322::verify(&address, &message, &signature) {
+            Ok(v) => v,
+            Err(e) => return e.to_program_error(),
+        };
+
+        console.line_start().new_line();
+        if signature_is_valid {
+            console.in_colours(constants::SUCCESS_COLOURS, |c| {
+                c.output_utf16_line(s16!("Signature is valid."))
+            });
+        } else {
+            console.in_colours(constants::ERROR_COLOURS, |c| {
+                c.output_utf16_line(s16!("Signature is NOT valid."))
+            });
+        }
+
+        ConsoleUiContinuePrompt::from(&self.system_services).prompt_for_continue();
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,164 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_network, prompt_for_selection};
+use crate::{
+    bitcoin::signed_messages::{self, SignedMessageAddressType},
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1,
+    encoding::base64,
+    programs::{console::write_string_program_output, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_bytes_from_any_data_type, prompt_for_clipboard_write,
+        prompt_for_fixed_length_bytes, ConsoleUiTitle, ConsoleWriteable,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleBitcoinMessageSigningProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleBitcoinMessageSigningProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleBitcoinMessageSigningProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Bitcoin Message Signing")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program signs a message with a secp256k1 private key, in the 'Bitcoin Signed Message' format used by Bitcoin Core, Electrum and hardware wallets (BIP 137). The signature is output as base-64, alongside the address it proves ownership of."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "Text is signed as UTF8. Pressing ENTER inserts CR LF, but other wallets typically use LF alone; use CTRL + ENTER for a LF newline."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel message signing?");
+
+        let mut private_key = match prompt_for_fixed_length_bytes::<32, TSystemServices>(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Private Key"),
+        ) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let public_key = match secp256k1::try_derive_serialized_public_key(&private_key) {
+            Some(k) => k,
+            None => {
+                private_key.fill(0);
+                return s16!("The private key is not a valid secp256k1 private key.")
+                    .to_program_error();
+            }
+        };
+
+        let address_type = match prompt_for_selection(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!(" Address Type "),
+            &[
+                SignedMessageAddressType::P2pkh,
+                SignedMessageAddressType::P2shP2wpkh,
+                SignedMessageAddressType::P2wpkh,
+                SignedMessageAddressType::P2pkhUncompressed,
+            ],
+        ) {
+            Some(t) => t,
+            None => {
+                private_key.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let network = match prompt_for_network(&self.system_services, CANCEL_PROMPT) {
+            Some(n) => n,
+            None => {
+                private_key.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => {
+                private_key.fill(0);
+                return e;
+            }
+        };
+
+        let signature = signed_messages::try_sign(&private_key, &message, address_type);
+        private_key.fill(0);
+
+        let signature = match signature {
+            Some(s) => s,
+            None => {
+                return s16!("Failed to sign the message.").to_program_error();
+            }
+        };
+
+        // The address type's public key format; uncompressed keys are only used with legacy addresses.
+        let address = match address_type {
+            SignedMessageAddressType::P2pkhUncompressed => {
+                let uncompressed_key = secp256k1::try_decode_public_key(&public_key)
+                    .unwrap()
+                    .try_serialize_uncompressed()
+                    .unwrap();
+                address_type.address(&uncompressed_key, network)
+            }
+            _ => address_type.address(&public_key, network),
+        }
+        .unwrap();
+
+        console.line_start().new_line();
+        write_string_program_output(
+            &self.system_services,
+            s16!("Address"),
+            String16::from(&address),
+        );
+
+        const LABEL: String16 = s16!("Signature (Base-64)");
+        let signature = base64::encode(&signature);
+        write_string_program_output(&self.system_services, LABEL, String16::from(&signature));
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::String16(LABEL, signature.into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,179 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_selection;
+use crate::{
+    bitcoin::signed_messages,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1,
+    encoding::base64,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_bytes_from_any_data_type, prompt_for_data_input, ConsoleUiContinuePrompt,
+            ConsoleUiTitle, ConsoleWriteable,
+        },
+        ContinuePrompt, DataInput, DataInputType,
+    },
+    String16,
+};
+use macros::s16;
+
+#[derive(Clone, Copy)]
+enum ExpectedSigner {
+    Address,
+    PublicKey,
+}
+
+impl ConsoleWriteable for ExpectedSigner {
+    fn write_to<T: ConsoleOut>(&self, console: &T) {
+        console.output_utf16(match self {
+            ExpectedSigner::Address => s16!("Address"),
+            ExpectedSigner::PublicKey => s16!("Public Key"),
+        });
+    }
+}
+
+pub struct ConsoleBitcoinMessageVerificationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleBitcoinMessageVerificationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleBitcoinMessageVerificationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Bitcoin Message Verification")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program verifies a base-64 'Bitcoin Signed Message' signature (BIP 137) by recovering the signer's public key from it, then checking that key against an expected address or public key."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "Any signature recovers some public key; the signature only proves anything once the key is checked. Pressing ENTER inserts CR LF, but other wallets typically use LF alone; use CTRL + ENTER for a LF newline."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel message verification?");
+
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => return e,
+        };
+
+        let (public_key, address_type) = loop {
+            let signature = match prompt_for_data_input(
+                None,
+                &[DataInputType::Text],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Signature (Base-64)"),
+            ) {
+                DataInput::Text(t) => t,
+                _ => return ProgramExitResult::UserCancelled,
+            };
+
+            let error = match base64::decode(&signature) {
+                Some(s) => match signed_messages::try_recover_public_key(&message, &s) {
+                    Ok(r) => break r,
+                    Err(e) => e,
+                },
+                None => s16!("The signature is not valid base-64."),
+            };
+
+            console.in_colours(constants::ERROR_COLOURS, |c| {
+                c.line_start().new_line().output_utf16_line(error)
+            });
+        };
+
+        console.line_start().new_line();
+        write_bytes(
+            &self.system_services,
+            s16!("Recovered Public Key"),
+            &public_key,
+        );
+
+        let expected_signer = match prompt_for_selection(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!(" Expected Signer "),
+            &[ExpectedSigner::Address, ExpectedSigner::PublicKey],
+        ) {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let signer_matches = match expected_signer {
+            ExpectedSigner::Address => match prompt_for_data_input(
+                None,
+                &[DataInputType::Text],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Address"),
+            ) {
+                DataInput::Text(a) => {
+                    signed_messages::address_matches_signer(&a, &public_key, address_type)
+                }
+                _ => return ProgramExitResult::UserCancelled,
+            },
+            ExpectedSigner::PublicKey => match prompt_for_data_input(
+                None,
+                &[DataInputType::Bytes],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Public Key"),
+            ) {
+                // Compare the points, rather than the bytes, so either key format can be entered.
+                DataInput::Bytes(k) => match secp256k1::try_decode_public_key(&k) {
+                    Some(p) => p.equals(&secp256k1::try_decode_public_key(&public_key).unwrap()),
+                    None => false,
+                },
+                _ => return ProgramExitResult::UserCancelled,
+            },
+        };
+
+        console.line_start().new_line();
+        if signer_matches {
+            console.in_colours(constants::SUCCESS_COLOURS, |c| {
+                c.output_utf16_line(s16!("The message was signed by the expected signer."))
+            });
+        } else {
+            console.in_colours(constants::ERROR_COLOURS, |c| {
+                c.output_utf16_line(s16!("The message was NOT signed by the expected signer."))
+            });
+        }
+
+        ConsoleUiContinuePrompt::from(&self.system_services).prompt_for_continue();
+        ProgramExitResult::Success
+    }
+}
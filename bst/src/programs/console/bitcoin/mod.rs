@@ -0,0 +1,587 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod address_derivation;
+mod address_search;
+mod base_58_check_decoder;
+mod bip_322_signing;
+mod bip_322_verification;
+mod message_signing;
+mod message_verification;
+mod psbt_finalizer;
+mod psbt_signing;
+mod psbt_viewer;
+mod signature_hash_calculator;
+mod transaction_viewer;
+mod wif_export;
+
+use crate::{
+    bitcoin::{
+        addresses,
+        bip_322::{Bip322AddressType, Bip322SignatureFormat},
+        hd_wallets::{
+            try_parse_derivation_path, Bip32CkdContext, Bip32DerivationPathPoint, Bip32KeyNetwork,
+            Bip32SerializedExtendedKey, MAX_DERIVATION_POINT,
+        },
+        psbt::{Psbt, PsbtBip32Derivation},
+        signature_hashes::SighashType,
+        signed_messages::SignedMessageAddressType,
+        transactions::{Transaction, SATOSHIS_PER_BITCOIN},
+        validate_checksum_in,
+    },
+    console_out::ConsoleOut,
+    constants,
+    encoding::base64,
+    integers::{NumericBase, NumericBases},
+    programs::{
+        exit_result_handlers::ProgramExitResultHandler,
+        program_lists::{ProgramList, ProgramListProgram, ProgramSelector},
+        Program,
+    },
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_data_input, ConsoleUiConfirmationPrompt, ConsoleUiList, ConsoleUiTextBox,
+            ConsoleUiTitle, ConsoleWriteable,
+        },
+        ConfirmationPrompt, DataInput, DataInputType,
+    },
+    String16,
+};
+use address_derivation::ConsoleBitcoinAddressDerivationProgram;
+use address_search::ConsoleBitcoinAddressSearchProgram;
+use alloc::{format, string::String, sync::Arc, vec::Vec};
+use base_58_check_decoder::ConsoleBase58CheckDecoderProgram;
+use bip_322_signing::ConsoleBip322SigningProgram;
+use bip_322_verification::ConsoleBip322VerificationProgram;
+use macros::s16;
+use message_signing::ConsoleBitcoinMessageSigningProgram;
+use message_verification::ConsoleBitcoinMessageVerificationProgram;
+use psbt_finalizer::ConsolePsbtFinalizerProgram;
+use psbt_signing::ConsolePsbtSigningProgram;
+use psbt_viewer::ConsolePsbtViewerProgram;
+use signature_hash_calculator::ConsoleSignatureHashCalculatorProgram;
+use transaction_viewer::ConsoleBitcoinTransactionViewerProgram;
+use wif_export::ConsoleWifExportProgram;
+
+pub fn get_bitcoin_program_list<
+    'a,
+    TSystemServices: SystemServices,
+    TProgramSelector: ProgramSelector + 'static,
+    TProgramExitResultHandler: ProgramExitResultHandler + 'static,
+>(
+    system_services: &TSystemServices,
+    program_selector: &TProgramSelector,
+    exit_result_handler: &TProgramExitResultHandler,
+) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
+    let programs: [Arc<dyn Program>; 13] = [
+        Arc::from(ConsoleBitcoinAddressDerivationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleBitcoinAddressSearchProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleBase58CheckDecoderProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleWifExportProgram::from(system_services.clone())),
+        Arc::from(ConsoleBitcoinMessageSigningProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleBitcoinMessageVerificationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleBip322SigningProgram::from(system_services.clone())),
+        Arc::from(ConsoleBip322VerificationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleBitcoinTransactionViewerProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsolePsbtViewerProgram::from(system_services.clone())),
+        Arc::from(ConsolePsbtSigningProgram::from(system_services.clone())),
+        Arc::from(ConsolePsbtFinalizerProgram::from(system_services.clone())),
+        Arc::from(ConsoleSignatureHashCalculatorProgram::from(
+            system_services.clone(),
+        )),
+    ];
+
+    ProgramList::from(Arc::from(programs), s16!("Bitcoin Programs"))
+        .as_program(program_selector.clone(), exit_result_handler.clone())
+}
+
+impl ConsoleWriteable for SignedMessageAddressType {
+    fn write_to<T: ConsoleOut>(&self, console: &T) {
+        console.output_utf16((*self).into());
+    }
+}
+
+impl ConsoleWriteable for Bip322AddressType {
+    fn write_to<T: ConsoleOut>(&self, console: &T) {
+        console.output_utf16((*self).into());
+    }
+}
+
+impl ConsoleWriteable for SighashType {
+    fn write_to<T: ConsoleOut>(&self, console: &T) {
+        console.output_utf16((*self).into());
+    }
+}
+
+impl ConsoleWriteable for Bip322SignatureFormat {
+    fn write_to<T: ConsoleOut>(&self, console: &T) {
+        console.output_utf16((*self).into());
+    }
+}
+
+fn prompt_for_selection<TSystemServices: SystemServices, TOption: ConsoleWriteable + Copy>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16,
+    title: String16,
+    options: &[TOption],
+) -> Option<TOption> {
+    loop {
+        system_services.get_console_out().line_start().new_line();
+        match ConsoleUiList::from(
+            ConsoleUiTitle::from(title, constants::SMALL_TITLE),
+            constants::SELECT_LIST,
+            options,
+        )
+        .prompt_for_selection(system_services)
+        {
+            Some((o, _, _)) => break Some(*o),
+            None => {
+                if ConsoleUiConfirmationPrompt::from(system_services)
+                    .prompt_for_confirmation(cancel_prompt)
+                {
+                    break None;
+                }
+            }
+        }
+    }
+}
+
+pub(in crate::programs::console) fn prompt_for_network<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16,
+) -> Option<Bip32KeyNetwork> {
+    prompt_for_selection(
+        system_services,
+        cancel_prompt,
+        s16!(" Network "),
+        &[Bip32KeyNetwork::MainNet, Bip32KeyNetwork::TestNet],
+    )
+}
+
+fn prompt_for_extended_key<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16,
+    label: String16<'static>,
+) -> Option<Bip32SerializedExtendedKey> {
+    let console = system_services.get_console_out();
+    loop {
+        let mut bytes = match prompt_for_data_input(
+            Some(NumericBases::Base58.into()),
+            &[DataInputType::Bytes],
+            system_services,
+            cancel_prompt,
+            label,
+        ) {
+            DataInput::Bytes(b) => b,
+            _ => return None,
+        };
+
+        // Extended keys are 78 bytes, usually followed by a 4 byte checksum.
+        let error = if bytes.len() == 82 && !validate_checksum_in(&bytes).0 {
+            s16!("The extended key failed checksumming.")
+        } else if bytes.len() == 78 || bytes.len() == 82 {
+            let key = Bip32SerializedExtendedKey::from_bytes(&bytes[..78]).unwrap();
+            bytes.fill(0);
+            match key.try_get_key_version() {
+                Ok(_) => return Some(key),
+                Err(e) => {
+                    key.zero();
+                    e
+                }
+            }
+        } else {
+            s16!("BIP 32 extended keys are exactly 78 bytes in length (plus an optional 4 byte checksum).")
+        };
+
+        bytes.fill(0);
+        console.in_colours(constants::ERROR_COLOURS, |c| {
+            c.line_start().new_line().output_utf16_line(error)
+        });
+    }
+}
+
+fn prompt_for_derivation_path<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16,
+) -> Option<Vec<Bip32DerivationPathPoint>> {
+    loop {
+        match prompt_for_data_input(
+            None,
+            &[DataInputType::Text],
+            system_services,
+            cancel_prompt,
+            s16!("Derivation Path (e.g. m/84'/0'/0'/0/0)"),
+        ) {
+            DataInput::Text(t) => match try_parse_derivation_path(&t) {
+                Some(p) => return Some(p),
+                None => {
+                    system_services
+                        .get_console_out()
+                        .in_colours(constants::ERROR_COLOURS, |c| {
+                            c.line_start().new_line().output_utf16_line(s16!(
+                                "Derivation paths are '/' separated indexes below 2^31, with hardened indexes suffixed by '."
+                            ))
+                        });
+                }
+            },
+            _ => return None,
+        }
+    }
+}
+
+fn try_derive_child_key<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    parent_key: Bip32SerializedExtendedKey,
+    derivation_path: &[Bip32DerivationPathPoint],
+) -> Result<Bip32SerializedExtendedKey, String16<'static>> {
+    let console = system_services.get_console_out();
+    let mut context = Bip32CkdContext::new();
+    let child_key = context.derive(
+        |p| {
+            console
+                .line_start()
+                .new_line()
+                .in_colours(constants::SUCCESS_COLOURS, |c| {
+                    c.output_utf16(s16!("Deriving "));
+                    p.write_to(c);
+                    c.output_utf16(s16!("..."))
+                });
+        },
+        parent_key,
+        derivation_path,
+    );
+
+    context.reset();
+    child_key
+}
+
+fn format_amount(value: u64) -> String {
+    // Amounts are displayed in both bitcoin, to 8 decimal places, and satoshis.
+    format!(
+        "{}.{:08} BTC ({} sats)",
+        value / SATOSHIS_PER_BITCOIN,
+        value % SATOSHIS_PER_BITCOIN,
+        value
+    )
+}
+
+fn push_text(content: &mut Vec<u16>, text: &str) {
+    content.extend(text.encode_utf16());
+}
+
+fn push_hex(content: &mut Vec<u16>, bytes: &[u8], reverse: bool) {
+    // Transaction IDs are conventionally displayed in reverse byte order.
+    if bytes.is_empty() {
+        push_text(content, "(empty)");
+    } else if reverse {
+        let mut reversed = Vec::from(bytes);
+        reversed.reverse();
+        content.extend(NumericBase::BASE_16.build_string_from_bytes(&reversed, false));
+    } else {
+        content.extend(NumericBase::BASE_16.build_string_from_bytes(bytes, false));
+    }
+}
+
+fn write_transaction_summary(
+    content: &mut Vec<u16>,
+    transaction: &Transaction,
+    network: Bip32KeyNetwork,
+) {
+    push_text(content, "TXID: ");
+    push_hex(content, &transaction.txid(), true);
+    if transaction.has_witness() {
+        push_text(content, "\nWTXID: ");
+        push_hex(content, &transaction.wtxid(), true);
+    }
+
+    // Locktimes below 500,000,000 are block heights; larger locktimes are UNIX timestamps.
+    push_text(
+        content,
+        &format!(
+            "\nVersion: {}\nLocktime: {} ({})\nSize: {} bytes ({} vbytes)\n",
+            transaction.version(),
+            transaction.lock_time(),
+            if transaction.lock_time() < 500_000_000 {
+                "Block Height"
+            } else {
+                "UNIX Timestamp"
+            },
+            transaction.serialize(true).len(),
+            transaction.virtual_size()
+        ),
+    );
+
+    for (i, input) in transaction.inputs().iter().enumerate() {
+        push_text(content, &format!("\nInput {}\n  Outpoint: ", i));
+        push_hex(content, input.previous_txid(), true);
+        push_text(
+            content,
+            &format!(
+                ":{}\n  Sequence: 0x{:08X}\n  ScriptSig: ",
+                input.previous_output_index(),
+                input.sequence()
+            ),
+        );
+        push_hex(content, input.script_sig(), false);
+        for (j, item) in input.witness().iter().enumerate() {
+            push_text(content, &format!("\n  Witness {}: ", j));
+            push_hex(content, item, false);
+        }
+
+        push_text(content, "\n");
+    }
+
+    for (i, output) in transaction.outputs().iter().enumerate() {
+        push_text(
+            content,
+            &format!(
+                "\nOutput {}\n  Amount: {}\n  Type: ",
+                i,
+                format_amount(output.value())
+            ),
+        );
+        let script_type: String16 = output.script_type().into();
+        content.extend(script_type.content_iterator());
+        if let Some(a) = addresses::from_script_pubkey(output.script_pubkey(), network) {
+            push_text(content, "\n  Address: ");
+            content.extend(a);
+        }
+
+        push_text(content, "\n  Script: ");
+        push_hex(content, output.script_pubkey(), false);
+        push_text(content, "\n");
+    }
+
+    push_text(
+        content,
+        &format!(
+            "\nTotal Output: {}",
+            format_amount(transaction.total_output_value())
+        ),
+    );
+}
+
+fn prompt_for_psbt<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16,
+) -> Option<Psbt> {
+    loop {
+        // PSBTs are usually exchanged as base-64 text, but can be entered as raw bytes.
+        let bytes = match prompt_for_data_input(
+            Some(NumericBases::Hexadecimal.into()),
+            &[DataInputType::Text, DataInputType::Bytes],
+            system_services,
+            cancel_prompt,
+            s16!("PSBT (Base-64 or Bytes)"),
+        ) {
+            DataInput::Text(t) => base64::decode(&t),
+            DataInput::Bytes(b) => Some(b),
+            _ => return None,
+        };
+
+        let error = match bytes {
+            Some(b) => match Psbt::try_deserialize(&b) {
+                Ok(p) => return Some(p),
+                Err(e) => e,
+            },
+            None => s16!("The PSBT is not valid base-64."),
+        };
+
+        system_services
+            .get_console_out()
+            .in_colours(constants::ERROR_COLOURS, |c| {
+                c.line_start().new_line().output_utf16_line(error)
+            });
+    }
+}
+
+fn render_summary<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    label: String16<'static>,
+    content: &[u16],
+) {
+    // Give the summary the whole screen; transactions with many inputs and outputs are long.
+    let console = system_services.get_console_out();
+    console.clear();
+
+    let console_size = console.size();
+    ConsoleUiTextBox::from(
+        system_services,
+        constants::TEXT_DISPLAY
+            .with_scroll_text_height(console_size.height() - constants::SMALL_TITLE.height() - 1),
+    )
+    .render_text(console_size.width(), label, content);
+}
+
+fn push_derivation(content: &mut Vec<u16>, derivation: &PsbtBip32Derivation) {
+    // Derivations are displayed as [master key fingerprint] m/path.
+    push_text(content, "[");
+    push_hex(content, derivation.fingerprint(), false);
+    push_text(content, "] m");
+    for point in derivation.path() {
+        if point.is_for_hardened_key() {
+            push_text(
+                content,
+                &format!("/{}'", point.numeric_value() & MAX_DERIVATION_POINT),
+            );
+        } else {
+            push_text(content, &format!("/{}", point.numeric_value()));
+        }
+    }
+}
+
+fn write_psbt_summary(
+    content: &mut Vec<u16>,
+    psbt: &Psbt,
+    network: Bip32KeyNetwork,
+    key: Option<(&Bip32SerializedExtendedKey, &[u8; 4])>,
+) {
+    let transaction = psbt.unsigned_transaction();
+    push_text(content, "TXID: ");
+    push_hex(content, &transaction.txid(), true);
+    push_text(
+        content,
+        &format!(
+            "\nPSBT Version: {}\nVersion: {}\nLocktime: {}\n",
+            psbt.version(),
+            transaction.version(),
+            transaction.lock_time()
+        ),
+    );
+
+    if let Some((_, f)) = key {
+        push_text(content, "Wallet Key Fingerprint: ");
+        push_hex(content, f, false);
+        push_text(content, "\n");
+    }
+
+    for (i, input) in transaction.inputs().iter().enumerate() {
+        push_text(content, &format!("\nInput {}\n  Outpoint: ", i));
+        push_hex(content, input.previous_txid(), true);
+        push_text(content, &format!(":{}", input.previous_output_index()));
+        match psbt.spent_output(i) {
+            Some(o) => {
+                push_text(
+                    content,
+                    &format!("\n  Amount: {}\n  Type: ", format_amount(o.value())),
+                );
+                let script_type: String16 = o.script_type().into();
+                content.extend(script_type.content_iterator());
+                if let Some(a) = addresses::from_script_pubkey(o.script_pubkey(), network) {
+                    push_text(content, "\n  Address: ");
+                    content.extend(a);
+                }
+            }
+            None => push_text(content, "\n  Amount: Unknown (no UTXO)"),
+        }
+
+        if let Some(t) = psbt.input_sighash_type(i) {
+            push_text(content, &format!("\n  Sighash Type: 0x{:02X}", t));
+        }
+
+        for derivation in psbt.input_bip32_derivations(i) {
+            push_text(content, "\n  Key: ");
+            push_derivation(content, &derivation);
+            if key.is_some_and(|(k, f)| derivation.try_derive_public_key_from(k, f).is_some()) {
+                push_text(content, " (Wallet)");
+            }
+        }
+
+        if psbt.is_input_finalized(i) {
+            push_text(content, "\n  Signatures: Finalized");
+        } else {
+            let signature_count = psbt.input_partial_signatures(i).count()
+                + psbt.input_taproot_key_signature(i).is_some() as usize;
+            push_text(content, &format!("\n  Signatures: {}", signature_count));
+        }
+
+        push_text(content, "\n");
+    }
+
+    let mut change_value = 0u64;
+    for (i, output) in transaction.outputs().iter().enumerate() {
+        push_text(
+            content,
+            &format!(
+                "\nOutput {}\n  Amount: {}\n  Type: ",
+                i,
+                format_amount(output.value())
+            ),
+        );
+        let script_type: String16 = output.script_type().into();
+        content.extend(script_type.content_iterator());
+        if let Some(a) = addresses::from_script_pubkey(output.script_pubkey(), network) {
+            push_text(content, "\n  Address: ");
+            content.extend(a);
+        }
+
+        match key.and_then(|(k, f)| psbt.try_identify_change_output(i, k, f)) {
+            Some(d) => {
+                change_value += output.value();
+                push_text(content, "\n  Change: ");
+                push_derivation(content, &d);
+            }
+            None => push_text(content, "\n  Change: No"),
+        }
+
+        push_text(content, "\n");
+    }
+
+    let total_output_value = transaction.total_output_value();
+    push_text(
+        content,
+        &format!(
+            "\nTotal Input: {}\nTotal Output: {}\nSending: {}\nFee: {}",
+            match psbt.total_input_value() {
+                Some(v) => format_amount(v),
+                None => String::from("Unknown"),
+            },
+            format_amount(total_output_value),
+            format_amount(total_output_value - change_value),
+            match (psbt.total_input_value(), psbt.fee()) {
+                (Some(_), Some(f)) => format_amount(f),
+                (Some(_), None) => String::from("Invalid (the outputs exceed the inputs)"),
+                _ => String::from("Unknown"),
+            }
+        ),
+    );
+
+    let unknown_pair_count = psbt.unknown_pair_count();
+    if unknown_pair_count > 0 {
+        push_text(
+            content,
+            &format!(
+                "\nUnknown or Proprietary Fields: {} (retained)",
+                unknown_pair_count
+            ),
+        );
+    }
+}
@@ -0,0 +1,141 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_psbt;
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    encoding::base64,
+    programs::{
+        console::{write_bytes, write_string_program_output},
+        Program, ProgramExitResult,
+    },
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_clipboard_write, ConsoleUiConfirmationPrompt, ConsoleUiTitle,
+            ConsoleWriteable,
+        },
+        ConfirmationPrompt,
+    },
+    String16,
+};
+use alloc::format;
+use macros::s16;
+
+pub struct ConsolePsbtFinalizerProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsolePsbtFinalizerProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for ConsolePsbtFinalizerProgram<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("PSBT Combiner & Finalizer")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program combines partially signed copies of a version 0 or version 2 PSBT (BIP 174, BIP 370), then finalizes each P2PKH, P2SH-P2WPKH, P2WPKH, P2TR key path, and P2SH, P2SH-P2WSH or P2WSH multisig input with enough signatures. Once every input is finalized, the signed transaction is extracted."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "Signatures are verified before inputs are finalized; a PSBT with an invalid signature is rejected."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel PSBT finalization?");
+
+        let mut psbt = match prompt_for_psbt(&self.system_services, CANCEL_PROMPT) {
+            Some(p) => p,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        loop {
+            console.line_start().new_line();
+            if !ConsoleUiConfirmationPrompt::from(&self.system_services)
+                .prompt_for_confirmation(s16!("Combine with another copy of the PSBT?"))
+            {
+                break;
+            }
+
+            let other = match prompt_for_psbt(&self.system_services, CANCEL_PROMPT) {
+                Some(p) => p,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+            match psbt.try_combine(&other) {
+                Ok(p) => psbt = p,
+                Err(e) => {
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.line_start().new_line().output_utf16_line(e)
+                    });
+                }
+            }
+        }
+
+        let finalized_count = match psbt.try_finalize() {
+            Ok(c) => c,
+            Err(e) => return e.to_program_error(),
+        };
+
+        let input_count = psbt.unsigned_transaction().inputs().len();
+        console.line_start().new_line().output_utf32_line(&format!(
+            "Finalized {} input(s); {} of {} input(s) are finalized.\0",
+            finalized_count,
+            (0..input_count)
+                .filter(|i| psbt.is_input_finalized(*i))
+                .count(),
+            input_count
+        ));
+
+        match psbt.try_extract_transaction() {
+            Ok(t) => {
+                const LABEL: String16 = s16!("Signed Transaction");
+                let transaction = t.serialize(true);
+                let mut txid = t.txid();
+                txid.reverse();
+                write_bytes(&self.system_services, s16!("TXID"), &txid);
+                write_bytes(&self.system_services, LABEL, &transaction);
+                prompt_for_clipboard_write(
+                    &self.system_services,
+                    ClipboardEntry::Bytes(LABEL, transaction.into()),
+                );
+            }
+            Err(_) => {
+                // The PSBT still needs more signatures; it can be passed on to the other signers.
+                const LABEL: String16 = s16!("PSBT (Base-64)");
+                let psbt = base64::encode(&psbt.serialize());
+                write_string_program_output(&self.system_services, LABEL, String16::from(&psbt));
+                prompt_for_clipboard_write(
+                    &self.system_services,
+                    ClipboardEntry::String16(LABEL, psbt.into()),
+                );
+            }
+        }
+
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,166 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_extended_key, prompt_for_psbt, render_summary, write_psbt_summary};
+use crate::{
+    bitcoin::{hd_wallets::Bip32KeyType, psbt, Hash160},
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    encoding::base64,
+    programs::{console::write_string_program_output, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_clipboard_write, ConsoleUiConfirmationPrompt, ConsoleUiTitle,
+            ConsoleWriteable,
+        },
+        ConfirmationPrompt,
+    },
+    String16,
+};
+use alloc::{format, vec::Vec};
+use macros::s16;
+
+pub struct ConsolePsbtSigningProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsolePsbtSigningProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for ConsolePsbtSigningProgram<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("PSBT Signing")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program signs a version 0 or version 2 Partially Signed Bitcoin Transaction (BIP 174, BIP 370) with an extended private key. Keys are derived from each input's BIP 32 derivations, and P2PKH, P2SH-P2WPKH, P2WPKH and BIP 86 taproot key path inputs are signed; the signed PSBT is output as base-64."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "Review the transaction before signing it. Any output which isn't identified as change is a payment, and the fee is paid to miners."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel PSBT signing?");
+
+        let mut psbt = match prompt_for_psbt(&self.system_services, CANCEL_PROMPT) {
+            Some(p) => p,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let key = match prompt_for_extended_key(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Extended Private Key"),
+        ) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let key_version = key.try_get_key_version().unwrap();
+        if key_version.key_type() != Bip32KeyType::Private {
+            key.zero();
+            return s16!("Signing requires an extended private key.").to_program_error();
+        }
+
+        let fingerprint = match psbt::try_get_public_key(&key) {
+            Some(p) => Hash160::new().fingerprint(&p),
+            None => {
+                key.zero();
+                return s16!("The extended key's key material is invalid.").to_program_error();
+            }
+        };
+
+        let mut content = Vec::new();
+        write_psbt_summary(
+            &mut content,
+            &psbt,
+            key_version.key_network(),
+            Some((&key, &fingerprint)),
+        );
+
+        render_summary(&self.system_services, s16!("PSBT"), &content);
+
+        console.clear();
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+
+        // Segwit v0 amounts are only proven by the previous transaction; an understated amount inflates the fee.
+        let allow_unverified_amounts = psbt.has_unverified_segwit_v0_amounts();
+        if allow_unverified_amounts {
+            console.in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "One or more segwit inputs don't include their previous transaction, so their amounts can't be verified. A malicious PSBT can misstate them to make you pay a much larger fee than shown (CVE-2020-14199). Only continue if you've verified the amounts independently."
+                ))
+            });
+
+            if !ConsoleUiConfirmationPrompt::from(&self.system_services)
+                .prompt_for_confirmation(s16!("Sign inputs with unverified amounts?"))
+            {
+                key.zero();
+                return ProgramExitResult::UserCancelled;
+            }
+        }
+
+        if !ConsoleUiConfirmationPrompt::from(&self.system_services)
+            .prompt_for_confirmation(s16!("Sign the transaction?"))
+        {
+            key.zero();
+            return ProgramExitResult::UserCancelled;
+        }
+
+        let signature_count = psbt.try_sign(&key, &fingerprint, allow_unverified_amounts);
+        key.zero();
+
+        let signature_count = match signature_count {
+            Ok(0) => {
+                return s16!("The extended key cannot sign any of the PSBT's unsigned inputs.")
+                    .to_program_error()
+            }
+            Ok(c) => c,
+            Err(e) => return e.to_program_error(),
+        };
+
+        console
+            .line_start()
+            .new_line()
+            .in_colours(constants::SUCCESS_COLOURS, |c| {
+                c.output_utf32_line(&format!(
+                    "Added {} signature(s) to the PSBT.\0",
+                    signature_count
+                ))
+            });
+
+        const LABEL: String16 = s16!("Signed PSBT (Base-64)");
+        let psbt = base64::encode(&psbt.serialize());
+        write_string_program_output(&self.system_services, LABEL, String16::from(&psbt));
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::String16(LABEL, psbt.into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
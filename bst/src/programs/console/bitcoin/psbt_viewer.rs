@@ -0,0 +1,119 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    prompt_for_extended_key, prompt_for_network, prompt_for_psbt, render_summary,
+    write_psbt_summary,
+};
+use crate::{
+    bitcoin::{psbt, Hash160},
+    console_out::ConsoleOut,
+    constants,
+    programs::{Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{ConsoleUiConfirmationPrompt, ConsoleUiTitle, ConsoleWriteable},
+        ConfirmationPrompt,
+    },
+    String16,
+};
+use alloc::vec::Vec;
+use macros::s16;
+
+pub struct ConsolePsbtViewerProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsolePsbtViewerProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for ConsolePsbtViewerProgram<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("PSBT Viewer")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program reads a version 0 or version 2 Partially Signed Bitcoin Transaction (BIP 174, BIP 370), and displays its inputs, outputs and fee for review. An extended key can be loaded to identify the PSBT's change outputs, and the inputs it can sign for."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "Without an extended key, every output should be treated as a payment; a PSBT's claims about change outputs are only trusted once they're verified against your own key."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel PSBT viewing?");
+
+        let psbt = match prompt_for_psbt(&self.system_services, CANCEL_PROMPT) {
+            Some(p) => p,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        console.line_start().new_line();
+        let key = if ConsoleUiConfirmationPrompt::from(&self.system_services)
+            .prompt_for_confirmation(s16!("Load an extended key to identify change outputs?"))
+        {
+            match prompt_for_extended_key(
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Extended Public Key"),
+            ) {
+                Some(k) => match psbt::try_get_public_key(&k) {
+                    Some(p) => Some((k, Hash160::new().fingerprint(&p))),
+                    None => {
+                        k.zero();
+                        return s16!("The extended key's key material is invalid.")
+                            .to_program_error();
+                    }
+                },
+                None => return ProgramExitResult::UserCancelled,
+            }
+        } else {
+            None
+        };
+
+        // Extended keys encode their network; otherwise, it only determines how scripts are displayed as addresses.
+        let network = match &key {
+            Some((k, _)) => k.try_get_key_version().unwrap().key_network(),
+            None => match prompt_for_network(&self.system_services, CANCEL_PROMPT) {
+                Some(n) => n,
+                None => return ProgramExitResult::UserCancelled,
+            },
+        };
+
+        let mut content = Vec::new();
+        write_psbt_summary(
+            &mut content,
+            &psbt,
+            network,
+            key.as_ref().map(|(k, f)| (k, f)),
+        );
+
+        if let Some((k, _)) = key {
+            k.zero();
+        }
+
+        render_summary(&self.system_services, s16!("PSBT"), &content);
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,406 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{format_amount, prompt_for_selection, push_hex, push_text, render_summary};
+use crate::{
+    bitcoin::{
+        signature_hashes::{
+            legacy, segwit_v0, taproot_key_path, SegwitV0Hashes, SighashType, TaprootHashes,
+            SIGHASH_ANYONECANPAY, SIGHASH_OUTPUT_MASK, SIGHASH_SINGLE,
+        },
+        transactions::{ScriptType, Transaction, TransactionOutput, MAX_MONEY},
+        Hash160,
+    },
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    hashing::{Hasher, Sha256},
+    integers::NumericBases,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_clipboard_write, prompt_for_data_input, prompt_for_u32, prompt_for_u64,
+            ConsoleUiTitle, ConsoleWriteable,
+        },
+        DataInput, DataInputType,
+    },
+    String16,
+};
+use alloc::{format, vec, vec::Vec};
+use macros::s16;
+
+const CANCEL_PROMPT: String16 = s16!("Cancel signature hash calculation?");
+
+enum SignatureHashRules {
+    Legacy(Vec<u8>),
+    SegwitV0(Vec<u8>),
+    Taproot,
+}
+
+pub struct ConsoleSignatureHashCalculatorProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleSignatureHashCalculatorProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+
+    fn prompt_for_bytes(&self, label: String16<'static>) -> Option<Vec<u8>> {
+        match prompt_for_data_input(
+            Some(NumericBases::Hexadecimal.into()),
+            &[DataInputType::Bytes],
+            &self.system_services,
+            CANCEL_PROMPT,
+            label,
+        ) {
+            DataInput::Bytes(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    fn prompt_for_amount(&self) -> Option<u64> {
+        prompt_for_u64(
+            |a| match a {
+                a if a > MAX_MONEY => Some(s16!("Amounts cannot exceed 21,000,000 BTC.")),
+                _ => None,
+            },
+            s16!("Spent Amount (Satoshis)"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        )
+    }
+
+    fn prompt_for_matching_script<F: Fn(&[u8]) -> bool>(
+        &self,
+        label: String16<'static>,
+        matches_script_pubkey: F,
+    ) -> Option<Vec<u8>> {
+        loop {
+            let script = self.prompt_for_bytes(label)?;
+            if matches_script_pubkey(&script) {
+                break Some(script);
+            }
+
+            self.system_services
+                .get_console_out()
+                .in_colours(constants::ERROR_COLOURS, |c| {
+                    c.line_start().new_line().output_utf16_line(s16!(
+                        "The script does not match the spent script's hash."
+                    ))
+                });
+        }
+    }
+
+    fn try_get_rules(
+        &self,
+        script_pubkey: &[u8],
+    ) -> Option<Result<SignatureHashRules, String16<'static>>> {
+        // P2SH outputs commit to a redeem script, which is either the script code or a wrapped segwit program.
+        let is_wrapped = ScriptType::from_script_pubkey(script_pubkey) == ScriptType::P2sh;
+        let script = if is_wrapped {
+            self.prompt_for_matching_script(s16!("Redeem Script"), |s| {
+                Hash160::new().hash_160(s) == script_pubkey[2..22]
+            })?
+        } else {
+            Vec::from(script_pubkey)
+        };
+
+        Some(Ok(match ScriptType::from_script_pubkey(&script) {
+            ScriptType::P2wpkh => {
+                // The script code of a P2WPKH spend is the equivalent P2PKH script.
+                let mut script_code = Vec::with_capacity(25);
+                script_code.extend([0x76, 0xA9, 0x14]);
+                script_code.extend(&script[2..22]);
+                script_code.extend([0x88, 0xAC]);
+                SignatureHashRules::SegwitV0(script_code)
+            }
+            ScriptType::P2wsh => SignatureHashRules::SegwitV0(
+                self.prompt_for_matching_script(s16!("Witness Script"), |s| {
+                    Sha256::new().get_hash_of(s) == script[2..34]
+                })?,
+            ),
+            ScriptType::P2tr if !is_wrapped => SignatureHashRules::Taproot,
+            ScriptType::P2tr | ScriptType::WitnessUnknown(_) => {
+                return Some(Err(s16!(
+                    "The spent script is a witness program without signature hash rules."
+                )))
+            }
+            _ => SignatureHashRules::Legacy(script),
+        }))
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleSignatureHashCalculatorProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Signature Hash Calculator")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program calculates the message digest signed by an input of a raw Bitcoin transaction, using the legacy, segwit version 0 (BIP 143) or taproot key path (BIP 341) rules for the script it spends, along with the intermediate hashes of the transaction's prevouts, sequences and outputs."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "Legacy script codes must not contain OP_CODESEPARATOR, and taproot script path spends are not supported. Hashes are displayed in the byte order they're signed in."
+                ))
+            });
+
+        let transaction = loop {
+            let bytes = match self.prompt_for_bytes(s16!("Raw Transaction")) {
+                Some(b) => b,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+            match Transaction::try_deserialize(&bytes) {
+                Ok(t) => break t,
+                Err(e) => {
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.line_start().new_line().output_utf16_line(e)
+                    });
+                }
+            }
+        };
+
+        let input_count = transaction.inputs().len();
+        let input_index = match prompt_for_u32(
+            |i| match i {
+                i if i as usize >= input_count => {
+                    Some(s16!("The transaction has no input at that index."))
+                }
+                _ => None,
+            },
+            s16!("Input Index"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(i) => i as usize,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let script_pubkey = match self.prompt_for_bytes(s16!("Spent Script Pubkey")) {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let rules = match self.try_get_rules(&script_pubkey) {
+            Some(Ok(r)) => r,
+            Some(Err(e)) => return e.to_program_error(),
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        // Legacy signature hashes don't commit to the spent amount.
+        let amount = match rules {
+            SignatureHashRules::Legacy(_) => 0,
+            _ => match self.prompt_for_amount() {
+                Some(a) => a,
+                None => return ProgramExitResult::UserCancelled,
+            },
+        };
+
+        let sighash_types: &[SighashType] = match rules {
+            SignatureHashRules::Taproot => &[
+                SighashType::Default,
+                SighashType::All,
+                SighashType::None,
+                SighashType::Single,
+                SighashType::AllAnyoneCanPay,
+                SighashType::NoneAnyoneCanPay,
+                SighashType::SingleAnyoneCanPay,
+            ],
+            _ => &[
+                SighashType::All,
+                SighashType::None,
+                SighashType::Single,
+                SighashType::AllAnyoneCanPay,
+                SighashType::NoneAnyoneCanPay,
+                SighashType::SingleAnyoneCanPay,
+            ],
+        };
+
+        let sighash_type = match prompt_for_selection(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Sighash Type"),
+            sighash_types,
+        ) {
+            Some(t) => t,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut content = Vec::new();
+        push_text(
+            &mut content,
+            &format!(
+                "Input: {}\nSighash Type: 0x{:02X} (",
+                input_index,
+                sighash_type.value()
+            ),
+        );
+        let sighash_name: String16 = sighash_type.into();
+        content.extend(sighash_name.content_iterator());
+
+        let signature_hash = match rules {
+            SignatureHashRules::Legacy(script_code) => {
+                push_text(&mut content, ")\nRules: Legacy\nScript Code: ");
+                push_hex(&mut content, &script_code, false);
+                if sighash_type.value() & SIGHASH_OUTPUT_MASK == SIGHASH_SINGLE
+                    && input_index >= transaction.outputs().len()
+                {
+                    push_text(
+                        &mut content,
+                        "\n\nThere's no output at the input's index; SIGHASH_SINGLE signs the number 1.",
+                    );
+                }
+
+                legacy(
+                    &transaction,
+                    input_index,
+                    &script_code,
+                    sighash_type.value() as u32,
+                )
+            }
+            SignatureHashRules::SegwitV0(script_code) => {
+                let hashes =
+                    SegwitV0Hashes::from(&transaction, input_index, sighash_type.value() as u32);
+                push_text(
+                    &mut content,
+                    ")\nRules: Segwit Version 0 (BIP 143)\nScript Code: ",
+                );
+                push_hex(&mut content, &script_code, false);
+                push_text(
+                    &mut content,
+                    &format!("\nAmount: {}\n\nhashPrevouts: ", format_amount(amount)),
+                );
+                push_hex(&mut content, hashes.hash_prevouts(), false);
+                push_text(&mut content, "\nhashSequence: ");
+                push_hex(&mut content, hashes.hash_sequence(), false);
+                push_text(&mut content, "\nhashOutputs: ");
+                push_hex(&mut content, hashes.hash_outputs(), false);
+
+                segwit_v0(
+                    &transaction,
+                    input_index,
+                    &script_code,
+                    amount,
+                    sighash_type.value() as u32,
+                )
+            }
+            SignatureHashRules::Taproot => {
+                let spent_output = TransactionOutput::from(amount, script_pubkey);
+                let spent_outputs = if sighash_type.value() & SIGHASH_ANYONECANPAY != 0 {
+                    // Only the signed input's spent output is committed to; the others aren't needed.
+                    vec![spent_output; input_count]
+                } else {
+                    let mut spent_outputs = Vec::with_capacity(input_count);
+                    for i in 0..input_count {
+                        if i == input_index {
+                            spent_outputs.push(spent_output.clone());
+                            continue;
+                        }
+
+                        console.line_start().new_line().output_utf32_line(&format!(
+                            "Taproot signatures commit to every spent output; enter the output spent by input {}.\0",
+                            i
+                        ));
+                        let script_pubkey = match self.prompt_for_bytes(s16!("Spent Script Pubkey"))
+                        {
+                            Some(s) => s,
+                            None => return ProgramExitResult::UserCancelled,
+                        };
+
+                        let amount = match self.prompt_for_amount() {
+                            Some(a) => a,
+                            None => return ProgramExitResult::UserCancelled,
+                        };
+
+                        spent_outputs.push(TransactionOutput::from(amount, script_pubkey));
+                    }
+
+                    spent_outputs
+                };
+
+                let hashes = match TaprootHashes::try_from(
+                    &transaction,
+                    input_index,
+                    &spent_outputs,
+                    sighash_type.value(),
+                ) {
+                    Ok(h) => h,
+                    Err(e) => return e.to_program_error(),
+                };
+
+                push_text(
+                    &mut content,
+                    &format!(
+                        ")\nRules: Taproot Key Path (BIP 341)\nAmount: {}\n",
+                        format_amount(amount)
+                    ),
+                );
+                for (label, hash) in [
+                    ("sha_prevouts", hashes.sha_prevouts()),
+                    ("sha_amounts", hashes.sha_amounts()),
+                    ("sha_scriptpubkeys", hashes.sha_script_pubkeys()),
+                    ("sha_sequences", hashes.sha_sequences()),
+                    ("sha_outputs", hashes.sha_outputs()),
+                    ("sha_single_output", hashes.sha_single_output()),
+                ] {
+                    push_text(&mut content, &format!("\n{}: ", label));
+                    match hash {
+                        Some(h) => push_hex(&mut content, h, false),
+                        None => push_text(&mut content, "(Not Committed)"),
+                    }
+                }
+
+                match taproot_key_path(
+                    &transaction,
+                    input_index,
+                    &spent_outputs,
+                    sighash_type.value(),
+                ) {
+                    Ok(h) => h,
+                    Err(e) => return e.to_program_error(),
+                }
+            }
+        };
+
+        push_text(&mut content, "\n\nSignature Hash: ");
+        push_hex(&mut content, &signature_hash, false);
+        render_summary(&self.system_services, s16!("Signature Hash"), &content);
+
+        console.clear();
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+
+        const LABEL: String16 = s16!("Signature Hash");
+        write_bytes(&self.system_services, LABEL, &signature_hash);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(LABEL, signature_hash[..].into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
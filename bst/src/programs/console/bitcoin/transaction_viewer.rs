@@ -0,0 +1,101 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_network, render_summary, write_transaction_summary};
+use crate::{
+    bitcoin::transactions::Transaction,
+    console_out::ConsoleOut,
+    constants,
+    integers::NumericBases,
+    programs::{Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{prompt_for_data_input, ConsoleUiTitle, ConsoleWriteable},
+        DataInput, DataInputType,
+    },
+    String16,
+};
+use alloc::vec::Vec;
+use macros::s16;
+
+pub struct ConsoleBitcoinTransactionViewerProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleBitcoinTransactionViewerProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleBitcoinTransactionViewerProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Bitcoin Transaction Viewer")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program deserializes a raw Bitcoin transaction, with or without segwit witness data, and displays its version, inputs, outputs and locktime, along with its transaction IDs and the addresses its outputs pay to."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "Input values are not part of a transaction; the fee cannot be determined without the transactions being spent."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel transaction viewing?");
+
+        let transaction = loop {
+            let bytes = match prompt_for_data_input(
+                Some(NumericBases::Hexadecimal.into()),
+                &[DataInputType::Bytes],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Raw Transaction"),
+            ) {
+                DataInput::Bytes(b) => b,
+                _ => return ProgramExitResult::UserCancelled,
+            };
+
+            match Transaction::try_deserialize(&bytes) {
+                Ok(t) => break t,
+                Err(e) => {
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.line_start().new_line().output_utf16_line(e)
+                    });
+                }
+            }
+        };
+
+        // The network only determines how output scripts are displayed as addresses.
+        let network = match prompt_for_network(&self.system_services, CANCEL_PROMPT) {
+            Some(n) => n,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut content = Vec::new();
+        write_transaction_summary(&mut content, &transaction, network);
+
+        render_summary(&self.system_services, s16!("Transaction"), &content);
+        ProgramExitResult::Success
+    }
+}
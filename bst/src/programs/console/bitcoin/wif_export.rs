@@ -0,0 +1,120 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_derivation_path, prompt_for_extended_key, try_derive_child_key};
+use crate::{
+    bitcoin::{hd_wallets::Bip32KeyType, psbt, wif_encode},
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    programs::{console::write_string_program_output, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{prompt_for_clipboard_write, ConsoleUiTitle, ConsoleWriteable},
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleWifExportProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleWifExportProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for ConsoleWifExportProgram<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("WIF Private Key Export")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program exports the private key of a BIP 32 extended private key, or of a key derived from it with a derivation path relative to it, in Wallet Import Format (WIF). BIP 32 keys' public keys are always compressed."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "A WIF key can spend everything sent to its addresses; handle it as carefully as the extended key it came from."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel WIF private key export?");
+
+        let parent_key = match prompt_for_extended_key(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Extended Private Key"),
+        ) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let key_version = parent_key.try_get_key_version().unwrap();
+        if key_version.key_type() != Bip32KeyType::Private {
+            parent_key.zero();
+            return s16!("WIF export requires an extended private key.").to_program_error();
+        }
+
+        // An empty derivation path exports the extended key itself, so its key material must be valid.
+        if psbt::try_get_public_key(&parent_key).is_none() {
+            parent_key.zero();
+            return s16!("The extended key's key material is invalid.").to_program_error();
+        }
+
+        let derivation_path = match prompt_for_derivation_path(&self.system_services, CANCEL_PROMPT)
+        {
+            Some(p) => p,
+            None => {
+                parent_key.zero();
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let child_key =
+            match try_derive_child_key(&self.system_services, parent_key, &derivation_path) {
+                Ok(k) => k,
+                Err(e) => return e.to_program_error(),
+            };
+
+        // Private key material is a zero byte, followed by a key in the range 0 < k < n.
+        if psbt::try_get_public_key(&child_key).is_none() {
+            child_key.zero();
+            return s16!("The extended key's key material is invalid.").to_program_error();
+        }
+
+        let mut private_key = [0u8; 32];
+        private_key.copy_from_slice(&child_key.key_material()[1..]);
+        child_key.zero();
+
+        let wif = wif_encode(&private_key, key_version.key_network(), true);
+        private_key.fill(0);
+
+        console.line_start().new_line();
+        const LABEL: String16 = s16!("WIF Private Key");
+        write_string_program_output(&self.system_services, LABEL, String16::from(&wif));
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::String16(LABEL, wif.into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
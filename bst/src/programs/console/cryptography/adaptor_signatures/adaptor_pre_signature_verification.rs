@@ -0,0 +1,117 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_point;
+use crate::{
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::{
+        adaptor::{self, PRE_SIGNATURE_LENGTH},
+        schnorr,
+    },
+    programs::{Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_bytes_from_any_data_type, prompt_for_fixed_length_bytes,
+            ConsoleUiContinuePrompt, ConsoleUiTitle, ConsoleWriteable,
+        },
+        ContinuePrompt,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleAdaptorPreSignatureVerificationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices>
+    ConsoleAdaptorPreSignatureVerificationProgram<TSystemServices>
+{
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleAdaptorPreSignatureVerificationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Adaptor Pre-Signature Verification")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program verifies a Schnorr adaptor pre-signature; a valid pre-signature is guaranteed to complete to a BIP 340 signature for the message and public key once the adaptor point's secret is known."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel pre-signature verification?");
+
+        let public_key =
+            match prompt_for_fixed_length_bytes::<{ schnorr::PUBLIC_KEY_LENGTH }, TSystemServices>(
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("X-Only Public Key"),
+            ) {
+                Some(k) => k,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        // Text is verified as its UTF8 bytes.
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => return e,
+        };
+
+        let adaptor_point =
+            match prompt_for_point(&self.system_services, CANCEL_PROMPT, s16!("Adaptor Point")) {
+                Some(p) => p,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        let pre_signature = match prompt_for_fixed_length_bytes::<
+            PRE_SIGNATURE_LENGTH,
+            TSystemServices,
+        >(
+            &self.system_services, CANCEL_PROMPT, s16!("Pre-Signature")
+        ) {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        console.line_start().new_line();
+        if adaptor::verify_pre_signature(&public_key, &message, &adaptor_point, &pre_signature) {
+            console.in_colours(constants::SUCCESS_COLOURS, |c| {
+                c.output_utf16_line(s16!("Pre-signature is valid."))
+            });
+        } else {
+            console.in_colours(constants::ERROR_COLOURS, |c| {
+                c.output_utf16_line(s16!("Pre-signature is NOT valid."))
+            });
+        }
+
+        ConsoleUiContinuePrompt::from(&self.system_services).prompt_for_continue();
+        ProgramExitResult::Success
+    }
+}
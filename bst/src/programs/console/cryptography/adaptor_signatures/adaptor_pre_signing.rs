@@ -0,0 +1,121 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_point, prompt_for_secret};
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::{adaptor, schnorr},
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_bytes_from_any_data_type, prompt_for_clipboard_write, ConsoleUiTitle,
+        ConsoleWriteable,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleAdaptorPreSigningProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleAdaptorPreSigningProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for ConsoleAdaptorPreSigningProgram<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("Adaptor Pre-Signing")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program produces a Schnorr adaptor pre-signature for a message, locked to an adaptor point. The holder of the adaptor point's secret can complete it to a BIP 340 signature, and publishing that signature reveals the secret to you."
+            ))
+            .output_utf16_line(s16!(
+                "The pre-signature format is specific to bst, and can only be completed or verified by bst; the completed signature is a standard BIP 340 signature."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "Only pre-sign a message you're willing to have signed; anybody who learns the adaptor secret can complete the signature."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel pre-signing?");
+
+        let (mut private_key, _) =
+            match prompt_for_secret(&self.system_services, CANCEL_PROMPT, s16!("Private Key")) {
+                Some(k) => k,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        // Text is signed as its UTF8 bytes.
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => {
+                private_key.fill(0);
+                return e;
+            }
+        };
+
+        let adaptor_point =
+            match prompt_for_point(&self.system_services, CANCEL_PROMPT, s16!("Adaptor Point")) {
+                Some(p) => p,
+                None => {
+                    private_key.fill(0);
+                    return ProgramExitResult::UserCancelled;
+                }
+            };
+
+        // The nonce commits to the private key, message and adaptor point, so omitting auxiliary randomness is safe.
+        let public_key = schnorr::try_derive_x_only_public_key(&private_key);
+        let pre_signature =
+            adaptor::try_pre_sign(&private_key, &message, &adaptor_point, &[0u8; 32]);
+        private_key.fill(0);
+
+        let (public_key, pre_signature) = match (public_key, pre_signature) {
+            (Some(k), Some(s)) => (k, s),
+            _ => return s16!("Failed to produce a pre-signature.").to_program_error(),
+        };
+
+        write_bytes(
+            &self.system_services,
+            s16!("X-Only Public Key"),
+            &public_key,
+        );
+
+        write_bytes(&self.system_services, s16!("Pre-Signature"), &pre_signature);
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("Adaptor pre-signature"), pre_signature[..].into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
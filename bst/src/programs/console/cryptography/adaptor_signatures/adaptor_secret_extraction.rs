@@ -0,0 +1,110 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_point;
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::{
+        adaptor::{self, PRE_SIGNATURE_LENGTH},
+        schnorr,
+    },
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_clipboard_write, prompt_for_fixed_length_bytes, ConsoleUiTitle, ConsoleWriteable,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleAdaptorSecretExtractionProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleAdaptorSecretExtractionProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleAdaptorSecretExtractionProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Adaptor Secret Extraction")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program extracts the adaptor secret from a Schnorr adaptor pre-signature and the BIP 340 signature it was completed to."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel secret extraction?");
+
+        let pre_signature = match prompt_for_fixed_length_bytes::<
+            PRE_SIGNATURE_LENGTH,
+            TSystemServices,
+        >(
+            &self.system_services, CANCEL_PROMPT, s16!("Pre-Signature")
+        ) {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let signature = match prompt_for_fixed_length_bytes::<
+            { schnorr::SIGNATURE_LENGTH },
+            TSystemServices,
+        >(&self.system_services, CANCEL_PROMPT, s16!("Signature"))
+        {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let adaptor_point =
+            match prompt_for_point(&self.system_services, CANCEL_PROMPT, s16!("Adaptor Point")) {
+                Some(p) => p,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        let mut adaptor_secret =
+            match adaptor::try_extract_secret(&pre_signature, &signature, &adaptor_point) {
+                Some(s) => s,
+                None => {
+                    return s16!("The signature was not completed from the pre-signature with the adaptor point's secret.")
+                        .to_program_error()
+                }
+            };
+
+        write_bytes(
+            &self.system_services,
+            s16!("Adaptor Secret"),
+            &adaptor_secret,
+        );
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("Adaptor secret"), adaptor_secret[..].into()),
+        );
+
+        adaptor_secret.fill(0);
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,130 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_secret;
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::{
+        adaptor::{self, PRE_SIGNATURE_LENGTH},
+        schnorr,
+    },
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_bytes_from_any_data_type, prompt_for_clipboard_write,
+        prompt_for_fixed_length_bytes, ConsoleUiTitle, ConsoleWriteable,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleAdaptorSignatureCompletionProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleAdaptorSignatureCompletionProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleAdaptorSignatureCompletionProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Adaptor Signature Completion")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program completes a Schnorr adaptor pre-signature to a BIP 340 signature using the adaptor secret, and verifies the result against the signer's public key and message."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "Publishing the completed signature reveals the adaptor secret to the pre-signer."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel signature completion?");
+
+        let public_key =
+            match prompt_for_fixed_length_bytes::<{ schnorr::PUBLIC_KEY_LENGTH }, TSystemServices>(
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("X-Only Public Key"),
+            ) {
+                Some(k) => k,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        // Text is signed as its UTF8 bytes.
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => return e,
+        };
+
+        let pre_signature = match prompt_for_fixed_length_bytes::<
+            PRE_SIGNATURE_LENGTH,
+            TSystemServices,
+        >(
+            &self.system_services, CANCEL_PROMPT, s16!("Pre-Signature")
+        ) {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let (mut adaptor_secret, adaptor_point) =
+            match prompt_for_secret(&self.system_services, CANCEL_PROMPT, s16!("Adaptor Secret")) {
+                Some(s) => s,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        // Check the pre-signature first, so an invalid one can be distinguished from the wrong secret.
+        if !adaptor::verify_pre_signature(&public_key, &message, &adaptor_point, &pre_signature) {
+            adaptor_secret.fill(0);
+            return s16!(
+                "The pre-signature is NOT valid for the public key, message and adaptor secret."
+            )
+            .to_program_error();
+        }
+
+        let signature = adaptor::try_adapt(&pre_signature, &adaptor_secret);
+        adaptor_secret.fill(0);
+
+        let signature = match signature {
+            Some(s) if schnorr::verify(&public_key, &message, &s) => s,
+            _ => return s16!("Failed to complete the signature.").to_program_error(),
+        };
+
+        write_bytes(&self.system_services, s16!("Signature"), &signature);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("Schnorr signature"), signature[..].into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,91 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_point, prompt_for_secret};
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::dleq,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{prompt_for_clipboard_write, ConsoleUiTitle, ConsoleWriteable},
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleDleqProofGenerationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleDleqProofGenerationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleDleqProofGenerationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("DLEQ Proof Generation")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program produces a BIP 374 discrete logarithm equality proof; given a secret a and a base point B, it proves that A = aG and C = aB share the same secret, without revealing it."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel proof generation?");
+
+        let (mut secret, public_key) =
+            match prompt_for_secret(&self.system_services, CANCEL_PROMPT, s16!("Secret")) {
+                Some(s) => s,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        let base = match prompt_for_point(&self.system_services, CANCEL_PROMPT, s16!("Base Point"))
+        {
+            Some(p) => p,
+            None => {
+                secret.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        // The nonce commits to the secret and points, so omitting auxiliary randomness is safe.
+        let proof = dleq::try_generate_proof(&secret, &base, &[0u8; 32], None);
+        secret.fill(0);
+
+        let (shared_key, proof) = match proof {
+            Some(p) => p,
+            None => return s16!("Failed to produce a proof.").to_program_error(),
+        };
+
+        write_bytes(&self.system_services, s16!("Point A (aG)"), &public_key);
+        write_bytes(&self.system_services, s16!("Point C (aB)"), &shared_key);
+        write_bytes(&self.system_services, s16!("Proof"), &proof);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("DLEQ proof"), proof[..].into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,103 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_point;
+use crate::{
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::dleq::{self, PROOF_LENGTH},
+    programs::{Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_fixed_length_bytes, ConsoleUiContinuePrompt, ConsoleUiTitle,
+            ConsoleWriteable,
+        },
+        ContinuePrompt,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleDleqProofVerificationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleDleqProofVerificationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleDleqProofVerificationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("DLEQ Proof Verification")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program verifies a BIP 374 discrete logarithm equality proof that A = aG and C = aB share the same secret a."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel proof verification?");
+
+        let public_key =
+            match prompt_for_point(&self.system_services, CANCEL_PROMPT, s16!("Point A (aG)")) {
+                Some(p) => p,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        let base = match prompt_for_point(&self.system_services, CANCEL_PROMPT, s16!("Base Point"))
+        {
+            Some(p) => p,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let shared_key =
+            match prompt_for_point(&self.system_services, CANCEL_PROMPT, s16!("Point C (aB)")) {
+                Some(p) => p,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        let proof = match prompt_for_fixed_length_bytes::<PROOF_LENGTH, TSystemServices>(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Proof"),
+        ) {
+            Some(p) => p,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        console.line_start().new_line();
+        if dleq::verify_proof(&public_key, &base, &shared_key, &proof, None) {
+            console.in_colours(constants::SUCCESS_COLOURS, |c| {
+                c.output_utf16_line(s16!("Proof is valid."))
+            });
+        } else {
+            console.in_colours(constants::ERROR_COLOURS, |c| {
+                c.output_utf16_line(s16!("Proof is NOT valid."))
+            });
+        }
+
+        ConsoleUiContinuePrompt::from(&self.system_services).prompt_for_continue();
+        ProgramExitResult::Success
+    }
+}
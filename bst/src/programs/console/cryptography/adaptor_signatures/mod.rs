@@ -0,0 +1,140 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod adaptor_pre_signature_verification;
+mod adaptor_pre_signing;
+mod adaptor_secret_extraction;
+mod adaptor_signature_completion;
+mod dleq_proof_generation;
+mod dleq_proof_verification;
+
+use crate::{
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::{self, ProjectivePoint},
+    programs::{
+        exit_result_handlers::ProgramExitResultHandler,
+        program_lists::{ProgramList, ProgramListProgram, ProgramSelector},
+        Program,
+    },
+    system_services::SystemServices,
+    ui::console::prompt_for_fixed_length_bytes,
+    String16,
+};
+use adaptor_pre_signature_verification::ConsoleAdaptorPreSignatureVerificationProgram;
+use adaptor_pre_signing::ConsoleAdaptorPreSigningProgram;
+use adaptor_secret_extraction::ConsoleAdaptorSecretExtractionProgram;
+use adaptor_signature_completion::ConsoleAdaptorSignatureCompletionProgram;
+use alloc::sync::Arc;
+use dleq_proof_generation::ConsoleDleqProofGenerationProgram;
+use dleq_proof_verification::ConsoleDleqProofVerificationProgram;
+use macros::s16;
+
+pub fn get_adaptor_signature_program_list<
+    'a,
+    TSystemServices: SystemServices,
+    TProgramSelector: ProgramSelector + 'static,
+    TProgramExitResultHandler: ProgramExitResultHandler + 'static,
+>(
+    system_services: &TSystemServices,
+    program_selector: &TProgramSelector,
+    exit_result_handler: &TProgramExitResultHandler,
+) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
+    let programs: [Arc<dyn Program>; 6] = [
+        Arc::from(ConsoleAdaptorPreSigningProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleAdaptorPreSignatureVerificationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleAdaptorSignatureCompletionProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleAdaptorSecretExtractionProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleDleqProofGenerationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleDleqProofVerificationProgram::from(
+            system_services.clone(),
+        )),
+    ];
+
+    ProgramList::from(
+        Arc::from(programs),
+        s16!("Adaptor Signature & DLEQ Proof Programs"),
+    )
+    .as_program(program_selector.clone(), exit_result_handler.clone())
+}
+
+fn prompt_for_secret<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16<'static>,
+    label: String16<'static>,
+) -> Option<([u8; 32], [u8; 33])> {
+    // Returns the secret and its public point, re-prompting until the secret is a valid secp256k1 private key.
+    loop {
+        let mut secret = match prompt_for_fixed_length_bytes::<32, TSystemServices>(
+            system_services,
+            cancel_prompt,
+            label,
+        ) {
+            Some(s) => s,
+            None => return None,
+        };
+
+        match secp256k1::try_derive_serialized_public_key(&secret) {
+            Some(p) => return Some((secret, p)),
+            None => {
+                secret.fill(0);
+                system_services
+                    .get_console_out()
+                    .in_colours(constants::ERROR_COLOURS, |c| {
+                        c.output_utf16_line(s16!("Invalid secp256k1 private key."))
+                    });
+            }
+        }
+    }
+}
+
+fn prompt_for_point<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16<'static>,
+    label: String16<'static>,
+) -> Option<[u8; 33]> {
+    // Re-prompts until the bytes are a valid compressed secp256k1 point.
+    loop {
+        let point = match prompt_for_fixed_length_bytes::<33, TSystemServices>(
+            system_services,
+            cancel_prompt,
+            label,
+        ) {
+            Some(p) => p,
+            None => return None,
+        };
+
+        if ProjectivePoint::try_from_serialized(&point).is_some() {
+            return Some(point);
+        }
+
+        system_services
+            .get_console_out()
+            .in_colours(constants::ERROR_COLOURS, |c| {
+                c.output_utf16_line(s16!("Invalid compressed secp256k1 point."))
+            });
+    }
+}
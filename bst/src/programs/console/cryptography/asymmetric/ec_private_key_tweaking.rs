@@ -0,0 +1,135 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    programs::{
+        console::{
+            cryptography::asymmetric::{
+                prompt_for_curve_selection, prompt_for_scalar, prompt_for_tweak_operation,
+                TweakOperation,
+            },
+            write_bytes,
+        },
+        Program, ProgramExitResult,
+    },
+    system_services::SystemServices,
+    ui::console::{prompt_for_clipboard_write, ConsoleUiTitle, ConsoleWriteable},
+    String16,
+};
+use alloc::vec;
+use macros::s16;
+
+pub struct ConsoleEllipticCurvePrivateKeyTweakingProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices>
+    ConsoleEllipticCurvePrivateKeyTweakingProgram<TSystemServices>
+{
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleEllipticCurvePrivateKeyTweakingProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Elliptic Curve Private Key Tweaking")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!("This program tweaks a private key k with a scalar t, calculating k + t (mod n), or k * t (mod n)."))
+            .output_utf16_line(s16!("The corresponding public keys can be checked with the public key tweaking program."));
+        const CANCEL_PROMPT: String16 = s16!("Cancel private key tweaking?");
+
+        // Select a curve.
+        let curve = match prompt_for_curve_selection(&self.system_services, CANCEL_PROMPT) {
+            None => return ProgramExitResult::UserCancelled,
+            Some(c) => c,
+        };
+
+        let operation = match prompt_for_tweak_operation(&self.system_services, CANCEL_PROMPT) {
+            Some(o) => o,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut private_key = match prompt_for_scalar(
+            &self.system_services,
+            &curve,
+            CANCEL_PROMPT,
+            s16!("Private Key"),
+            false,
+        ) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        // Adding zero is a no-op, but it is a valid tweak; multiplying by zero is not.
+        let mut tweak = match prompt_for_scalar(
+            &self.system_services,
+            &curve,
+            CANCEL_PROMPT,
+            s16!("Tweak"),
+            operation == TweakOperation::Add,
+        ) {
+            Some(t) => t,
+            None => {
+                private_key.zero();
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let context = (curve.point_multiplication_context)();
+        let success = match operation {
+            TweakOperation::Add => context.try_tweak_add_scalar(&mut private_key, &tweak),
+            TweakOperation::Multiply => context.try_tweak_multiply_scalar(&mut private_key, &tweak),
+        };
+
+        tweak.zero();
+        if !success {
+            private_key.zero();
+            return s16!("The tweaked private key is zero, which is not a valid private key.")
+                .to_program_error();
+        }
+
+        // Pad the private key to the curve's key length.
+        let mut private_key_bytes = vec![0u8; curve.key_length];
+        assert!(private_key.try_copy_be_bytes_to(&mut private_key_bytes));
+        private_key.zero();
+
+        write_bytes(
+            &self.system_services,
+            s16!("Tweaked Private Key"),
+            &private_key_bytes,
+        );
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(curve.private_key_clipboard_name, private_key_bytes.into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
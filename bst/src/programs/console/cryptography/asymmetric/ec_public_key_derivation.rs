@@ -15,16 +15,23 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
+    bitcoin::{addresses, try_wif_decode},
     console_out::ConsoleOut,
     constants,
+    cryptography::asymmetric::ecc::secp256k1,
+    integers::BigUnsigned,
     programs::{
-        console::{cryptography::asymmetric::prompt_for_curve_selection, write_bytes},
+        console::{
+            bitcoin::prompt_for_network, cryptography::asymmetric::prompt_for_curve_selection,
+            write_bytes,
+        },
         Program, ProgramExitResult,
     },
     system_services::SystemServices,
     ui::{
         console::{
-            prompt_for_clipboard_write, prompt_for_data_input, ConsoleUiTitle, ConsoleWriteable,
+            prompt_for_clipboard_write, prompt_for_data_input, ConsoleUiKeyValue, ConsoleUiTitle,
+            ConsoleWriteable,
         },
         DataInput, DataInputType,
     },
@@ -58,52 +65,77 @@ impl<TSystemServices: SystemServices> Program
 
         ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
         console
-            .output_utf16_line(s16!("This program derives a public key from a private key using Elliptic Curve cryptography."));
+            .output_utf16_line(s16!("This program derives a public key from a private key using Elliptic Curve cryptography."))
+            .output_utf16_line(s16!("secp256k1 private keys can also be input as WIF (Wallet Import Format) text, such as from a paper wallet; the Bitcoin addresses of secp256k1 keys are derived alongside their public keys."));
         const CANCEL_PROMPT: String16 = s16!("Cancel public key derivation?");
 
         // Select a curve.
-        let mut curve = match prompt_for_curve_selection(&self.system_services, CANCEL_PROMPT) {
+        let curve = match prompt_for_curve_selection(&self.system_services, CANCEL_PROMPT) {
             None => return ProgramExitResult::UserCancelled,
             Some(c) => c,
         };
 
-        // Get the private key to derive a public key from.
-        let mut private_key = loop {
-            match prompt_for_data_input(
+        // Get the private key to derive a public key from; secp256k1 keys can also be input as WIF text.
+        let (mut private_key, wif) = loop {
+            let (mut b, wif) = match prompt_for_data_input(
                 None,
-                &[DataInputType::Number],
+                if curve.supports_wif {
+                    &[DataInputType::Number, DataInputType::Text]
+                } else {
+                    &[DataInputType::Number]
+                },
                 &self.system_services,
                 CANCEL_PROMPT,
                 s16!("Private Key"),
             ) {
-                DataInput::Number(mut b) => {
-                    if b.is_zero() {
-                        // Zero is an invalid private key.
-                        console.in_colours(constants::ERROR_COLOURS, |c| {
-                            c.line_start()
-                                .new_line()
-                                .output_utf16(s16!("Zero is not a valid private key."))
-                        });
-
-                        continue;
-                    } else if b.cmp(curve.n) != Ordering::Less {
-                        // Private keys must be less than the N value of the curve.
-                        console.in_colours(constants::ERROR_COLOURS, |c| {
-                            c.line_start().new_line()
-                                .output_utf16_line(s16!(
-                                    "The input is >= the curve's N value; we cannot derive a public key from it directly."))
-                                .output_utf16_line(s16!("You may wish to use the private key fitter program."))
-                        });
-
-                        b.zero();
-                        continue;
+                DataInput::Number(b) => (b, None),
+                DataInput::Text(mut t) => {
+                    let wif = try_wif_decode(&t);
+                    t.fill(0);
+                    match wif {
+                        Some((mut k, network, is_compressed)) => {
+                            let b = BigUnsigned::from_be_bytes(&k);
+                            k.fill(0);
+                            (b, Some((network, is_compressed)))
+                        }
+                        None => {
+                            console.in_colours(constants::ERROR_COLOURS, |c| {
+                                c.line_start().new_line().output_utf16(s16!(
+                                    "Text private keys must be valid WIF (Wallet Import Format) keys."
+                                ))
+                            });
+
+                            continue;
+                        }
                     }
-
-                    // The key was not zero, and not oversized; we can use it.
-                    break b;
                 }
                 _ => return ProgramExitResult::UserCancelled,
             };
+
+            if b.is_zero() {
+                // Zero is an invalid private key.
+                console.in_colours(constants::ERROR_COLOURS, |c| {
+                    c.line_start()
+                        .new_line()
+                        .output_utf16(s16!("Zero is not a valid private key."))
+                });
+
+                continue;
+            } else if b.cmp(curve.n) != Ordering::Less {
+                // Private keys must be less than the N value of the curve.
+                console.in_colours(constants::ERROR_COLOURS, |c| {
+                    c.line_start().new_line()
+                        .output_utf16_line(s16!(
+                            "The input is >= the curve's N value; we cannot derive a public key from it directly."))
+                        .output_utf16_line(s16!("You may wish to use the private key fitter program."))
+                });
+
+                b.zero();
+                continue;
+            }
+
+            // The key was not zero, and not oversized; we can use it.
+            break (b, wif);
         };
 
         console.in_colours(constants::SUCCESS_COLOURS, |c| {
@@ -112,22 +144,77 @@ impl<TSystemServices: SystemServices> Program
                 .output_utf16(s16!("Deriving public key..."))
         });
 
-        let point =
-            match curve
-                .multiplication_context
-                .multiply_point(curve.g_x, curve.g_y, &private_key)
-            {
-                Some(point) => point,
-                None => return s16!("Failed to derive a public key.").to_program_error(),
-            };
+        // Derive and serialize the public key.
+        let serialized_point = match (curve.public_key_deriver)(&private_key) {
+            Some(serialized_point) => serialized_point,
+            None => return s16!("Failed to derive a public key.").to_program_error(),
+        };
 
         // We're done with the private key; zero it.
         private_key.zero();
 
-        // Serialize the public key for output.
-        let serialized_point = match (curve.point_serializer)(point) {
-            Some(serialized_point) => serialized_point,
-            None => return s16!("Failed to serialize the public key.").to_program_error(),
+        let serialized_point = if curve.supports_wif {
+            // WIF keys specify their network and public key format; other secp256k1 keys use the compressed format.
+            let (network, is_compressed) = match wif {
+                Some(w) => w,
+                None => match prompt_for_network(&self.system_services, CANCEL_PROMPT) {
+                    Some(n) => (n, true),
+                    None => return ProgramExitResult::UserCancelled,
+                },
+            };
+
+            let compressed_public_key: [u8; 33] = match serialized_point[..].try_into() {
+                Ok(k) => k,
+                Err(_) => return s16!("Failed to derive a public key.").to_program_error(),
+            };
+
+            let uncompressed_public_key =
+                match secp256k1::try_decode_public_key(&compressed_public_key) {
+                    Some(p) => match p.try_serialize_uncompressed() {
+                        Some(k) => k,
+                        None => return s16!("Failed to derive a public key.").to_program_error(),
+                    },
+                    None => return s16!("Failed to derive a public key.").to_program_error(),
+                };
+
+            // Write out the key's addresses.
+            console.line_start().new_line();
+            ConsoleUiKeyValue::from(s16!("Network"), network.into()).write_to(&console);
+            if is_compressed {
+                let mut x_only_public_key = [0u8; 32];
+                x_only_public_key.copy_from_slice(&compressed_public_key[1..]);
+                for (label, address) in [
+                    (
+                        s16!("P2PKH"),
+                        Some(addresses::p2pkh(&compressed_public_key, network)),
+                    ),
+                    (
+                        s16!("P2SH-P2WPKH"),
+                        Some(addresses::p2sh_p2wpkh(&compressed_public_key, network)),
+                    ),
+                    (
+                        s16!("P2WPKH"),
+                        Some(addresses::p2wpkh(&compressed_public_key, network)),
+                    ),
+                    (s16!("P2TR"), addresses::p2tr(&x_only_public_key, network)),
+                ] {
+                    if let Some(a) = address {
+                        ConsoleUiKeyValue::from(label, String16::from(&a)).write_to(&console);
+                    }
+                }
+
+                serialized_point
+            } else {
+                // Uncompressed WIF keys are only used for legacy addresses.
+                ConsoleUiKeyValue::from(
+                    s16!("P2PKH"),
+                    String16::from(&addresses::p2pkh(&uncompressed_public_key, network)),
+                )
+                .write_to(&console);
+                uncompressed_public_key[..].into()
+            }
+        } else {
+            serialized_point
         };
 
         write_bytes(&self.system_services, s16!("Public Key"), &serialized_point);
@@ -0,0 +1,130 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    programs::{
+        console::{
+            cryptography::asymmetric::{prompt_for_curve_selection, prompt_for_public_key},
+            write_bytes,
+        },
+        Program, ProgramExitResult,
+    },
+    system_services::SystemServices,
+    ui::console::{prompt_for_clipboard_write, ConsoleUiTitle, ConsoleWriteable},
+    String16,
+};
+use alloc::{sync::Arc, vec::Vec};
+use macros::s16;
+
+pub struct ConsoleEllipticCurvePublicKeyFormatConversionProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices>
+    ConsoleEllipticCurvePublicKeyFormatConversionProgram<TSystemServices>
+{
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleEllipticCurvePublicKeyFormatConversionProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Elliptic Curve Public Key Format Conversion")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!("This program validates a compressed, uncompressed or x-only (BIP 340) public key, and outputs it in every other encoding."))
+            .output_utf16_line(s16!("x-only public keys are only defined for secp256k1, and are assumed to have an even Y coordinate."));
+        const CANCEL_PROMPT: String16 = s16!("Cancel public key format conversion?");
+
+        // Select a curve.
+        let curve = match prompt_for_curve_selection(&self.system_services, CANCEL_PROMPT) {
+            None => return ProgramExitResult::UserCancelled,
+            Some(c) => c,
+        };
+
+        // Get a public key, and check it's a valid point on the curve.
+        let uncompressed = match prompt_for_public_key(&self.system_services, &curve, CANCEL_PROMPT)
+        {
+            Some(p) => p,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        console.in_colours(constants::SUCCESS_COLOURS, |c| {
+            c.line_start()
+                .new_line()
+                .output_utf16_line(s16!("The public key is a valid point on the curve."))
+        });
+
+        // Every other encoding can be built from the uncompressed encoding; 0x04 || X || Y.
+        let x = &uncompressed[1..curve.key_length + 1];
+        let mut compressed = Vec::with_capacity(curve.key_length + 1);
+        compressed.push(0x02 | (uncompressed[uncompressed.len() - 1] & 1));
+        compressed.extend_from_slice(x);
+
+        for (label, clipboard_name, bytes) in [
+            (
+                s16!("Compressed Public Key"),
+                curve.public_key_clipboard_name,
+                Arc::<[u8]>::from(compressed),
+            ),
+            (
+                s16!("Uncompressed Public Key"),
+                s16!("Uncompressed public key"),
+                uncompressed.clone(),
+            ),
+        ] {
+            write_bytes(&self.system_services, label, &bytes);
+            prompt_for_clipboard_write(
+                &self.system_services,
+                ClipboardEntry::Bytes(clipboard_name, bytes),
+            );
+        }
+
+        if !curve.supports_x_only {
+            return ProgramExitResult::Success;
+        }
+
+        write_bytes(&self.system_services, s16!("x-only Public Key"), x);
+        if uncompressed[uncompressed.len() - 1] & 1 == 1 {
+            // BIP 340 x-only keys always refer to the point with an even Y coordinate; the negation of this one.
+            console.in_colours(constants::WARNING_COLOURS, |c| {
+                c.line_start()
+                    .new_line()
+                    .output_utf16_line(s16!("This public key has an odd Y coordinate; the x-only key represents its negation."))
+                    .output_utf16_line(s16!("To sign for the x-only key, the private key must be negated (n - d) first; BIP 340 signers do this automatically."))
+            });
+        }
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("x-only public key"), x.into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,147 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    programs::{
+        console::{
+            cryptography::asymmetric::{
+                prompt_for_curve_selection, prompt_for_public_key, prompt_for_scalar,
+                prompt_for_tweak_operation, TweakOperation,
+            },
+            write_bytes,
+        },
+        Program, ProgramExitResult,
+    },
+    system_services::SystemServices,
+    ui::console::{prompt_for_clipboard_write, ConsoleUiTitle, ConsoleWriteable},
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleEllipticCurvePublicKeyTweakingProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices>
+    ConsoleEllipticCurvePublicKeyTweakingProgram<TSystemServices>
+{
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleEllipticCurvePublicKeyTweakingProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Elliptic Curve Public Key Tweaking")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program tweaks a public key P with a scalar t, calculating P + tG, or tP."
+            ))
+            .output_utf16_line(s16!(
+                "For a private key k with public key P, (k + t)G = P + tG, and (k * t)G = tP."
+            ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel public key tweaking?");
+
+        // Select a curve.
+        let curve = match prompt_for_curve_selection(&self.system_services, CANCEL_PROMPT) {
+            None => return ProgramExitResult::UserCancelled,
+            Some(c) => c,
+        };
+
+        let operation = match prompt_for_tweak_operation(&self.system_services, CANCEL_PROMPT) {
+            Some(o) => o,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let public_key = match prompt_for_public_key(&self.system_services, &curve, CANCEL_PROMPT) {
+            Some(p) => p,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let tweak = match prompt_for_scalar(
+            &self.system_services,
+            &curve,
+            CANCEL_PROMPT,
+            s16!("Tweak"),
+            operation == TweakOperation::Add,
+        ) {
+            Some(t) => t,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        console.in_colours(constants::SUCCESS_COLOURS, |c| {
+            c.line_start()
+                .new_line()
+                .output_utf16(s16!("Tweaking public key..."))
+        });
+
+        // The public key has already been validated; decoding it again with the generic context can't fail.
+        let mut context = (curve.point_multiplication_context)();
+        let mut point = context.try_decode_point(&public_key).unwrap();
+        let tweaked = match operation {
+            TweakOperation::Add => {
+                if context.try_tweak_add_point(&mut point, curve.g_x, curve.g_y, &tweak) {
+                    Some(point)
+                } else {
+                    None
+                }
+            }
+            TweakOperation::Multiply => {
+                let (x, y) = point.borrow_coordinates_mut();
+                context.multiply_point(x.borrow_unsigned(), y.borrow_unsigned(), &tweak)
+            }
+        };
+
+        let serialized = match tweaked {
+            Some(p) => match (curve.public_key_serializer)(p) {
+                Some(s) => s,
+                None => {
+                    return s16!("Failed to serialize the tweaked public key.").to_program_error()
+                }
+            },
+            None => {
+                return s16!(
+                "The tweaked public key is the point at infinity, which is not a valid public key."
+            )
+                .to_program_error()
+            }
+        };
+
+        write_bytes(
+            &self.system_services,
+            s16!("Tweaked Public Key"),
+            &serialized,
+        );
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(curve.public_key_clipboard_name, serialized),
+        );
+
+        ProgramExitResult::Success
+    }
+}
@@ -15,13 +15,16 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 mod ec_private_key_fitting;
+mod ec_private_key_tweaking;
 mod ec_public_key_derivation;
+mod ec_public_key_format_conversion;
+mod ec_public_key_tweaking;
 
 use crate::{
     console_out::ConsoleOut,
     constants,
     cryptography::asymmetric::ecc::{
-        secp256k1, EllipticCurvePoint, EllipticCurvePointMultiplicationContext,
+        secp256k1, secp256r1, EllipticCurvePoint, EllipticCurvePointMultiplicationContext,
     },
     integers::BigUnsigned,
     programs::{
@@ -31,14 +34,21 @@ use crate::{
     },
     system_services::SystemServices,
     ui::{
-        console::{ConsoleUiConfirmationPrompt, ConsoleUiList, ConsoleUiTitle, ConsoleWriteable},
-        ConfirmationPrompt,
+        console::{
+            prompt_for_data_input, ConsoleUiConfirmationPrompt, ConsoleUiList, ConsoleUiTitle,
+            ConsoleWriteable,
+        },
+        ConfirmationPrompt, DataInput, DataInputType,
     },
     String16,
 };
-use alloc::sync::Arc;
+use alloc::{format, sync::Arc};
+use core::cmp::Ordering;
 use ec_private_key_fitting::ConsoleEllipticCurvePrivateKeyFittingProgram;
+use ec_private_key_tweaking::ConsoleEllipticCurvePrivateKeyTweakingProgram;
 use ec_public_key_derivation::ConsoleEllipticCurvePublicKeyDerivationProgram;
+use ec_public_key_format_conversion::ConsoleEllipticCurvePublicKeyFormatConversionProgram;
+use ec_public_key_tweaking::ConsoleEllipticCurvePublicKeyTweakingProgram;
 use macros::s16;
 
 pub fn get_asymmetric_cryptography_program_list<
@@ -51,13 +61,22 @@ pub fn get_asymmetric_cryptography_program_list<
     program_selector: &TProgramSelector,
     exit_result_handler: &TProgramExitResultHandler,
 ) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
-    let programs: [Arc<dyn Program>; 2] = [
+    let programs: [Arc<dyn Program>; 5] = [
         Arc::from(ConsoleEllipticCurvePrivateKeyFittingProgram::from(
             system_services.clone(),
         )),
         Arc::from(ConsoleEllipticCurvePublicKeyDerivationProgram::from(
             system_services.clone(),
         )),
+        Arc::from(ConsoleEllipticCurvePublicKeyFormatConversionProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleEllipticCurvePrivateKeyTweakingProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleEllipticCurvePublicKeyTweakingProgram::from(
+            system_services.clone(),
+        )),
     ];
 
     ProgramList::from(
@@ -75,6 +94,11 @@ struct CurveOption {
 impl CurveOption {
     const SECP256K1: Self = Self::from(Self::secp256k1_context_builder, s16!("secp256k1"));
 
+    const SECP256R1: Self = Self::from(
+        Self::secp256r1_context_builder,
+        s16!("secp256r1 (NIST P-256)"),
+    );
+
     pub const fn from(
         curve_context_builder: fn() -> SelectedCurveContext,
         name: String16<'static>,
@@ -87,7 +111,27 @@ impl CurveOption {
 
     fn secp256k1_context_builder() -> SelectedCurveContext {
         SelectedCurveContext::from(
-            secp256k1::point_multiplication_context(),
+            |k| {
+                let mut private_key = [0u8; 32];
+                if !k.try_copy_be_bytes_to(&mut private_key[32 - k.byte_count()..]) {
+                    return None;
+                }
+
+                let public_key = secp256k1::try_derive_serialized_public_key(&private_key);
+                private_key.fill(0);
+                match public_key {
+                    Some(b) => Some(b[..].into()),
+                    None => None,
+                }
+            },
+            |b| match secp256k1::try_decode_public_key(b) {
+                Some(p) => match p.try_serialize_uncompressed() {
+                    Some(b) => Some(b[..].into()),
+                    None => None,
+                },
+                None => None,
+            },
+            secp256k1::point_multiplication_context,
             |p| match secp256k1::serialized_public_key_bytes(p) {
                 Some(b) => Some(b[..].into()),
                 None => None,
@@ -98,6 +142,49 @@ impl CurveOption {
             secp256k1::g_y(),
             secp256k1::n(),
             32,
+            true,
+            true,
+        )
+    }
+
+    fn secp256r1_context_builder() -> SelectedCurveContext {
+        SelectedCurveContext::from(
+            |k| {
+                // There is no dedicated implementation for secp256r1; use the generic multiplication context.
+                let point = match secp256r1::point_multiplication_context().multiply_point(
+                    secp256r1::g_x(),
+                    secp256r1::g_y(),
+                    k,
+                ) {
+                    Some(p) => p,
+                    None => return None,
+                };
+
+                match secp256r1::serialized_public_key_bytes(point) {
+                    Some(b) => Some(b[..].into()),
+                    None => None,
+                }
+            },
+            |b| match secp256r1::point_multiplication_context().try_decode_point(b) {
+                Some(p) => match p.try_serialize_uncompressed::<65>() {
+                    Some(b) => Some(b[..].into()),
+                    None => None,
+                },
+                None => None,
+            },
+            secp256r1::point_multiplication_context,
+            |p| match secp256r1::serialized_public_key_bytes(p) {
+                Some(b) => Some(b[..].into()),
+                None => None,
+            },
+            s16!("secp256r1 private key"),
+            s16!("secp256r1 public key"),
+            secp256r1::g_x(),
+            secp256r1::g_y(),
+            secp256r1::n(),
+            32,
+            false,
+            false,
         )
     }
 }
@@ -109,33 +196,49 @@ impl ConsoleWriteable for CurveOption {
 }
 
 struct SelectedCurveContext {
-    multiplication_context: EllipticCurvePointMultiplicationContext,
-    point_serializer: fn(EllipticCurvePoint) -> Option<Arc<[u8]>>,
+    public_key_deriver: fn(&BigUnsigned) -> Option<Arc<[u8]>>,
+    // Decodes a compressed, uncompressed or x-only public key, returning the validated point's uncompressed encoding.
+    public_key_decoder: fn(&[u8]) -> Option<Arc<[u8]>>,
+    // The generic multiplication context; used where we need to operate on arbitrary points, rather than just derive public keys.
+    point_multiplication_context: fn() -> EllipticCurvePointMultiplicationContext,
+    public_key_serializer: fn(EllipticCurvePoint) -> Option<Arc<[u8]>>,
     private_key_clipboard_name: String16<'static>,
     public_key_clipboard_name: String16<'static>,
     g_x: &'static BigUnsigned,
     g_y: &'static BigUnsigned,
     n: &'static BigUnsigned,
     key_length: usize,
+    // Whether private keys can be input in Bitcoin's Wallet Import Format.
+    supports_wif: bool,
+    // Whether public keys can be x-only (BIP 340); x-only keys are only defined for secp256k1.
+    supports_x_only: bool,
 }
 
 impl SelectedCurveContext {
     pub const fn from(
-        multiplication_context: EllipticCurvePointMultiplicationContext,
-        point_serializer: fn(EllipticCurvePoint) -> Option<Arc<[u8]>>,
+        public_key_deriver: fn(&BigUnsigned) -> Option<Arc<[u8]>>,
+        public_key_decoder: fn(&[u8]) -> Option<Arc<[u8]>>,
+        point_multiplication_context: fn() -> EllipticCurvePointMultiplicationContext,
+        public_key_serializer: fn(EllipticCurvePoint) -> Option<Arc<[u8]>>,
         private_key_clipboard_name: String16<'static>,
         public_key_clipboard_name: String16<'static>,
         g_x: &'static BigUnsigned,
         g_y: &'static BigUnsigned,
         n: &'static BigUnsigned,
         key_length: usize,
+        supports_wif: bool,
+        supports_x_only: bool,
     ) -> Self {
         Self {
+            point_multiplication_context,
             private_key_clipboard_name,
             public_key_clipboard_name,
-            multiplication_context,
-            point_serializer,
+            public_key_serializer,
+            public_key_deriver,
+            public_key_decoder,
             key_length,
+            supports_wif,
+            supports_x_only,
             g_x,
             g_y,
             n,
@@ -152,7 +255,7 @@ fn prompt_for_curve_selection<TSystemServices: SystemServices>(
         match ConsoleUiList::from(
             ConsoleUiTitle::from(s16!("Elliptic Curve"), constants::SMALL_TITLE),
             constants::SELECT_LIST,
-            &[CurveOption::SECP256K1][..],
+            &[CurveOption::SECP256K1, CurveOption::SECP256R1][..],
         )
         .prompt_for_selection(system_services)
         {
@@ -167,3 +270,150 @@ fn prompt_for_curve_selection<TSystemServices: SystemServices>(
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum TweakOperation {
+    Add,
+    Multiply,
+}
+
+impl ConsoleWriteable for TweakOperation {
+    fn write_to<T: ConsoleOut>(&self, console: &T) {
+        console.output_utf16(match self {
+            TweakOperation::Add => s16!("Add"),
+            TweakOperation::Multiply => s16!("Multiply"),
+        });
+    }
+}
+
+fn prompt_for_tweak_operation<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancellation_prompt: String16,
+) -> Option<TweakOperation> {
+    loop {
+        system_services.get_console_out().line_start().new_line();
+        match ConsoleUiList::from(
+            ConsoleUiTitle::from(s16!("Tweak Operation"), constants::SMALL_TITLE),
+            constants::SELECT_LIST,
+            &[TweakOperation::Add, TweakOperation::Multiply][..],
+        )
+        .prompt_for_selection(system_services)
+        {
+            Some((o, _, _)) => break Some(*o),
+            None => {
+                if ConsoleUiConfirmationPrompt::from(system_services)
+                    .prompt_for_confirmation(cancellation_prompt)
+                {
+                    break None;
+                }
+            }
+        }
+    }
+}
+
+fn prompt_for_scalar<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    curve: &SelectedCurveContext,
+    cancellation_prompt: String16,
+    label: String16<'static>,
+    allow_zero: bool,
+) -> Option<BigUnsigned> {
+    let console = system_services.get_console_out();
+    loop {
+        match prompt_for_data_input(
+            None,
+            &[DataInputType::Number],
+            system_services,
+            cancellation_prompt,
+            label,
+        ) {
+            DataInput::Number(mut b) => {
+                if !allow_zero && b.is_zero() {
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.line_start()
+                            .new_line()
+                            .output_utf16(label)
+                            .output_utf16_line(s16!(" cannot be zero."))
+                    });
+                } else if b.cmp(curve.n) != Ordering::Less {
+                    // Scalars must be less than the N value of the curve.
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.line_start()
+                            .new_line()
+                            .output_utf16(label)
+                            .output_utf16_line(s16!(" must be less than the curve's N value."))
+                    });
+
+                    b.zero();
+                } else {
+                    break Some(b);
+                }
+            }
+            _ => break None,
+        }
+    }
+}
+
+fn prompt_for_public_key<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    curve: &SelectedCurveContext,
+    cancellation_prompt: String16,
+) -> Option<Arc<[u8]>> {
+    // Returns the uncompressed encoding of a valid compressed, uncompressed or (where the curve supports it) x-only public key.
+    let console = system_services.get_console_out();
+    let encoded_lengths = [
+        curve.key_length + 1,
+        curve.key_length * 2 + 1,
+        curve.key_length,
+    ];
+    let encoded_lengths = if curve.supports_x_only {
+        &encoded_lengths[..]
+    } else {
+        &encoded_lengths[..2]
+    };
+
+    loop {
+        let public_key = match prompt_for_data_input(
+            None,
+            &[DataInputType::Bytes],
+            system_services,
+            cancellation_prompt,
+            s16!("Public Key"),
+        ) {
+            DataInput::Bytes(b) => b,
+            _ => return None,
+        };
+
+        if !encoded_lengths.contains(&public_key.len()) {
+            console.in_colours(constants::ERROR_COLOURS, |c| {
+                c.line_start()
+                    .new_line()
+                    .output_utf32_line(&if curve.supports_x_only {
+                        format!(
+                            "Public keys on this curve must be {}, {} or {} bytes long.\0",
+                            encoded_lengths[0], encoded_lengths[1], encoded_lengths[2]
+                        )
+                    } else {
+                        format!(
+                            "Public keys on this curve must be {} or {} bytes long.\0",
+                            encoded_lengths[0], encoded_lengths[1]
+                        )
+                    })
+            });
+
+            continue;
+        }
+
+        match (curve.public_key_decoder)(&public_key) {
+            Some(p) => break Some(p),
+            None => {
+                // Either the prefix was invalid, the coordinates were out of range, or the point isn't on the curve.
+                console.in_colours(constants::ERROR_COLOURS, |c| {
+                    c.line_start().new_line().output_utf16_line(s16!(
+                        "The input is not a valid public key on the selected curve."
+                    ))
+                });
+            }
+        }
+    }
+}
@@ -23,8 +23,8 @@ use crate::{
     clipboard::ClipboardEntry,
     console_out::ConsoleOut,
     constants,
-    cryptography::asymmetric::ecc::secp256k1::{self, serialized_public_key_bytes},
-    integers::{BigUnsigned, NumericBase, NumericBases},
+    cryptography::asymmetric::ecc::secp256k1,
+    integers::{NumericBase, NumericBases},
     programs::{console::write_string_program_output, Program, ProgramExitResult},
     system_services::SystemServices,
     ui::{
@@ -188,47 +188,29 @@ impl<TSystemServices: SystemServices> Program
         if ConsoleUiConfirmationPrompt::from(&self.system_services)
             .prompt_for_confirmation(s16!("Derive extended public key?"))
         {
-            // Build a secp256k1 multiplication context.
-            let mut multiplication_context = secp256k1::point_multiplication_context();
-
-            // Extract the private key material, minus its identifying byte.
-            let mut private_key =
-                BigUnsigned::from_be_bytes(&serialized_private_key.key_material()[1..]);
-
             console.in_colours(constants::SUCCESS_COLOURS, |c| {
                 c.line_start()
                     .new_line()
                     .output_utf16(s16!("Deriving public key..."))
             });
 
-            // Derive the public key for the private key.
-            let point = match multiplication_context.multiply_point(
-                secp256k1::g_x(),
-                secp256k1::g_y(),
-                &private_key,
+            // Derive the public key for the private key material, minus its identifying byte.
+            let public_key = match secp256k1::try_derive_serialized_public_key(
+                &serialized_private_key.key_material()[1..],
             ) {
-                Some(p) => p,
+                Some(k) => k,
                 None => return s16!("Failed to derive public key.").to_program_error(),
             };
 
-            // Zero the extracted private key; we're done with it.
-            private_key.zero();
-
             // Serialize the public key.
-            let serialized_public_key = match serialized_private_key.build_public_key_variant_from(
-                match serialized_public_key_bytes(point) {
+            let serialized_public_key =
+                match serialized_private_key.build_public_key_variant_from(public_key) {
                     Some(k) => k,
                     None => {
-                        return s16!("Failed to serialize secp256k1 public key.").to_program_error()
+                        return s16!("Failed to serialize BIP 32 extended public key.")
+                            .to_program_error()
                     }
-                },
-            ) {
-                Some(k) => k,
-                None => {
-                    return s16!("Failed to serialize BIP 32 extended public key.")
-                        .to_program_error()
-                }
-            };
+                };
 
             // Base-58 encode the extended public key with a checksum.
             let base58_key = base_58_encode_with_checksum(&serialized_public_key.as_bytes());
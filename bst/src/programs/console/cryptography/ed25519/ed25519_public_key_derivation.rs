@@ -0,0 +1,87 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::edwards::ed25519::{Ed25519KeyPair, SEED_LENGTH},
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_clipboard_write, prompt_for_fixed_length_bytes, ConsoleUiTitle, ConsoleWriteable,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleEd25519PublicKeyDerivationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleEd25519PublicKeyDerivationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleEd25519PublicKeyDerivationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Ed25519 Public Key Derivation")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!("This program derives an Ed25519 (RFC 8032) public key from a 32 byte private key seed."))
+            .output_utf16_line(s16!("Any 32 bytes of entropy are a valid seed; you may wish to use the output of one of the entropy collection programs."));
+
+        // Get the seed to derive a public key from.
+        let mut seed = match prompt_for_fixed_length_bytes::<SEED_LENGTH, TSystemServices>(
+            &self.system_services,
+            s16!("Cancel public key derivation?"),
+            s16!("Private Key Seed"),
+        ) {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        console.in_colours(constants::SUCCESS_COLOURS, |c| {
+            c.line_start()
+                .new_line()
+                .output_utf16(s16!("Deriving public key..."))
+        });
+
+        // Derive the key pair; we only want the public key, so zero everything else immediately.
+        let mut key_pair = Ed25519KeyPair::from_seed(&seed);
+        let public_key = *key_pair.public_key();
+        key_pair.zero();
+        seed.fill(0);
+
+        write_bytes(&self.system_services, s16!("Public Key"), &public_key);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("Ed25519 public key"), public_key[..].into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,105 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::edwards::ed25519::{verify, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH},
+    programs::{Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_bytes_from_any_data_type, prompt_for_fixed_length_bytes,
+            ConsoleUiContinuePrompt, ConsoleUiTitle, ConsoleWriteable,
+        },
+        ContinuePrompt,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleEd25519SignatureVerificationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleEd25519SignatureVerificationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleEd25519SignatureVerificationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Ed25519 Signature Verification")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program verifies an Ed25519 (RFC 8032) signature over a message."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel signature verification?");
+
+        let public_key = match prompt_for_fixed_length_bytes::<PUBLIC_KEY_LENGTH, TSystemServices>(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Public Key"),
+        ) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        // Text is verified as its UTF8 bytes.
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => return e,
+        };
+
+        let signature = match prompt_for_fixed_length_bytes::<SIGNATURE_LENGTH, TSystemServices>(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Signature"),
+        ) {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        console.line_start().new_line();
+        if verify(&public_key, &message, &signature) {
+            console.in_colours(constants::SUCCESS_COLOURS, |c| {
+                c.output_utf16_line(s16!("Signature is valid."))
+            });
+        } else {
+            // We don't distinguish between a malformed public key or signature, and a signature which doesn't match the message;
+            // either way, the signature doesn't prove anything.
+            console.in_colours(constants::ERROR_COLOURS, |c| {
+                c.output_utf16_line(s16!("Signature is NOT valid."))
+            });
+        }
+
+        ConsoleUiContinuePrompt::from(&self.system_services).prompt_for_continue();
+        ProgramExitResult::Success
+    }
+}
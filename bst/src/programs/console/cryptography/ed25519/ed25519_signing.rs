@@ -0,0 +1,102 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::edwards::ed25519::{Ed25519KeyPair, SEED_LENGTH},
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_bytes_from_any_data_type, prompt_for_clipboard_write,
+        prompt_for_fixed_length_bytes, ConsoleUiTitle, ConsoleWriteable,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleEd25519SigningProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleEd25519SigningProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for ConsoleEd25519SigningProgram<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("Ed25519 Message Signing")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program signs a message with an Ed25519 (RFC 8032) private key seed."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel message signing?");
+
+        // Get the seed to sign with.
+        let mut seed = match prompt_for_fixed_length_bytes::<SEED_LENGTH, TSystemServices>(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Private Key Seed"),
+        ) {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut key_pair = Ed25519KeyPair::from_seed(&seed);
+        seed.fill(0);
+
+        // Get the message to sign; text is signed as its UTF8 bytes.
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => {
+                key_pair.zero();
+                return e;
+            }
+        };
+
+        console.in_colours(constants::SUCCESS_COLOURS, |c| {
+            c.line_start()
+                .new_line()
+                .output_utf16(s16!("Signing message..."))
+        });
+
+        let signature = key_pair.sign(&message);
+        let public_key = *key_pair.public_key();
+        key_pair.zero();
+
+        write_bytes(&self.system_services, s16!("Public Key"), &public_key);
+        write_bytes(&self.system_services, s16!("Signature"), &signature);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("Ed25519 signature"), signature[..].into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,284 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_seed;
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::frost::{DkgParticipant, ROUND_2_PACKAGE_LENGTH},
+    integers::NumericBases,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_clipboard_write, prompt_for_data_input, prompt_for_fixed_length_bytes,
+            prompt_for_u8, ConsoleUiTitle, ConsoleWriteable,
+        },
+        DataInput, DataInputType,
+    },
+    String16,
+};
+use alloc::{format, vec::Vec};
+use macros::s16;
+
+pub struct ConsoleFrostDistributedKeyGenerationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleFrostDistributedKeyGenerationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleFrostDistributedKeyGenerationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("FROST Distributed Key Generation")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program takes one participant through a FROST distributed key generation, so that no single machine ever holds the group's private key. Every participant runs it at the same time, exchanging packages as they go."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "Round 1 packages are public, but each round 2 package is secret, and must only be given to the participant it is addressed to. The key generation cannot be resumed if cancelled."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel distributed key generation?");
+
+        let threshold = match prompt_for_u8(
+            |t| {
+                if t < 2 {
+                    Some(s16!("The threshold must be at least 2."))
+                } else {
+                    None
+                }
+            },
+            s16!("Threshold"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(t) => t,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let participant_count = match prompt_for_u8(
+            |c| {
+                if c < threshold {
+                    Some(s16!(
+                        "The number of participants cannot be lower than the threshold."
+                    ))
+                } else {
+                    None
+                }
+            },
+            s16!("Participant Count"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(c) => c,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let identifier = match prompt_for_u8(
+            |i| {
+                if i == 0 || i > participant_count {
+                    Some(s16!(
+                        "The identifier must be between 1 and the participant count."
+                    ))
+                } else {
+                    None
+                }
+            },
+            s16!("Our Participant Identifier"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(i) => i,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut coefficient_seed = match prompt_for_seed(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Coefficient Seed"),
+        ) {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let participant =
+            DkgParticipant::try_from(identifier, threshold, participant_count, &coefficient_seed);
+        coefficient_seed.fill(0);
+        let participant = match participant {
+            Ok(p) => p,
+            Err(e) => return e.to_program_error(),
+        };
+
+        write_bytes(
+            &self.system_services,
+            s16!("Our Round 1 Package"),
+            participant.round_1_package(),
+        );
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(
+                s16!("FROST round 1 package"),
+                participant.round_1_package().into(),
+            ),
+        );
+
+        // Collect every other participant's round 1 package, verifying each proof of knowledge as it's entered.
+        let mut round_1_packages: Vec<Vec<u8>> = Vec::with_capacity(participant_count as usize - 1);
+        while round_1_packages.len() < participant_count as usize - 1 {
+            console.line_start().new_line().output_utf32_line(&format!(
+                "Round 1 package {} of {}:\0",
+                round_1_packages.len() + 1,
+                participant_count - 1
+            ));
+
+            let package = match prompt_for_data_input(
+                None,
+                &[DataInputType::Bytes],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Round 1 Package"),
+            ) {
+                DataInput::Bytes(b) => b,
+                _ => return ProgramExitResult::UserCancelled,
+            };
+
+            let error = match participant.try_verify_round_1_package(&package) {
+                Ok((i, _)) => {
+                    if !round_1_packages.iter().any(|p| p[0] == i) {
+                        round_1_packages.push(package);
+                        continue;
+                    }
+
+                    s16!("A round 1 package from that participant was already entered.")
+                }
+                Err(e) => e,
+            };
+
+            console.in_colours(constants::ERROR_COLOURS, |c| c.output_utf16_line(error));
+        }
+
+        for recipient in (1..=participant_count).filter(|i| *i != identifier) {
+            let label: Vec<u16> = format!("Round 2 Package for Participant {}\0", recipient)
+                .encode_utf16()
+                .collect();
+
+            let mut package = participant.round_2_package(recipient);
+            write_bytes(&self.system_services, String16::from(&label), &package);
+            prompt_for_clipboard_write(
+                &self.system_services,
+                ClipboardEntry::Bytes(s16!("FROST round 2 package"), package[..].into()),
+            );
+
+            package.fill(0);
+        }
+
+        // Collect the round 2 packages addressed to us; they're validated against their senders' commitments when we finish.
+        let mut round_2_packages = Vec::with_capacity(participant_count as usize - 1);
+        while round_2_packages.len() < participant_count as usize - 1 {
+            console.line_start().new_line().output_utf32_line(&format!(
+                "Round 2 package {} of {}:\0",
+                round_2_packages.len() + 1,
+                participant_count - 1
+            ));
+
+            let package =
+                match prompt_for_fixed_length_bytes::<ROUND_2_PACKAGE_LENGTH, TSystemServices>(
+                    &self.system_services,
+                    CANCEL_PROMPT,
+                    s16!("Round 2 Package"),
+                ) {
+                    Some(p) => p,
+                    None => {
+                        round_2_packages
+                            .iter_mut()
+                            .for_each(|p: &mut [u8; ROUND_2_PACKAGE_LENGTH]| p.fill(0));
+                        return ProgramExitResult::UserCancelled;
+                    }
+                };
+
+            if package[1] != identifier {
+                console.in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16_line(s16!("The round 2 package is not addressed to us."))
+                });
+            } else if round_2_packages.iter().any(|p| p[0] == package[0]) {
+                console.in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16_line(s16!(
+                        "A round 2 package from that participant was already entered."
+                    ))
+                });
+            } else {
+                round_2_packages.push(package);
+            }
+        }
+
+        let result = participant.try_finish(&round_1_packages, &round_2_packages);
+        round_2_packages.iter_mut().for_each(|p| p.fill(0));
+        let (public_key_package, key_package) = match result {
+            Ok(r) => r,
+            Err(e) => return e.to_program_error(),
+        };
+
+        write_bytes(
+            &self.system_services,
+            s16!("Group Public Key"),
+            &public_key_package.group_public_key(),
+        );
+
+        let public_key_package = public_key_package.to_bytes();
+        write_bytes(
+            &self.system_services,
+            s16!("Public Key Package"),
+            &public_key_package,
+        );
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(
+                s16!("FROST public key package"),
+                public_key_package[..].into(),
+            ),
+        );
+
+        let mut key_package = key_package.to_bytes();
+        write_bytes(&self.system_services, s16!("Our Key Package"), &key_package);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("FROST key package"), key_package[..].into()),
+        );
+
+        key_package.fill(0);
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,137 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_public_key_package, prompt_for_signing_package};
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::frost::{self, SIGNATURE_SHARE_LENGTH},
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_clipboard_write, prompt_for_fixed_length_bytes, ConsoleUiTitle, ConsoleWriteable,
+    },
+    String16,
+};
+use alloc::{format, vec::Vec};
+use macros::s16;
+
+pub struct ConsoleFrostSignatureAggregationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleFrostSignatureAggregationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleFrostSignatureAggregationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("FROST Signature Aggregation")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program is for the coordinator of a FROST (RFC 9591) signing session; it verifies each participant's signature share, and aggregates them into a signature for the group public key."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel signature aggregation?");
+
+        let public_key_package =
+            match prompt_for_public_key_package(&self.system_services, CANCEL_PROMPT) {
+                Some(p) => p,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        let signing_package = match prompt_for_signing_package(&self.system_services, CANCEL_PROMPT)
+        {
+            Some(p) => p,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        // Verify each share as it's entered, so a misbehaving participant can be identified.
+        let identifiers = signing_package.identifiers();
+        let mut signature_shares = Vec::with_capacity(identifiers.len());
+        for identifier in identifiers.iter() {
+            console
+                .line_start()
+                .new_line()
+                .output_utf32_line(&format!("Participant {}:\0", identifier));
+
+            let signature_share = loop {
+                let signature_share =
+                    match prompt_for_fixed_length_bytes::<SIGNATURE_SHARE_LENGTH, TSystemServices>(
+                        &self.system_services,
+                        CANCEL_PROMPT,
+                        s16!("Signature Share"),
+                    ) {
+                        Some(s) => s,
+                        None => return ProgramExitResult::UserCancelled,
+                    };
+
+                if signature_share[0] == *identifier
+                    && frost::verify_signature_share(
+                        &public_key_package,
+                        &signing_package,
+                        &signature_share,
+                    )
+                {
+                    break signature_share;
+                }
+
+                console.in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16_line(s16!(
+                        "The signature share is NOT valid for the participant and signing package."
+                    ))
+                });
+            };
+
+            signature_shares.push(signature_share);
+        }
+
+        let signature =
+            match frost::try_aggregate(&public_key_package, &signing_package, &signature_shares) {
+                Ok(s) => s,
+                Err(e) => return e.to_program_error(),
+            };
+
+        let group_public_key = public_key_package.group_public_key();
+        if !frost::verify(&group_public_key, signing_package.message(), &signature) {
+            return s16!("The aggregate signature failed verification.").to_program_error();
+        }
+
+        write_bytes(
+            &self.system_services,
+            s16!("Group Public Key"),
+            &group_public_key,
+        );
+
+        write_bytes(&self.system_services, s16!("Signature"), &signature);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("FROST signature"), signature[..].into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,150 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    prompt_for_key_package, prompt_for_public_key_package, prompt_for_seed,
+    prompt_for_signing_package,
+};
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::frost::{self, SigningNonces},
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_clipboard_write, ConsoleUiConfirmationPrompt, ConsoleUiTitle,
+            ConsoleWriteable,
+        },
+        ConfirmationPrompt,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleFrostSigningProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleFrostSigningProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for ConsoleFrostSigningProgram<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("FROST Signing")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program takes one participant through a FROST (RFC 9591) signing session. It produces a round 1 commitment for the coordinator, then signs the coordinator's signing package, producing a signature share."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "The signing nonces never leave this program; if you cancel after sharing your commitment, you must commit again in a new signing session."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel signing?");
+
+        let public_key_package =
+            match prompt_for_public_key_package(&self.system_services, CANCEL_PROMPT) {
+                Some(p) => p,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        let key_package =
+            match prompt_for_key_package(&self.system_services, CANCEL_PROMPT, &public_key_package)
+            {
+                Some(k) => k,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        // A nonce seed must NEVER be reused; reused nonces leak the signing share.
+        let mut nonce_seed =
+            match prompt_for_seed(&self.system_services, CANCEL_PROMPT, s16!("Nonce Seed")) {
+                Some(s) => s,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        let nonces = SigningNonces::try_generate(&key_package, &nonce_seed);
+        nonce_seed.fill(0);
+        let mut nonces = match nonces {
+            Ok(n) => n,
+            Err(e) => return e.to_program_error(),
+        };
+
+        write_bytes(
+            &self.system_services,
+            s16!("Commitment"),
+            nonces.commitment(),
+        );
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("FROST commitment"), nonces.commitment()[..].into()),
+        );
+
+        let signing_package = match prompt_for_signing_package(&self.system_services, CANCEL_PROMPT)
+        {
+            Some(p) => p,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        // Show the message before signing it; the coordinator chooses what we sign.
+        write_bytes(
+            &self.system_services,
+            s16!("Message"),
+            signing_package.message(),
+        );
+
+        if !ConsoleUiConfirmationPrompt::from(&self.system_services)
+            .prompt_for_confirmation(s16!("Sign the message?"))
+        {
+            return ProgramExitResult::UserCancelled;
+        }
+
+        let signature_share = match frost::try_sign(
+            &key_package,
+            &public_key_package,
+            &mut nonces,
+            &signing_package,
+        ) {
+            Ok(s) => s,
+            Err(e) => return e.to_program_error(),
+        };
+
+        write_bytes(
+            &self.system_services,
+            s16!("Signature Share"),
+            &signature_share,
+        );
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("FROST signature share"), signature_share[..].into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
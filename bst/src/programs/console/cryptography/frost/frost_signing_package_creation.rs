@@ -0,0 +1,152 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_public_key_package;
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::frost::{SigningPackage, COMMITMENT_LENGTH},
+    integers::NumericBases,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_bytes_from_any_data_type, prompt_for_clipboard_write,
+        prompt_for_fixed_length_bytes, prompt_for_u8, ConsoleUiTitle, ConsoleWriteable,
+    },
+    String16,
+};
+use alloc::{format, vec::Vec};
+use macros::s16;
+
+pub struct ConsoleFrostSigningPackageCreationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleFrostSigningPackageCreationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleFrostSigningPackageCreationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("FROST Signing Package Creation")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program is for the coordinator of a FROST (RFC 9591) signing session; it combines the signing participants' round 1 commitments with the message to sign, producing the signing package each participant signs in round 2."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel signing package creation?");
+
+        let public_key_package =
+            match prompt_for_public_key_package(&self.system_services, CANCEL_PROMPT) {
+                Some(p) => p,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        let threshold = public_key_package.threshold();
+        let participant_count = public_key_package.participant_count();
+        let signer_count = match prompt_for_u8(
+            |c| {
+                if c < threshold || c > participant_count {
+                    Some(s16!(
+                        "The signer count must be between the threshold and the participant count."
+                    ))
+                } else {
+                    None
+                }
+            },
+            s16!("Signer Count"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(c) => c,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut commitments: Vec<[u8; COMMITMENT_LENGTH]> =
+            Vec::with_capacity(signer_count as usize);
+        while commitments.len() < signer_count as usize {
+            console.line_start().new_line().output_utf32_line(&format!(
+                "Commitment {} of {}:\0",
+                commitments.len() + 1,
+                signer_count
+            ));
+
+            let commitment = match prompt_for_fixed_length_bytes::<COMMITMENT_LENGTH, TSystemServices>(
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Commitment"),
+            ) {
+                Some(c) => c,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+            let error = if commitment[0] == 0 || commitment[0] > participant_count {
+                s16!("The commitment's identifier does not belong to a participant.")
+            } else if commitments.iter().any(|c| c[0] == commitment[0]) {
+                s16!("A commitment from that participant was already entered.")
+            } else {
+                match SigningPackage::try_from(&[commitment], &[]) {
+                    Ok(_) => {
+                        commitments.push(commitment);
+                        continue;
+                    }
+                    Err(e) => e,
+                }
+            };
+
+            console.in_colours(constants::ERROR_COLOURS, |c| c.output_utf16_line(error));
+        }
+
+        // Text is signed as its UTF8 bytes.
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => return e,
+        };
+
+        let signing_package = match SigningPackage::try_from(&commitments, &message) {
+            Ok(p) => p.to_bytes(),
+            Err(e) => return e.to_program_error(),
+        };
+
+        write_bytes(
+            &self.system_services,
+            s16!("Signing Package"),
+            &signing_package,
+        );
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("FROST signing package"), signing_package[..].into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
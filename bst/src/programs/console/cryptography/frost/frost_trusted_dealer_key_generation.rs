@@ -0,0 +1,190 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_seed;
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::frost,
+    integers::NumericBases,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_clipboard_write, prompt_for_fixed_length_bytes, prompt_for_u8, ConsoleUiTitle,
+        ConsoleWriteable,
+    },
+    String16,
+};
+use alloc::{format, vec::Vec};
+use macros::s16;
+
+pub struct ConsoleFrostTrustedDealerKeyGenerationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices>
+    ConsoleFrostTrustedDealerKeyGenerationProgram<TSystemServices>
+{
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleFrostTrustedDealerKeyGenerationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("FROST Trusted Dealer Key Generation")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program splits a secp256k1 private key into FROST (RFC 9591) key packages for a threshold of participants, and produces the public key package every participant and coordinator needs."
+            ))
+            .output_utf16_line(s16!(
+                "The polynomial's random coefficients are derived from a coefficient seed of at least 32 bytes; you may wish to use the output of one of the entropy collection programs. Never reuse a coefficient seed."
+            ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel key generation?");
+
+        let threshold = match prompt_for_u8(
+            |t| {
+                if t < 2 {
+                    Some(s16!("The threshold must be at least 2."))
+                } else {
+                    None
+                }
+            },
+            s16!("Threshold"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(t) => t,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let participant_count = match prompt_for_u8(
+            |c| {
+                if c < threshold {
+                    Some(s16!(
+                        "The number of participants cannot be lower than the threshold."
+                    ))
+                } else {
+                    None
+                }
+            },
+            s16!("Participant Count"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(c) => c,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut secret_key = match prompt_for_fixed_length_bytes::<32, TSystemServices>(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Private Key"),
+        ) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut coefficient_seed = match prompt_for_seed(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Coefficient Seed"),
+        ) {
+            Some(s) => s,
+            None => {
+                secret_key.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let result = frost::try_generate_with_trusted_dealer(
+            &secret_key,
+            threshold,
+            participant_count,
+            &coefficient_seed,
+        );
+
+        secret_key.fill(0);
+        coefficient_seed.fill(0);
+        let (public_key_package, key_packages) = match result {
+            Ok(r) => r,
+            Err(e) => return e.to_program_error(),
+        };
+
+        write_bytes(
+            &self.system_services,
+            s16!("Group Public Key"),
+            &public_key_package.group_public_key(),
+        );
+
+        let public_key_package = public_key_package.to_bytes();
+        write_bytes(
+            &self.system_services,
+            s16!("Public Key Package"),
+            &public_key_package,
+        );
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(
+                s16!("FROST public key package"),
+                public_key_package[..].into(),
+            ),
+        );
+
+        console
+            .line_start()
+            .new_line()
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "NOTE: Each key package is secret; give each participant only their own key package, alongside the public key package."
+                ))
+            });
+
+        for key_package in key_packages.iter() {
+            let label: Vec<u16> = format!(
+                "Participant {} of {} Key Package\0",
+                key_package.identifier(),
+                participant_count
+            )
+            .encode_utf16()
+            .collect();
+
+            let mut bytes = key_package.to_bytes();
+            write_bytes(&self.system_services, String16::from(&label), &bytes);
+            prompt_for_clipboard_write(
+                &self.system_services,
+                ClipboardEntry::Bytes(s16!("FROST key package"), bytes[..].into()),
+            );
+
+            bytes.fill(0);
+        }
+
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,187 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod frost_distributed_key_generation;
+mod frost_signature_aggregation;
+mod frost_signing;
+mod frost_signing_package_creation;
+mod frost_trusted_dealer_key_generation;
+
+use crate::{
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::frost::{
+        KeyPackage, PublicKeyPackage, SigningPackage, KEY_PACKAGE_LENGTH, MINIMUM_SEED_LENGTH,
+    },
+    programs::{
+        exit_result_handlers::ProgramExitResultHandler,
+        program_lists::{ProgramList, ProgramListProgram, ProgramSelector},
+        Program,
+    },
+    system_services::SystemServices,
+    ui::{
+        console::{prompt_for_data_input, prompt_for_fixed_length_bytes},
+        DataInput, DataInputType,
+    },
+    String16,
+};
+use alloc::{sync::Arc, vec::Vec};
+use frost_distributed_key_generation::ConsoleFrostDistributedKeyGenerationProgram;
+use frost_signature_aggregation::ConsoleFrostSignatureAggregationProgram;
+use frost_signing::ConsoleFrostSigningProgram;
+use frost_signing_package_creation::ConsoleFrostSigningPackageCreationProgram;
+use frost_trusted_dealer_key_generation::ConsoleFrostTrustedDealerKeyGenerationProgram;
+use macros::s16;
+
+pub fn get_frost_program_list<
+    'a,
+    TSystemServices: SystemServices,
+    TProgramSelector: ProgramSelector + 'static,
+    TProgramExitResultHandler: ProgramExitResultHandler + 'static,
+>(
+    system_services: &TSystemServices,
+    program_selector: &TProgramSelector,
+    exit_result_handler: &TProgramExitResultHandler,
+) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
+    let programs: [Arc<dyn Program>; 5] = [
+        Arc::from(ConsoleFrostTrustedDealerKeyGenerationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleFrostDistributedKeyGenerationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleFrostSigningPackageCreationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleFrostSigningProgram::from(system_services.clone())),
+        Arc::from(ConsoleFrostSignatureAggregationProgram::from(
+            system_services.clone(),
+        )),
+    ];
+
+    ProgramList::from(Arc::from(programs), s16!("FROST Programs"))
+        .as_program(program_selector.clone(), exit_result_handler.clone())
+}
+
+fn prompt_for_seed<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16<'static>,
+    label: String16<'static>,
+) -> Option<Vec<u8>> {
+    loop {
+        match prompt_for_data_input(
+            None,
+            &[DataInputType::Bytes],
+            system_services,
+            cancel_prompt,
+            label,
+        ) {
+            DataInput::Bytes(mut b) => {
+                if b.len() >= MINIMUM_SEED_LENGTH {
+                    return Some(b);
+                }
+
+                b.fill(0);
+                system_services
+                    .get_console_out()
+                    .in_colours(constants::ERROR_COLOURS, |c| {
+                        c.output_utf16_line(s16!("The seed must be at least 32 bytes."))
+                    });
+            }
+            _ => return None,
+        }
+    }
+}
+
+fn prompt_for_public_key_package<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16<'static>,
+) -> Option<PublicKeyPackage> {
+    loop {
+        match prompt_for_data_input(
+            None,
+            &[DataInputType::Bytes],
+            system_services,
+            cancel_prompt,
+            s16!("Public Key Package"),
+        ) {
+            DataInput::Bytes(b) => match PublicKeyPackage::try_from_bytes(&b) {
+                Ok(p) => return Some(p),
+                Err(e) => system_services
+                    .get_console_out()
+                    .in_colours(constants::ERROR_COLOURS, |c| c.output_utf16_line(e)),
+            },
+            _ => return None,
+        };
+    }
+}
+
+fn prompt_for_key_package<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16<'static>,
+    public_key_package: &PublicKeyPackage,
+) -> Option<KeyPackage> {
+    loop {
+        let mut bytes = match prompt_for_fixed_length_bytes::<KEY_PACKAGE_LENGTH, TSystemServices>(
+            system_services,
+            cancel_prompt,
+            s16!("Key Package"),
+        ) {
+            Some(b) => b,
+            None => return None,
+        };
+
+        let key_package = KeyPackage::try_from_bytes(&bytes);
+        bytes.fill(0);
+        let error = match key_package {
+            Ok(k) => {
+                if k.is_valid_for(public_key_package) {
+                    return Some(k);
+                }
+
+                s16!("The key package does not belong to the public key package.")
+            }
+            Err(e) => e,
+        };
+
+        system_services
+            .get_console_out()
+            .in_colours(constants::ERROR_COLOURS, |c| c.output_utf16_line(error));
+    }
+}
+
+fn prompt_for_signing_package<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16<'static>,
+) -> Option<SigningPackage> {
+    loop {
+        match prompt_for_data_input(
+            None,
+            &[DataInputType::Bytes],
+            system_services,
+            cancel_prompt,
+            s16!("Signing Package"),
+        ) {
+            DataInput::Bytes(b) => match SigningPackage::try_from_bytes(&b) {
+                Ok(p) => return Some(p),
+                Err(e) => system_services
+                    .get_console_out()
+                    .in_colours(constants::ERROR_COLOURS, |c| c.output_utf16_line(e)),
+            },
+            _ => return None,
+        };
+    }
+}
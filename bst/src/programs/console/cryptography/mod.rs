@@ -14,8 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod adaptor_signatures;
 mod asymmetric;
 mod bip_32;
+mod ed25519;
+mod frost;
+mod musig2;
+mod rsa;
+mod shamir;
+mod x25519;
 
 use crate::{
     programs::{
@@ -38,7 +45,7 @@ pub fn get_cryptography_program_list<
     program_selector: &TProgramSelector,
     exit_result_handler: &TProgramExitResultHandler,
 ) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
-    let programs: [Arc<dyn Program>; 2] = [
+    let programs: [Arc<dyn Program>; 9] = [
         Arc::from(bip_32::get_bip_32_program_list(
             system_services,
             program_selector,
@@ -49,6 +56,41 @@ pub fn get_cryptography_program_list<
             program_selector,
             exit_result_handler,
         )),
+        Arc::from(musig2::get_musig2_program_list(
+            system_services,
+            program_selector,
+            exit_result_handler,
+        )),
+        Arc::from(adaptor_signatures::get_adaptor_signature_program_list(
+            system_services,
+            program_selector,
+            exit_result_handler,
+        )),
+        Arc::from(frost::get_frost_program_list(
+            system_services,
+            program_selector,
+            exit_result_handler,
+        )),
+        Arc::from(rsa::get_rsa_program_list(
+            system_services,
+            program_selector,
+            exit_result_handler,
+        )),
+        Arc::from(ed25519::get_ed25519_program_list(
+            system_services,
+            program_selector,
+            exit_result_handler,
+        )),
+        Arc::from(x25519::get_x25519_program_list(
+            system_services,
+            program_selector,
+            exit_result_handler,
+        )),
+        Arc::from(shamir::get_shamir_program_list(
+            system_services,
+            program_selector,
+            exit_result_handler,
+        )),
     ];
     ProgramList::from(Arc::from(programs), s16!("Cryptography Programs"))
         .as_program(program_selector.clone(), exit_result_handler.clone())
@@ -0,0 +1,207 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod musig2_key_aggregation;
+mod musig2_partial_signature_verification;
+mod musig2_partial_signing;
+mod musig2_signature_aggregation;
+
+use crate::{
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::{
+        self,
+        musig2::{self, KeyAggregationContext, PUBLIC_KEY_LENGTH, PUBLIC_NONCE_LENGTH},
+        schnorr::tagged_hash,
+    },
+    integers::{NumericBase, NumericBases},
+    programs::{
+        exit_result_handlers::ProgramExitResultHandler,
+        program_lists::{ProgramList, ProgramListProgram, ProgramSelector},
+        Program,
+    },
+    system_services::SystemServices,
+    ui::{
+        console::{prompt_for_fixed_length_bytes, prompt_for_u8, ConsoleUiConfirmationPrompt},
+        ConfirmationPrompt,
+    },
+    String16,
+};
+use alloc::{format, sync::Arc, vec::Vec};
+use macros::s16;
+use musig2_key_aggregation::ConsoleMuSig2KeyAggregationProgram;
+use musig2_partial_signature_verification::ConsoleMuSig2PartialSignatureVerificationProgram;
+use musig2_partial_signing::ConsoleMuSig2PartialSigningProgram;
+use musig2_signature_aggregation::ConsoleMuSig2SignatureAggregationProgram;
+
+pub fn get_musig2_program_list<
+    'a,
+    TSystemServices: SystemServices,
+    TProgramSelector: ProgramSelector + 'static,
+    TProgramExitResultHandler: ProgramExitResultHandler + 'static,
+>(
+    system_services: &TSystemServices,
+    program_selector: &TProgramSelector,
+    exit_result_handler: &TProgramExitResultHandler,
+) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
+    let programs: [Arc<dyn Program>; 4] = [
+        Arc::from(ConsoleMuSig2KeyAggregationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleMuSig2PartialSigningProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleMuSig2PartialSignatureVerificationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleMuSig2SignatureAggregationProgram::from(
+            system_services.clone(),
+        )),
+    ];
+
+    ProgramList::from(Arc::from(programs), s16!("MuSig2 Programs"))
+        .as_program(program_selector.clone(), exit_result_handler.clone())
+}
+
+fn prompt_for_key_aggregation_context<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16<'static>,
+) -> Option<KeyAggregationContext> {
+    let console = system_services.get_console_out();
+    let signer_count = match prompt_for_u8(
+        |c| {
+            if c < 2 {
+                Some(s16!("A MuSig2 session requires at least 2 signers."))
+            } else {
+                None
+            }
+        },
+        s16!("Signer Count"),
+        system_services,
+        cancel_prompt,
+        Some(NumericBases::Decimal.into()),
+    ) {
+        Some(c) => c,
+        None => return None,
+    };
+
+    let mut public_keys = Vec::with_capacity(signer_count as usize);
+    for i in 1..=signer_count {
+        console
+            .line_start()
+            .new_line()
+            .output_utf32_line(&format!("Signer {} of {}:\0", i, signer_count));
+
+        let public_key = loop {
+            match prompt_for_fixed_length_bytes::<PUBLIC_KEY_LENGTH, TSystemServices>(
+                system_services,
+                cancel_prompt,
+                s16!("Compressed Public Key"),
+            ) {
+                Some(k) => match secp256k1::try_decode_public_key(&k) {
+                    Some(_) => break k,
+                    None => console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.output_utf16_line(s16!("Invalid secp256k1 public key."))
+                    }),
+                },
+                None => return None,
+            };
+        };
+
+        public_keys.push(public_key);
+    }
+
+    // Every signer must aggregate the keys in the same order; sorting them means the order they're entered in doesn't matter.
+    console.line_start().new_line();
+    if ConsoleUiConfirmationPrompt::from(system_services)
+        .prompt_for_confirmation(s16!("Sort the public keys (BIP 327 KeySort)?"))
+    {
+        musig2::sort_public_keys(&mut public_keys);
+        console.line_start().new_line();
+        for (i, public_key) in public_keys.iter().enumerate() {
+            let hex = NumericBase::BASE_16.build_string_from_bytes(public_key, true);
+            console
+                .output_utf32(&format!("Signer {}: \0", i + 1))
+                .output_utf16_line(String16::from(&hex));
+        }
+    }
+
+    let mut context = match KeyAggregationContext::try_from(&public_keys) {
+        Ok(c) => c,
+        Err(e) => {
+            console.in_colours(constants::ERROR_COLOURS, |c| c.output_utf16_line(e));
+            return None;
+        }
+    };
+
+    // Taproot outputs without a script tree commit to a tweak of the internal key; t = hash_TapTweak(xbytes(P)).
+    console.line_start().new_line();
+    if ConsoleUiConfirmationPrompt::from(system_services).prompt_for_confirmation(s16!(
+        "Apply a BIP 86 Taproot tweak (key path spending only) to the aggregate public key?"
+    )) {
+        let tweak = tagged_hash(b"TapTweak", &[&context.x_only_aggregate_public_key()]);
+        if let Err(e) = context.try_apply_tweak(&tweak, true) {
+            console.in_colours(constants::ERROR_COLOURS, |c| c.output_utf16_line(e));
+            return None;
+        }
+    }
+
+    Some(context)
+}
+
+fn prompt_for_public_nonces<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16<'static>,
+    signer_count: usize,
+    own_nonce: Option<(usize, [u8; PUBLIC_NONCE_LENGTH])>,
+) -> Option<Vec<[u8; PUBLIC_NONCE_LENGTH]>> {
+    let console = system_services.get_console_out();
+    let mut public_nonces = Vec::with_capacity(signer_count);
+    for i in 0..signer_count {
+        if let Some((own_index, own_nonce)) = own_nonce {
+            if own_index == i {
+                public_nonces.push(own_nonce);
+                continue;
+            }
+        }
+
+        console.line_start().new_line().output_utf32_line(&format!(
+            "Signer {} of {}:\0",
+            i + 1,
+            signer_count
+        ));
+
+        let public_nonce = loop {
+            match prompt_for_fixed_length_bytes::<PUBLIC_NONCE_LENGTH, TSystemServices>(
+                system_services,
+                cancel_prompt,
+                s16!("Public Nonce"),
+            ) {
+                Some(n) => match musig2::try_aggregate_nonces(&[n]) {
+                    Ok(_) => break n,
+                    Err(e) => {
+                        console.in_colours(constants::ERROR_COLOURS, |c| c.output_utf16_line(e))
+                    }
+                },
+                None => return None,
+            };
+        };
+
+        public_nonces.push(public_nonce);
+    }
+
+    Some(public_nonces)
+}
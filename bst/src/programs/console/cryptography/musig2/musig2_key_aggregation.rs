@@ -0,0 +1,79 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_key_aggregation_context;
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{prompt_for_clipboard_write, ConsoleUiTitle, ConsoleWriteable},
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleMuSig2KeyAggregationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleMuSig2KeyAggregationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleMuSig2KeyAggregationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("MuSig2 Key Aggregation")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program aggregates the compressed public keys of a group of signers into a single MuSig2 (BIP 327) x-only public key. Signatures produced by the group verify as BIP 340 Schnorr signatures against the aggregate public key."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel key aggregation?");
+
+        let key_context =
+            match prompt_for_key_aggregation_context(&self.system_services, CANCEL_PROMPT) {
+                Some(c) => c,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        let aggregate_public_key = key_context.x_only_aggregate_public_key();
+        write_bytes(
+            &self.system_services,
+            s16!("Aggregate Public Key"),
+            &aggregate_public_key,
+        );
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(
+                s16!("MuSig2 aggregate public key"),
+                aggregate_public_key[..].into(),
+            ),
+        );
+
+        ProgramExitResult::Success
+    }
+}
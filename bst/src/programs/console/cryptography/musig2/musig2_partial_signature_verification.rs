@@ -0,0 +1,149 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_key_aggregation_context, prompt_for_public_nonces};
+use crate::{
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::musig2::{
+        self, SigningSession, PARTIAL_SIGNATURE_LENGTH,
+    },
+    integers::NumericBases,
+    programs::{Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_bytes_from_any_data_type, prompt_for_fixed_length_bytes, prompt_for_u8,
+            ConsoleUiContinuePrompt, ConsoleUiTitle, ConsoleWriteable,
+        },
+        ContinuePrompt,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleMuSig2PartialSignatureVerificationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices>
+    ConsoleMuSig2PartialSignatureVerificationProgram<TSystemServices>
+{
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleMuSig2PartialSignatureVerificationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("MuSig2 Partial Signature Verification")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program verifies one signer's MuSig2 (BIP 327) partial signature, given the signing session's public keys, public nonces and message."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel partial signature verification?");
+
+        let key_context =
+            match prompt_for_key_aggregation_context(&self.system_services, CANCEL_PROMPT) {
+                Some(c) => c,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        // Text is verified as its UTF8 bytes.
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => return e,
+        };
+
+        let signer_count = key_context.public_keys().len();
+        let public_nonces = match prompt_for_public_nonces(
+            &self.system_services,
+            CANCEL_PROMPT,
+            signer_count,
+            None,
+        ) {
+            Some(n) => n,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let signer_index = match prompt_for_u8(
+            |i| {
+                if i == 0 || i as usize > signer_count {
+                    Some(s16!(
+                        "The signer number must be between 1 and the signer count."
+                    ))
+                } else {
+                    None
+                }
+            },
+            s16!("Signer Number"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(i) => i as usize - 1,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let partial_signature =
+            match prompt_for_fixed_length_bytes::<PARTIAL_SIGNATURE_LENGTH, TSystemServices>(
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Partial Signature"),
+            ) {
+                Some(s) => s,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        let public_key = key_context.public_keys()[signer_index];
+        let session = match musig2::try_aggregate_nonces(&public_nonces)
+            .and_then(|n| SigningSession::try_from(key_context, &n, &message))
+        {
+            Ok(s) => s,
+            Err(e) => return e.to_program_error(),
+        };
+
+        console.line_start().new_line();
+        if session.verify_partial_signature(
+            &partial_signature,
+            &public_nonces[signer_index],
+            &public_key,
+        ) {
+            console.in_colours(constants::SUCCESS_COLOURS, |c| {
+                c.output_utf16_line(s16!("Partial signature is valid."))
+            });
+        } else {
+            console.in_colours(constants::ERROR_COLOURS, |c| {
+                c.output_utf16_line(s16!("Partial signature is NOT valid."))
+            });
+        }
+
+        ConsoleUiContinuePrompt::from(&self.system_services).prompt_for_continue();
+        ProgramExitResult::Success
+    }
+}
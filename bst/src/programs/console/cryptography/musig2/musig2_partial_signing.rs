@@ -0,0 +1,243 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_key_aggregation_context, prompt_for_public_nonces};
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::{
+        musig2::{self, SigningSession},
+        try_derive_serialized_public_key,
+    },
+    hashing::derive_bytes,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_bytes_from_any_data_type, prompt_for_clipboard_write, prompt_for_data_input,
+            prompt_for_fixed_length_bytes, ConsoleUiTitle, ConsoleWriteable,
+        },
+        DataInput, DataInputType,
+    },
+    String16,
+};
+use alloc::format;
+use macros::s16;
+
+// The nonce seed is expanded into the 32 random bytes BIP 327's nonce generation requires.
+const MINIMUM_NONCE_SEED_LENGTH: usize = 32;
+
+pub struct ConsoleMuSig2PartialSigningProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleMuSig2PartialSigningProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleMuSig2PartialSigningProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("MuSig2 Partial Signing")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program produces a MuSig2 (BIP 327) partial signature with one signer's private key. It generates a public nonce to share with the other signers, then collects their public nonces and signs."
+            ))
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "The secret nonce never leaves this program; if you cancel after sharing your public nonce, you must start a new signing session with new nonces."
+                ))
+            });
+        const CANCEL_PROMPT: String16 = s16!("Cancel partial signing?");
+
+        let key_context =
+            match prompt_for_key_aggregation_context(&self.system_services, CANCEL_PROMPT) {
+                Some(c) => c,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        // Text is signed as its UTF8 bytes; Taproot transactions sign a 32 byte sighash.
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => return e,
+        };
+
+        let (mut private_key, public_key, signer_index) = loop {
+            let mut private_key = match prompt_for_fixed_length_bytes::<32, TSystemServices>(
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Private Key"),
+            ) {
+                Some(k) => k,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+            let public_key = match try_derive_serialized_public_key(&private_key) {
+                Some(k) => k,
+                None => {
+                    private_key.fill(0);
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.output_utf16_line(s16!("Invalid secp256k1 private key."))
+                    });
+                    continue;
+                }
+            };
+
+            match key_context
+                .public_keys()
+                .iter()
+                .position(|k| *k == public_key)
+            {
+                Some(i) => break (private_key, public_key, i),
+                None => {
+                    private_key.fill(0);
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.output_utf16_line(s16!(
+                            "The private key does not belong to any of the signers."
+                        ))
+                    });
+                }
+            }
+        };
+
+        let mut nonce_seed = loop {
+            match prompt_for_data_input(
+                None,
+                &[DataInputType::Bytes],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Nonce Seed"),
+            ) {
+                DataInput::Bytes(mut b) => {
+                    if b.len() >= MINIMUM_NONCE_SEED_LENGTH {
+                        break b;
+                    }
+
+                    b.fill(0);
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.output_utf16_line(s16!("The nonce seed must be at least 32 bytes."))
+                    });
+                }
+                _ => {
+                    private_key.fill(0);
+                    return ProgramExitResult::UserCancelled;
+                }
+            }
+        };
+
+        // The message and key are mixed into the nonce too, but a nonce seed must NEVER be reused; a reused nonce leaks the private key.
+        let mut random = [0u8; 32];
+        let mut random_bytes = derive_bytes(&nonce_seed, b"MuSig2 nonce", 32);
+        random.copy_from_slice(&random_bytes);
+        random_bytes.fill(0);
+        nonce_seed.fill(0);
+
+        let nonce = musig2::try_generate_nonce(
+            &random,
+            Some(&private_key),
+            &public_key,
+            Some(&key_context.x_only_aggregate_public_key()),
+            Some(&message),
+            None,
+        );
+
+        random.fill(0);
+        let (mut secret_nonce, public_nonce) = match nonce {
+            Ok(n) => n,
+            Err(e) => {
+                private_key.fill(0);
+                return e.to_program_error();
+            }
+        };
+
+        console
+            .line_start()
+            .new_line()
+            .in_colours(constants::SUCCESS_COLOURS, |c| {
+                c.output_utf32_line(&format!(
+                    "Generated the public nonce for signer {}; share it with the other signers.\0",
+                    signer_index + 1
+                ))
+            });
+
+        write_bytes(&self.system_services, s16!("Public Nonce"), &public_nonce);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("MuSig2 public nonce"), public_nonce[..].into()),
+        );
+
+        let public_nonces = match prompt_for_public_nonces(
+            &self.system_services,
+            CANCEL_PROMPT,
+            key_context.public_keys().len(),
+            Some((signer_index, public_nonce)),
+        ) {
+            Some(n) => n,
+            None => {
+                private_key.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let session = match musig2::try_aggregate_nonces(&public_nonces)
+            .and_then(|n| SigningSession::try_from(key_context, &n, &message))
+        {
+            Ok(s) => s,
+            Err(e) => {
+                private_key.fill(0);
+                return e.to_program_error();
+            }
+        };
+
+        let partial_signature = session.try_sign(&mut secret_nonce, &private_key);
+        private_key.fill(0);
+        let partial_signature = match partial_signature {
+            Ok(s) => s,
+            Err(e) => return e.to_program_error(),
+        };
+
+        write_bytes(
+            &self.system_services,
+            s16!("Partial Signature"),
+            &partial_signature,
+        );
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(
+                s16!("MuSig2 partial signature"),
+                partial_signature[..].into(),
+            ),
+        );
+
+        ProgramExitResult::Success
+    }
+}
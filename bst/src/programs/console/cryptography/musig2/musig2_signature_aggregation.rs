@@ -0,0 +1,162 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_key_aggregation_context, prompt_for_public_nonces};
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::ecc::secp256k1::{
+        musig2::{self, SigningSession, PARTIAL_SIGNATURE_LENGTH},
+        schnorr,
+    },
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_bytes_from_any_data_type, prompt_for_clipboard_write,
+        prompt_for_fixed_length_bytes, ConsoleUiTitle, ConsoleWriteable,
+    },
+    String16,
+};
+use alloc::{format, vec::Vec};
+use macros::s16;
+
+pub struct ConsoleMuSig2SignatureAggregationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleMuSig2SignatureAggregationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleMuSig2SignatureAggregationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("MuSig2 Signature Aggregation")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program verifies every signer's MuSig2 (BIP 327) partial signature, and aggregates them into a BIP 340 Schnorr signature for the aggregate public key."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel signature aggregation?");
+
+        let key_context =
+            match prompt_for_key_aggregation_context(&self.system_services, CANCEL_PROMPT) {
+                Some(c) => c,
+                None => return ProgramExitResult::UserCancelled,
+            };
+
+        // Text is signed as its UTF8 bytes.
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => return e,
+        };
+
+        let public_keys = Vec::from(key_context.public_keys());
+        let public_nonces = match prompt_for_public_nonces(
+            &self.system_services,
+            CANCEL_PROMPT,
+            public_keys.len(),
+            None,
+        ) {
+            Some(n) => n,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let session = match musig2::try_aggregate_nonces(&public_nonces)
+            .and_then(|n| SigningSession::try_from(key_context, &n, &message))
+        {
+            Ok(s) => s,
+            Err(e) => return e.to_program_error(),
+        };
+
+        // Verify each partial signature as it's entered, so a faulty or dishonest signer can be identified.
+        let mut partial_signatures = Vec::with_capacity(public_keys.len());
+        for i in 0..public_keys.len() {
+            console.line_start().new_line().output_utf32_line(&format!(
+                "Signer {} of {}:\0",
+                i + 1,
+                public_keys.len()
+            ));
+
+            let partial_signature = loop {
+                let partial_signature = match prompt_for_fixed_length_bytes::<
+                    PARTIAL_SIGNATURE_LENGTH,
+                    TSystemServices,
+                >(
+                    &self.system_services,
+                    CANCEL_PROMPT,
+                    s16!("Partial Signature"),
+                ) {
+                    Some(s) => s,
+                    None => return ProgramExitResult::UserCancelled,
+                };
+
+                if session.verify_partial_signature(
+                    &partial_signature,
+                    &public_nonces[i],
+                    &public_keys[i],
+                ) {
+                    break partial_signature;
+                }
+
+                console.in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16_line(s16!(
+                        "The partial signature is NOT valid for the signer's public key and public nonce."
+                    ))
+                });
+            };
+
+            partial_signatures.push(partial_signature);
+        }
+
+        let signature = match session.try_aggregate_partial_signatures(&partial_signatures) {
+            Ok(s) => s,
+            Err(e) => return e.to_program_error(),
+        };
+
+        let aggregate_public_key = session.key_context().x_only_aggregate_public_key();
+        if !schnorr::verify(&aggregate_public_key, &message, &signature) {
+            return s16!("The aggregate signature failed verification.").to_program_error();
+        }
+
+        write_bytes(
+            &self.system_services,
+            s16!("Aggregate Public Key"),
+            &aggregate_public_key,
+        );
+
+        write_bytes(&self.system_services, s16!("Signature"), &signature);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("BIP 340 Schnorr signature"), signature[..].into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,194 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod rsa_decryption;
+mod rsa_encryption;
+mod rsa_key_generation;
+mod rsa_signature_verification;
+mod rsa_signing;
+
+use crate::{
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::rsa::{
+        RsaPrivateKey, RsaPublicKey, SignatureScheme, MINIMUM_SEED_LENGTH,
+    },
+    programs::{
+        exit_result_handlers::ProgramExitResultHandler,
+        program_lists::{ProgramList, ProgramListProgram, ProgramSelector},
+        Program,
+    },
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_data_input, ConsoleUiConfirmationPrompt, ConsoleUiList, ConsoleUiTitle,
+            ConsoleWriteable,
+        },
+        ConfirmationPrompt, DataInput, DataInputType,
+    },
+    String16,
+};
+use alloc::{sync::Arc, vec::Vec};
+use macros::s16;
+use rsa_decryption::ConsoleRsaDecryptionProgram;
+use rsa_encryption::ConsoleRsaEncryptionProgram;
+use rsa_key_generation::ConsoleRsaKeyGenerationProgram;
+use rsa_signature_verification::ConsoleRsaSignatureVerificationProgram;
+use rsa_signing::ConsoleRsaSigningProgram;
+
+pub fn get_rsa_program_list<
+    'a,
+    TSystemServices: SystemServices,
+    TProgramSelector: ProgramSelector + 'static,
+    TProgramExitResultHandler: ProgramExitResultHandler + 'static,
+>(
+    system_services: &TSystemServices,
+    program_selector: &TProgramSelector,
+    exit_result_handler: &TProgramExitResultHandler,
+) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
+    let programs: [Arc<dyn Program>; 5] = [
+        Arc::from(ConsoleRsaKeyGenerationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleRsaSigningProgram::from(system_services.clone())),
+        Arc::from(ConsoleRsaSignatureVerificationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleRsaEncryptionProgram::from(system_services.clone())),
+        Arc::from(ConsoleRsaDecryptionProgram::from(system_services.clone())),
+    ];
+
+    ProgramList::from(Arc::from(programs), s16!("RSA Programs"))
+        .as_program(program_selector.clone(), exit_result_handler.clone())
+}
+
+impl ConsoleWriteable for SignatureScheme {
+    fn write_to<T: ConsoleOut>(&self, console: &T) {
+        console.output_utf16(match self {
+            SignatureScheme::Pss => s16!("RSASSA-PSS (SHA-256)"),
+            SignatureScheme::Pkcs1V1_5 => s16!("RSASSA-PKCS1-v1_5 (SHA-256)"),
+        });
+    }
+}
+
+fn prompt_for_signature_scheme<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancellation_prompt: String16,
+) -> Option<SignatureScheme> {
+    loop {
+        system_services.get_console_out().line_start().new_line();
+        match ConsoleUiList::from(
+            ConsoleUiTitle::from(s16!(" Signature Scheme "), constants::SMALL_TITLE),
+            constants::SELECT_LIST,
+            &[SignatureScheme::Pss, SignatureScheme::Pkcs1V1_5][..],
+        )
+        .prompt_for_selection(system_services)
+        {
+            Some((s, _, _)) => break Some(*s),
+            None => {
+                if ConsoleUiConfirmationPrompt::from(system_services)
+                    .prompt_for_confirmation(cancellation_prompt)
+                {
+                    break None;
+                }
+            }
+        }
+    }
+}
+
+fn prompt_for_seed<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16<'static>,
+    label: String16<'static>,
+) -> Option<Vec<u8>> {
+    loop {
+        match prompt_for_data_input(
+            None,
+            &[DataInputType::Bytes],
+            system_services,
+            cancel_prompt,
+            label,
+        ) {
+            DataInput::Bytes(mut b) => {
+                if b.len() >= MINIMUM_SEED_LENGTH {
+                    return Some(b);
+                }
+
+                b.fill(0);
+                system_services
+                    .get_console_out()
+                    .in_colours(constants::ERROR_COLOURS, |c| {
+                        c.output_utf16_line(s16!("The seed must be at least 32 bytes."))
+                    });
+            }
+            _ => return None,
+        }
+    }
+}
+
+fn prompt_for_public_key<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16<'static>,
+) -> Option<RsaPublicKey> {
+    loop {
+        match prompt_for_data_input(
+            None,
+            &[DataInputType::Bytes],
+            system_services,
+            cancel_prompt,
+            s16!("Public Key (DER)"),
+        ) {
+            DataInput::Bytes(b) => match RsaPublicKey::try_from_der(&b) {
+                Ok(k) => return Some(k),
+                Err(e) => {
+                    system_services
+                        .get_console_out()
+                        .in_colours(constants::ERROR_COLOURS, |c| c.output_utf16_line(e));
+                }
+            },
+            _ => return None,
+        }
+    }
+}
+
+fn prompt_for_private_key<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt: String16<'static>,
+) -> Option<RsaPrivateKey> {
+    loop {
+        match prompt_for_data_input(
+            None,
+            &[DataInputType::Bytes],
+            system_services,
+            cancel_prompt,
+            s16!("Private Key (DER)"),
+        ) {
+            DataInput::Bytes(mut b) => {
+                let key = RsaPrivateKey::try_from_der(&b);
+                b.fill(0);
+                match key {
+                    Ok(k) => return Some(k),
+                    Err(e) => {
+                        system_services
+                            .get_console_out()
+                            .in_colours(constants::ERROR_COLOURS, |c| c.output_utf16_line(e));
+                    }
+                }
+            }
+            _ => return None,
+        }
+    }
+}
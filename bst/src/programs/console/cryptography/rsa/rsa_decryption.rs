@@ -0,0 +1,90 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_private_key;
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::rsa,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_clipboard_write, prompt_for_data_input, ConsoleUiTitle, ConsoleWriteable,
+        },
+        DataInput, DataInputType,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleRsaDecryptionProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleRsaDecryptionProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for ConsoleRsaDecryptionProgram<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("RSA Decryption")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program decrypts an RSAES-OAEP ciphertext, encrypted with SHA-256 and an empty label, using a PKCS #1 DER RSA private key."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel decryption?");
+
+        let private_key = match prompt_for_private_key(&self.system_services, CANCEL_PROMPT) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let ciphertext = match prompt_for_data_input(
+            None,
+            &[DataInputType::Bytes],
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Ciphertext"),
+        ) {
+            DataInput::Bytes(c) => c,
+            _ => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut message = match rsa::try_decrypt(&private_key, &ciphertext) {
+            Ok(m) => m,
+            Err(e) => return e.to_program_error(),
+        };
+
+        write_bytes(&self.system_services, s16!("Message"), &message);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("RSA decrypted message"), message[..].into()),
+        );
+
+        message.fill(0);
+        ProgramExitResult::Success
+    }
+}
@@ -0,0 +1,113 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_public_key, prompt_for_seed};
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::rsa::{self, OAEP_SEED_LENGTH},
+    hashing::derive_bytes,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_bytes_from_any_data_type, prompt_for_clipboard_write, ConsoleUiTitle,
+        ConsoleWriteable,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleRsaEncryptionProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleRsaEncryptionProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for ConsoleRsaEncryptionProgram<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("RSA Encryption")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program encrypts a short message to an RSA public key with RSAES-OAEP, using SHA-256 and an empty label. The message can be at most the modulus length, minus 66 bytes; RSA is typically used to encrypt a symmetric key, rather than the data itself."
+            ))
+            .output_utf16_line(s16!(
+                "OAEP is randomized; its seed is derived from an encryption seed of at least 32 bytes, which should never be reused."
+            ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel encryption?");
+
+        let public_key = match prompt_for_public_key(&self.system_services, CANCEL_PROMPT) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        // Text is encrypted as its UTF8 bytes.
+        let mut message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => return e,
+        };
+
+        let mut encryption_seed = match prompt_for_seed(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Encryption Seed"),
+        ) {
+            Some(s) => s,
+            None => {
+                message.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let mut oaep_seed = [0u8; OAEP_SEED_LENGTH];
+        let mut derived = derive_bytes(&encryption_seed, &message, OAEP_SEED_LENGTH);
+        oaep_seed.copy_from_slice(&derived);
+        derived.fill(0);
+        encryption_seed.fill(0);
+
+        let ciphertext = rsa::try_encrypt(&public_key, &message, &oaep_seed);
+        oaep_seed.fill(0);
+        message.fill(0);
+
+        let ciphertext = match ciphertext {
+            Ok(c) => c,
+            Err(e) => return e.to_program_error(),
+        };
+
+        write_bytes(&self.system_services, s16!("Ciphertext"), &ciphertext);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("RSA ciphertext"), ciphertext[..].into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
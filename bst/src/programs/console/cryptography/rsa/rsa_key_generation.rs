@@ -0,0 +1,118 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_seed;
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::rsa::RsaPrivateKey,
+    integers::NumericBases,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{prompt_for_clipboard_write, prompt_for_u16, ConsoleUiTitle, ConsoleWriteable},
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleRsaKeyGenerationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleRsaKeyGenerationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for ConsoleRsaKeyGenerationProgram<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("RSA Key Generation")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!(
+                "This program generates an RSA key pair with a public exponent of 65537. The primes are found by Miller-Rabin testing candidates derived from a seed of at least 32 bytes; you may wish to use the output of one of the entropy collection programs. The same seed always produces the same key."
+            ))
+            .output_utf16_line(s16!(
+                "Keys are output as PKCS #1 DER, which most tools can import; for example, 'openssl rsa -inform DER'."
+            ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel key generation?");
+
+        let modulus_bits = match prompt_for_u16(
+            |b| match b {
+                2048 | 3072 | 4096 => None,
+                _ => Some(s16!("The modulus size must be 2048, 3072 or 4096 bits.")),
+            },
+            s16!("Modulus Size (Bits)"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(b) => b,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut seed = match prompt_for_seed(&self.system_services, CANCEL_PROMPT, s16!("Seed")) {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        console
+            .line_start()
+            .new_line()
+            .output_utf16_line(s16!("Generating primes; this may take a while..."));
+        let private_key = RsaPrivateKey::try_generate(modulus_bits as usize, &seed);
+        seed.fill(0);
+
+        let private_key = match private_key {
+            Ok(k) => k,
+            Err(e) => return e.to_program_error(),
+        };
+
+        let public_key = private_key.public_key().to_der();
+        write_bytes(
+            &self.system_services,
+            s16!("Public Key (PKCS #1 DER)"),
+            &public_key,
+        );
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("RSA public key"), public_key[..].into()),
+        );
+
+        let mut private_key = private_key.to_der();
+        write_bytes(
+            &self.system_services,
+            s16!("Private Key (PKCS #1 DER)"),
+            &private_key,
+        );
+
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("RSA private key"), private_key[..].into()),
+        );
+
+        private_key.fill(0);
+        ProgramExitResult::Success
+    }
+}
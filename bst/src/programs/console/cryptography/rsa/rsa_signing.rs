@@ -0,0 +1,91 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_private_key, prompt_for_signature_scheme};
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::rsa,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        prompt_for_bytes_from_any_data_type, prompt_for_clipboard_write, ConsoleUiTitle,
+        ConsoleWriteable,
+    },
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleRsaSigningProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleRsaSigningProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for ConsoleRsaSigningProgram<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("RSA Signing")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program signs a message with a PKCS #1 DER RSA private key, using SHA-256 with either RSASSA-PSS or RSASSA-PKCS1-v1_5. Prefer PSS, unless the verifier only supports PKCS1-v1_5."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel signing?");
+
+        let private_key = match prompt_for_private_key(&self.system_services, CANCEL_PROMPT) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let scheme = match prompt_for_signature_scheme(&self.system_services, CANCEL_PROMPT) {
+            Some(s) => s,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        // Text is signed as its UTF8 bytes.
+        let message = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Message"),
+        ) {
+            Ok(m) => m,
+            Err(e) => return e,
+        };
+
+        let signature = match rsa::try_sign(&private_key, scheme, &message) {
+            Ok(s) => s,
+            Err(e) => return e.to_program_error(),
+        };
+
+        write_bytes(&self.system_services, s16!("Signature"), &signature);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("RSA signature"), signature[..].into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
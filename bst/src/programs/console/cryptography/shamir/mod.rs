@@ -0,0 +1,110 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod shamir_secret_combining;
+mod shamir_secret_splitting;
+
+use crate::{
+    bitcoin::mnemonics::{bip_39, MnemonicWordList},
+    console_out::ConsoleOut,
+    constants,
+    programs::{
+        exit_result_handlers::ProgramExitResultHandler,
+        program_lists::{ProgramList, ProgramListProgram, ProgramSelector},
+        Program,
+    },
+    system_services::SystemServices,
+    ui::{
+        console::{ConsoleUiConfirmationPrompt, ConsoleUiList, ConsoleUiTitle, ConsoleWriteable},
+        ConfirmationPrompt,
+    },
+    String16,
+};
+use alloc::sync::Arc;
+use macros::s16;
+use shamir_secret_combining::ConsoleShamirSecretCombiningProgram;
+use shamir_secret_splitting::ConsoleShamirSecretSplittingProgram;
+
+pub fn get_shamir_program_list<
+    'a,
+    TSystemServices: SystemServices,
+    TProgramSelector: ProgramSelector + 'static,
+    TProgramExitResultHandler: ProgramExitResultHandler + 'static,
+>(
+    system_services: &TSystemServices,
+    program_selector: &TProgramSelector,
+    exit_result_handler: &TProgramExitResultHandler,
+) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
+    let programs: [Arc<dyn Program>; 2] = [
+        Arc::from(ConsoleShamirSecretSplittingProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleShamirSecretCombiningProgram::from(
+            system_services.clone(),
+        )),
+    ];
+
+    ProgramList::from(
+        Arc::from(programs),
+        s16!("Shamir's Secret Sharing Programs"),
+    )
+    .as_program(program_selector.clone(), exit_result_handler.clone())
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum ShareFormat {
+    Hexadecimal,
+    Mnemonic(MnemonicWordList),
+}
+
+impl ConsoleWriteable for ShareFormat {
+    fn write_to<T: ConsoleOut>(&self, console: &T) {
+        match self {
+            ShareFormat::Hexadecimal => console.output_utf16(s16!("Hexadecimal")),
+            ShareFormat::Mnemonic(w) => console
+                .output_utf16(w.name())
+                .output_utf16(s16!(" Mnemonic Words")),
+        };
+    }
+}
+
+fn prompt_for_share_format<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancellation_prompt: String16,
+) -> Option<ShareFormat> {
+    loop {
+        system_services.get_console_out().line_start().new_line();
+        match ConsoleUiList::from(
+            ConsoleUiTitle::from(s16!(" Share Format "), constants::SMALL_TITLE),
+            constants::SELECT_LIST,
+            &[
+                ShareFormat::Hexadecimal,
+                ShareFormat::Mnemonic(bip_39::WORD_LIST),
+            ][..],
+        )
+        .prompt_for_selection(system_services)
+        {
+            Some((f, _, _)) => break Some(*f),
+            None => {
+                if ConsoleUiConfirmationPrompt::from(system_services)
+                    .prompt_for_confirmation(cancellation_prompt)
+                {
+                    break None;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_share_format, ShareFormat};
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::shamir,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_clipboard_write, prompt_for_data_input, ConsoleUiTitle, ConsoleWriteable,
+        },
+        DataInput, DataInputType,
+    },
+    String16,
+};
+use alloc::{format, vec::Vec};
+use macros::{c16, s16};
+
+pub struct ConsoleShamirSecretCombiningProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleShamirSecretCombiningProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleShamirSecretCombiningProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Shamir Secret Combining")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!("This program recovers a secret from shares produced by the Shamir Secret Splitting program."))
+            .output_utf16_line(s16!("Each share's checksum is validated as it is entered; shares will be requested until the threshold recorded in the first share is met."));
+        const CANCEL_PROMPT: String16 = s16!("Cancel secret combining?");
+
+        let format = match prompt_for_share_format(&self.system_services, CANCEL_PROMPT) {
+            Some(f) => f,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut shares: Vec<Vec<u8>> = Vec::new();
+        loop {
+            let mut share = match self.prompt_for_share(format, CANCEL_PROMPT) {
+                Some(s) => s,
+                None => {
+                    shares.iter_mut().for_each(|s| s.fill(0));
+                    return ProgramExitResult::UserCancelled;
+                }
+            };
+
+            let (threshold, index) = match shamir::try_parse_share(&share) {
+                Ok(s) => (s.threshold(), s.index()),
+                Err(e) => {
+                    share.fill(0);
+                    console.in_colours(constants::ERROR_COLOURS, |c| c.output_utf16_line(e));
+                    continue;
+                }
+            };
+
+            if shares.len() > 0 {
+                if shares[0][0] != threshold || shares[0].len() != share.len() {
+                    share.fill(0);
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.output_utf16_line(s16!(
+                            "The share does not belong to the same secret as the first share."
+                        ))
+                    });
+                    continue;
+                }
+
+                if shares.iter().any(|s| s[1] == index) {
+                    share.fill(0);
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.output_utf16_line(s16!("A share with that index was already entered."))
+                    });
+                    continue;
+                }
+            }
+
+            shares.push(share);
+            console.in_colours(constants::SUCCESS_COLOURS, |c| {
+                c.output_utf32_line(&format!(
+                    "Accepted share {}; collected {} of {} shares.\0",
+                    index,
+                    shares.len(),
+                    threshold
+                ))
+            });
+
+            if shares.len() >= threshold as usize {
+                break;
+            }
+        }
+
+        let share_slices: Vec<&[u8]> = shares.iter().map(|s| &s[..]).collect();
+        let secret = shamir::try_combine_shares(&share_slices);
+        shares.iter_mut().for_each(|s| s.fill(0));
+        let mut secret = match secret {
+            Ok(s) => s,
+            Err(e) => return e.to_program_error(),
+        };
+
+        write_bytes(&self.system_services, s16!("Secret"), &secret);
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("Shamir secret"), secret[..].into()),
+        );
+
+        secret.fill(0);
+        ProgramExitResult::Success
+    }
+}
+
+impl<TSystemServices: SystemServices> ConsoleShamirSecretCombiningProgram<TSystemServices> {
+    fn prompt_for_share(&self, format: ShareFormat, cancel_prompt: String16) -> Option<Vec<u8>> {
+        let console = self.system_services.get_console_out();
+        loop {
+            match format {
+                ShareFormat::Hexadecimal => {
+                    return match prompt_for_data_input(
+                        None,
+                        &[DataInputType::Bytes],
+                        &self.system_services,
+                        cancel_prompt,
+                        s16!("Share"),
+                    ) {
+                        DataInput::Bytes(b) => Some(b),
+                        _ => None,
+                    };
+                }
+                ShareFormat::Mnemonic(word_list) => {
+                    let mut text = match prompt_for_data_input(
+                        None,
+                        &[DataInputType::Text],
+                        &self.system_services,
+                        cancel_prompt,
+                        s16!("Share Words"),
+                    ) {
+                        DataInput::Text(t) => t,
+                        _ => return None,
+                    };
+
+                    // Word lists are lower case; split the input on spaces.
+                    for character in text.iter_mut() {
+                        if *character >= c16!("A") && *character <= c16!("Z") {
+                            *character += 32;
+                        }
+                    }
+
+                    let words: Vec<String16> = text
+                        .split(|c| *c == c16!(" ") || *c == 0)
+                        .filter(|w| w.len() > 0)
+                        .map(|w| String16::from(w))
+                        .collect();
+
+                    let result = shamir::try_read_share_words(&word_list, &words);
+                    match result {
+                        Ok(b) => {
+                            text.fill(0);
+                            return Some(b);
+                        }
+                        Err((word, _)) => {
+                            console.in_colours(constants::ERROR_COLOURS, |c| {
+                                c.output_utf16(s16!("Invalid word: "))
+                                    .output_utf16_line(word)
+                            });
+                        }
+                    }
+
+                    text.fill(0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_share_format, ShareFormat};
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::shamir,
+    integers::{NumericBase, NumericBases},
+    programs::{console::write_string_program_output, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_bytes_from_any_data_type, prompt_for_clipboard_write, prompt_for_data_input,
+            prompt_for_u8, ConsoleUiTitle, ConsoleWriteable,
+        },
+        DataInput, DataInputType,
+    },
+    String16,
+};
+use alloc::{format, vec::Vec};
+use macros::{c16, s16};
+
+pub struct ConsoleShamirSecretSplittingProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleShamirSecretSplittingProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleShamirSecretSplittingProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("Shamir Secret Splitting")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!("This program splits a secret into shares with Shamir's Secret Sharing over GF(256); any threshold-sized subset of the shares can recover the secret, while fewer shares reveal nothing about it."))
+            .output_utf16_line(s16!("Each share carries the threshold, its index and a checksum. A digest of the secret is split alongside it, so a recombined secret can be verified."))
+            .output_utf16_line(s16!("The polynomials' random coefficients are derived from a coefficient seed of at least 32 bytes; you may wish to use the output of one of the entropy collection programs. Never reuse a coefficient seed."));
+        const CANCEL_PROMPT: String16 = s16!("Cancel secret splitting?");
+
+        let mut secret = match prompt_for_bytes_from_any_data_type(
+            &self.system_services,
+            CANCEL_PROMPT,
+            s16!("Secret"),
+        ) {
+            Ok(s) => s,
+            Err(e) => return e,
+        };
+
+        if secret.len() == 0 {
+            return s16!("The secret cannot be empty.").to_program_error();
+        }
+
+        let threshold = match prompt_for_u8(
+            |i| match i {
+                0 => Some(s16!("Threshold must be greater than zero.")),
+                _ => None,
+            },
+            s16!("Threshold"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(t) => t,
+            None => {
+                secret.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let share_count = match prompt_for_u8(
+            |i| {
+                if i < threshold {
+                    Some(s16!("Share Count cannot be lower than the threshold."))
+                } else {
+                    None
+                }
+            },
+            s16!("Share Count"),
+            &self.system_services,
+            CANCEL_PROMPT,
+            Some(NumericBases::Decimal.into()),
+        ) {
+            Some(c) => c,
+            None => {
+                secret.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let mut coefficient_seed = loop {
+            match prompt_for_data_input(
+                None,
+                &[DataInputType::Bytes],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Coefficient Seed"),
+            ) {
+                DataInput::Bytes(mut b) => {
+                    if b.len() >= shamir::MINIMUM_COEFFICIENT_SEED_LENGTH {
+                        break b;
+                    }
+
+                    b.fill(0);
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.output_utf16_line(s16!("The coefficient seed must be at least 32 bytes."))
+                    });
+                }
+                _ => {
+                    secret.fill(0);
+                    return ProgramExitResult::UserCancelled;
+                }
+            }
+        };
+
+        let format = match prompt_for_share_format(&self.system_services, CANCEL_PROMPT) {
+            Some(f) => f,
+            None => {
+                secret.fill(0);
+                coefficient_seed.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let shares = shamir::try_split_secret(&secret, threshold, share_count, &coefficient_seed);
+        secret.fill(0);
+        coefficient_seed.fill(0);
+        let mut shares = match shares {
+            Ok(s) => s,
+            Err(e) => return e.to_program_error(),
+        };
+
+        console
+            .line_start()
+            .new_line()
+            .in_colours(constants::SUCCESS_COLOURS, |c| {
+                c.output_utf32_line(&format!(
+                    "Split the secret into {} shares with a threshold of {}.\0",
+                    share_count, threshold
+                ))
+            })
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "NOTE: Distribute each share to a separate custodian; any shares which are not written down or clipboarded are lost."
+                ))
+            });
+
+        for (i, share) in shares.iter_mut().enumerate() {
+            let label: Vec<u16> = format!("Share {} of {}\0", i + 1, share_count)
+                .encode_utf16()
+                .collect();
+
+            let mut encoded_share = match format {
+                ShareFormat::Hexadecimal => {
+                    NumericBase::BASE_16.build_string_from_bytes(share, true)
+                }
+                ShareFormat::Mnemonic(word_list) => {
+                    let mut words = shamir::get_share_words(&word_list, share);
+                    let mut encoded_share = Vec::with_capacity(
+                        (words.len() - 1) + words.iter().map(|w| w.content_length()).sum::<usize>(),
+                    );
+
+                    for j in 0..words.len() {
+                        encoded_share.extend(words[j].content_iterator().by_ref());
+                        if j < words.len() - 1 {
+                            // Append a space if not the last word.
+                            encoded_share.push(c16!(" "));
+                        }
+
+                        // Clear the word in the underlying vector.
+                        words[j] = word_list.words()[0];
+                    }
+
+                    encoded_share
+                }
+            };
+
+            write_string_program_output(
+                &self.system_services,
+                String16::from(&label),
+                String16::from(&encoded_share),
+            );
+            encoded_share.fill(0);
+
+            if format == ShareFormat::Hexadecimal {
+                prompt_for_clipboard_write(
+                    &self.system_services,
+                    ClipboardEntry::Bytes(s16!("Shamir share"), share[..].into()),
+                );
+            }
+
+            share.fill(0);
+        }
+
+        ProgramExitResult::Success
+    }
+}
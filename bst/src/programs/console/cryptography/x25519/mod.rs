@@ -0,0 +1,221 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod x25519_key_agreement;
+mod x25519_key_pair_derivation;
+
+use crate::{
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::edwards::x25519::{
+        self, AGE_IDENTITY_HUMAN_READABLE_PART, AGE_RECIPIENT_HUMAN_READABLE_PART, KEY_LENGTH,
+    },
+    encoding::base64,
+    integers::NumericBase,
+    programs::{
+        exit_result_handlers::ProgramExitResultHandler,
+        program_lists::{ProgramList, ProgramListProgram, ProgramSelector},
+        Program,
+    },
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_data_input, prompt_for_fixed_length_bytes, ConsoleUiConfirmationPrompt,
+            ConsoleUiList, ConsoleUiTitle, ConsoleWriteable,
+        },
+        ConfirmationPrompt, DataInput, DataInputType,
+    },
+    String16,
+};
+use alloc::{sync::Arc, vec::Vec};
+use macros::s16;
+use x25519_key_agreement::ConsoleX25519KeyAgreementProgram;
+use x25519_key_pair_derivation::ConsoleX25519KeyPairDerivationProgram;
+
+pub fn get_x25519_program_list<
+    'a,
+    TSystemServices: SystemServices,
+    TProgramSelector: ProgramSelector + 'static,
+    TProgramExitResultHandler: ProgramExitResultHandler + 'static,
+>(
+    system_services: &TSystemServices,
+    program_selector: &TProgramSelector,
+    exit_result_handler: &TProgramExitResultHandler,
+) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
+    let programs: [Arc<dyn Program>; 2] = [
+        Arc::from(ConsoleX25519KeyPairDerivationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleX25519KeyAgreementProgram::from(
+            system_services.clone(),
+        )),
+    ];
+
+    ProgramList::from(Arc::from(programs), s16!("X25519 Programs"))
+        .as_program(program_selector.clone(), exit_result_handler.clone())
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum X25519KeyFormat {
+    Hexadecimal,
+    WireGuard,
+    Age,
+}
+
+impl X25519KeyFormat {
+    fn encode_private_key(&self, private_key: &[u8; KEY_LENGTH]) -> Vec<u16> {
+        match self {
+            X25519KeyFormat::Hexadecimal => {
+                NumericBase::BASE_16.build_string_from_bytes(private_key, false)
+            }
+            X25519KeyFormat::WireGuard => base64::encode(private_key),
+            X25519KeyFormat::Age => {
+                // age identities are written in upper case, with the 'AGE-SECRET-KEY-' human readable part.
+                let mut string =
+                    x25519::encode_age_key(AGE_IDENTITY_HUMAN_READABLE_PART, private_key);
+                for character in string.iter_mut() {
+                    if *character >= b'a' as u16 && *character <= b'z' as u16 {
+                        *character -= 32;
+                    }
+                }
+
+                string
+            }
+        }
+    }
+
+    fn encode_public_key(&self, public_key: &[u8; KEY_LENGTH]) -> Vec<u16> {
+        match self {
+            X25519KeyFormat::Hexadecimal => {
+                NumericBase::BASE_16.build_string_from_bytes(public_key, false)
+            }
+            X25519KeyFormat::WireGuard => base64::encode(public_key),
+            X25519KeyFormat::Age => {
+                x25519::encode_age_key(AGE_RECIPIENT_HUMAN_READABLE_PART, public_key)
+            }
+        }
+    }
+
+    fn try_decode_key(&self, string: &[u16], is_private_key: bool) -> Option<[u8; KEY_LENGTH]> {
+        match self {
+            // Hexadecimal keys are entered as bytes, rather than text.
+            X25519KeyFormat::Hexadecimal => None,
+            X25519KeyFormat::WireGuard => match base64::decode(string) {
+                Some(mut b) => {
+                    let key = <[u8; KEY_LENGTH]>::try_from(&b[..]).ok();
+                    b.fill(0);
+                    key
+                }
+                None => None,
+            },
+            X25519KeyFormat::Age => x25519::try_decode_age_key(
+                if is_private_key {
+                    AGE_IDENTITY_HUMAN_READABLE_PART
+                } else {
+                    AGE_RECIPIENT_HUMAN_READABLE_PART
+                },
+                string,
+            ),
+        }
+    }
+}
+
+impl ConsoleWriteable for X25519KeyFormat {
+    fn write_to<T: ConsoleOut>(&self, console: &T) {
+        console.output_utf16(match self {
+            X25519KeyFormat::Hexadecimal => s16!("Hexadecimal"),
+            X25519KeyFormat::WireGuard => s16!("WireGuard (Base-64)"),
+            X25519KeyFormat::Age => s16!("age (Bech32)"),
+        });
+    }
+}
+
+fn prompt_for_key_format<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancellation_prompt: String16,
+) -> Option<X25519KeyFormat> {
+    loop {
+        system_services.get_console_out().line_start().new_line();
+        match ConsoleUiList::from(
+            ConsoleUiTitle::from(s16!(" Key Format "), constants::SMALL_TITLE),
+            constants::SELECT_LIST,
+            &[
+                X25519KeyFormat::Hexadecimal,
+                X25519KeyFormat::WireGuard,
+                X25519KeyFormat::Age,
+            ][..],
+        )
+        .prompt_for_selection(system_services)
+        {
+            Some((f, _, _)) => break Some(*f),
+            None => {
+                if ConsoleUiConfirmationPrompt::from(system_services)
+                    .prompt_for_confirmation(cancellation_prompt)
+                {
+                    break None;
+                }
+            }
+        }
+    }
+}
+
+fn prompt_for_key<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    format: X25519KeyFormat,
+    cancellation_prompt: String16,
+    label: String16<'static>,
+    is_private_key: bool,
+) -> Option<[u8; KEY_LENGTH]> {
+    if format == X25519KeyFormat::Hexadecimal {
+        return prompt_for_fixed_length_bytes::<KEY_LENGTH, TSystemServices>(
+            system_services,
+            cancellation_prompt,
+            label,
+        );
+    }
+
+    let console = system_services.get_console_out();
+    loop {
+        let mut text = match prompt_for_data_input(
+            None,
+            &[DataInputType::Text],
+            system_services,
+            cancellation_prompt,
+            label,
+        ) {
+            DataInput::Text(t) => t,
+            _ => return None,
+        };
+
+        let key = format.try_decode_key(&text, is_private_key);
+        text.fill(0);
+        if key.is_some() {
+            return key;
+        }
+
+        console.in_colours(constants::ERROR_COLOURS, |c| {
+            c.line_start().new_line().output_utf16_line(match (format, is_private_key) {
+                (X25519KeyFormat::Age, true) => {
+                    s16!("age identities are 32 byte keys encoded as bech32, starting 'AGE-SECRET-KEY-1'.")
+                }
+                (X25519KeyFormat::Age, false) => {
+                    s16!("age recipients are 32 byte keys encoded as bech32, starting 'age1'.")
+                }
+                _ => s16!("WireGuard keys are 32 byte keys encoded as base-64."),
+            })
+        });
+    }
+}
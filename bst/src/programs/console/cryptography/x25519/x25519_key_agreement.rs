@@ -0,0 +1,110 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_key, prompt_for_key_format};
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::edwards::x25519,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{prompt_for_clipboard_write, ConsoleUiTitle, ConsoleWriteable},
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleX25519KeyAgreementProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleX25519KeyAgreementProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleX25519KeyAgreementProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("X25519 Key Agreement")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console.output_utf16_line(s16!(
+            "This program calculates an X25519 (RFC 7748) shared secret from our private key and a peer's public key."
+        ));
+        console.output_utf16_line(s16!(
+            "Keys can be entered as hexadecimal bytes, WireGuard (base-64) keys, or age identities and recipients."
+        ));
+        const CANCEL_PROMPT: String16 = s16!("Cancel key agreement?");
+
+        let format = match prompt_for_key_format(&self.system_services, CANCEL_PROMPT) {
+            Some(f) => f,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut private_key = match prompt_for_key(
+            &self.system_services,
+            format,
+            CANCEL_PROMPT,
+            s16!("Private Key"),
+            true,
+        ) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let peer_public_key = match prompt_for_key(
+            &self.system_services,
+            format,
+            CANCEL_PROMPT,
+            s16!("Peer Public Key"),
+            false,
+        ) {
+            Some(k) => k,
+            None => {
+                private_key.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let shared_secret = x25519::try_agree(&private_key, &peer_public_key);
+        private_key.fill(0);
+
+        match shared_secret {
+            Some(mut s) => {
+                write_bytes(&self.system_services, s16!("Shared Secret"), &s);
+                prompt_for_clipboard_write(
+                    &self.system_services,
+                    ClipboardEntry::Bytes(s16!("X25519 shared secret"), s[..].into()),
+                );
+
+                s.fill(0);
+                ProgramExitResult::Success
+            }
+            None => {
+                s16!("The peer public key is a low order point; the shared secret would be zero.")
+                    .to_program_error()
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::{prompt_for_key, prompt_for_key_format};
+use crate::{
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    cryptography::asymmetric::edwards::x25519,
+    programs::{console::write_string_program_output, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{prompt_for_clipboard_write, ConsoleUiTitle, ConsoleWriteable},
+    String16,
+};
+use macros::s16;
+
+pub struct ConsoleX25519KeyPairDerivationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleX25519KeyPairDerivationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleX25519KeyPairDerivationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("X25519 Key Pair Derivation")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!("This program derives an X25519 (RFC 7748) key pair from a 32 byte private key, as used by WireGuard and age."))
+            .output_utf16_line(s16!("Any 32 bytes of entropy are a valid private key; you may wish to use the output of one of the entropy collection programs."))
+            .output_utf16_line(s16!("Existing WireGuard private keys and age identities can also be entered, to recover their public keys."));
+        const CANCEL_PROMPT: String16 = s16!("Cancel key pair derivation?");
+
+        let format = match prompt_for_key_format(&self.system_services, CANCEL_PROMPT) {
+            Some(f) => f,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        let mut private_key = match prompt_for_key(
+            &self.system_services,
+            format,
+            CANCEL_PROMPT,
+            s16!("Private Key"),
+            true,
+        ) {
+            Some(k) => k,
+            None => return ProgramExitResult::UserCancelled,
+        };
+
+        // Clamp the private key; the result is the same either way, but WireGuard stores its private keys clamped.
+        x25519::clamp(&mut private_key);
+        let public_key = x25519::derive_public_key(&private_key);
+
+        let mut encoded_private_key = format.encode_private_key(&private_key);
+        private_key.fill(0);
+
+        write_string_program_output(
+            &self.system_services,
+            s16!("Private Key"),
+            String16::from(&encoded_private_key),
+        );
+        encoded_private_key.fill(0);
+
+        let encoded_public_key = format.encode_public_key(&public_key);
+        write_string_program_output(
+            &self.system_services,
+            s16!("Public Key"),
+            String16::from(&encoded_public_key),
+        );
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::String16(s16!("X25519 public key"), encoded_public_key.into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
@@ -78,6 +78,14 @@ where
             .output_utf16(mnemonic_format.word_list().name())
             .output_utf16_line(s16!(" word list."));
 
+        let derivation_settings = match self.mnemonic_parser.bip_32_derivation_settings() {
+            Some(s) => s,
+            None => {
+                return s16!("This mnemonic format does not define a BIP 32 seed derivation.")
+                    .to_program_error()
+            }
+        };
+
         let mnemonic_input_result = get_mnemonic_input(
             |r| r.can_derive_bip_32_seed(),
             &self.system_services,
@@ -87,20 +95,17 @@ where
 
         match mnemonic_input_result {
             Some((mnemonic, _)) => {
-                let mut derived_seed = self
-                    .mnemonic_parser
-                    .bip_32_derivation_settings()
-                    .derive_hd_wallet_seed(
-                        ConsoleUiTextBox::from(&self.system_services, constants::TEXT_INPUT)
-                            .get_text_input(
-                                console.size().width(),
-                                text_input_paste_handler,
-                                s16!("Extension Phrase"),
-                                s16!(" Text "),
-                                Some(allow_mnemonic_text_character),
-                            ),
-                        mnemonic,
-                    );
+                let mut derived_seed = derivation_settings.derive_hd_wallet_seed(
+                    ConsoleUiTextBox::from(&self.system_services, constants::TEXT_INPUT)
+                        .get_text_input(
+                            console.size().width(),
+                            text_input_paste_handler,
+                            s16!("Extension Phrase"),
+                            s16!(" Text "),
+                            Some(allow_mnemonic_text_character),
+                        ),
+                    mnemonic,
+                );
 
                 write_bytes(
                     &self.system_services,
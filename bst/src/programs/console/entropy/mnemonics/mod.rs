@@ -19,6 +19,7 @@ mod electrum;
 mod mnemonic_bip_32_seed_deriver;
 mod mnemonic_entropy_decoder;
 mod mnemonic_entropy_encoder;
+mod slip_39;
 
 use crate::{
     console_out::ConsoleOut,
@@ -43,7 +44,7 @@ pub fn get_entropy_mnemonic_program_list<
     program_selector: &TProgramSelector,
     exit_result_handler: &TProgramExitResultHandler,
 ) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
-    let programs: [Arc<dyn Program>; 2] = [
+    let programs: [Arc<dyn Program>; 3] = [
         Arc::from(bip_39::get_bip_39_mnemonic_program_list(
             system_services,
             program_selector,
@@ -54,6 +55,11 @@ pub fn get_entropy_mnemonic_program_list<
             program_selector,
             exit_result_handler,
         )),
+        Arc::from(slip_39::get_slip_39_mnemonic_program_list(
+            system_services,
+            program_selector,
+            exit_result_handler,
+        )),
     ];
     ProgramList::from(Arc::from(programs), s16!("Entropy Mnemonic Programs"))
         .as_program(program_selector.clone(), exit_result_handler.clone())
@@ -0,0 +1,124 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+mod slip_39_share_generation;
+mod slip_39_share_recovery;
+
+use crate::{
+    bitcoin::mnemonics::slip_39,
+    console_out::ConsoleOut,
+    constants,
+    programs::{
+        exit_result_handlers::ProgramExitResultHandler,
+        program_lists::{ProgramList, ProgramListProgram, ProgramSelector},
+        Program,
+    },
+    system_services::SystemServices,
+    ui::console::{text_input_paste_handler, ConsoleUiTextBox, ConsoleWriteable},
+    String16,
+};
+use alloc::{format, sync::Arc, vec::Vec};
+use macros::s16;
+use slip_39_share_generation::ConsoleSlip39ShareGenerationProgram;
+use slip_39_share_recovery::ConsoleSlip39ShareRecoveryProgram;
+
+pub fn get_slip_39_mnemonic_program_list<
+    'a,
+    TSystemServices: SystemServices,
+    TProgramSelector: ProgramSelector + 'static,
+    TProgramExitResultHandler: ProgramExitResultHandler + 'static,
+>(
+    system_services: &TSystemServices,
+    program_selector: &TProgramSelector,
+    exit_result_handler: &TProgramExitResultHandler,
+) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
+    let programs: [Arc<dyn Program>; 2] = [
+        Arc::from(ConsoleSlip39ShareGenerationProgram::from(
+            system_services.clone(),
+        )),
+        Arc::from(ConsoleSlip39ShareRecoveryProgram::from(
+            system_services.clone(),
+        )),
+    ];
+
+    ProgramList::from(Arc::from(programs), s16!("SLIP 39 Mnemonic Programs"))
+        .as_program(program_selector.clone(), exit_result_handler.clone())
+}
+
+fn allow_passphrase_character(character: u16) -> bool {
+    // SLIP 39 passphrases are restricted to printable ASCII.
+    character >= 32 && character <= 126
+}
+
+fn prompt_for_passphrase<TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+) -> Vec<u8> {
+    let mut passphrase = ConsoleUiTextBox::from(system_services, constants::TEXT_INPUT)
+        .get_text_input(
+            system_services.get_console_out().size().width(),
+            text_input_paste_handler,
+            s16!("Passphrase"),
+            s16!(" Text "),
+            Some(allow_passphrase_character),
+        );
+
+    let passphrase_string = String16::from(&passphrase);
+    let mut passphrase_bytes = Vec::with_capacity(passphrase_string.utf8_content_length());
+    passphrase_string.write_content_to_utf8_vec(&mut passphrase_bytes);
+    passphrase.fill(0);
+    passphrase_bytes
+}
+
+impl<'a> ConsoleWriteable for slip_39::MnemonicParsingResult<'a> {
+    fn write_to<T: ConsoleOut>(&self, console: &T) {
+        match self {
+            slip_39::MnemonicParsingResult::InvalidLength => console
+                .in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16(s16!("Invalid Length"))
+                }),
+            slip_39::MnemonicParsingResult::InvalidWordEncountered(w, _) => console
+                .in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16(s16!("Invalid word: ")).output_utf16(*w)
+                }),
+            slip_39::MnemonicParsingResult::InvalidChecksum => console
+                .in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16(s16!("Share failed checksum."))
+                }),
+            slip_39::MnemonicParsingResult::InvalidPadding => console
+                .in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16(s16!("Share has invalid padding."))
+                }),
+            slip_39::MnemonicParsingResult::InvalidGroupParameters => console
+                .in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16(s16!("Share has invalid group parameters."))
+                }),
+            slip_39::MnemonicParsingResult::Valid(share) => {
+                console.in_colours(constants::SUCCESS_COLOURS, |c| {
+                    c.output_utf32(&format!(
+                        "Valid {}share {}; group {} of {} ({} required), member {} ({} required).\0",
+                        if share.extendable() { "extendable " } else { "" },
+                        share.identifier(),
+                        share.group_index() + 1,
+                        share.group_count(),
+                        share.group_threshold(),
+                        share.member_index() + 1,
+                        share.member_threshold()
+                    ))
+                })
+            }
+        };
+    }
+}
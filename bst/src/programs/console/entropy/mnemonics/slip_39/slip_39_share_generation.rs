@@ -0,0 +1,301 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_passphrase;
+use crate::{
+    bitcoin::mnemonics::slip_39,
+    console_out::ConsoleOut,
+    constants,
+    integers::NumericBases,
+    programs::{console::write_string_program_output, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_data_input, prompt_for_u8, ConsoleUiConfirmationPrompt, ConsoleUiTitle,
+            ConsoleWriteable,
+        },
+        ConfirmationPrompt, DataInput, DataInputType,
+    },
+    String16,
+};
+use alloc::{format, vec::Vec};
+use macros::{c16, s16};
+
+pub struct ConsoleSlip39ShareGenerationProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleSlip39ShareGenerationProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleSlip39ShareGenerationProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("SLIP 39 Share Generation")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!("This program splits a 16 or 32 byte master secret into SLIP 39 mnemonic shares, as used by Trezor wallets. The master secret is encrypted with a passphrase, then split into groups, each of which is split into member shares."))
+            .output_utf16_line(s16!("The master secret is used directly as the BIP 32 HD wallet seed; you may wish to use the output of one of the entropy collection programs."))
+            .output_utf16_line(s16!("The identifier and random share data are derived from a random seed of at least 32 bytes. Never reuse a random seed."));
+        const CANCEL_PROMPT: String16 = s16!("Cancel share generation?");
+
+        let mut master_secret = loop {
+            match prompt_for_data_input(
+                None,
+                &[DataInputType::Bytes],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Master Secret"),
+            ) {
+                DataInput::Bytes(mut b) => {
+                    if slip_39::MNEMONIC_FORMAT
+                        .get_available_lengths_for_byte_count(b.len())
+                        .len()
+                        > 0
+                    {
+                        break b;
+                    }
+
+                    b.fill(0);
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.output_utf16_line(s16!("The master secret must be 16 or 32 bytes."))
+                    });
+                }
+                _ => return ProgramExitResult::UserCancelled,
+            }
+        };
+
+        let mut passphrase = prompt_for_passphrase(&self.system_services);
+        let extendable = ConsoleUiConfirmationPrompt::from(&self.system_services)
+            .prompt_for_confirmation(s16!("Create an extendable backup?"));
+
+        let parameters = self.prompt_for_parameters(CANCEL_PROMPT);
+        let (iteration_exponent, group_threshold, groups) = match parameters {
+            Some(p) => p,
+            None => {
+                master_secret.fill(0);
+                passphrase.fill(0);
+                return ProgramExitResult::UserCancelled;
+            }
+        };
+
+        let mut random_seed = loop {
+            match prompt_for_data_input(
+                None,
+                &[DataInputType::Bytes],
+                &self.system_services,
+                CANCEL_PROMPT,
+                s16!("Random Seed"),
+            ) {
+                DataInput::Bytes(mut b) => {
+                    if b.len() >= slip_39::MINIMUM_RANDOM_SEED_LENGTH {
+                        break b;
+                    }
+
+                    b.fill(0);
+                    console.in_colours(constants::ERROR_COLOURS, |c| {
+                        c.output_utf16_line(s16!("The random seed must be at least 32 bytes."))
+                    });
+                }
+                _ => {
+                    master_secret.fill(0);
+                    passphrase.fill(0);
+                    return ProgramExitResult::UserCancelled;
+                }
+            }
+        };
+
+        let shares = slip_39::try_generate_shares(
+            &master_secret,
+            &passphrase,
+            extendable,
+            iteration_exponent,
+            group_threshold,
+            &groups,
+            &random_seed,
+        );
+
+        master_secret.fill(0);
+        passphrase.fill(0);
+        random_seed.fill(0);
+        let shares = match shares {
+            Ok(s) => s,
+            Err(e) => return e.to_program_error(),
+        };
+
+        console
+            .line_start()
+            .new_line()
+            .in_colours(constants::SUCCESS_COLOURS, |c| {
+                c.output_utf16_line(s16!("Generated shares successfully."))
+            })
+            .in_colours(constants::WARNING_COLOURS, |c| {
+                c.output_utf16_line(s16!(
+                    "NOTE: You will not be prompted to clipboard the shares. Write them down."
+                ))
+            });
+
+        for group in &shares {
+            for share in group {
+                let label: Vec<u16> = format!(
+                    "Group {} of {}, Member {} of {}\0",
+                    share.group_index() + 1,
+                    share.group_count(),
+                    share.member_index() + 1,
+                    group.len()
+                )
+                .encode_utf16()
+                .collect();
+
+                let mut words = slip_39::get_share_mnemonic(share);
+                let mut mnemonic = Vec::with_capacity(
+                    (words.len() - 1) + words.iter().map(|w| w.content_length()).sum::<usize>(),
+                );
+
+                for i in 0..words.len() {
+                    mnemonic.extend(words[i].content_iterator().by_ref());
+                    if i < words.len() - 1 {
+                        // Append a space if not the last word.
+                        mnemonic.push(c16!(" "));
+                    }
+
+                    // Clear the word in the underlying vector.
+                    words[i] = slip_39::WORD_LIST.words()[0];
+                }
+
+                write_string_program_output(
+                    &self.system_services,
+                    String16::from(&label),
+                    String16::from(&mnemonic),
+                );
+                mnemonic.fill(0);
+            }
+        }
+
+        ProgramExitResult::Success
+    }
+}
+
+impl<TSystemServices: SystemServices> ConsoleSlip39ShareGenerationProgram<TSystemServices> {
+    fn prompt_for_parameters(
+        &self,
+        cancel_prompt: String16<'static>,
+    ) -> Option<(u8, u8, Vec<(u8, u8)>)> {
+        let console = self.system_services.get_console_out();
+        let iteration_exponent = prompt_for_u8(
+            |i| {
+                if i > slip_39::MAXIMUM_ITERATION_EXPONENT {
+                    Some(s16!("Iteration Exponent cannot exceed 15."))
+                } else {
+                    None
+                }
+            },
+            s16!("Iteration Exponent"),
+            &self.system_services,
+            cancel_prompt,
+            Some(NumericBases::Decimal.into()),
+        )?;
+
+        let group_count = prompt_for_u8(
+            |i| {
+                if i == 0 || i > slip_39::MAXIMUM_SHARE_COUNT {
+                    Some(s16!("Group Count must be between 1 and 16."))
+                } else {
+                    None
+                }
+            },
+            s16!("Group Count"),
+            &self.system_services,
+            cancel_prompt,
+            Some(NumericBases::Decimal.into()),
+        )?;
+
+        let group_threshold = prompt_for_u8(
+            |i| {
+                if i == 0 || i > group_count {
+                    Some(s16!(
+                        "Group Threshold must be between 1 and the group count."
+                    ))
+                } else {
+                    None
+                }
+            },
+            s16!("Group Threshold"),
+            &self.system_services,
+            cancel_prompt,
+            Some(NumericBases::Decimal.into()),
+        )?;
+
+        let mut groups = Vec::with_capacity(group_count as usize);
+        for group_index in 0..group_count {
+            console
+                .line_start()
+                .new_line()
+                .in_colours(constants::LABEL_COLOURS, |c| {
+                    c.output_utf32_line(&format!("Group {} of {}\0", group_index + 1, group_count))
+                });
+
+            let member_count = prompt_for_u8(
+                |i| {
+                    if i == 0 || i > slip_39::MAXIMUM_SHARE_COUNT {
+                        Some(s16!("Member Count must be between 1 and 16."))
+                    } else {
+                        None
+                    }
+                },
+                s16!("Member Count"),
+                &self.system_services,
+                cancel_prompt,
+                Some(NumericBases::Decimal.into()),
+            )?;
+
+            let member_threshold = if member_count == 1 {
+                1
+            } else {
+                // A member threshold of 1 is only permitted for single member groups.
+                prompt_for_u8(
+                    |i| {
+                        if i < 2 || i > member_count {
+                            Some(s16!(
+                                "Member Threshold must be between 2 and the member count."
+                            ))
+                        } else {
+                            None
+                        }
+                    },
+                    s16!("Member Threshold"),
+                    &self.system_services,
+                    cancel_prompt,
+                    Some(NumericBases::Decimal.into()),
+                )?
+            };
+
+            groups.push((member_threshold, member_count));
+        }
+
+        Some((iteration_exponent, group_threshold, groups))
+    }
+}
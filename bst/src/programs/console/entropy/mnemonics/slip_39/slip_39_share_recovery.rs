@@ -0,0 +1,112 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use super::prompt_for_passphrase;
+use crate::{
+    bitcoin::mnemonics::slip_39,
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    programs::{console::write_bytes, Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::console::{
+        get_mnemonic_input, prompt_for_clipboard_write, ConsoleUiTitle, ConsoleWriteable,
+    },
+    String16,
+};
+use alloc::{format, vec::Vec};
+use macros::s16;
+
+pub struct ConsoleSlip39ShareRecoveryProgram<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> ConsoleSlip39ShareRecoveryProgram<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program
+    for ConsoleSlip39ShareRecoveryProgram<TSystemServices>
+{
+    fn name(&self) -> String16<'static> {
+        s16!("SLIP 39 Master Secret Recovery")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!("This program recovers a master secret from SLIP 39 mnemonic shares, such as a Trezor Model T backup. Shares will be requested until enough groups meet their member thresholds."))
+            .output_utf16_line(s16!("The master secret is the BIP 32 HD wallet seed. Any passphrase will produce a valid master secret, so double-check the passphrase you enter."));
+
+        let mut shares: Vec<slip_39::Share> = Vec::new();
+        loop {
+            let share = match get_mnemonic_input(
+                |r| match r {
+                    slip_39::MnemonicParsingResult::Valid(..) => true,
+                    _ => false,
+                },
+                &self.system_services,
+                &slip_39::MnemonicParser,
+                5,
+            ) {
+                Some((_, slip_39::MnemonicParsingResult::Valid(share))) => share,
+                _ => return ProgramExitResult::UserCancelled,
+            };
+
+            shares.push(share);
+            match slip_39::try_validate_share_set(&shares) {
+                Ok(true) => break,
+                Ok(false) => console.in_colours(constants::SUCCESS_COLOURS, |c| {
+                    c.output_utf32_line(&format!(
+                        "Accepted share; collected {} shares, more are required.\0",
+                        shares.len()
+                    ))
+                }),
+                Err(e) => {
+                    shares.pop();
+                    console.in_colours(constants::ERROR_COLOURS, |c| c.output_utf16_line(e))
+                }
+            };
+        }
+
+        let mut passphrase = prompt_for_passphrase(&self.system_services);
+        let master_secret = slip_39::try_recover_master_secret(&shares, &passphrase);
+        passphrase.fill(0);
+
+        let mut master_secret = match master_secret {
+            Ok(s) => s,
+            Err(e) => return e.to_program_error(),
+        };
+
+        write_bytes(
+            &self.system_services,
+            s16!("BIP 32 HD Wallet Seed"),
+            &master_secret,
+        );
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::Bytes(s16!("BIP 32 HD Wallet Seed"), master_secret[..].into()),
+        );
+
+        master_secret.fill(0);
+        ProgramExitResult::Success
+    }
+}
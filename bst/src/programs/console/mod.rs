@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod bitcoin;
 mod cryptography;
 mod entropy;
 mod hashing;
@@ -49,7 +50,7 @@ pub fn get_programs_list<
     program_selector: &TProgramSelector,
     exit_result_handler: &TProgramExitResultHandler,
 ) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
-    let programs: [Arc<dyn Program>; 6] = [
+    let programs: [Arc<dyn Program>; 7] = [
         Arc::from(instructions::get_instructional_programs_list(
             system_services,
             program_selector,
@@ -75,6 +76,11 @@ pub fn get_programs_list<
             program_selector,
             exit_result_handler,
         )),
+        Arc::from(bitcoin::get_bitcoin_program_list(
+            system_services,
+            program_selector,
+            exit_result_handler,
+        )),
         Arc::from(power_option_programs::get_power_options_list(
             system_services,
             program_selector,
@@ -0,0 +1,178 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    bitcoin::addresses,
+    clipboard::ClipboardEntry,
+    console_out::ConsoleOut,
+    constants,
+    encoding::bech32::{self, Bech32Variant},
+    integers::NumericBase,
+    programs::{Program, ProgramExitResult},
+    system_services::SystemServices,
+    ui::{
+        console::{
+            prompt_for_clipboard_write, prompt_for_data_input, ConsoleUiContinuePrompt,
+            ConsoleUiKeyValue, ConsoleUiLabel, ConsoleUiTitle, ConsoleWriteable,
+        },
+        ContinuePrompt, DataInput, DataInputType,
+    },
+    String16,
+};
+use alloc::{format, vec::Vec};
+use macros::s16;
+
+pub struct Bech32Validator<TSystemServices: SystemServices> {
+    system_services: TSystemServices,
+}
+
+impl<TSystemServices: SystemServices> Bech32Validator<TSystemServices> {
+    pub const fn from(system_services: TSystemServices) -> Self {
+        Self { system_services }
+    }
+}
+
+impl<TSystemServices: SystemServices> Program for Bech32Validator<TSystemServices> {
+    fn name(&self) -> String16<'static> {
+        s16!("Bech32 Validator")
+    }
+
+    fn run(&self) -> ProgramExitResult {
+        let console = self.system_services.get_console_out();
+        console.clear();
+
+        ConsoleUiTitle::from(self.name(), constants::BIG_TITLE).write_to(&console);
+        console
+            .output_utf16_line(s16!("This program validates a bech32 (BIP 173) or bech32m (BIP 350) string's checksum, such as a segwit or taproot address."))
+            .output_utf16_line(s16!("If the checksum fails, it will try to locate up to 2 mistyped characters after the '1' separator; typos before the separator can't be located."))
+            .in_colours(constants::WARNING_COLOURS, |c| c.output_utf16(s16!("With more than 2 mistyped characters, the suggested corrections may be wrong; always double check them against the source.")));
+
+        let input = match prompt_for_data_input(
+            None,
+            &[DataInputType::Text],
+            &self.system_services,
+            s16!("Cancel bech32 validation?"),
+            s16!("String to validate"),
+        ) {
+            DataInput::Text(t) => t,
+            _ => return ProgramExitResult::UserCancelled,
+        };
+
+        console.line_start().new_line();
+        if let Some((human_readable_part, data, variant)) = bech32::decode(&input) {
+            console.in_colours(constants::SUCCESS_COLOURS, |c| {
+                c.output_utf16_line(match variant {
+                    Bech32Variant::Bech32 => s16!("Valid bech32 checksum."),
+                    Bech32Variant::Bech32m => s16!("Valid bech32m checksum."),
+                })
+            });
+
+            let human_readable_part: Vec<u16> =
+                human_readable_part.iter().map(|c| *c as u16).collect();
+            ConsoleUiKeyValue::from(
+                s16!("Human Readable Part"),
+                String16::from(&human_readable_part),
+            )
+            .write_to(&console);
+
+            if let Some((network, witness_version, witness_program)) =
+                addresses::try_decode_segwit(&input)
+            {
+                ConsoleUiKeyValue::from(s16!("Network"), network.into()).write_to(&console);
+                console
+                    .in_colours(constants::LABEL_COLOURS, |c| {
+                        c.output_utf16(s16!("Witness Version: "))
+                    })
+                    .output_utf32_line(&format!("{}\0", witness_version));
+                ConsoleUiKeyValue::from(
+                    s16!("Witness Program"),
+                    String16::from(
+                        &NumericBase::BASE_16.build_string_from_bytes(&witness_program, false),
+                    ),
+                )
+                .write_to(&console);
+            } else if let Some(bytes) = bech32::regroup_bits(&data, 5, 8, false) {
+                ConsoleUiKeyValue::from(
+                    s16!("Data"),
+                    String16::from(&NumericBase::BASE_16.build_string_from_bytes(&bytes, false)),
+                )
+                .write_to(&console);
+            }
+
+            ConsoleUiContinuePrompt::from(&self.system_services).prompt_for_continue();
+            return ProgramExitResult::Success;
+        }
+
+        let (variant, errors) = match bech32::try_locate_errors(&input) {
+            Some((v, e)) if e.len() > 0 => (v, e),
+            _ => {
+                console.in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16_line(s16!("Invalid bech32 string; no correction with 2 or fewer substitutions could be found."))
+                });
+                ConsoleUiContinuePrompt::from(&self.system_services).prompt_for_continue();
+                return ProgramExitResult::Success;
+            }
+        };
+
+        console.in_colours(constants::ERROR_COLOURS, |c| {
+            c.output_utf16_line(s16!(
+                "Checksum failed; the string is likely to contain the following mistakes:"
+            ))
+        });
+
+        // Write out the string with the mistaken characters highlighted.
+        let mut start = 0;
+        for (i, _) in &errors {
+            console
+                .output_utf16(String16::from(&input[start..*i]))
+                .in_colours(constants::ERROR_COLOURS, |c| {
+                    c.output_utf16(String16::from(&input[*i..*i + 1]))
+                });
+            start = i + 1;
+        }
+
+        console.output_utf16_line(String16::from(&input[start..]));
+        for (i, correction) in &errors {
+            console.output_utf32_line(&format!(
+                "Character {}: '{}' should be '{}'\0",
+                i + 1,
+                char::from_u32(input[*i] as u32).unwrap_or(char::REPLACEMENT_CHARACTER),
+                char::from_u32(*correction as u32).unwrap()
+            ));
+        }
+
+        let mut corrected = input.clone();
+        for (i, correction) in &errors {
+            corrected[*i] = *correction;
+        }
+
+        console.line_start().new_line();
+        ConsoleUiLabel::from(match variant {
+            Bech32Variant::Bech32 => s16!("Corrected String (Bech32)"),
+            Bech32Variant::Bech32m => s16!("Corrected String (Bech32m)"),
+        })
+        .write_to(&console);
+        console.output_utf16_line(String16::from(&corrected));
+
+        ConsoleUiContinuePrompt::from(&self.system_services).prompt_for_continue();
+        prompt_for_clipboard_write(
+            &self.system_services,
+            ClipboardEntry::String16(s16!("Corrected Bech32 String"), corrected.into()),
+        );
+
+        ProgramExitResult::Success
+    }
+}
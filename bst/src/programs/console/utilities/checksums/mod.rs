@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod bech32_validator;
 mod checksum_calculator;
 mod checksum_validator;
 
@@ -38,14 +39,17 @@ pub fn get_checksum_programs<
     program_selector: &TProgramSelector,
     exit_result_handler: &TProgramExitResultHandler,
 ) -> ProgramListProgram<TProgramSelector, TProgramExitResultHandler> {
-    let programs: [Arc<dyn Program>; 2] = [
+    let programs: [Arc<dyn Program>; 3] = [
         Arc::from(checksum_validator::ChecksumValidator::from(
             system_services.clone(),
         )),
         Arc::from(checksum_calculator::ChecksumCalculator::from(
             system_services.clone(),
         )),
+        Arc::from(bech32_validator::Bech32Validator::from(
+            system_services.clone(),
+        )),
     ];
-    ProgramList::from(Arc::from(programs), s16!("Double SHA256 Checksum Programs"))
+    ProgramList::from(Arc::from(programs), s16!("Checksum Programs"))
         .as_program(program_selector.clone(), exit_result_handler.clone())
 }
@@ -16,6 +16,7 @@
 
 mod big_signed_integers;
 mod big_unsigned_integers;
+mod montgomery;
 
 use crate::integers::{BigSigned, BigUnsigned, BITS_PER_DIGIT};
 use core::mem::size_of;
@@ -0,0 +1,114 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    integers::{BigUnsigned, BigUnsignedCalculator, MontgomeryContext},
+    tests::{big_integers::big_unsigned_to_u128, PARALLELIZED_TEST_THREAD_COUNT},
+};
+use alloc::vec::Vec;
+use rand::{random, thread_rng, Rng};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const RANDOM_ITERATIONS: usize = 10000;
+
+fn u64_modpow(base: u64, exponent: u64, modulus: u64) -> u64 {
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1u128 % modulus;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+
+    result as u64
+}
+
+fn random_bytes(length: usize) -> Vec<u8> {
+    (0..length).map(|_| random::<u8>()).collect()
+}
+
+#[test]
+fn even_and_unit_moduli_are_rejected() {
+    assert!(MontgomeryContext::try_from(&BigUnsigned::from_be_bytes(&[0])).is_none());
+    assert!(MontgomeryContext::try_from(&BigUnsigned::from_be_bytes(&[1])).is_none());
+    assert!(MontgomeryContext::try_from(&BigUnsigned::from_be_bytes(&[1, 0])).is_none());
+}
+
+#[test]
+fn random_small_modpow() {
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                let modulus = random::<u64>() | 1;
+                if modulus == 1 {
+                    continue;
+                }
+
+                let base = random::<u64>();
+                let exponent = match thread_rng().gen_range(0..3) {
+                    0 => 0,
+                    1 => random::<u8>() as u64,
+                    _ => random::<u64>(),
+                };
+
+                let context = MontgomeryContext::try_from(&BigUnsigned::from_be_bytes(
+                    &modulus.to_be_bytes(),
+                ))
+                .unwrap();
+                let result = context.modpow(
+                    &BigUnsigned::from_be_bytes(&base.to_be_bytes()),
+                    &BigUnsigned::from_be_bytes(&exponent.to_be_bytes()),
+                );
+
+                assert_eq!(
+                    big_unsigned_to_u128(&result),
+                    u64_modpow(base, exponent, modulus) as u128
+                );
+            }
+        });
+}
+
+#[test]
+fn random_large_modpow_matches_big_unsigned_calculator() {
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT / 100 {
+                let mut modulus_bytes = random_bytes(thread_rng().gen_range(9..=128));
+                *modulus_bytes.last_mut().unwrap() |= 1;
+                let modulus = BigUnsigned::from_be_bytes(&modulus_bytes);
+                let base =
+                    BigUnsigned::from_be_bytes(&random_bytes(thread_rng().gen_range(1..=160)));
+                let exponent =
+                    BigUnsigned::from_be_bytes(&random_bytes(thread_rng().gen_range(1..=32)));
+
+                let mut expected = base.clone();
+                BigUnsignedCalculator::new(256).modpow(&mut expected, &exponent, &modulus);
+                assert_eq!(
+                    MontgomeryContext::try_from(&modulus)
+                        .unwrap()
+                        .modpow(&base, &exponent),
+                    expected
+                );
+            }
+        });
+}
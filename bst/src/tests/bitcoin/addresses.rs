@@ -0,0 +1,113 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::bitcoin::{addresses, hd_wallets::Bip32KeyNetwork};
+use alloc::string::String;
+use hex_literal::hex;
+
+// The generator point's public key; the private key is 1.
+const COMPRESSED_PUBLIC_KEY: [u8; 33] =
+    hex!("0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798");
+const UNCOMPRESSED_PUBLIC_KEY: [u8; 65] = hex!("0479BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8");
+
+fn string(address: alloc::vec::Vec<u16>) -> String {
+    String::from_utf16(&address).unwrap()
+}
+
+#[test]
+fn p2pkh_addresses() {
+    assert_eq!(
+        string(addresses::p2pkh(
+            &COMPRESSED_PUBLIC_KEY,
+            Bip32KeyNetwork::MainNet
+        )),
+        "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"
+    );
+    assert_eq!(
+        string(addresses::p2pkh(
+            &UNCOMPRESSED_PUBLIC_KEY,
+            Bip32KeyNetwork::MainNet
+        )),
+        "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm"
+    );
+    assert_eq!(
+        string(addresses::p2pkh(
+            &COMPRESSED_PUBLIC_KEY,
+            Bip32KeyNetwork::TestNet
+        )),
+        "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r"
+    );
+}
+
+#[test]
+fn p2sh_p2wpkh_addresses() {
+    assert_eq!(
+        string(addresses::p2sh_p2wpkh(
+            &COMPRESSED_PUBLIC_KEY,
+            Bip32KeyNetwork::MainNet
+        )),
+        "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN"
+    );
+    assert_eq!(
+        string(addresses::p2sh_p2wpkh(
+            &COMPRESSED_PUBLIC_KEY,
+            Bip32KeyNetwork::TestNet
+        )),
+        "2NAUYAHhujozruyzpsFRP63mbrdaU5wnEpN"
+    );
+}
+
+#[test]
+fn p2wpkh_addresses() {
+    // BIP 173's example address.
+    assert_eq!(
+        string(addresses::p2wpkh(
+            &COMPRESSED_PUBLIC_KEY,
+            Bip32KeyNetwork::MainNet
+        )),
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+    );
+    assert_eq!(
+        string(addresses::p2wpkh(
+            &COMPRESSED_PUBLIC_KEY,
+            Bip32KeyNetwork::TestNet
+        )),
+        "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+    );
+}
+
+#[test]
+fn p2tr_addresses() {
+    // BIP 86's first receiving address for the 'abandon abandon ... about' mnemonic.
+    let internal_key = hex!("CC8A4BC64D897BDDC5FBC2F670F7A8BA0B386779106CF1223C6FC5D7CD6FC115");
+    assert_eq!(
+        string(addresses::p2tr(&internal_key, Bip32KeyNetwork::MainNet).unwrap()),
+        "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+    );
+
+    let (network, witness_version, program) = addresses::try_decode_segwit(
+        &"bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+            .encode_utf16()
+            .collect::<alloc::vec::Vec<u16>>(),
+    )
+    .unwrap();
+    assert_eq!(network, Bip32KeyNetwork::MainNet);
+    assert_eq!(witness_version, 1);
+    assert_eq!(
+        program,
+        hex!("A60869F0DBCF1DC659C9CECBAF8050135EA9E8CDC487053F1DC6880949DC684C")
+    );
+}
@@ -0,0 +1,163 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::bitcoin::{
+    base_58_encode_with_checksum,
+    hd_wallets::{Bip32KeyNetwork, Bip32KeyType},
+    try_base_58_decode_with_checksum, try_wif_decode, wif_encode, Base58CheckPayloadType,
+};
+use alloc::{string::String, vec::Vec};
+use hex_literal::hex;
+
+fn decode(string: &str) -> Option<Vec<u8>> {
+    try_base_58_decode_with_checksum(&string.encode_utf16().collect::<Vec<u16>>())
+}
+
+fn payload_type(string: &str) -> Base58CheckPayloadType {
+    Base58CheckPayloadType::from_payload(&decode(string).unwrap())
+}
+
+#[test]
+fn base_58_check_round_trips() {
+    for bytes in [
+        &hex!("00")[..],
+        &hex!("0000000102")[..],
+        &hex!("00751E76E8199196D454941C45D1B3A323F1433BD6")[..],
+        &hex!("FFFFFFFFFFFFFFFFFFFF")[..],
+    ] {
+        assert_eq!(
+            try_base_58_decode_with_checksum(&base_58_encode_with_checksum(bytes)).unwrap(),
+            bytes
+        );
+    }
+}
+
+#[test]
+fn base_58_check_decodes_addresses() {
+    assert_eq!(
+        decode("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH").unwrap(),
+        hex!("00751E76E8199196D454941C45D1B3A323F1433BD6")
+    );
+    assert_eq!(
+        payload_type("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"),
+        Base58CheckPayloadType::P2pkhAddress(Bip32KeyNetwork::MainNet)
+    );
+    assert_eq!(
+        payload_type("mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r"),
+        Base58CheckPayloadType::P2pkhAddress(Bip32KeyNetwork::TestNet)
+    );
+    assert_eq!(
+        payload_type("3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN"),
+        Base58CheckPayloadType::P2shAddress(Bip32KeyNetwork::MainNet)
+    );
+    assert_eq!(
+        payload_type("2NAUYAHhujozruyzpsFRP63mbrdaU5wnEpN"),
+        Base58CheckPayloadType::P2shAddress(Bip32KeyNetwork::TestNet)
+    );
+}
+
+#[test]
+fn base_58_check_decodes_private_keys() {
+    // The private key 1.
+    assert_eq!(
+        payload_type("5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDf"),
+        Base58CheckPayloadType::WifPrivateKey(Bip32KeyNetwork::MainNet, false)
+    );
+    assert_eq!(
+        payload_type("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn"),
+        Base58CheckPayloadType::WifPrivateKey(Bip32KeyNetwork::MainNet, true)
+    );
+    assert_eq!(
+        payload_type("cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN87JcbXMTcA"),
+        Base58CheckPayloadType::WifPrivateKey(Bip32KeyNetwork::TestNet, true)
+    );
+
+    // BIP 32 test vector 1's master keys.
+    match payload_type("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi") {
+        Base58CheckPayloadType::ExtendedKey(v) => {
+            assert_eq!(v.key_network(), Bip32KeyNetwork::MainNet);
+            assert_eq!(v.key_type(), Bip32KeyType::Private);
+        }
+        _ => panic!("Expected an extended key."),
+    }
+
+    match payload_type("xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8") {
+        Base58CheckPayloadType::ExtendedKey(v) => {
+            assert_eq!(v.key_network(), Bip32KeyNetwork::MainNet);
+            assert_eq!(v.key_type(), Bip32KeyType::Public);
+        }
+        _ => panic!("Expected an extended key."),
+    }
+}
+
+#[test]
+fn invalid_base_58_check_strings_dont_decode() {
+    // A changed character fails the checksum.
+    assert!(decode("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMJ").is_none());
+
+    // '0', 'O', 'I' and 'l' aren't in the base-58 alphabet.
+    assert!(decode("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAM0").is_none());
+
+    // There must be a checksum.
+    assert!(decode("1111").is_none());
+    assert!(decode("").is_none());
+
+    assert_eq!(
+        Base58CheckPayloadType::from_payload(&hex!("01020304")),
+        Base58CheckPayloadType::Unknown
+    );
+}
+
+#[test]
+fn wif_private_keys_round_trip() {
+    let mut private_key = [0u8; 32];
+    private_key[31] = 1;
+
+    for (wif, network, is_compressed) in [
+        (
+            "5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDf",
+            Bip32KeyNetwork::MainNet,
+            false,
+        ),
+        (
+            "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn",
+            Bip32KeyNetwork::MainNet,
+            true,
+        ),
+        (
+            "cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN87JcbXMTcA",
+            Bip32KeyNetwork::TestNet,
+            true,
+        ),
+    ] {
+        assert_eq!(
+            String::from_utf16(&wif_encode(&private_key, network, is_compressed)).unwrap(),
+            wif
+        );
+        assert_eq!(
+            try_wif_decode(&wif.encode_utf16().collect::<Vec<u16>>()).unwrap(),
+            (private_key, network, is_compressed)
+        );
+    }
+
+    // Addresses aren't private keys.
+    assert!(try_wif_decode(
+        &"1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"
+            .encode_utf16()
+            .collect::<Vec<u16>>()
+    )
+    .is_none());
+}
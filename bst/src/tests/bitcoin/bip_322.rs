@@ -0,0 +1,155 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    bitcoin::{
+        addresses,
+        bip_322::{
+            message_hash, to_spend_txid, try_sign, verify, Bip322AddressType, Bip322SignatureFormat,
+        },
+        hd_wallets::Bip32KeyNetwork,
+    },
+    cryptography::asymmetric::ecc::secp256k1::{schnorr, try_derive_serialized_public_key},
+    encoding::base64,
+};
+use alloc::{string::String, vec::Vec};
+use hex_literal::hex;
+
+// BIP 322's test vectors use the private key L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k.
+const PRIVATE_KEY: [u8; 32] =
+    hex!("bb051cd0dda0246f33c5a9e133ebd8e7bc02a92af6c41adc131ccd7826c5b004");
+const P2WPKH_ADDRESS: &str = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+const P2TR_ADDRESS: &str = "bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3";
+
+fn utf16(string: &str) -> Vec<u16> {
+    string.encode_utf16().collect()
+}
+
+fn base64_decode(string: &str) -> Vec<u8> {
+    base64::decode(&utf16(string)).unwrap()
+}
+
+#[test]
+fn message_hashes() {
+    assert_eq!(
+        message_hash(b""),
+        hex!("c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1")
+    );
+    assert_eq!(
+        message_hash(b"Hello World"),
+        hex!("f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a")
+    );
+}
+
+#[test]
+fn to_spend_transaction_ids() {
+    // Transaction IDs are conventionally displayed in reverse byte order.
+    let script_pubkey = hex!("00142b05d564e6a7a33c087f16e0f730d1440123799d");
+    let mut txid = to_spend_txid(&script_pubkey, b"");
+    txid.reverse();
+    assert_eq!(
+        txid,
+        hex!("c5680aa69bb8d860bf82d4e9cd3504b55dde018de765a91bb566283c545a99a7")
+    );
+
+    let mut txid = to_spend_txid(&script_pubkey, b"Hello World");
+    txid.reverse();
+    assert_eq!(
+        txid,
+        hex!("b79d196740ad5217771c1098fc4a4b51e0535c32236c71f1ea4d61a2d603352b")
+    );
+}
+
+#[test]
+fn p2wpkh_test_vectors_verify() {
+    // These signatures were produced with low R grinding, so they don't match our RFC 6979 signatures.
+    for (message, signature) in [
+        (&b""[..], "AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI="),
+        (b"Hello World", "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI="),
+    ] {
+        let signature = base64_decode(signature);
+        assert!(verify(&utf16(P2WPKH_ADDRESS), message, &signature).unwrap());
+        assert!(!verify(&utf16(P2WPKH_ADDRESS), b"Hello World!", &signature).unwrap());
+    }
+}
+
+#[test]
+fn p2tr_test_vector_verifies() {
+    let internal_key = schnorr::try_derive_x_only_public_key(&PRIVATE_KEY).unwrap();
+    assert_eq!(
+        String::from_utf16(&addresses::p2tr(&internal_key, Bip32KeyNetwork::MainNet).unwrap())
+            .unwrap(),
+        P2TR_ADDRESS
+    );
+
+    // This signature has an explicit SIGHASH_ALL type.
+    let signature = base64_decode("AUHd69PrJQEv+oKTfZ8l+WROBHuy9HKrbFCJu7U1iK2iiEy1vMU5EfMtjc+VSHM7aU0SDbak5IUZRVno2P5mjSafAQ==");
+    assert!(verify(&utf16(P2TR_ADDRESS), b"Hello World", &signature).unwrap());
+    assert!(!verify(&utf16(P2TR_ADDRESS), b"", &signature).unwrap());
+}
+
+#[test]
+fn signatures_round_trip_in_both_formats() {
+    for address_type in [Bip322AddressType::P2wpkh, Bip322AddressType::P2tr] {
+        let address = match address_type {
+            Bip322AddressType::P2wpkh => P2WPKH_ADDRESS,
+            Bip322AddressType::P2tr => P2TR_ADDRESS,
+        };
+
+        let simple = try_sign(
+            &PRIVATE_KEY,
+            address_type,
+            b"Proof of reserves",
+            Bip322SignatureFormat::Simple,
+        )
+        .unwrap();
+        let full = try_sign(
+            &PRIVATE_KEY,
+            address_type,
+            b"Proof of reserves",
+            Bip322SignatureFormat::Full,
+        )
+        .unwrap();
+
+        // The full signature is the to_sign transaction, wrapping the same witness.
+        assert!(full.len() > simple.len());
+        assert_eq!(full[59..full.len() - 4], simple[..]);
+        assert!(verify(&utf16(address), b"Proof of reserves", &simple).unwrap());
+        assert!(verify(&utf16(address), b"Proof of reserves", &full).unwrap());
+        assert!(!matches!(
+            verify(&utf16(address), b"Proof of reserve", &full),
+            Ok(true)
+        ));
+        assert!(!verify(&utf16(address), b"Proof of reserve", &simple).unwrap());
+    }
+}
+
+#[test]
+fn other_keys_and_unsupported_addresses_are_rejected() {
+    let other_signature = try_sign(
+        &[1u8; 32],
+        Bip322AddressType::P2wpkh,
+        b"",
+        Bip322SignatureFormat::Simple,
+    )
+    .unwrap();
+    assert!(!verify(&utf16(P2WPKH_ADDRESS), b"", &other_signature).unwrap());
+
+    // Legacy addresses are signed with BIP 137 signed messages instead.
+    let public_key = try_derive_serialized_public_key(&PRIVATE_KEY).unwrap();
+    let legacy_address = addresses::p2pkh(&public_key, Bip32KeyNetwork::MainNet);
+    assert!(verify(&legacy_address, b"", &other_signature).is_err());
+}
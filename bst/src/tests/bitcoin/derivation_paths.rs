@@ -15,9 +15,11 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::bitcoin::hd_wallets::{
-    Bip32CkdContext, Bip32DerivationPathPoint, Bip32SerializedExtendedKey,
+    try_parse_derivation_path, try_parse_derivation_path_template, Bip32CkdContext,
+    Bip32DerivationPathPoint, Bip32DerivationPathTemplateSegment, Bip32SerializedExtendedKey,
     HARDENED_CHILD_DERIVATION_THRESHOLD,
 };
+use alloc::vec::Vec;
 use hex_literal::hex;
 use macros::s16;
 
@@ -76,3 +78,76 @@ pub fn public_child_key_derivation_doesnt_do_hardened_derivation() {
         Err(s16!("Cannot derive hardened child key from a public key."))
     );
 }
+
+#[test]
+pub fn derivation_paths_are_parsed() {
+    let expected = [
+        Bip32DerivationPathPoint::from(84 | HARDENED_CHILD_DERIVATION_THRESHOLD),
+        Bip32DerivationPathPoint::from(0 | HARDENED_CHILD_DERIVATION_THRESHOLD),
+        Bip32DerivationPathPoint::from(0 | HARDENED_CHILD_DERIVATION_THRESHOLD),
+        Bip32DerivationPathPoint::from(0),
+        Bip32DerivationPathPoint::from(5),
+    ];
+
+    for path in [
+        s16!("m/84'/0'/0'/0/5"),
+        s16!("84h/0H/0'/0/5/"),
+        s16!("m / 84' / 0' / 0' / 0 / 5"),
+    ] {
+        assert_eq!(
+            try_parse_derivation_path(path.content_slice()).unwrap(),
+            expected
+        );
+    }
+
+    assert!(try_parse_derivation_path(s16!("m").content_slice())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+pub fn invalid_derivation_paths_are_rejected() {
+    for path in [
+        s16!("m//0"),
+        s16!("m/0''"),
+        s16!("m/x"),
+        s16!("m/2147483648"),
+        s16!("m/4294967296'"),
+    ] {
+        assert!(try_parse_derivation_path(path.content_slice()).is_none());
+    }
+}
+
+#[test]
+pub fn derivation_path_templates_are_parsed() {
+    let hardened = |i: u32| Bip32DerivationPathPoint::from(i | HARDENED_CHILD_DERIVATION_THRESHOLD);
+    assert_eq!(
+        try_parse_derivation_path_template(s16!("m/84'/0'/0'/{0,1}/*").content_slice()).unwrap(),
+        [
+            Bip32DerivationPathTemplateSegment::Points(Vec::from([hardened(84)])),
+            Bip32DerivationPathTemplateSegment::Points(Vec::from([hardened(0)])),
+            Bip32DerivationPathTemplateSegment::Points(Vec::from([hardened(0)])),
+            Bip32DerivationPathTemplateSegment::Points(Vec::from([
+                Bip32DerivationPathPoint::from(0),
+                Bip32DerivationPathPoint::from(1),
+            ])),
+            Bip32DerivationPathTemplateSegment::Wildcard(false),
+        ]
+    );
+    assert_eq!(
+        try_parse_derivation_path_template(s16!("*h").content_slice()).unwrap(),
+        [Bip32DerivationPathTemplateSegment::Wildcard(true)]
+    );
+
+    for template in [
+        s16!("m/0/*/1"),
+        s16!("m/0"),
+        s16!("m/*/*"),
+        s16!("m/{}/*"),
+        s16!("m/{0,}/*"),
+        s16!("m/{0,1/*"),
+        s16!("m"),
+    ] {
+        assert!(try_parse_derivation_path_template(template.content_slice()).is_none());
+    }
+}
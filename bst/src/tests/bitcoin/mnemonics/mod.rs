@@ -16,3 +16,4 @@
 
 mod bip_39;
 mod electrum;
+mod slip_39;
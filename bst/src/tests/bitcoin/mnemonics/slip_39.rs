@@ -0,0 +1,229 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    bitcoin::mnemonics::slip_39::{
+        self, get_share_mnemonic, try_generate_shares, try_parse_slip39_mnemonic,
+        try_recover_master_secret, try_validate_share_set, MnemonicParsingResult, Share,
+    },
+    String16,
+};
+use alloc::vec::Vec;
+use hex_literal::hex;
+use rand::{thread_rng, Rng};
+
+const PASSPHRASE: &[u8] = b"TREZOR";
+
+fn parse_share(mnemonic: &str) -> MnemonicParsingResult<'static> {
+    let words: Vec<String16<'static>> = mnemonic
+        .split_ascii_whitespace()
+        .map(|w| {
+            let word: Vec<u16> = w.encode_utf16().collect();
+            String16::from(&*word.leak())
+        })
+        .collect();
+    try_parse_slip39_mnemonic(&words)
+}
+
+fn share(mnemonic: &str) -> Share {
+    match parse_share(mnemonic) {
+        MnemonicParsingResult::Valid(s) => s,
+        _ => panic!("Failed to parse share '{}'.", mnemonic),
+    }
+}
+
+fn mnemonic_string(share: &Share) -> alloc::string::String {
+    get_share_mnemonic(share)
+        .iter()
+        .map(|w| alloc::string::String::from_utf16(w.content_slice()).unwrap())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn random_bytes(length: usize) -> Vec<u8> {
+    let mut rng = thread_rng();
+    (0..length).map(|_| rng.gen()).collect()
+}
+
+#[test]
+fn word_list_is_well_formed() {
+    let words = slip_39::WORD_LIST.words();
+    assert_eq!(words.len(), 1024);
+    assert_eq!(
+        words.iter().map(|w| w.content_length()).max().unwrap(),
+        slip_39::WORD_LIST.longest_word_length()
+    );
+
+    for i in 1..words.len() {
+        // The words are sorted, and uniquely identified by their first four letters.
+        assert!(words[i - 1].content_slice() < words[i].content_slice());
+        assert_ne!(
+            words[i - 1].content_slice()[..4],
+            words[i].content_slice()[..4]
+        );
+    }
+}
+
+#[test]
+fn trezor_vectors_recover_master_secret() {
+    let vectors: [(&[&str], &[u8]); 4] = [
+        (
+            &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"],
+            &hex!("bb54aac4b89dc868ba37d9cc21b2cece"),
+        ),
+        (
+            &[
+                "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+                "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+            ],
+            &hex!("b43ceb7e57a0ea8766221624d01b0864"),
+        ),
+        (
+            &["theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect luck"],
+            &hex!("989baf9dcaad5b10ca33dfd8cc75e42477025dce88ae83e75a230086a0e00e92"),
+        ),
+        (
+            &["testify swimming academic academic column loyalty smear include exotic bedroom exotic wrist lobe cover grief golden smart junior estimate learn"],
+            &hex!("1679b4516e0ee5954351d288a838f45e"),
+        ),
+    ];
+
+    for (mnemonics, expected) in vectors {
+        let shares: Vec<Share> = mnemonics.iter().map(|m| share(m)).collect();
+        assert_eq!(
+            try_recover_master_secret(&shares, PASSPHRASE).unwrap(),
+            expected
+        );
+
+        // Re-encoding the parsed shares must produce the original mnemonics.
+        for (mnemonic, share) in mnemonics.iter().zip(&shares) {
+            assert_eq!(mnemonic_string(share), *mnemonic);
+        }
+    }
+}
+
+#[test]
+fn extendable_flag_is_parsed() {
+    assert!(share("testify swimming academic academic column loyalty smear include exotic bedroom exotic wrist lobe cover grief golden smart junior estimate learn").extendable());
+    assert!(!share("duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard").extendable());
+}
+
+#[test]
+fn invalid_mnemonics_are_rejected() {
+    assert!(matches!(
+        parse_share("duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney"),
+        MnemonicParsingResult::InvalidChecksum
+    ));
+    assert!(matches!(
+        parse_share("duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision"),
+        MnemonicParsingResult::InvalidLength
+    ));
+    assert!(matches!(
+        parse_share("duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision abandon"),
+        MnemonicParsingResult::InvalidWordEncountered(_, 19)
+    ));
+}
+
+#[test]
+fn insufficient_or_mismatched_shares_are_rejected() {
+    let first = share("shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed");
+    let unrelated = share("duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard");
+
+    assert_eq!(try_validate_share_set(&[first.clone()]), Ok(false));
+    assert!(try_recover_master_secret(&[first.clone()], PASSPHRASE).is_err());
+    assert!(try_validate_share_set(&[first.clone(), first.clone()]).is_err());
+    assert!(try_validate_share_set(&[first, unrelated]).is_err());
+}
+
+#[test]
+fn generated_shares_round_trip() {
+    let configurations: [(u8, &[(u8, u8)]); 5] = [
+        (1, &[(1, 1)]),
+        (1, &[(3, 5)]),
+        (2, &[(1, 1), (2, 3), (3, 4)]),
+        (3, &[(2, 2), (1, 1), (4, 16), (2, 3)]),
+        (1, &[(16, 16)]),
+    ];
+
+    for (i, (group_threshold, groups)) in configurations.into_iter().enumerate() {
+        let master_secret = random_bytes(if i % 2 == 0 { 16 } else { 32 });
+        let extendable = i % 2 == 1;
+        let shares = try_generate_shares(
+            &master_secret,
+            PASSPHRASE,
+            extendable,
+            0,
+            group_threshold,
+            groups,
+            &random_bytes(32),
+        )
+        .unwrap();
+
+        // Every share survives a round trip through its mnemonic.
+        let mut parsed: Vec<Vec<Share>> = Vec::new();
+        for group in &shares {
+            parsed.push(group.iter().map(|s| share(&mnemonic_string(s))).collect());
+            for s in group {
+                assert!(share(&mnemonic_string(s)) == *s);
+                assert_eq!(s.extendable(), extendable);
+            }
+        }
+
+        // Take the last member threshold's worth of shares from the last group threshold's worth of groups.
+        let mut subset = Vec::new();
+        for (group, (member_threshold, _)) in parsed
+            .iter()
+            .zip(groups)
+            .rev()
+            .take(group_threshold as usize)
+        {
+            subset.extend(group.iter().rev().take(*member_threshold as usize).cloned());
+        }
+
+        assert_eq!(try_validate_share_set(&subset), Ok(true));
+        assert_eq!(
+            try_recover_master_secret(&subset, PASSPHRASE).unwrap(),
+            master_secret
+        );
+
+        // A different passphrase recovers a different secret, by design.
+        assert_ne!(
+            try_recover_master_secret(&subset, b"").unwrap(),
+            master_secret
+        );
+
+        // Removing any share leaves the set short of its thresholds.
+        if group_threshold > 1 || groups[groups.len() - 1].0 > 1 {
+            assert_eq!(try_validate_share_set(&subset[1..]), Ok(false));
+        }
+    }
+}
+
+#[test]
+fn invalid_generation_parameters_are_rejected() {
+    let seed = random_bytes(32);
+    let secret = random_bytes(16);
+    assert!(try_generate_shares(&secret[..15], PASSPHRASE, true, 0, 1, &[(1, 1)], &seed).is_err());
+    assert!(try_generate_shares(&secret, PASSPHRASE, true, 16, 1, &[(1, 1)], &seed).is_err());
+    assert!(try_generate_shares(&secret, PASSPHRASE, true, 0, 2, &[(1, 1)], &seed).is_err());
+    assert!(try_generate_shares(&secret, PASSPHRASE, true, 0, 1, &[(1, 2)], &seed).is_err());
+    assert!(try_generate_shares(&secret, PASSPHRASE, true, 0, 1, &[(3, 2)], &seed).is_err());
+    assert!(try_generate_shares(&secret, PASSPHRASE, true, 0, 1, &[(2, 17)], &seed).is_err());
+    assert!(
+        try_generate_shares(&secret, "\u{e9}".as_bytes(), true, 0, 1, &[(1, 1)], &seed).is_err()
+    );
+    assert!(try_generate_shares(&secret, PASSPHRASE, true, 0, 1, &[(1, 1)], &seed[..31]).is_err());
+}
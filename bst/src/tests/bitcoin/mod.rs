@@ -14,5 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod addresses;
+mod base_58_check;
+mod bip_322;
 mod derivation_paths;
 mod mnemonics;
+mod psbt;
+mod signature_hashes;
+mod signed_messages;
+mod transactions;
@@ -0,0 +1,802 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    bitcoin::{
+        hd_wallets::{
+            Bip32CkdContext, Bip32DerivationPathPoint, Bip32SerializedExtendedKey,
+            HARDENED_CHILD_DERIVATION_THRESHOLD,
+        },
+        psbt::{self, Psbt},
+        signature_hashes::{self, SIGHASH_ALL, SIGHASH_DEFAULT},
+        taproot,
+        transactions::{Transaction, TransactionInput, TransactionOutput},
+        write_compact_size, Hash160,
+    },
+    cryptography::asymmetric::ecc::secp256k1::{ecdsa, schnorr, try_decode_public_key},
+    encoding::base64,
+    hashing::{Hasher, Sha256},
+};
+use alloc::vec::Vec;
+use hex_literal::hex;
+
+// A valid PSBT from BIP 174's test vectors; a single P2PKH input with its previous transaction, and two outputs.
+const BIP_174_PSBT: &str = "cHNidP8BAHUCAAAAASaBcTce3/KF6Tet7qSze3gADAVmy7OtZGQXE8pCFxv2AAAAAAD+////AtPf9QUAAAAAGXapFNDFmQPFusKGh2DpD9UhpGZap2UgiKwA4fUFAAAAABepFDVF5uM7gyxHBQ8k0+65PJwDlIvHh7MuEwAAAQD9pQEBAAAAAAECiaPHHqtNIOA3G7ukzGmPopXJRjr6Ljl/hTPMti+VZ+UBAAAAFxYAFL4Y0VKpsBIDna89p95PUzSe7LmF/////4b4qkOnHf8USIk6UwpyN+9rRgi7st0tAXHmOuxqSJC0AQAAABcWABT+Pp7xp0XpdNkCxDVZQ6vLNL1TU/////8CAMLrCwAAAAAZdqkUhc/xCX/Z4Ai7NK9wnGIZeziXikiIrHL++E4sAAAAF6kUM5cluiHv1irHU6m80GfWx6ajnQWHAkcwRAIgJxK+IuAnDzlPVoMR3HyppolwuAJf3TskAinwf4pfOiQCIAGLONfc0xTnNMkna9b7QPZzMlvEuqFEyADS8vAtsnZcASED0uFWdJQbrUqZY3LLh+GFbTZSYG2YVi/jnF6efkE/IQUCSDBFAiEA0SuFLYXc2WHS9fSrZgZU327tzHlMDDPOXMMJ/7X85Y0CIGczio4OFyXBl/saiK9Z9R5E5CVbIBZ8hoQDHAXR8lkqASECI7cr7vCWXRC+B3jv7NYfysb3mk6haTkzgHNEZPhPKrMAAAAAAAAA";
+
+// The abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about master private key.
+const MASTER_KEY: [u8; 78] = hex!("0488ADE40000000000000000007923408DADD3C7B56EED15567707AE5E5DCA089DE972E07F3B860450E2A3B70E001837C1BE8E2995EC11CDA2B066151BE2CFB48ADF9E47B151D46ADAB3A21CDF67");
+
+const H: u32 = HARDENED_CHILD_DERIVATION_THRESHOLD;
+
+fn decode(string: &str) -> Vec<u8> {
+    base64::decode(&string.encode_utf16().collect::<Vec<u16>>()).unwrap()
+}
+
+fn derive(path: &[u32]) -> Bip32SerializedExtendedKey {
+    let path: Vec<Bip32DerivationPathPoint> = path
+        .iter()
+        .map(|p| Bip32DerivationPathPoint::from(*p))
+        .collect();
+    Bip32CkdContext::new()
+        .derive(
+            |_| {},
+            Bip32SerializedExtendedKey::from_bytes(&MASTER_KEY).unwrap(),
+            &path,
+        )
+        .unwrap()
+}
+
+fn push_pair(bytes: &mut Vec<u8>, key: &[u8], value: &[u8]) {
+    write_compact_size(key.len() as u64, bytes);
+    bytes.extend(key);
+    write_compact_size(value.len() as u64, bytes);
+    bytes.extend(value);
+}
+
+fn p2wpkh_script(public_key: &[u8]) -> Vec<u8> {
+    let mut script = Vec::from([0x00, 0x14]);
+    script.extend(Hash160::new().hash_160(public_key));
+    script
+}
+
+fn change_psbt(change_path: &[u32], output_script: &[u8]) -> Vec<u8> {
+    // A single P2WPKH input worth 1 BTC, spending to an external P2WPKH output and an output claiming to be change.
+    let master_fingerprint =
+        Hash160::new().fingerprint(&psbt::try_get_public_key(&derive(&[])).unwrap());
+    let input_key = psbt::try_get_public_key(&derive(&[84 | H, H, H, 0, 0])).unwrap();
+    let change_key = psbt::try_get_public_key(&derive(change_path)).unwrap();
+
+    let mut transaction = Vec::from(hex!("02000000"));
+    transaction.push(1);
+    transaction.extend([0x11; 32]);
+    transaction.extend([0u8; 4]);
+    transaction.push(0);
+    transaction.extend([0xFD, 0xFF, 0xFF, 0xFF]);
+    transaction.push(2);
+    transaction.extend(60_000_000u64.to_le_bytes());
+    transaction.push(22);
+    transaction.extend(p2wpkh_script(&[0x02; 33]));
+    transaction.extend(39_990_000u64.to_le_bytes());
+    write_compact_size(output_script.len() as u64, &mut transaction);
+    transaction.extend(output_script);
+    transaction.extend([0u8; 4]);
+
+    let mut bytes = Vec::from(psbt::MAGIC);
+    push_pair(&mut bytes, &[0x00], &transaction);
+    push_pair(&mut bytes, &[0xFC, 0x01, 0xAB], &[0xCD]);
+    bytes.push(0);
+
+    let mut witness_utxo = Vec::from(100_000_000u64.to_le_bytes());
+    witness_utxo.push(22);
+    witness_utxo.extend(p2wpkh_script(&input_key));
+    push_pair(&mut bytes, &[0x01], &witness_utxo);
+
+    let mut derivation = Vec::from(master_fingerprint);
+    for point in [84 | H, H, H, 0, 0] {
+        derivation.extend(point.to_le_bytes());
+    }
+
+    let mut key = Vec::from([0x06]);
+    key.extend(input_key);
+    push_pair(&mut bytes, &key, &derivation);
+    bytes.push(0);
+
+    // The external output has no map entries.
+    bytes.push(0);
+
+    let mut derivation = Vec::from(master_fingerprint);
+    for point in change_path {
+        derivation.extend(point.to_le_bytes());
+    }
+
+    let mut key = Vec::from([0x02]);
+    key.extend(change_key);
+    push_pair(&mut bytes, &key, &derivation);
+    bytes.push(0);
+    bytes
+}
+
+fn derivation_value(path: &[u32]) -> Vec<u8> {
+    let mut value =
+        Vec::from(Hash160::new().fingerprint(&psbt::try_get_public_key(&derive(&[])).unwrap()));
+    for point in path {
+        value.extend(point.to_le_bytes());
+    }
+
+    value
+}
+
+fn signing_psbt() -> (Vec<u8>, Vec<TransactionOutput>) {
+    // Spends P2WPKH, P2SH-P2WPKH, P2PKH and BIP 86 P2TR outputs from the first receive address of each account type.
+    let keys = [
+        psbt::try_get_public_key(&derive(&[84 | H, H, H, 0, 0])).unwrap(),
+        psbt::try_get_public_key(&derive(&[49 | H, H, H, 0, 0])).unwrap(),
+        psbt::try_get_public_key(&derive(&[44 | H, H, H, 0, 0])).unwrap(),
+        psbt::try_get_public_key(&derive(&[86 | H, H, H, 0, 0])).unwrap(),
+    ];
+
+    let mut p2sh_script = Vec::from([0xA9, 0x14]);
+    p2sh_script.extend(Hash160::new().hash_160(&p2wpkh_script(&keys[1])));
+    p2sh_script.push(0x87);
+
+    let mut p2pkh_script = Vec::from([0x76, 0xA9, 0x14]);
+    p2pkh_script.extend(Hash160::new().hash_160(&keys[2]));
+    p2pkh_script.extend([0x88, 0xAC]);
+
+    let mut x_only_key = [0u8; 32];
+    x_only_key.copy_from_slice(&keys[3][1..]);
+    let mut p2tr_script = Vec::from([0x51, 0x20]);
+    p2tr_script.extend(taproot::try_tweak_public_key(&x_only_key).unwrap());
+
+    let spent_outputs = Vec::from([
+        TransactionOutput::from(10_000_000, p2wpkh_script(&keys[0])),
+        TransactionOutput::from(20_000_000, p2sh_script),
+        TransactionOutput::from(30_000_000, p2pkh_script),
+        TransactionOutput::from(40_000_000, p2tr_script),
+    ]);
+
+    // The ECDSA inputs' previous transactions are included; they prove the amounts being spent.
+    let previous_transactions: Vec<Transaction> = spent_outputs[..3]
+        .iter()
+        .enumerate()
+        .map(|(i, o)| {
+            Transaction::from(
+                2,
+                Vec::from([TransactionInput::from(
+                    [0x30 + i as u8; 32],
+                    0,
+                    Vec::new(),
+                    0xFFFFFFFF,
+                    Vec::new(),
+                )]),
+                Vec::from([o.clone()]),
+                0,
+            )
+        })
+        .collect();
+
+    let inputs = (0..4)
+        .map(|i| {
+            let txid = match previous_transactions.get(i) {
+                Some(t) => t.txid(),
+                None => [0x10 + i as u8; 32],
+            };
+
+            TransactionInput::from(txid, 0, Vec::new(), 0xFFFFFFFD, Vec::new())
+        })
+        .collect();
+
+    let transaction = Transaction::from(
+        2,
+        inputs,
+        Vec::from([TransactionOutput::from(
+            99_990_000,
+            p2wpkh_script(&[0x02; 33]),
+        )]),
+        0,
+    );
+
+    let mut bytes = Vec::from(psbt::MAGIC);
+    push_pair(&mut bytes, &[0x00], &transaction.serialize(false));
+    bytes.push(0);
+
+    let purposes = [84, 49, 44, 86];
+    for (i, output) in spent_outputs.iter().enumerate() {
+        let path = [purposes[i] | H, H, H, 0, 0];
+        if let Some(t) = previous_transactions.get(i) {
+            push_pair(&mut bytes, &[0x00], &t.serialize(false));
+        } else {
+            let mut witness_utxo = Vec::from(output.value().to_le_bytes());
+            write_compact_size(output.script_pubkey().len() as u64, &mut witness_utxo);
+            witness_utxo.extend(output.script_pubkey());
+            push_pair(&mut bytes, &[0x01], &witness_utxo);
+        }
+
+        if i == 3 {
+            // Taproot derivations are keyed by the x-only key, and have no leaf hashes for a key path spend.
+            let mut key = Vec::from([0x16]);
+            key.extend(&keys[i][1..]);
+            let mut value = Vec::from([0x00]);
+            value.extend(derivation_value(&path));
+            push_pair(&mut bytes, &key, &value);
+            push_pair(&mut bytes, &[0x17], &keys[i][1..]);
+        } else {
+            let mut key = Vec::from([0x06]);
+            key.extend(keys[i]);
+            push_pair(&mut bytes, &key, &derivation_value(&path));
+        }
+
+        bytes.push(0);
+    }
+
+    bytes.push(0);
+    (bytes, spent_outputs)
+}
+
+#[test]
+fn bip_174_psbts_deserialize() {
+    let bytes = decode(BIP_174_PSBT);
+    let psbt = Psbt::try_deserialize(&bytes).unwrap();
+    assert_eq!(psbt.unsigned_transaction().inputs().len(), 1);
+    assert_eq!(psbt.unsigned_transaction().outputs().len(), 2);
+    assert_eq!(psbt.spent_output(0).unwrap().value(), 200_000_000);
+    assert_eq!(psbt.total_input_value(), Some(200_000_000));
+    assert_eq!(psbt.fee(), Some(301));
+    assert_eq!(psbt.unknown_pair_count(), 0);
+    assert_eq!(psbt.serialize(), bytes);
+}
+
+#[test]
+fn invalid_psbts_do_not_deserialize() {
+    let bytes = decode(BIP_174_PSBT);
+
+    // Missing magic, truncated, and with trailing bytes.
+    assert!(Psbt::try_deserialize(&bytes[1..]).is_err());
+    assert!(Psbt::try_deserialize(&bytes[..bytes.len() - 1]).is_err());
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(Psbt::try_deserialize(&trailing).is_err());
+
+    // A previous transaction which isn't the one the input spends.
+    let mut tampered = bytes.clone();
+    let spent_output_value = tampered
+        .windows(8)
+        .position(|w| w == 200_000_000u64.to_le_bytes())
+        .unwrap();
+    tampered[spent_output_value] ^= 1;
+    assert!(Psbt::try_deserialize(&tampered).is_err());
+
+    // A non-zero version.
+    let mut versioned = Vec::from(psbt::MAGIC);
+    push_pair(&mut versioned, &[0xFB], &2u32.to_le_bytes());
+    versioned.extend(&bytes[psbt::MAGIC.len()..]);
+    assert!(Psbt::try_deserialize(&versioned).is_err());
+}
+
+#[test]
+fn unknown_pairs_pass_through() {
+    let change_path = [84 | H, H, H, 1, 0];
+    let change_key = psbt::try_get_public_key(&derive(&change_path)).unwrap();
+    let bytes = change_psbt(&change_path, &p2wpkh_script(&change_key));
+    let psbt = Psbt::try_deserialize(&bytes).unwrap();
+    assert_eq!(psbt.unknown_pair_count(), 1);
+    assert_eq!(psbt.serialize(), bytes);
+    assert_eq!(psbt.fee(), Some(10_000));
+}
+
+#[test]
+fn change_outputs_are_identified() {
+    let change_path = [84 | H, H, H, 1, 0];
+    let change_key = psbt::try_get_public_key(&derive(&change_path)).unwrap();
+    let psbt =
+        Psbt::try_deserialize(&change_psbt(&change_path, &p2wpkh_script(&change_key))).unwrap();
+
+    // Both the master key, and the account's extended public key, derive the change key.
+    let master_key = derive(&[]);
+    let master_fingerprint =
+        Hash160::new().fingerprint(&psbt::try_get_public_key(&master_key).unwrap());
+    let account_key = derive(&[84 | H, H, H]);
+    let account_key = account_key
+        .build_public_key_variant_from(psbt::try_get_public_key(&account_key).unwrap())
+        .unwrap();
+    let account_fingerprint =
+        Hash160::new().fingerprint(&psbt::try_get_public_key(&account_key).unwrap());
+
+    assert!(psbt
+        .try_identify_change_output(0, &master_key, &master_fingerprint)
+        .is_none());
+    assert_eq!(
+        psbt.try_identify_change_output(1, &master_key, &master_fingerprint)
+            .unwrap()
+            .path()
+            .len(),
+        5
+    );
+    assert!(psbt
+        .try_identify_change_output(1, &account_key, &account_fingerprint)
+        .is_some());
+    assert!(psbt.input_bip32_derivations(0)[0]
+        .try_derive_public_key_from(&account_key, &account_fingerprint)
+        .is_some());
+
+    // A key from another account doesn't derive the change key.
+    let other_account_key = derive(&[84 | H, H, 1 | H]);
+    let other_fingerprint =
+        Hash160::new().fingerprint(&psbt::try_get_public_key(&other_account_key).unwrap());
+    assert!(psbt
+        .try_identify_change_output(1, &other_account_key, &other_fingerprint)
+        .is_none());
+}
+
+#[test]
+fn change_outputs_must_pay_to_their_derivations() {
+    // The output claims to be change, but pays to a different key.
+    let change_path = [84 | H, H, H, 1, 0];
+    let psbt =
+        Psbt::try_deserialize(&change_psbt(&change_path, &p2wpkh_script(&[0x03; 33]))).unwrap();
+
+    let master_key = derive(&[]);
+    let master_fingerprint =
+        Hash160::new().fingerprint(&psbt::try_get_public_key(&master_key).unwrap());
+    assert!(psbt
+        .try_identify_change_output(1, &master_key, &master_fingerprint)
+        .is_none());
+}
+
+#[test]
+fn psbts_are_signed() {
+    let (bytes, spent_outputs) = signing_psbt();
+    let mut psbt = Psbt::try_deserialize(&bytes).unwrap();
+    let master_key = derive(&[]);
+    let master_fingerprint =
+        Hash160::new().fingerprint(&psbt::try_get_public_key(&master_key).unwrap());
+    assert_eq!(
+        psbt.try_sign(&master_key, &master_fingerprint, false),
+        Ok(4)
+    );
+
+    // The ECDSA inputs; P2WPKH and P2SH-P2WPKH use BIP 143, and P2PKH the legacy signature hash.
+    let transaction = psbt.unsigned_transaction().clone();
+    for i in 0..3 {
+        let signatures: Vec<_> = psbt.input_partial_signatures(i).collect();
+        assert_eq!(signatures.len(), 1);
+        let public_key = signatures[0].key_data();
+        let signature = signatures[0].value();
+        assert_eq!(signature[signature.len() - 1], SIGHASH_ALL);
+
+        let mut script_code = Vec::from([0x76, 0xA9, 0x14]);
+        script_code.extend(Hash160::new().hash_160(public_key));
+        script_code.extend([0x88, 0xAC]);
+        let sighash = if i == 2 {
+            signature_hashes::legacy(&transaction, i, &script_code, SIGHASH_ALL as u32)
+        } else {
+            signature_hashes::segwit_v0(
+                &transaction,
+                i,
+                &script_code,
+                spent_outputs[i].value(),
+                SIGHASH_ALL as u32,
+            )
+        };
+
+        assert!(ecdsa::verify(
+            &try_decode_public_key(public_key).unwrap(),
+            &sighash,
+            &ecdsa::try_from_der(&signature[..signature.len() - 1]).unwrap()
+        ));
+    }
+
+    // The taproot input's signature is for the tweaked output key, with an implicit SIGHASH_DEFAULT.
+    assert_eq!(psbt.input_partial_signatures(3).count(), 0);
+    let signature = psbt.input_taproot_key_signature(3).unwrap();
+    let sighash =
+        signature_hashes::taproot_key_path(&transaction, 3, &spent_outputs, SIGHASH_DEFAULT)
+            .unwrap();
+    let mut output_key = [0u8; 32];
+    output_key.copy_from_slice(&spent_outputs[3].script_pubkey()[2..]);
+    let mut schnorr_signature = [0u8; 64];
+    schnorr_signature.copy_from_slice(signature);
+    assert!(schnorr::verify(&output_key, &sighash, &schnorr_signature));
+
+    // Signed PSBTs round trip, and signing them again adds nothing.
+    let signed = psbt.serialize();
+    let mut psbt = Psbt::try_deserialize(&signed).unwrap();
+    assert_eq!(
+        psbt.try_sign(&master_key, &master_fingerprint, false),
+        Ok(0)
+    );
+    assert_eq!(psbt.serialize(), signed);
+}
+
+#[test]
+fn segwit_inputs_without_previous_transactions_are_not_signed() {
+    // Witness UTXO amounts can't be verified (CVE-2020-14199); they're only signed when explicitly allowed.
+    let (bytes, _) = signing_psbt();
+    let psbt = Psbt::try_deserialize(&bytes).unwrap();
+    assert!(!psbt.has_unverified_segwit_v0_amounts());
+
+    let mut psbt = Psbt::try_deserialize(&v2_psbt(&[(None, None)], None)).unwrap();
+    assert!(psbt.has_unverified_segwit_v0_amounts());
+    let master_key = derive(&[]);
+    let master_fingerprint =
+        Hash160::new().fingerprint(&psbt::try_get_public_key(&master_key).unwrap());
+    assert!(psbt
+        .try_sign(&master_key, &master_fingerprint, false)
+        .is_err());
+    assert_eq!(psbt.input_partial_signatures(0).count(), 0);
+    assert_eq!(psbt.try_sign(&master_key, &master_fingerprint, true), Ok(1));
+}
+
+#[test]
+fn psbts_are_signed_by_account_keys() {
+    // The account key only derives the P2WPKH input's key; extended public keys can't sign.
+    let (bytes, _) = signing_psbt();
+    let mut psbt = Psbt::try_deserialize(&bytes).unwrap();
+    let account_key = derive(&[84 | H, H, H]);
+    let public_key = psbt::try_get_public_key(&account_key).unwrap();
+    let account_fingerprint = Hash160::new().fingerprint(&public_key);
+    let account_public_key = account_key
+        .build_public_key_variant_from(public_key)
+        .unwrap();
+
+    assert!(psbt
+        .try_sign(&account_public_key, &account_fingerprint, false)
+        .is_err());
+    assert_eq!(
+        psbt.try_sign(&account_key, &account_fingerprint, false),
+        Ok(1)
+    );
+    assert_eq!(psbt.input_partial_signatures(0).count(), 1);
+    assert_eq!(psbt.input_partial_signatures(1).count(), 0);
+    assert!(psbt.input_taproot_key_signature(3).is_none());
+}
+
+#[test]
+fn extended_keys_with_invalid_key_material_have_no_public_key() {
+    // Private key material must be a zero byte followed by 0 < k < n; public key material must be a point on the curve.
+    let n = hex!("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141");
+    for (prefix, private_key) in [(0x00, [0u8; 32]), (0x00, n), (0x01, [0x01; 32])] {
+        let mut bytes = MASTER_KEY;
+        bytes[45] = prefix;
+        bytes[46..].copy_from_slice(&private_key);
+        let key = Bip32SerializedExtendedKey::from_bytes(&bytes).unwrap();
+        assert!(psbt::try_get_public_key(&key).is_none());
+    }
+
+    let mut off_curve_key = [0u8; 33];
+    off_curve_key[0] = 0x02;
+    off_curve_key[32] = 5;
+    for key_material in [off_curve_key, [0x04; 33]] {
+        let key = derive(&[])
+            .build_public_key_variant_from(key_material)
+            .unwrap();
+        assert!(psbt::try_get_public_key(&key).is_none());
+    }
+
+    let master_key = derive(&[]);
+    let public_key = psbt::try_get_public_key(&master_key).unwrap();
+    let master_public_key = master_key
+        .build_public_key_variant_from(public_key)
+        .unwrap();
+    assert_eq!(
+        psbt::try_get_public_key(&master_public_key),
+        Some(public_key)
+    );
+}
+
+fn v2_psbt(locktime_requirements: &[(Option<u32>, Option<u32>)], fallback: Option<u32>) -> Vec<u8> {
+    // A version 2 PSBT with modifiable inputs and outputs; the first input spends a P2WPKH output worth 1 BTC.
+    let input_key = psbt::try_get_public_key(&derive(&[84 | H, H, H, 0, 0])).unwrap();
+    let mut bytes = Vec::from(psbt::MAGIC);
+    push_pair(&mut bytes, &[0x02], &2u32.to_le_bytes());
+    if let Some(l) = fallback {
+        push_pair(&mut bytes, &[0x03], &l.to_le_bytes());
+    }
+
+    push_pair(&mut bytes, &[0x04], &[locktime_requirements.len() as u8]);
+    push_pair(&mut bytes, &[0x05], &[1]);
+    push_pair(&mut bytes, &[0x06], &[0x03]);
+    push_pair(&mut bytes, &[0xFB], &2u32.to_le_bytes());
+    bytes.push(0);
+
+    for (i, (height, time)) in locktime_requirements.iter().enumerate() {
+        push_pair(&mut bytes, &[0x0E], &[0x40 + i as u8; 32]);
+        push_pair(&mut bytes, &[0x0F], &0u32.to_le_bytes());
+        if let Some(h) = height {
+            push_pair(&mut bytes, &[0x12], &h.to_le_bytes());
+        }
+
+        if let Some(t) = time {
+            push_pair(&mut bytes, &[0x11], &t.to_le_bytes());
+        }
+
+        if i == 0 {
+            let mut witness_utxo = Vec::from(100_000_000u64.to_le_bytes());
+            witness_utxo.push(22);
+            witness_utxo.extend(p2wpkh_script(&input_key));
+            push_pair(&mut bytes, &[0x01], &witness_utxo);
+
+            let mut key = Vec::from([0x06]);
+            key.extend(input_key);
+            push_pair(&mut bytes, &key, &derivation_value(&[84 | H, H, H, 0, 0]));
+        }
+
+        bytes.push(0);
+    }
+
+    push_pair(&mut bytes, &[0x03], &99_990_000u64.to_le_bytes());
+    push_pair(&mut bytes, &[0x04], &p2wpkh_script(&[0x02; 33]));
+    bytes.push(0);
+    bytes
+}
+
+fn multisig_keys() -> [[u8; 33]; 3] {
+    [1, 2, 3].map(|i| psbt::try_get_public_key(&derive(&[i])).unwrap())
+}
+
+fn multisig_witness_script() -> Vec<u8> {
+    // OP_2 <key 1> <key 2> <key 3> OP_3 OP_CHECKMULTISIG
+    let mut script = Vec::from([0x52]);
+    for key in multisig_keys() {
+        script.push(33);
+        script.extend(key);
+    }
+
+    script.extend([0x53, 0xAE]);
+    script
+}
+
+fn multisig_psbt(signatures: &[(usize, Vec<u8>)]) -> Vec<u8> {
+    // A single 2 of 3 P2WSH multisig input worth 1 BTC, with the given partial signatures.
+    let witness_script = multisig_witness_script();
+    let mut script_pubkey = Vec::from([0x00, 0x20]);
+    script_pubkey.extend(Sha256::new().get_hash_of(&witness_script));
+
+    let transaction = Transaction::from(
+        2,
+        Vec::from([TransactionInput::from(
+            [0x55; 32],
+            1,
+            Vec::new(),
+            0xFFFFFFFD,
+            Vec::new(),
+        )]),
+        Vec::from([TransactionOutput::from(
+            99_990_000,
+            p2wpkh_script(&[0x02; 33]),
+        )]),
+        0,
+    );
+
+    let mut bytes = Vec::from(psbt::MAGIC);
+    push_pair(&mut bytes, &[0x00], &transaction.serialize(false));
+    bytes.push(0);
+
+    let mut witness_utxo = Vec::from(100_000_000u64.to_le_bytes());
+    witness_utxo.push(34);
+    witness_utxo.extend(script_pubkey);
+    push_pair(&mut bytes, &[0x01], &witness_utxo);
+    push_pair(&mut bytes, &[0x05], &witness_script);
+    for (i, signature) in signatures {
+        let mut key = Vec::from([0x02]);
+        key.extend(multisig_keys()[*i]);
+        push_pair(&mut bytes, &key, signature);
+    }
+
+    bytes.push(0);
+    bytes.push(0);
+    bytes
+}
+
+fn multisig_signature(key_index: usize) -> Vec<u8> {
+    let psbt = Psbt::try_deserialize(&multisig_psbt(&[])).unwrap();
+    let sighash = signature_hashes::segwit_v0(
+        psbt.unsigned_transaction(),
+        0,
+        &multisig_witness_script(),
+        100_000_000,
+        SIGHASH_ALL as u32,
+    );
+
+    let mut private_key = [0u8; 32];
+    private_key.copy_from_slice(&derive(&[key_index as u32 + 1]).key_material()[1..]);
+    let mut signature = ecdsa::to_der(
+        &ecdsa::try_sign_recoverable(&private_key, &sighash)
+            .unwrap()
+            .0,
+    );
+    signature.push(SIGHASH_ALL);
+    signature
+}
+
+#[test]
+fn version_2_psbts_deserialize() {
+    let bytes = v2_psbt(&[(None, None), (None, None)], Some(800_000));
+    let psbt = Psbt::try_deserialize(&bytes).unwrap();
+    assert_eq!(psbt.version(), 2);
+    assert_eq!(psbt.serialize(), bytes);
+
+    let transaction = psbt.unsigned_transaction();
+    assert_eq!(transaction.version(), 2);
+    assert_eq!(transaction.inputs().len(), 2);
+    assert_eq!(transaction.inputs()[1].previous_txid(), &[0x41; 32]);
+    assert_eq!(transaction.inputs()[1].sequence(), 0xFFFFFFFF);
+    assert_eq!(transaction.outputs()[0].value(), 99_990_000);
+    assert_eq!(transaction.lock_time(), 800_000);
+}
+
+#[test]
+fn version_2_locktimes_are_determined() {
+    let lock_time = |requirements: &[(Option<u32>, Option<u32>)]| {
+        Psbt::try_deserialize(&v2_psbt(requirements, Some(1)))
+            .map(|p| p.unsigned_transaction().lock_time())
+    };
+
+    // Heights are preferred where every input with a requirement allows one.
+    assert_eq!(lock_time(&[(None, None), (None, None)]), Ok(1));
+    assert_eq!(
+        lock_time(&[(Some(800_000), None), (Some(800_010), None)]),
+        Ok(800_010)
+    );
+    assert_eq!(
+        lock_time(&[(Some(800_000), Some(1_700_000_000)), (None, None)]),
+        Ok(800_000)
+    );
+    assert_eq!(
+        lock_time(&[
+            (Some(800_000), Some(1_700_000_000)),
+            (None, Some(1_700_000_100))
+        ]),
+        Ok(1_700_000_100)
+    );
+    assert!(lock_time(&[(Some(800_000), None), (None, Some(1_700_000_000))]).is_err());
+    assert!(lock_time(&[(Some(1_700_000_000), None)]).is_err());
+}
+
+#[test]
+fn invalid_version_2_psbts_do_not_deserialize() {
+    let bytes = v2_psbt(&[(None, None)], None);
+    assert!(Psbt::try_deserialize(&bytes).is_ok());
+
+    // A version 0 PSBT with version 2 fields, and a version 2 PSBT with an unsigned transaction.
+    let mut v0_bytes = decode(BIP_174_PSBT);
+    v0_bytes.truncate(psbt::MAGIC.len());
+    push_pair(&mut v0_bytes, &[0x02], &2u32.to_le_bytes());
+    v0_bytes.extend(&decode(BIP_174_PSBT)[psbt::MAGIC.len()..]);
+    assert!(Psbt::try_deserialize(&v0_bytes).is_err());
+
+    let mut v2_bytes = Vec::from(psbt::MAGIC);
+    push_pair(&mut v2_bytes, &[0x00], &[0x00]);
+    v2_bytes.extend(&bytes[psbt::MAGIC.len()..]);
+    assert!(Psbt::try_deserialize(&v2_bytes).is_err());
+
+    // Transaction versions are signed, so a version with its top bit set is below the minimum of 2.
+    let mut negative_version = bytes.clone();
+    let version = negative_version
+        .windows(7)
+        .position(|w| w == [0x01, 0x02, 0x04, 0x02, 0x00, 0x00, 0x00])
+        .unwrap();
+    negative_version[version + 3..version + 7].copy_from_slice(&(-2i32).to_le_bytes());
+    assert!(Psbt::try_deserialize(&negative_version).is_err());
+
+    // An input count which doesn't match the maps, and a missing output script.
+    let mut miscounted = bytes.clone();
+    let count = miscounted
+        .windows(3)
+        .position(|w| w == [0x01, 0x04, 0x01])
+        .unwrap();
+    miscounted[count + 3] = 2;
+    assert!(Psbt::try_deserialize(&miscounted).is_err());
+
+    let script_position = bytes.windows(2).rposition(|w| w == [0x01, 0x04]).unwrap();
+    let mut scriptless = Vec::from(&bytes[..script_position]);
+    scriptless.push(0);
+    assert!(Psbt::try_deserialize(&scriptless).is_err());
+}
+
+#[test]
+fn version_2_psbts_are_signed_and_finalized() {
+    // The P2WPKH input only has its witness UTXO, so its amount must be explicitly allowed to be unverified.
+    let mut psbt = Psbt::try_deserialize(&v2_psbt(&[(Some(800_000), None)], None)).unwrap();
+    let master_key = derive(&[]);
+    let master_fingerprint =
+        Hash160::new().fingerprint(&psbt::try_get_public_key(&master_key).unwrap());
+    assert_eq!(psbt.try_sign(&master_key, &master_fingerprint, true), Ok(1));
+
+    // SIGHASH_ALL signatures commit to every input and output, so neither can be modified.
+    let signed = psbt.serialize();
+    assert!(signed.windows(4).any(|w| w == [0x01, 0x06, 0x01, 0x00]));
+
+    let mut psbt = Psbt::try_deserialize(&signed).unwrap();
+    assert_eq!(psbt.try_finalize(), Ok(1));
+    let transaction = psbt.try_extract_transaction().unwrap();
+    assert_eq!(transaction.lock_time(), 800_000);
+    assert_eq!(transaction.inputs()[0].witness().len(), 2);
+    assert_eq!(Psbt::try_deserialize(&psbt.serialize()), Ok(psbt));
+}
+
+#[test]
+fn signed_psbts_are_finalized_and_extracted() {
+    let (bytes, _) = signing_psbt();
+    let mut psbt = Psbt::try_deserialize(&bytes).unwrap();
+    assert_eq!(psbt.try_finalize(), Ok(0));
+    assert!(psbt.try_extract_transaction().is_err());
+
+    let master_key = derive(&[]);
+    let master_fingerprint =
+        Hash160::new().fingerprint(&psbt::try_get_public_key(&master_key).unwrap());
+    psbt.try_sign(&master_key, &master_fingerprint, false)
+        .unwrap();
+    assert_eq!(psbt.try_finalize(), Ok(4));
+    assert_eq!(psbt.input_partial_signatures(0).count(), 0);
+    assert!(psbt.input_bip32_derivations(0).is_empty());
+
+    // P2WPKH: <signature> <key>; P2SH-P2WPKH additionally pushes the redeem script; P2PKH's script sig pushes the
+    // signature and key; P2TR key path spends are just the signature.
+    let transaction = psbt.try_extract_transaction().unwrap();
+    let inputs = transaction.inputs();
+    assert!(inputs[0].script_sig().is_empty());
+    assert_eq!(inputs[0].witness().len(), 2);
+    assert_eq!(inputs[1].script_sig()[..3], [0x16, 0x00, 0x14]);
+    assert_eq!(inputs[1].witness().len(), 2);
+    assert!(inputs[2].witness().is_empty());
+    let script_sig = inputs[2].script_sig();
+    assert_eq!(script_sig[script_sig.len() - 34], 0x21);
+    assert_eq!(inputs[3].witness().len(), 1);
+    assert_eq!(inputs[3].witness()[0].len(), 64);
+
+    let serialized = transaction.serialize(true);
+    assert_eq!(Transaction::try_deserialize(&serialized), Ok(transaction));
+}
+
+#[test]
+fn multisig_psbts_are_combined_and_finalized() {
+    let first = Psbt::try_deserialize(&multisig_psbt(&[(0, multisig_signature(0))])).unwrap();
+    let third = Psbt::try_deserialize(&multisig_psbt(&[(2, multisig_signature(2))])).unwrap();
+
+    // One signature isn't enough.
+    let mut unfinalized = first.clone();
+    assert_eq!(unfinalized.try_finalize(), Ok(0));
+
+    let mut combined = third.try_combine(&first).unwrap();
+    assert_eq!(combined.input_partial_signatures(0).count(), 2);
+    assert_eq!(combined.try_finalize(), Ok(1));
+
+    // The signatures are in the order of their keys, following OP_CHECKMULTISIG's extra stack item.
+    let transaction = combined.try_extract_transaction().unwrap();
+    assert_eq!(
+        transaction.inputs()[0].witness(),
+        [
+            Vec::new(),
+            multisig_signature(0),
+            multisig_signature(2),
+            multisig_witness_script()
+        ]
+    );
+
+    // PSBTs for other transactions can't be combined, and invalid signatures are rejected.
+    let (other, _) = signing_psbt();
+    assert!(first
+        .try_combine(&Psbt::try_deserialize(&other).unwrap())
+        .is_err());
+
+    let mut invalid_signature = multisig_signature(1);
+    invalid_signature[10] ^= 1;
+    let mut invalid = first
+        .try_combine(&Psbt::try_deserialize(&multisig_psbt(&[(1, invalid_signature)])).unwrap())
+        .unwrap();
+    assert!(invalid.try_finalize().is_err());
+}
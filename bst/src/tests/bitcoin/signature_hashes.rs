@@ -0,0 +1,421 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    bitcoin::{
+        bip_322::to_spend_txid,
+        signature_hashes::{
+            legacy, segwit_v0, taproot_key_path, taproot_key_path_message, SegwitV0Hashes,
+            SighashType, TaprootHashes, SIGHASH_ALL, SIGHASH_ANYONECANPAY, SIGHASH_DEFAULT,
+            SIGHASH_NONE, SIGHASH_SINGLE,
+        },
+        taproot,
+        transactions::{Transaction, TransactionInput, TransactionOutput},
+    },
+    cryptography::asymmetric::ecc::secp256k1::{ecdsa, schnorr, try_decode_public_key},
+};
+use alloc::vec::Vec;
+use hex_literal::hex;
+
+// BIP 143's native P2WPKH example; the first input spends a P2PK output, and the second a P2WPKH output.
+const NATIVE_P2WPKH_TRANSACTION: [u8; 160] = hex!("0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000");
+
+// BIP 341's key path spending test vector; the unsigned transaction spends nine outputs, seven of them taproot.
+const BIP_341_TRANSACTION: [u8; 454] = hex!("02000000097de20cbff686da83a54981d2b9bab3586f4ca7e48f57f5b55963115f3b334e9c010000000000000000d7b7cab57b1393ace2d064f4d4a2cb8af6def61273e127517d44759b6dafdd990000000000fffffffff8e1f583384333689228c5d28eac13366be082dc57441760d957275419a418420000000000fffffffff0689180aa63b30cb162a73c6d2a38b7eeda2a83ece74310fda0843ad604853b0100000000feffffffaa5202bdf6d8ccd2ee0f0202afbbb7461d9264a25e5bfd3c5a52ee1239e0ba6c0000000000feffffff956149bdc66faa968eb2be2d2faa29718acbfe3941215893a2a3446d32acd050000000000000000000e664b9773b88c09c32cb70a2a3e4da0ced63b7ba3b22f848531bbb1d5d5f4c94010000000000000000e9aa6b8e6c9de67619e6a3924ae25696bb7b694bb677a632a74ef7eadfd4eabf0000000000ffffffffa778eb6a263dc090464cd125c466b5a99667720b1c110468831d058aa1b82af10100000000ffffffff0200ca9a3b000000001976a91406afd46bcdfd22ef94ac122aa11f241244a37ecc88ac807840cb0000000020ac9a87f5594be208f8532db38cff670c450ed2fea8fcdefcc9a663f78bab962b0065cd1d");
+
+// Each spent output's amount and script pubkey.
+const BIP_341_SPENT_OUTPUTS: [(u64, &[u8]); 9] = [
+    (
+        420_000_000,
+        &hex!("512053a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343"),
+    ),
+    (
+        462_000_000,
+        &hex!("5120147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3"),
+    ),
+    (
+        294_000_000,
+        &hex!("76a914751e76e8199196d454941c45d1b3a323f1433bd688ac"),
+    ),
+    (
+        504_000_000,
+        &hex!("5120e4d810fd50586274face62b8a807eb9719cef49c04177cc6b76a9a4251d5450e"),
+    ),
+    (
+        630_000_000,
+        &hex!("512091b64d5324723a985170e4dc5a0f84c041804f2cd12660fa5dec09fc21783605"),
+    ),
+    (
+        378_000_000,
+        &hex!("00147dd65592d0ab2fe0d0257d571abf032cd9db93dc"),
+    ),
+    (
+        672_000_000,
+        &hex!("512075169f4001aa68f15bbed28b218df1d0a62cbbcf1188c6665110c293c907b831"),
+    ),
+    (
+        546_000_000,
+        &hex!("5120712447206d7a5238acc7ff53fbe94a3b64539ad291c7cdbc490b7577e4b17df5"),
+    ),
+    (
+        588_000_000,
+        &hex!("512077e30a5522dd9f894c3f8b8bd4c4b2cf82ca7da8a3ea6a239655c39c050ab220"),
+    ),
+];
+
+// The taproot inputs' hash types, signature messages (SigMsg) and signature hashes.
+const BIP_341_KEY_PATH_SPENDS: [(usize, u8, &[u8], [u8; 32]); 7] = [
+    (
+        0,
+        0x03,
+        &hex!("03020000000065cd1de3b33bb4ef3a52ad1fffb555c0d82828eb22737036eaeb02a235d82b909c4c3f58a6964a4f5f8f0b642ded0a8a553be7622a719da71d1f5befcefcdee8e0fde623ad0f61ad2bca5ba6a7693f50fce988e17c3780bf2b1e720cfbb38fbdd52e2118959c7221ab5ce9e26c3cd67b22c24f8baa54bac281d8e6b05e400e6c3a957e0000000000d0418f0e9a36245b9a50ec87f8bf5be5bcae434337b87139c3a5b1f56e33cba0"),
+        hex!("2514a6272f85cfa0f45eb907fcb0d121b808ed37c6ea160a5a9046ed5526d555"),
+    ),
+    (
+        1,
+        0x83,
+        &hex!("83020000000065cd1d00d7b7cab57b1393ace2d064f4d4a2cb8af6def61273e127517d44759b6dafdd9900000000808f891b00000000225120147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3ffffffffffcef8fb4ca7efc5433f591ecfc57391811ce1e186a3793024def5c884cba51d"),
+        hex!("325a644af47e8a5a2591cda0ab0723978537318f10e6a63d4eed783b96a71a4d"),
+    ),
+    (
+        3,
+        0x01,
+        &hex!("01020000000065cd1de3b33bb4ef3a52ad1fffb555c0d82828eb22737036eaeb02a235d82b909c4c3f58a6964a4f5f8f0b642ded0a8a553be7622a719da71d1f5befcefcdee8e0fde623ad0f61ad2bca5ba6a7693f50fce988e17c3780bf2b1e720cfbb38fbdd52e2118959c7221ab5ce9e26c3cd67b22c24f8baa54bac281d8e6b05e400e6c3a957ea2e6dab7c1f0dcd297c8d61647fd17d821541ea69c3cc37dcbad7f90d4eb4bc50003000000"),
+        hex!("bf013ea93474aa67815b1b6cc441d23b64fa310911d991e713cd34c7f5d46669"),
+    ),
+    (
+        4,
+        0x00,
+        &hex!("00020000000065cd1de3b33bb4ef3a52ad1fffb555c0d82828eb22737036eaeb02a235d82b909c4c3f58a6964a4f5f8f0b642ded0a8a553be7622a719da71d1f5befcefcdee8e0fde623ad0f61ad2bca5ba6a7693f50fce988e17c3780bf2b1e720cfbb38fbdd52e2118959c7221ab5ce9e26c3cd67b22c24f8baa54bac281d8e6b05e400e6c3a957ea2e6dab7c1f0dcd297c8d61647fd17d821541ea69c3cc37dcbad7f90d4eb4bc50004000000"),
+        hex!("4f900a0bae3f1446fd48490c2958b5a023228f01661cda3496a11da502a7f7ef"),
+    ),
+    (
+        6,
+        0x02,
+        &hex!("02020000000065cd1de3b33bb4ef3a52ad1fffb555c0d82828eb22737036eaeb02a235d82b909c4c3f58a6964a4f5f8f0b642ded0a8a553be7622a719da71d1f5befcefcdee8e0fde623ad0f61ad2bca5ba6a7693f50fce988e17c3780bf2b1e720cfbb38fbdd52e2118959c7221ab5ce9e26c3cd67b22c24f8baa54bac281d8e6b05e400e6c3a957e0006000000"),
+        hex!("15f25c298eb5cdc7eb1d638dd2d45c97c4c59dcaec6679cfc16ad84f30876b85"),
+    ),
+    (
+        7,
+        0x82,
+        &hex!("82020000000065cd1d00e9aa6b8e6c9de67619e6a3924ae25696bb7b694bb677a632a74ef7eadfd4eabf00000000804c8b2000000000225120712447206d7a5238acc7ff53fbe94a3b64539ad291c7cdbc490b7577e4b17df5ffffffff"),
+        hex!("cd292de50313804dabe4685e83f923d2969577191a3e1d2882220dca88cbeb10"),
+    ),
+    (
+        8,
+        0x81,
+        &hex!("81020000000065cd1da2e6dab7c1f0dcd297c8d61647fd17d821541ea69c3cc37dcbad7f90d4eb4bc500a778eb6a263dc090464cd125c466b5a99667720b1c110468831d058aa1b82af101000000002b0c230000000022512077e30a5522dd9f894c3f8b8bd4c4b2cf82ca7da8a3ea6a239655c39c050ab220ffffffff"),
+        hex!("cccb739eca6c13a8a89e6e5cd317ffe55669bbda23f2fd37b0f18755e008edd2"),
+    ),
+];
+
+#[test]
+fn bip_143_native_p2wpkh_signature_hash() {
+    let transaction = Transaction::try_deserialize(&NATIVE_P2WPKH_TRANSACTION).unwrap();
+    assert_eq!(
+        segwit_v0(
+            &transaction,
+            1,
+            &hex!("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac"),
+            600_000_000,
+            SIGHASH_ALL as u32,
+        ),
+        hex!("c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670")
+    );
+}
+
+#[test]
+fn bip_143_intermediate_hashes() {
+    let transaction = Transaction::try_deserialize(&NATIVE_P2WPKH_TRANSACTION).unwrap();
+    let hashes = SegwitV0Hashes::from(&transaction, 1, SIGHASH_ALL as u32);
+    assert_eq!(
+        hashes.hash_prevouts(),
+        &hex!("96b827c8483d4e9b96712b6713a7b68d6e8003a781feba36c31143470b4efd37")
+    );
+    assert_eq!(
+        hashes.hash_sequence(),
+        &hex!("52b0a642eea2fb7ae638c36f6252b6750293dbe574a806984b8e4d8548339a3b")
+    );
+    assert_eq!(
+        hashes.hash_outputs(),
+        &hex!("863ef3e1a92afbfdb97f31ad0fc7683ee943e9abcf2501590ff8f6551f47e5e5")
+    );
+
+    // Excluded data is zeroed; SIGHASH_SINGLE only hashes the output at the input's index.
+    let hashes = SegwitV0Hashes::from(
+        &transaction,
+        0,
+        SighashType::SingleAnyoneCanPay.value() as u32,
+    );
+    assert_eq!(hashes.hash_prevouts(), &[0u8; 32]);
+    assert_eq!(hashes.hash_sequence(), &[0u8; 32]);
+    assert_eq!(
+        hashes.hash_outputs(),
+        &hex!("0701d9ec53c6c2f46dec454a0dab027e7b2cf8b6be6979f205672036280ce0b0")
+    );
+
+    let hashes = SegwitV0Hashes::from(&transaction, 0, SIGHASH_NONE as u32);
+    assert_ne!(hashes.hash_prevouts(), &[0u8; 32]);
+    assert_eq!(hashes.hash_sequence(), &[0u8; 32]);
+    assert_eq!(hashes.hash_outputs(), &[0u8; 32]);
+}
+
+#[test]
+fn bip_143_p2sh_p2wpkh_signature_hash() {
+    let transaction = Transaction::try_deserialize(&hex!("0100000001db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a54770100000000feffffff02b8b4eb0b000000001976a914a457b684d7f0d539a46a45bbc043f35b59d0d96388ac0008af2f000000001976a914fd270b1ee6abcaea97fea7ad0402e8bd8ad6d77c88ac92040000")).unwrap();
+    assert_eq!(
+        segwit_v0(
+            &transaction,
+            0,
+            &hex!("76a91479091972186c449eb1ded22b78e40d009bdf008988ac"),
+            1_000_000_000,
+            SIGHASH_ALL as u32,
+        ),
+        hex!("64f3b0f4dd2bb3aa1ce8566d220cc74dda9df97d8490cc81d89d735c92e59fb6")
+    );
+}
+
+#[test]
+fn legacy_signature_hash_verifies() {
+    // The signature from BIP 143's signed transaction for the first, P2PK, input; its script code is its script pubkey.
+    let transaction = Transaction::try_deserialize(&NATIVE_P2WPKH_TRANSACTION).unwrap();
+    let public_key = hex!("03c9f4836b9a4f77fc0d81f7bcb01b7f1b35916864b9476c241ce9fc198bd25432");
+    let mut script_code = Vec::from([0x21]);
+    script_code.extend(public_key);
+    script_code.push(0xAC);
+
+    let signature = ecdsa::try_from_der(&hex!("30450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed")).unwrap();
+    let public_key = try_decode_public_key(&public_key).unwrap();
+    let sighash = legacy(&transaction, 0, &script_code, SIGHASH_ALL as u32);
+    assert!(ecdsa::verify(&public_key, &sighash, &signature));
+
+    // Each sighash type commits to different data.
+    for sighash_type in [
+        SIGHASH_NONE,
+        SIGHASH_SINGLE,
+        SIGHASH_ALL | SIGHASH_ANYONECANPAY,
+    ] {
+        assert_ne!(
+            legacy(&transaction, 0, &script_code, sighash_type as u32),
+            sighash
+        );
+    }
+}
+
+#[test]
+fn legacy_sighash_single_without_an_output_signs_one() {
+    let transaction = Transaction::from(
+        2,
+        Vec::from([
+            TransactionInput::from([0x11; 32], 0, Vec::new(), 0xFFFFFFFF, Vec::new()),
+            TransactionInput::from([0x22; 32], 0, Vec::new(), 0xFFFFFFFF, Vec::new()),
+        ]),
+        Vec::from([TransactionOutput::from(1_000, Vec::from([0x6A]))]),
+        0,
+    );
+
+    let mut one = [0u8; 32];
+    one[0] = 1;
+    assert_eq!(legacy(&transaction, 1, &[0x51], SIGHASH_SINGLE as u32), one);
+    assert_ne!(legacy(&transaction, 0, &[0x51], SIGHASH_SINGLE as u32), one);
+}
+
+#[test]
+fn bip_322_taproot_signature_hash_verifies() {
+    // BIP 322's P2TR "Hello World" signature signs a virtual transaction's only input, with SIGHASH_ALL.
+    let internal_key = schnorr::try_derive_x_only_public_key(&hex!(
+        "bb051cd0dda0246f33c5a9e133ebd8e7bc02a92af6c41adc131ccd7826c5b004"
+    ))
+    .unwrap();
+    let output_key = taproot::try_tweak_public_key(&internal_key).unwrap();
+    let mut script_pubkey = Vec::from([0x51, 0x20]);
+    script_pubkey.extend(output_key);
+
+    let transaction = Transaction::from(
+        0,
+        Vec::from([TransactionInput::from(
+            to_spend_txid(&script_pubkey, b"Hello World"),
+            0,
+            Vec::new(),
+            0,
+            Vec::new(),
+        )]),
+        Vec::from([TransactionOutput::from(0, Vec::from([0x6A]))]),
+        0,
+    );
+
+    let spent_outputs = [TransactionOutput::from(0, script_pubkey)];
+    let sighash = taproot_key_path(&transaction, 0, &spent_outputs, SIGHASH_ALL).unwrap();
+    let signature = hex!("ddebd3eb25012ffa82937d9f25f9644e047bb2f472ab6c5089bbb53588ada2884cb5bcc53911f32d8dcf9548733b694d120db6a4e485194559e8d8fe668d269f");
+    assert!(schnorr::verify(&output_key, &sighash, &signature));
+
+    // SIGHASH_DEFAULT commits to the same data, but the type is part of the message.
+    let default_sighash =
+        taproot_key_path(&transaction, 0, &spent_outputs, SIGHASH_DEFAULT).unwrap();
+    assert_ne!(default_sighash, sighash);
+}
+
+#[test]
+fn invalid_taproot_signature_hashes_are_rejected() {
+    let transaction = Transaction::from(
+        2,
+        Vec::from([
+            TransactionInput::from([0x11; 32], 0, Vec::new(), 0xFFFFFFFF, Vec::new()),
+            TransactionInput::from([0x22; 32], 0, Vec::new(), 0xFFFFFFFF, Vec::new()),
+        ]),
+        Vec::from([TransactionOutput::from(1_000, Vec::from([0x6A]))]),
+        0,
+    );
+
+    let spent_output = TransactionOutput::from(2_000, Vec::from([0x51, 0x20]));
+    let spent_outputs = [spent_output.clone(), spent_output.clone()];
+    assert!(taproot_key_path(&transaction, 1, &spent_outputs, SIGHASH_ALL).is_ok());
+    assert!(taproot_key_path(&transaction, 1, &spent_outputs, 0x04).is_err());
+    assert!(taproot_key_path(&transaction, 1, &spent_outputs, SIGHASH_ANYONECANPAY).is_err());
+    assert!(taproot_key_path(&transaction, 1, &spent_outputs, SIGHASH_SINGLE).is_err());
+    assert!(taproot_key_path(&transaction, 0, &spent_outputs, SIGHASH_SINGLE).is_ok());
+    assert!(taproot_key_path(&transaction, 1, &spent_outputs[..1], SIGHASH_ALL).is_err());
+}
+
+#[test]
+fn taproot_intermediate_hashes_follow_the_sighash_type() {
+    let transaction = Transaction::from(
+        2,
+        Vec::from([
+            TransactionInput::from([0x11; 32], 0, Vec::new(), 0xFFFFFFFF, Vec::new()),
+            TransactionInput::from([0x22; 32], 0, Vec::new(), 0xFFFFFFFF, Vec::new()),
+        ]),
+        Vec::from([TransactionOutput::from(1_000, Vec::from([0x6A]))]),
+        0,
+    );
+
+    let spent_output = TransactionOutput::from(2_000, Vec::from([0x51, 0x20]));
+    let spent_outputs = [spent_output.clone(), spent_output.clone()];
+    let hashes = TaprootHashes::try_from(&transaction, 0, &spent_outputs, SIGHASH_DEFAULT).unwrap();
+    assert_eq!(
+        hashes.sha_prevouts(),
+        Some(&hex!(
+            "0b98d62d0fee1219d703a9a831a293edb3c74e4864a6911e2b41995d56d97b7c"
+        ))
+    );
+    assert!(hashes.sha_amounts().is_some());
+    assert!(hashes.sha_script_pubkeys().is_some());
+    assert!(hashes.sha_sequences().is_some());
+    assert_eq!(
+        hashes.sha_outputs(),
+        Some(&hex!(
+            "02b0d135748fe39a817107f9856289b632e2b5a693339baca7b8e9354c2bb353"
+        ))
+    );
+    assert!(hashes.sha_single_output().is_none());
+
+    // Data excluded by the sighash type is omitted, rather than zeroed.
+    let hashes = TaprootHashes::try_from(
+        &transaction,
+        0,
+        &spent_outputs,
+        SighashType::SingleAnyoneCanPay.value(),
+    )
+    .unwrap();
+    assert!(hashes.sha_prevouts().is_none());
+    assert!(hashes.sha_amounts().is_none());
+    assert!(hashes.sha_script_pubkeys().is_none());
+    assert!(hashes.sha_sequences().is_none());
+    assert!(hashes.sha_outputs().is_none());
+    assert_eq!(
+        hashes.sha_single_output(),
+        Some(&hex!(
+            "02b0d135748fe39a817107f9856289b632e2b5a693339baca7b8e9354c2bb353"
+        ))
+    );
+
+    let hashes = TaprootHashes::try_from(&transaction, 1, &spent_outputs, SIGHASH_NONE).unwrap();
+    assert!(hashes.sha_prevouts().is_some());
+    assert!(hashes.sha_outputs().is_none());
+    assert!(hashes.sha_single_output().is_none());
+}
+
+fn bip_341_spent_outputs() -> Vec<TransactionOutput> {
+    BIP_341_SPENT_OUTPUTS
+        .iter()
+        .map(|(a, s)| TransactionOutput::from(*a, Vec::from(*s)))
+        .collect()
+}
+
+#[test]
+fn bip_341_key_path_signature_hashes() {
+    let transaction = Transaction::try_deserialize(&BIP_341_TRANSACTION).unwrap();
+    let spent_outputs = bip_341_spent_outputs();
+    for (input_index, sighash_type, message, sighash) in BIP_341_KEY_PATH_SPENDS {
+        assert_eq!(
+            taproot_key_path_message(&transaction, input_index, &spent_outputs, sighash_type)
+                .unwrap(),
+            message
+        );
+        assert_eq!(
+            taproot_key_path(&transaction, input_index, &spent_outputs, sighash_type),
+            Ok(sighash)
+        );
+    }
+}
+
+#[test]
+fn bip_341_intermediate_hashes() {
+    let transaction = Transaction::try_deserialize(&BIP_341_TRANSACTION).unwrap();
+    let spent_outputs = bip_341_spent_outputs();
+    let hashes = TaprootHashes::try_from(&transaction, 3, &spent_outputs, SIGHASH_ALL).unwrap();
+    assert_eq!(
+        hashes.sha_prevouts(),
+        Some(&hex!(
+            "e3b33bb4ef3a52ad1fffb555c0d82828eb22737036eaeb02a235d82b909c4c3f"
+        ))
+    );
+    assert_eq!(
+        hashes.sha_amounts(),
+        Some(&hex!(
+            "58a6964a4f5f8f0b642ded0a8a553be7622a719da71d1f5befcefcdee8e0fde6"
+        ))
+    );
+    assert_eq!(
+        hashes.sha_script_pubkeys(),
+        Some(&hex!(
+            "23ad0f61ad2bca5ba6a7693f50fce988e17c3780bf2b1e720cfbb38fbdd52e21"
+        ))
+    );
+    assert_eq!(
+        hashes.sha_sequences(),
+        Some(&hex!(
+            "18959c7221ab5ce9e26c3cd67b22c24f8baa54bac281d8e6b05e400e6c3a957e"
+        ))
+    );
+    assert_eq!(
+        hashes.sha_outputs(),
+        Some(&hex!(
+            "a2e6dab7c1f0dcd297c8d61647fd17d821541ea69c3cc37dcbad7f90d4eb4bc5"
+        ))
+    );
+    assert!(hashes.sha_single_output().is_none());
+
+    // SIGHASH_SINGLE commits to the output with the input's index.
+    let hashes = TaprootHashes::try_from(&transaction, 0, &spent_outputs, SIGHASH_SINGLE).unwrap();
+    assert!(hashes.sha_outputs().is_none());
+    assert_eq!(
+        hashes.sha_single_output(),
+        Some(&hex!(
+            "d0418f0e9a36245b9a50ec87f8bf5be5bcae434337b87139c3a5b1f56e33cba0"
+        ))
+    );
+}
@@ -0,0 +1,125 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::bitcoin::{
+    hd_wallets::Bip32KeyNetwork,
+    signed_messages::{
+        address_matches_signer, message_hash, try_recover_public_key, try_sign,
+        SignedMessageAddressType,
+    },
+};
+use alloc::vec::Vec;
+use hex_literal::hex;
+use macros::u16_array;
+
+const PRIVATE_KEY: [u8; 32] =
+    hex!("0000000000000000000000000000000000000000000000000000000000000001");
+const COMPRESSED_PUBLIC_KEY: [u8; 33] =
+    hex!("0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798");
+
+#[test]
+fn message_hashes() {
+    assert_eq!(
+        message_hash(b"Hello World"),
+        hex!("a7af0baad5ae99b97fc69b3a0d1abcf3ef17f131cc4776e1bc11933ec8550f49")
+    );
+
+    // Messages of 253 bytes or more have a 3 byte compact size length prefix.
+    assert_eq!(
+        message_hash(&[b'a'; 300]),
+        hex!("3ec158a43b80359df647352dac1d37dbf26a94e5f06e5790760290c75cd11dc0")
+    );
+}
+
+#[test]
+fn signatures_recover_the_signer_for_each_address_type() {
+    for (address_type, header_base) in [
+        (SignedMessageAddressType::P2pkhUncompressed, 27),
+        (SignedMessageAddressType::P2pkh, 31),
+        (SignedMessageAddressType::P2shP2wpkh, 35),
+        (SignedMessageAddressType::P2wpkh, 39),
+    ] {
+        let signature = try_sign(&PRIVATE_KEY, b"Hello World", address_type).unwrap();
+        assert!(signature[0] >= header_base && signature[0] < header_base + 4);
+
+        let (public_key, recovered_type) =
+            try_recover_public_key(b"Hello World", &signature).unwrap();
+        assert_eq!(recovered_type, address_type);
+        match address_type {
+            SignedMessageAddressType::P2pkhUncompressed => {
+                assert_eq!(public_key.len(), 65);
+                assert_eq!(public_key[1..33], COMPRESSED_PUBLIC_KEY[1..]);
+            }
+            _ => assert_eq!(public_key, COMPRESSED_PUBLIC_KEY),
+        }
+
+        let address = address_type
+            .address(&public_key, Bip32KeyNetwork::MainNet)
+            .unwrap();
+        assert!(address_matches_signer(&address, &public_key, address_type));
+
+        // A different message recovers a different key.
+        let (other_key, _) = try_recover_public_key(b"Hello World!", &signature).unwrap();
+        assert_ne!(other_key, public_key);
+    }
+}
+
+#[test]
+fn invalid_signatures_are_rejected() {
+    let signature = try_sign(&PRIVATE_KEY, b"", SignedMessageAddressType::P2pkh).unwrap();
+    assert!(try_recover_public_key(b"", &signature[..64]).is_err());
+
+    let mut invalid_header = signature;
+    invalid_header[0] = 43;
+    assert!(try_recover_public_key(b"", &invalid_header).is_err());
+}
+
+#[test]
+fn compressed_key_signatures_match_any_single_key_address() {
+    // Electrum signs for segwit addresses with the P2PKH header.
+    let matches = |address: &[u16]| {
+        address_matches_signer(
+            address,
+            &COMPRESSED_PUBLIC_KEY,
+            SignedMessageAddressType::P2pkh,
+        )
+    };
+
+    assert!(matches(&u16_array!("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH")));
+    assert!(matches(&u16_array!("3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN")));
+    assert!(matches(&u16_array!(
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+    )));
+    assert!(matches(&u16_array!(
+        "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4"
+    )));
+    assert!(matches(&u16_array!(
+        "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+    )));
+
+    // The uncompressed key's address, and a different key's address, don't match.
+    assert!(!matches(&u16_array!("1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm")));
+    assert!(!matches(&u16_array!("1bgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH")));
+    assert!(!matches(&Vec::new()));
+
+    // Mixed case bech32 addresses are invalid.
+    assert!(!matches(&u16_array!(
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3T4"
+    )));
+    assert!(!matches(&u16_array!(
+        "Bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+    )));
+}
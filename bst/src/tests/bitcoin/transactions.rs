@@ -0,0 +1,173 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::bitcoin::{
+    addresses,
+    hd_wallets::Bip32KeyNetwork,
+    transactions::{ScriptType, Transaction},
+};
+use alloc::string::String;
+use hex_literal::hex;
+
+// The genesis block's coinbase transaction.
+const GENESIS_COINBASE: [u8; 204] = hex!("01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000");
+
+// BIP 143's native P2WPKH example; a P2PK input, and a P2WPKH input.
+const SEGWIT_TRANSACTION: [u8; 343] = hex!("01000000000102fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000494830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac000247304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee0121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee635711000000");
+
+fn reversed(mut hash: [u8; 32]) -> [u8; 32] {
+    // Transaction IDs are conventionally displayed in reverse byte order.
+    hash.reverse();
+    hash
+}
+
+#[test]
+fn legacy_transactions_deserialize() {
+    let transaction = Transaction::try_deserialize(&GENESIS_COINBASE).unwrap();
+    assert_eq!(transaction.version(), 1);
+    assert_eq!(transaction.lock_time(), 0);
+    assert!(!transaction.has_witness());
+    assert_eq!(transaction.inputs().len(), 1);
+    assert_eq!(transaction.inputs()[0].previous_txid(), &[0u8; 32]);
+    assert_eq!(transaction.inputs()[0].previous_output_index(), 0xFFFFFFFF);
+    assert_eq!(transaction.inputs()[0].script_sig().len(), 77);
+    assert_eq!(transaction.outputs().len(), 1);
+    assert_eq!(transaction.outputs()[0].value(), 5_000_000_000);
+    assert_eq!(transaction.outputs()[0].script_type(), ScriptType::P2pk);
+    assert_eq!(transaction.serialize(true), GENESIS_COINBASE);
+    assert_eq!(
+        reversed(transaction.txid()),
+        hex!("4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b")
+    );
+    assert_eq!(transaction.wtxid(), transaction.txid());
+}
+
+#[test]
+fn transaction_versions_are_signed() {
+    // A version with its top bit set is negative, and serializes back to the same bytes.
+    let mut bytes = GENESIS_COINBASE;
+    bytes[..4].copy_from_slice(&[0xFF; 4]);
+    let transaction = Transaction::try_deserialize(&bytes).unwrap();
+    assert_eq!(transaction.version(), -1);
+    assert_eq!(transaction.serialize(false), bytes);
+}
+
+#[test]
+fn segwit_transactions_deserialize() {
+    let transaction = Transaction::try_deserialize(&SEGWIT_TRANSACTION).unwrap();
+    assert_eq!(transaction.lock_time(), 17);
+    assert!(transaction.has_witness());
+    assert_eq!(transaction.inputs().len(), 2);
+    assert_eq!(transaction.inputs()[0].sequence(), 0xFFFFFFEE);
+    assert!(transaction.inputs()[0].witness().is_empty());
+    assert_eq!(transaction.inputs()[1].previous_output_index(), 1);
+    assert_eq!(transaction.inputs()[1].witness().len(), 2);
+    assert_eq!(transaction.outputs()[0].value(), 112_340_000);
+    assert_eq!(transaction.outputs()[1].value(), 223_450_000);
+    assert_eq!(transaction.total_output_value(), 335_790_000);
+    assert_eq!(transaction.serialize(true), SEGWIT_TRANSACTION);
+    assert_eq!(transaction.serialize(false).len(), 233);
+    assert_eq!(transaction.virtual_size(), 261);
+    assert_eq!(
+        reversed(transaction.txid()),
+        hex!("e8151a2af31c368a35053ddd4bdb285a8595c769a3ad83e0fa02314a602d4609")
+    );
+    assert_eq!(
+        reversed(transaction.wtxid()),
+        hex!("c36c38370907df2324d9ce9d149d191192f338b37665a82e78e76a12c909b762")
+    );
+}
+
+#[test]
+fn malformed_transactions_do_not_deserialize() {
+    // Truncated, with trailing bytes, and with an input count far exceeding the data.
+    assert!(Transaction::try_deserialize(&GENESIS_COINBASE[..GENESIS_COINBASE.len() - 1]).is_err());
+    let mut trailing = GENESIS_COINBASE.to_vec();
+    trailing.push(0);
+    assert!(Transaction::try_deserialize(&trailing).is_err());
+    assert!(Transaction::try_deserialize(&hex!("01000000FFFFFFFFFF0000000000")).is_err());
+
+    // The segwit marker without any witness data.
+    let mut bytes = SEGWIT_TRANSACTION.to_vec();
+    let witness_start = bytes.len() - 4 - 1 - 1 - 0x47 - 1 - 0x21 - 1;
+    bytes.splice(witness_start..bytes.len() - 4, [0x00, 0x00]);
+    assert!(Transaction::try_deserialize(&bytes).is_err());
+}
+
+#[test]
+fn script_types_are_identified() {
+    for (script, script_type) in [
+        (
+            &hex!("76a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac")[..],
+            ScriptType::P2pkh,
+        ),
+        (
+            &hex!("a914b472a266d0bd89c13706a4132ccfb16f7c3b9fcb87")[..],
+            ScriptType::P2sh,
+        ),
+        (
+            &hex!("0014751e76e8199196d454941c45d1b3a323f1433bd6")[..],
+            ScriptType::P2wpkh,
+        ),
+        (
+            &hex!("00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262")[..],
+            ScriptType::P2wsh,
+        ),
+        (
+            &hex!("5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c")[..],
+            ScriptType::P2tr,
+        ),
+        (
+            &hex!("5210751e76e8199196d454941c45d1b3a323")[..],
+            ScriptType::WitnessUnknown(2),
+        ),
+        (&hex!("6a0568656c6c6f")[..], ScriptType::NullData),
+        (
+            &hex!("0015751e76e8199196d454941c45d1b3a323f1433bd6ff")[..],
+            ScriptType::NonStandard,
+        ),
+    ] {
+        assert_eq!(ScriptType::from_script_pubkey(script), script_type);
+    }
+}
+
+#[test]
+fn output_scripts_have_addresses() {
+    for (script, address) in [
+        (
+            &hex!("76a914751e76e8199196d454941c45d1b3a323f1433bd688ac")[..],
+            "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH",
+        ),
+        (
+            &hex!("0014751e76e8199196d454941c45d1b3a323f1433bd6")[..],
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+        ),
+        (
+            &hex!("5210751e76e8199196d454941c45d1b3a323")[..],
+            "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+        ),
+    ] {
+        assert_eq!(
+            String::from_utf16(
+                &addresses::from_script_pubkey(script, Bip32KeyNetwork::MainNet).unwrap()
+            )
+            .unwrap(),
+            address
+        );
+    }
+
+    assert!(addresses::from_script_pubkey(&hex!("6a00"), Bip32KeyNetwork::MainNet).is_none());
+}
@@ -0,0 +1,125 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::{
+        adaptor::{try_adapt, try_extract_secret, try_pre_sign, verify_pre_signature},
+        schnorr::{try_derive_x_only_public_key, verify},
+        try_derive_serialized_public_key,
+    },
+    tests::PARALLELIZED_TEST_THREAD_COUNT,
+};
+use hex_literal::hex;
+use rand::random;
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const RANDOM_ITERATIONS: usize = 40;
+
+fn random_private_key() -> [u8; 32] {
+    secp256k1::SecretKey::from_slice(&random::<[u8; 32]>())
+        .unwrap()
+        .secret_bytes()
+}
+
+#[test]
+fn invalid_inputs_are_rejected() {
+    let adaptor_point = try_derive_serialized_public_key(&[1u8; 32]).unwrap();
+    assert!(try_pre_sign(&[0u8; 32], &[], &adaptor_point, &[0u8; 32]).is_none());
+    assert!(try_pre_sign(
+        &hex!("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"),
+        &[],
+        &adaptor_point,
+        &[0u8; 32]
+    )
+    .is_none());
+
+    let mut invalid_adaptor_point = adaptor_point;
+    invalid_adaptor_point[0] = 0x04;
+    assert!(try_pre_sign(&[2u8; 32], &[], &invalid_adaptor_point, &[0u8; 32]).is_none());
+
+    let pre_signature = try_pre_sign(&[2u8; 32], &[], &adaptor_point, &[0u8; 32]).unwrap();
+    assert!(try_adapt(&pre_signature, &[0u8; 32]).is_none());
+}
+
+#[test]
+fn random_adaptor_signatures_complete_to_valid_signatures_and_reveal_the_secret() {
+    let secp = secp256k1::Secp256k1::new();
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                let private_key = random_private_key();
+                let adaptor_secret = random_private_key();
+                let message = random::<[u8; 32]>();
+
+                let public_key = try_derive_x_only_public_key(&private_key).unwrap();
+                let adaptor_point = try_derive_serialized_public_key(&adaptor_secret).unwrap();
+                let pre_signature =
+                    try_pre_sign(&private_key, &message, &adaptor_point, &random()).unwrap();
+                assert!(verify_pre_signature(
+                    &public_key,
+                    &message,
+                    &adaptor_point,
+                    &pre_signature
+                ));
+
+                // A pre-signature is not a valid signature, and is only valid for its adaptor point and message.
+                let mut incomplete_signature = [0u8; 64];
+                incomplete_signature[..32].copy_from_slice(&pre_signature[1..33]);
+                incomplete_signature[32..].copy_from_slice(&pre_signature[33..]);
+                assert!(!verify(&public_key, &message, &incomplete_signature));
+
+                let other_adaptor_point =
+                    try_derive_serialized_public_key(&random_private_key()).unwrap();
+                assert!(!verify_pre_signature(
+                    &public_key,
+                    &message,
+                    &other_adaptor_point,
+                    &pre_signature
+                ));
+                assert!(!verify_pre_signature(
+                    &public_key,
+                    &random::<[u8; 32]>(),
+                    &adaptor_point,
+                    &pre_signature
+                ));
+
+                // The completed signature is a valid BIP 340 signature.
+                let signature = try_adapt(&pre_signature, &adaptor_secret).unwrap();
+                assert!(verify(&public_key, &message, &signature));
+                assert!(secp
+                    .verify_schnorr(
+                        &secp256k1::schnorr::Signature::from_slice(&signature).unwrap(),
+                        &secp256k1::Message::from_digest(message),
+                        &secp256k1::XOnlyPublicKey::from_slice(&public_key).unwrap(),
+                    )
+                    .is_ok());
+
+                // Publishing the signature reveals the adaptor secret.
+                assert_eq!(
+                    try_extract_secret(&pre_signature, &signature, &adaptor_point).unwrap(),
+                    adaptor_secret
+                );
+                assert!(
+                    try_extract_secret(&pre_signature, &signature, &other_adaptor_point).is_none()
+                );
+
+                // Completing with the wrong secret produces an invalid signature.
+                let wrong_signature = try_adapt(&pre_signature, &random_private_key()).unwrap();
+                assert!(!verify(&public_key, &message, &wrong_signature));
+            }
+        });
+}
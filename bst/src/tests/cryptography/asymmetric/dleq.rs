@@ -0,0 +1,212 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::{
+        dleq::{try_generate_proof, verify_proof},
+        try_derive_serialized_public_key,
+    },
+    tests::PARALLELIZED_TEST_THREAD_COUNT,
+};
+use hex_literal::hex;
+use rand::{random, thread_rng, Rng};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const RANDOM_ITERATIONS: usize = 40;
+
+// Deterministic proofs; the expected values were calculated with an independent implementation of BIP 374's GenerateProof.
+const BASE: [u8; 33] = hex!("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9");
+const MESSAGE: [u8; 32] = hex!("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF");
+const VECTORS: [([u8; 32], [u8; 32], Option<&[u8; 32]>, [u8; 33], [u8; 33], [u8; 64]); 2] = [
+    (
+        hex!("0000000000000000000000000000000000000000000000000000000000000001"),
+        [0u8; 32],
+        None,
+        hex!("0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798"),
+        BASE,
+        hex!("34C9ED825409995BC0381AE467AE40F46FA0CA19766293B0FE6BE9E62711B431DC5A91B15754C165AFCFF58BE204D32AEEF9E9E371147680D70F0FBE4581F13C"),
+    ),
+    (
+        hex!("7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671"),
+        [1u8; 32],
+        Some(&MESSAGE),
+        hex!("03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9"),
+        hex!("031AF71DF2295235A8BFCEF6D2E263E950840063F37B2449AAEF43BAF280ECE8C4"),
+        hex!("72965564A53AD02F13E627A3389ED4254185350D2E2BB2FCE382A17DE619C0BFF76803AD43D8497DBFCAEAA4C47D089A99C677E579D5A8E6508A269BF622016D"),
+    ),
+];
+
+#[test]
+fn proofs_match_the_expected_vectors() {
+    for (secret, auxiliary_random, message, public_key, shared_key, proof) in VECTORS {
+        assert_eq!(
+            try_derive_serialized_public_key(&secret).unwrap(),
+            public_key
+        );
+        assert_eq!(
+            try_generate_proof(&secret, &BASE, &auxiliary_random, message),
+            Some((shared_key, proof))
+        );
+        assert!(verify_proof(
+            &public_key,
+            &BASE,
+            &shared_key,
+            &proof,
+            message
+        ));
+    }
+}
+
+#[test]
+fn invalid_proofs_fail_verification() {
+    let (_, _, message, public_key, shared_key, proof) = VECTORS[1];
+
+    // A and C swapped, the message omitted, e changed, and s not less than n.
+    assert!(!verify_proof(
+        &shared_key,
+        &BASE,
+        &public_key,
+        &proof,
+        message
+    ));
+    assert!(!verify_proof(&public_key, &BASE, &shared_key, &proof, None));
+
+    let mut changed_e = proof;
+    changed_e[31] ^= 1;
+    assert!(!verify_proof(
+        &public_key,
+        &BASE,
+        &shared_key,
+        &changed_e,
+        message
+    ));
+
+    let mut large_s = proof;
+    large_s[32..].copy_from_slice(&hex!(
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"
+    ));
+    assert!(!verify_proof(
+        &public_key,
+        &BASE,
+        &shared_key,
+        &large_s,
+        message
+    ));
+}
+
+#[test]
+fn invalid_inputs_are_rejected() {
+    let base = try_derive_serialized_public_key(&[1u8; 32]).unwrap();
+    assert!(try_generate_proof(&[0u8; 32], &base, &[0u8; 32], None).is_none());
+    assert!(try_generate_proof(
+        &hex!("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"),
+        &base,
+        &[0u8; 32],
+        None
+    )
+    .is_none());
+
+    let mut invalid_base = base;
+    invalid_base[0] = 0x04;
+    assert!(try_generate_proof(&[2u8; 32], &invalid_base, &[0u8; 32], None).is_none());
+}
+
+#[test]
+fn random_proofs_are_valid_and_bound_to_their_points() {
+    let secp = secp256k1::Secp256k1::new();
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                let secret = secp256k1::SecretKey::from_slice(&random::<[u8; 32]>()).unwrap();
+                let base_secret = secp256k1::SecretKey::from_slice(&random::<[u8; 32]>()).unwrap();
+                let base_point = base_secret.public_key(&secp);
+                let base = base_point.serialize();
+                let message = if random() {
+                    Some(random::<[u8; 32]>())
+                } else {
+                    None
+                };
+
+                let public_key = secret.public_key(&secp).serialize();
+                let (shared_key, mut proof) =
+                    try_generate_proof(&secret.secret_bytes(), &base, &random(), message.as_ref())
+                        .unwrap();
+
+                assert_eq!(
+                    shared_key,
+                    base_point
+                        .mul_tweak(&secp, &secp256k1::Scalar::from(secret))
+                        .unwrap()
+                        .serialize()
+                );
+                assert!(verify_proof(
+                    &public_key,
+                    &base,
+                    &shared_key,
+                    &proof,
+                    message.as_ref()
+                ));
+
+                // The proof is bound to its message, and to each of its points.
+                let other_message = random::<[u8; 32]>();
+                assert!(!verify_proof(
+                    &public_key,
+                    &base,
+                    &shared_key,
+                    &proof,
+                    Some(&other_message)
+                ));
+
+                let other_point = secp256k1::SecretKey::from_slice(&random::<[u8; 32]>())
+                    .unwrap()
+                    .public_key(&secp)
+                    .serialize();
+                assert!(!verify_proof(
+                    &other_point,
+                    &base,
+                    &shared_key,
+                    &proof,
+                    message.as_ref()
+                ));
+                assert!(!verify_proof(
+                    &public_key,
+                    &other_point,
+                    &shared_key,
+                    &proof,
+                    message.as_ref()
+                ));
+                assert!(!verify_proof(
+                    &public_key,
+                    &base,
+                    &other_point,
+                    &proof,
+                    message.as_ref()
+                ));
+
+                // Tamper with the proof.
+                let index = thread_rng().gen_range(0..64);
+                proof[index] ^= 1 << thread_rng().gen_range(0..8);
+                assert!(!verify_proof(
+                    &public_key,
+                    &base,
+                    &shared_key,
+                    &proof,
+                    message.as_ref()
+                ));
+            }
+        });
+}
@@ -0,0 +1,107 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::{
+        ecdsa::{to_der, try_from_der, try_recover_public_key, try_sign_recoverable, verify},
+        try_derive_serialized_public_key,
+    },
+    tests::PARALLELIZED_TEST_THREAD_COUNT,
+};
+use hex_literal::hex;
+use rand::random;
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const RANDOM_ITERATIONS: usize = 64;
+
+#[test]
+fn invalid_private_keys_are_rejected() {
+    assert!(try_sign_recoverable(&[0u8; 32], &[1u8; 32]).is_none());
+    assert!(try_sign_recoverable(
+        &hex!("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"),
+        &[1u8; 32]
+    )
+    .is_none());
+}
+
+#[test]
+fn invalid_signatures_recover_no_public_key() {
+    let (signature, recovery_id) = try_sign_recoverable(&[1u8; 32], &[2u8; 32]).unwrap();
+    assert!(try_recover_public_key(&[2u8; 32], &signature, 4).is_none());
+
+    let mut zero_r = signature;
+    zero_r[..32].fill(0);
+    assert!(try_recover_public_key(&[2u8; 32], &zero_r, recovery_id).is_none());
+
+    let mut overflowing_s = signature;
+    overflowing_s[32..].copy_from_slice(&hex!(
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"
+    ));
+    assert!(try_recover_public_key(&[2u8; 32], &overflowing_s, recovery_id).is_none());
+}
+
+#[test]
+fn random_signatures_match_libsecp256k1_and_recover_the_signer() {
+    let secp = secp256k1::Secp256k1::new();
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                let private_key = secp256k1::SecretKey::from_slice(&random::<[u8; 32]>()).unwrap();
+                let message_hash = random::<[u8; 32]>();
+
+                // libsecp256k1 also uses RFC 6979 nonces and low-S normalization, so the signatures should be identical.
+                let (signature, recovery_id) =
+                    try_sign_recoverable(&private_key.secret_bytes(), &message_hash).unwrap();
+                let expected =
+                    secp.sign_ecdsa(&secp256k1::Message::from_digest(message_hash), &private_key);
+                assert_eq!(signature, expected.serialize_compact());
+
+                let public_key =
+                    try_derive_serialized_public_key(&private_key.secret_bytes()).unwrap();
+                let recovered =
+                    try_recover_public_key(&message_hash, &signature, recovery_id).unwrap();
+                assert_eq!(recovered.try_serialize_compressed().unwrap(), public_key);
+                assert!(verify(&recovered, &message_hash, &signature));
+                assert!(!verify(&recovered, &random::<[u8; 32]>(), &signature));
+
+                // Flipping the Y parity recovers a different key.
+                match try_recover_public_key(&message_hash, &signature, recovery_id ^ 1) {
+                    Some(p) => assert_ne!(p.try_serialize_compressed().unwrap(), public_key),
+                    None => {}
+                }
+            }
+        });
+}
+
+#[test]
+fn non_strict_der_is_rejected() {
+    let (signature, _) = try_sign_recoverable(&[1u8; 32], &[2u8; 32]).unwrap();
+    let der = to_der(&signature);
+
+    // Trailing bytes, and an inner length which disagrees with the outer sequence.
+    let mut trailing = der.clone();
+    trailing.push(0);
+    assert!(try_from_der(&trailing).is_none());
+
+    let mut truncated = der.clone();
+    truncated.pop();
+    assert!(try_from_der(&truncated).is_none());
+
+    // A redundant leading zero byte.
+    assert!(try_from_der(&hex!("3007020200010201 01")).is_none());
+    assert!(try_from_der(&hex!("3006020101020101")).is_some());
+}
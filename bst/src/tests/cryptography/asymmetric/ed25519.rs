@@ -0,0 +1,168 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cryptography::asymmetric::edwards::{
+        ed25519::{verify, Ed25519KeyPair},
+        EdwardsPoint,
+    },
+    tests::PARALLELIZED_TEST_THREAD_COUNT,
+};
+use hex_literal::hex;
+use rand::{random, thread_rng, Rng};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const RANDOM_ITERATIONS: usize = 40;
+
+fn test_vector(seed: [u8; 32], public_key: [u8; 32], message: &[u8], signature: [u8; 64]) {
+    let mut key_pair = Ed25519KeyPair::from_seed(&seed);
+    assert_eq!(*key_pair.public_key(), public_key);
+    assert_eq!(key_pair.sign(message), signature);
+    assert!(verify(&public_key, message, &signature));
+    key_pair.zero();
+}
+
+#[test]
+fn rfc_8032_test_1() {
+    test_vector(
+        hex!("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"),
+        hex!("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"),
+        &[],
+        hex!("e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"),
+    );
+}
+
+#[test]
+fn rfc_8032_test_2() {
+    test_vector(
+        hex!("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb"),
+        hex!("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c"),
+        &hex!("72"),
+        hex!("92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"),
+    );
+}
+
+#[test]
+fn rfc_8032_test_3() {
+    test_vector(
+        hex!("c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7"),
+        hex!("fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025"),
+        &hex!("af82"),
+        hex!("6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a"),
+    );
+}
+
+#[test]
+fn rfc_8032_test_sha_abc() {
+    test_vector(
+        hex!("833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42"),
+        hex!("ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf"),
+        &hex!("ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"),
+        hex!("dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b58909351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704"),
+    );
+}
+
+#[test]
+fn non_canonical_signature_scalars_are_rejected() {
+    let public_key = hex!("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    let mut signature = hex!("e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b");
+
+    // S + L is congruent to S, but isn't a valid encoding.
+    let l = hex!("edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010");
+    let mut carry = 0u16;
+    for i in 0..32 {
+        let sum = signature[32 + i] as u16 + l[i] as u16 + carry;
+        signature[32 + i] = sum as u8;
+        carry = sum >> 8;
+    }
+
+    assert!(!verify(&public_key, &[], &signature));
+}
+
+#[test]
+fn invalid_point_encodings_are_rejected() {
+    // y = p is not canonical.
+    assert!(EdwardsPoint::try_decompress(&hex!(
+        "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f"
+    ))
+    .is_none());
+
+    // y = 2 has no corresponding x.
+    assert!(EdwardsPoint::try_decompress(&hex!(
+        "0200000000000000000000000000000000000000000000000000000000000000"
+    ))
+    .is_none());
+
+    // y = 1 has x = 0, which has no negative.
+    assert!(EdwardsPoint::try_decompress(&hex!(
+        "0100000000000000000000000000000000000000000000000000000000000080"
+    ))
+    .is_none());
+
+    assert!(EdwardsPoint::try_decompress(&hex!(
+        "0100000000000000000000000000000000000000000000000000000000000000"
+    ))
+    .unwrap()
+    .is_identity());
+}
+
+#[test]
+fn point_compression_round_trips() {
+    let base = EdwardsPoint::BASE.compress();
+    assert_eq!(
+        base,
+        hex!("5866666666666666666666666666666666666666666666666666666666666666")
+    );
+
+    assert!(EdwardsPoint::try_decompress(&base)
+        .unwrap()
+        .equals(&EdwardsPoint::BASE));
+
+    assert!(EdwardsPoint::BASE
+        .add(&EdwardsPoint::BASE)
+        .equals(&EdwardsPoint::BASE.double()));
+
+    assert!(EdwardsPoint::BASE
+        .add(&EdwardsPoint::BASE.negate())
+        .is_identity());
+}
+
+#[test]
+fn random_signatures_verify() {
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                let key_pair = Ed25519KeyPair::from_seed(&random());
+                let mut message = (0..thread_rng().gen_range(0..300))
+                    .map(|_| random::<u8>())
+                    .collect::<Vec<u8>>();
+
+                let mut signature = key_pair.sign(&message);
+                assert!(verify(key_pair.public_key(), &message, &signature));
+
+                // Tamper with the message.
+                message.push(random());
+                assert!(!verify(key_pair.public_key(), &message, &signature));
+                message.pop();
+
+                // Tamper with the signature.
+                let index = thread_rng().gen_range(0..64);
+                signature[index] ^= 1 << thread_rng().gen_range(0..8);
+                assert!(!verify(key_pair.public_key(), &message, &signature));
+            }
+        });
+}
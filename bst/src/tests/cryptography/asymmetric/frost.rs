@@ -0,0 +1,312 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::frost::{
+        try_aggregate, try_generate_with_trusted_dealer, try_sign, verify, verify_signature_share,
+        DkgParticipant, KeyPackage, PublicKeyPackage, SigningNonces, SigningPackage,
+        COMMITMENT_LENGTH, SIGNATURE_SHARE_LENGTH,
+    },
+    tests::PARALLELIZED_TEST_THREAD_COUNT,
+};
+use alloc::vec::Vec;
+use hex_literal::hex;
+use rand::{random, seq::SliceRandom, thread_rng, Rng};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const RANDOM_ITERATIONS: usize = 16;
+
+// The RFC 9591 FROST(secp256k1, SHA-256) test vector's key material; 2 of 3, with a trusted dealer.
+const GROUP_PUBLIC_KEY: [u8; 33] =
+    hex!("02f37c34b66ced1fb51c34a90bdae006901f10625cc06c4f64663b0eae87d87b4f");
+
+fn rfc_public_key_package() -> PublicKeyPackage {
+    let mut bytes = Vec::from([2u8, 3u8]);
+    bytes.extend(GROUP_PUBLIC_KEY);
+    bytes.extend(hex!(
+        "033edecb0840954631b668f2ccd1250832007486de1dbe3d08b84466b26e215eec"
+    ));
+    PublicKeyPackage::try_from_bytes(&bytes).unwrap()
+}
+
+fn rfc_key_package(identifier: u8) -> KeyPackage {
+    let mut bytes = [0u8; 33];
+    bytes[0] = identifier;
+    bytes[1..].copy_from_slice(&match identifier {
+        1 => hex!("08f89ffe80ac94dcb920c26f3f46140bfc7f95b493f8310f5fc1ea2b01f4254c"),
+        2 => hex!("04f0feac2edcedc6ce1253b7fab8c86b856a797f44d83d82a385554e6e401984"),
+        _ => hex!("00e95d59dd0d46b0e303e500b62b7ccb0e555d49f5b849f5e748c071da8c0dbc"),
+    });
+    KeyPackage::try_from_bytes(&bytes).unwrap()
+}
+
+#[test]
+fn rfc_9591_key_shares_are_valid() {
+    let public_key_package = rfc_public_key_package();
+    assert_eq!(public_key_package.group_public_key(), GROUP_PUBLIC_KEY);
+    for identifier in 1..=3 {
+        assert!(rfc_key_package(identifier).is_valid_for(&public_key_package));
+    }
+
+    // A share presented with the wrong identifier doesn't match the verification share.
+    let mut bytes = rfc_key_package(1).to_bytes();
+    bytes[0] = 2;
+    assert!(!KeyPackage::try_from_bytes(&bytes)
+        .unwrap()
+        .is_valid_for(&public_key_package));
+}
+
+#[test]
+fn signing_matches_reference_implementation() {
+    let public_key_package = rfc_public_key_package();
+    let key_packages = [rfc_key_package(1), rfc_key_package(3)];
+    let mut nonces = [
+        SigningNonces::try_generate(&key_packages[0], &[1u8; 32]).unwrap(),
+        SigningNonces::try_generate(&key_packages[1], &[3u8; 32]).unwrap(),
+    ];
+
+    assert_eq!(*nonces[0].commitment(), hex!("01038cf239635fe42d6bc73a56d32ae7d8196b35332953a78637067f5c8fc0db7113033f2169a38f2650a969010a0e311397967a9a5cc138ac941a9e50d2549293c2bd"));
+    assert_eq!(*nonces[1].commitment(), hex!("03022f897ceaaab8a68d7c2b9a767fb691f3f17f7b2cae92e37b919ed7d8e1031e6902f540524f4bcd5abf7b7cb7459558261106b7d28c76ef20959fe40e900619bb03"));
+
+    // The commitment list is sorted, whatever order it's provided in.
+    let signing_package =
+        SigningPackage::try_from(&[*nonces[1].commitment(), *nonces[0].commitment()], b"test")
+            .unwrap();
+
+    let signing_package = SigningPackage::try_from_bytes(&signing_package.to_bytes()).unwrap();
+    assert_eq!(signing_package.identifiers(), [1, 3]);
+    assert_eq!(signing_package.message(), b"test");
+
+    let shares = [
+        try_sign(
+            &key_packages[0],
+            &public_key_package,
+            &mut nonces[0],
+            &signing_package,
+        )
+        .unwrap(),
+        try_sign(
+            &key_packages[1],
+            &public_key_package,
+            &mut nonces[1],
+            &signing_package,
+        )
+        .unwrap(),
+    ];
+
+    assert_eq!(
+        shares[0],
+        hex!("01ae6d97e885d53973af010d9f3b118b7b56d4e805d7cb519b5e4c42464a547b1d")
+    );
+    assert_eq!(
+        shares[1],
+        hex!("03fd6e4123d29523d39b63eab23b33713a2d83c5488879bdfd497fa91acc6eb7bd")
+    );
+
+    let signature = try_aggregate(&public_key_package, &signing_package, &shares).unwrap();
+    assert_eq!(signature, hex!("02c6b384b1095220a40be14c94f936d82d5c25f27586eac666aab39e5a2be5bf92abdbd90c586a5d474a64f8517644fcb6c9a9d067b0fc6f5ce7f98cd4468cf199"));
+    assert!(verify(&GROUP_PUBLIC_KEY, b"test", &signature));
+    assert!(!verify(&GROUP_PUBLIC_KEY, b"tess", &signature));
+
+    // Nonces can only be used once.
+    assert!(try_sign(
+        &key_packages[0],
+        &public_key_package,
+        &mut nonces[0],
+        &signing_package
+    )
+    .is_err());
+}
+
+#[test]
+fn invalid_parameters_are_rejected() {
+    assert!(try_generate_with_trusted_dealer(&[1u8; 32], 1, 3, &[0u8; 32]).is_err());
+    assert!(try_generate_with_trusted_dealer(&[1u8; 32], 3, 2, &[0u8; 32]).is_err());
+    assert!(try_generate_with_trusted_dealer(&[0u8; 32], 2, 3, &[0u8; 32]).is_err());
+    assert!(try_generate_with_trusted_dealer(&[1u8; 32], 2, 3, &[0u8; 31]).is_err());
+    assert!(DkgParticipant::try_from(4, 2, 3, &[0u8; 32]).is_err());
+    assert!(DkgParticipant::try_from(0, 2, 3, &[0u8; 32]).is_err());
+}
+
+fn sign_with_random_signers(public_key_package: &PublicKeyPackage, key_packages: &[KeyPackage]) {
+    let threshold = public_key_package.threshold() as usize;
+    let signer_count = thread_rng().gen_range(threshold..=key_packages.len());
+    let mut signers: Vec<&KeyPackage> = key_packages.iter().collect();
+    signers.shuffle(&mut thread_rng());
+    signers.truncate(signer_count);
+
+    let message = random::<[u8; 32]>();
+    let mut nonces: Vec<SigningNonces> = signers
+        .iter()
+        .map(|k| SigningNonces::try_generate(k, &random::<[u8; 32]>()).unwrap())
+        .collect();
+
+    let commitments: Vec<[u8; COMMITMENT_LENGTH]> =
+        nonces.iter().map(|n| *n.commitment()).collect();
+
+    // Too few commitments can't produce a signature.
+    let too_few = SigningPackage::try_from(&commitments[..threshold - 1], &message).unwrap();
+    assert!(try_sign(
+        signers[0],
+        public_key_package,
+        &mut SigningNonces::try_generate(signers[0], &random::<[u8; 32]>()).unwrap(),
+        &too_few
+    )
+    .is_err());
+
+    let signing_package = SigningPackage::try_from(&commitments, &message).unwrap();
+    let mut shares: Vec<[u8; SIGNATURE_SHARE_LENGTH]> = signers
+        .iter()
+        .zip(nonces.iter_mut())
+        .map(|(k, n)| try_sign(k, public_key_package, n, &signing_package).unwrap())
+        .collect();
+
+    for share in shares.iter() {
+        assert!(verify_signature_share(
+            public_key_package,
+            &signing_package,
+            share
+        ));
+    }
+
+    let signature = try_aggregate(public_key_package, &signing_package, &shares).unwrap();
+    assert!(verify(
+        &public_key_package.group_public_key(),
+        &message,
+        &signature
+    ));
+
+    // A tampered share is identified, and prevents aggregation.
+    shares[0][SIGNATURE_SHARE_LENGTH - 1] ^= 1;
+    assert!(!verify_signature_share(
+        public_key_package,
+        &signing_package,
+        &shares[0]
+    ));
+    assert!(try_aggregate(public_key_package, &signing_package, &shares).is_err());
+
+    // A missing share prevents aggregation.
+    shares.pop();
+    assert!(try_aggregate(public_key_package, &signing_package, &shares).is_err());
+}
+
+#[test]
+fn random_trusted_dealer_signing_sessions_produce_valid_signatures() {
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                let threshold = thread_rng().gen_range(2..5);
+                let participant_count = thread_rng().gen_range(threshold..7);
+                let secret_key = secp256k1::SecretKey::from_slice(&random::<[u8; 32]>())
+                    .unwrap()
+                    .secret_bytes();
+
+                let (public_key_package, key_packages) = try_generate_with_trusted_dealer(
+                    &secret_key,
+                    threshold,
+                    participant_count,
+                    &random::<[u8; 32]>(),
+                )
+                .unwrap();
+
+                // The group public key is the secret key's public key.
+                assert_eq!(
+                    public_key_package.group_public_key(),
+                    secp256k1::PublicKey::from_secret_key(
+                        &secp256k1::Secp256k1::new(),
+                        &secp256k1::SecretKey::from_slice(&secret_key).unwrap()
+                    )
+                    .serialize()
+                );
+
+                let public_key_package =
+                    PublicKeyPackage::try_from_bytes(&public_key_package.to_bytes()).unwrap();
+                for key_package in key_packages.iter() {
+                    assert!(key_package.is_valid_for(&public_key_package));
+                }
+
+                sign_with_random_signers(&public_key_package, &key_packages);
+            }
+        });
+}
+
+#[test]
+fn random_dkg_signing_sessions_produce_valid_signatures() {
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                let threshold = thread_rng().gen_range(2..5);
+                let participant_count = thread_rng().gen_range(threshold..7);
+                let participants: Vec<DkgParticipant> = (1..=participant_count)
+                    .map(|i| {
+                        DkgParticipant::try_from(
+                            i,
+                            threshold,
+                            participant_count,
+                            &random::<[u8; 32]>(),
+                        )
+                        .unwrap()
+                    })
+                    .collect();
+
+                let mut public_key_packages = Vec::new();
+                let mut key_packages = Vec::new();
+                for participant in participants.iter() {
+                    let identifier = participant.round_1_package()[0];
+                    let round_1_packages: Vec<Vec<u8>> = participants
+                        .iter()
+                        .filter(|p| p.round_1_package()[0] != identifier)
+                        .map(|p| p.round_1_package().into())
+                        .collect();
+
+                    let round_2_packages: Vec<[u8; 34]> = participants
+                        .iter()
+                        .filter(|p| p.round_1_package()[0] != identifier)
+                        .map(|p| p.round_2_package(identifier))
+                        .collect();
+
+                    // A tampered proof of knowledge is rejected.
+                    let mut tampered = round_1_packages[0].clone();
+                    tampered[40] ^= 1;
+                    assert!(participant.try_verify_round_1_package(&tampered).is_err());
+
+                    // A tampered share is rejected.
+                    let mut tampered_round_2_packages = round_2_packages.clone();
+                    tampered_round_2_packages[0][33] ^= 1;
+                    assert!(participant
+                        .try_finish(&round_1_packages, &tampered_round_2_packages)
+                        .is_err());
+
+                    let (public_key_package, key_package) = participant
+                        .try_finish(&round_1_packages, &round_2_packages)
+                        .unwrap();
+
+                    assert!(key_package.is_valid_for(&public_key_package));
+                    public_key_packages.push(public_key_package);
+                    key_packages.push(key_package);
+                }
+
+                // Every participant agrees on the public key package.
+                for package in public_key_packages.iter() {
+                    assert_eq!(package.to_bytes(), public_key_packages[0].to_bytes());
+                }
+
+                sign_with_random_signers(&public_key_packages[0], &key_packages);
+            }
+        });
+}
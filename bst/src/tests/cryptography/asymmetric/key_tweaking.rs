@@ -0,0 +1,184 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cryptography::asymmetric::ecc::{secp256r1, EllipticCurvePoint},
+    integers::BigUnsigned,
+    tests::PARALLELIZED_TEST_THREAD_COUNT,
+};
+use rand::{thread_rng, Rng};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const RANDOM_ITERATIONS: usize = 16;
+
+fn compressed(point: Option<EllipticCurvePoint>) -> [u8; 33] {
+    point.unwrap().try_serialize_compressed().unwrap()
+}
+
+fn to_be_bytes(integer: &BigUnsigned) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    assert!(integer.try_copy_be_bytes_to(&mut bytes));
+    bytes
+}
+
+#[test]
+fn secp256k1_tweaks_match_reference_implementation() {
+    let secp256k1 = secp256k1::Secp256k1::new();
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            let mut rng = thread_rng();
+            let mut context =
+                crate::cryptography::asymmetric::ecc::secp256k1::point_multiplication_context();
+            let (g_x, g_y) = (
+                crate::cryptography::asymmetric::ecc::secp256k1::g_x(),
+                crate::cryptography::asymmetric::ecc::secp256k1::g_y(),
+            );
+
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                let private_key = secp256k1::SecretKey::from_slice(&rng.gen::<[u8; 32]>()).unwrap();
+                let public_key = private_key.public_key(&secp256k1);
+                let tweak_bytes = secp256k1::SecretKey::from_slice(&rng.gen::<[u8; 32]>())
+                    .unwrap()
+                    .secret_bytes();
+                let tweak_scalar = secp256k1::Scalar::from_be_bytes(tweak_bytes).unwrap();
+                let tweak = BigUnsigned::from_be_bytes(&tweak_bytes);
+
+                // k + t (mod n)
+                let mut k = BigUnsigned::from_be_bytes(&private_key.secret_bytes());
+                assert!(context.try_tweak_add_scalar(&mut k, &tweak));
+                assert_eq!(
+                    to_be_bytes(&k),
+                    private_key.add_tweak(&tweak_scalar).unwrap().secret_bytes()
+                );
+
+                // k * t (mod n)
+                let mut k = BigUnsigned::from_be_bytes(&private_key.secret_bytes());
+                assert!(context.try_tweak_multiply_scalar(&mut k, &tweak));
+                assert_eq!(
+                    to_be_bytes(&k),
+                    private_key.mul_tweak(&tweak_scalar).unwrap().secret_bytes()
+                );
+
+                // P + tG
+                let mut point = context.try_decode_point(&public_key.serialize()).unwrap();
+                assert!(context.try_tweak_add_point(&mut point, g_x, g_y, &tweak));
+                assert_eq!(
+                    compressed(Some(point)),
+                    public_key
+                        .add_exp_tweak(&secp256k1, &tweak_scalar)
+                        .unwrap()
+                        .serialize()
+                );
+
+                // tP
+                let mut point = context.try_decode_point(&public_key.serialize()).unwrap();
+                let (x, y) = point.borrow_coordinates_mut();
+                assert_eq!(
+                    compressed(context.multiply_point(
+                        x.borrow_unsigned(),
+                        y.borrow_unsigned(),
+                        &tweak
+                    )),
+                    public_key
+                        .mul_tweak(&secp256k1, &tweak_scalar)
+                        .unwrap()
+                        .serialize()
+                );
+            }
+        });
+}
+
+#[test]
+fn secp256r1_tweaks_are_consistent() {
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            let mut rng = thread_rng();
+            let mut context = secp256r1::point_multiplication_context();
+            let (g_x, g_y) = (secp256r1::g_x(), secp256r1::g_y());
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                // Random values below 2^255 are always less than n.
+                let mut private_key_bytes = rng.gen::<[u8; 32]>();
+                private_key_bytes[0] &= 0x7F;
+                let mut tweak_bytes = rng.gen::<[u8; 32]>();
+                tweak_bytes[0] &= 0x7F;
+
+                let private_key = BigUnsigned::from_be_bytes(&private_key_bytes);
+                let tweak = BigUnsigned::from_be_bytes(&tweak_bytes);
+                let public_key = context.multiply_point(g_x, g_y, &private_key).unwrap();
+
+                // (k + t)G = P + tG
+                let mut tweaked_private_key = private_key.clone();
+                assert!(context.try_tweak_add_scalar(&mut tweaked_private_key, &tweak));
+                let mut tweaked_public_key = public_key.clone();
+                assert!(context.try_tweak_add_point(&mut tweaked_public_key, g_x, g_y, &tweak));
+                assert_eq!(
+                    compressed(context.multiply_point(g_x, g_y, &tweaked_private_key)),
+                    compressed(Some(tweaked_public_key))
+                );
+
+                // (k * t)G = tP
+                let mut tweaked_private_key = private_key.clone();
+                assert!(context.try_tweak_multiply_scalar(&mut tweaked_private_key, &tweak));
+                let mut point = public_key.clone();
+                let (x, y) = point.borrow_coordinates_mut();
+                assert_eq!(
+                    compressed(context.multiply_point(g_x, g_y, &tweaked_private_key)),
+                    compressed(context.multiply_point(
+                        x.borrow_unsigned(),
+                        y.borrow_unsigned(),
+                        &tweak
+                    ))
+                );
+            }
+        });
+}
+
+#[test]
+fn invalid_tweaks_are_rejected() {
+    let mut context = secp256r1::point_multiplication_context();
+    let (g_x, g_y, n) = (secp256r1::g_x(), secp256r1::g_y(), secp256r1::n());
+    let one = BigUnsigned::from_be_bytes(&[1]);
+    let mut n_minus_one = n.clone();
+    n_minus_one.subtract(&[1]);
+
+    // Tweaks must be < n.
+    let mut k = one.clone();
+    assert!(!context.try_tweak_add_scalar(&mut k, n));
+    let mut k = one.clone();
+    assert!(!context.try_tweak_multiply_scalar(&mut k, n));
+
+    // Multiplicative tweaks can't be zero.
+    let mut k = one.clone();
+    assert!(!context.try_tweak_multiply_scalar(&mut k, &BigUnsigned::with_byte_capacity(1)));
+
+    // 1 + (n - 1) = 0 (mod n), which isn't a valid private key.
+    let mut k = one.clone();
+    assert!(!context.try_tweak_add_scalar(&mut k, &n_minus_one));
+
+    // G + (n - 1)G = nG = infinity, which isn't a valid public key.
+    let mut point = context.multiply_point(g_x, g_y, &one).unwrap();
+    assert!(!context.try_tweak_add_point(&mut point, g_x, g_y, &n_minus_one));
+
+    // P + 0G = P.
+    let mut point = context.multiply_point(g_x, g_y, &one).unwrap();
+    assert!(context.try_tweak_add_point(&mut point, g_x, g_y, &BigUnsigned::with_byte_capacity(1)));
+    assert_eq!(
+        compressed(Some(point)),
+        compressed(context.multiply_point(g_x, g_y, &one))
+    );
+}
@@ -14,5 +14,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+mod adaptor;
+mod dleq;
+mod ecdsa;
+mod ed25519;
+mod frost;
+mod key_tweaking;
+mod musig2;
+mod point_encoding;
 mod point_operations;
+mod rsa;
 mod scalar_multiplication;
+mod schnorr;
+mod secp256k1_arithmetic;
+mod x25519;
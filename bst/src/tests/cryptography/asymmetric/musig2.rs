@@ -0,0 +1,540 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::{
+        musig2::{
+            sort_public_keys, try_aggregate_nonces, try_generate_nonce, KeyAggregationContext,
+            SecretNonce, SigningSession,
+        },
+        schnorr::{self, tagged_hash},
+        try_derive_serialized_public_key,
+    },
+    tests::PARALLELIZED_TEST_THREAD_COUNT,
+};
+use alloc::vec::Vec;
+use hex_literal::hex;
+use rand::{random, thread_rng, Rng};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const RANDOM_ITERATIONS: usize = 16;
+
+const X1: [u8; 33] = hex!("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9");
+const X2: [u8; 33] = hex!("03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659");
+const X3: [u8; 33] = hex!("023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66");
+
+fn test_key_aggregation(public_keys: &[[u8; 33]], expected: [u8; 32]) {
+    assert_eq!(
+        KeyAggregationContext::try_from(public_keys)
+            .unwrap()
+            .x_only_aggregate_public_key(),
+        expected
+    );
+}
+
+#[test]
+fn bip_327_key_aggregation_vectors() {
+    test_key_aggregation(
+        &[X1, X2, X3],
+        hex!("90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C"),
+    );
+    test_key_aggregation(
+        &[X3, X2, X1],
+        hex!("6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B"),
+    );
+    test_key_aggregation(
+        &[X1, X1, X1],
+        hex!("B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935"),
+    );
+    test_key_aggregation(
+        &[X1, X1, X2, X2],
+        hex!("69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E"),
+    );
+}
+
+#[test]
+fn invalid_public_keys_are_rejected() {
+    assert!(KeyAggregationContext::try_from(&[]).is_err());
+
+    let mut invalid = X1;
+    invalid[0] = 0x04;
+    assert!(KeyAggregationContext::try_from(&[X1, invalid]).is_err());
+}
+
+#[test]
+fn key_sorting_is_lexicographical() {
+    let mut public_keys = [X2, X3, X1];
+    sort_public_keys(&mut public_keys);
+    assert_eq!(public_keys, [X3, X1, X2]);
+}
+
+#[test]
+fn nonce_aggregation_of_opposite_nonces_is_encoded_as_zero() {
+    let (_, public_nonce) = try_generate_nonce(&random(), None, &X1, None, None, None).unwrap();
+    let mut negated_nonce = public_nonce;
+    negated_nonce[0] ^= 1;
+    negated_nonce[33] ^= 1;
+
+    assert_eq!(
+        try_aggregate_nonces(&[public_nonce, negated_nonce]).unwrap(),
+        [0u8; 66]
+    );
+}
+
+// The BIP 327 sign_verify_vectors.json and tweak_vectors.json inputs.
+const SIGNER_PRIVATE_KEY: [u8; 32] =
+    hex!("7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671");
+const SIGNER_PUBLIC_KEYS: [[u8; 33]; 4] = [
+    hex!("03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9"),
+    hex!("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+    hex!("02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA661"),
+    // Not a valid point.
+    hex!("020000000000000000000000000000000000000000000000000000000000000007"),
+];
+const TWEAK_PUBLIC_KEY: [u8; 33] =
+    hex!("02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659");
+const SIGNER_SECRET_NONCES: [[u8; 97]; 2] = [
+    hex!("508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F703935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9"),
+    // A zeroed secret nonce, as left behind after signing.
+    hex!("0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9"),
+];
+const PUBLIC_NONCES: [[u8; 66]; 5] = [
+    hex!("0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480"),
+    hex!("0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F817980279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798"),
+    hex!("032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE9303E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046"),
+    hex!("0237C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0387BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480"),
+    // The first half is not a valid point.
+    hex!("0200000000000000000000000000000000000000000000000000000000000000090287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480"),
+];
+const AGGREGATE_NONCES: [[u8; 66]; 5] = [
+    hex!("028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9"),
+    [0u8; 66],
+    // Invalid; the first half has a 0x04 tag, the second half isn't an X coordinate, and the second half exceeds the field size.
+    hex!("048465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9"),
+    hex!("028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61020000000000000000000000000000000000000000000000000000000000000009"),
+    hex!("028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD6102FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30"),
+];
+const MESSAGES: [&[u8]; 3] = [
+    &hex!("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF"),
+    &[],
+    &hex!("2626262626262626262626262626262626262626262626262626262626262626262626262626"),
+];
+const TWEAKS: [[u8; 32]; 5] = [
+    hex!("E8F791FF9225A2AF0102AFFF4A9A723D9612A682A25EBE79802B263CDFCD83BB"),
+    hex!("AE2EA797CC0FE72AC5B97B97F3C6957D7E4199A167A58EB08BCAFFDA70AC0455"),
+    hex!("F52ECBC565B3D8BEA2DFD5B75A4F457E54369809322E4120831626F290FA87E0"),
+    hex!("1969AD73CC177FA0B4FCED6DF1F7BF9907E665FDE9BA196A74FED0A3CF5AEF9D"),
+    // Equal to the curve order.
+    hex!("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"),
+];
+
+fn build_signing_session(
+    public_keys: &[[u8; 33]],
+    tweaks: &[(usize, bool)],
+    aggregate_nonce: &[u8; 66],
+    message: &[u8],
+) -> SigningSession {
+    let mut key_context = KeyAggregationContext::try_from(public_keys).unwrap();
+    for (tweak_index, is_x_only) in tweaks {
+        key_context
+            .try_apply_tweak(&TWEAKS[*tweak_index], *is_x_only)
+            .unwrap();
+    }
+
+    SigningSession::try_from(key_context, aggregate_nonce, message).unwrap()
+}
+
+fn select<T: Copy>(values: &[T], indices: &[usize]) -> Vec<T> {
+    indices.iter().map(|i| values[*i]).collect()
+}
+
+#[test]
+fn nonce_generation_with_the_bip_327_nonce_gen_inputs() {
+    // The inputs of nonce_gen_vectors.json; the expected public nonces were calculated with an independent implementation of NonceGen.
+    let private_key = [0x02; 32];
+    let public_key = hex!("024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766");
+    assert_eq!(
+        try_derive_serialized_public_key(&private_key).unwrap(),
+        public_key
+    );
+
+    // A 32 byte message, an empty message, and a 38 byte message; an empty message isn't the same as no message.
+    for (message, expected) in [
+        (&[0x01; 32][..], hex!("020A25526B002885996358B3EE5092F2F2F197393E59C06CDFC7A92A91931E20C3024C9FECC6795D5D761F96968D871A1F3BAC605F6ECC4E52E1EBF49E1FF9208AD0")),
+        (&[], hex!("0283D01F92F2B6A8540867AD8C7E725E420BBE27D8A949B67F1602219A3218EDE3034EDB05E0FCC6A1AF733DA418D47F863C874ED150B0F92821BF38B9C1835958E5")),
+        (&[0x26; 38], hex!("036C9E0851CCC4C93589C870EF67ECAD52CF883FBAFAA27C1D980199B33407D7D3023AFDDECC096613B4A8B3288FC7A2918F5014674E9F8A80A24572D68CA5506AA8")),
+    ] {
+        let (_, public_nonce) = try_generate_nonce(
+            &[0; 32],
+            Some(&private_key),
+            &public_key,
+            Some(&[0x07; 32]),
+            Some(message),
+            Some(&[0x08; 32]),
+        )
+        .unwrap();
+        assert_eq!(public_nonce, expected);
+    }
+
+    // Every optional input omitted.
+    let (_, public_nonce) = try_generate_nonce(&[0; 32], None, &X1, None, None, None).unwrap();
+    assert_eq!(
+        public_nonce,
+        hex!("02237A448A2848DD07B3C01C618EB926DFA2F5C294ADC68CBAADA183F016E1EB0E03CA63E5E8EB6DA599C5605FC9340BE1AFAAAFED278500844132B562DB2B1E1ED3")
+    );
+}
+
+#[test]
+fn bip_327_sign_verify_vectors() {
+    // Key indices, nonce indices, aggregate nonce index, message index, signer index, and the expected partial signature.
+    for (
+        key_indices,
+        nonce_indices,
+        aggregate_nonce_index,
+        message_index,
+        signer_index,
+        expected,
+    ) in [
+        (
+            &[0, 1, 2][..],
+            &[0, 1, 2][..],
+            0,
+            0,
+            0,
+            hex!("012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB"),
+        ),
+        (
+            &[1, 0, 2],
+            &[1, 0, 2],
+            0,
+            0,
+            1,
+            hex!("9FF2F7AAA856150CC8819254218D3ADEEB0535269051897724F9DB3789513A52"),
+        ),
+        (
+            &[1, 2, 0],
+            &[1, 2, 0],
+            0,
+            0,
+            2,
+            hex!("FA23C359F6FAC4E7796BB93BC9F0532A95468C539BA20FF86D7C76ED92227900"),
+        ),
+        // Both halves of the aggregate nonce are infinity.
+        (
+            &[0, 1],
+            &[0, 3],
+            1,
+            0,
+            0,
+            hex!("AE386064B26105404798F75DE2EB9AF5EDA5387B064B83D049CB7C5E08879531"),
+        ),
+        // An empty message, and a message which isn't 32 bytes.
+        (
+            &[0, 1, 2],
+            &[0, 1, 2],
+            0,
+            1,
+            0,
+            hex!("D7D63FFD644CCDA4E62BC2BC0B1D02DD32A1DC3030E155195810231D1037D82D"),
+        ),
+        (
+            &[0, 1, 2],
+            &[0, 1, 2],
+            0,
+            2,
+            0,
+            hex!("E184351828DA5094A97C79CABDAAA0BFB87608C32E8829A4DF5340A6F243B78C"),
+        ),
+    ] {
+        let public_keys = select(&SIGNER_PUBLIC_KEYS, key_indices);
+        let public_nonces = select(&PUBLIC_NONCES, nonce_indices);
+        assert_eq!(
+            try_aggregate_nonces(&public_nonces).unwrap(),
+            AGGREGATE_NONCES[aggregate_nonce_index]
+        );
+
+        let session = build_signing_session(
+            &public_keys,
+            &[],
+            &AGGREGATE_NONCES[aggregate_nonce_index],
+            MESSAGES[message_index],
+        );
+        let partial_signature = session
+            .try_sign(
+                &mut SecretNonce::from(SIGNER_SECRET_NONCES[0]),
+                &SIGNER_PRIVATE_KEY,
+            )
+            .unwrap();
+        assert_eq!(partial_signature, expected);
+        assert!(session.verify_partial_signature(
+            &expected,
+            &public_nonces[signer_index],
+            &public_keys[signer_index]
+        ));
+    }
+}
+
+#[test]
+fn bip_327_sign_error_vectors() {
+    let sign = |key_indices: &[usize], aggregate_nonce_index: usize, secret_nonce_index: usize| {
+        let public_keys = select(&SIGNER_PUBLIC_KEYS, key_indices);
+        let key_context = KeyAggregationContext::try_from(&public_keys)?;
+        SigningSession::try_from(
+            key_context,
+            &AGGREGATE_NONCES[aggregate_nonce_index],
+            MESSAGES[0],
+        )?
+        .try_sign(
+            &mut SecretNonce::from(SIGNER_SECRET_NONCES[secret_nonce_index]),
+            &SIGNER_PRIVATE_KEY,
+        )
+    };
+
+    // The signer's public key is not in the list of public keys.
+    assert!(sign(&[1, 2], 0, 0).is_err());
+    // Signer 2 provided an invalid public key.
+    assert!(sign(&[1, 0, 3], 0, 0).is_err());
+    // The aggregate nonce has a 0x04 tag in the first half, its second half isn't an X coordinate, and its second half exceeds the field size.
+    assert!(sign(&[1, 2, 0], 2, 0).is_err());
+    assert!(sign(&[1, 2, 0], 3, 0).is_err());
+    assert!(sign(&[1, 2, 0], 4, 0).is_err());
+    // The secret nonce is invalid, which may indicate nonce reuse.
+    assert!(sign(&[0, 1, 2], 0, 1).is_err());
+}
+
+#[test]
+fn bip_327_verify_fail_vectors() {
+    let public_keys = select(&SIGNER_PUBLIC_KEYS, &[0, 1, 2]);
+    let session = build_signing_session(&public_keys, &[], &AGGREGATE_NONCES[0], MESSAGES[0]);
+
+    // The negation of a valid partial signature, the wrong signer, and a partial signature exceeding the group size.
+    for (partial_signature, signer_index) in [
+        (
+            hex!("FED54434AD4CFE953FC527DC6A5E5BE8F6234907B7C187559557CE87A0541C46"),
+            0,
+        ),
+        (
+            hex!("012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB"),
+            1,
+        ),
+        (
+            hex!("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"),
+            0,
+        ),
+    ] {
+        assert!(!session.verify_partial_signature(
+            &partial_signature,
+            &PUBLIC_NONCES[signer_index],
+            &public_keys[signer_index]
+        ));
+    }
+}
+
+#[test]
+fn bip_327_verify_error_vectors() {
+    let partial_signature =
+        hex!("012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB");
+
+    // Signer 0's public nonce is invalid; it can't be aggregated, or used for verification.
+    let public_keys = select(&SIGNER_PUBLIC_KEYS, &[0, 1, 2]);
+    assert!(try_aggregate_nonces(&select(&PUBLIC_NONCES, &[4, 1, 2])).is_err());
+    let session = build_signing_session(&public_keys, &[], &AGGREGATE_NONCES[0], MESSAGES[0]);
+    assert!(!session.verify_partial_signature(
+        &partial_signature,
+        &PUBLIC_NONCES[4],
+        &public_keys[0]
+    ));
+
+    // Signer 0's public key is invalid.
+    assert!(KeyAggregationContext::try_from(&select(&SIGNER_PUBLIC_KEYS, &[3, 1, 2])).is_err());
+}
+
+#[test]
+fn bip_327_tweak_vectors() {
+    let public_keys = [
+        SIGNER_PUBLIC_KEYS[1],
+        TWEAK_PUBLIC_KEY,
+        SIGNER_PUBLIC_KEYS[0],
+    ];
+    let public_nonces = select(&PUBLIC_NONCES, &[1, 2, 0]);
+    assert_eq!(
+        try_aggregate_nonces(&public_nonces).unwrap(),
+        AGGREGATE_NONCES[0]
+    );
+
+    // Tweak indices and whether they're x-only, and the expected partial signature of signer 2.
+    for (tweaks, expected) in [
+        (
+            &[(0, true)][..],
+            hex!("E28A5C66E61E178C2BA19DB77B6CF9F7E2F0F56C17918CD13135E60CC848FE91"),
+        ),
+        (
+            &[(0, false)],
+            hex!("38B0767798252F21BF5702C48028B095428320F73A4B14DB1E25DE58543D2D2D"),
+        ),
+        (
+            &[(0, false), (1, true)],
+            hex!("408A0A21C4A0F5DACAF9646AD6EB6FECD7F7A11F03ED1F48DFFF2185BC2C2408"),
+        ),
+        (
+            &[(0, false), (1, false), (2, true), (3, true)],
+            hex!("45ABD206E61E3DF2EC9E264A6FEC8292141A633C28586388235541F9ADE75435"),
+        ),
+        (
+            &[(0, true), (1, false), (2, true), (3, false)],
+            hex!("B255FDCAC27B40C7CE7848E2D3B7BF5EA0ED756DA81565AC804CCCA3E1D5D239"),
+        ),
+    ] {
+        let session =
+            build_signing_session(&public_keys, tweaks, &AGGREGATE_NONCES[0], MESSAGES[0]);
+        let partial_signature = session
+            .try_sign(
+                &mut SecretNonce::from(SIGNER_SECRET_NONCES[0]),
+                &SIGNER_PRIVATE_KEY,
+            )
+            .unwrap();
+        assert_eq!(partial_signature, expected);
+        assert!(session.verify_partial_signature(&expected, &public_nonces[2], &public_keys[2]));
+    }
+
+    // The tweak is invalid, because it exceeds the group size.
+    let mut key_context = KeyAggregationContext::try_from(&public_keys).unwrap();
+    assert!(key_context.try_apply_tweak(&TWEAKS[4], false).is_err());
+}
+
+fn random_private_key() -> [u8; 32] {
+    secp256k1::SecretKey::from_slice(&random::<[u8; 32]>())
+        .unwrap()
+        .secret_bytes()
+}
+
+fn build_key_context(public_keys: &[[u8; 33]], taproot_tweak: bool) -> KeyAggregationContext {
+    let mut key_context = KeyAggregationContext::try_from(public_keys).unwrap();
+    if taproot_tweak {
+        let tweak = tagged_hash(b"TapTweak", &[&key_context.x_only_aggregate_public_key()]);
+        key_context.try_apply_tweak(&tweak, true).unwrap();
+    }
+
+    key_context
+}
+
+#[test]
+fn random_signing_sessions_produce_valid_schnorr_signatures() {
+    let secp = secp256k1::Secp256k1::new();
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                let signer_count = thread_rng().gen_range(1..5);
+                let taproot_tweak = random::<bool>();
+                let message = random::<[u8; 32]>();
+
+                let private_keys = (0..signer_count)
+                    .map(|_| random_private_key())
+                    .collect::<Vec<[u8; 32]>>();
+                let public_keys = private_keys
+                    .iter()
+                    .map(|k| try_derive_serialized_public_key(k).unwrap())
+                    .collect::<Vec<[u8; 33]>>();
+
+                let key_context = build_key_context(&public_keys, taproot_tweak);
+                let aggregate_public_key = key_context.x_only_aggregate_public_key();
+                if taproot_tweak {
+                    // The tweaked key should match libsecp256k1's x-only key tweaking.
+                    let internal_key = KeyAggregationContext::try_from(&public_keys)
+                        .unwrap()
+                        .x_only_aggregate_public_key();
+                    let tweak = tagged_hash(b"TapTweak", &[&internal_key]);
+                    let (expected, _) = secp256k1::XOnlyPublicKey::from_slice(&internal_key)
+                        .unwrap()
+                        .add_tweak(&secp, &secp256k1::Scalar::from_be_bytes(tweak).unwrap())
+                        .unwrap();
+                    assert_eq!(aggregate_public_key, expected.serialize());
+                }
+
+                let mut nonces = Vec::with_capacity(signer_count);
+                for i in 0..signer_count {
+                    nonces.push(
+                        try_generate_nonce(
+                            &random(),
+                            Some(&private_keys[i]),
+                            &public_keys[i],
+                            Some(&aggregate_public_key),
+                            Some(&message),
+                            None,
+                        )
+                        .unwrap(),
+                    );
+                }
+
+                let public_nonces = nonces.iter().map(|(_, p)| *p).collect::<Vec<[u8; 66]>>();
+                let aggregate_nonce = try_aggregate_nonces(&public_nonces).unwrap();
+                let session = SigningSession::try_from(
+                    build_key_context(&public_keys, taproot_tweak),
+                    &aggregate_nonce,
+                    &message,
+                )
+                .unwrap();
+
+                let mut partial_signatures = Vec::with_capacity(signer_count);
+                for i in 0..signer_count {
+                    let partial_signature = session
+                        .try_sign(&mut nonces[i].0, &private_keys[i])
+                        .unwrap();
+                    assert!(session.verify_partial_signature(
+                        &partial_signature,
+                        &public_nonces[i],
+                        &public_keys[i]
+                    ));
+
+                    // A secret nonce can't be used twice.
+                    assert!(session
+                        .try_sign(&mut nonces[i].0, &private_keys[i])
+                        .is_err());
+                    partial_signatures.push(partial_signature);
+                }
+
+                let signature = session
+                    .try_aggregate_partial_signatures(&partial_signatures)
+                    .unwrap();
+                assert!(schnorr::verify(&aggregate_public_key, &message, &signature));
+                assert!(secp
+                    .verify_schnorr(
+                        &secp256k1::schnorr::Signature::from_slice(&signature).unwrap(),
+                        &secp256k1::Message::from_digest(message),
+                        &secp256k1::XOnlyPublicKey::from_slice(&aggregate_public_key).unwrap(),
+                    )
+                    .is_ok());
+
+                // Tampered partial signatures fail verification, and produce an invalid signature.
+                partial_signatures[0][31] ^= 1;
+                assert!(!session.verify_partial_signature(
+                    &partial_signatures[0],
+                    &public_nonces[0],
+                    &public_keys[0]
+                ));
+
+                let signature = session
+                    .try_aggregate_partial_signatures(&partial_signatures)
+                    .unwrap();
+                assert!(!schnorr::verify(
+                    &aggregate_public_key,
+                    &message,
+                    &signature
+                ));
+            }
+        });
+}
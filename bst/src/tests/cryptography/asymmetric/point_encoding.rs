@@ -0,0 +1,145 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{cryptography::asymmetric::ecc::secp256r1, tests::PARALLELIZED_TEST_THREAD_COUNT};
+use hex_literal::hex;
+use rand::{thread_rng, Rng};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const RANDOM_ITERATIONS: usize = 40;
+
+// The secp256r1 generator point.
+const P256_G_UNCOMPRESSED: [u8; 65] = hex!("046b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c2964fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5");
+
+fn decode_secp256r1(bytes: &[u8]) -> Option<[u8; 65]> {
+    match secp256r1::point_multiplication_context().try_decode_point(bytes) {
+        Some(p) => p.try_serialize_uncompressed(),
+        None => None,
+    }
+}
+
+fn decode_secp256k1_generic(bytes: &[u8]) -> Option<[u8; 65]> {
+    match crate::cryptography::asymmetric::ecc::secp256k1::point_multiplication_context()
+        .try_decode_point(bytes)
+    {
+        Some(p) => p.try_serialize_uncompressed(),
+        None => None,
+    }
+}
+
+#[test]
+fn secp256r1_generator_encodings_decode() {
+    let x = &P256_G_UNCOMPRESSED[1..33];
+    let mut compressed = [0u8; 33];
+    compressed[0] = 0x03;
+    compressed[1..].copy_from_slice(x);
+
+    assert_eq!(
+        decode_secp256r1(&P256_G_UNCOMPRESSED),
+        Some(P256_G_UNCOMPRESSED)
+    );
+    assert_eq!(decode_secp256r1(&compressed), Some(P256_G_UNCOMPRESSED));
+
+    // The generator's Y coordinate is odd; the x-only encoding implies the other, even, Y coordinate.
+    let negated = decode_secp256r1(x).unwrap();
+    assert_eq!(negated[..33], P256_G_UNCOMPRESSED[..33]);
+    assert_eq!(negated[64] & 1, 0);
+    compressed[0] = 0x02;
+    assert_eq!(decode_secp256r1(&compressed), Some(negated));
+}
+
+#[test]
+fn secp256r1_invalid_encodings_are_rejected() {
+    // Wrong prefixes.
+    let mut uncompressed = P256_G_UNCOMPRESSED;
+    uncompressed[0] = 0x06;
+    assert_eq!(decode_secp256r1(&uncompressed), None);
+
+    let mut compressed = [0u8; 33];
+    compressed[1..].copy_from_slice(&P256_G_UNCOMPRESSED[1..33]);
+    compressed[0] = 0x04;
+    assert_eq!(decode_secp256r1(&compressed), None);
+
+    // A Y coordinate which doesn't match X.
+    uncompressed = P256_G_UNCOMPRESSED;
+    uncompressed[64] ^= 1;
+    assert_eq!(decode_secp256r1(&uncompressed), None);
+
+    // An X coordinate with no corresponding point (x^3 - 3x + b is not a square for x = 2).
+    let mut x = [0u8; 32];
+    x[31] = 2;
+    assert_eq!(decode_secp256r1(&x), None);
+
+    // An X coordinate >= p.
+    assert_eq!(decode_secp256r1(&[0xFF; 32]), None);
+
+    // Unsupported lengths, including the single zero byte sometimes used for infinity.
+    assert_eq!(decode_secp256r1(&[0x00]), None);
+    assert_eq!(decode_secp256r1(&P256_G_UNCOMPRESSED[..64]), None);
+}
+
+#[test]
+fn secp256k1_encodings_decode_identically() {
+    let secp256k1 = secp256k1::Secp256k1::new();
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            let mut rng = thread_rng();
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                let private_key = secp256k1::SecretKey::from_slice(&rng.gen::<[u8; 32]>()).unwrap();
+                let public_key = private_key.public_key(&secp256k1);
+                let compressed = public_key.serialize();
+                let uncompressed = public_key.serialize_uncompressed();
+                let (x_only, parity) = public_key.x_only_public_key();
+                let x_only = x_only.serialize();
+
+                // The x-only key corresponds to the public key with an even Y coordinate.
+                let even_y = if parity == secp256k1::Parity::Even {
+                    public_key
+                } else {
+                    public_key.negate(&secp256k1)
+                }
+                .serialize_uncompressed();
+
+                for (encoded, expected) in [
+                    (&compressed[..], uncompressed),
+                    (&uncompressed[..], uncompressed),
+                    (&x_only[..], even_y),
+                ] {
+                    assert_eq!(decode_secp256k1_generic(encoded), Some(expected));
+                    assert_eq!(
+                        crate::cryptography::asymmetric::ecc::secp256k1::try_decode_public_key(
+                            encoded
+                        )
+                        .unwrap()
+                        .try_serialize_uncompressed(),
+                        Some(expected)
+                    );
+                }
+
+                // Flipping a bit of the Y coordinate always produces a point off the curve.
+                let mut invalid = uncompressed;
+                invalid[1 + rng.gen_range(32..64)] ^= 1 << rng.gen_range(0..8);
+                assert_eq!(decode_secp256k1_generic(&invalid), None);
+                assert!(
+                    crate::cryptography::asymmetric::ecc::secp256k1::try_decode_public_key(
+                        &invalid
+                    )
+                    .is_none()
+                );
+            }
+        });
+}
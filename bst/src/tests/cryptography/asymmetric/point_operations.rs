@@ -20,17 +20,17 @@ use crate::{
     integers::BigUnsigned,
 };
 
-static mut P1: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+static P1: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     GlobalRuntimeImmutable::from(|| BigUnsigned::from_be_bytes(&2551u16.to_be_bytes()));
 
-static mut A1: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+static A1: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     GlobalRuntimeImmutable::from(|| BigUnsigned::from_be_bytes(&5u16.to_be_bytes()));
 // B1 = 1326
 
-static mut P2: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+static P2: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     GlobalRuntimeImmutable::from(|| BigUnsigned::from_be_bytes(&97u16.to_be_bytes()));
 
-static mut A2: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
+static A2: GlobalRuntimeImmutable<BigUnsigned, fn() -> BigUnsigned> =
     GlobalRuntimeImmutable::from(|| BigUnsigned::from_be_bytes(&2u16.to_be_bytes()));
 // B2 = 3
 
@@ -433,19 +433,19 @@ fn point_addition_context_2() -> EllipticCurvePointAdditionContext {
 }
 
 fn p1() -> &'static BigUnsigned {
-    unsafe { P1.value() }
+    P1.value()
 }
 
 fn a1() -> &'static BigUnsigned {
-    unsafe { A1.value() }
+    A1.value()
 }
 
 fn p2() -> &'static BigUnsigned {
-    unsafe { P2.value() }
+    P2.value()
 }
 
 fn a2() -> &'static BigUnsigned {
-    unsafe { A2.value() }
+    A2.value()
 }
 
 fn point(x: u16, y: u16) -> EllipticCurvePoint {
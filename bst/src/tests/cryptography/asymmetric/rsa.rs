@@ -0,0 +1,204 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cryptography::asymmetric::rsa::{
+        self, primes::is_probable_prime, RsaPrivateKey, RsaPublicKey, SignatureScheme,
+    },
+    integers::BigUnsigned,
+};
+use hex_literal::hex;
+use rand::{random, thread_rng, Rng};
+
+const MESSAGE: &[u8] = b"Bootable Security Tools";
+
+// A 1024 bit key, and signatures and ciphertext of MESSAGE, produced with OpenSSL.
+const PRIVATE_KEY: [u8; 608] = hex!("3082025C02010002818100FD0A3D7F2364BCBAC38F89B68301583783BEA236B5CEB0768D528A413DF568E421543BC5E01708698195B367A10FCBEE6AD95FD4CFF1B708A2E7C385AF06E02A89A17137F4B263D41A36AF54B74D14C7C57BB087B91E91A4B7850DC47621A41969637D64C89878C4CC463BA20DEA70ADD4AF4DD345419B23A11D3A84D5B8C971020301000102818100A79C67DDD680FCEEAD695FEC305CECA561341E592516B316E447BF62B5FAE02EF4D0CA03298BB83BC2E5797FF454F55D891352023277C729065784D1017E096F72AA4CE779080F51A7300A7485BCA6570D8632C043A0747A9697266A5B681FD11B5D3A69153DDE473BD9581783FB5108D9F3C546563EF6A8D5068A1EB95DBC9D024100FF237482C5D3B0E20DC0F7A8FDBD562797F82EBF2BD38BF3ADFA1B31060EE774ECF39F98F46DBEDCFA7C5570B02F6781E48563063506EF0E743D6985883FAC53024100FDE4F89C4F34E3A84D5A8421ECF73892F50D9C2D1B80FAF3DFF6B4A5B2427907D87BB28572DBD7DCC177C9BA6EBA23AD9F314C8E979403A0700D57611D86DAAB02405AE314742968B9D02D3A1A7F56B485E660DA863C8FAE3262C20D20769C43DEEBE624FB2B8F9730B0A3827B25437DFF8F640AA835E3D9EE7AE5A9A2D770ABCE050240549D0761886016F2D2FCB4865DE3B21DFFBF0E6A55FD31252F6197A894F4E44302198F503C0C1A2F7EE67DFB3CC599F855D267322A3FF0C97A2E680D7C7AA1CB02406DAA701D506220CFAE63A3C352F65CBCC36265BD45514EF36B6F2CFFAD73957FC8B2AF42448476F4733B410778400B00AEE266957C62469EED54545FD6EE42A9");
+const PUBLIC_KEY: [u8; 140] = hex!("30818902818100FD0A3D7F2364BCBAC38F89B68301583783BEA236B5CEB0768D528A413DF568E421543BC5E01708698195B367A10FCBEE6AD95FD4CFF1B708A2E7C385AF06E02A89A17137F4B263D41A36AF54B74D14C7C57BB087B91E91A4B7850DC47621A41969637D64C89878C4CC463BA20DEA70ADD4AF4DD345419B23A11D3A84D5B8C9710203010001");
+const SUBJECT_PUBLIC_KEY_INFO: [u8; 162] = hex!("30819F300D06092A864886F70D010101050003818D0030818902818100FD0A3D7F2364BCBAC38F89B68301583783BEA236B5CEB0768D528A413DF568E421543BC5E01708698195B367A10FCBEE6AD95FD4CFF1B708A2E7C385AF06E02A89A17137F4B263D41A36AF54B74D14C7C57BB087B91E91A4B7850DC47621A41969637D64C89878C4CC463BA20DEA70ADD4AF4DD345419B23A11D3A84D5B8C9710203010001");
+const PKCS1_V1_5_SIGNATURE: [u8; 128] = hex!("87B48349C6A14D197C5B335BAC1FF55537C8027B8AC9554CE0E99FF066185A8784DF2881AC2DA2826E954CD8525B42FB9C8730453E80C791524A16E08FCC209F78C798AAC9256370EC11D6B6577ABE7D46D39A724ED64096372AC01165C27C7AC871C1DE992DED267C7B1F816CE4365CD8A08F39934966B3C55122533DF5E333");
+const PSS_SIGNATURE: [u8; 128] = hex!("DF62CD3A62EFEEB087201E834C499BB5BC000A6DE3DABB6400D78302CFE3EDDCA6FA8CF04474E73DAA92D6D64D8390C890D572615B4CF64E92844FBBF8645B69630C24B724090C2A078EF5C3C79FD212FB32056EDE3E1C0B8663E9894EB85B451AB306F3E151F41205E3F8410BB9E2FBF04C28F8B5B57D012E5DD0B3D30D0685");
+const OAEP_CIPHERTEXT: [u8; 128] = hex!("BF6EC0AB702CE8126266AB43EBB4FA905A80118AEFDF70C47F02C8ECFC434EB0BB0CD770779E4F0D4A8893431BA4104A91F515C9D512D9B9C4849E56164C0B27C2B416C4F6DDC288B8BAE5A486AAD0AFF9BC32CED93DFF38BAAA42C52856F5B9285A734DAF9B2A2C129EB9F728C14B2511C75F1EE7CFE14DD8E5B969AF7052B8");
+
+fn assert_round_trips(private_key: &RsaPrivateKey) {
+    for scheme in [SignatureScheme::Pss, SignatureScheme::Pkcs1V1_5] {
+        let mut signature = rsa::try_sign(private_key, scheme, MESSAGE).unwrap();
+        assert!(rsa::verify(
+            private_key.public_key(),
+            scheme,
+            MESSAGE,
+            &signature
+        ));
+        assert!(!rsa::verify(
+            private_key.public_key(),
+            scheme,
+            b"Another message",
+            &signature
+        ));
+
+        // Tamper with the signature.
+        let index = thread_rng().gen_range(0..signature.len());
+        signature[index] ^= 1 << thread_rng().gen_range(0..8);
+        assert!(!rsa::verify(
+            private_key.public_key(),
+            scheme,
+            MESSAGE,
+            &signature
+        ));
+    }
+
+    let ciphertext = rsa::try_encrypt(private_key.public_key(), MESSAGE, &random()).unwrap();
+    assert_eq!(rsa::try_decrypt(private_key, &ciphertext).unwrap(), MESSAGE);
+}
+
+#[test]
+fn keys_round_trip_through_der() {
+    let private_key = RsaPrivateKey::try_from_der(&PRIVATE_KEY).unwrap();
+    assert_eq!(private_key.to_der(), PRIVATE_KEY);
+    assert_eq!(private_key.public_key().to_der(), PUBLIC_KEY);
+    assert_eq!(private_key.public_key().modulus_bits(), 1024);
+
+    // SubjectPublicKeyInfo-wrapped keys are unwrapped.
+    assert_eq!(
+        RsaPublicKey::try_from_der(&SUBJECT_PUBLIC_KEY_INFO)
+            .unwrap()
+            .to_der(),
+        PUBLIC_KEY
+    );
+}
+
+#[test]
+fn invalid_keys_are_rejected() {
+    assert!(RsaPublicKey::try_from_der(&PUBLIC_KEY[..PUBLIC_KEY.len() - 1]).is_err());
+    assert!(RsaPrivateKey::try_from_der(&PRIVATE_KEY[1..]).is_err());
+
+    // Change the final byte of the coefficient.
+    let mut private_key = PRIVATE_KEY;
+    private_key[PRIVATE_KEY.len() - 1] ^= 1;
+    assert!(RsaPrivateKey::try_from_der(&private_key).is_err());
+}
+
+#[test]
+fn pkcs1_v1_5_signatures_match_openssl() {
+    let private_key = RsaPrivateKey::try_from_der(&PRIVATE_KEY).unwrap();
+    assert_eq!(
+        rsa::try_sign(&private_key, SignatureScheme::Pkcs1V1_5, MESSAGE).unwrap(),
+        PKCS1_V1_5_SIGNATURE
+    );
+    assert!(rsa::verify(
+        private_key.public_key(),
+        SignatureScheme::Pkcs1V1_5,
+        MESSAGE,
+        &PKCS1_V1_5_SIGNATURE
+    ));
+}
+
+#[test]
+fn openssl_pss_signatures_are_valid() {
+    let public_key = RsaPublicKey::try_from_der(&PUBLIC_KEY).unwrap();
+    assert!(rsa::verify(
+        &public_key,
+        SignatureScheme::Pss,
+        MESSAGE,
+        &PSS_SIGNATURE
+    ));
+    assert!(!rsa::verify(
+        &public_key,
+        SignatureScheme::Pkcs1V1_5,
+        MESSAGE,
+        &PSS_SIGNATURE
+    ));
+}
+
+#[test]
+fn openssl_oaep_ciphertexts_are_decrypted() {
+    let private_key = RsaPrivateKey::try_from_der(&PRIVATE_KEY).unwrap();
+    assert_eq!(
+        rsa::try_decrypt(&private_key, &OAEP_CIPHERTEXT).unwrap(),
+        MESSAGE
+    );
+
+    let mut ciphertext = OAEP_CIPHERTEXT;
+    ciphertext[64] ^= 1;
+    assert!(rsa::try_decrypt(&private_key, &ciphertext).is_err());
+}
+
+#[test]
+fn messages_too_long_for_oaep_are_rejected() {
+    // A 1024 bit key can encrypt at most 128 - 2 * 32 - 2 = 62 bytes.
+    let public_key = RsaPublicKey::try_from_der(&PUBLIC_KEY).unwrap();
+    assert!(rsa::try_encrypt(&public_key, &[0u8; 62], &random()).is_ok());
+    assert!(rsa::try_encrypt(&public_key, &[0u8; 63], &random()).is_err());
+}
+
+#[test]
+fn signatures_and_ciphertexts_round_trip() {
+    assert_round_trips(&RsaPrivateKey::try_from_der(&PRIVATE_KEY).unwrap());
+}
+
+#[test]
+fn generated_keys_are_valid_and_deterministic() {
+    let seed = random::<[u8; 32]>();
+    let private_key = RsaPrivateKey::try_generate(1024, &seed).unwrap();
+    assert_eq!(private_key.public_key().modulus_bits(), 1024);
+    assert_round_trips(&private_key);
+
+    let der = private_key.to_der();
+    assert_eq!(RsaPrivateKey::try_from_der(&der).unwrap().to_der(), der);
+    assert_eq!(
+        RsaPrivateKey::try_generate(1024, &seed).unwrap().to_der(),
+        der
+    );
+}
+
+#[test]
+fn invalid_generation_parameters_are_rejected() {
+    assert!(RsaPrivateKey::try_generate(1024, &[0u8; 31]).is_err());
+    assert!(RsaPrivateKey::try_generate(1008, &[0u8; 32]).is_err());
+    assert!(RsaPrivateKey::try_generate(1032, &[0u8; 32]).is_err());
+}
+
+#[test]
+fn miller_rabin_identifies_primes_and_composites() {
+    let seed = random::<[u8; 32]>();
+    let is_prime =
+        |be_bytes: &[u8]| is_probable_prime(&BigUnsigned::from_be_bytes(be_bytes), &seed, 64);
+
+    for prime in [2u64, 3, 997, 1009, 65537, 2147483647] {
+        assert!(is_prime(&prime.to_be_bytes()));
+    }
+
+    // Mersenne primes.
+    assert!(is_prime(&hex!("7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF")));
+    assert!(is_prime(&hex!("01FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF")));
+
+    // Carmichael numbers, and strong pseudoprimes to many small bases.
+    for composite in [
+        0u64,
+        1,
+        4,
+        561,
+        41041,
+        1009 * 1013,
+        3215031751,
+        3825123056546413051,
+    ] {
+        assert!(!is_prime(&composite.to_be_bytes()));
+    }
+
+    // An RSA modulus.
+    assert!(!is_prime(&PUBLIC_KEY[7..135]));
+}
@@ -15,10 +15,12 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-    cryptography::asymmetric::ecc::COMPRESSED_Y_IS_EVEN_IDENTIFIER, integers::BigUnsigned,
+    cryptography::asymmetric::ecc::{secp256r1, COMPRESSED_Y_IS_EVEN_IDENTIFIER},
+    integers::BigUnsigned,
     tests::PARALLELIZED_TEST_THREAD_COUNT,
 };
 use core::cmp::Ordering;
+use hex_literal::hex;
 use rand::{random, thread_rng, Rng};
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 
@@ -153,3 +155,88 @@ fn secp256k1_derive_pubkey_random_privkey() {
         }
     });
 }
+
+#[test]
+fn secp256r1_derive_pubkey_zero_and_n_privkey() {
+    let mut context = secp256r1::point_multiplication_context();
+    for private_key in [BigUnsigned::with_byte_capacity(1), secp256r1::n().clone()] {
+        assert_eq!(
+            context.multiply_point(secp256r1::g_x(), secp256r1::g_y(), &private_key),
+            None
+        );
+    }
+}
+
+#[test]
+fn secp256r1_derive_pubkey_known_privkeys() {
+    // (private key, public key x, public key y)
+    let vectors: [([u8; 32], [u8; 32], [u8; 32]); 7] = [
+        (
+            hex!("0000000000000000000000000000000000000000000000000000000000000001"),
+            hex!("6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296"),
+            hex!("4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5"),
+        ),
+        (
+            hex!("0000000000000000000000000000000000000000000000000000000000000002"),
+            hex!("7CF27B188D034F7E8A52380304B51AC3C08969E277F21B35A60B48FC47669978"),
+            hex!("07775510DB8ED040293D9AC69F7430DBBA7DADE63CE982299E04B79D227873D1"),
+        ),
+        (
+            hex!("0000000000000000000000000000000000000000000000000000000000000003"),
+            hex!("5ECBE4D1A6330A44C8F7EF951D4BF165E6C6B721EFADA985FB41661BC6E7FD6C"),
+            hex!("8734640C4998FF7E374B06CE1A64A2ECD82AB036384FB83D9A79B127A27D5032"),
+        ),
+        (
+            hex!("FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632550"),
+            hex!("6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296"),
+            hex!("B01CBD1C01E58065711814B583F061E9D431CCA994CEA1313449BF97C840AE0A"),
+        ),
+        (
+            hex!("750B79840A35E888CEA8684B60033CD65DB233956EA88F4B4F72FD3F7D254DB9"),
+            hex!("B0A1F58FE3E991C56FB54053DED00CBA1F1CAEF91B1009F2B37DCF58BCA51935"),
+            hex!("9D240905A3E6EB0B75BB669C3A2238D24487258E4DCA0196CA1917A60AB603FC"),
+        ),
+        (
+            hex!("AACDABBB49C9C6072C54A01283037CADFDE8EC5E3E1544596EBBEC4CC598E828"),
+            hex!("46ECCBE9FA9B957F1A338C341E529480453F04F742BF4AF271C2819731389A78"),
+            hex!("6212760B85334C16CA8D8D36031B726385A7B2E872156D0FB76406A49606B397"),
+        ),
+        (
+            hex!("D2AEEAF914C7D3FD9A1AC067541B8EE6F0969FE15284B2BF8E56916A518A4445"),
+            hex!("4E44C477E8811F72009C089EA65F3C681787DB692D440F9E31B9001DD4AE7841"),
+            hex!("AF182E2B55132AD5E5DBD9F7B1CBDAB3792BA61EF84486C4385FC5FE38EFEC76"),
+        ),
+    ];
+
+    let mut context = secp256r1::point_multiplication_context();
+    let mut decompression_buffer_x = BigUnsigned::with_byte_capacity(32);
+    let mut decompression_buffer_y = BigUnsigned::with_byte_capacity(32);
+    for (private_key, x, y) in vectors {
+        let point = context
+            .multiply_point(
+                secp256r1::g_x(),
+                secp256r1::g_y(),
+                &BigUnsigned::from_be_bytes(&private_key),
+            )
+            .unwrap();
+
+        let serialized = secp256r1::serialized_public_key_bytes(point).unwrap();
+
+        // Assert public key is as expected.
+        assert_eq!(serialized[1..], x);
+        assert_eq!(
+            serialized[0] == COMPRESSED_Y_IS_EVEN_IDENTIFIER,
+            y[31] & 1 == 0
+        );
+
+        // Decompress the point and assert the decompressed Y coordinate is the same as the original point.
+        decompression_buffer_x.copy_be_bytes_from(&x);
+        context.calculate_y_from_x(
+            serialized[0] == COMPRESSED_Y_IS_EVEN_IDENTIFIER,
+            &decompression_buffer_x,
+            &mut decompression_buffer_y,
+        );
+
+        assert_eq!(decompression_buffer_y, BigUnsigned::from_be_bytes(&y));
+    }
+}
@@ -0,0 +1,132 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::schnorr::{
+        try_derive_x_only_public_key, try_sign, verify,
+    },
+    tests::PARALLELIZED_TEST_THREAD_COUNT,
+};
+use hex_literal::hex;
+use rand::{random, thread_rng, Rng};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const RANDOM_ITERATIONS: usize = 40;
+
+fn test_vector(
+    private_key: [u8; 32],
+    public_key: [u8; 32],
+    auxiliary_random: [u8; 32],
+    message: &[u8],
+    signature: [u8; 64],
+) {
+    assert_eq!(
+        try_derive_x_only_public_key(&private_key).unwrap(),
+        public_key
+    );
+    assert_eq!(
+        try_sign(&private_key, message, &auxiliary_random).unwrap(),
+        signature
+    );
+    assert!(verify(&public_key, message, &signature));
+}
+
+#[test]
+fn bip_340_test_vector_0() {
+    test_vector(
+        hex!("0000000000000000000000000000000000000000000000000000000000000003"),
+        hex!("F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+        [0u8; 32],
+        &[0u8; 32],
+        hex!("E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0"),
+    );
+}
+
+#[test]
+fn bip_340_test_vector_1() {
+    test_vector(
+        hex!("B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF"),
+        hex!("DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659"),
+        hex!("0000000000000000000000000000000000000000000000000000000000000001"),
+        &hex!("243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89"),
+        hex!("6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A"),
+    );
+}
+
+#[test]
+fn invalid_private_keys_are_rejected() {
+    assert!(try_derive_x_only_public_key(&[0u8; 32]).is_none());
+    assert!(try_sign(&[0u8; 32], &[], &[0u8; 32]).is_none());
+    assert!(try_sign(
+        &hex!("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"),
+        &[],
+        &[0u8; 32]
+    )
+    .is_none());
+}
+
+#[test]
+fn out_of_range_signature_components_are_rejected() {
+    let public_key = hex!("F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9");
+    let mut signature = hex!("E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0");
+    assert!(verify(&public_key, &[0u8; 32], &signature));
+
+    // s = n
+    signature[32..].copy_from_slice(&hex!(
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141"
+    ));
+    assert!(!verify(&public_key, &[0u8; 32], &signature));
+
+    // r = p
+    signature[..32].copy_from_slice(&hex!(
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F"
+    ));
+    assert!(!verify(&public_key, &[0u8; 32], &signature));
+}
+
+#[test]
+fn random_signatures_match_libsecp256k1() {
+    let secp = secp256k1::Secp256k1::new();
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                let secret_key = secp256k1::SecretKey::from_slice(&random::<[u8; 32]>()).unwrap();
+                let key_pair = secp256k1::Keypair::from_secret_key(&secp, &secret_key);
+                let private_key = secret_key.secret_bytes();
+                let auxiliary_random = random::<[u8; 32]>();
+                let message = random::<[u8; 32]>();
+
+                let public_key = try_derive_x_only_public_key(&private_key).unwrap();
+                assert_eq!(public_key, key_pair.x_only_public_key().0.serialize());
+
+                let mut signature = try_sign(&private_key, &message, &auxiliary_random).unwrap();
+                let expected = secp.sign_schnorr_with_aux_rand(
+                    &secp256k1::Message::from_digest(message),
+                    &key_pair,
+                    &auxiliary_random,
+                );
+
+                assert_eq!(signature, *expected.as_ref());
+                assert!(verify(&public_key, &message, &signature));
+
+                // Tamper with the signature.
+                let index = thread_rng().gen_range(0..64);
+                signature[index] ^= 1 << thread_rng().gen_range(0..8);
+                assert!(!verify(&public_key, &message, &signature));
+            }
+        });
+}
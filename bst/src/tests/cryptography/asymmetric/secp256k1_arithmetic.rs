@@ -0,0 +1,313 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    cryptography::asymmetric::ecc::secp256k1::{FieldElement, ProjectivePoint, Scalar},
+    integers::BigUnsigned,
+    tests::PARALLELIZED_TEST_THREAD_COUNT,
+};
+use hex_literal::hex;
+use rand::random;
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+const RANDOM_ITERATIONS: usize = 200;
+
+const P: [u8; 32] = hex!("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F");
+const N: [u8; 32] = hex!("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141");
+
+fn for_random_iterations<F: Fn() + Sync>(f: F) {
+    (0..PARALLELIZED_TEST_THREAD_COUNT)
+        .into_par_iter()
+        .for_each(|_| {
+            for _ in 0..RANDOM_ITERATIONS / PARALLELIZED_TEST_THREAD_COUNT {
+                f()
+            }
+        });
+}
+
+fn random_field_element() -> FieldElement {
+    loop {
+        match FieldElement::from_be_bytes(&random::<[u8; 32]>()) {
+            Some(f) => return f,
+            None => continue,
+        }
+    }
+}
+
+fn random_scalar() -> Scalar {
+    loop {
+        match Scalar::from_be_bytes(&random::<[u8; 32]>()) {
+            Some(s) => {
+                if !s.is_zero() {
+                    return s;
+                }
+            }
+            None => continue,
+        }
+    }
+}
+
+fn reference_operation(a: &[u8; 32], b: &[u8; 32], modulus: &[u8; 32], multiply: bool) -> [u8; 32] {
+    let mut result = BigUnsigned::from_be_bytes(a);
+    let operand = BigUnsigned::from_be_bytes(b);
+    if multiply {
+        result.multiply_big_unsigned(&operand);
+    } else {
+        result.add_big_unsigned(&operand);
+    }
+
+    result.modulo_big_unsigned(&BigUnsigned::from_be_bytes(modulus));
+    let mut bytes = [0u8; 32];
+    assert!(result.try_copy_be_bytes_to(&mut bytes[32 - result.byte_count()..]));
+    bytes
+}
+
+#[test]
+fn field_elements_greater_than_or_equal_to_p_are_rejected() {
+    assert!(FieldElement::from_be_bytes(&P).is_none());
+    assert!(FieldElement::from_be_bytes(&[0xFF; 32]).is_none());
+
+    let mut p_minus_one = P;
+    p_minus_one[31] -= 1;
+    assert_eq!(
+        FieldElement::from_be_bytes(&p_minus_one)
+            .unwrap()
+            .to_be_bytes(),
+        p_minus_one
+    );
+}
+
+#[test]
+fn field_element_arithmetic_matches_big_unsigned_arithmetic() {
+    for_random_iterations(|| {
+        let a = random_field_element();
+        let b = random_field_element();
+        let (a_bytes, b_bytes) = (a.to_be_bytes(), b.to_be_bytes());
+
+        assert_eq!(
+            a.add(&b).to_be_bytes(),
+            reference_operation(&a_bytes, &b_bytes, &P, false)
+        );
+
+        assert_eq!(
+            a.multiply(&b).to_be_bytes(),
+            reference_operation(&a_bytes, &b_bytes, &P, true)
+        );
+
+        assert!(a.subtract(&b).add(&b).equals(&a));
+        assert!(a.add(&a.negate()).is_zero());
+    });
+}
+
+#[test]
+fn field_element_inversion_and_square_roots() {
+    assert!(FieldElement::ZERO.invert().is_zero());
+    for_random_iterations(|| {
+        let a = random_field_element();
+        assert!(a.multiply(&a.invert()).equals(&FieldElement::ONE));
+
+        let square = a.square();
+        let root = square.try_sqrt().unwrap();
+        assert!(root.equals(&a) || root.equals(&a.negate()));
+    });
+}
+
+#[test]
+fn scalars_greater_than_or_equal_to_n_are_rejected_or_reduced() {
+    assert!(Scalar::from_be_bytes(&N).is_none());
+    assert!(Scalar::from_be_bytes_reduced(&N).is_zero());
+    assert!(Scalar::try_from_be_slice(&[0x01; 33]).is_none());
+    assert!(Scalar::try_from_be_slice(&[0x00, 0x01])
+        .unwrap()
+        .equals(&Scalar::ONE));
+}
+
+#[test]
+fn scalar_arithmetic_matches_big_unsigned_arithmetic() {
+    for_random_iterations(|| {
+        let a = random_scalar();
+        let b = random_scalar();
+        let (a_bytes, b_bytes) = (a.to_be_bytes(), b.to_be_bytes());
+
+        assert_eq!(
+            a.add(&b).to_be_bytes(),
+            reference_operation(&a_bytes, &b_bytes, &N, false)
+        );
+
+        assert_eq!(
+            a.multiply(&b).to_be_bytes(),
+            reference_operation(&a_bytes, &b_bytes, &N, true)
+        );
+
+        assert!(a.subtract(&b).add(&b).equals(&a));
+        assert!(a.multiply(&a.invert()).equals(&Scalar::ONE));
+    });
+}
+
+#[test]
+fn generator_multiplication_matches_libsecp256k1() {
+    let context = secp256k1::Secp256k1::new();
+    for_random_iterations(|| {
+        let private_key = random_scalar();
+        let expected = secp256k1::SecretKey::from_slice(&private_key.to_be_bytes())
+            .unwrap()
+            .public_key(&context);
+
+        let point = ProjectivePoint::multiply_generator(&private_key);
+        assert_eq!(
+            point.try_serialize_compressed().unwrap(),
+            expected.serialize()
+        );
+        assert_eq!(
+            point.try_serialize_uncompressed().unwrap(),
+            expected.serialize_uncompressed()
+        );
+    });
+}
+
+#[test]
+fn point_multiplication_and_addition_match_libsecp256k1() {
+    let context = secp256k1::Secp256k1::new();
+    for_random_iterations(|| {
+        let (a, b) = (random_scalar(), random_scalar());
+        let a_point = ProjectivePoint::multiply_generator(&a);
+        let b_point = ProjectivePoint::multiply_generator(&b);
+        let a_key =
+            secp256k1::PublicKey::from_slice(&a_point.try_serialize_compressed().unwrap()).unwrap();
+        let b_key =
+            secp256k1::PublicKey::from_slice(&b_point.try_serialize_compressed().unwrap()).unwrap();
+
+        // A + B
+        assert_eq!(
+            a_point.add(&b_point).try_serialize_compressed().unwrap(),
+            a_key.combine(&b_key).unwrap().serialize()
+        );
+
+        // A * b
+        assert_eq!(
+            a_point.multiply(&b).try_serialize_compressed().unwrap(),
+            a_key
+                .mul_tweak(
+                    &context,
+                    &secp256k1::Scalar::from_be_bytes(b.to_be_bytes()).unwrap()
+                )
+                .unwrap()
+                .serialize()
+        );
+
+        // (a * G) * b = (a * b) * G
+        assert!(a_point
+            .multiply(&b)
+            .equals(&ProjectivePoint::multiply_generator(&a.multiply(&b))));
+
+        // A + A = 2A
+        assert!(a_point.add(&a_point).equals(&a_point.double()));
+    });
+}
+
+#[test]
+fn point_addition_edge_cases() {
+    let g = ProjectivePoint::GENERATOR;
+    let infinity = ProjectivePoint::INFINITY;
+
+    assert!(g.add(&g.negate()).is_infinity());
+    assert!(g.add(&infinity).equals(&g));
+    assert!(infinity.add(&g).equals(&g));
+    assert!(infinity.add(&infinity).is_infinity());
+    assert!(infinity.double().is_infinity());
+    assert!(!infinity.equals(&g));
+    assert!(ProjectivePoint::multiply_generator(&Scalar::ZERO).is_infinity());
+    assert!(ProjectivePoint::multiply_generator(&Scalar::ONE).equals(&g));
+    assert!(ProjectivePoint::multiply_generator(&Scalar::ONE.negate()).equals(&g.negate()));
+    assert!(infinity.try_serialize_compressed().is_none());
+}
+
+#[test]
+fn point_deserialization_round_trips_and_rejects_invalid_points() {
+    for_random_iterations(|| {
+        let point = ProjectivePoint::multiply_generator(&random_scalar());
+        let compressed = point.try_serialize_compressed().unwrap();
+        let uncompressed = point.try_serialize_uncompressed().unwrap();
+
+        assert!(ProjectivePoint::try_from_serialized(&compressed)
+            .unwrap()
+            .equals(&point));
+        assert!(ProjectivePoint::try_from_serialized(&uncompressed)
+            .unwrap()
+            .equals(&point));
+
+        // Flip the y coordinate's least significant bit; the point is no longer on the curve.
+        let mut invalid = uncompressed;
+        invalid[64] ^= 1;
+        assert!(ProjectivePoint::try_from_serialized(&invalid).is_none());
+    });
+
+    // x = 5 has no corresponding y on secp256k1.
+    let mut no_y = [0u8; 33];
+    no_y[0] = 0x02;
+    no_y[32] = 5;
+    assert!(ProjectivePoint::try_from_serialized(&no_y).is_none());
+
+    // x >= p is not a valid coordinate.
+    let mut oversized = [0xFFu8; 33];
+    oversized[0] = 0x02;
+    assert!(ProjectivePoint::try_from_serialized(&oversized).is_none());
+}
+
+#[test]
+fn precomputed_generator_multiplication_matches_generic_multiplication() {
+    for_random_iterations(|| {
+        let multiplier = random_scalar();
+        assert!(ProjectivePoint::multiply_generator(&multiplier)
+            .equals(&ProjectivePoint::GENERATOR.multiply(&multiplier)));
+    });
+}
+
+#[test]
+fn variable_time_multiplication_matches_constant_time_multiplication() {
+    let edge_cases = [
+        Scalar::ZERO,
+        Scalar::ONE,
+        Scalar::ONE.negate(),
+        Scalar::from_be_bytes(&hex!(
+            "00000000000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"
+        ))
+        .unwrap(),
+        // λ
+        Scalar::from_be_bytes(&hex!(
+            "5363AD4CC05C30E0A5261C028812645A122E22EA20816678DF02967C1B23BD72"
+        ))
+        .unwrap(),
+    ];
+
+    for multiplier in edge_cases.iter() {
+        assert!(ProjectivePoint::GENERATOR
+            .multiply_variable_time(multiplier)
+            .equals(&ProjectivePoint::GENERATOR.multiply(multiplier)));
+    }
+
+    assert!(ProjectivePoint::INFINITY
+        .multiply_variable_time(&Scalar::ONE)
+        .is_infinity());
+
+    for_random_iterations(|| {
+        let point = ProjectivePoint::multiply_generator(&random_scalar());
+        let multiplier = random_scalar();
+        assert!(point
+            .multiply_variable_time(&multiplier)
+            .equals(&point.multiply(&multiplier)));
+    });
+}
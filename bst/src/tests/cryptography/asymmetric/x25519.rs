@@ -0,0 +1,174 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::cryptography::asymmetric::edwards::x25519::{
+    derive_public_key, encode_age_key, try_agree, try_decode_age_key, x25519,
+    AGE_IDENTITY_HUMAN_READABLE_PART, AGE_RECIPIENT_HUMAN_READABLE_PART, BASE_POINT,
+};
+use crate::encoding::bech32::{encode, regroup_bits, Bech32Variant};
+use alloc::vec::Vec;
+use hex_literal::hex;
+
+#[test]
+fn rfc_7748_test_vector_1() {
+    assert_eq!(
+        x25519(
+            &hex!("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"),
+            &hex!("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c")
+        ),
+        hex!("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552")
+    );
+}
+
+#[test]
+fn rfc_7748_test_vector_2() {
+    // The most significant bit of the u coordinate is set; it must be ignored.
+    assert_eq!(
+        x25519(
+            &hex!("4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d"),
+            &hex!("e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493")
+        ),
+        hex!("95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957")
+    );
+}
+
+#[test]
+fn rfc_7748_iterated() {
+    let (mut k, mut u) = (BASE_POINT, BASE_POINT);
+    for i in 1..=1000 {
+        let result = x25519(&k, &u);
+        u = k;
+        k = result;
+
+        if i == 1 {
+            assert_eq!(
+                k,
+                hex!("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079")
+            );
+        }
+    }
+
+    assert_eq!(
+        k,
+        hex!("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51")
+    );
+}
+
+#[test]
+fn rfc_7748_diffie_hellman() {
+    let alice_private_key =
+        hex!("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    let bob_private_key = hex!("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+    let alice_public_key = derive_public_key(&alice_private_key);
+    let bob_public_key = derive_public_key(&bob_private_key);
+    assert_eq!(
+        alice_public_key,
+        hex!("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+    );
+    assert_eq!(
+        bob_public_key,
+        hex!("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+    );
+
+    let shared_secret = hex!("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert_eq!(
+        try_agree(&alice_private_key, &bob_public_key),
+        Some(shared_secret)
+    );
+    assert_eq!(
+        try_agree(&bob_private_key, &alice_public_key),
+        Some(shared_secret)
+    );
+}
+
+#[test]
+fn low_order_points_are_rejected() {
+    let private_key = hex!("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    for low_order_point in [
+        [0u8; 32],
+        hex!("0100000000000000000000000000000000000000000000000000000000000000"),
+        hex!("e0eb7a7c3b41b8ae1656e3faf19fc46ada098deb9c32b1fd866205165f49b800"),
+        hex!("ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f"),
+    ] {
+        assert_eq!(try_agree(&private_key, &low_order_point), None);
+    }
+}
+
+#[test]
+fn age_keys_round_trip() {
+    let private_key = hex!("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    let public_key = derive_public_key(&private_key);
+    let identity = "age-secret-key-1wurk6znnrzjh60qkc9e9rvnxgh05ctu8a0qfj243wla628de9s4qrfh26j";
+    let recipient = "age1s5s0qzvfxzn4gayt0hwtg0hhtgxm7wsdycup4a8t5j5ca25mfe4qt4hs7q";
+
+    assert_eq!(
+        encode_age_key(AGE_IDENTITY_HUMAN_READABLE_PART, &private_key),
+        utf16(identity)
+    );
+    assert_eq!(
+        encode_age_key(AGE_RECIPIENT_HUMAN_READABLE_PART, &public_key),
+        utf16(recipient)
+    );
+
+    // Identities are usually written in upper case; either case decodes.
+    assert_eq!(
+        try_decode_age_key(AGE_IDENTITY_HUMAN_READABLE_PART, &utf16(identity)),
+        Some(private_key)
+    );
+    assert_eq!(
+        try_decode_age_key(
+            AGE_IDENTITY_HUMAN_READABLE_PART,
+            &utf16(&identity.to_ascii_uppercase())
+        ),
+        Some(private_key)
+    );
+    assert_eq!(
+        try_decode_age_key(AGE_RECIPIENT_HUMAN_READABLE_PART, &utf16(recipient)),
+        Some(public_key)
+    );
+}
+
+#[test]
+fn invalid_age_keys_are_rejected() {
+    let recipient = "age1s5s0qzvfxzn4gayt0hwtg0hhtgxm7wsdycup4a8t5j5ca25mfe4qt4hs7q";
+
+    // The wrong human readable part, a bad checksum, and a truncated key.
+    assert_eq!(
+        try_decode_age_key(AGE_IDENTITY_HUMAN_READABLE_PART, &utf16(recipient)),
+        None
+    );
+    assert_eq!(
+        try_decode_age_key(
+            AGE_RECIPIENT_HUMAN_READABLE_PART,
+            &utf16("age1s5s0qzvfxzn4gayt0hwtg0hhtgxm7wsdycup4a8t5j5ca25mfe4qt4hs7r")
+        ),
+        None
+    );
+    let short_recipient = encode_short_key(AGE_RECIPIENT_HUMAN_READABLE_PART);
+    assert_eq!(
+        try_decode_age_key(AGE_RECIPIENT_HUMAN_READABLE_PART, &short_recipient),
+        None
+    );
+}
+
+fn encode_short_key(human_readable_part: &[u8]) -> Vec<u16> {
+    let data = regroup_bits(&[0x55; 31], 8, 5, true).unwrap();
+    encode(human_readable_part, &data, Bech32Variant::Bech32)
+}
+
+fn utf16(string: &str) -> Vec<u16> {
+    string.encode_utf16().collect()
+}
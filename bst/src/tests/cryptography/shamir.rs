@@ -0,0 +1,169 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    bitcoin::mnemonics::bip_39,
+    cryptography::shamir::{
+        get_share_words, interpolate, try_combine_shares, try_parse_share, try_read_share_words,
+        try_split_secret, SHARE_OVERHEAD,
+    },
+};
+use alloc::vec::Vec;
+use rand::{random, thread_rng, Rng};
+
+fn random_bytes(length: usize) -> Vec<u8> {
+    let mut rng = thread_rng();
+    (0..length).map(|_| rng.gen()).collect()
+}
+
+fn combine(shares: &[&Vec<u8>]) -> Result<Vec<u8>, ()> {
+    let slices: Vec<&[u8]> = shares.iter().map(|s| &s[..]).collect();
+    try_combine_shares(&slices).map_err(|_| ())
+}
+
+#[test]
+fn interpolation_of_a_line() {
+    // f(x) = 0x53 + 0xCA * x, so f(1) = 0x99 and f(2) = 0xDC.
+    assert_eq!(
+        interpolate(&[(1, &[0x99][..]), (2, &[0xDC][..])], 0).unwrap(),
+        [0x53]
+    );
+}
+
+#[test]
+fn interpolation_inverts_field_elements() {
+    // 0x53 and 0xCA are multiplicative inverses in the AES field; f(x) = 0x53 * x passes through (0xCA, 1).
+    assert_eq!(
+        interpolate(&[(0, &[0][..]), (0xCA, &[1][..])], 1).unwrap(),
+        [0x53]
+    );
+}
+
+#[test]
+fn interpolation_rejects_duplicate_or_mismatched_points() {
+    assert!(interpolate(&[], 0).is_none());
+    assert!(interpolate(&[(1, &[1][..]), (1, &[2][..])], 0).is_none());
+    assert!(interpolate(&[(1, &[1][..]), (2, &[2, 3][..])], 0).is_none());
+}
+
+#[test]
+fn shares_have_expected_layout() {
+    let secret = random_bytes(32);
+    let shares = try_split_secret(&secret, 3, 5, &random_bytes(32)).unwrap();
+    assert_eq!(shares.len(), 5);
+    for (i, share) in shares.iter().enumerate() {
+        assert_eq!(share.len(), secret.len() + SHARE_OVERHEAD);
+        let parsed = try_parse_share(share).unwrap();
+        assert_eq!(parsed.threshold(), 3);
+        assert_eq!(parsed.index() as usize, i + 1);
+    }
+}
+
+#[test]
+fn every_threshold_subset_recovers_the_secret() {
+    for (threshold, share_count) in [(1u8, 1u8), (1, 3), (2, 2), (2, 3), (3, 5), (4, 6)] {
+        let secret = random_bytes(1 + random::<usize>() % 64);
+        let shares = try_split_secret(&secret, threshold, share_count, &random_bytes(32)).unwrap();
+
+        // Walk every subset of the shares, represented as a bit mask.
+        for mask in 1u32..(1 << share_count) {
+            let subset: Vec<&Vec<u8>> = (0..share_count as usize)
+                .filter(|i| mask & (1 << i) != 0)
+                .map(|i| &shares[i])
+                .collect();
+
+            if subset.len() >= threshold as usize {
+                assert_eq!(combine(&subset).unwrap(), secret);
+            } else {
+                assert!(combine(&subset).is_err());
+            }
+        }
+    }
+}
+
+#[test]
+fn maximum_share_count() {
+    let secret = random_bytes(16);
+    let shares = try_split_secret(&secret, 255, 255, &random_bytes(64)).unwrap();
+    let subset: Vec<&Vec<u8>> = shares.iter().rev().collect();
+    assert_eq!(combine(&subset).unwrap(), secret);
+}
+
+#[test]
+fn splitting_is_deterministic_for_a_coefficient_seed() {
+    let secret = random_bytes(32);
+    let seed = random_bytes(32);
+    assert_eq!(
+        try_split_secret(&secret, 2, 3, &seed).unwrap(),
+        try_split_secret(&secret, 2, 3, &seed).unwrap()
+    );
+    assert_ne!(
+        try_split_secret(&secret, 2, 3, &seed).unwrap(),
+        try_split_secret(&secret, 2, 3, &random_bytes(32)).unwrap()
+    );
+}
+
+#[test]
+fn invalid_split_parameters_are_rejected() {
+    let seed = random_bytes(32);
+    assert!(try_split_secret(&[], 2, 3, &seed).is_err());
+    assert!(try_split_secret(&[1], 0, 3, &seed).is_err());
+    assert!(try_split_secret(&[1], 4, 3, &seed).is_err());
+    assert!(try_split_secret(&[1], 2, 3, &seed[..31]).is_err());
+}
+
+#[test]
+fn corrupted_shares_fail_their_checksum() {
+    let secret = random_bytes(32);
+    let shares = try_split_secret(&secret, 2, 3, &random_bytes(32)).unwrap();
+    for i in 0..shares[0].len() {
+        let mut corrupted = shares[0].clone();
+        corrupted[i] ^= 1 << (i % 8);
+        assert!(try_parse_share(&corrupted).is_err());
+        assert!(combine(&[&corrupted, &shares[1]]).is_err());
+    }
+}
+
+#[test]
+fn shares_from_different_splits_are_not_combined() {
+    let secret = random_bytes(32);
+    let first = try_split_secret(&secret, 2, 3, &random_bytes(32)).unwrap();
+    let second = try_split_secret(&secret, 2, 3, &random_bytes(32)).unwrap();
+
+    // The shares are individually valid, but the recovered secret won't match its digest.
+    assert!(combine(&[&first[0], &second[1]]).is_err());
+    assert!(combine(&[&first[0], &first[0]]).is_err());
+    assert_eq!(combine(&[&second[0], &second[1]]).unwrap(), secret);
+}
+
+#[test]
+fn share_words_round_trip() {
+    for secret_length in 1..=64 {
+        let secret = random_bytes(secret_length);
+        let shares = try_split_secret(&secret, 2, 2, &random_bytes(32)).unwrap();
+        for share in &shares {
+            let words = get_share_words(&bip_39::WORD_LIST, share);
+            assert_eq!(
+                words.len(),
+                ((secret_length + SHARE_OVERHEAD) * 8 + 10) / 11
+            );
+            assert_eq!(
+                try_read_share_words(&bip_39::WORD_LIST, &words).unwrap(),
+                *share
+            );
+        }
+    }
+}
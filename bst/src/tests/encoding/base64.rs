@@ -0,0 +1,68 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::encoding::base64;
+use alloc::{string::String, vec::Vec};
+
+fn encode(bytes: &[u8]) -> String {
+    String::from_utf16(&base64::encode(bytes)).unwrap()
+}
+
+fn decode(string: &str) -> Option<Vec<u8>> {
+    base64::decode(&string.encode_utf16().collect::<Vec<u16>>())
+}
+
+#[test]
+fn rfc_4648_test_vectors() {
+    assert_eq!(encode(b""), "");
+    assert_eq!(encode(b"f"), "Zg==");
+    assert_eq!(encode(b"fo"), "Zm8=");
+    assert_eq!(encode(b"foo"), "Zm9v");
+    assert_eq!(encode(b"foob"), "Zm9vYg==");
+    assert_eq!(encode(b"fooba"), "Zm9vYmE=");
+    assert_eq!(encode(b"foobar"), "Zm9vYmFy");
+}
+
+#[test]
+fn wireguard_public_key() {
+    let key: Vec<u8> = (0..32u8).collect();
+    assert_eq!(encode(&key), "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=");
+}
+
+#[test]
+fn rfc_4648_test_vectors_decode() {
+    for (string, bytes) in [
+        ("", &b""[..]),
+        ("Zg==", b"f"),
+        ("Zm8=", b"fo"),
+        ("Zm9v", b"foo"),
+        ("Zm9vYg==", b"foob"),
+        ("Zm9vYmE=", b"fooba"),
+        ("Zm9vYmFy", b"foobar"),
+        ("Zm9v\r\nYmFy", b"foobar"),
+    ] {
+        assert_eq!(decode(string).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn invalid_strings_are_not_decoded() {
+    for string in [
+        "Zg", "Zg=", "Zg===", "Z===", "Zg==Zm8=", "Zm9v!", "Zh==", "Zm9=", "Zm8==",
+    ] {
+        assert!(decode(string).is_none(), "{}", string);
+    }
+}
@@ -0,0 +1,208 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::encoding::bech32::{decode, encode, regroup_bits, try_locate_errors, Bech32Variant};
+use alloc::{string::String, vec::Vec};
+use hex_literal::hex;
+
+fn encode_string(human_readable_part: &str, data: &[u8], variant: Bech32Variant) -> String {
+    String::from_utf16(&encode(human_readable_part.as_bytes(), data, variant)).unwrap()
+}
+
+#[test]
+fn bip_173_checksums() {
+    assert_eq!(encode_string("a", &[], Bech32Variant::Bech32), "a12uel5l");
+
+    let data: Vec<u8> = (0..32).collect();
+    assert_eq!(
+        encode_string("abcdef", &data, Bech32Variant::Bech32),
+        "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw"
+    );
+}
+
+#[test]
+fn bip_350_checksums() {
+    assert_eq!(encode_string("a", &[], Bech32Variant::Bech32m), "a1lqfn3a");
+
+    let data: Vec<u8> = (0..32).rev().collect();
+    assert_eq!(
+        encode_string("abcdef", &data, Bech32Variant::Bech32m),
+        "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx"
+    );
+}
+
+#[test]
+fn age_recipient() {
+    let data = regroup_bits(
+        &hex!("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"),
+        8,
+        5,
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(
+        encode_string("age", &data, Bech32Variant::Bech32),
+        "age1s5s0qzvfxzn4gayt0hwtg0hhtgxm7wsdycup4a8t5j5ca25mfe4qt4hs7q"
+    );
+}
+
+#[test]
+fn regrouping_round_trips() {
+    let bytes = hex!("751e76e8199196d454941c45d1b3a323f1433bd6");
+    let five_bit = regroup_bits(&bytes, 8, 5, true).unwrap();
+    assert!(five_bit.iter().all(|v| *v < 32));
+    assert_eq!(regroup_bits(&five_bit, 5, 8, false).unwrap(), bytes);
+
+    // Values too large for the input group size, and non-zero padding, are rejected.
+    assert_eq!(regroup_bits(&[32], 5, 8, false), None);
+    assert_eq!(regroup_bits(&[0, 1], 5, 8, false), None);
+}
+
+#[test]
+fn valid_strings_decode() {
+    for (string, variant) in [
+        ("A12UEL5L", Bech32Variant::Bech32),
+        (
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+            Bech32Variant::Bech32,
+        ),
+        ("?1ezyfcl", Bech32Variant::Bech32),
+        ("a1lqfn3a", Bech32Variant::Bech32m),
+        (
+            "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+            Bech32Variant::Bech32m,
+        ),
+    ] {
+        let (human_readable_part, data, decoded_variant) =
+            decode(&string.encode_utf16().collect::<Vec<u16>>()).unwrap();
+        assert_eq!(decoded_variant, variant);
+        assert_eq!(
+            encode_string(
+                core::str::from_utf8(&human_readable_part).unwrap(),
+                &data,
+                variant
+            ),
+            string.to_lowercase()
+        );
+    }
+}
+
+#[test]
+fn invalid_strings_dont_decode() {
+    for string in [
+        // Mixed case, no separator, empty human readable part, short checksum, invalid character, bad checksum.
+        "A12uEL5L",
+        "pzry9x0s0muk",
+        "1pzry9x0s0muk",
+        "li1dgmt3",
+        "x1b4n0q5v",
+        "a12uel5m",
+    ] {
+        assert!(decode(&string.encode_utf16().collect::<Vec<u16>>()).is_none());
+    }
+}
+
+fn utf16(string: &str) -> Vec<u16> {
+    string.encode_utf16().collect()
+}
+
+fn substitute(string: &str, substitutions: &[(usize, char)]) -> Vec<u16> {
+    let mut characters = utf16(string);
+    for (i, c) in substitutions {
+        characters[*i] = *c as u16;
+    }
+
+    characters
+}
+
+#[test]
+fn valid_strings_have_no_errors() {
+    assert_eq!(
+        try_locate_errors(&utf16("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4")),
+        Some((Bech32Variant::Bech32, Vec::new()))
+    );
+    assert_eq!(
+        try_locate_errors(&utf16(
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        )),
+        Some((Bech32Variant::Bech32m, Vec::new()))
+    );
+}
+
+#[test]
+fn substitution_errors_are_located() {
+    const SEGWIT: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+    const TAPROOT: &str = "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr";
+
+    // A single substitution, including in the checksum.
+    assert_eq!(
+        try_locate_errors(&substitute(SEGWIT, &[(7, 'z')])),
+        Some((Bech32Variant::Bech32, Vec::from([(7, '8' as u16)])))
+    );
+    assert_eq!(
+        try_locate_errors(&substitute(TAPROOT, &[(61, 'q')])),
+        Some((Bech32Variant::Bech32m, Vec::from([(61, 'r' as u16)])))
+    );
+
+    // Two substitutions.
+    assert_eq!(
+        try_locate_errors(&substitute(TAPROOT, &[(4, 'x'), (30, '8')])),
+        Some((
+            Bech32Variant::Bech32m,
+            Vec::from([(4, '5' as u16), (30, '0' as u16)])
+        ))
+    );
+
+    // Characters outside the character set are always errors.
+    assert_eq!(
+        try_locate_errors(&substitute(SEGWIT, &[(10, 'b')])),
+        Some((Bech32Variant::Bech32, Vec::from([(10, 'q' as u16)])))
+    );
+    assert_eq!(
+        try_locate_errors(&substitute(SEGWIT, &[(10, 'b'), (20, 'o')])),
+        Some((
+            Bech32Variant::Bech32,
+            Vec::from([(10, 'q' as u16), (20, 'r' as u16)])
+        ))
+    );
+    assert_eq!(
+        try_locate_errors(&substitute(SEGWIT, &[(10, 'i'), (30, 'd')])),
+        Some((
+            Bech32Variant::Bech32,
+            Vec::from([(10, 'q' as u16), (30, 'c' as u16)])
+        ))
+    );
+
+    // Corrections match the string's case.
+    assert_eq!(
+        try_locate_errors(&utf16("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T5")),
+        Some((Bech32Variant::Bech32, Vec::from([(41, '4' as u16)])))
+    );
+}
+
+#[test]
+fn uncorrectable_strings_arent_located() {
+    assert_eq!(
+        try_locate_errors(&substitute(
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            &[(5, 'b'), (6, 'b'), (7, 'b')]
+        )),
+        None
+    );
+    assert_eq!(try_locate_errors(&utf16("bc1qw508d6qE")), None);
+    assert_eq!(try_locate_errors(&utf16("qw508d6qejxtdg4y5r3")), None);
+}
@@ -0,0 +1,57 @@
+// Poodle Labs' Bootable Security Tools (BST)
+// Copyright (C) 2023 Isaac Beizsley (isaac@poodlelabs.com)
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::hashing::derive_bytes;
+use hex_literal::hex;
+
+// Expected outputs were calculated independently, as HMAC-SHA512(seed, context || counter) blocks.
+const SEED: [u8; 32] = hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+
+#[test]
+fn output_is_hmac_sha512_in_counter_mode() {
+    assert_eq!(
+        derive_bytes(&SEED, b"context", 100),
+        hex!("a22638b25fc3fb8d34a0737db48ab27253c023062c3a0cd32df33e3bbfeeed5a1a20da80c79c1924b8ea1c31f1843398c3766819a1c6393c905165dbc2f274ed902bf051f4505eee0b648c62f061199671571397ffecd11c482715c2c4e4bbca9a707524")
+    );
+}
+
+#[test]
+fn empty_seed_and_context() {
+    assert_eq!(
+        derive_bytes(&[], &[], 16),
+        hex!("4f09310cef6dfa0268b846b2e15885f1")
+    );
+}
+
+#[test]
+fn shorter_outputs_are_prefixes_of_longer_outputs() {
+    let long = derive_bytes(&SEED, b"context", 200);
+    for length in [0, 1, 63, 64, 65, 128, 199] {
+        assert_eq!(derive_bytes(&SEED, b"context", length), &long[..length]);
+    }
+}
+
+#[test]
+fn context_changes_the_output() {
+    assert_ne!(
+        derive_bytes(&SEED, b"context", 32),
+        derive_bytes(&SEED, b"context2", 32)
+    );
+    assert_ne!(
+        derive_bytes(&SEED, b"context", 32),
+        derive_bytes(&SEED[1..], b"context", 32)
+    );
+}
@@ -15,6 +15,7 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 mod hmac;
+mod key_derivation;
 mod pbkdf2;
 mod ripemd_160;
 mod sha_256;
@@ -22,6 +22,7 @@ extern crate secp256k1;
 mod big_integers;
 mod bitcoin;
 mod cryptography;
+mod encoding;
 mod hashing;
 mod numeric_collector;
 
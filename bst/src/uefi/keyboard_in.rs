@@ -62,4 +62,36 @@ impl KeyboardIn for UefiKeyboardIn {
             }
         }
     }
+
+    fn try_read_key(&self) -> Option<KeyPress> {
+        match self
+            .system_table
+            .boot_services()
+            .open_protocol::<UefiSimpleTextInputExtended>(
+                self.system_table.console_in().handle(),
+                self.image_handle,
+                UefiHandle::NULL,
+                UefiProtocolAttributes::BY_HANDLE_PROTOCOL,
+            ) {
+            Ok(p) => {
+                let protocol = unsafe { p.as_mut().unwrap() };
+                match protocol.try_read_key_stroke() {
+                    Some(k) => Some(k.into()),
+                    None => None,
+                }
+            }
+            Err(_) => {
+                let protocol_handle = self.system_table.console_in();
+                let protocol = protocol_handle.protocol();
+                match protocol.try_read_key_stroke() {
+                    Some(k) => Some(KeyPress::from(
+                        k.into(),
+                        ToggleKeys::NONE,
+                        ModifierKeys::NONE,
+                    )),
+                    None => None,
+                }
+            }
+        }
+    }
 }
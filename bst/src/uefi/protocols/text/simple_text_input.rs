@@ -395,6 +395,15 @@ impl UefiSimpleTextInput {
             Err(c) => Err(c),
         }
     }
+
+    pub fn try_read_key_stroke(&self) -> Option<UefiInputKey> {
+        // Doesn't wait for a key stroke; there's no key if the read fails (typically with NOT_READY).
+        let mut key = UefiInputKey::NULL;
+        match (self.read_key_stroke)(self, &mut key).into() {
+            Ok(_) => Some(key),
+            Err(_) => None,
+        }
+    }
 }
 
 impl UefiProtocol for UefiSimpleTextInput {
@@ -440,6 +449,15 @@ impl UefiSimpleTextInputExtended {
             Err(c) => Err(c),
         }
     }
+
+    pub fn try_read_key_stroke(&self) -> Option<UefiKeyData> {
+        // Doesn't wait for a key stroke; there's no key if the read fails (typically with NOT_READY).
+        let mut key = UefiKeyData::NULL;
+        match (self.read_key_stroke)(self, &mut key).into() {
+            Ok(_) => Some(key),
+            Err(_) => None,
+        }
+    }
 }
 
 impl UefiProtocol for UefiSimpleTextInputExtended {
@@ -28,7 +28,7 @@ use crate::{
     ui::{ConfirmationPrompt, DataInput, DataInputType},
     String16,
 };
-use alloc::{vec, vec::Vec};
+use alloc::{format, vec, vec::Vec};
 use macros::s16;
 
 pub fn prompt_for_bytes_from_any_data_type<TSystemServices: SystemServices>(
@@ -70,6 +70,48 @@ pub fn prompt_for_bytes_from_any_data_type<TSystemServices: SystemServices>(
     }
 }
 
+pub fn prompt_for_fixed_length_bytes<const LENGTH: usize, TSystemServices: SystemServices>(
+    system_services: &TSystemServices,
+    cancel_prompt_string: String16,
+    label: String16<'static>,
+) -> Option<[u8; LENGTH]> {
+    let console = system_services.get_console_out();
+    loop {
+        // Prompt the user for some bytes.
+        let mut bytes = match prompt_for_data_input(
+            None,
+            &[DataInputType::Bytes],
+            system_services,
+            cancel_prompt_string,
+            label,
+        ) {
+            DataInput::Bytes(b) => b,
+            _ => return None,
+        };
+
+        if bytes.len() == LENGTH {
+            let mut buffer = [0u8; LENGTH];
+            buffer.copy_from_slice(&bytes);
+
+            // Pre-emptively zero the input now we've copied it into the fixed length buffer.
+            bytes.fill(0);
+            return Some(buffer);
+        }
+
+        // The input is the wrong length; write an error.
+        console
+            .line_start()
+            .new_line()
+            .in_colours(constants::ERROR_COLOURS, |c| {
+                c.output_utf16(label)
+                    .output_utf32_line(&format!(" must be exactly {} bytes long.\0", LENGTH))
+            });
+
+        // Pre-emptively zero the input before we try again.
+        bytes.fill(0);
+    }
+}
+
 pub fn prompt_for_data_input<TSystemServices: SystemServices>(
     base: Option<NumericBaseWithCharacterPredicate>,
     allowed_input_types: &[DataInputType],
@@ -241,6 +283,28 @@ pub fn prompt_for_u32<
     )
 }
 
+pub fn prompt_for_u64<
+    'a,
+    TSystemServices: SystemServices,
+    FValidate: Fn(u64) -> Option<String16<'a>>,
+>(
+    validate: FValidate,
+    label: String16<'static>,
+    system_services: &TSystemServices,
+    cancel_prompt_string: String16<'static>,
+    base: Option<NumericBaseWithCharacterPredicate>,
+) -> Option<u64> {
+    prompt_for_unsigned_integer(
+        validate,
+        u64::from_be_bytes,
+        label,
+        s16!("18,446,744,073,709,551,616 (2^64)"),
+        cancel_prompt_string,
+        system_services,
+        base,
+    )
+}
+
 fn prompt_for_unsigned_integer<
     'a,
     const SIZE: usize,
@@ -32,8 +32,8 @@ pub use clipboard::{prompt_for_clipboard_select, prompt_for_clipboard_write};
 pub use confirmation_prompt::ConsoleUiConfirmationPrompt;
 pub use continue_prompt::ConsoleUiContinuePrompt;
 pub use data_input::{
-    prompt_for_bytes_from_any_data_type, prompt_for_data_input, prompt_for_u16, prompt_for_u32,
-    prompt_for_u8, text_input_paste_handler,
+    prompt_for_bytes_from_any_data_type, prompt_for_data_input, prompt_for_fixed_length_bytes,
+    prompt_for_u16, prompt_for_u32, prompt_for_u64, prompt_for_u8, text_input_paste_handler,
 };
 pub use key_value::ConsoleUiKeyValue;
 pub use label::ConsoleUiLabel;